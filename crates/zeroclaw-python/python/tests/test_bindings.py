@@ -0,0 +1,24 @@
+"""Smoke tests for the native bindings; skipped unless built with maturin."""
+
+import pytest
+
+zeroclaw = pytest.importorskip("zeroclaw")
+
+
+def test_module_exposes_agent_and_version():
+    assert hasattr(zeroclaw, "Agent")
+    assert isinstance(zeroclaw.__version__, str)
+
+
+def test_register_tool_rejects_non_callable(tmp_path):
+    agent = zeroclaw.Agent(config_dir=str(tmp_path))
+    with pytest.raises(ValueError):
+        agent.register_tool("bad", "not callable", 42)
+
+
+def test_registered_tool_is_listed(tmp_path):
+    agent = zeroclaw.Agent(config_dir=str(tmp_path))
+    agent.register_tool("echo_py", "Echo input", lambda text="": text, read_only=True)
+    assert "echo_py" in agent.tools()
+    with pytest.raises(ValueError):
+        agent.register_tool("echo_py", "duplicate", lambda: "")
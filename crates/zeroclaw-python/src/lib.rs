@@ -0,0 +1,199 @@
+//! Python bindings for the ZeroClaw agent library.
+//!
+//! Exposes a single `zeroclaw.Agent` class that loads the normal ZeroClaw
+//! configuration, sends messages, and accepts Python callables as tools.
+//! Python tools are wrapped in [`PolicyGatedTool`], so they pass through the
+//! same autonomy and rate-limit checks as built-in tools.
+
+use anyhow::Context;
+use async_trait::async_trait;
+use pyo3::exceptions::{PyRuntimeError, PyValueError};
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use std::sync::Arc;
+use zeroclaw_lib::security::policy::ToolOperation;
+use zeroclaw_lib::tools::PolicyGatedTool;
+use zeroclaw_lib::{Config, SecurityPolicy, Tool, ToolResult};
+
+fn runtime_error(error: anyhow::Error) -> PyErr {
+    PyRuntimeError::new_err(format!("{error:#}"))
+}
+
+/// A tool whose `execute` calls back into a Python callable.
+///
+/// The callable receives the tool arguments as keyword arguments and may
+/// return a string (used verbatim) or any JSON-serializable value.
+struct PythonTool {
+    name: String,
+    description: String,
+    parameters: serde_json::Value,
+    callback: Arc<Py<PyAny>>,
+}
+
+impl PythonTool {
+    fn invoke(callback: &Py<PyAny>, args: &serde_json::Value) -> PyResult<String> {
+        Python::with_gil(|py| {
+            let json = py.import_bound("json")?;
+            let kwargs = json
+                .call_method1("loads", (args.to_string(),))?
+                .downcast_into::<PyDict>()
+                .map_err(|_| PyValueError::new_err("tool arguments must be a JSON object"))?;
+            let result = callback.call_bound(py, (), Some(&kwargs))?;
+            let result = result.bind(py);
+            if let Ok(text) = result.extract::<String>() {
+                return Ok(text);
+            }
+            json.call_method1("dumps", (result,))?.extract()
+        })
+    }
+}
+
+#[async_trait]
+impl Tool for PythonTool {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn description(&self) -> &str {
+        &self.description
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        self.parameters.clone()
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        // Python code may block or take the GIL for a while; keep it off the
+        // async workers.
+        let callback = Arc::clone(&self.callback);
+        let outcome = tokio::task::spawn_blocking(move || Self::invoke(&callback, &args))
+            .await
+            .context("Python tool task panicked")?;
+        Ok(match outcome {
+            Ok(output) => ToolResult {
+                success: true,
+                output,
+                error: None,
+            },
+            Err(error) => ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(error.to_string()),
+            },
+        })
+    }
+}
+
+/// `zeroclaw.Agent(config_dir=None)` — an agent built from ZeroClaw config.
+#[pyclass(name = "Agent", module = "zeroclaw")]
+struct PyAgent {
+    runtime: tokio::runtime::Runtime,
+    agent: zeroclaw_lib::Agent,
+    security: Arc<SecurityPolicy>,
+}
+
+#[pymethods]
+impl PyAgent {
+    #[new]
+    #[pyo3(signature = (config_dir=None))]
+    fn new(config_dir: Option<String>) -> PyResult<Self> {
+        if let Some(dir) = config_dir {
+            std::env::set_var("ZEROCLAW_CONFIG_DIR", dir);
+        }
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .enable_all()
+            .build()
+            .map_err(|e| runtime_error(e.into()))?;
+        let config = runtime
+            .block_on(Config::load_or_init())
+            .map_err(runtime_error)?;
+        let agent = zeroclaw_lib::Agent::from_config(&config).map_err(runtime_error)?;
+        // Python tools share the built-in tools' budget and trackers.
+        let security = Arc::clone(agent.security());
+        Ok(Self {
+            runtime,
+            agent,
+            security,
+        })
+    }
+
+    /// Send one user message and return the agent's final reply.
+    fn send(&mut self, py: Python<'_>, message: &str) -> PyResult<String> {
+        let Self { runtime, agent, .. } = self;
+        // Release the GIL so Python tools can run on worker threads.
+        py.allow_threads(|| runtime.block_on(agent.turn(message)))
+            .map_err(runtime_error)
+    }
+
+    /// Register `callback` as a tool named `name`.
+    ///
+    /// `parameters` is a JSON Schema dict for the keyword arguments. Tools are
+    /// treated as side-effecting unless `read_only=True`, which exempts them
+    /// from the autonomy and action-budget checks like built-in read tools.
+    /// Calls are audited, size-limited, scanned and approval-gated like those
+    /// of the built-in tools.
+    #[pyo3(signature = (name, description, callback, parameters=None, read_only=false))]
+    fn register_tool(
+        &mut self,
+        py: Python<'_>,
+        name: String,
+        description: String,
+        callback: Py<PyAny>,
+        parameters: Option<Bound<'_, PyDict>>,
+        read_only: bool,
+    ) -> PyResult<()> {
+        if !callback.bind(py).is_callable() {
+            return Err(PyValueError::new_err("callback must be callable"));
+        }
+        let parameters = match parameters {
+            Some(schema) => {
+                let text: String = py
+                    .import_bound("json")?
+                    .call_method1("dumps", (schema,))?
+                    .extract()?;
+                serde_json::from_str(&text)
+                    .map_err(|e| PyValueError::new_err(format!("invalid parameters: {e}")))?
+            }
+            None => serde_json::json!({ "type": "object", "properties": {} }),
+        };
+        let operation = if read_only {
+            ToolOperation::Read
+        } else {
+            ToolOperation::Act
+        };
+        let tool = PythonTool {
+            name,
+            description,
+            parameters,
+            callback: Arc::new(callback),
+        };
+        self.agent
+            .register_tool(Box::new(PolicyGatedTool::new(
+                Box::new(tool),
+                Arc::clone(&self.security),
+                operation,
+            )))
+            .map_err(|e| PyValueError::new_err(e.to_string()))
+    }
+
+    /// Names of all tools available to the agent.
+    fn tools(&self) -> Vec<String> {
+        self.agent
+            .tool_specs()
+            .iter()
+            .map(|spec| spec.name.clone())
+            .collect()
+    }
+
+    /// Forget the conversation so far.
+    fn clear_history(&mut self) {
+        self.agent.clear_history();
+    }
+}
+
+#[pymodule]
+fn zeroclaw(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<PyAgent>()?;
+    m.add("__version__", env!("CARGO_PKG_VERSION"))?;
+    Ok(())
+}
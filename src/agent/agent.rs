@@ -3,7 +3,7 @@ use crate::agent::dispatcher::{
 };
 use crate::agent::loop_::detection::{DetectionVerdict, LoopDetectionConfig, LoopDetector};
 use crate::agent::loop_::history::{extract_facts_from_turns, TurnBuffer};
-use crate::agent::memory_loader::{DefaultMemoryLoader, MemoryLoader};
+use crate::agent::memory_loader::{DefaultMemoryLoader, MemoryLoader, PinnedMemoryLoader};
 use crate::agent::prompt::{PromptContext, SystemPromptBuilder};
 use crate::agent::research;
 use crate::config::{Config, ResearchPhaseConfig};
@@ -45,6 +45,8 @@ pub struct Agent {
     available_hints: Vec<String>,
     route_model_by_hint: HashMap<String, String>,
     research_config: ResearchPhaseConfig,
+    security: Arc<SecurityPolicy>,
+    tool_wrappers: tools::ToolWrappers,
 }
 
 pub struct AgentBuilder {
@@ -68,6 +70,8 @@ pub struct AgentBuilder {
     available_hints: Option<Vec<String>>,
     route_model_by_hint: Option<HashMap<String, String>>,
     research_config: Option<ResearchPhaseConfig>,
+    security: Option<Arc<SecurityPolicy>>,
+    tool_wrappers: Option<tools::ToolWrappers>,
 }
 
 impl AgentBuilder {
@@ -93,6 +97,8 @@ impl AgentBuilder {
             available_hints: None,
             route_model_by_hint: None,
             research_config: None,
+            security: None,
+            tool_wrappers: None,
         }
     }
 
@@ -106,6 +112,12 @@ impl AgentBuilder {
         self
     }
 
+    /// Use the tools collected in `registry`.
+    pub fn tool_registry(mut self, registry: tools::ToolRegistry) -> Self {
+        self.tools = Some(registry.into_tools());
+        self
+    }
+
     pub fn memory(mut self, memory: Arc<dyn Memory>) -> Self {
         self.memory = Some(memory);
         self
@@ -203,16 +215,51 @@ impl AgentBuilder {
         self
     }
 
+    /// Security policy shared with the agent's tools, so tools added later
+    /// with [`Agent::register_tool`] draw on the same budgets and trackers.
+    pub fn security(mut self, security: Arc<SecurityPolicy>) -> Self {
+        self.security = Some(security);
+        self
+    }
+
+    /// Wrappers for tools added later with [`Agent::register_tool`]; by
+    /// default only those the security policy itself asks for.
+    pub fn tool_wrappers(mut self, wrappers: tools::ToolWrappers) -> Self {
+        self.tool_wrappers = Some(wrappers);
+        self
+    }
+
+    /// Assemble the agent.
+    ///
+    /// `provider`, `tools`, and `memory` are required. Without an explicit
+    /// observer, events are discarded; without an explicit tool dispatcher,
+    /// native tool calling is used when the provider supports it and XML
+    /// tool calls otherwise.
     pub fn build(self) -> Result<Agent> {
         let tools = self
             .tools
             .ok_or_else(|| anyhow::anyhow!("tools are required"))?;
         let tool_specs = tools.iter().map(|tool| tool.spec()).collect();
+        let provider = self
+            .provider
+            .ok_or_else(|| anyhow::anyhow!("provider is required"))?;
+        let tool_dispatcher = self.tool_dispatcher.unwrap_or_else(|| {
+            if provider.supports_native_tools() {
+                Box::new(NativeToolDispatcher)
+            } else {
+                Box::new(XmlToolDispatcher)
+            }
+        });
+
+        let security = self
+            .security
+            .unwrap_or_else(|| Arc::new(SecurityPolicy::default()));
+        let tool_wrappers = self
+            .tool_wrappers
+            .unwrap_or_else(|| tools::ToolWrappers::new(Arc::clone(&security)));
 
         Ok(Agent {
-            provider: self
-                .provider
-                .ok_or_else(|| anyhow::anyhow!("provider is required"))?,
+            provider,
             tools,
             tool_specs,
             memory: self
@@ -220,13 +267,11 @@ impl AgentBuilder {
                 .ok_or_else(|| anyhow::anyhow!("memory is required"))?,
             observer: self
                 .observer
-                .ok_or_else(|| anyhow::anyhow!("observer is required"))?,
+                .unwrap_or_else(|| Arc::new(observability::NoopObserver)),
             prompt_builder: self
                 .prompt_builder
                 .unwrap_or_else(SystemPromptBuilder::with_defaults),
-            tool_dispatcher: self
-                .tool_dispatcher
-                .ok_or_else(|| anyhow::anyhow!("tool_dispatcher is required"))?,
+            tool_dispatcher,
             memory_loader: self
                 .memory_loader
                 .unwrap_or_else(|| Box::new(DefaultMemoryLoader::default())),
@@ -247,6 +292,8 @@ impl AgentBuilder {
             available_hints: self.available_hints.unwrap_or_default(),
             route_model_by_hint: self.route_model_by_hint.unwrap_or_default(),
             research_config: self.research_config.unwrap_or_default(),
+            security,
+            tool_wrappers,
         })
     }
 }
@@ -260,6 +307,29 @@ impl Agent {
         &self.tool_specs
     }
 
+    /// The security policy the agent's tools enforce. Gate tools passed to
+    /// [`Agent::register_tool`] with it rather than a fresh policy, or they
+    /// get a budget of their own.
+    pub fn security(&self) -> &Arc<SecurityPolicy> {
+        &self.security
+    }
+
+    /// Add a tool after construction, e.g. from language bindings.
+    ///
+    /// Fails if a tool with the same name is already registered. The tool is
+    /// wrapped like the built-in ones (audit, size limits, content scanning,
+    /// approval); wrap it in [`PolicyGatedTool`](crate::tools::PolicyGatedTool)
+    /// first if it does not enforce the security policy itself.
+    pub fn register_tool(&mut self, tool: Box<dyn Tool>) -> Result<()> {
+        if self.tools.iter().any(|t| t.name() == tool.name()) {
+            anyhow::bail!("tool '{}' is already registered", tool.name());
+        }
+        let tool = self.tool_wrappers.wrap(tool)?;
+        self.tool_specs.push(tool.spec());
+        self.tools.push(tool);
+        Ok(())
+    }
+
     pub fn history(&self) -> &[ConversationMessage] {
         &self.history
     }
@@ -273,14 +343,14 @@ impl Agent {
             tracing::warn!("plugin registry initialization skipped: {error}");
         }
 
-        let observer: Arc<dyn Observer> =
-            Arc::from(observability::create_observer(&config.observability));
+        let security = Arc::new(SecurityPolicy::from_root_config(&config));
+        let observer: Arc<dyn Observer> = Arc::from(observability::with_security_budget(
+            observability::create_observer(&config.observability),
+            &security,
+            &config.cost,
+        ));
         let runtime: Arc<dyn runtime::RuntimeAdapter> =
             Arc::from(runtime::create_runtime(&config.runtime)?);
-        let security = Arc::new(SecurityPolicy::from_config(
-            &config.autonomy,
-            &config.workspace_dir,
-        ));
 
         let memory: Arc<dyn Memory> = Arc::from(memory::create_memory_with_storage_and_routes(
             &config.memory,
@@ -301,7 +371,7 @@ impl Agent {
             None
         };
 
-        let tools = tools::all_tools_with_runtime(
+        let (tools, tool_wrappers) = tools::tool_registry_with_runtime(
             Arc::new(config.clone()),
             &security,
             runtime,
@@ -404,6 +474,8 @@ impl Agent {
             .skills_prompt_mode(config.skills.prompt_injection_mode)
             .auto_save(config.memory.auto_save)
             .research_config(config.research.clone())
+            .security(security)
+            .tool_wrappers(tool_wrappers)
             .build()
     }
 
@@ -529,7 +601,13 @@ impl Agent {
 
     pub async fn turn(&mut self, user_message: &str) -> Result<String> {
         if self.history.is_empty() {
-            let system_prompt = self.build_system_prompt()?;
+            let mut system_prompt = self.build_system_prompt()?;
+            system_prompt.push_str(
+                &PinnedMemoryLoader::default()
+                    .load(self.memory.as_ref())
+                    .await
+                    .unwrap_or_default(),
+            );
             self.history
                 .push(ConversationMessage::Chat(ChatMessage::system(
                     system_prompt,
@@ -693,6 +771,16 @@ impl Agent {
                 return Ok(final_text);
             }
 
+            // Checked before the calls enter the history, so a later turn
+            // never sees calls without results.
+            if crate::security::kill_switch::is_engaged(&self.workspace_dir) {
+                anyhow::bail!(
+                    "Kill switch engaged (remove {} in the workspace to resume); \
+                     tool calls stopped",
+                    crate::security::kill_switch::KILL_SWITCH_FILE
+                );
+            }
+
             if !text.is_empty() {
                 self.history
                     .push(ConversationMessage::Chat(ChatMessage::assistant(
@@ -1020,6 +1108,149 @@ mod tests {
         assert_eq!(response, "hello");
     }
 
+    #[tokio::test]
+    async fn builder_defaults_observer_and_dispatcher() {
+        let provider = Box::new(MockProvider {
+            responses: Mutex::new(vec![crate::providers::ChatResponse {
+                text: Some("hello".into()),
+                tool_calls: vec![],
+                usage: None,
+                reasoning_content: None,
+                quota_metadata: None,
+                stop_reason: None,
+                raw_stop_reason: None,
+            }]),
+        });
+        let mem: Arc<dyn Memory> = Arc::new(crate::memory::NoneMemory::new());
+
+        let mut agent = Agent::builder()
+            .provider(provider)
+            .tool_registry(tools::ToolRegistry::new().with(MockTool))
+            .memory(mem)
+            .workspace_dir(std::path::PathBuf::from("/tmp"))
+            .build()
+            .expect("observer and dispatcher should be optional");
+
+        assert_eq!(agent.tool_specs().len(), 1);
+        assert_eq!(agent.turn("hi").await.unwrap(), "hello");
+    }
+
+    #[test]
+    fn register_tool_appends_spec_and_rejects_duplicates() {
+        let provider = Box::new(MockProvider {
+            responses: Mutex::new(vec![]),
+        });
+        let mem: Arc<dyn Memory> = Arc::new(crate::memory::NoneMemory::new());
+        let mut agent = Agent::builder()
+            .provider(provider)
+            .tools(vec![])
+            .memory(mem)
+            .workspace_dir(std::path::PathBuf::from("/tmp"))
+            .build()
+            .unwrap();
+
+        agent.register_tool(Box::new(MockTool)).unwrap();
+        assert_eq!(agent.tool_specs().len(), 1);
+        assert_eq!(agent.tool_specs()[0].name, "echo");
+
+        let err = agent.register_tool(Box::new(MockTool)).unwrap_err();
+        assert!(err.to_string().contains("already registered"));
+        assert_eq!(agent.tool_specs().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn turn_stops_tool_calls_while_kill_switch_is_engaged() {
+        let workspace = TempDir::new().unwrap();
+        let kill_file = crate::security::kill_switch::kill_switch_path(workspace.path());
+        std::fs::create_dir_all(kill_file.parent().unwrap()).unwrap();
+        std::fs::write(&kill_file, "").unwrap();
+        let provider = Box::new(MockProvider {
+            responses: Mutex::new(vec![crate::providers::ChatResponse {
+                text: Some(String::new()),
+                tool_calls: vec![crate::providers::ToolCall {
+                    id: "tc1".into(),
+                    name: "echo".into(),
+                    arguments: "{}".into(),
+                }],
+                usage: None,
+                reasoning_content: None,
+                quota_metadata: None,
+                stop_reason: None,
+                raw_stop_reason: None,
+            }]),
+        });
+        let mut agent = Agent::builder()
+            .provider(provider)
+            .tools(vec![Box::new(MockTool)])
+            .memory(Arc::new(crate::memory::NoneMemory::new()))
+            .tool_dispatcher(Box::new(NativeToolDispatcher))
+            .workspace_dir(workspace.path().to_path_buf())
+            .build()
+            .unwrap();
+
+        let err = agent.turn("hi").await.unwrap_err();
+        assert!(err.to_string().contains("Kill switch engaged"));
+        assert!(!agent
+            .history()
+            .iter()
+            .any(|message| matches!(message, ConversationMessage::ToolResults(_))));
+    }
+
+    #[derive(Debug)]
+    struct DenyAll;
+
+    #[async_trait]
+    impl crate::security::ApprovalHook for DenyAll {
+        async fn request_approval(
+            &self,
+            _request: &crate::approval::ApprovalRequest,
+        ) -> crate::approval::ApprovalResponse {
+            crate::approval::ApprovalResponse::No
+        }
+    }
+
+    #[tokio::test]
+    async fn registered_tool_is_audited_and_approval_gated() {
+        let tmp = TempDir::new().unwrap();
+        let logger = Arc::new(
+            crate::security::AuditLogger::new(
+                crate::config::AuditConfig::default(),
+                tmp.path().to_path_buf(),
+            )
+            .unwrap(),
+        );
+        let security = Arc::new(SecurityPolicy {
+            approval_hook: Some(Arc::new(DenyAll)),
+            ..SecurityPolicy::default()
+        });
+        let mut agent = Agent::builder()
+            .provider(Box::new(MockProvider {
+                responses: Mutex::new(vec![]),
+            }))
+            .tools(vec![])
+            .memory(Arc::new(crate::memory::NoneMemory::new()))
+            .workspace_dir(tmp.path().to_path_buf())
+            .security(Arc::clone(&security))
+            .tool_wrappers(tools::ToolWrappers::new(Arc::clone(&security)).with_audit(Some(logger)))
+            .build()
+            .unwrap();
+        agent
+            .register_tool(Box::new(crate::tools::PolicyGatedTool::new(
+                Box::new(MockTool),
+                Arc::clone(&security),
+                crate::security::policy::ToolOperation::Act,
+            )))
+            .unwrap();
+
+        let pending = agent.tools[0].execute(serde_json::json!({})).await.unwrap();
+        assert_ne!(pending.output, "tool-out");
+        let result = crate::tools::test_support::approval_outcome(&security, &pending).await;
+        assert!(result.error.unwrap().contains("Operator denied"));
+
+        let log = std::fs::read_to_string(tmp.path().join("ariadne/audit/audit.log")).unwrap();
+        assert!(log.contains("echo"));
+    }
+
     #[tokio::test]
     async fn turn_with_native_dispatcher_handles_tool_results_variant() {
         let provider = Box::new(MockProvider {
@@ -1,3 +1,4 @@
+use crate::agent::memory_loader::PinnedMemoryLoader;
 use crate::approval::{ApprovalManager, ApprovalRequest, ApprovalResponse};
 use crate::config::schema::{CostEnforcementMode, ModelPricing};
 use crate::config::{Config, ProgressMode};
@@ -10,7 +11,7 @@ use crate::providers::{
     ToolCall,
 };
 use crate::runtime;
-use crate::security::{CanaryGuard, SecurityPolicy};
+use crate::security::{kill_switch, CanaryGuard, SecurityPolicy};
 use crate::tools::{self, Tool};
 use crate::util::truncate_with_ellipsis;
 use anyhow::Result;
@@ -26,7 +27,7 @@ use std::collections::{BTreeSet, HashMap, HashSet};
 use std::fmt::Write;
 use std::future::Future;
 use std::io::Write as _;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::sync::{Arc, LazyLock};
 use std::time::{Duration, Instant};
 use tokio_util::sync::CancellationToken;
@@ -273,37 +274,40 @@ static CJK_SCRIPT_REGEX: LazyLock<Regex> = LazyLock::new(|| {
 /// Replaces known credential patterns with a redacted placeholder while preserving
 /// a small prefix for context.
 pub(crate) fn scrub_credentials(input: &str) -> String {
-    SENSITIVE_KV_REGEX
-        .replace_all(input, |caps: &regex::Captures| {
-            let full_match = &caps[0];
-            let key = &caps[1];
-            let val = caps
-                .get(2)
-                .or(caps.get(3))
-                .or(caps.get(4))
-                .map(|m| m.as_str())
-                .unwrap_or("");
-
-            // Preserve first 4 chars for context, then redact
-            let prefix = if val.len() > 4 { &val[..4] } else { "" };
-
-            if full_match.contains(':') {
-                if full_match.contains('"') {
-                    format!("\"{}\": \"{}*[REDACTED]\"", key, prefix)
-                } else {
-                    format!("{}: {}*[REDACTED]", key, prefix)
-                }
-            } else if full_match.contains('=') {
-                if full_match.contains('"') {
-                    format!("{}=\"{}*[REDACTED]\"", key, prefix)
-                } else {
-                    format!("{}={}*[REDACTED]", key, prefix)
-                }
+    redact_credentials(input).into_owned()
+}
+
+/// [`scrub_credentials`] without the copy when nothing needs redacting.
+pub(crate) fn redact_credentials(input: &str) -> Cow<'_, str> {
+    SENSITIVE_KV_REGEX.replace_all(input, |caps: &regex::Captures| {
+        let full_match = &caps[0];
+        let key = &caps[1];
+        let val = caps
+            .get(2)
+            .or(caps.get(3))
+            .or(caps.get(4))
+            .map(|m| m.as_str())
+            .unwrap_or("");
+
+        // Preserve first 4 chars for context, then redact
+        let prefix = if val.len() > 4 { &val[..4] } else { "" };
+
+        if full_match.contains(':') {
+            if full_match.contains('"') {
+                format!("\"{}\": \"{}*[REDACTED]\"", key, prefix)
             } else {
                 format!("{}: {}*[REDACTED]", key, prefix)
             }
-        })
-        .to_string()
+        } else if full_match.contains('=') {
+            if full_match.contains('"') {
+                format!("{}=\"{}*[REDACTED]\"", key, prefix)
+            } else {
+                format!("{}={}*[REDACTED]", key, prefix)
+            }
+        } else {
+            format!("{}: {}*[REDACTED]", key, prefix)
+        }
+    })
 }
 
 /// Default trigger for auto-compaction when non-system message count exceeds this threshold.
@@ -369,6 +373,8 @@ tokio::task_local! {
     static SAFETY_HEARTBEAT_CONFIG: Option<SafetyHeartbeatConfig>;
     static TOOL_LOOP_PROGRESS_MODE: ProgressMode;
     static TOOL_LOOP_COST_ENFORCEMENT_CONTEXT: Option<CostEnforcementContext>;
+    static TOOL_LOOP_SESSION_ID: Option<String>;
+    static TOOL_LOOP_KILL_SWITCH_WORKSPACE: Option<PathBuf>;
 }
 
 /// Configuration for periodic safety-constraint re-injection (heartbeat).
@@ -397,7 +403,11 @@ pub(crate) fn create_cost_enforcement_context(
         return None;
     }
     let tracker = match CostTracker::new(cost_config.clone(), workspace_dir) {
-        Ok(tracker) => Arc::new(tracker),
+        Ok(tracker) => {
+            let tracker = Arc::new(tracker);
+            tracker.spawn_flush_task();
+            tracker
+        }
         Err(error) => {
             tracing::warn!("Cost budget preflight disabled: failed to initialize tracker: {error}");
             return None;
@@ -430,6 +440,54 @@ where
         .await
 }
 
+/// Run `future` with the conversation session id that tool calls report in
+/// their [`ToolExecutionContext`](crate::tools::ToolExecutionContext).
+pub(crate) async fn scope_session_id<F>(session_id: Option<String>, future: F) -> F::Output
+where
+    F: Future,
+{
+    TOOL_LOOP_SESSION_ID.scope(session_id, future).await
+}
+
+/// Run `future` with the workspace whose kill switch
+/// ([`crate::security::kill_switch`]) pauses the tool loop.
+pub(crate) async fn scope_kill_switch<F>(workspace_dir: Option<PathBuf>, future: F) -> F::Output
+where
+    F: Future,
+{
+    TOOL_LOOP_KILL_SWITCH_WORKSPACE
+        .scope(workspace_dir, future)
+        .await
+}
+
+/// Block while the workspace kill switch is engaged, polling until the file
+/// is removed or the loop is cancelled.
+async fn wait_for_kill_switch_release(
+    workspace_dir: &Path,
+    cancellation_token: Option<&CancellationToken>,
+) -> Result<()> {
+    if !kill_switch::is_engaged(workspace_dir) {
+        return Ok(());
+    }
+    tracing::warn!(
+        path = %kill_switch::kill_switch_path(workspace_dir).display(),
+        "Kill switch engaged; pausing agent loop until it is removed"
+    );
+    while kill_switch::is_engaged(workspace_dir) {
+        match cancellation_token {
+            Some(token) => {
+                tokio::select! {
+                    () = token.cancelled() => return Err(ToolLoopCancelled.into()),
+                    () = tokio::time::sleep(kill_switch::KILL_SWITCH_POLL_INTERVAL) => {}
+                }
+            }
+            None => tokio::time::sleep(kill_switch::KILL_SWITCH_POLL_INTERVAL).await,
+        }
+    }
+    tracing::info!("Kill switch released; resuming agent loop");
+    Ok(())
+}
+
 fn should_inject_safety_heartbeat(counter: usize, interval: usize) -> bool {
     interval > 0 && counter > 0 && counter % interval == 0
 }
@@ -1219,6 +1277,11 @@ pub async fn run_tool_call_loop(
         .try_with(Clone::clone)
         .ok()
         .flatten();
+    let session_id = TOOL_LOOP_SESSION_ID.try_with(Clone::clone).ok().flatten();
+    let kill_switch_workspace = TOOL_LOOP_KILL_SWITCH_WORKSPACE
+        .try_with(Clone::clone)
+        .ok()
+        .flatten();
     let mut progress_tracker = ProgressTracker::default();
     let mut active_model = model.to_string();
     let canary_guard = CanaryGuard::new(
@@ -1260,6 +1323,9 @@ pub async fn run_tool_call_loop(
         {
             return Err(ToolLoopCancelled.into());
         }
+        if let Some(workspace_dir) = kill_switch_workspace.as_deref() {
+            wait_for_kill_switch_release(workspace_dir, cancellation_token.as_ref()).await?;
+        }
 
         let image_marker_count = multimodal::count_image_markers(history);
         let provider_supports_vision =
@@ -2011,11 +2077,11 @@ pub async fn run_tool_call_loop(
 
         // Execute tool calls and build results. `individual_results` tracks per-call output so
         // native-mode history can emit one role=tool message per tool call with the correct ID.
+        // Each output is one shared buffer, copied only where it is serialized into history.
         //
         // When multiple tool calls are present and interactive CLI approval is not needed, run
         // tool executions concurrently for lower wall-clock latency.
-        let mut tool_results = String::new();
-        let mut individual_results: Vec<(Option<String>, String)> = Vec::new();
+        let mut individual_results: Vec<(String, Option<String>, Arc<str>)> = Vec::new();
         let mut ordered_results: Vec<Option<(String, Option<String>, ToolExecutionOutcome)>> =
             (0..tool_calls.len()).map(|_| None).collect();
         let allow_parallel_execution = should_execute_tools_in_parallel(&tool_calls, approval);
@@ -2053,7 +2119,7 @@ pub async fn run_tool_call_loop(
                             call.name.clone(),
                             call.tool_call_id.clone(),
                             ToolExecutionOutcome {
-                                output: cancelled,
+                                output: cancelled.into(),
                                 success: false,
                                 error_reason: Some(scrub_credentials(&reason)),
                                 duration: Duration::ZERO,
@@ -2098,7 +2164,7 @@ pub async fn run_tool_call_loop(
                     tool_name.clone(),
                     call.tool_call_id.clone(),
                     ToolExecutionOutcome {
-                        output: blocked.clone(),
+                        output: Arc::from(blocked.as_str()),
                         success: false,
                         error_reason: Some(blocked),
                         duration: Duration::ZERO,
@@ -2218,7 +2284,7 @@ pub async fn run_tool_call_loop(
                             tool_name.clone(),
                             call.tool_call_id.clone(),
                             ToolExecutionOutcome {
-                                output: denied.clone(),
+                                output: Arc::from(denied.as_str()),
                                 success: false,
                                 error_reason: Some(denied),
                                 duration: Duration::ZERO,
@@ -2271,7 +2337,7 @@ pub async fn run_tool_call_loop(
                     tool_name.clone(),
                     call.tool_call_id.clone(),
                     ToolExecutionOutcome {
-                        output: duplicate.clone(),
+                        output: Arc::from(duplicate.as_str()),
                         success: false,
                         error_reason: Some(duplicate),
                         duration: Duration::ZERO,
@@ -2316,9 +2382,15 @@ pub async fn run_tool_call_loop(
             progress_indices.push(progress_idx);
         }
 
+        let tool_context = crate::tools::ToolExecutionContext {
+            session_id: session_id.clone(),
+            correlation_id: Some(turn_id.clone()),
+            model: Some(active_model.clone()),
+        };
         let executed_outcomes = if allow_parallel_execution && executable_calls.len() > 1 {
             execute_tools_parallel(
                 &executable_calls,
+                &tool_context,
                 tools_registry,
                 observer,
                 cancellation_token.as_ref(),
@@ -2327,6 +2399,7 @@ pub async fn run_tool_call_loop(
         } else {
             execute_tools_sequential(
                 &executable_calls,
+                &tool_context,
                 tools_registry,
                 observer,
                 cancellation_token.as_ref(),
@@ -2352,15 +2425,17 @@ pub async fn run_tool_call_loop(
                     "iteration": iteration + 1,
                     "tool": call.name.clone(),
                     "duration_ms": outcome.duration.as_millis(),
-                    "output": scrub_credentials(&outcome.output),
+                    "output": redact_credentials(&outcome.output),
                 }),
             );
 
             // ── Hook: after_tool_call (void) ─────────────────
+            // Hooks take an owned `ToolResult`, so the output is copied for them only; a
+            // hook that leaves it unchanged keeps the shared buffer.
             if let Some(hooks) = hooks {
                 let mut tool_result_obj = crate::tools::ToolResult {
                     success: outcome.success,
-                    output: outcome.output.clone(),
+                    output: outcome.output.to_string(),
                     error: outcome.error_reason.clone(),
                 };
                 match hooks
@@ -2370,16 +2445,18 @@ pub async fn run_tool_call_loop(
                     crate::hooks::HookResult::Continue(next) => {
                         tool_result_obj = next;
                         outcome.success = tool_result_obj.success;
-                        outcome.output = tool_result_obj.output.clone();
+                        if tool_result_obj.output != *outcome.output {
+                            outcome.output = Arc::from(tool_result_obj.output.as_str());
+                        }
                         outcome.error_reason = tool_result_obj.error.clone();
                     }
                     crate::hooks::HookResult::Cancel(reason) => {
                         outcome.success = false;
                         outcome.error_reason = Some(scrub_credentials(&reason));
-                        outcome.output = format!("Tool result blocked by hook: {reason}");
+                        outcome.output = format!("Tool result blocked by hook: {reason}").into();
                         tool_result_obj.success = false;
                         tool_result_obj.error = Some(reason);
-                        tool_result_obj.output = outcome.output.clone();
+                        tool_result_obj.output = outcome.output.to_string();
                     }
                 }
                 hooks
@@ -2406,12 +2483,7 @@ pub async fn run_tool_call_loop(
         }
 
         for (tool_name, tool_call_id, outcome) in ordered_results.into_iter().flatten() {
-            individual_results.push((tool_call_id, outcome.output.clone()));
-            let _ = writeln!(
-                tool_results,
-                "<tool_result name=\"{}\">\n{}\n</tool_result>",
-                tool_name, outcome.output
-            );
+            individual_results.push((tool_name, tool_call_id, outcome.output));
         }
 
         // Add assistant message with tool calls + tool results to history.
@@ -2424,25 +2496,32 @@ pub async fn run_tool_call_loop(
                 && !individual_results.is_empty()
                 && individual_results
                     .iter()
-                    .all(|(tool_call_id, _)| tool_call_id.is_some());
+                    .all(|(_, tool_call_id, _)| tool_call_id.is_some());
             if all_results_have_ids {
-                for (tool_call_id, result) in &individual_results {
+                for (_, tool_call_id, result) in &individual_results {
                     let tool_msg = serde_json::json!({
                         "tool_call_id": tool_call_id,
-                        "content": result,
+                        "content": &**result,
                     });
                     history.push(ChatMessage::tool(tool_msg.to_string()));
                 }
             } else {
-                history.push(ChatMessage::user(format!("[Tool results]\n{tool_results}")));
+                let mut tool_results = String::from("[Tool results]\n");
+                for (tool_name, _, result) in &individual_results {
+                    let _ = writeln!(
+                        tool_results,
+                        "<tool_result name=\"{tool_name}\">\n{result}\n</tool_result>"
+                    );
+                }
+                history.push(ChatMessage::user(tool_results));
             }
         } else {
-            for (native_call, (_, result)) in
+            for (native_call, (_, _, result)) in
                 native_tool_calls.iter().zip(individual_results.iter())
             {
                 let tool_msg = serde_json::json!({
                     "tool_call_id": native_call.id,
-                    "content": result,
+                    "content": &**result,
                 });
                 history.push(ChatMessage::tool(tool_msg.to_string()));
             }
@@ -2561,6 +2640,7 @@ pub(crate) fn build_shell_policy_instructions(autonomy: &crate::config::Autonomy
     let autonomy_label = match autonomy.level {
         crate::security::AutonomyLevel::ReadOnly => "read_only",
         crate::security::AutonomyLevel::Supervised => "supervised",
+        crate::security::AutonomyLevel::Autonomous => "autonomous",
         crate::security::AutonomyLevel::Full => "full",
     };
     let _ = writeln!(instructions, "- Autonomy level: `{autonomy_label}`");
@@ -2649,17 +2729,17 @@ pub async fn run(
     }
 
     // ── Wire up agnostic subsystems ──────────────────────────────
-    let base_observer: Arc<dyn Observer> =
-        Arc::from(observability::create_observer(&config.observability));
+    let security = Arc::new(SecurityPolicy::from_root_config(&config));
+    let base_observer: Arc<dyn Observer> = Arc::from(observability::with_security_budget(
+        observability::create_observer(&config.observability),
+        &security,
+        &config.cost,
+    ));
     let observer: Arc<dyn Observer> = Arc::new(
         crate::plugins::bridge::observer::ObserverBridge::new(base_observer),
     );
     let runtime: Arc<dyn runtime::RuntimeAdapter> =
         Arc::from(runtime::create_runtime(&config.runtime)?);
-    let security = Arc::new(SecurityPolicy::from_config(
-        &config.autonomy,
-        &config.workspace_dir,
-    ));
 
     // ── Memory (the brain) ────────────────────────────────────────
     let mem: Arc<dyn Memory> = Arc::from(memory::create_memory_with_storage(
@@ -2800,10 +2880,34 @@ pub async fn run(
             "memory_recall",
             "Search memory. Use when: retrieving prior decisions, user preferences, historical context. Don't use when: answer is already in current context.",
         ),
+        (
+            "memory_search",
+            "Filter memory by keywords, #tags, category, or date range. Use when: listing notes with a tag or from a period. Don't use when: a fuzzy relevance search is enough (use memory_recall).",
+        ),
         (
             "memory_forget",
             "Delete a memory entry. Use when: memory is incorrect/stale or explicitly requested for removal. Don't use when: impact is uncertain.",
         ),
+        (
+            "redact_memory",
+            "Replace a memory entry's content with [REDACTED] and log it to an audit file. Use when: a stored note contains a secret or data that must not be kept. Supervised mode only.",
+        ),
+        (
+            "consolidate_memory",
+            "List recent short-term memories and promote a summary of them to core memory. Use when: distilling daily notes/observations into durable facts. Don't use when: storing a single new fact (use memory_store).",
+        ),
+        (
+            "digest_memory",
+            "List the past 24 hours of notes, then store a digest of them tagged #digest. Use when: running the daily digest or catching up on a busy day. Don't use when: promoting durable facts (use consolidate_memory).",
+        ),
+        (
+            "scratchpad",
+            "Stash temporary key/value working state for this session (set, get, list, delete, clear). Use when: holding intermediate results across steps of a task. Don't use when: the information should outlive the session (use memory_store).",
+        ),
+        (
+            "request_escalation",
+            "Ask the operator for a time-boxed higher autonomy level or extra rate budget. Use when: policy blocks a step the task genuinely needs. Don't use when: the task can proceed within current policy.",
+        ),
     ];
     tool_descs.push((
         "cron_add",
@@ -2922,6 +3026,12 @@ pub async fn run(
         system_prompt.push_str(&build_tool_instructions(&tools_registry));
     }
     system_prompt.push_str(&build_shell_policy_instructions(&config.autonomy));
+    system_prompt.push_str(
+        &PinnedMemoryLoader::default()
+            .load(mem.as_ref())
+            .await
+            .unwrap_or_default(),
+    );
 
     let configured_hooks = crate::hooks::create_runner_from_config(&config.hooks);
     let effective_hooks = hooks.or(configured_hooks.as_deref());
@@ -2984,31 +3094,34 @@ pub async fn run(
         } else {
             None
         };
-        let response = scope_cost_enforcement_context(
-            cost_enforcement_context.clone(),
-            SAFETY_HEARTBEAT_CONFIG.scope(
-                hb_cfg,
-                LOOP_DETECTION_CONFIG.scope(
-                    ld_cfg,
-                    TOOL_LOOP_CANARY_TOKENS_ENABLED.scope(
-                        config.security.canary_tokens,
-                        run_tool_call_loop(
-                            provider.as_ref(),
-                            &mut history,
-                            &tools_registry,
-                            observer.as_ref(),
-                            provider_name,
-                            &model_name,
-                            temperature,
-                            false,
-                            approval_manager.as_ref(),
-                            channel_name,
-                            &config.multimodal,
-                            config.agent.max_tool_iterations,
-                            None,
-                            None,
-                            effective_hooks,
-                            &[],
+        let response = scope_kill_switch(
+            Some(config.workspace_dir.clone()),
+            scope_cost_enforcement_context(
+                cost_enforcement_context.clone(),
+                SAFETY_HEARTBEAT_CONFIG.scope(
+                    hb_cfg,
+                    LOOP_DETECTION_CONFIG.scope(
+                        ld_cfg,
+                        TOOL_LOOP_CANARY_TOKENS_ENABLED.scope(
+                            config.security.canary_tokens,
+                            run_tool_call_loop(
+                                provider.as_ref(),
+                                &mut history,
+                                &tools_registry,
+                                observer.as_ref(),
+                                provider_name,
+                                &model_name,
+                                temperature,
+                                false,
+                                approval_manager.as_ref(),
+                                channel_name,
+                                &config.multimodal,
+                                config.agent.max_tool_iterations,
+                                None,
+                                None,
+                                effective_hooks,
+                                &[],
+                            ),
                         ),
                     ),
                 ),
@@ -3213,31 +3326,34 @@ pub async fn run(
             } else {
                 None
             };
-            let response = match scope_cost_enforcement_context(
-                cost_enforcement_context.clone(),
-                SAFETY_HEARTBEAT_CONFIG.scope(
-                    hb_cfg,
-                    LOOP_DETECTION_CONFIG.scope(
-                        ld_cfg,
-                        TOOL_LOOP_CANARY_TOKENS_ENABLED.scope(
-                            config.security.canary_tokens,
-                            run_tool_call_loop(
-                                provider.as_ref(),
-                                &mut history,
-                                &tools_registry,
-                                observer.as_ref(),
-                                provider_name,
-                                &model_name,
-                                temperature,
-                                false,
-                                approval_manager.as_ref(),
-                                channel_name,
-                                &config.multimodal,
-                                config.agent.max_tool_iterations,
-                                None,
-                                None,
-                                effective_hooks,
-                                &[],
+            let response = match scope_kill_switch(
+                Some(config.workspace_dir.clone()),
+                scope_cost_enforcement_context(
+                    cost_enforcement_context.clone(),
+                    SAFETY_HEARTBEAT_CONFIG.scope(
+                        hb_cfg,
+                        LOOP_DETECTION_CONFIG.scope(
+                            ld_cfg,
+                            TOOL_LOOP_CANARY_TOKENS_ENABLED.scope(
+                                config.security.canary_tokens,
+                                run_tool_call_loop(
+                                    provider.as_ref(),
+                                    &mut history,
+                                    &tools_registry,
+                                    observer.as_ref(),
+                                    provider_name,
+                                    &model_name,
+                                    temperature,
+                                    false,
+                                    approval_manager.as_ref(),
+                                    channel_name,
+                                    &config.multimodal,
+                                    config.agent.max_tool_iterations,
+                                    None,
+                                    None,
+                                    effective_hooks,
+                                    &[],
+                                ),
                             ),
                         ),
                     ),
@@ -3379,17 +3495,17 @@ pub async fn process_message_with_session(
     if let Err(error) = crate::plugins::runtime::initialize_from_config(&config.plugins) {
         tracing::warn!("plugin registry initialization skipped: {error}");
     }
-    let base_observer: Arc<dyn Observer> =
-        Arc::from(observability::create_observer(&config.observability));
+    let security = Arc::new(SecurityPolicy::from_root_config(&config));
+    let base_observer: Arc<dyn Observer> = Arc::from(observability::with_security_budget(
+        observability::create_observer(&config.observability),
+        &security,
+        &config.cost,
+    ));
     let observer: Arc<dyn Observer> = Arc::new(
         crate::plugins::bridge::observer::ObserverBridge::new(base_observer),
     );
     let runtime: Arc<dyn runtime::RuntimeAdapter> =
         Arc::from(runtime::create_runtime(&config.runtime)?);
-    let security = Arc::new(SecurityPolicy::from_config(
-        &config.autonomy,
-        &config.workspace_dir,
-    ));
     let mem: Arc<dyn Memory> = Arc::from(memory::create_memory_with_storage(
         &config.memory,
         Some(&config.storage.provider.config),
@@ -3476,7 +3592,19 @@ pub async fn process_message_with_session(
         ("memory_store", "Save to memory."),
         ("memory_observe", "Store observation memory."),
         ("memory_recall", "Search memory."),
+        ("memory_search", "Filter memory by keyword, tag, or date."),
         ("memory_forget", "Delete a memory entry."),
+        (
+            "redact_memory",
+            "Redact a memory entry with an audit record.",
+        ),
+        ("consolidate_memory", "Promote short-term memories to core."),
+        ("digest_memory", "Digest the last 24h of memory."),
+        ("scratchpad", "Session-only scratch notes."),
+        (
+            "request_escalation",
+            "Ask the operator for more autonomy or rate budget.",
+        ),
         (
             "model_routing_config",
             "Configure default model, scenario routing, and delegate agents.",
@@ -3547,6 +3675,12 @@ pub async fn process_message_with_session(
         system_prompt.push_str(&build_tool_instructions(&tools_registry));
     }
     system_prompt.push_str(&build_shell_policy_instructions(&config.autonomy));
+    system_prompt.push_str(
+        &PinnedMemoryLoader::default()
+            .load(mem.as_ref())
+            .await
+            .unwrap_or_default(),
+    );
 
     let mem_context = build_context(
         mem.as_ref(),
@@ -3583,21 +3717,24 @@ pub async fn process_message_with_session(
     } else {
         None
     };
-    let response = scope_cost_enforcement_context(
-        cost_enforcement_context,
-        SAFETY_HEARTBEAT_CONFIG.scope(
-            hb_cfg,
-            agent_turn(
-                provider.as_ref(),
-                &mut history,
-                &tools_registry,
-                observer.as_ref(),
-                provider_name,
-                &model_name,
-                config.default_temperature,
-                true,
-                &config.multimodal,
-                config.agent.max_tool_iterations,
+    let response = scope_kill_switch(
+        Some(config.workspace_dir.clone()),
+        scope_cost_enforcement_context(
+            cost_enforcement_context,
+            SAFETY_HEARTBEAT_CONFIG.scope(
+                hb_cfg,
+                agent_turn(
+                    provider.as_ref(),
+                    &mut history,
+                    &tools_registry,
+                    observer.as_ref(),
+                    provider_name,
+                    &model_name,
+                    config.default_temperature,
+                    true,
+                    &config.multimodal,
+                    config.agent.max_tool_iterations,
+                ),
             ),
         ),
     )
@@ -4267,6 +4404,55 @@ mod tests {
         assert_eq!(calls.load(Ordering::SeqCst), 0);
     }
 
+    #[tokio::test]
+    async fn run_tool_call_loop_pauses_while_kill_switch_is_engaged() {
+        let workspace = tempfile::tempdir().unwrap();
+        let kill_file = kill_switch::kill_switch_path(workspace.path());
+        std::fs::create_dir_all(kill_file.parent().unwrap()).unwrap();
+        std::fs::write(&kill_file, "").unwrap();
+
+        let calls = Arc::new(AtomicUsize::new(0));
+        let provider = NonVisionProvider {
+            calls: Arc::clone(&calls),
+        };
+        let mut history = vec![ChatMessage::user("hello".to_string())];
+        let tools_registry: Vec<Box<dyn Tool>> = Vec::new();
+        let observer = NoopObserver;
+        let cancellation_token = CancellationToken::new();
+        let canceller = cancellation_token.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            canceller.cancel();
+        });
+
+        let err = scope_kill_switch(
+            Some(workspace.path().to_path_buf()),
+            run_tool_call_loop(
+                &provider,
+                &mut history,
+                &tools_registry,
+                &observer,
+                "mock-provider",
+                "mock-model",
+                0.0,
+                true,
+                None,
+                "cli",
+                &crate::config::MultimodalConfig::default(),
+                3,
+                Some(cancellation_token),
+                None,
+                None,
+                &[],
+            ),
+        )
+        .await
+        .expect_err("cancelling a paused loop should stop it");
+
+        assert!(is_tool_loop_cancelled(&err));
+        assert_eq!(calls.load(Ordering::SeqCst), 0);
+    }
+
     #[tokio::test]
     async fn run_tool_call_loop_allows_anthropic_route_on_vision_probe_false_negative() {
         let provider = ScriptedProvider::from_text_responses(vec!["vision-ok"]);
@@ -7263,6 +7449,18 @@ Let me check the result."#;
         );
     }
 
+    #[test]
+    fn redact_credentials_borrows_clean_output() {
+        assert!(matches!(
+            redact_credentials("normal text without any secrets"),
+            Cow::Borrowed(_)
+        ));
+        assert!(matches!(
+            redact_credentials("api_key=sk-1234567890abcdef"),
+            Cow::Owned(_)
+        ));
+    }
+
     #[test]
     fn scrub_credentials_short_values_not_redacted() {
         // Values shorter than 8 chars should not be redacted
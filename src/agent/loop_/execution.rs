@@ -1,9 +1,10 @@
 use super::parsing::ParsedToolCall;
-use super::{scrub_credentials, ToolLoopCancelled};
+use super::{redact_credentials, scrub_credentials, ToolLoopCancelled};
 use crate::approval::ApprovalManager;
 use crate::observability::{Observer, ObserverEvent};
-use crate::tools::Tool;
+use crate::tools::{Tool, ToolExecutionContext};
 use anyhow::Result;
+use std::sync::Arc;
 use std::time::{Duration, Instant};
 use tokio_util::sync::CancellationToken;
 
@@ -13,6 +14,7 @@ fn find_tool<'a>(tools: &'a [Box<dyn Tool>], name: &str) -> Option<&'a dyn Tool>
 async fn execute_one_tool(
     call_name: &str,
     call_arguments: serde_json::Value,
+    context: ToolExecutionContext,
     tools_registry: &[Box<dyn Tool>],
     observer: &dyn Observer,
     cancellation_token: Option<&CancellationToken>,
@@ -31,14 +33,14 @@ async fn execute_one_tool(
             success: false,
         });
         return Ok(ToolExecutionOutcome {
-            output: reason.clone(),
+            output: Arc::from(reason.as_str()),
             success: false,
             error_reason: Some(scrub_credentials(&reason)),
             duration,
         });
     };
 
-    let tool_future = tool.execute(call_arguments);
+    let tool_future = context.scope(tool.execute(call_arguments));
     let tool_result = if let Some(token) = cancellation_token {
         tokio::select! {
             () = token.cancelled() => return Err(ToolLoopCancelled.into()),
@@ -58,7 +60,7 @@ async fn execute_one_tool(
             });
             if r.success {
                 Ok(ToolExecutionOutcome {
-                    output: scrub_credentials(&r.output),
+                    output: Arc::from(redact_credentials(&r.output)),
                     success: true,
                     error_reason: None,
                     duration,
@@ -66,7 +68,7 @@ async fn execute_one_tool(
             } else {
                 let reason = r.error.unwrap_or(r.output);
                 Ok(ToolExecutionOutcome {
-                    output: format!("Error: {reason}"),
+                    output: format!("Error: {reason}").into(),
                     success: false,
                     error_reason: Some(scrub_credentials(&reason)),
                     duration,
@@ -82,7 +84,7 @@ async fn execute_one_tool(
             });
             let reason = format!("Error executing {call_name}: {e}");
             Ok(ToolExecutionOutcome {
-                output: reason.clone(),
+                output: Arc::from(reason.as_str()),
                 success: false,
                 error_reason: Some(scrub_credentials(&reason)),
                 duration,
@@ -92,7 +94,8 @@ async fn execute_one_tool(
 }
 
 pub(super) struct ToolExecutionOutcome {
-    pub(super) output: String,
+    /// Shared by every sink the loop hands the output to.
+    pub(super) output: Arc<str>,
     pub(super) success: bool,
     pub(super) error_reason: Option<String>,
     pub(super) duration: Duration,
@@ -120,8 +123,21 @@ pub(super) fn should_execute_tools_in_parallel(
     true
 }
 
+/// Context for one call: the turn's context, keyed to the provider's call id
+/// when it sent one.
+fn call_context(turn: &ToolExecutionContext, call: &ParsedToolCall) -> ToolExecutionContext {
+    ToolExecutionContext {
+        correlation_id: call
+            .tool_call_id
+            .clone()
+            .or_else(|| turn.correlation_id.clone()),
+        ..turn.clone()
+    }
+}
+
 pub(super) async fn execute_tools_parallel(
     tool_calls: &[ParsedToolCall],
+    context: &ToolExecutionContext,
     tools_registry: &[Box<dyn Tool>],
     observer: &dyn Observer,
     cancellation_token: Option<&CancellationToken>,
@@ -132,6 +148,7 @@ pub(super) async fn execute_tools_parallel(
             execute_one_tool(
                 &call.name,
                 call.arguments.clone(),
+                call_context(context, call),
                 tools_registry,
                 observer,
                 cancellation_token,
@@ -145,6 +162,7 @@ pub(super) async fn execute_tools_parallel(
 
 pub(super) async fn execute_tools_sequential(
     tool_calls: &[ParsedToolCall],
+    context: &ToolExecutionContext,
     tools_registry: &[Box<dyn Tool>],
     observer: &dyn Observer,
     cancellation_token: Option<&CancellationToken>,
@@ -156,6 +174,7 @@ pub(super) async fn execute_tools_sequential(
             execute_one_tool(
                 &call.name,
                 call.arguments.clone(),
+                call_context(context, call),
                 tools_registry,
                 observer,
                 cancellation_token,
@@ -0,0 +1,246 @@
+//! Process-wide LRU cache for rendered workspace prompt layers.
+//!
+//! Every turn (and every channel message) rebuilds the system prompt from the
+//! same handful of workspace files (`AGENTS.md`, `SOUL.md`, `MEMORY.md`, ...).
+//! Rendering a layer means reading the file, trimming it, truncating on a
+//! character boundary, and wrapping it in a `### <file>` header. This cache
+//! keeps the rendered block and revalidates it with a single `stat` call:
+//! when the file's modification time or length changes, the entry is
+//! re-rendered from disk.
+
+use parking_lot::Mutex;
+use std::collections::{HashMap, VecDeque};
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, OnceLock};
+use std::time::SystemTime;
+
+/// Maximum number of rendered layers kept in memory.
+const PROMPT_LAYER_CACHE_CAPACITY: usize = 64;
+
+/// Cheap change detector for a file on disk.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct FileStamp {
+    modified: Option<SystemTime>,
+    len: u64,
+}
+
+impl FileStamp {
+    fn of(path: &Path) -> Option<Self> {
+        let meta = std::fs::metadata(path).ok()?;
+        Some(Self {
+            modified: meta.modified().ok(),
+            len: meta.len(),
+        })
+    }
+}
+
+/// Minimal least-recently-used map with a fixed capacity.
+#[derive(Debug)]
+pub struct LruCache<K, V> {
+    capacity: usize,
+    entries: HashMap<K, V>,
+    order: VecDeque<K>,
+}
+
+impl<K: Clone + Eq + std::hash::Hash, V: Clone> LruCache<K, V> {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            entries: HashMap::new(),
+            order: VecDeque::new(),
+        }
+    }
+
+    /// Return a clone of the cached value and mark it as most recently used.
+    pub fn get(&mut self, key: &K) -> Option<V> {
+        let value = self.entries.get(key)?.clone();
+        self.touch(key);
+        Some(value)
+    }
+
+    /// Insert or replace a value, evicting the least recently used entry when full.
+    pub fn put(&mut self, key: K, value: V) {
+        if self.entries.insert(key.clone(), value).is_some() {
+            self.touch(&key);
+            return;
+        }
+        self.order.push_back(key);
+        while self.entries.len() > self.capacity {
+            let Some(oldest) = self.order.pop_front() else {
+                break;
+            };
+            self.entries.remove(&oldest);
+        }
+    }
+
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        let value = self.entries.remove(key)?;
+        self.order.retain(|k| k != key);
+        Some(value)
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    fn touch(&mut self, key: &K) {
+        if let Some(pos) = self.order.iter().position(|k| k == key) {
+            if let Some(k) = self.order.remove(pos) {
+                self.order.push_back(k);
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct CachedLayer {
+    stamp: FileStamp,
+    rendered: Arc<str>,
+}
+
+type LayerKey = (PathBuf, usize);
+
+fn layer_cache() -> &'static Mutex<LruCache<LayerKey, CachedLayer>> {
+    static CACHE: OnceLock<Mutex<LruCache<LayerKey, CachedLayer>>> = OnceLock::new();
+    CACHE.get_or_init(|| Mutex::new(LruCache::new(PROMPT_LAYER_CACHE_CAPACITY)))
+}
+
+/// Render `workspace_dir/filename` as a prompt layer, reusing the cached
+/// rendering while the file is unchanged on disk.
+///
+/// Empty files render to an empty string; missing files render to a
+/// `[File not found: ...]` marker (matching OpenClaw behavior). Content longer
+/// than `max_chars` characters is truncated on a UTF-8 boundary.
+pub fn render_workspace_file(workspace_dir: &Path, filename: &str, max_chars: usize) -> Arc<str> {
+    let path = workspace_dir.join(filename);
+    let key = (path, max_chars);
+
+    let Some(stamp) = FileStamp::of(&key.0) else {
+        layer_cache().lock().remove(&key);
+        return missing_layer(filename);
+    };
+
+    if let Some(cached) = layer_cache().lock().get(&key) {
+        if cached.stamp == stamp {
+            return cached.rendered;
+        }
+    }
+
+    let rendered: Arc<str> = match std::fs::read_to_string(&key.0) {
+        Ok(content) => Arc::from(render_layer(filename, &content, max_chars)),
+        Err(_) => return missing_layer(filename),
+    };
+    layer_cache().lock().put(
+        key,
+        CachedLayer {
+            stamp,
+            rendered: Arc::clone(&rendered),
+        },
+    );
+    rendered
+}
+
+/// Missing-file marker (matches OpenClaw behavior).
+fn missing_layer(filename: &str) -> Arc<str> {
+    Arc::from(format!(
+        "### {filename}\n\n[File not found: {filename}]\n\n"
+    ))
+}
+
+fn render_layer(filename: &str, content: &str, max_chars: usize) -> String {
+    let trimmed = content.trim();
+    if trimmed.is_empty() {
+        return String::new();
+    }
+
+    let mut out =
+        String::with_capacity(trimmed.len().min(max_chars.saturating_mul(4)) + filename.len() + 16);
+    let _ = writeln!(out, "### {filename}\n");
+    // Use character-boundary-safe truncation for UTF-8
+    let truncated = trimmed
+        .char_indices()
+        .nth(max_chars)
+        .map_or(trimmed, |(idx, _)| &trimmed[..idx]);
+    out.push_str(truncated);
+    if truncated.len() < trimmed.len() {
+        let _ = writeln!(
+            out,
+            "\n\n[... truncated at {max_chars} chars — use `read` for full file]\n"
+        );
+    } else {
+        out.push_str("\n\n");
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn lru_evicts_least_recently_used() {
+        let mut cache = LruCache::new(2);
+        cache.put("a", 1);
+        cache.put("b", 2);
+        assert_eq!(cache.get(&"a"), Some(1));
+        cache.put("c", 3);
+        assert_eq!(cache.get(&"b"), None);
+        assert_eq!(cache.get(&"a"), Some(1));
+        assert_eq!(cache.get(&"c"), Some(3));
+        assert_eq!(cache.len(), 2);
+    }
+
+    #[test]
+    fn lru_replace_does_not_grow() {
+        let mut cache = LruCache::new(2);
+        cache.put("a", 1);
+        cache.put("a", 2);
+        assert_eq!(cache.len(), 1);
+        assert_eq!(cache.get(&"a"), Some(2));
+        assert_eq!(cache.remove(&"a"), Some(2));
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn render_reuses_cached_layer_until_file_changes() {
+        let tmp = TempDir::new().unwrap();
+        std::fs::write(tmp.path().join("SOUL.md"), "Be kind.").unwrap();
+
+        let first = render_workspace_file(tmp.path(), "SOUL.md", 1000);
+        let second = render_workspace_file(tmp.path(), "SOUL.md", 1000);
+        assert!(first.contains("### SOUL.md"));
+        assert!(first.contains("Be kind."));
+        assert!(
+            Arc::ptr_eq(&first, &second),
+            "unchanged file should hit cache"
+        );
+
+        std::fs::write(tmp.path().join("SOUL.md"), "Be kind and brief.").unwrap();
+        let third = render_workspace_file(tmp.path(), "SOUL.md", 1000);
+        assert!(third.contains("Be kind and brief."));
+    }
+
+    #[test]
+    fn render_marks_missing_and_skips_empty_files() {
+        let tmp = TempDir::new().unwrap();
+        let missing = render_workspace_file(tmp.path(), "USER.md", 1000);
+        assert!(missing.contains("[File not found: USER.md]"));
+
+        std::fs::write(tmp.path().join("USER.md"), "   \n").unwrap();
+        assert!(render_workspace_file(tmp.path(), "USER.md", 1000).is_empty());
+    }
+
+    #[test]
+    fn render_truncates_on_char_boundary() {
+        let tmp = TempDir::new().unwrap();
+        std::fs::write(tmp.path().join("AGENTS.md"), "🦀🦀🦀🦀").unwrap();
+        let rendered = render_workspace_file(tmp.path(), "AGENTS.md", 2);
+        assert!(rendered.contains("🦀🦀\n\n[... truncated at 2 chars"));
+    }
+}
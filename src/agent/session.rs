@@ -109,6 +109,11 @@ pub trait SessionManager: Send + Sync {
     async fn delete(&self, session_id: &str) -> Result<()>;
     async fn cleanup_expired(&self) -> Result<usize>;
 
+    /// Known session IDs, most recently updated first.
+    async fn list_sessions(&self) -> Result<Vec<String>> {
+        Ok(Vec::new())
+    }
+
     async fn get_or_create(&self, session_id: &str) -> Result<Session> {
         self.ensure_exists(session_id).await?;
         Ok(Session {
@@ -250,6 +255,16 @@ impl SessionManager for MemorySessionManager {
         sessions.retain(|_, s| s.updated_at_unix.load(Ordering::Relaxed) >= cutoff);
         Ok(before.saturating_sub(sessions.len()))
     }
+
+    async fn list_sessions(&self) -> Result<Vec<String>> {
+        let sessions = self.inner.sessions.read().await;
+        let mut ids: Vec<(i64, String)> = sessions
+            .iter()
+            .map(|(id, s)| (s.updated_at_unix.load(Ordering::Relaxed), id.clone()))
+            .collect();
+        ids.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| a.1.cmp(&b.1)));
+        Ok(ids.into_iter().map(|(_, id)| id).collect())
+    }
 }
 
 #[derive(Clone)]
@@ -429,6 +444,23 @@ impl SessionManager for SqliteSessionManager {
         .await
         .context("SQLite blocking task panicked")?
     }
+
+    async fn list_sessions(&self) -> Result<Vec<String>> {
+        let conn = self.conn.clone();
+
+        tokio::task::spawn_blocking(move || {
+            let conn = conn.lock();
+            let mut stmt = conn.prepare(
+                "SELECT session_id FROM agent_sessions ORDER BY updated_at DESC, session_id ASC",
+            )?;
+            let ids = stmt
+                .query_map([], |row| row.get::<_, String>(0))?
+                .collect::<rusqlite::Result<Vec<_>>>()?;
+            Ok(ids)
+        })
+        .await
+        .context("SQLite blocking task panicked")?
+    }
 }
 
 fn cleanup_interval(ttl: Duration) -> Duration {
@@ -552,6 +584,24 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn sqlite_session_lists_known_sessions() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        let mgr = SqliteSessionManager::new(
+            dir.path().join("sessions.db"),
+            Duration::from_secs(3600),
+            50,
+        )?;
+        assert!(mgr.list_sessions().await?.is_empty());
+
+        mgr.get_or_create("telegram:alice").await?;
+        mgr.get_or_create("main").await?;
+        let mut ids = mgr.list_sessions().await?;
+        ids.sort();
+        assert_eq!(ids, vec!["main".to_string(), "telegram:alice".to_string()]);
+        Ok(())
+    }
+
     #[tokio::test]
     async fn sqlite_session_cleanup_expires() -> Result<()> {
         let dir = tempfile::tempdir()?;
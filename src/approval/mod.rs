@@ -3,7 +3,9 @@
 //! Provides a pre-execution hook that prompts the user before tool calls,
 //! with session-scoped "Always" allowlists and audit logging.
 
-use crate::config::{AutonomyConfig, CommandContextRuleAction, NonCliNaturalLanguageApprovalMode};
+use crate::config::{
+    AutonomyConfig, CommandContextRuleAction, NonCliNaturalLanguageApprovalMode, OperatorRole,
+};
 use crate::security::AutonomyLevel;
 use chrono::{Duration, Utc};
 use parking_lot::{Mutex, RwLock};
@@ -90,6 +92,8 @@ pub struct ApprovalManager {
     non_cli_allow_all_once_remaining: Mutex<u32>,
     /// Optional allowlist of senders allowed to manage non-CLI approvals.
     non_cli_approval_approvers: RwLock<HashSet<String>>,
+    /// Named operator roles keyed by approver-style sender entries.
+    non_cli_operator_roles: RwLock<HashMap<String, OperatorRole>>,
     /// Default natural-language handling mode for non-CLI approval-management commands.
     non_cli_natural_language_approval_mode: RwLock<NonCliNaturalLanguageApprovalMode>,
     /// Optional per-channel overrides for natural-language approval mode.
@@ -113,6 +117,16 @@ impl ApprovalManager {
             .collect()
     }
 
+    fn normalize_non_cli_operator_roles(
+        entries: &HashMap<String, OperatorRole>,
+    ) -> HashMap<String, OperatorRole> {
+        entries
+            .iter()
+            .map(|(entry, role)| (entry.trim().to_string(), *role))
+            .filter(|(entry, _)| !entry.is_empty())
+            .collect()
+    }
+
     fn normalize_non_cli_natural_language_mode_by_channel(
         entries: &HashMap<String, NonCliNaturalLanguageApprovalMode>,
     ) -> HashMap<String, NonCliNaturalLanguageApprovalMode> {
@@ -154,6 +168,9 @@ impl ApprovalManager {
             non_cli_approval_approvers: RwLock::new(Self::normalize_non_cli_approvers(
                 &config.non_cli_approval_approvers,
             )),
+            non_cli_operator_roles: RwLock::new(Self::normalize_non_cli_operator_roles(
+                &config.non_cli_operator_roles,
+            )),
             non_cli_natural_language_approval_mode: RwLock::new(
                 config.non_cli_natural_language_approval_mode,
             ),
@@ -172,8 +189,8 @@ impl ApprovalManager {
     ///
     /// Returns `true` if the call needs a prompt, `false` if it can proceed.
     pub fn needs_approval(&self, tool_name: &str) -> bool {
-        // Full autonomy never prompts.
-        if self.autonomy_level == AutonomyLevel::Full {
+        // Autonomous and full autonomy never prompt per action.
+        if self.autonomy_level >= AutonomyLevel::Autonomous {
             return false;
         }
 
@@ -213,7 +230,11 @@ impl ApprovalManager {
             return true;
         }
 
-        if self.autonomy_level != AutonomyLevel::Supervised {
+        // `require_approval` command rules bind every level short of full.
+        if !matches!(
+            self.autonomy_level,
+            AutonomyLevel::Supervised | AutonomyLevel::Autonomous
+        ) {
             return false;
         }
 
@@ -375,6 +396,40 @@ impl ApprovalManager {
         approvers.contains(&sender_any_channel)
     }
 
+    /// Snapshot configured non-CLI operator roles.
+    pub fn non_cli_operator_roles(&self) -> HashMap<String, OperatorRole> {
+        self.non_cli_operator_roles.read().clone()
+    }
+
+    /// Resolve the approval role of `sender` on `channel`.
+    ///
+    /// The result is the highest role granted by a matching
+    /// `non_cli_operator_roles` entry or, for senders admitted by
+    /// `non_cli_approval_approvers`, `Admin`. With neither list configured
+    /// every sender is `Admin`, as before roles existed.
+    pub fn non_cli_operator_role(&self, channel: &str, sender: &str) -> Option<OperatorRole> {
+        let roles = self.non_cli_operator_roles.read();
+        let keys = [
+            "*".to_string(),
+            sender.to_string(),
+            format!("{channel}:{sender}"),
+            format!("{channel}:*"),
+            format!("*:{sender}"),
+        ];
+        let role = keys.iter().filter_map(|key| roles.get(key)).max().copied();
+
+        let is_approver = if self.non_cli_approval_approvers.read().is_empty() {
+            roles.is_empty()
+        } else {
+            self.is_non_cli_approval_actor_allowed(channel, sender)
+        };
+        if is_approver {
+            Some(OperatorRole::Admin)
+        } else {
+            role
+        }
+    }
+
     /// Apply runtime + persisted approval grant semantics:
     /// add to auto_approve and remove from always_ask.
     pub fn apply_persistent_runtime_grant(&self, tool_name: &str) {
@@ -393,6 +448,11 @@ impl ApprovalManager {
         auto.remove(tool_name)
     }
 
+    /// Replace non-CLI operator roles from config hot-reload.
+    pub fn replace_non_cli_operator_roles(&self, roles: &HashMap<String, OperatorRole>) {
+        *self.non_cli_operator_roles.write() = Self::normalize_non_cli_operator_roles(roles);
+    }
+
     /// Replace runtime-persistent non-CLI policy from config hot-reload.
     ///
     /// This updates the effective policy sets used by non-CLI approval commands
@@ -983,6 +1043,18 @@ mod tests {
         assert!(!mgr.needs_approval("anything"));
     }
 
+    #[test]
+    fn autonomous_skips_tool_prompts_but_keeps_command_rules() {
+        let mut config = shell_auto_approve_with_command_rule_approval();
+        config.level = AutonomyLevel::Autonomous;
+        let mgr = ApprovalManager::from_config(&config);
+        assert!(!mgr.needs_approval("file_write"));
+        assert!(!mgr.needs_approval_for_call("shell", &serde_json::json!({"command": "ls"})));
+        assert!(
+            mgr.needs_approval_for_call("shell", &serde_json::json!({"command": "rm -f tmp.txt"}))
+        );
+    }
+
     #[test]
     fn readonly_never_prompts() {
         let config = AutonomyConfig {
@@ -1335,6 +1407,45 @@ mod tests {
             .needs_approval_for_call("shell", &serde_json::json!({"command": "rm -f notes.txt"})));
     }
 
+    #[test]
+    fn non_cli_operator_roles_resolve_highest_matching_role() {
+        let mgr = ApprovalManager::from_config(&supervised_config());
+        assert_eq!(
+            mgr.non_cli_operator_role("telegram", "anyone"),
+            Some(OperatorRole::Admin)
+        );
+
+        let mut roles = HashMap::new();
+        roles.insert("telegram:*".to_string(), OperatorRole::Viewer);
+        roles.insert(" bob ".to_string(), OperatorRole::Reviewer);
+        mgr.replace_non_cli_operator_roles(&roles);
+
+        assert_eq!(
+            mgr.non_cli_operator_role("telegram", "bob"),
+            Some(OperatorRole::Reviewer)
+        );
+        assert_eq!(
+            mgr.non_cli_operator_role("telegram", "carol"),
+            Some(OperatorRole::Viewer)
+        );
+        assert_eq!(mgr.non_cli_operator_role("discord", "carol"), None);
+
+        let cfg = AutonomyConfig {
+            non_cli_approval_approvers: vec!["alice".to_string()],
+            non_cli_operator_roles: roles,
+            ..supervised_config()
+        };
+        let mgr = ApprovalManager::from_config(&cfg);
+        assert_eq!(
+            mgr.non_cli_operator_role("telegram", "alice"),
+            Some(OperatorRole::Admin)
+        );
+        assert_eq!(
+            mgr.non_cli_operator_role("discord", "bob"),
+            Some(OperatorRole::Reviewer)
+        );
+    }
+
     // ── audit log ────────────────────────────────────────────
 
     #[test]
@@ -2,6 +2,7 @@ use anyhow::{bail, Context, Result};
 use serde::Deserialize;
 use tracing_subscriber::EnvFilter;
 use zeroclaw::config::schema::McpServerConfig;
+use zeroclaw::security::SecurityPolicy;
 
 #[derive(Default, Deserialize)]
 struct FileMcp {
@@ -23,7 +24,8 @@ async fn main() -> Result<()> {
         .with_env_filter(EnvFilter::from_default_env())
         .init();
 
-    let (enabled, servers) = match std::fs::read_to_string("config.toml") {
+    // A bare ./config.toml only supplies [mcp]; the default policy applies.
+    let (enabled, servers, security) = match std::fs::read_to_string("config.toml") {
         Ok(s) => {
             let start = s
                 .lines()
@@ -31,11 +33,16 @@ async fn main() -> Result<()> {
                 .unwrap_or(0);
             let slice = s.lines().skip(start).collect::<Vec<_>>().join("\n");
             let root: FileRoot = toml::from_str(&slice).context("failed to parse ./config.toml")?;
-            (root.mcp.enabled, root.mcp.servers)
+            (
+                root.mcp.enabled,
+                root.mcp.servers,
+                SecurityPolicy::default(),
+            )
         }
         Err(_) => {
             let config = zeroclaw::Config::load_or_init().await?;
-            (config.mcp.enabled, config.mcp.servers)
+            let security = SecurityPolicy::from_root_config(&config);
+            (config.mcp.enabled, config.mcp.servers, security)
         }
     };
 
@@ -43,7 +50,7 @@ async fn main() -> Result<()> {
         bail!("MCP is disabled or no servers configured");
     }
 
-    let registry = zeroclaw::tools::McpRegistry::connect_all(&servers).await?;
+    let registry = zeroclaw::tools::McpRegistry::connect_all(&servers, &security).await?;
     let tool_count = registry.tool_names().len();
     tracing::info!(
         "MCP smoke ok: {} server(s), {} tool(s)",
@@ -83,7 +83,7 @@ use crate::agent::loop_::{
 };
 use crate::agent::session::{resolve_session_id, shared_session_manager, Session, SessionManager};
 use crate::approval::{ApprovalManager, ApprovalResponse, PendingApprovalError};
-use crate::config::{Config, NonCliNaturalLanguageApprovalMode, ProgressMode};
+use crate::config::{Config, NonCliNaturalLanguageApprovalMode, OperatorRole, ProgressMode};
 use crate::identity;
 use crate::memory::{self, Memory};
 use crate::observability::{self, runtime_trace, Observer};
@@ -302,6 +302,7 @@ struct RuntimeAutonomyPolicy {
     command_context_rules: Vec<crate::config::CommandContextRuleConfig>,
     non_cli_excluded_tools: Vec<String>,
     non_cli_approval_approvers: Vec<String>,
+    non_cli_operator_roles: HashMap<String, OperatorRole>,
     non_cli_natural_language_approval_mode: NonCliNaturalLanguageApprovalMode,
     non_cli_natural_language_approval_mode_by_channel:
         HashMap<String, NonCliNaturalLanguageApprovalMode>,
@@ -1087,6 +1088,30 @@ fn is_approval_management_command(command: &ChannelRuntimeCommand) -> bool {
     )
 }
 
+/// Lowest operator role allowed to run an approval-management command.
+///
+/// Viewers can inspect approvals, reviewers can decide individual pending
+/// requests, and only admins can change which tools are approved.
+fn required_operator_role(command: &ChannelRuntimeCommand) -> OperatorRole {
+    match command {
+        ChannelRuntimeCommand::ListPendingApprovals | ChannelRuntimeCommand::ListApprovals => {
+            OperatorRole::Viewer
+        }
+        ChannelRuntimeCommand::ApprovePendingRequest(_)
+        | ChannelRuntimeCommand::DenyToolApproval(_) => OperatorRole::Reviewer,
+        _ => OperatorRole::Admin,
+    }
+}
+
+fn operator_role_label(role: Option<OperatorRole>) -> &'static str {
+    match role {
+        Some(OperatorRole::Admin) => "admin",
+        Some(OperatorRole::Reviewer) => "reviewer",
+        Some(OperatorRole::Viewer) => "viewer",
+        None => "none",
+    }
+}
+
 fn non_cli_natural_language_mode_label(mode: NonCliNaturalLanguageApprovalMode) -> &'static str {
     match mode {
         NonCliNaturalLanguageApprovalMode::Disabled => "disabled",
@@ -1173,6 +1198,7 @@ fn runtime_autonomy_policy_from_config(config: &Config) -> RuntimeAutonomyPolicy
         command_context_rules: config.autonomy.command_context_rules.clone(),
         non_cli_excluded_tools: config.autonomy.non_cli_excluded_tools.clone(),
         non_cli_approval_approvers: config.autonomy.non_cli_approval_approvers.clone(),
+        non_cli_operator_roles: config.autonomy.non_cli_operator_roles.clone(),
         non_cli_natural_language_approval_mode: config
             .autonomy
             .non_cli_natural_language_approval_mode,
@@ -1684,6 +1710,20 @@ async fn describe_non_cli_approvals(
             approval_approvers.join(", ")
         );
     }
+    let mut operator_roles = ctx
+        .approval_manager
+        .non_cli_operator_roles()
+        .into_iter()
+        .map(|(entry, role)| format!("{entry}={}", operator_role_label(Some(role))))
+        .collect::<Vec<_>>();
+    if !operator_roles.is_empty() {
+        operator_roles.sort();
+        let _ = writeln!(
+            response,
+            "- Runtime non_cli_operator_roles: {}",
+            operator_roles.join(", ")
+        );
+    }
 
     let default_mode = non_cli_natural_language_mode_label(
         ctx.approval_manager
@@ -1880,6 +1920,8 @@ async fn maybe_apply_runtime_config_update(ctx: &ChannelRuntimeContext) -> Resul
         next_autonomy_policy.non_cli_natural_language_approval_mode,
         &next_autonomy_policy.non_cli_natural_language_approval_mode_by_channel,
     );
+    ctx.approval_manager
+        .replace_non_cli_operator_roles(&next_autonomy_policy.non_cli_operator_roles);
     {
         let mut excluded = ctx
             .non_cli_excluded_tools
@@ -1975,6 +2017,8 @@ fn clear_sender_history(ctx: &ChannelRuntimeContext, sender_key: &str) {
         .lock()
         .unwrap_or_else(|e| e.into_inner())
         .remove(sender_key);
+    // The history key is the tool session id, so the scratchpad ends with it.
+    crate::tools::scratchpad::Scratchpads::shared().clear_session(sender_key);
 }
 
 fn compact_sender_history(ctx: &ChannelRuntimeContext, sender_key: &str) -> bool {
@@ -2335,10 +2379,12 @@ async fn handle_runtime_command_if_needed(
     let is_natural_language_approval_command =
         !is_slash_command && is_approval_management_command(&command);
 
+    let required_role = required_operator_role(&command);
+    let sender_role = ctx
+        .approval_manager
+        .non_cli_operator_role(source_channel, sender);
     if is_approval_management_command(&command)
-        && !ctx
-            .approval_manager
-            .is_non_cli_approval_actor_allowed(source_channel, sender)
+        && sender_role.is_none_or(|role| role < required_role)
     {
         let mut approvers = ctx
             .approval_manager
@@ -2352,7 +2398,9 @@ async fn handle_runtime_command_if_needed(
             approvers.join(", ")
         };
         let response = format!(
-            "Approval-management command denied for sender `{sender}` on channel `{source_channel}`.\nAllowed approvers: {allowed}\nConfigure `[autonomy].non_cli_approval_approvers` to adjust this policy."
+            "Approval-management command denied for sender `{sender}` on channel `{source_channel}`.\nSender role: {}; required role: {}\nAllowed approvers: {allowed}\nConfigure `[autonomy].non_cli_approval_approvers` or `[autonomy].non_cli_operator_roles` to adjust this policy.",
+            operator_role_label(sender_role),
+            operator_role_label(Some(required_role)),
         );
         runtime_trace::record_event(
             "approval_management_denied",
@@ -2366,6 +2414,8 @@ async fn handle_runtime_command_if_needed(
                 "sender": sender,
                 "channel": source_channel,
                 "allowed_approvers": approvers,
+                "sender_role": operator_role_label(sender_role),
+                "required_role": operator_role_label(Some(required_role)),
             }),
         );
 
@@ -4087,29 +4137,35 @@ If this input is legitimate, rephrase the request and avoid instruction-override
         () = cancellation_token.cancelled() => LlmExecutionResult::Cancelled,
         result = tokio::time::timeout(
             Duration::from_secs(timeout_budget_secs),
-            crate::agent::loop_::scope_cost_enforcement_context(
-                cost_enforcement_context,
-                run_tool_call_loop_with_non_cli_approval_context(
-                    active_provider.as_ref(),
-                    &mut history,
-                    ctx.tools_registry.as_ref(),
-                    ctx.observer.as_ref(),
-                    route.provider.as_str(),
-                    route.model.as_str(),
-                    runtime_defaults.temperature,
-                    true,
-                    Some(ctx.approval_manager.as_ref()),
-                    msg.channel.as_str(),
-                    non_cli_approval_context,
-                    &runtime_defaults.multimodal,
-                    runtime_defaults.max_tool_iterations,
-                    Some(cancellation_token.clone()),
-                    delta_tx,
-                    ctx.hooks.as_deref(),
-                    &excluded_tools_snapshot,
-                    progress_mode,
-                    ctx.safety_heartbeat.clone(),
-                    runtime_canary_tokens_snapshot(ctx.as_ref()),
+            crate::agent::loop_::scope_kill_switch(
+                Some(ctx.workspace_dir.as_ref().clone()),
+                crate::agent::loop_::scope_cost_enforcement_context(
+                    cost_enforcement_context,
+                    crate::agent::loop_::scope_session_id(
+                        Some(history_key.clone()),
+                        run_tool_call_loop_with_non_cli_approval_context(
+                            active_provider.as_ref(),
+                            &mut history,
+                            ctx.tools_registry.as_ref(),
+                            ctx.observer.as_ref(),
+                            route.provider.as_str(),
+                            route.model.as_str(),
+                            runtime_defaults.temperature,
+                            true,
+                            Some(ctx.approval_manager.as_ref()),
+                            msg.channel.as_str(),
+                            non_cli_approval_context,
+                            &runtime_defaults.multimodal,
+                            runtime_defaults.max_tool_iterations,
+                            Some(cancellation_token.clone()),
+                            delta_tx,
+                            ctx.hooks.as_deref(),
+                            &excluded_tools_snapshot,
+                            progress_mode,
+                            ctx.safety_heartbeat.clone(),
+                            runtime_canary_tokens_snapshot(ctx.as_ref()),
+                        ),
+                    ),
                 ),
             ),
         ) => LlmExecutionResult::Completed(result),
@@ -4970,48 +5026,20 @@ pub fn build_system_prompt_with_mode(
 }
 
 /// Inject a single workspace file into the prompt with truncation and missing-file markers.
+///
+/// Rendered layers are cached and revalidated by mtime, so repeated prompt
+/// builds do not re-read unchanged workspace files.
 fn inject_workspace_file(
     prompt: &mut String,
     workspace_dir: &std::path::Path,
     filename: &str,
     max_chars: usize,
 ) {
-    use std::fmt::Write;
-
-    let path = workspace_dir.join(filename);
-    match std::fs::read_to_string(&path) {
-        Ok(content) => {
-            let trimmed = content.trim();
-            if trimmed.is_empty() {
-                return;
-            }
-            let _ = writeln!(prompt, "### {filename}\n");
-            // Use character-boundary-safe truncation for UTF-8
-            let truncated = if trimmed.chars().count() > max_chars {
-                trimmed
-                    .char_indices()
-                    .nth(max_chars)
-                    .map(|(idx, _)| &trimmed[..idx])
-                    .unwrap_or(trimmed)
-            } else {
-                trimmed
-            };
-            if truncated.len() < trimmed.len() {
-                prompt.push_str(truncated);
-                let _ = writeln!(
-                    prompt,
-                    "\n\n[... truncated at {max_chars} chars — use `read` for full file]\n"
-                );
-            } else {
-                prompt.push_str(trimmed);
-                prompt.push_str("\n\n");
-            }
-        }
-        Err(_) => {
-            // Missing-file marker (matches OpenClaw behavior)
-            let _ = writeln!(prompt, "### {filename}\n\n[File not found: {filename}]\n");
-        }
-    }
+    prompt.push_str(&crate::agent::prompt_cache::render_workspace_file(
+        workspace_dir,
+        filename,
+        max_chars,
+    ));
 }
 
 fn normalize_telegram_identity(value: &str) -> String {
@@ -5763,17 +5791,17 @@ pub async fn start_channels(config: Config) -> Result<()> {
         config.api_key.as_deref(),
     );
 
-    let base_observer: Arc<dyn Observer> =
-        Arc::from(observability::create_observer(&config.observability));
+    let security = Arc::new(SecurityPolicy::from_root_config(&config));
+    let base_observer: Arc<dyn Observer> = Arc::from(observability::with_security_budget(
+        observability::create_observer(&config.observability),
+        &security,
+        &config.cost,
+    ));
     let observer: Arc<dyn Observer> = Arc::new(
         crate::plugins::bridge::observer::ObserverBridge::new(base_observer),
     );
     let runtime: Arc<dyn runtime::RuntimeAdapter> =
         Arc::from(runtime::create_runtime(&config.runtime)?);
-    let security = Arc::new(SecurityPolicy::from_config(
-        &config.autonomy,
-        &config.workspace_dir,
-    ));
     let temperature = config.default_temperature;
     let mem: Arc<dyn Memory> = Arc::from(memory::create_memory_with_storage(
         &config.memory,
@@ -5813,7 +5841,7 @@ pub async fn start_channels(config: Config) -> Result<()> {
             "Initializing MCP client — {} server(s) configured",
             config.mcp.servers.len()
         );
-        match crate::tools::McpRegistry::connect_all(&config.mcp.servers).await {
+        match crate::tools::McpRegistry::connect_all(&config.mcp.servers, &security).await {
             Ok(registry) => {
                 let registry = std::sync::Arc::new(registry);
                 let names = registry.tool_names();
@@ -5872,10 +5900,34 @@ pub async fn start_channels(config: Config) -> Result<()> {
             "memory_recall",
             "Search memory to retrieve prior decisions, user preferences, and historical context.",
         ),
+        (
+            "memory_search",
+            "Filter memory by keywords, #tags, category, or date range.",
+        ),
         (
             "memory_forget",
             "Delete a memory entry when it's incorrect, stale, or explicitly requested for removal.",
         ),
+        (
+            "redact_memory",
+            "Replace a memory entry's content with [REDACTED] when it holds a secret; logged to an audit file.",
+        ),
+        (
+            "consolidate_memory",
+            "Summarize recent short-term memories (daily notes, observations) into a core memory entry that lists its sources.",
+        ),
+        (
+            "digest_memory",
+            "Condense the past 24 hours of memory notes into one digest entry tagged #digest.",
+        ),
+        (
+            "scratchpad",
+            "Keep temporary working notes for this conversation; cleared when the conversation resets and never saved to memory.",
+        ),
+        (
+            "request_escalation",
+            "Ask the operator for a temporarily higher autonomy level or extra rate budget when policy blocks a needed step; nothing changes until they grant it.",
+        ),
     ];
 
     if config.browser.enabled {
@@ -5949,6 +6001,12 @@ pub async fn start_channels(config: Config) -> Result<()> {
         system_prompt.push_str(&build_tool_instructions_from_specs(&filtered_specs));
     }
     system_prompt.push_str(&build_shell_policy_instructions(&config.autonomy));
+    system_prompt.push_str(
+        &crate::agent::memory_loader::PinnedMemoryLoader::default()
+            .load(mem.as_ref())
+            .await
+            .unwrap_or_default(),
+    );
 
     if !skills.is_empty() {
         println!(
@@ -6265,6 +6323,21 @@ mod tests {
         assert_eq!(parse_runtime_command("slack", "/models"), None);
     }
 
+    #[test]
+    fn approval_commands_require_role_matching_their_effect() {
+        let required = |content: &str| {
+            required_operator_role(&parse_runtime_command("slack", content).unwrap())
+        };
+        assert_eq!(required("/approvals"), OperatorRole::Viewer);
+        assert_eq!(required("/approve-pending"), OperatorRole::Viewer);
+        assert_eq!(required("/approve-allow apr-1"), OperatorRole::Reviewer);
+        assert_eq!(required("/approve-deny apr-1"), OperatorRole::Reviewer);
+        assert_eq!(required("/approve shell"), OperatorRole::Admin);
+        assert_eq!(required("/approve-confirm apr-1"), OperatorRole::Admin);
+        assert_eq!(required("/approve-all-once"), OperatorRole::Admin);
+        assert_eq!(required("/unapprove shell"), OperatorRole::Admin);
+    }
+
     #[test]
     fn parse_runtime_command_supports_natural_language_approval_intents() {
         assert_eq!(
@@ -11,23 +11,27 @@ pub use schema::{
     AgentSessionBackend, AgentSessionConfig, AgentSessionStrategy, AgentTeamsConfig,
     AgentsIpcConfig, AuditConfig, AutonomyConfig, BrowserComputerUseConfig, BrowserConfig,
     BuiltinHooksConfig, ChannelsConfig, ClassificationRule, CommandContextRuleAction,
-    CommandContextRuleConfig, ComposioConfig, Config, CoordinationConfig, CostConfig, CronConfig,
+    CommandContextRuleConfig, CommandPolicyConfig, ComposioConfig, Config, ContentScanAction,
+    ContentScanRuleConfig, ContentScannerConfig, CoordinationConfig, CostConfig, CronConfig,
     DelegateAgentConfig, DiscordConfig, DockerRuntimeConfig, EconomicConfig, EconomicTokenPricing,
     EmbeddingRouteConfig, EstopConfig, FeishuConfig, GatewayConfig, GroupReplyConfig,
-    GroupReplyMode, HardwareConfig, HardwareTransport, HeartbeatConfig, HooksConfig,
-    HttpRequestConfig, HttpRequestCredentialProfile, IMessageConfig, IdentityConfig, LarkConfig,
-    MatrixConfig, MemoryConfig, ModelRouteConfig, MultimodalConfig, NextcloudTalkConfig,
-    NonCliNaturalLanguageApprovalMode, ObservabilityConfig, OtpChallengeDelivery, OtpConfig,
-    OtpMethod, OutboundLeakGuardAction, OutboundLeakGuardConfig, PeripheralBoardConfig,
-    PeripheralsConfig, PerplexityFilterConfig, PluginEntryConfig, PluginsConfig, ProgressMode,
-    ProviderConfig, ProxyConfig, ProxyScope, QdrantConfig, QueryClassificationConfig,
-    ReliabilityConfig, ResearchPhaseConfig, ResearchTrigger, ResourceLimitsConfig, RuntimeConfig,
-    SandboxBackend, SandboxConfig, SchedulerConfig, SecretsConfig, SecurityConfig,
-    SecurityRoleConfig, SkillsConfig, SkillsPromptInjectionMode, SlackConfig, StorageConfig,
-    StorageProviderConfig, StorageProviderSection, StreamMode, SubAgentsConfig,
-    SyscallAnomalyConfig, TelegramConfig, TranscriptionConfig, TunnelConfig, UrlAccessConfig,
-    WasmCapabilityEscalationMode, WasmConfig, WasmModuleHashPolicy, WasmRuntimeConfig,
-    WasmSecurityConfig, WebFetchConfig, WebSearchConfig, WebhookConfig, DEFAULT_MODEL_FALLBACK,
+    GroupReplyMode, HardwareConfig, HardwareTransport, HeartbeatConfig, HomeAssistantConfig,
+    HooksConfig, HttpRequestConfig, HttpRequestCredentialProfile, IMessageConfig, IdentityConfig,
+    LarkConfig, MatrixConfig, MemoryConfig, MemoryDigestConfig, MemoryGitSyncConfig,
+    MemorySearchRankingConfig, ModelRouteConfig, MultimodalConfig, NextcloudTalkConfig,
+    NonCliNaturalLanguageApprovalMode, ObservabilityConfig, OperatingWindowConfig, OperatorRole,
+    OtpChallengeDelivery, OtpConfig, OtpMethod, OutboundLeakGuardAction, OutboundLeakGuardConfig,
+    PathPermission, PeripheralBoardConfig, PeripheralsConfig, PerplexityFilterConfig,
+    PluginEntryConfig, PluginsConfig, ProgressMode, ProviderConfig, ProxyConfig, ProxyScope,
+    QdrantConfig, QueryClassificationConfig, ReliabilityConfig, ResearchPhaseConfig,
+    ResearchTrigger, ResourceLimitsConfig, RuntimeConfig, SandboxBackend, SandboxConfig,
+    SchedulerConfig, SecretsConfig, SecurityConfig, SecurityEventKind, SecurityRoleConfig,
+    SecurityWebhookConfig, SecurityWebhookFormat, SkillsConfig, SkillsPromptInjectionMode,
+    SlackConfig, StorageConfig, StorageProviderConfig, StorageProviderSection, StreamMode,
+    SubAgentsConfig, SyscallAnomalyConfig, TagSuggestionRule, TelegramConfig, TokioRuntimeConfig,
+    TranscriptionConfig, TunnelConfig, UrlAccessConfig, WasmCapabilityEscalationMode, WasmConfig,
+    WasmModuleHashPolicy, WasmRuntimeConfig, WasmSecurityConfig, WebFetchConfig, WebSearchConfig,
+    WebhookConfig, DEFAULT_MODEL_FALLBACK,
 };
 
 pub fn name_and_presence<T: traits::ChannelConfig>(channel: Option<&T>) -> (&'static str, bool) {
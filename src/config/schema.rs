@@ -145,12 +145,14 @@ const SUPPORTED_PROXY_SERVICE_KEYS: &[&str] = &[
     "channel.whatsapp",
     "tool.browser",
     "tool.composio",
+    "tool.home_assistant",
     "tool.http_request",
     "tool.multimodal",
     "tool.pushover",
     "memory.embeddings",
     "tunnel.custom",
     "transcription.groq",
+    "security.webhook",
 ];
 
 const SUPPORTED_PROXY_SERVICE_SELECTORS: &[&str] = &[
@@ -160,6 +162,7 @@ const SUPPORTED_PROXY_SERVICE_SELECTORS: &[&str] = &[
     "memory.*",
     "tunnel.*",
     "transcription.*",
+    "security.*",
 ];
 
 static RUNTIME_PROXY_CONFIG: OnceLock<RwLock<ProxyConfig>> = OnceLock::new();
@@ -205,6 +208,10 @@ pub struct Config {
     /// Path to config.toml - computed from home, not serialized
     #[serde(skip)]
     pub config_path: PathBuf,
+    /// Secret references resolved at load, by field path; `save` writes them
+    /// back in place of the resolved values.
+    #[serde(skip)]
+    pub secret_refs: HashMap<String, ResolvedSecret>,
     /// API key for the selected provider. Always overridden by `ZEROCLAW_API_KEY` env var.
     /// `API_KEY` env var is only used as fallback when no config key is set.
     pub api_key: Option<String>,
@@ -336,6 +343,10 @@ pub struct Config {
     #[serde(default)]
     pub web_search: WebSearchConfig,
 
+    /// Home Assistant tool configuration (`[home_assistant]`).
+    #[serde(default)]
+    pub home_assistant: HomeAssistantConfig,
+
     /// Proxy configuration for outbound HTTP/HTTPS/SOCKS5 traffic (`[proxy]`).
     #[serde(default)]
     pub proxy: ProxyConfig,
@@ -1437,6 +1448,17 @@ pub struct CostConfig {
     /// Runtime budget enforcement policy (`[cost.enforcement]`).
     #[serde(default)]
     pub enforcement: CostEnforcementConfig,
+
+    /// Usage records buffered in memory before they are written in one batch.
+    ///
+    /// `1` (default) writes and fsyncs every record immediately. Budget checks
+    /// always see buffered records; only a hard crash can lose them.
+    #[serde(default = "default_cost_batch_size")]
+    pub batch_size: usize,
+
+    /// Maximum age of buffered usage records before a flush is forced, in milliseconds.
+    #[serde(default = "default_cost_flush_interval_ms")]
+    pub flush_interval_ms: u64,
 }
 
 /// Budget enforcement behavior when projected spend approaches/exceeds limits.
@@ -1511,6 +1533,14 @@ fn default_warn_percent() -> u8 {
     80
 }
 
+fn default_cost_batch_size() -> usize {
+    1
+}
+
+fn default_cost_flush_interval_ms() -> u64 {
+    1000
+}
+
 impl Default for CostConfig {
     fn default() -> Self {
         Self {
@@ -1521,6 +1551,8 @@ impl Default for CostConfig {
             allow_override: false,
             prices: get_default_pricing(),
             enforcement: CostEnforcementConfig::default(),
+            batch_size: default_cost_batch_size(),
+            flush_interval_ms: default_cost_flush_interval_ms(),
         }
     }
 }
@@ -1889,16 +1921,31 @@ impl Default for ComposioConfig {
 // ── Secrets (encrypted credential store) ────────────────────────
 
 /// Secrets encryption configuration (`[secrets]` section).
+///
+/// Secret fields may also hold `${keychain:<name>}`, `${env:<VAR>}`, or
+/// `${secretfile:<name>}` references; see
+/// [`crate::security::secrets_provider`].
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct SecretsConfig {
     /// Enable encryption for API keys and tokens in config.toml
     #[serde(default = "default_true")]
     pub encrypt: bool,
+    /// Service name `${keychain:...}` references are stored under in the OS
+    /// keychain
+    #[serde(default = "default_keychain_service")]
+    pub keychain_service: String,
+}
+
+fn default_keychain_service() -> String {
+    "zeroclaw".into()
 }
 
 impl Default for SecretsConfig {
     fn default() -> Self {
-        Self { encrypt: true }
+        Self {
+            encrypt: true,
+            keychain_service: default_keychain_service(),
+        }
     }
 }
 
@@ -2129,6 +2176,52 @@ fn default_http_timeout_secs() -> u64 {
     30
 }
 
+// ── Home Assistant ───────────────────────────────────────────────
+
+/// Home Assistant tool configuration (`[home_assistant]` section).
+///
+/// Entity states are readable once enabled. Service calls are deny-by-default:
+/// only entities matched by `allowed_entities` can be targeted.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct HomeAssistantConfig {
+    /// Enable the `home_assistant` tool
+    #[serde(default)]
+    pub enabled: bool,
+    /// Base URL of the Home Assistant instance
+    #[serde(default = "default_home_assistant_url")]
+    pub url: String,
+    /// Long-lived access token (falls back to the `HOME_ASSISTANT_TOKEN` env var)
+    #[serde(default)]
+    pub token: Option<String>,
+    /// Entities service calls may target: exact IDs (`light.kitchen`),
+    /// domain wildcards (`light.*`), or `*` for all
+    #[serde(default)]
+    pub allowed_entities: Vec<String>,
+    /// Request timeout in seconds (default: 15)
+    #[serde(default = "default_home_assistant_timeout_secs")]
+    pub timeout_secs: u64,
+}
+
+impl Default for HomeAssistantConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            url: default_home_assistant_url(),
+            token: None,
+            allowed_entities: vec![],
+            timeout_secs: default_home_assistant_timeout_secs(),
+        }
+    }
+}
+
+fn default_home_assistant_url() -> String {
+    "http://homeassistant.local:8123".into()
+}
+
+fn default_home_assistant_timeout_secs() -> u64 {
+    15
+}
+
 // ── Web fetch ────────────────────────────────────────────────────
 
 /// Web fetch tool configuration (`[web_fetch]` section).
@@ -3092,6 +3185,61 @@ pub struct MemoryConfig {
     #[serde(default = "default_sqlite_journal_mode")]
     pub sqlite_journal_mode: String,
 
+    // ── Markdown backend options ───────────────────────────────
+    /// For markdown backend: rotate MEMORY.md into `memory/core/` once it
+    /// reaches this many bytes, keeping the prompt-injected file small.
+    /// Rotated files remain searchable. `0` disables rotation (default).
+    #[serde(default)]
+    pub markdown_rotate_bytes: u64,
+
+    // ── Encryption at rest ─────────────────────────────────────
+    /// Encrypt memory entry content (ChaCha20-Poly1305) before it reaches the
+    /// backend. Keys and categories stay readable; recall falls back to
+    /// in-process keyword matching because backend search cannot see plaintext.
+    #[serde(default)]
+    pub encrypt_at_rest: bool,
+    /// Key file for `encrypt_at_rest`. Created on first use if missing.
+    /// Defaults to a dedicated memory key in the policy state directory
+    /// (`~/.zeroclaw/state/memory.key`). Tools may not read it.
+    #[serde(default)]
+    pub encryption_key_file: Option<String>,
+    /// Environment variable holding a passphrase to derive the
+    /// `encrypt_at_rest` key from (PBKDF2-HMAC-SHA256) instead of keeping
+    /// the key in a file. The salt is stored next to `encryption_key_file`.
+    #[serde(default)]
+    pub encryption_passphrase_env: Option<String>,
+
+    // ── Tag suggestion ─────────────────────────────────────────
+    /// Keyword rules that suggest tags when `memory_store` is called without
+    /// any. Suggestions are recorded apart from author tags, on a
+    /// `Suggested-Tags:` line. Empty disables suggestion (default).
+    ///
+    /// Example:
+    /// ```toml
+    /// [[memory.tag_suggestions]]
+    /// tag = "decision"
+    /// keywords = ["decided", "we will", "agreed"]
+    /// ```
+    #[serde(default)]
+    pub tag_suggestions: Vec<TagSuggestionRule>,
+
+    // ── Search ranking ─────────────────────────────────────────
+    /// How `memory_search` orders its hits (`[memory.search_ranking]`).
+    #[serde(default)]
+    pub search_ranking: MemorySearchRankingConfig,
+
+    // ── Git sync ───────────────────────────────────────────────
+    /// Periodically commit a JSON export of memory to a git repository and
+    /// push it to a remote for off-machine backup (`[memory.git_sync]`).
+    #[serde(default)]
+    pub git_sync: MemoryGitSyncConfig,
+
+    // ── Daily digest ───────────────────────────────────────────
+    /// Daily cron job condensing the past day's notes into one digest entry
+    /// (`[memory.digest]`).
+    #[serde(default)]
+    pub digest: MemoryDigestConfig,
+
     // ── Qdrant backend options ─────────────────────────────────
     /// Configuration for Qdrant vector database backend.
     /// Used when `backend = "qdrant"` or `backend = "sqlite_qdrant_hybrid"`.
@@ -3099,6 +3247,137 @@ pub struct MemoryConfig {
     pub qdrant: QdrantConfig,
 }
 
+/// Memory backup to a git repository (`[memory.git_sync]`).
+///
+/// The daemon exports every entry to `state/memory-sync/memory.json` next to
+/// the config file (a git repository it creates) and commits when enough
+/// entries changed or the interval passed. Pushing to `remote` also requires
+/// `[autonomy] allow_memory_push = true`. Unavailable while
+/// `[memory] encrypt_at_rest` is on.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct MemoryGitSyncConfig {
+    /// Enable the memory sync worker. Default: `false`.
+    #[serde(default)]
+    pub enabled: bool,
+    /// Git remote URL to push to. Unset: commit locally only.
+    #[serde(default)]
+    pub remote: Option<String>,
+    /// Branch to commit and push. Default: `"main"`.
+    #[serde(default = "default_memory_sync_branch")]
+    pub branch: String,
+    /// Sync pending changes at least this often, in minutes (0 = never on a
+    /// timer). Default: `60`.
+    #[serde(default = "default_memory_sync_interval_minutes")]
+    pub interval_minutes: u32,
+    /// Sync as soon as this many entries changed (0 = only on the timer).
+    /// Default: `20`.
+    #[serde(default = "default_memory_sync_after_changes")]
+    pub after_changes: u32,
+}
+
+fn default_memory_sync_branch() -> String {
+    "main".into()
+}
+
+fn default_memory_sync_interval_minutes() -> u32 {
+    60
+}
+
+fn default_memory_sync_after_changes() -> u32 {
+    20
+}
+
+impl Default for MemoryGitSyncConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            remote: None,
+            branch: default_memory_sync_branch(),
+            interval_minutes: default_memory_sync_interval_minutes(),
+            after_changes: default_memory_sync_after_changes(),
+        }
+    }
+}
+
+/// Daily memory digest (`[memory.digest]`).
+///
+/// When enabled, the scheduler registers a cron agent job on startup that
+/// runs the `digest_memory` tool over the past 24 hours of notes and stores
+/// one entry tagged `digest`. Disabling it removes the job again.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct MemoryDigestConfig {
+    /// Register the digest job. Default: `false`.
+    #[serde(default)]
+    pub enabled: bool,
+    /// Cron expression for the job. Default: `"30 23 * * *"` (11:30 PM).
+    #[serde(default = "default_memory_digest_schedule")]
+    pub schedule: String,
+    /// IANA timezone for `schedule`. Unset: local time.
+    #[serde(default)]
+    pub tz: Option<String>,
+}
+
+fn default_memory_digest_schedule() -> String {
+    "30 23 * * *".into()
+}
+
+impl Default for MemoryDigestConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            schedule: default_memory_digest_schedule(),
+            tz: None,
+        }
+    }
+}
+
+/// Ranking of `memory_search` results (`[memory.search_ranking]`).
+///
+/// Each hit scores `2^(-age_days / recency_half_life_days) * (1 + sum of
+/// its tag weights)`, and results are returned highest score first. With no
+/// tag weights this is newest first.
+///
+/// Example:
+/// ```toml
+/// [memory.search_ranking]
+/// recency_half_life_days = 14
+/// tag_weights = { decision = 1.0, observation = -0.5 }
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct MemorySearchRankingConfig {
+    /// Age in days at which a hit's recency factor halves. Default: `30`.
+    #[serde(default = "default_search_recency_half_life_days")]
+    pub recency_half_life_days: f64,
+    /// Score boost per tag, without `#`. Tags on the `Suggested-Tags:` line
+    /// count too. Negative weights demote; the multiplier never drops below
+    /// zero. Default: empty.
+    #[serde(default)]
+    pub tag_weights: HashMap<String, f64>,
+}
+
+fn default_search_recency_half_life_days() -> f64 {
+    30.0
+}
+
+impl Default for MemorySearchRankingConfig {
+    fn default() -> Self {
+        Self {
+            recency_half_life_days: default_search_recency_half_life_days(),
+            tag_weights: HashMap::new(),
+        }
+    }
+}
+
+/// Suggest `tag` for an untagged memory note that mentions any of `keywords`.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, JsonSchema)]
+pub struct TagSuggestionRule {
+    /// Tag to suggest, without `#`.
+    pub tag: String,
+    /// Case-insensitive substring matches.
+    #[serde(default)]
+    pub keywords: Vec<String>,
+}
+
 fn default_sqlite_journal_mode() -> String {
     "wal".into()
 }
@@ -3171,6 +3450,14 @@ impl Default for MemoryConfig {
             auto_hydrate: true,
             sqlite_open_timeout_secs: None,
             sqlite_journal_mode: default_sqlite_journal_mode(),
+            markdown_rotate_bytes: 0,
+            encrypt_at_rest: false,
+            encryption_key_file: None,
+            encryption_passphrase_env: None,
+            tag_suggestions: Vec::new(),
+            search_ranking: MemorySearchRankingConfig::default(),
+            git_sync: MemoryGitSyncConfig::default(),
+            digest: MemoryDigestConfig::default(),
             qdrant: QdrantConfig::default(),
         }
     }
@@ -3367,6 +3654,37 @@ pub enum NonCliNaturalLanguageApprovalMode {
     Direct,
 }
 
+/// Approval rights of a named non-CLI operator.
+///
+/// Roles are ordered: each one includes the rights of the roles below it.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, JsonSchema,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum OperatorRole {
+    /// May list pending requests and current approvals.
+    Viewer,
+    /// May also approve or deny individual pending requests.
+    Reviewer,
+    /// May also grant and revoke persistent tool approvals.
+    Admin,
+}
+
+/// What file tools may do under a workspace path prefix
+/// (`autonomy.path_permissions`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum PathPermission {
+    /// Files may be read but not written.
+    Read,
+    /// Files may be read; changes must be proposed to an operator instead of
+    /// written.
+    #[serde(alias = "propose-only")]
+    ProposeOnly,
+    /// Files may be read and written.
+    Write,
+}
+
 /// Action to apply when a command-context rule matches.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "snake_case")]
@@ -3434,6 +3752,43 @@ pub struct CommandContextRuleConfig {
     pub allow_high_risk: bool,
 }
 
+/// Hours and days in which `Act` operations are allowed
+/// (`[autonomy.operating_window]`).
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct OperatingWindowConfig {
+    /// Start of the window, `HH:MM`.
+    pub start: String,
+    /// End of the window, `HH:MM` (exclusive). An end before `start` runs
+    /// past midnight, e.g. `22:00`-`06:00`.
+    pub end: String,
+    /// Days the window opens on (`mon` ... `sun`). Empty means every day.
+    #[serde(default)]
+    pub days: Vec<String>,
+    /// IANA time zone the window is expressed in, e.g. `Europe/Berlin`.
+    /// Defaults to the host's local time.
+    #[serde(default)]
+    pub timezone: Option<String>,
+}
+
+/// Structured limits on shell commands (`[autonomy.command_policy]`), on
+/// top of the `allowed_commands` binary allowlist.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct CommandPolicyConfig {
+    /// Flags refused per binary, e.g. `git = ["--force", "-f"]`. Flags under
+    /// `"*"` are refused for every binary.
+    #[serde(default)]
+    pub banned_flags: HashMap<String, Vec<String>>,
+    /// Longest a command may run before it is killed, in seconds. `0`
+    /// (default) keeps the shell tool's own limit.
+    #[serde(default)]
+    pub max_runtime_secs: u64,
+    /// Directories commands may run in (absolute, `~/...`, or
+    /// workspace-relative). Empty means anywhere file tools may read.
+    #[serde(default)]
+    pub working_dirs: Vec<String>,
+}
+
 /// Autonomy and security policy configuration (`[autonomy]` section).
 ///
 /// Controls what the agent is allowed to do: shell commands, filesystem access,
@@ -3456,8 +3811,43 @@ pub struct AutonomyConfig {
     pub command_context_rules: Vec<CommandContextRuleConfig>,
     /// Explicit path denylist. Default includes system-critical paths and sensitive dotdirs.
     pub forbidden_paths: Vec<String>,
-    /// Maximum actions allowed per hour per policy. Default: `100`.
+    /// Rate at which the action token bucket refills, in actions per hour.
+    /// Default: `100`.
     pub max_actions_per_hour: u32,
+    /// Capacity of the action token bucket: how many actions may run back to
+    /// back before the refill rate applies. `0` (default) means
+    /// `max_actions_per_hour`.
+    #[serde(default)]
+    pub max_actions_burst: u32,
+    /// Maximum actions a single conversation session may take over its
+    /// lifetime, on top of the hourly limit. `0` (default) means unlimited.
+    #[serde(default)]
+    pub max_actions_per_session: u32,
+    /// Maximum size of one tool call's JSON arguments in bytes; larger calls
+    /// are refused. `0` means unlimited.
+    #[serde(default = "default_max_tool_io_bytes")]
+    pub max_tool_input_bytes: usize,
+    /// Maximum size of one tool result in bytes; longer output is truncated
+    /// with a marker. `0` means unlimited.
+    #[serde(default = "default_max_tool_io_bytes")]
+    pub max_tool_output_bytes: usize,
+    /// Consecutive policy denials of one tool before further calls to it are
+    /// refused for a cooldown, so an agent retrying a blocked operation stops
+    /// burning tokens. `0` disables the cooldown. Default: `3`.
+    #[serde(default = "default_denial_cooldown_after")]
+    pub denial_cooldown_after: u32,
+    /// First cooldown in seconds. Each denial after a cooldown doubles the
+    /// next one, up to `denial_cooldown_max_secs`. Default: `30`.
+    #[serde(default = "default_denial_cooldown_secs")]
+    pub denial_cooldown_secs: u64,
+    /// Longest cooldown in seconds. Default: `900`.
+    #[serde(default = "default_denial_cooldown_max_secs")]
+    pub denial_cooldown_max_secs: u64,
+    /// Save the action bucket level to `state/ratelimit.json` next to the
+    /// config file whenever it changes, and reload it on startup, so
+    /// restarts do not refill the bucket. Default: `true`.
+    #[serde(default = "default_true")]
+    pub persist_rate_limit: bool,
     /// Maximum cost per day in cents per policy. Default: `1000`.
     pub max_cost_per_day_cents: u32,
 
@@ -3469,13 +3859,19 @@ pub struct AutonomyConfig {
     #[serde(default = "default_true")]
     pub block_high_risk_commands: bool,
 
-    /// Additional environment variables allowed for shell tool subprocesses.
+    /// Additional environment variables allowed for tool subprocesses.
     ///
     /// These names are explicitly allowlisted and merged with the built-in safe
     /// baseline (`PATH`, `HOME`, etc.) after `env_clear()`.
     #[serde(default)]
     pub shell_env_passthrough: Vec<String>,
 
+    /// Name suffixes of environment variables never passed to tool
+    /// subprocesses, even when listed in `shell_env_passthrough`.
+    /// Matching is case-insensitive.
+    #[serde(default = "default_env_secret_suffixes")]
+    pub env_secret_suffixes: Vec<String>,
+
     /// Allow `file_read` to access sensitive workspace secrets such as `.env`,
     /// key material, and credential files.
     ///
@@ -3490,6 +3886,78 @@ pub struct AutonomyConfig {
     #[serde(default)]
     pub allow_sensitive_file_writes: bool,
 
+    /// Maximum total bytes of memory content before `memory_store` and
+    /// `memory_observe` refuse new entries. `0` (default) means unlimited.
+    #[serde(default)]
+    pub max_memory_bytes: u64,
+
+    /// Maximum entries `memory_store` and `memory_observe` may write in any
+    /// 24-hour window. `0` (default) means unlimited.
+    #[serde(default)]
+    pub max_memory_entries_per_day: u32,
+
+    /// Maximum LLM tokens (input + output) the agent may use in any 24-hour
+    /// window. Once spent, tools are limited to read-only operations until
+    /// usage falls back under the budget. `0` (default) means unlimited.
+    #[serde(default)]
+    pub max_tokens_per_day: u64,
+
+    /// Maximum estimated LLM spend in USD, priced with `[cost.prices]`, in
+    /// any 24-hour window. Exceeding it degrades to read-only like
+    /// `max_tokens_per_day`. `0` (default) means unlimited.
+    #[serde(default)]
+    pub max_cost_usd_per_day: f64,
+
+    /// Allow `[memory.git_sync]` to push memory to its git remote. Off by
+    /// default: sync then only commits locally.
+    #[serde(default)]
+    pub allow_memory_push: bool,
+
+    /// Tools the security policy lets the agent run. When non-empty, any
+    /// tool not listed is refused at call time. `"*"` matches every tool.
+    #[serde(default)]
+    pub allowed_tools: Vec<String>,
+
+    /// Tools the security policy always refuses, even when `allowed_tools`
+    /// lists them.
+    #[serde(default)]
+    pub denied_tools: Vec<String>,
+
+    /// Hosts network tools may connect to. When non-empty, any other host is
+    /// refused. `"*.example.com"` matches subdomains; `"*"` matches every host.
+    #[serde(default)]
+    pub allowed_hosts: Vec<String>,
+
+    /// Hosts network tools may never connect to, even when `allowed_hosts`
+    /// lists them.
+    #[serde(default)]
+    pub denied_hosts: Vec<String>,
+
+    /// Hourly action caps for individual tools (e.g. `memory_store = 30`),
+    /// enforced in addition to `max_actions_per_hour`.
+    #[serde(default)]
+    pub per_tool_limits: HashMap<String, u32>,
+
+    /// Permissions for workspace-relative path prefixes (e.g.
+    /// `"docs/" = "read"`), enforced by every file tool that writes. The
+    /// longest matching prefix wins; paths no entry covers are writable.
+    #[serde(default)]
+    pub path_permissions: HashMap<String, PathPermission>,
+
+    /// Validate `Act` tool calls and report what they would do without
+    /// carrying them out. Default: `false`.
+    #[serde(default)]
+    pub dry_run: bool,
+
+    /// Operating hours for `Act` operations. Outside the window they are
+    /// refused; reads are unaffected. Unset (default) means always open.
+    #[serde(default)]
+    pub operating_window: Option<OperatingWindowConfig>,
+
+    /// Banned flags, runtime cap, and working directories for shell commands.
+    #[serde(default)]
+    pub command_policy: CommandPolicyConfig,
+
     /// Tools that never require approval (e.g. read-only tools).
     #[serde(default = "default_auto_approve")]
     pub auto_approve: Vec<String>,
@@ -3525,6 +3993,18 @@ pub struct AutonomyConfig {
     #[serde(default)]
     pub non_cli_approval_approvers: Vec<String>,
 
+    /// Named operator roles for non-CLI approval-management commands.
+    ///
+    /// Keys use the same entry formats as `non_cli_approval_approvers`;
+    /// values are `admin`, `reviewer`, or `viewer`. A sender's role is the
+    /// highest one granted by any matching key, and senders matched by
+    /// `non_cli_approval_approvers` are `admin`.
+    ///
+    /// Once any role is configured, an empty approver list no longer admits
+    /// every sender: only senders with a role can manage approvals.
+    #[serde(default)]
+    pub non_cli_operator_roles: HashMap<String, OperatorRole>,
+
     /// Natural-language handling mode for non-CLI approval-management commands.
     ///
     /// Values:
@@ -3555,6 +4035,29 @@ fn default_always_ask() -> Vec<String> {
     vec![]
 }
 
+fn default_max_tool_io_bytes() -> usize {
+    1_048_576
+}
+
+fn default_denial_cooldown_after() -> u32 {
+    3
+}
+
+fn default_denial_cooldown_secs() -> u64 {
+    30
+}
+
+fn default_denial_cooldown_max_secs() -> u64 {
+    900
+}
+
+fn default_env_secret_suffixes() -> Vec<String> {
+    crate::security::env_filter::DEFAULT_SECRET_ENV_SUFFIXES
+        .iter()
+        .map(|suffix| (*suffix).to_string())
+        .collect()
+}
+
 fn default_non_cli_excluded_tools() -> Vec<String> {
     [
         "shell",
@@ -3597,6 +4100,154 @@ fn is_valid_env_var_name(name: &str) -> bool {
     chars.all(|ch| ch.is_ascii_alphanumeric() || ch == '_')
 }
 
+impl AutonomyConfig {
+    /// Check the settings a [`SecurityPolicy`](crate::security::SecurityPolicy)
+    /// is built from. Errors name the offending key under `autonomy.`.
+    pub fn validate(&self) -> Result<()> {
+        if self.max_actions_per_hour == 0 {
+            anyhow::bail!("autonomy.max_actions_per_hour must be greater than 0");
+        }
+        if !self.max_cost_usd_per_day.is_finite() || self.max_cost_usd_per_day < 0.0 {
+            anyhow::bail!("autonomy.max_cost_usd_per_day must be a non-negative number");
+        }
+        if self.denial_cooldown_after > 0
+            && (self.denial_cooldown_secs == 0
+                || self.denial_cooldown_max_secs < self.denial_cooldown_secs)
+        {
+            anyhow::bail!(
+                "autonomy.denial_cooldown_secs must be greater than 0 and at most autonomy.denial_cooldown_max_secs"
+            );
+        }
+        if let Some(window) = &self.operating_window {
+            crate::security::policy::OperatingWindow::from_config(window)
+                .context("autonomy.operating_window is invalid")?;
+        }
+        for (key, hosts) in [
+            ("allowed_hosts", &self.allowed_hosts),
+            ("denied_hosts", &self.denied_hosts),
+        ] {
+            for (i, host) in hosts.iter().enumerate() {
+                DomainMatcher::validate_pattern(host)
+                    .with_context(|| format!("autonomy.{key}[{i}] is invalid"))?;
+            }
+        }
+        for (binary, flags) in &self.command_policy.banned_flags {
+            if flags.iter().any(|flag| !flag.trim().starts_with('-')) {
+                anyhow::bail!(
+                    "autonomy.command_policy.banned_flags.{binary} entries must be flags starting with '-'"
+                );
+            }
+        }
+        for (tool, limit) in &self.per_tool_limits {
+            if *limit == 0 {
+                anyhow::bail!(
+                    "autonomy.per_tool_limits.{tool} must be greater than 0; use autonomy.denied_tools to disable a tool"
+                );
+            }
+        }
+        for prefix in self.path_permissions.keys() {
+            let path = Path::new(prefix.trim());
+            if path.is_absolute()
+                || path
+                    .components()
+                    .any(|component| matches!(component, std::path::Component::ParentDir))
+            {
+                anyhow::bail!(
+                    "autonomy.path_permissions.\"{prefix}\" must be a workspace-relative path without '..'"
+                );
+            }
+        }
+        for (i, env_name) in self.shell_env_passthrough.iter().enumerate() {
+            if !is_valid_env_var_name(env_name) {
+                anyhow::bail!(
+                    "autonomy.shell_env_passthrough[{i}] is invalid ({env_name}); expected [A-Za-z_][A-Za-z0-9_]*"
+                );
+            }
+        }
+        for (i, suffix) in self.env_secret_suffixes.iter().enumerate() {
+            if suffix.trim().is_empty() {
+                anyhow::bail!("autonomy.env_secret_suffixes[{i}] must not be empty");
+            }
+        }
+        for (i, rule) in self.command_context_rules.iter().enumerate() {
+            let command = rule.command.trim();
+            if command.is_empty() {
+                anyhow::bail!("autonomy.command_context_rules[{i}].command must not be empty");
+            }
+            if !command
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '/' | '.' | '*'))
+            {
+                anyhow::bail!(
+                    "autonomy.command_context_rules[{i}].command contains invalid characters: {command}"
+                );
+            }
+
+            for (j, domain) in rule.allowed_domains.iter().enumerate() {
+                let normalized = domain.trim();
+                if normalized.is_empty() {
+                    anyhow::bail!(
+                        "autonomy.command_context_rules[{i}].allowed_domains[{j}] must not be empty"
+                    );
+                }
+                if normalized.chars().any(char::is_whitespace) {
+                    anyhow::bail!(
+                        "autonomy.command_context_rules[{i}].allowed_domains[{j}] must not contain whitespace"
+                    );
+                }
+            }
+
+            for (j, prefix) in rule.allowed_path_prefixes.iter().enumerate() {
+                let normalized = prefix.trim();
+                if normalized.is_empty() {
+                    anyhow::bail!(
+                        "autonomy.command_context_rules[{i}].allowed_path_prefixes[{j}] must not be empty"
+                    );
+                }
+                if normalized.contains('\0') {
+                    anyhow::bail!(
+                        "autonomy.command_context_rules[{i}].allowed_path_prefixes[{j}] must not contain null bytes"
+                    );
+                }
+            }
+            for (j, prefix) in rule.denied_path_prefixes.iter().enumerate() {
+                let normalized = prefix.trim();
+                if normalized.is_empty() {
+                    anyhow::bail!(
+                        "autonomy.command_context_rules[{i}].denied_path_prefixes[{j}] must not be empty"
+                    );
+                }
+                if normalized.contains('\0') {
+                    anyhow::bail!(
+                        "autonomy.command_context_rules[{i}].denied_path_prefixes[{j}] must not contain null bytes"
+                    );
+                }
+            }
+        }
+        let mut seen_non_cli_excluded = std::collections::HashSet::new();
+        for (i, tool_name) in self.non_cli_excluded_tools.iter().enumerate() {
+            let normalized = tool_name.trim();
+            if normalized.is_empty() {
+                anyhow::bail!("autonomy.non_cli_excluded_tools[{i}] must not be empty");
+            }
+            if !normalized
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+            {
+                anyhow::bail!(
+                    "autonomy.non_cli_excluded_tools[{i}] contains invalid characters: {normalized}"
+                );
+            }
+            if !seen_non_cli_excluded.insert(normalized.to_string()) {
+                anyhow::bail!(
+                    "autonomy.non_cli_excluded_tools contains duplicate entry: {normalized}"
+                );
+            }
+        }
+        Ok(())
+    }
+}
+
 impl Default for AutonomyConfig {
     fn default() -> Self {
         Self {
@@ -3644,17 +4295,41 @@ impl Default for AutonomyConfig {
                 "~/.config".into(),
             ],
             max_actions_per_hour: 100,
+            persist_rate_limit: true,
             max_cost_per_day_cents: 1000,
             require_approval_for_medium_risk: true,
             block_high_risk_commands: true,
             shell_env_passthrough: vec![],
+            env_secret_suffixes: default_env_secret_suffixes(),
             allow_sensitive_file_reads: false,
             allow_sensitive_file_writes: false,
+            max_memory_bytes: 0,
+            max_memory_entries_per_day: 0,
+            max_tokens_per_day: 0,
+            max_cost_usd_per_day: 0.0,
+            allow_memory_push: false,
+            allowed_tools: Vec::new(),
+            denied_tools: Vec::new(),
+            allowed_hosts: Vec::new(),
+            denied_hosts: Vec::new(),
+            per_tool_limits: HashMap::new(),
+            path_permissions: HashMap::new(),
+            dry_run: false,
+            max_actions_burst: 0,
+            max_actions_per_session: 0,
+            max_tool_input_bytes: default_max_tool_io_bytes(),
+            max_tool_output_bytes: default_max_tool_io_bytes(),
+            denial_cooldown_after: default_denial_cooldown_after(),
+            denial_cooldown_secs: default_denial_cooldown_secs(),
+            denial_cooldown_max_secs: default_denial_cooldown_max_secs(),
+            operating_window: None,
+            command_policy: CommandPolicyConfig::default(),
             auto_approve: default_auto_approve(),
             always_ask: default_always_ask(),
             allowed_roots: Vec::new(),
             non_cli_excluded_tools: default_non_cli_excluded_tools(),
             non_cli_approval_approvers: Vec::new(),
+            non_cli_operator_roles: HashMap::new(),
             non_cli_natural_language_approval_mode: NonCliNaturalLanguageApprovalMode::default(),
             non_cli_natural_language_approval_mode_by_channel: HashMap::new(),
         }
@@ -3678,6 +4353,10 @@ pub struct RuntimeConfig {
     #[serde(default)]
     pub wasm: WasmRuntimeConfig,
 
+    /// Tokio executor tuning (`[runtime.tokio]`).
+    #[serde(default)]
+    pub tokio: TokioRuntimeConfig,
+
     /// Global reasoning override for providers that expose explicit controls.
     /// - `None`: provider default behavior
     /// - `Some(true)`: request reasoning/thinking when supported
@@ -3725,6 +4404,33 @@ pub struct DockerRuntimeConfig {
     pub allowed_workspace_roots: Vec<String>,
 }
 
+/// Tokio executor tuning (`[runtime.tokio]` section).
+///
+/// Read once at process start, before the async runtime is built, so changes
+/// take effect on the next restart. Unset values keep Tokio's defaults.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct TokioRuntimeConfig {
+    /// Async worker threads for the main runtime (`None` = one per CPU core).
+    #[serde(default)]
+    pub worker_threads: Option<usize>,
+
+    /// Upper bound on the blocking thread pool of the main runtime
+    /// (`None` = Tokio default of 512).
+    #[serde(default)]
+    pub max_blocking_threads: Option<usize>,
+
+    /// Worker threads for a dedicated runtime that executes filesystem-heavy
+    /// tool work (document extraction, large file parsing). `0` (default)
+    /// runs that work on the main runtime's blocking pool.
+    #[serde(default)]
+    pub fs_worker_threads: usize,
+
+    /// Upper bound on the blocking thread pool of the dedicated filesystem
+    /// runtime (`None` = Tokio default). Ignored when `fs_worker_threads = 0`.
+    #[serde(default)]
+    pub fs_max_blocking_threads: Option<usize>,
+}
+
 /// WASM runtime configuration (`[runtime.wasm]` section).
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct WasmRuntimeConfig {
@@ -3903,6 +4609,7 @@ impl Default for RuntimeConfig {
             kind: default_runtime_kind(),
             docker: DockerRuntimeConfig::default(),
             wasm: WasmRuntimeConfig::default(),
+            tokio: TokioRuntimeConfig::default(),
             reasoning_enabled: None,
             reasoning_level: None,
         }
@@ -5758,7 +6465,21 @@ pub struct SecurityConfig {
 
     /// Shared URL access policy for network-enabled tools.
     #[serde(default)]
-    pub url_access: UrlAccessConfig,
+    pub url_access: UrlAccessConfig,
+
+    /// Standalone policy file layered over `[autonomy]` (see
+    /// `zeroclaw policy check`). Tool calls must pass both policies, and the
+    /// file is reloaded while the agent runs. Relative to the zeroclaw dir.
+    #[serde(default)]
+    pub policy_file: Option<String>,
+
+    /// Dangerous-pattern scanner for `Act` tool arguments.
+    #[serde(default)]
+    pub content_scanner: ContentScannerConfig,
+
+    /// Webhook that receives security events as they happen.
+    #[serde(default)]
+    pub webhook: SecurityWebhookConfig,
 }
 
 impl Default for SecurityConfig {
@@ -5778,6 +6499,9 @@ impl Default for SecurityConfig {
             semantic_guard_collection: default_semantic_guard_collection(),
             semantic_guard_threshold: default_semantic_guard_threshold(),
             url_access: UrlAccessConfig::default(),
+            policy_file: None,
+            content_scanner: ContentScannerConfig::default(),
+            webhook: SecurityWebhookConfig::default(),
         }
     }
 }
@@ -5790,6 +6514,133 @@ fn default_semantic_guard_threshold() -> f64 {
     0.82
 }
 
+/// What the content scanner does when a rule matches a tool call.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum ContentScanAction {
+    /// Run the call and append the finding to its result.
+    #[default]
+    Flag,
+    /// Refuse the call.
+    Block,
+}
+
+/// Custom rule for the content scanner.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ContentScanRuleConfig {
+    /// Name reported with findings.
+    pub name: String,
+    /// Regular expression matched against every string in the tool arguments.
+    pub pattern: String,
+    /// Overrides `[security.content_scanner] action` for this rule.
+    #[serde(default)]
+    pub action: Option<ContentScanAction>,
+}
+
+/// Dangerous-pattern scanner for `Act` tool arguments
+/// (`[security.content_scanner]`).
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ContentScannerConfig {
+    /// Scan the arguments of `Act` tool calls.
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+
+    /// Include the built-in rules (recursive deletes, piping downloads into a
+    /// shell, security policy file edits, large base64 blobs).
+    #[serde(default = "default_true")]
+    pub builtin_rules: bool,
+
+    /// Action for rules that do not set their own.
+    #[serde(default)]
+    pub action: ContentScanAction,
+
+    /// Additional rules.
+    #[serde(default)]
+    pub rules: Vec<ContentScanRuleConfig>,
+}
+
+impl Default for ContentScannerConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            builtin_rules: true,
+            action: ContentScanAction::Flag,
+            rules: Vec::new(),
+        }
+    }
+}
+
+/// Kind of event sent to `[security.webhook]`.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, JsonSchema, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum SecurityEventKind {
+    /// The security policy refused a tool call.
+    PolicyDenial,
+    /// A tool call was refused because the workspace kill switch is engaged.
+    KillSwitch,
+    /// The content scanner flagged or blocked a tool call.
+    ScannerFinding,
+    /// A daily token or cost budget ran out.
+    BudgetExceeded,
+    /// A tool was put on cooldown after repeated denials.
+    DenialCooldown,
+}
+
+/// Payload shape for `[security.webhook]`.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SecurityWebhookFormat {
+    /// The event as a JSON object.
+    #[default]
+    Json,
+    /// A Slack incoming-webhook message (`{"text": ...}`).
+    Slack,
+}
+
+/// Webhook for security events (`[security.webhook]`).
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct SecurityWebhookConfig {
+    /// Send security events to `url`.
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// Endpoint events are POSTed to.
+    #[serde(default)]
+    pub url: String,
+
+    /// Payload shape.
+    #[serde(default)]
+    pub format: SecurityWebhookFormat,
+
+    /// Event kinds to send; empty sends every kind.
+    #[serde(default)]
+    pub events: Vec<SecurityEventKind>,
+
+    /// Minimum seconds between two events of the same kind for the same
+    /// tool; repeats inside the window are dropped. `0` sends every event.
+    #[serde(default = "default_security_webhook_cooldown_secs")]
+    pub cooldown_secs: u64,
+}
+
+fn default_security_webhook_cooldown_secs() -> u64 {
+    60
+}
+
+impl Default for SecurityWebhookConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            url: String::new(),
+            format: SecurityWebhookFormat::Json,
+            events: Vec::new(),
+            cooldown_secs: default_security_webhook_cooldown_secs(),
+        }
+    }
+}
+
 /// Outbound leak handling mode for channel responses.
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, JsonSchema, PartialEq, Eq)]
 #[serde(rename_all = "kebab-case")]
@@ -6369,7 +7220,8 @@ pub struct AuditConfig {
     #[serde(default = "default_audit_enabled")]
     pub enabled: bool,
 
-    /// Path to audit log file (relative to zeroclaw dir)
+    /// Path to audit log file (relative to zeroclaw dir). A log left at the
+    /// old default, `audit.log`, is moved here on first use.
     #[serde(default = "default_audit_log_path")]
     pub log_path: String,
 
@@ -6380,6 +7232,26 @@ pub struct AuditConfig {
     /// Sign events with HMAC for tamper evidence
     #[serde(default)]
     pub sign_events: bool,
+
+    /// Number of events buffered in memory before they are written in one batch.
+    ///
+    /// `1` (default) writes and fsyncs every event immediately (maximum durability).
+    /// Larger values trade a small window of potential loss on crash for throughput.
+    #[serde(default = "default_audit_batch_size")]
+    pub batch_size: usize,
+
+    /// Maximum age of buffered events before a flush is forced, in milliseconds.
+    #[serde(default = "default_audit_flush_interval_ms")]
+    pub flush_interval_ms: u64,
+
+    /// Records between two anchors of the hash chain head; `0` disables anchoring.
+    #[serde(default = "default_audit_anchor_every")]
+    pub anchor_every: u32,
+
+    /// File chain-head anchors are appended to (relative to zeroclaw dir).
+    /// Point it at separate or append-only storage so it survives a log edit.
+    #[serde(default = "default_audit_anchor_path")]
+    pub anchor_path: String,
 }
 
 fn default_audit_enabled() -> bool {
@@ -6387,13 +7259,29 @@ fn default_audit_enabled() -> bool {
 }
 
 fn default_audit_log_path() -> String {
-    "audit.log".to_string()
+    "ariadne/audit/audit.log".to_string()
 }
 
 fn default_audit_max_size_mb() -> u32 {
     100
 }
 
+fn default_audit_batch_size() -> usize {
+    1
+}
+
+fn default_audit_flush_interval_ms() -> u64 {
+    1000
+}
+
+fn default_audit_anchor_every() -> u32 {
+    100
+}
+
+fn default_audit_anchor_path() -> String {
+    "audit.anchors".to_string()
+}
+
 impl Default for AuditConfig {
     fn default() -> Self {
         Self {
@@ -6401,6 +7289,10 @@ impl Default for AuditConfig {
             log_path: default_audit_log_path(),
             max_size_mb: default_audit_max_size_mb(),
             sign_events: false,
+            batch_size: default_audit_batch_size(),
+            flush_interval_ms: default_audit_flush_interval_ms(),
+            anchor_every: default_audit_anchor_every(),
+            anchor_path: default_audit_anchor_path(),
         }
     }
 }
@@ -6538,6 +7430,7 @@ impl Default for Config {
         Self {
             workspace_dir: zeroclaw_dir.join("workspace"),
             config_path: zeroclaw_dir.join("config.toml"),
+            secret_refs: HashMap::new(),
             api_key: None,
             api_url: None,
             default_provider: Some(DEFAULT_PROVIDER_NAME.to_string()),
@@ -6572,6 +7465,7 @@ impl Default for Config {
             multimodal: MultimodalConfig::default(),
             web_fetch: WebFetchConfig::default(),
             web_search: WebSearchConfig::default(),
+            home_assistant: HomeAssistantConfig::default(),
             proxy: ProxyConfig::default(),
             identity: IdentityConfig::default(),
             cost: CostConfig::default(),
@@ -6592,6 +7486,14 @@ impl Default for Config {
     }
 }
 
+fn tokio_runtime_config_from_toml(contents: &str) -> TokioRuntimeConfig {
+    toml::from_str::<toml::Value>(contents)
+        .ok()
+        .and_then(|raw| raw.get("runtime")?.get("tokio").cloned())
+        .and_then(|section| section.try_into().ok())
+        .unwrap_or_default()
+}
+
 fn default_config_and_workspace_dirs() -> Result<(PathBuf, PathBuf)> {
     let config_dir = default_config_dir()?;
     Ok((config_dir.clone(), config_dir.join("workspace")))
@@ -6931,130 +7833,176 @@ async fn resolve_runtime_config_dirs(
     ))
 }
 
+/// A secret reference from config and the value it resolved to at load.
+#[derive(Debug, Clone)]
+pub struct ResolvedSecret {
+    pub reference: crate::security::SecretRef,
+    pub value: String,
+}
+
+/// Decrypts config secrets on load and encrypts them on save.
+///
+/// Fields holding a `${keychain:...}`, `${env:...}`, or `${secretfile:...}`
+/// reference are resolved on load and recorded by field path; on save, a
+/// field still holding the value it resolved to is written back as the
+/// reference.
+struct ConfigSecrets {
+    store: crate::security::SecretStore,
+    providers: crate::security::SecretsProviders,
+    refs: std::cell::RefCell<HashMap<String, ResolvedSecret>>,
+}
+
+impl ConfigSecrets {
+    fn new(zeroclaw_dir: &Path, config: &SecretsConfig) -> Self {
+        Self {
+            store: crate::security::SecretStore::new(zeroclaw_dir, config.encrypt),
+            providers: crate::security::SecretsProviders::new(
+                zeroclaw_dir,
+                &config.keychain_service,
+            ),
+            refs: std::cell::RefCell::default(),
+        }
+    }
+
+    fn with_refs(mut self, refs: HashMap<String, ResolvedSecret>) -> Self {
+        self.refs = std::cell::RefCell::new(refs);
+        self
+    }
+
+    /// The plaintext of `raw`, or `None` for a reference that cannot be
+    /// resolved. An unresolved reference only warns, so that commands like
+    /// `zeroclaw secrets set` still run with a config pointing at it.
+    fn decrypt(&self, raw: &str, field_name: &str) -> Result<Option<String>> {
+        let value = if crate::security::SecretStore::is_encrypted(raw) {
+            self.store
+                .decrypt(raw)
+                .with_context(|| format!("Failed to decrypt {field_name}"))?
+        } else {
+            raw.to_string()
+        };
+        let Some(reference) = crate::security::SecretRef::parse(&value) else {
+            return Ok(Some(value));
+        };
+        let resolved = match self.providers.resolve(&reference) {
+            Ok(resolved) => Some(resolved),
+            Err(e) => {
+                tracing::warn!("Leaving {field_name} unset: {e:#}");
+                None
+            }
+        };
+        self.refs.borrow_mut().insert(
+            field_name.to_string(),
+            ResolvedSecret {
+                reference,
+                value: resolved.clone().unwrap_or_default(),
+            },
+        );
+        Ok(resolved)
+    }
+
+    /// What to write for `value`: its reference if it still holds what the
+    /// reference resolved to, references and ciphertext as they are, and
+    /// anything else encrypted.
+    fn encrypt(&self, value: Option<&str>, field_name: &str) -> Result<Option<String>> {
+        if let Some(resolved) = self.refs.borrow().get(field_name) {
+            if value.unwrap_or_default() == resolved.value {
+                return Ok(Some(resolved.reference.to_string()));
+            }
+        }
+        match value {
+            None => Ok(None),
+            Some(value)
+                if crate::security::SecretStore::is_encrypted(value)
+                    || crate::security::SecretRef::parse(value).is_some() =>
+            {
+                Ok(Some(value.to_string()))
+            }
+            Some(value) => self
+                .store
+                .encrypt(value)
+                .map(Some)
+                .with_context(|| format!("Failed to encrypt {field_name}")),
+        }
+    }
+}
+
 fn decrypt_optional_secret(
-    store: &crate::security::SecretStore,
+    secrets: &ConfigSecrets,
     value: &mut Option<String>,
     field_name: &str,
 ) -> Result<()> {
-    if let Some(raw) = value.clone() {
-        if crate::security::SecretStore::is_encrypted(&raw) {
-            *value = Some(
-                store
-                    .decrypt(&raw)
-                    .with_context(|| format!("Failed to decrypt {field_name}"))?,
-            );
-        }
+    if let Some(raw) = value.take() {
+        *value = secrets.decrypt(&raw, field_name)?;
     }
     Ok(())
 }
 
-fn decrypt_secret(
-    store: &crate::security::SecretStore,
-    value: &mut String,
-    field_name: &str,
-) -> Result<()> {
-    if crate::security::SecretStore::is_encrypted(value) {
-        *value = store
-            .decrypt(value)
-            .with_context(|| format!("Failed to decrypt {field_name}"))?;
-    }
+fn decrypt_secret(secrets: &ConfigSecrets, value: &mut String, field_name: &str) -> Result<()> {
+    *value = secrets.decrypt(value, field_name)?.unwrap_or_default();
     Ok(())
 }
 
 fn decrypt_vec_secrets(
-    store: &crate::security::SecretStore,
+    secrets: &ConfigSecrets,
     values: &mut [String],
     field_name: &str,
 ) -> Result<()> {
     for (idx, value) in values.iter_mut().enumerate() {
-        if crate::security::SecretStore::is_encrypted(value) {
-            *value = store
-                .decrypt(value)
-                .with_context(|| format!("Failed to decrypt {field_name}[{idx}]"))?;
-        }
+        decrypt_secret(secrets, value, &format!("{field_name}[{idx}]"))?;
     }
     Ok(())
 }
 
 fn decrypt_map_secrets(
-    store: &crate::security::SecretStore,
+    secrets: &ConfigSecrets,
     values: &mut std::collections::HashMap<String, String>,
     field_name: &str,
 ) -> Result<()> {
     for (key, value) in values.iter_mut() {
-        if crate::security::SecretStore::is_encrypted(value) {
-            *value = store
-                .decrypt(value)
-                .with_context(|| format!("Failed to decrypt {field_name}.{key}"))?;
-        }
+        decrypt_secret(secrets, value, &format!("{field_name}.{key}"))?;
     }
     Ok(())
 }
 
 fn encrypt_optional_secret(
-    store: &crate::security::SecretStore,
+    secrets: &ConfigSecrets,
     value: &mut Option<String>,
     field_name: &str,
 ) -> Result<()> {
-    if let Some(raw) = value.clone() {
-        if !crate::security::SecretStore::is_encrypted(&raw) {
-            *value = Some(
-                store
-                    .encrypt(&raw)
-                    .with_context(|| format!("Failed to encrypt {field_name}"))?,
-            );
-        }
-    }
+    *value = secrets.encrypt(value.as_deref(), field_name)?;
     Ok(())
 }
 
-fn encrypt_secret(
-    store: &crate::security::SecretStore,
-    value: &mut String,
-    field_name: &str,
-) -> Result<()> {
-    if !crate::security::SecretStore::is_encrypted(value) {
-        *value = store
-            .encrypt(value)
-            .with_context(|| format!("Failed to encrypt {field_name}"))?;
-    }
+fn encrypt_secret(secrets: &ConfigSecrets, value: &mut String, field_name: &str) -> Result<()> {
+    *value = secrets
+        .encrypt(Some(value), field_name)?
+        .unwrap_or_default();
     Ok(())
 }
 
 fn encrypt_vec_secrets(
-    store: &crate::security::SecretStore,
+    secrets: &ConfigSecrets,
     values: &mut [String],
     field_name: &str,
 ) -> Result<()> {
     for (idx, value) in values.iter_mut().enumerate() {
-        if !crate::security::SecretStore::is_encrypted(value) {
-            *value = store
-                .encrypt(value)
-                .with_context(|| format!("Failed to encrypt {field_name}[{idx}]"))?;
-        }
+        encrypt_secret(secrets, value, &format!("{field_name}[{idx}]"))?;
     }
     Ok(())
 }
 
 fn encrypt_map_secrets(
-    store: &crate::security::SecretStore,
+    secrets: &ConfigSecrets,
     values: &mut std::collections::HashMap<String, String>,
     field_name: &str,
 ) -> Result<()> {
     for (key, value) in values.iter_mut() {
-        if !crate::security::SecretStore::is_encrypted(value) {
-            *value = store
-                .encrypt(value)
-                .with_context(|| format!("Failed to encrypt {field_name}.{key}"))?;
-        }
+        encrypt_secret(secrets, value, &format!("{field_name}.{key}"))?;
     }
     Ok(())
 }
 
-fn decrypt_channel_secrets(
-    store: &crate::security::SecretStore,
-    channels: &mut ChannelsConfig,
-) -> Result<()> {
+fn decrypt_channel_secrets(store: &ConfigSecrets, channels: &mut ChannelsConfig) -> Result<()> {
     if let Some(ref mut telegram) = channels.telegram {
         decrypt_secret(
             store,
@@ -7256,10 +8204,7 @@ fn decrypt_channel_secrets(
     Ok(())
 }
 
-fn encrypt_channel_secrets(
-    store: &crate::security::SecretStore,
-    channels: &mut ChannelsConfig,
-) -> Result<()> {
+fn encrypt_channel_secrets(store: &ConfigSecrets, channels: &mut ChannelsConfig) -> Result<()> {
     if let Some(ref mut telegram) = channels.telegram {
         encrypt_secret(
             store,
@@ -7650,6 +8595,28 @@ fn apply_feishu_legacy_compat(
 }
 
 impl Config {
+    /// Read only `[runtime.tokio]` from the resolved config file.
+    ///
+    /// Runs before the main runtime exists, so it never creates directories,
+    /// decrypts secrets, or rewrites legacy keys. Any failure (missing file,
+    /// parse error) falls back to Tokio defaults; the full `load_or_init`
+    /// later reports real config errors.
+    pub async fn load_runtime_tuning() -> TokioRuntimeConfig {
+        let Ok((default_zeroclaw_dir, default_workspace_dir)) = default_config_and_workspace_dirs()
+        else {
+            return TokioRuntimeConfig::default();
+        };
+        let Ok((zeroclaw_dir, _, _)) =
+            resolve_runtime_config_dirs(&default_zeroclaw_dir, &default_workspace_dir).await
+        else {
+            return TokioRuntimeConfig::default();
+        };
+        match fs::read_to_string(zeroclaw_dir.join("config.toml")).await {
+            Ok(contents) => tokio_runtime_config_from_toml(&contents),
+            Err(_) => TokioRuntimeConfig::default(),
+        }
+    }
+
     pub async fn load_or_init() -> Result<Self> {
         let (default_zeroclaw_dir, default_workspace_dir) = default_config_and_workspace_dirs()?;
 
@@ -7707,7 +8674,7 @@ impl Config {
             // Set computed paths that are skipped during serialization
             config.config_path = config_path.clone();
             config.workspace_dir = workspace_dir;
-            let store = crate::security::SecretStore::new(&zeroclaw_dir, config.secrets.encrypt);
+            let store = ConfigSecrets::new(&zeroclaw_dir, &config.secrets);
             decrypt_optional_secret(&store, &mut config.api_key, "config.api_key")?;
             for (profile_name, profile) in config.model_providers.iter_mut() {
                 let secret_path = format!("config.model_providers.{profile_name}.api_key");
@@ -7745,6 +8712,12 @@ impl Config {
                 "config.browser.computer_use.api_key",
             )?;
 
+            decrypt_optional_secret(
+                &store,
+                &mut config.home_assistant.token,
+                "config.home_assistant.token",
+            )?;
+
             decrypt_optional_secret(
                 &store,
                 &mut config.web_search.brave_api_key,
@@ -7787,11 +8760,18 @@ impl Config {
                 "config.gateway.paired_tokens",
             )?;
 
-            for agent in config.agents.values_mut() {
-                decrypt_optional_secret(&store, &mut agent.api_key, "config.agents.*.api_key")?;
+            for (agent_name, agent) in config.agents.iter_mut() {
+                let secret_path = format!("config.agents.{agent_name}.api_key");
+                decrypt_optional_secret(&store, &mut agent.api_key, &secret_path)?;
             }
 
             decrypt_channel_secrets(&store, &mut config.channels_config)?;
+            decrypt_secret(
+                &store,
+                &mut config.security.webhook.url,
+                "config.security.webhook.url",
+            )?;
+            config.secret_refs = store.refs.into_inner();
 
             config.apply_env_overrides();
             config.validate()?;
@@ -8147,90 +9127,27 @@ impl Config {
         }
 
         // Autonomy
-        if self.autonomy.max_actions_per_hour == 0 {
-            anyhow::bail!("autonomy.max_actions_per_hour must be greater than 0");
-        }
-        for (i, env_name) in self.autonomy.shell_env_passthrough.iter().enumerate() {
-            if !is_valid_env_var_name(env_name) {
-                anyhow::bail!(
-                    "autonomy.shell_env_passthrough[{i}] is invalid ({env_name}); expected [A-Za-z_][A-Za-z0-9_]*"
-                );
-            }
-        }
-        for (i, rule) in self.autonomy.command_context_rules.iter().enumerate() {
-            let command = rule.command.trim();
-            if command.is_empty() {
-                anyhow::bail!("autonomy.command_context_rules[{i}].command must not be empty");
-            }
-            if !command
-                .chars()
-                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '/' | '.' | '*'))
-            {
+        self.autonomy.validate()?;
+        if let Some(policy_file) = self.security.policy_file.as_deref() {
+            let base = self.config_path.parent().unwrap_or_else(|| Path::new("."));
+            crate::security::policy::load_policy_file(&base.join(policy_file))
+                .context("security.policy_file is invalid")?;
+        }
+        if self.security.webhook.enabled {
+            let url = self.security.webhook.url.trim();
+            if !(url.starts_with("https://") || url.starts_with("http://")) {
                 anyhow::bail!(
-                    "autonomy.command_context_rules[{i}].command contains invalid characters: {command}"
+                    "security.webhook.url must be an http(s) URL when the webhook is enabled"
                 );
             }
-
-            for (j, domain) in rule.allowed_domains.iter().enumerate() {
-                let normalized = domain.trim();
-                if normalized.is_empty() {
-                    anyhow::bail!(
-                        "autonomy.command_context_rules[{i}].allowed_domains[{j}] must not be empty"
-                    );
-                }
-                if normalized.chars().any(char::is_whitespace) {
-                    anyhow::bail!(
-                        "autonomy.command_context_rules[{i}].allowed_domains[{j}] must not contain whitespace"
-                    );
-                }
-            }
-
-            for (j, prefix) in rule.allowed_path_prefixes.iter().enumerate() {
-                let normalized = prefix.trim();
-                if normalized.is_empty() {
-                    anyhow::bail!(
-                        "autonomy.command_context_rules[{i}].allowed_path_prefixes[{j}] must not be empty"
-                    );
-                }
-                if normalized.contains('\0') {
-                    anyhow::bail!(
-                        "autonomy.command_context_rules[{i}].allowed_path_prefixes[{j}] must not contain null bytes"
-                    );
-                }
-            }
-            for (j, prefix) in rule.denied_path_prefixes.iter().enumerate() {
-                let normalized = prefix.trim();
-                if normalized.is_empty() {
-                    anyhow::bail!(
-                        "autonomy.command_context_rules[{i}].denied_path_prefixes[{j}] must not be empty"
-                    );
-                }
-                if normalized.contains('\0') {
-                    anyhow::bail!(
-                        "autonomy.command_context_rules[{i}].denied_path_prefixes[{j}] must not contain null bytes"
-                    );
-                }
-            }
         }
-        let mut seen_non_cli_excluded = std::collections::HashSet::new();
-        for (i, tool_name) in self.autonomy.non_cli_excluded_tools.iter().enumerate() {
-            let normalized = tool_name.trim();
-            if normalized.is_empty() {
-                anyhow::bail!("autonomy.non_cli_excluded_tools[{i}] must not be empty");
-            }
-            if !normalized
-                .chars()
-                .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
-            {
-                anyhow::bail!(
-                    "autonomy.non_cli_excluded_tools[{i}] contains invalid characters: {normalized}"
-                );
-            }
-            if !seen_non_cli_excluded.insert(normalized.to_string()) {
-                anyhow::bail!(
-                    "autonomy.non_cli_excluded_tools contains duplicate entry: {normalized}"
-                );
+        for (i, rule) in self.security.content_scanner.rules.iter().enumerate() {
+            if rule.name.trim().is_empty() {
+                anyhow::bail!("security.content_scanner.rules[{i}].name must not be empty");
             }
+            regex::Regex::new(&rule.pattern).with_context(|| {
+                format!("security.content_scanner.rules[{i}].pattern is not a valid regex")
+            })?;
         }
 
         // Security OTP / estop
@@ -9571,7 +10488,8 @@ impl Config {
             .config_path
             .parent()
             .context("Config path must have a parent directory")?;
-        let store = crate::security::SecretStore::new(zeroclaw_dir, self.secrets.encrypt);
+        let store =
+            ConfigSecrets::new(zeroclaw_dir, &self.secrets).with_refs(self.secret_refs.clone());
 
         encrypt_optional_secret(&store, &mut config_to_save.api_key, "config.api_key")?;
         for (profile_name, profile) in config_to_save.model_providers.iter_mut() {
@@ -9610,6 +10528,12 @@ impl Config {
             "config.browser.computer_use.api_key",
         )?;
 
+        encrypt_optional_secret(
+            &store,
+            &mut config_to_save.home_assistant.token,
+            "config.home_assistant.token",
+        )?;
+
         encrypt_optional_secret(
             &store,
             &mut config_to_save.web_search.brave_api_key,
@@ -9652,11 +10576,17 @@ impl Config {
             "config.gateway.paired_tokens",
         )?;
 
-        for agent in config_to_save.agents.values_mut() {
-            encrypt_optional_secret(&store, &mut agent.api_key, "config.agents.*.api_key")?;
+        for (agent_name, agent) in config_to_save.agents.iter_mut() {
+            let secret_path = format!("config.agents.{agent_name}.api_key");
+            encrypt_optional_secret(&store, &mut agent.api_key, &secret_path)?;
         }
 
         encrypt_channel_secrets(&store, &mut config_to_save.channels_config)?;
+        encrypt_secret(
+            &store,
+            &mut config_to_save.security.webhook.url,
+            "config.security.webhook.url",
+        )?;
 
         let toml_str =
             toml::to_string_pretty(&config_to_save).context("Failed to serialize config")?;
@@ -10386,6 +11316,7 @@ ws_url = "ws://127.0.0.1:3002"
         let config = Config {
             workspace_dir: PathBuf::from("/tmp/test/workspace"),
             config_path: PathBuf::from("/tmp/test/config.toml"),
+            secret_refs: HashMap::new(),
             api_key: Some("sk-test-key".into()),
             api_url: None,
             default_provider: Some("openrouter".into()),
@@ -10405,17 +11336,41 @@ ws_url = "ws://127.0.0.1:3002"
                 command_context_rules: vec![],
                 forbidden_paths: vec!["/secret".into()],
                 max_actions_per_hour: 50,
+                persist_rate_limit: true,
                 max_cost_per_day_cents: 1000,
                 require_approval_for_medium_risk: false,
                 block_high_risk_commands: true,
                 shell_env_passthrough: vec!["DATABASE_URL".into()],
+                env_secret_suffixes: default_env_secret_suffixes(),
                 allow_sensitive_file_reads: false,
                 allow_sensitive_file_writes: false,
+                max_memory_bytes: 0,
+                max_memory_entries_per_day: 0,
+                max_tokens_per_day: 0,
+                max_cost_usd_per_day: 0.0,
+                allow_memory_push: false,
+                allowed_tools: vec![],
+                denied_tools: vec![],
+                allowed_hosts: vec![],
+                denied_hosts: vec![],
+                per_tool_limits: HashMap::new(),
+                path_permissions: HashMap::new(),
+                dry_run: false,
+                max_actions_burst: 0,
+                max_actions_per_session: 0,
+                max_tool_input_bytes: default_max_tool_io_bytes(),
+                max_tool_output_bytes: default_max_tool_io_bytes(),
+                denial_cooldown_after: default_denial_cooldown_after(),
+                denial_cooldown_secs: default_denial_cooldown_secs(),
+                denial_cooldown_max_secs: default_denial_cooldown_max_secs(),
+                operating_window: None,
+                command_policy: CommandPolicyConfig::default(),
                 auto_approve: vec!["file_read".into()],
                 always_ask: vec![],
                 allowed_roots: vec![],
                 non_cli_excluded_tools: vec![],
                 non_cli_approval_approvers: vec![],
+                non_cli_operator_roles: HashMap::new(),
                 non_cli_natural_language_approval_mode:
                     NonCliNaturalLanguageApprovalMode::RequestConfirm,
                 non_cli_natural_language_approval_mode_by_channel: HashMap::new(),
@@ -10496,6 +11451,7 @@ ws_url = "ws://127.0.0.1:3002"
             multimodal: MultimodalConfig::default(),
             web_fetch: WebFetchConfig::default(),
             web_search: WebSearchConfig::default(),
+            home_assistant: HomeAssistantConfig::default(),
             proxy: ProxyConfig::default(),
             agent: AgentConfig::default(),
             identity: IdentityConfig::default(),
@@ -10605,6 +11561,30 @@ reasoning_enabled = false
         assert_eq!(parsed.runtime.reasoning_enabled, Some(false));
     }
 
+    #[test]
+    async fn runtime_tokio_tuning_deserializes() {
+        let raw = r#"
+default_temperature = 0.7
+
+[runtime.tokio]
+worker_threads = 2
+max_blocking_threads = 16
+fs_worker_threads = 1
+"#;
+
+        let parsed: Config = toml::from_str(raw).unwrap();
+        assert_eq!(parsed.runtime.tokio.worker_threads, Some(2));
+        assert_eq!(parsed.runtime.tokio.max_blocking_threads, Some(16));
+        assert_eq!(parsed.runtime.tokio.fs_worker_threads, 1);
+        assert_eq!(parsed.runtime.tokio.fs_max_blocking_threads, None);
+
+        assert_eq!(tokio_runtime_config_from_toml(raw), parsed.runtime.tokio);
+        assert_eq!(
+            tokio_runtime_config_from_toml("not = [valid"),
+            TokioRuntimeConfig::default()
+        );
+    }
+
     #[test]
     async fn runtime_wasm_deserializes() {
         let raw = r#"
@@ -10848,6 +11828,7 @@ denied_tools = ["shell"]
         let config = Config {
             workspace_dir: dir.join("workspace"),
             config_path: config_path.clone(),
+            secret_refs: HashMap::new(),
             api_key: Some("sk-roundtrip".into()),
             api_url: None,
             default_provider: Some("openrouter".into()),
@@ -10884,6 +11865,7 @@ denied_tools = ["shell"]
             multimodal: MultimodalConfig::default(),
             web_fetch: WebFetchConfig::default(),
             web_search: WebSearchConfig::default(),
+            home_assistant: HomeAssistantConfig::default(),
             proxy: ProxyConfig::default(),
             agent: AgentConfig::default(),
             identity: IdentityConfig::default(),
@@ -10918,6 +11900,43 @@ denied_tools = ["shell"]
         let _ = fs::remove_dir_all(&dir).await;
     }
 
+    #[tokio::test]
+    async fn config_secret_references_resolve_on_load_and_survive_save() {
+        let tmp = tempfile::tempdir().unwrap();
+        let secrets = ConfigSecrets::new(tmp.path(), &SecretsConfig::default());
+        secrets
+            .providers
+            .provider(crate::security::secrets_provider::SecretBackend::File)
+            .set("openrouter", "sk-or-from-file")
+            .unwrap();
+
+        let mut config = Config::default();
+        config.workspace_dir = tmp.path().join("workspace");
+        config.config_path = tmp.path().join("config.toml");
+        config.api_key = Some("${secretfile:openrouter}".into());
+        config.composio.api_key = Some("${secretfile:missing}".into());
+        decrypt_optional_secret(&secrets, &mut config.api_key, "config.api_key").unwrap();
+        decrypt_optional_secret(
+            &secrets,
+            &mut config.composio.api_key,
+            "config.composio.api_key",
+        )
+        .unwrap();
+        assert_eq!(config.api_key.as_deref(), Some("sk-or-from-file"));
+        assert_eq!(config.composio.api_key, None);
+        config.secret_refs = secrets.refs.into_inner();
+
+        config.save().await.unwrap();
+        let contents = fs::read_to_string(&config.config_path).await.unwrap();
+        let stored: Config = toml::from_str(&contents).unwrap();
+        assert_eq!(stored.api_key.as_deref(), Some("${secretfile:openrouter}"));
+        assert_eq!(
+            stored.composio.api_key.as_deref(),
+            Some("${secretfile:missing}")
+        );
+        assert!(!contents.contains("sk-or-from-file"));
+    }
+
     #[tokio::test]
     async fn config_save_encrypts_nested_credentials() {
         let dir = std::env::temp_dir().join(format!(
@@ -12092,10 +13111,14 @@ enable = true
 
     #[test]
     async fn secrets_config_serde_roundtrip() {
-        let s = SecretsConfig { encrypt: false };
+        let s = SecretsConfig {
+            encrypt: false,
+            keychain_service: "zeroclaw-dev".into(),
+        };
         let toml_str = toml::to_string(&s).unwrap();
         let parsed: SecretsConfig = toml::from_str(&toml_str).unwrap();
         assert!(!parsed.encrypt);
+        assert_eq!(parsed.keychain_service, "zeroclaw-dev");
     }
 
     #[test]
@@ -8,6 +8,7 @@ use std::fs::{self, File, OpenOptions};
 use std::io::{BufRead, BufReader, Write};
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 /// Cost tracker for API usage monitoring and budget enforcement.
 pub struct CostTracker {
@@ -22,9 +23,11 @@ impl CostTracker {
     pub fn new(config: CostConfig, workspace_dir: &Path) -> Result<Self> {
         let storage_path = resolve_storage_path(workspace_dir)?;
 
-        let storage = CostStorage::new(&storage_path).with_context(|| {
+        let mut storage = CostStorage::new(&storage_path).with_context(|| {
             format!("Failed to open cost storage at {}", storage_path.display())
         })?;
+        storage.batch_size = config.batch_size.max(1);
+        storage.flush_interval = Duration::from_millis(config.flush_interval_ms);
 
         Ok(Self {
             config,
@@ -120,7 +123,7 @@ impl CostTracker {
 
         let record = CostRecord::new(&self.session_id, usage);
 
-        // Persist first for durability guarantees.
+        // Persist (or buffer, with `batch_size > 1`) before the session snapshot.
         {
             let mut storage = self.lock_storage();
             storage.add_record(record.clone())?;
@@ -164,15 +167,50 @@ impl CostTracker {
 
     /// Get the daily cost for a specific date.
     pub fn get_daily_cost(&self, date: NaiveDate) -> Result<f64> {
-        let storage = self.lock_storage();
+        let mut storage = self.lock_storage();
         storage.get_cost_for_date(date)
     }
 
     /// Get the monthly cost for a specific month.
     pub fn get_monthly_cost(&self, year: i32, month: u32) -> Result<f64> {
-        let storage = self.lock_storage();
+        let mut storage = self.lock_storage();
         storage.get_cost_for_month(year, month)
     }
+
+    /// Write buffered usage records to disk with a single fsync.
+    pub fn flush(&self) -> Result<()> {
+        self.lock_storage().flush()
+    }
+
+    /// Flush buffered usage records every `flush_interval_ms` from a
+    /// background task while the tracker is alive. Does nothing when records
+    /// are not buffered or no Tokio runtime is running.
+    pub fn spawn_flush_task(self: &Arc<Self>) {
+        if !self.config.enabled || self.config.batch_size <= 1 {
+            return;
+        }
+        let Ok(runtime) = tokio::runtime::Handle::try_current() else {
+            return;
+        };
+        let tracker = Arc::downgrade(self);
+        let period = Duration::from_millis(self.config.flush_interval_ms.max(1));
+        runtime.spawn(async move {
+            let mut ticker = tokio::time::interval(period);
+            ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            ticker.tick().await;
+            loop {
+                ticker.tick().await;
+                let Some(tracker) = tracker.upgrade() else {
+                    break;
+                };
+                match tokio::task::spawn_blocking(move || tracker.flush()).await {
+                    Ok(Ok(())) => {}
+                    Ok(Err(e)) => tracing::warn!("cost: periodic flush failed: {e}"),
+                    Err(e) => tracing::warn!("cost: periodic flush task failed: {e}"),
+                }
+            }
+        });
+    }
 }
 
 fn resolve_storage_path(workspace_dir: &Path) -> Result<PathBuf> {
@@ -226,6 +264,9 @@ fn build_session_model_stats(session_costs: &[CostRecord]) -> HashMap<String, Mo
 }
 
 /// Persistent storage for cost records.
+///
+/// Records are appended to the file in batches of `batch_size`; the
+/// aggregates include buffered records, and reads from the file flush first.
 struct CostStorage {
     path: PathBuf,
     daily_cost_usd: f64,
@@ -233,6 +274,11 @@ struct CostStorage {
     cached_day: NaiveDate,
     cached_year: i32,
     cached_month: u32,
+    /// Serialized records not yet written.
+    pending: Vec<String>,
+    batch_size: usize,
+    flush_interval: Duration,
+    last_flush: Instant,
 }
 
 impl CostStorage {
@@ -251,6 +297,10 @@ impl CostStorage {
             cached_day: now.date_naive(),
             cached_year: now.year(),
             cached_month: now.month(),
+            pending: Vec::new(),
+            batch_size: 1,
+            flush_interval: Duration::ZERO,
+            last_flush: Instant::now(),
         };
 
         storage.rebuild_aggregates(
@@ -304,6 +354,7 @@ impl CostStorage {
     }
 
     fn rebuild_aggregates(&mut self, day: NaiveDate, year: i32, month: u32) -> Result<()> {
+        self.flush()?;
         let mut daily_cost = 0.0;
         let mut monthly_cost = 0.0;
 
@@ -341,21 +392,38 @@ impl CostStorage {
         Ok(())
     }
 
-    /// Add a new record.
-    fn add_record(&mut self, record: CostRecord) -> Result<()> {
+    /// Write all buffered records with a single fsync.
+    fn flush(&mut self) -> Result<()> {
+        self.last_flush = Instant::now();
+        if self.pending.is_empty() {
+            return Ok(());
+        }
         let mut file = OpenOptions::new()
             .create(true)
             .append(true)
             .open(&self.path)
             .with_context(|| format!("Failed to open cost storage at {}", self.path.display()))?;
 
-        writeln!(file, "{}", serde_json::to_string(&record)?)
+        let mut payload = self.pending.join("\n");
+        payload.push('\n');
+        file.write_all(payload.as_bytes())
             .with_context(|| format!("Failed to write cost record to {}", self.path.display()))?;
         file.sync_all()
             .with_context(|| format!("Failed to sync cost storage at {}", self.path.display()))?;
+        self.pending.clear();
+        Ok(())
+    }
 
+    /// Add a new record.
+    fn add_record(&mut self, record: CostRecord) -> Result<()> {
         self.ensure_period_cache_current()?;
 
+        self.pending.push(serde_json::to_string(&record)?);
+        if self.pending.len() >= self.batch_size || self.last_flush.elapsed() >= self.flush_interval
+        {
+            self.flush()?;
+        }
+
         let timestamp = record.usage.timestamp.naive_utc();
         if timestamp.date() == self.cached_day {
             self.daily_cost_usd += record.usage.cost_usd;
@@ -374,7 +442,8 @@ impl CostStorage {
     }
 
     /// Get cost for a specific date.
-    fn get_cost_for_date(&self, date: NaiveDate) -> Result<f64> {
+    fn get_cost_for_date(&mut self, date: NaiveDate) -> Result<f64> {
+        self.flush()?;
         let mut cost = 0.0;
 
         self.for_each_record(|record| {
@@ -387,7 +456,8 @@ impl CostStorage {
     }
 
     /// Get cost for a specific month.
-    fn get_cost_for_month(&self, year: i32, month: u32) -> Result<f64> {
+    fn get_cost_for_month(&mut self, year: i32, month: u32) -> Result<f64> {
+        self.flush()?;
         let mut cost = 0.0;
 
         self.for_each_record(|record| {
@@ -401,6 +471,14 @@ impl CostStorage {
     }
 }
 
+impl Drop for CostStorage {
+    fn drop(&mut self) {
+        if let Err(error) = self.flush() {
+            tracing::warn!("cost: failed to flush buffered usage on shutdown: {error}");
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -466,6 +544,30 @@ mod tests {
         assert!(matches!(check, BudgetCheck::Exceeded { .. }));
     }
 
+    #[test]
+    fn buffered_usage_counts_toward_budget_and_flushes_on_drop() {
+        let tmp = TempDir::new().unwrap();
+        let config = CostConfig {
+            enabled: true,
+            daily_limit_usd: 0.01,
+            batch_size: 10,
+            flush_interval_ms: 60_000,
+            ..Default::default()
+        };
+        let tracker = CostTracker::new(config, tmp.path()).unwrap();
+        let storage_path = tmp.path().join("state").join("costs.jsonl");
+
+        let usage = TokenUsage::new("test/model", 10000, 5000, 1.0, 2.0);
+        tracker.record_usage(usage).unwrap();
+        assert!(!storage_path.exists());
+        let check = tracker.check_budget(0.01).unwrap();
+        assert!(matches!(check, BudgetCheck::Exceeded { .. }));
+
+        drop(tracker);
+        let content = fs::read_to_string(&storage_path).unwrap();
+        assert_eq!(content.lines().count(), 1);
+    }
+
     #[test]
     fn summary_by_model_is_session_scoped() {
         let tmp = TempDir::new().unwrap();
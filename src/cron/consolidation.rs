@@ -9,7 +9,8 @@ const DEFAULT_SCHEDULE_EXPR: &str = "0 3 * * *";
 pub const CONSOLIDATION_JOB_NAME: &str = "__consolidate_nightly";
 
 /// The prompt instructs the agent to perform memory consolidation using
-/// existing tools (cron_runs, memory_recall, memory_store, file_write).
+/// existing tools (cron_runs, consolidate_memory, memory_recall, memory_store,
+/// file_write).
 const CONSOLIDATION_PROMPT: &str = "\
 You are running a nightly memory consolidation job. Your goal is to distill \
 the past 24 hours of operational activity into a concise, actionable summary \
@@ -20,8 +21,9 @@ Follow these steps exactly:
 1. Use `cron_runs` to review recent job execution results from the past 24 hours. \
    Note any recurring errors, timeouts, or policy denials.
 
-2. Use `memory_recall` to retrieve today's Daily memories. Look for patterns, \
-   discoveries, and progress toward goals.
+2. Use `consolidate_memory` with action \"candidates\" to list today's \
+   short-term memories (daily notes and observations), and `memory_recall` to \
+   pull related context. Look for patterns, discoveries, and progress toward goals.
 
 3. Identify and classify findings:
    - **Recurring errors**: problems that appeared more than once
@@ -32,8 +34,11 @@ Follow these steps exactly:
 4. Synthesize a concise summary (max 500 words) of actionable learnings. \
    Focus on what should change going forward, not just what happened.
 
-5. Store the summary using `memory_store` with category \"core\" and \
-   key format \"consolidation_YYYY-MM-DD\" (use today's date).
+5. Store the summary using `consolidate_memory` with action \"promote\", \
+   passing the keys of the entries you drew from as `sources` and key format \
+   \"consolidation_YYYY-MM-DD\" (use today's date). This writes a \"core\" \
+   entry; if nothing was drawn from short-term memory, use `memory_store` \
+   with category \"core\" instead.
 
 6. If the workspace file `MEMORY.md` exists, use `file_read` to read it, \
    then use `file_write` to append a dated section at the end with the \
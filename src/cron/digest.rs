@@ -0,0 +1,175 @@
+use crate::config::Config;
+use crate::cron::{
+    add_agent_job, list_jobs, remove_job, update_job, CronJob, CronJobPatch, Schedule,
+    SessionTarget,
+};
+use anyhow::Result;
+
+/// Default cron expression: 11:30 PM daily.
+const DEFAULT_SCHEDULE_EXPR: &str = "30 23 * * *";
+
+/// Job name marker used to identify digest jobs.
+pub const DIGEST_JOB_NAME: &str = "__digest_daily";
+
+/// The prompt drives the `digest_memory` tool: list the day's notes, write
+/// the digest, store it.
+const DIGEST_PROMPT: &str = "\
+You are running the daily memory digest job. Your goal is to condense the \
+past 24 hours of memory notes into one short digest entry, so later recall \
+can read the digest instead of every raw note.
+
+Follow these steps exactly:
+
+1. Call `digest_memory` without a summary to list the notes written in the \
+   past 24 hours.
+
+2. Write a digest (max 300 words) grouped by topic: decisions made, facts \
+   learned, open questions, and follow-ups. Mention note keys where a detail \
+   may need to be looked up later. Do not copy notes verbatim.
+
+3. Call `digest_memory` again with `summary` set to the digest. It is stored \
+   under key \"digest_YYYY-MM-DD\" and tagged #digest.
+
+If `digest_memory` reports no notes, do not store a digest.";
+
+/// Create a daily memory digest cron agent job.
+///
+/// Schedule: 11:30 PM daily (local time), configurable via `schedule_expr`.
+/// Job type: agent with `__digest` marker in the name.
+/// Session target: isolated (does not disturb main sessions).
+pub fn create_digest_job(config: &Config) -> Result<CronJob> {
+    create_digest_job_with_schedule(config, DEFAULT_SCHEDULE_EXPR, None)
+}
+
+/// Create a digest job with a custom cron expression and optional timezone.
+pub fn create_digest_job_with_schedule(
+    config: &Config,
+    cron_expr: &str,
+    tz: Option<String>,
+) -> Result<CronJob> {
+    let schedule = Schedule::Cron {
+        expr: cron_expr.into(),
+        tz,
+    };
+
+    add_agent_job(
+        config,
+        Some(DIGEST_JOB_NAME.into()),
+        schedule,
+        DIGEST_PROMPT,
+        SessionTarget::Isolated,
+        None,  // use default model
+        None,  // no delivery config
+        false, // recurring job — do not delete after run
+    )
+}
+
+/// Bring the registered digest job in line with `[memory.digest]`: create it
+/// when enabled and missing, update its schedule when that changed, and
+/// remove it when disabled. Returns the job while enabled.
+pub fn ensure_digest_job(config: &Config) -> Result<Option<CronJob>> {
+    let digest = &config.memory.digest;
+    let existing = list_jobs(config)?
+        .into_iter()
+        .find(|job| job.name.as_deref() == Some(DIGEST_JOB_NAME));
+
+    if !digest.enabled {
+        if let Some(job) = existing {
+            remove_job(config, &job.id)?;
+        }
+        return Ok(None);
+    }
+
+    let schedule = Schedule::Cron {
+        expr: digest.schedule.clone(),
+        tz: digest.tz.clone(),
+    };
+    match existing {
+        Some(job) if job.schedule == schedule => Ok(Some(job)),
+        Some(job) => update_job(
+            config,
+            &job.id,
+            CronJobPatch {
+                schedule: Some(schedule),
+                ..CronJobPatch::default()
+            },
+        )
+        .map(Some),
+        None => {
+            create_digest_job_with_schedule(config, &digest.schedule, digest.tz.clone()).map(Some)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cron::JobType;
+    use tempfile::TempDir;
+
+    fn test_config(tmp: &TempDir) -> Config {
+        let config = Config {
+            workspace_dir: tmp.path().join("workspace"),
+            config_path: tmp.path().join("config.toml"),
+            ..Config::default()
+        };
+        std::fs::create_dir_all(&config.workspace_dir).unwrap();
+        config
+    }
+
+    #[test]
+    fn create_digest_job_produces_valid_job() {
+        let tmp = TempDir::new().unwrap();
+        let config = test_config(&tmp);
+
+        let job = create_digest_job(&config).unwrap();
+
+        assert_eq!(job.name.as_deref(), Some(DIGEST_JOB_NAME));
+        assert_eq!(job.job_type, JobType::Agent);
+        assert_eq!(job.session_target, SessionTarget::Isolated);
+        assert!(!job.delete_after_run);
+        match &job.schedule {
+            Schedule::Cron { expr, tz } => {
+                assert_eq!(expr, DEFAULT_SCHEDULE_EXPR);
+                assert!(tz.is_none());
+            }
+            other => panic!("Expected Cron schedule, got {other:?}"),
+        }
+
+        let prompt = job.prompt.expect("digest job must have a prompt");
+        assert!(prompt.contains("digest_memory"));
+        assert!(prompt.contains("digest_YYYY-MM-DD"));
+    }
+
+    #[test]
+    fn ensure_digest_job_follows_config() {
+        let tmp = TempDir::new().unwrap();
+        let mut config = test_config(&tmp);
+
+        assert!(ensure_digest_job(&config).unwrap().is_none());
+        assert!(list_jobs(&config).unwrap().is_empty());
+
+        config.memory.digest.enabled = true;
+        let job = ensure_digest_job(&config).unwrap().unwrap();
+        assert_eq!(job.name.as_deref(), Some(DIGEST_JOB_NAME));
+        // Registering again on the next startup keeps the one job.
+        let again = ensure_digest_job(&config).unwrap().unwrap();
+        assert_eq!(again.id, job.id);
+        assert_eq!(list_jobs(&config).unwrap().len(), 1);
+
+        config.memory.digest.schedule = "0 22 * * *".into();
+        let updated = ensure_digest_job(&config).unwrap().unwrap();
+        assert_eq!(updated.id, job.id);
+        assert_eq!(
+            updated.schedule,
+            Schedule::Cron {
+                expr: "0 22 * * *".into(),
+                tz: None
+            }
+        );
+
+        config.memory.digest.enabled = false;
+        assert!(ensure_digest_job(&config).unwrap().is_none());
+        assert!(list_jobs(&config).unwrap().is_empty());
+    }
+}
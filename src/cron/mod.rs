@@ -3,6 +3,7 @@ use crate::security::SecurityPolicy;
 use anyhow::{anyhow, bail, Result};
 
 pub mod consolidation;
+pub mod digest;
 mod schedule;
 mod store;
 mod types;
@@ -21,7 +22,7 @@ pub use store::{
 pub use types::{CronJob, CronJobPatch, CronRun, DeliveryConfig, JobType, Schedule, SessionTarget};
 
 fn validate_shell_command(config: &Config, command: &str, approved: bool) -> Result<()> {
-    let security = SecurityPolicy::from_config(&config.autonomy, &config.workspace_dir);
+    let security = SecurityPolicy::from_root_config(&config);
     security
         .validate_command_execution(command, approved)
         .map(|_| ())
@@ -77,6 +78,9 @@ pub fn handle_command(command: crate::CronCommands, config: &Config) -> Result<(
     match command {
         crate::CronCommands::List => {
             let jobs = list_jobs(config)?;
+            if crate::util::json_output() {
+                return crate::util::print_json(&jobs);
+            }
             if jobs.is_empty() {
                 println!("No scheduled tasks yet.");
                 println!("\nUsage:");
@@ -117,6 +121,9 @@ pub fn handle_command(command: crate::CronCommands, config: &Config) -> Result<(
                 tz,
             };
             let job = add_shell_job(config, None, schedule, &command)?;
+            if crate::util::json_output() {
+                return crate::util::print_json(&job);
+            }
             println!("✅ Added cron job {}", job.id);
             println!("  Expr: {}", job.expression);
             println!("  Next: {}", job.next_run.to_rfc3339());
@@ -129,6 +136,9 @@ pub fn handle_command(command: crate::CronCommands, config: &Config) -> Result<(
                 .with_timezone(&chrono::Utc);
             let schedule = Schedule::At { at };
             let job = add_shell_job(config, None, schedule, &command)?;
+            if crate::util::json_output() {
+                return crate::util::print_json(&job);
+            }
             println!("✅ Added one-shot cron job {}", job.id);
             println!("  At  : {}", job.next_run.to_rfc3339());
             println!("  Cmd : {}", job.command);
@@ -137,6 +147,9 @@ pub fn handle_command(command: crate::CronCommands, config: &Config) -> Result<(
         crate::CronCommands::AddEvery { every_ms, command } => {
             let schedule = Schedule::Every { every_ms };
             let job = add_shell_job(config, None, schedule, &command)?;
+            if crate::util::json_output() {
+                return crate::util::print_json(&job);
+            }
             println!("✅ Added interval cron job {}", job.id);
             println!("  Every(ms): {every_ms}");
             println!("  Next     : {}", job.next_run.to_rfc3339());
@@ -145,6 +158,9 @@ pub fn handle_command(command: crate::CronCommands, config: &Config) -> Result<(
         }
         crate::CronCommands::Once { delay, command } => {
             let job = add_once(config, &delay, &command)?;
+            if crate::util::json_output() {
+                return crate::util::print_json(&job);
+            }
             println!("✅ Added one-shot cron job {}", job.id);
             println!("  At  : {}", job.next_run.to_rfc3339());
             println!("  Cmd : {}", job.command);
@@ -189,20 +205,36 @@ pub fn handle_command(command: crate::CronCommands, config: &Config) -> Result<(
             };
 
             let job = update_shell_job(config, &id, patch)?;
+            if crate::util::json_output() {
+                return crate::util::print_json(&job);
+            }
             println!("\u{2705} Updated cron job {}", job.id);
             println!("  Expr: {}", job.expression);
             println!("  Next: {}", job.next_run.to_rfc3339());
             println!("  Cmd : {}", job.command);
             Ok(())
         }
-        crate::CronCommands::Remove { id } => remove_job(config, &id),
+        crate::CronCommands::Remove { id } => {
+            remove_job(config, &id)?;
+            if crate::util::json_output() {
+                return crate::util::print_json(&serde_json::json!({ "removed": id }));
+            }
+            println!("✅ Removed cron job {id}");
+            Ok(())
+        }
         crate::CronCommands::Pause { id } => {
-            pause_job(config, &id)?;
+            let job = pause_job(config, &id)?;
+            if crate::util::json_output() {
+                return crate::util::print_json(&job);
+            }
             println!("⏸️  Paused cron job {id}");
             Ok(())
         }
         crate::CronCommands::Resume { id } => {
-            resume_job(config, &id)?;
+            let job = resume_job(config, &id)?;
+            if crate::util::json_output() {
+                return crate::util::print_json(&job);
+            }
             println!("▶️  Resumed cron job {id}");
             Ok(())
         }
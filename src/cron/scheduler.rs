@@ -32,10 +32,11 @@ pub async fn run(config: Config) -> Result<()> {
     let poll_secs = config.reliability.scheduler_poll_secs.max(MIN_POLL_SECONDS);
     let mut interval = time::interval(Duration::from_secs(poll_secs));
     interval.set_missed_tick_behavior(time::MissedTickBehavior::Skip);
-    let security = Arc::new(SecurityPolicy::from_config(
-        &config.autonomy,
-        &config.workspace_dir,
-    ));
+    let security = Arc::new(SecurityPolicy::from_root_config(&config));
+
+    if let Err(e) = super::digest::ensure_digest_job(&config) {
+        tracing::warn!("Failed to register the memory digest job: {e}");
+    }
 
     crate::health::mark_component_ok(SCHEDULER_COMPONENT);
 
@@ -66,7 +67,7 @@ pub async fn execute_job_now_with_approval(
     job: &CronJob,
     approved: bool,
 ) -> (bool, String) {
-    let security = SecurityPolicy::from_config(&config.autonomy, &config.workspace_dir);
+    let security = SecurityPolicy::from_root_config(&config);
     execute_job_with_retry(config, &security, job, approved).await
 }
 
@@ -608,10 +609,10 @@ async fn run_job_command_with_timeout(
         .stdin(Stdio::null())
         .stdout(Stdio::piped())
         .stderr(Stdio::piped())
-        .kill_on_drop(true)
-        // Keep shell child behavior deterministic under CI wrappers that set ENV/BASH_ENV.
-        .env_remove("ENV")
-        .env_remove("BASH_ENV");
+        .kill_on_drop(true);
+    // Only the policy's allowlisted variables; this also drops ENV/BASH_ENV,
+    // keeping shell child behavior deterministic under CI wrappers.
+    security.env_filter().apply(command.as_std_mut());
 
     let child = match command.spawn() {
         Ok(child) => child,
@@ -695,6 +696,19 @@ mod tests {
         config
     }
 
+    #[tokio::test]
+    async fn run_registers_the_digest_job_when_enabled() {
+        let tmp = TempDir::new().unwrap();
+        let mut config = test_config(&tmp).await;
+        config.memory.digest.enabled = true;
+
+        let _ = time::timeout(Duration::from_millis(200), run(config.clone())).await;
+
+        let jobs = cron::list_jobs(&config).unwrap();
+        assert_eq!(jobs.len(), 1);
+        assert_eq!(jobs[0].name.as_deref(), Some(cron::digest::DIGEST_JOB_NAME));
+    }
+
     fn test_job(command: &str) -> CronJob {
         CronJob {
             id: "test-job".into(),
@@ -755,6 +769,23 @@ mod tests {
         assert!(output.contains("scheduler-ok"));
     }
 
+    #[tokio::test]
+    async fn run_job_command_does_not_leak_secrets() {
+        let _env = env_lock().await;
+        let tmp = TempDir::new().unwrap();
+        let mut config = test_config(&tmp).await;
+        config.autonomy.allowed_commands.push("env".into());
+        let _secret = EnvGuard::set("ZEROCLAW_CRON_TEST_API_KEY", "sk-cron-secret");
+
+        let job = test_job("env");
+        let security = SecurityPolicy::from_config(&config.autonomy, &config.workspace_dir);
+
+        let (success, output) = run_job_command(&config, &security, &job).await;
+        assert!(success, "{output}");
+        assert!(output.contains("PATH="));
+        assert!(!output.contains("sk-cron-secret"));
+    }
+
     #[tokio::test]
     async fn run_job_command_failure() {
         let tmp = TempDir::new().unwrap();
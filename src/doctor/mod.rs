@@ -82,6 +82,7 @@ pub fn diagnose(config: &Config) -> Vec<DiagResult> {
     check_config_semantics(config, &mut items);
     check_runtime_capabilities(config, &mut items);
     check_workspace(config, &mut items);
+    check_memory_index(config, &mut items);
     check_daemon_state(config, &mut items);
     check_environment(&mut items);
     check_cli_tools(&mut items);
@@ -92,6 +93,25 @@ pub fn diagnose(config: &Config) -> Vec<DiagResult> {
 /// Run diagnostics and print human-readable report to stdout.
 pub fn run(config: &Config) -> Result<()> {
     let results = diagnose(config);
+    let errors = results
+        .iter()
+        .filter(|i| i.severity == Severity::Error)
+        .count();
+    let warns = results
+        .iter()
+        .filter(|i| i.severity == Severity::Warn)
+        .count();
+    let oks = results
+        .iter()
+        .filter(|i| i.severity == Severity::Ok)
+        .count();
+
+    if crate::util::json_output() {
+        return crate::util::print_json(&serde_json::json!({
+            "results": results,
+            "summary": { "ok": oks, "warnings": warns, "errors": errors },
+        }));
+    }
 
     // Print report
     println!("🩺 ZeroClaw Doctor (enhanced)");
@@ -111,19 +131,6 @@ pub fn run(config: &Config) -> Result<()> {
         println!("    {} {}", icon, item.message);
     }
 
-    let errors = results
-        .iter()
-        .filter(|i| i.severity == Severity::Error)
-        .count();
-    let warns = results
-        .iter()
-        .filter(|i| i.severity == Severity::Warn)
-        .count();
-    let oks = results
-        .iter()
-        .filter(|i| i.severity == Severity::Ok)
-        .count();
-
     println!();
     println!("  Summary: {oks} ok, {warns} warnings, {errors} errors");
 
@@ -837,6 +844,85 @@ fn check_workspace(config: &Config, items: &mut Vec<DiagItem>) {
     check_file_exists(ws, "AGENTS.md", false, cat, items);
 }
 
+fn check_memory_index(config: &Config, items: &mut Vec<DiagItem>) {
+    use crate::memory::{classify_memory_backend, MemoryBackendKind, SqliteMemory};
+
+    let cat = "memory";
+    if !matches!(
+        classify_memory_backend(&config.memory.backend),
+        MemoryBackendKind::Sqlite
+            | MemoryBackendKind::SqliteQdrantHybrid
+            | MemoryBackendKind::Lucid
+            | MemoryBackendKind::CortexMem
+    ) {
+        return;
+    }
+
+    let db_path = config.workspace_dir.join("memory").join("brain.db");
+    if !db_path.exists() {
+        items.push(DiagItem::ok(cat, "search index not created yet"));
+        return;
+    }
+
+    let expected_dims = if config.memory.embedding_provider.trim() == "none" {
+        0
+    } else {
+        config.memory.embedding_dimensions
+    };
+    let health = match SqliteMemory::index_health(&db_path, expected_dims) {
+        Ok(health) => health,
+        Err(e) => {
+            items.push(DiagItem::error(
+                cat,
+                format!(
+                    "cannot inspect {}: {}",
+                    db_path.display(),
+                    format_error_chain(&e)
+                ),
+            ));
+            return;
+        }
+    };
+
+    match &health.fts_error {
+        None => items.push(DiagItem::ok(
+            cat,
+            format!(
+                "keyword index consistent ({} memories, checksum verified)",
+                health.memories
+            ),
+        )),
+        Some(err) => items.push(DiagItem::error(
+            cat,
+            format!("keyword index out of sync: {err} — run `zeroclaw memory reindex`"),
+        )),
+    }
+
+    if expected_dims > 0 {
+        if health.mismatched_embeddings > 0 {
+            items.push(DiagItem::error(
+                cat,
+                format!(
+                    "{} embeddings do not match embedding_dimensions = {expected_dims} — run `zeroclaw memory reindex`",
+                    health.mismatched_embeddings
+                ),
+            ));
+        }
+        if health.missing_embeddings > 0 {
+            items.push(DiagItem::warn(
+                cat,
+                format!(
+                    "{} memories have no embedding yet — run `zeroclaw memory reindex`",
+                    health.missing_embeddings
+                ),
+            ));
+        }
+        if health.mismatched_embeddings == 0 && health.missing_embeddings == 0 {
+            items.push(DiagItem::ok(cat, "vector index covers all memories"));
+        }
+    }
+}
+
 fn check_file_exists(
     base: &Path,
     name: &str,
@@ -1398,6 +1484,33 @@ mod tests {
             .is_some_and(|name| name.starts_with(".zeroclaw_doctor_probe_")));
     }
 
+    #[tokio::test]
+    async fn memory_index_check_verifies_sqlite_checksums() {
+        use crate::memory::{Memory, MemoryCategory, SqliteMemory};
+
+        let tmp = TempDir::new().unwrap();
+        let mut config = Config::default();
+        config.workspace_dir = tmp.path().to_path_buf();
+        config.memory.backend = "sqlite".into();
+        config.memory.embedding_provider = "none".into();
+
+        let mut items = Vec::new();
+        check_memory_index(&config, &mut items);
+        assert!(items[0].message.contains("not created yet"));
+
+        let mem = SqliteMemory::new(tmp.path()).unwrap();
+        mem.store("k", "indexed", MemoryCategory::Core, None)
+            .await
+            .unwrap();
+        drop(mem);
+
+        let mut items = Vec::new();
+        check_memory_index(&config, &mut items);
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].severity, Severity::Ok);
+        assert!(items[0].message.contains("1 memories, checksum verified"));
+    }
+
     #[test]
     fn config_validation_reports_delegate_agents_in_sorted_order() {
         let mut config = Config::default();
@@ -0,0 +1,234 @@
+//! `zeroclaw export` — render agent state for external tools.
+//!
+//! Also hosts `zeroclaw sessions export`, which renders a single session as a
+//! shareable transcript.
+
+pub mod obsidian;
+pub mod sqlite;
+pub mod transcript;
+
+use crate::agent::session::{SessionManager, SqliteSessionManager};
+use crate::config::Config;
+use crate::providers::ChatMessage;
+use anyhow::{bail, Context, Result};
+use console::style;
+use std::path::Path;
+use std::time::Duration;
+
+/// Handle `zeroclaw export <subcommand>` CLI commands.
+pub async fn handle_command(command: crate::ExportCommands, config: &Config) -> Result<()> {
+    match command {
+        crate::ExportCommands::Obsidian { dir, force } => {
+            handle_obsidian(config, Path::new(&dir), force).await
+        }
+        crate::ExportCommands::Sqlite { file, force } => {
+            handle_sqlite(config, Path::new(&file), force).await
+        }
+    }
+}
+
+/// Handle `zeroclaw sessions <subcommand>` CLI commands.
+pub async fn handle_session_command(
+    command: crate::SessionCommands,
+    config: &Config,
+) -> Result<()> {
+    match command {
+        crate::SessionCommands::Export {
+            id,
+            format,
+            redact,
+            output,
+        } => handle_session_export(config, &id, &format, redact, output.as_deref()).await,
+    }
+}
+
+/// History of session `id` from the sqlite session store.
+pub async fn load_session_history(config: &Config, id: &str) -> Result<Vec<ChatMessage>> {
+    let db_path = SqliteSessionManager::default_db_path(&config.workspace_dir);
+    if !db_path.exists() {
+        bail!(
+            "No stored sessions found at {}. Sessions are only persisted with \
+             [agent.session] backend = \"sqlite\".",
+            db_path.display()
+        );
+    }
+    let manager = SqliteSessionManager::new(db_path, Duration::ZERO, 0)?;
+    let known = manager.list_sessions().await?;
+    if !known.iter().any(|s| s == id) {
+        let hint = if known.is_empty() {
+            "No sessions are stored yet.".to_string()
+        } else {
+            format!("Known sessions: {}", known.join(", "))
+        };
+        bail!("Session '{id}' not found. {hint}");
+    }
+    manager.get_history(id).await
+}
+
+async fn handle_session_export(
+    config: &Config,
+    id: &str,
+    format: &str,
+    redact: bool,
+    output: Option<&str>,
+) -> Result<()> {
+    let format = transcript::TranscriptFormat::parse(format)?;
+    let items = transcript::parse_history(&load_session_history(config, id).await?);
+    let options = transcript::TranscriptOptions {
+        redact,
+        home_dir: directories::BaseDirs::new().map(|d| d.home_dir().to_string_lossy().into_owned()),
+    };
+    let document = transcript::render(id, &items, format, &options);
+
+    if let Some(path) = output {
+        std::fs::write(path, &document).with_context(|| format!("Failed to write {path}"))?;
+        if crate::util::json_output() {
+            return crate::util::print_json(&serde_json::json!({
+                "session": id,
+                "format": format.extension(),
+                "path": path,
+            }));
+        }
+        println!(
+            "{} Exported session {id} ({} items) to {path}",
+            style("✓").green().bold(),
+            items.len()
+        );
+    } else if crate::util::json_output() {
+        crate::util::print_json(&serde_json::json!({
+            "session": id,
+            "format": format.extension(),
+            "document": document,
+        }))?;
+    } else {
+        print!("{document}");
+    }
+    Ok(())
+}
+
+async fn handle_obsidian(config: &Config, dir: &Path, force: bool) -> Result<()> {
+    ensure_export_dir(dir, force)?;
+
+    let mem = crate::memory::cli::create_cli_memory(config)?;
+    let entries = mem.list(None, None).await?;
+    let sessions = load_session_summaries(&config.workspace_dir).await?;
+
+    let report = obsidian::write_vault(dir, &entries, &sessions)?;
+    if crate::util::json_output() {
+        return crate::util::print_json(&serde_json::json!({
+            "dir": dir,
+            "report": report,
+        }));
+    }
+    println!(
+        "{} Exported {} memories, {} categories, {} sessions ({} notes) to {}",
+        style("✓").green().bold(),
+        report.memories,
+        report.categories,
+        report.sessions,
+        report.files_written,
+        dir.display()
+    );
+    Ok(())
+}
+
+async fn handle_sqlite(config: &Config, file: &Path, force: bool) -> Result<()> {
+    let mut data = sqlite::ExportData::default();
+
+    let zeroclaw_dir = config.config_path.parent().unwrap_or(Path::new("."));
+    crate::security::audit::migrate_legacy_log(&config.security.audit, zeroclaw_dir)?;
+    let audit_log = zeroclaw_dir.join(&config.security.audit.log_path);
+    for path in sqlite::audit_log_files(&audit_log) {
+        let (events, skipped) = sqlite::read_jsonl(&path)?;
+        data.audit_events.extend(events);
+        data.skipped_lines += skipped;
+    }
+    data.sources
+        .push(("audit".into(), audit_log.display().to_string()));
+
+    let usage_path = config.workspace_dir.join("state").join("costs.jsonl");
+    let (usage, skipped) = sqlite::read_jsonl(&usage_path)?;
+    data.usage = usage;
+    data.skipped_lines += skipped;
+    data.sources
+        .push(("usage".into(), usage_path.display().to_string()));
+
+    let mem = crate::memory::cli::create_cli_memory(config)?;
+    data.memories = mem.list(None, None).await?;
+    data.sources.push(("memory".into(), mem.name().to_string()));
+
+    let report = sqlite::write_database(file, &data, force)?;
+    if crate::util::json_output() {
+        return crate::util::print_json(&serde_json::json!({
+            "file": file,
+            "report": report,
+        }));
+    }
+    println!(
+        "{} Exported {} audit events, {} memories, {} usage records to {}",
+        style("✓").green().bold(),
+        report.audit_events,
+        report.memories,
+        report.usage_records,
+        file.display()
+    );
+    if report.skipped_lines > 0 {
+        println!(
+            "  {} Skipped {} malformed log lines",
+            style("!").yellow(),
+            report.skipped_lines
+        );
+    }
+    Ok(())
+}
+
+/// Refuse to scatter notes into an unrelated non-empty directory unless forced.
+fn ensure_export_dir(dir: &Path, force: bool) -> Result<()> {
+    if dir.is_file() {
+        bail!("Export target {} is a file", dir.display());
+    }
+    let non_empty = dir.is_dir() && std::fs::read_dir(dir)?.next().is_some();
+    if non_empty && !force {
+        bail!(
+            "Export target {} is not empty. Pass --force to write into it anyway.",
+            dir.display()
+        );
+    }
+    Ok(())
+}
+
+/// Read persisted agent sessions, if the SQLite session store exists.
+///
+/// Opens the store with a zero TTL so exporting never expires sessions.
+async fn load_session_summaries(workspace_dir: &Path) -> Result<Vec<obsidian::SessionSummary>> {
+    let db_path = SqliteSessionManager::default_db_path(workspace_dir);
+    if !db_path.exists() {
+        return Ok(Vec::new());
+    }
+    let manager = SqliteSessionManager::new(db_path, Duration::ZERO, 0)?;
+    let mut summaries = Vec::new();
+    for id in manager.list_sessions().await? {
+        let messages = manager.get_history(&id).await?;
+        summaries.push(obsidian::SessionSummary { id, messages });
+    }
+    Ok(summaries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn export_dir_must_be_empty_unless_forced() {
+        let tmp = TempDir::new().unwrap();
+        assert!(ensure_export_dir(&tmp.path().join("vault"), false).is_ok());
+        assert!(ensure_export_dir(tmp.path(), false).is_ok());
+
+        std::fs::write(tmp.path().join("existing.md"), "keep").unwrap();
+        let err = ensure_export_dir(tmp.path(), false).unwrap_err();
+        assert!(err.to_string().contains("--force"));
+        assert!(ensure_export_dir(tmp.path(), true).is_ok());
+        assert!(ensure_export_dir(&tmp.path().join("existing.md"), true).is_err());
+    }
+}
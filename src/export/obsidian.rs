@@ -0,0 +1,476 @@
+//! Render agent state as an Obsidian vault.
+//!
+//! Layout (all notes are plain Markdown with YAML frontmatter):
+//!
+//! ```text
+//! <dir>/ZeroClaw.md                       — vault home, links every index note
+//! <dir>/Memory/<key>.md                   — one note per memory entry
+//! <dir>/Categories/Category - <name>.md   — entries grouped by category
+//! <dir>/Sessions/Session - <id>.md        — per-session summary
+//! ```
+//!
+//! Memory notes link to their category and session notes and to any other
+//! entry whose key they mention, so Obsidian's backlink pane and graph view
+//! show how the agent's knowledge connects.
+
+use crate::memory::MemoryEntry;
+use crate::providers::ChatMessage;
+use crate::util::truncate_with_ellipsis;
+use anyhow::{Context, Result};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt::Write;
+use std::path::Path;
+
+/// Characters of a session message quoted in its summary note.
+const SESSION_EXCERPT_CHARS: usize = 280;
+
+/// Minimum key length considered when linking entries that mention each other;
+/// shorter keys produce too many accidental matches.
+const MIN_MENTION_KEY_CHARS: usize = 4;
+
+/// A conversation session to summarize in the vault.
+#[derive(Debug, Clone)]
+pub struct SessionSummary {
+    pub id: String,
+    pub messages: Vec<ChatMessage>,
+}
+
+/// Counts reported after a vault export.
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize)]
+pub struct VaultReport {
+    pub memories: usize,
+    pub categories: usize,
+    pub sessions: usize,
+    pub files_written: usize,
+}
+
+/// Write `entries` and `sessions` into `dir` as an interlinked vault.
+///
+/// Existing files with the same names are overwritten; other files in `dir`
+/// are left untouched.
+pub fn write_vault(
+    dir: &Path,
+    entries: &[MemoryEntry],
+    sessions: &[SessionSummary],
+) -> Result<VaultReport> {
+    for sub in ["Memory", "Categories", "Sessions"] {
+        std::fs::create_dir_all(dir.join(sub))
+            .with_context(|| format!("Failed to create {}", dir.join(sub).display()))?;
+    }
+
+    let mut names = NoteNames::default();
+    let note_names: Vec<String> = entries
+        .iter()
+        .map(|entry| names.claim(&sanitize_note_name(&entry.key)))
+        .collect();
+    let mut by_category: BTreeMap<String, Vec<usize>> = BTreeMap::new();
+    let mut by_session: BTreeMap<String, Vec<usize>> = BTreeMap::new();
+    for (idx, entry) in entries.iter().enumerate() {
+        by_category
+            .entry(entry.category.to_string())
+            .or_default()
+            .push(idx);
+        if let Some(session) = entry.session_id.as_deref().filter(|s| !s.is_empty()) {
+            by_session.entry(session.to_string()).or_default().push(idx);
+        }
+    }
+
+    let mut session_ids: Vec<&str> = sessions.iter().map(|s| s.id.as_str()).collect();
+    for id in by_session.keys() {
+        if !session_ids.contains(&id.as_str()) {
+            session_ids.push(id.as_str());
+        }
+    }
+    // Wikilinks resolve by note name anywhere in the vault, so categories
+    // and sessions draw from the same pool of names as memories.
+    let category_names: HashMap<&str, String> = by_category
+        .keys()
+        .map(|category| (category.as_str(), names.claim(&category_note(category))))
+        .collect();
+    let session_names: HashMap<&str, String> = session_ids
+        .iter()
+        .map(|id| (*id, names.claim(&session_note(id))))
+        .collect();
+
+    let mut files_written = 0;
+    let mut write = |relative: String, body: String| -> Result<()> {
+        let path = dir.join(&relative);
+        std::fs::write(&path, body)
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+        files_written += 1;
+        Ok(())
+    };
+
+    for (idx, entry) in entries.iter().enumerate() {
+        let mentions = mentioned_entries(entry, entries, &note_names, idx);
+        let category = &category_names[entry.category.to_string().as_str()];
+        let session = entry
+            .session_id
+            .as_deref()
+            .and_then(|id| session_names.get(id))
+            .map(String::as_str);
+        write(
+            format!("Memory/{}.md", note_names[idx]),
+            render_memory_note(entry, category, session, &mentions),
+        )?;
+    }
+
+    for (category, members) in &by_category {
+        let mut body = frontmatter(&[
+            ("category", yaml_str(category)),
+            ("entries", members.len().to_string()),
+            ("tags", "[zeroclaw/category]".into()),
+        ]);
+        let _ = writeln!(body, "# Category: {category}\n");
+        for &idx in members {
+            let _ = writeln!(body, "- [[{}]]", note_names[idx]);
+        }
+        write(
+            format!("Categories/{}.md", category_names[category.as_str()]),
+            body,
+        )?;
+    }
+
+    let transcripts: HashMap<&str, &SessionSummary> =
+        sessions.iter().map(|s| (s.id.as_str(), s)).collect();
+    for id in &session_ids {
+        let members = by_session.get(*id).map_or(&[][..], Vec::as_slice);
+        write(
+            format!("Sessions/{}.md", session_names[id]),
+            render_session_note(id, transcripts.get(id).copied(), members, &note_names),
+        )?;
+    }
+
+    let mut home = frontmatter(&[("tags", "[zeroclaw/home]".into())]);
+    home.push_str("# ZeroClaw\n\n");
+    let _ = writeln!(
+        home,
+        "{} memories, {} categories, {} sessions.\n",
+        entries.len(),
+        by_category.len(),
+        session_ids.len()
+    );
+    home.push_str("## Categories\n\n");
+    for (category, members) in &by_category {
+        let _ = writeln!(
+            home,
+            "- [[{}]] ({})",
+            category_names[category.as_str()],
+            members.len()
+        );
+    }
+    if !session_ids.is_empty() {
+        home.push_str("\n## Sessions\n\n");
+        for id in &session_ids {
+            let _ = writeln!(home, "- [[{}]]", session_names[id]);
+        }
+    }
+    write("ZeroClaw.md".into(), home)?;
+
+    Ok(VaultReport {
+        memories: entries.len(),
+        categories: by_category.len(),
+        sessions: session_ids.len(),
+        files_written,
+    })
+}
+
+fn render_memory_note(
+    entry: &MemoryEntry,
+    category_note: &str,
+    session_note: Option<&str>,
+    mentions: &[&str],
+) -> String {
+    let category = entry.category.to_string();
+    let mut fields = vec![
+        ("key", yaml_str(&entry.key)),
+        ("id", yaml_str(&entry.id)),
+        ("category", yaml_str(&category)),
+        ("timestamp", yaml_str(&entry.timestamp)),
+    ];
+    if let Some(session) = entry.session_id.as_deref().filter(|s| !s.is_empty()) {
+        fields.push(("session", yaml_str(session)));
+    }
+    fields.push((
+        "tags",
+        format!("[zeroclaw/memory, zeroclaw/{}]", tag_segment(&category)),
+    ));
+
+    let mut body = frontmatter(&fields);
+    let _ = writeln!(body, "# {}\n", entry.key);
+    body.push_str(entry.content.trim());
+    body.push_str("\n\n## Links\n\n");
+    let _ = writeln!(body, "- Category: [[{category_note}]]");
+    if let Some(session_note) = session_note {
+        let _ = writeln!(body, "- Session: [[{session_note}]]");
+    }
+    for name in mentions {
+        let _ = writeln!(body, "- Mentions: [[{name}]]");
+    }
+    body
+}
+
+fn render_session_note(
+    id: &str,
+    transcript: Option<&SessionSummary>,
+    members: &[usize],
+    note_names: &[String],
+) -> String {
+    let messages = transcript.map_or(&[][..], |t| t.messages.as_slice());
+    let user_turns = messages.iter().filter(|m| m.role == "user").count();
+    let assistant_turns = messages.iter().filter(|m| m.role == "assistant").count();
+
+    let mut body = frontmatter(&[
+        ("session", yaml_str(id)),
+        ("messages", messages.len().to_string()),
+        ("memories", members.len().to_string()),
+        ("tags", "[zeroclaw/session]".into()),
+    ]);
+    let _ = writeln!(body, "# Session: {id}\n");
+    let _ = writeln!(
+        body,
+        "{user_turns} user turns, {assistant_turns} assistant turns, {} memories.\n",
+        members.len()
+    );
+
+    if let Some(first) = messages.iter().find(|m| m.role == "user") {
+        let _ = writeln!(
+            body,
+            "**Opened with:** {}\n",
+            one_line(&first.content, SESSION_EXCERPT_CHARS)
+        );
+    }
+    if let Some(last) = messages.iter().rev().find(|m| m.role == "assistant") {
+        let _ = writeln!(
+            body,
+            "**Last reply:** {}\n",
+            one_line(&last.content, SESSION_EXCERPT_CHARS)
+        );
+    }
+
+    if !members.is_empty() {
+        body.push_str("## Memories\n\n");
+        for &idx in members {
+            let _ = writeln!(body, "- [[{}]]", note_names[idx]);
+        }
+    }
+    body
+}
+
+/// Other entries whose key appears in `entry`'s content.
+fn mentioned_entries<'a>(
+    entry: &MemoryEntry,
+    entries: &[MemoryEntry],
+    note_names: &'a [String],
+    self_idx: usize,
+) -> Vec<&'a str> {
+    let content = entry.content.to_lowercase();
+    entries
+        .iter()
+        .enumerate()
+        .filter(|(idx, other)| {
+            *idx != self_idx
+                && other.key.chars().count() >= MIN_MENTION_KEY_CHARS
+                && content.contains(&other.key.to_lowercase())
+        })
+        .map(|(idx, _)| note_names[idx].as_str())
+        .collect()
+}
+
+/// Note names handed out so far. Names are compared case-insensitively, as
+/// on macOS and Windows filesystems, and a taken name gets a ` (2)`, ` (3)`,
+/// … suffix so no two notes share a file.
+#[derive(Default)]
+struct NoteNames {
+    used: HashSet<String>,
+}
+
+impl NoteNames {
+    fn claim(&mut self, base: &str) -> String {
+        let mut name = base.to_string();
+        let mut n = 2;
+        while !self.used.insert(name.to_lowercase()) {
+            name = format!("{base} ({n})");
+            n += 1;
+        }
+        name
+    }
+}
+
+fn sanitize_note_name(raw: &str) -> String {
+    let cleaned: String = raw
+        .trim()
+        .chars()
+        .map(|c| match c {
+            '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' | '#' | '^' | '[' | ']' => '-',
+            c if c.is_control() => '-',
+            c => c,
+        })
+        .collect();
+    let cleaned = cleaned.trim_matches(|c: char| c == '.' || c.is_whitespace());
+    if cleaned.is_empty() {
+        "untitled".into()
+    } else {
+        truncate_chars(cleaned, 120)
+    }
+}
+
+fn category_note(category: &str) -> String {
+    format!("Category - {}", sanitize_note_name(category))
+}
+
+fn session_note(id: &str) -> String {
+    format!("Session - {}", sanitize_note_name(id))
+}
+
+fn tag_segment(raw: &str) -> String {
+    raw.chars()
+        .map(|c| {
+            if c.is_alphanumeric() || c == '_' || c == '-' {
+                c
+            } else {
+                '-'
+            }
+        })
+        .collect()
+}
+
+fn truncate_chars(s: &str, max_chars: usize) -> String {
+    s.chars().take(max_chars).collect()
+}
+
+fn one_line(s: &str, max_chars: usize) -> String {
+    truncate_with_ellipsis(
+        &s.split_whitespace().collect::<Vec<_>>().join(" "),
+        max_chars,
+    )
+}
+
+/// JSON string literals are valid YAML double-quoted scalars.
+fn yaml_str(value: &str) -> String {
+    serde_json::to_string(value).unwrap_or_else(|_| "\"\"".into())
+}
+
+fn frontmatter(fields: &[(&str, String)]) -> String {
+    let mut out = String::from("---\n");
+    for (key, value) in fields {
+        let _ = writeln!(out, "{key}: {value}");
+    }
+    out.push_str("---\n\n");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::MemoryCategory;
+    use tempfile::TempDir;
+
+    fn entry(
+        key: &str,
+        content: &str,
+        category: MemoryCategory,
+        session: Option<&str>,
+    ) -> MemoryEntry {
+        MemoryEntry {
+            id: format!("id-{key}"),
+            key: key.into(),
+            content: content.into(),
+            category,
+            timestamp: "2026-03-01T10:00:00Z".into(),
+            session_id: session.map(str::to_string),
+            score: None,
+        }
+    }
+
+    #[test]
+    fn vault_links_entries_categories_and_sessions() {
+        let tmp = TempDir::new().unwrap();
+        let entries = vec![
+            entry("rust_pref", "User prefers Rust", MemoryCategory::Core, None),
+            entry(
+                "deploy/notes",
+                "Deploys use rust_pref tooling",
+                MemoryCategory::Daily,
+                Some("telegram:alice"),
+            ),
+        ];
+        let sessions = vec![SessionSummary {
+            id: "telegram:alice".into(),
+            messages: vec![
+                ChatMessage::user("How do we deploy?"),
+                ChatMessage::assistant("With cargo."),
+            ],
+        }];
+
+        let report = write_vault(tmp.path(), &entries, &sessions).unwrap();
+        assert_eq!(report.memories, 2);
+        assert_eq!(report.categories, 2);
+        assert_eq!(report.sessions, 1);
+        assert_eq!(report.files_written, 2 + 2 + 1 + 1);
+
+        let note = std::fs::read_to_string(tmp.path().join("Memory/deploy-notes.md")).unwrap();
+        assert!(note.starts_with("---\nkey: \"deploy/notes\"\n"));
+        assert!(note.contains("- Category: [[Category - daily]]"));
+        assert!(note.contains("- Session: [[Session - telegram-alice]]"));
+        assert!(note.contains("- Mentions: [[rust_pref]]"));
+
+        let session =
+            std::fs::read_to_string(tmp.path().join("Sessions/Session - telegram-alice.md"))
+                .unwrap();
+        assert!(session.contains("**Opened with:** How do we deploy?"));
+        assert!(session.contains("- [[deploy-notes]]"));
+
+        let home = std::fs::read_to_string(tmp.path().join("ZeroClaw.md")).unwrap();
+        assert!(home.contains("[[Category - core]] (1)"));
+    }
+
+    #[test]
+    fn note_names_are_sanitized_and_unique() {
+        let mut names = NoteNames::default();
+        let claimed: Vec<String> = ["a:b", "a/b", "A|B", "  "]
+            .iter()
+            .map(|key| names.claim(&sanitize_note_name(key)))
+            .collect();
+        assert_eq!(claimed, vec!["a-b", "a-b (2)", "A-B (3)", "untitled"]);
+    }
+
+    #[test]
+    fn colliding_categories_and_sessions_get_separate_notes() {
+        let tmp = tempfile::tempdir().unwrap();
+        let entries = vec![
+            entry(
+                "one",
+                "x",
+                MemoryCategory::Custom("a:b".into()),
+                Some("tg:1"),
+            ),
+            entry(
+                "two",
+                "y",
+                MemoryCategory::Custom("a/b".into()),
+                Some("tg/1"),
+            ),
+        ];
+        let report = write_vault(tmp.path(), &entries, &[]).unwrap();
+        assert_eq!(report.categories, 2);
+        assert_eq!(report.sessions, 2);
+
+        let categories = tmp.path().join("Categories");
+        assert!(categories.join("Category - a-b.md").exists());
+        assert!(categories.join("Category - a-b (2).md").exists());
+        let sessions = tmp.path().join("Sessions");
+        assert!(sessions.join("Session - tg-1.md").exists());
+        assert!(sessions.join("Session - tg-1 (2).md").exists());
+
+        // Names are claimed in sorted order, and `/` sorts before `:`.
+        let one = std::fs::read_to_string(tmp.path().join("Memory/one.md")).unwrap();
+        assert!(one.contains("[[Category - a-b (2)]]"));
+        assert!(one.contains("[[Session - tg-1 (2)]]"));
+    }
+
+    #[test]
+    fn yaml_strings_escape_quotes_and_newlines() {
+        assert_eq!(yaml_str("say \"hi\"\nnow"), r#""say \"hi\"\nnow""#);
+    }
+}
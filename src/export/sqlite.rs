@@ -0,0 +1,375 @@
+//! Relational SQLite dump of audit, memory, and usage data.
+//!
+//! Audit events and cost records live in append-only JSONL files and memory
+//! lives behind the configured backend, so ad-hoc questions ("which channel
+//! triggered the most denied commands last week?") otherwise need bespoke
+//! scripts. This module flattens all three into plain tables:
+//!
+//! ```text
+//! audit_events  — one row per security audit event (actor/action/result flattened)
+//! memories      — one row per memory entry
+//! usage         — one row per LLM call cost record
+//! export_meta   — exported_at, zeroclaw version, source paths
+//! ```
+
+use crate::cost::CostRecord;
+use crate::memory::MemoryEntry;
+use crate::security::AuditEvent;
+use anyhow::{bail, Context, Result};
+use rusqlite::{params, Connection};
+use serde::de::DeserializeOwned;
+use std::path::{Path, PathBuf};
+
+const SCHEMA: &str = "
+CREATE TABLE export_meta (
+    key   TEXT PRIMARY KEY,
+    value TEXT NOT NULL
+);
+CREATE TABLE audit_events (
+    event_id             TEXT PRIMARY KEY,
+    timestamp            TEXT NOT NULL,
+    event_type           TEXT NOT NULL,
+    channel              TEXT,
+    user_id              TEXT,
+    username             TEXT,
+    command              TEXT,
+    risk_level           TEXT,
+    approved             INTEGER,
+    allowed              INTEGER,
+    success              INTEGER,
+    exit_code            INTEGER,
+    duration_ms          INTEGER,
+    error                TEXT,
+    policy_violation     INTEGER NOT NULL,
+    rate_limit_remaining INTEGER,
+    sandbox_backend      TEXT
+);
+CREATE INDEX idx_audit_events_timestamp ON audit_events(timestamp);
+CREATE TABLE memories (
+    id         TEXT PRIMARY KEY,
+    key        TEXT NOT NULL,
+    content    TEXT NOT NULL,
+    category   TEXT NOT NULL,
+    timestamp  TEXT NOT NULL,
+    session_id TEXT
+);
+CREATE INDEX idx_memories_category ON memories(category);
+CREATE TABLE usage (
+    id            TEXT PRIMARY KEY,
+    session_id    TEXT NOT NULL,
+    model         TEXT NOT NULL,
+    input_tokens  INTEGER NOT NULL,
+    output_tokens INTEGER NOT NULL,
+    total_tokens  INTEGER NOT NULL,
+    cost_usd      REAL NOT NULL,
+    timestamp     TEXT NOT NULL
+);
+CREATE INDEX idx_usage_timestamp ON usage(timestamp);
+";
+
+/// Counts reported after a SQLite export.
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize)]
+pub struct SqliteExportReport {
+    pub audit_events: usize,
+    pub memories: usize,
+    pub usage_records: usize,
+    /// JSONL lines that could not be parsed and were left out.
+    pub skipped_lines: usize,
+}
+
+/// Data gathered for an export.
+#[derive(Debug, Default)]
+pub struct ExportData {
+    pub audit_events: Vec<AuditEvent>,
+    pub memories: Vec<MemoryEntry>,
+    pub usage: Vec<CostRecord>,
+    pub skipped_lines: usize,
+    /// `(name, path)` pairs recorded in `export_meta` as `source.<name>`.
+    pub sources: Vec<(String, String)>,
+}
+
+/// Parse a JSONL file, skipping blank and malformed lines.
+///
+/// Returns the parsed records and the number of skipped lines. A missing
+/// file yields no records.
+pub fn read_jsonl<T: DeserializeOwned>(path: &Path) -> Result<(Vec<T>, usize)> {
+    if !path.exists() {
+        return Ok((Vec::new(), 0));
+    }
+    let raw = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    let mut records = Vec::new();
+    let mut skipped = 0;
+    for line in raw.lines().map(str::trim).filter(|l| !l.is_empty()) {
+        match serde_json::from_str(line) {
+            Ok(record) => records.push(record),
+            Err(_) => skipped += 1,
+        }
+    }
+    Ok((records, skipped))
+}
+
+/// The audit log plus its rotated siblings (`<log>.1.log`, `<log>.2.log`, ...),
+/// oldest first.
+pub fn audit_log_files(log_path: &Path) -> Vec<PathBuf> {
+    let mut rotated = Vec::new();
+    for i in 1.. {
+        let candidate = PathBuf::from(format!("{}.{i}.log", log_path.display()));
+        if !candidate.exists() {
+            break;
+        }
+        rotated.push(candidate);
+    }
+    rotated.reverse();
+    rotated.push(log_path.to_path_buf());
+    rotated
+}
+
+/// Write `data` into a new SQLite database at `path`.
+///
+/// Refuses to touch an existing file unless `force` is set, in which case the
+/// file is replaced.
+pub fn write_database(path: &Path, data: &ExportData, force: bool) -> Result<SqliteExportReport> {
+    if path.exists() {
+        if !force {
+            bail!(
+                "Export target {} already exists. Pass --force to replace it.",
+                path.display()
+            );
+        }
+        std::fs::remove_file(path)
+            .with_context(|| format!("Failed to remove {}", path.display()))?;
+    }
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create {}", parent.display()))?;
+    }
+
+    let mut conn =
+        Connection::open(path).with_context(|| format!("Failed to create {}", path.display()))?;
+    conn.execute_batch(SCHEMA)?;
+    let tx = conn.transaction()?;
+
+    {
+        let mut meta = tx.prepare("INSERT INTO export_meta (key, value) VALUES (?1, ?2)")?;
+        meta.execute(params!["exported_at", chrono::Utc::now().to_rfc3339()])?;
+        meta.execute(params!["zeroclaw_version", env!("CARGO_PKG_VERSION")])?;
+        for (name, source) in &data.sources {
+            meta.execute(params![format!("source.{name}"), source])?;
+        }
+    }
+
+    let mut report = SqliteExportReport {
+        skipped_lines: data.skipped_lines,
+        ..SqliteExportReport::default()
+    };
+
+    {
+        let mut insert = tx.prepare(
+            "INSERT OR REPLACE INTO audit_events (
+                event_id, timestamp, event_type, channel, user_id, username, command,
+                risk_level, approved, allowed, success, exit_code, duration_ms, error,
+                policy_violation, rate_limit_remaining, sandbox_backend
+             ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17)",
+        )?;
+        for event in &data.audit_events {
+            let event_type = serde_json::to_value(&event.event_type)?
+                .as_str()
+                .unwrap_or_default()
+                .to_string();
+            let actor = event.actor.as_ref();
+            let action = event.action.as_ref();
+            let result = event.result.as_ref();
+            insert.execute(params![
+                event.event_id,
+                event.timestamp.to_rfc3339(),
+                event_type,
+                actor.map(|a| a.channel.as_str()),
+                actor.and_then(|a| a.user_id.as_deref()),
+                actor.and_then(|a| a.username.as_deref()),
+                action.and_then(|a| a.command.as_deref()),
+                action.and_then(|a| a.risk_level.as_deref()),
+                action.map(|a| a.approved),
+                action.map(|a| a.allowed),
+                result.map(|r| r.success),
+                result.and_then(|r| r.exit_code),
+                result.and_then(|r| r.duration_ms),
+                result.and_then(|r| r.error.as_deref()),
+                event.security.policy_violation,
+                event.security.rate_limit_remaining,
+                event.security.sandbox_backend.as_deref(),
+            ])?;
+            report.audit_events += 1;
+        }
+    }
+
+    {
+        let mut insert = tx.prepare(
+            "INSERT OR REPLACE INTO memories (id, key, content, category, timestamp, session_id)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+        )?;
+        for entry in &data.memories {
+            insert.execute(params![
+                entry.id,
+                entry.key,
+                entry.content,
+                entry.category.to_string(),
+                entry.timestamp,
+                entry.session_id.as_deref(),
+            ])?;
+            report.memories += 1;
+        }
+    }
+
+    {
+        let mut insert = tx.prepare(
+            "INSERT OR REPLACE INTO usage (
+                id, session_id, model, input_tokens, output_tokens, total_tokens, cost_usd, timestamp
+             ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+        )?;
+        for record in &data.usage {
+            let usage = &record.usage;
+            insert.execute(params![
+                record.id,
+                record.session_id,
+                usage.model,
+                usage.input_tokens,
+                usage.output_tokens,
+                usage.total_tokens,
+                usage.cost_usd,
+                usage.timestamp.to_rfc3339(),
+            ])?;
+            report.usage_records += 1;
+        }
+    }
+
+    tx.commit()?;
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cost::TokenUsage;
+    use crate::memory::MemoryCategory;
+    use crate::security::AuditEventType;
+    use tempfile::TempDir;
+
+    fn sample_data() -> ExportData {
+        let event = AuditEvent::new(AuditEventType::CommandExecution)
+            .with_actor("telegram".into(), Some("42".into()), None)
+            .with_action("rm -rf /".into(), "high".into(), false, false);
+        ExportData {
+            audit_events: vec![event],
+            memories: vec![MemoryEntry {
+                id: "m1".into(),
+                key: "deploy_window".into(),
+                content: "Fridays only".into(),
+                category: MemoryCategory::Core,
+                timestamp: "2026-01-01T00:00:00Z".into(),
+                session_id: Some("main".into()),
+                score: None,
+            }],
+            usage: vec![CostRecord::new(
+                "main",
+                TokenUsage::new("test/model", 1000, 500, 1.0, 2.0),
+            )],
+            skipped_lines: 2,
+            sources: vec![("audit".into(), "/tmp/audit.log".into())],
+        }
+    }
+
+    #[test]
+    fn write_database_creates_queryable_tables() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("out/zeroclaw.sqlite");
+        let report = write_database(&path, &sample_data(), false).unwrap();
+        assert_eq!(
+            report,
+            SqliteExportReport {
+                audit_events: 1,
+                memories: 1,
+                usage_records: 1,
+                skipped_lines: 2,
+            }
+        );
+
+        let conn = Connection::open(&path).unwrap();
+        let (event_type, channel, allowed): (String, String, bool) = conn
+            .query_row(
+                "SELECT event_type, channel, allowed FROM audit_events",
+                [],
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+            )
+            .unwrap();
+        assert_eq!(event_type, "command_execution");
+        assert_eq!(channel, "telegram");
+        assert!(!allowed);
+
+        let category: String = conn
+            .query_row(
+                "SELECT category FROM memories WHERE key = 'deploy_window'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(category, "core");
+
+        let total: i64 = conn
+            .query_row("SELECT SUM(total_tokens) FROM usage", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(total, 1500);
+
+        let source: String = conn
+            .query_row(
+                "SELECT value FROM export_meta WHERE key = 'source.audit'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(source, "/tmp/audit.log");
+    }
+
+    #[test]
+    fn write_database_requires_force_to_replace() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("zeroclaw.sqlite");
+        std::fs::write(&path, "not a database").unwrap();
+
+        let err = write_database(&path, &ExportData::default(), false).unwrap_err();
+        assert!(err.to_string().contains("--force"));
+        let report = write_database(&path, &ExportData::default(), true).unwrap();
+        assert_eq!(report.memories, 0);
+    }
+
+    #[test]
+    fn read_jsonl_skips_malformed_lines_and_orders_rotations() {
+        let tmp = TempDir::new().unwrap();
+        let log = tmp.path().join("audit.log");
+        let event = AuditEvent::new(AuditEventType::AuthFailure);
+        std::fs::write(
+            &log,
+            format!("{}\n\nnot json\n", serde_json::to_string(&event).unwrap()),
+        )
+        .unwrap();
+        std::fs::write(tmp.path().join("audit.log.1.log"), "").unwrap();
+        std::fs::write(tmp.path().join("audit.log.2.log"), "").unwrap();
+
+        let (events, skipped) = read_jsonl::<AuditEvent>(&log).unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(skipped, 1);
+
+        let files = audit_log_files(&log);
+        let names: Vec<_> = files
+            .iter()
+            .map(|p| p.file_name().unwrap().to_string_lossy().into_owned())
+            .collect();
+        assert_eq!(
+            names,
+            vec!["audit.log.2.log", "audit.log.1.log", "audit.log"]
+        );
+
+        let (missing, _) = read_jsonl::<AuditEvent>(&tmp.path().join("none.log")).unwrap();
+        assert!(missing.is_empty());
+    }
+}
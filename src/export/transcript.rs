@@ -0,0 +1,489 @@
+//! Render a stored session as a shareable Markdown or HTML transcript.
+//!
+//! Session history keeps tool activity in wire formats (native `tool_calls`
+//! JSON, `<tool_call>` tags, `[Tool results]` user turns). This module folds
+//! those back into readable call/result blocks, drops system prompts, caps
+//! long tool output, and can scrub secrets and home-directory paths so the
+//! result can be pasted into a review or incident writeup.
+
+use crate::providers::{scrub_secret_patterns, ChatMessage};
+use crate::util::truncate_with_ellipsis;
+use anyhow::{bail, Result};
+use std::collections::HashMap;
+use std::fmt::Write;
+
+/// Characters of a single tool result kept in the transcript.
+const TOOL_OUTPUT_MAX_CHARS: usize = 2000;
+
+/// Output document format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TranscriptFormat {
+    Markdown,
+    Html,
+}
+
+impl TranscriptFormat {
+    pub fn parse(raw: &str) -> Result<Self> {
+        match raw.trim().to_ascii_lowercase().as_str() {
+            "md" | "markdown" => Ok(Self::Markdown),
+            "html" => Ok(Self::Html),
+            other => bail!("Unknown transcript format '{other}'. Use 'md' or 'html'."),
+        }
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Markdown => "md",
+            Self::Html => "html",
+        }
+    }
+}
+
+/// Rendering options.
+#[derive(Debug, Clone, Default)]
+pub struct TranscriptOptions {
+    /// Scrub API keys/tokens and replace the home directory with `~`.
+    pub redact: bool,
+    /// Home directory to hide when redacting.
+    pub home_dir: Option<String>,
+}
+
+/// One readable step of a conversation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TranscriptItem {
+    Message {
+        role: String,
+        text: String,
+    },
+    ToolCall {
+        name: String,
+        arguments: String,
+    },
+    ToolResult {
+        name: Option<String>,
+        output: String,
+    },
+}
+
+/// Fold raw session history into transcript items.
+pub fn parse_history(messages: &[ChatMessage]) -> Vec<TranscriptItem> {
+    let mut items = Vec::new();
+    let mut call_names: HashMap<String, String> = HashMap::new();
+
+    for message in messages {
+        match message.role.as_str() {
+            "system" => {}
+            "assistant" => parse_assistant(&message.content, &mut items, &mut call_names),
+            "tool" => items.push(parse_tool_message(&message.content, &call_names)),
+            "user" if message.content.starts_with("[Tool results]") => {
+                parse_tool_result_tags(&message.content, &mut items);
+            }
+            role => push_message(&mut items, role, &message.content),
+        }
+    }
+    items
+}
+
+fn push_message(items: &mut Vec<TranscriptItem>, role: &str, text: &str) {
+    let text = text.trim();
+    if !text.is_empty() {
+        items.push(TranscriptItem::Message {
+            role: role.to_string(),
+            text: text.to_string(),
+        });
+    }
+}
+
+fn parse_assistant(
+    content: &str,
+    items: &mut Vec<TranscriptItem>,
+    call_names: &mut HashMap<String, String>,
+) {
+    // Native tool calling stores `{"content": ..., "tool_calls": [...]}`.
+    if let Ok(serde_json::Value::Object(obj)) = serde_json::from_str(content) {
+        if let Some(calls) = obj.get("tool_calls").and_then(|v| v.as_array()) {
+            if let Some(text) = obj.get("content").and_then(|v| v.as_str()) {
+                push_message(items, "assistant", text);
+            }
+            for call in calls {
+                let function = call.get("function").unwrap_or(call);
+                let name = function
+                    .get("name")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or("unknown")
+                    .to_string();
+                let arguments = match function.get("arguments") {
+                    Some(serde_json::Value::String(s)) => s.clone(),
+                    Some(other) => other.to_string(),
+                    None => String::new(),
+                };
+                if let Some(id) = call.get("id").and_then(|v| v.as_str()) {
+                    call_names.insert(id.to_string(), name.clone());
+                }
+                items.push(TranscriptItem::ToolCall { name, arguments });
+            }
+            return;
+        }
+    }
+
+    // Prompt-mode tool calling embeds `<tool_call>{...}</tool_call>` blocks.
+    let mut rest = content;
+    let mut text = String::new();
+    let mut calls = Vec::new();
+    while let Some(start) = rest.find("<tool_call>") {
+        text.push_str(&rest[..start]);
+        let after = &rest[start + "<tool_call>".len()..];
+        let Some(end) = after.find("</tool_call>") else {
+            rest = &rest[start..];
+            break;
+        };
+        let body = after[..end].trim();
+        let parsed: Option<serde_json::Value> = serde_json::from_str(body).ok();
+        let name = parsed
+            .as_ref()
+            .and_then(|v| v.get("name"))
+            .and_then(|v| v.as_str())
+            .unwrap_or("unknown")
+            .to_string();
+        let arguments = parsed
+            .as_ref()
+            .and_then(|v| v.get("arguments"))
+            .map_or_else(|| body.to_string(), ToString::to_string);
+        calls.push(TranscriptItem::ToolCall { name, arguments });
+        rest = &after[end + "</tool_call>".len()..];
+    }
+    text.push_str(rest);
+    push_message(items, "assistant", &text);
+    items.extend(calls);
+}
+
+fn parse_tool_message(content: &str, call_names: &HashMap<String, String>) -> TranscriptItem {
+    if let Ok(value) = serde_json::from_str::<serde_json::Value>(content) {
+        if let Some(output) = value.get("content").and_then(|v| v.as_str()) {
+            let name = value
+                .get("tool_call_id")
+                .and_then(|v| v.as_str())
+                .and_then(|id| call_names.get(id))
+                .cloned();
+            return TranscriptItem::ToolResult {
+                name,
+                output: output.to_string(),
+            };
+        }
+    }
+    TranscriptItem::ToolResult {
+        name: None,
+        output: content.to_string(),
+    }
+}
+
+fn parse_tool_result_tags(content: &str, items: &mut Vec<TranscriptItem>) {
+    let mut rest = content;
+    while let Some(start) = rest.find("<tool_result name=\"") {
+        let after = &rest[start + "<tool_result name=\"".len()..];
+        let Some(name_end) = after.find("\">") else {
+            break;
+        };
+        let name = after[..name_end].to_string();
+        let body = &after[name_end + 2..];
+        let Some(end) = body.find("</tool_result>") else {
+            break;
+        };
+        items.push(TranscriptItem::ToolResult {
+            name: Some(name),
+            output: body[..end].trim().to_string(),
+        });
+        rest = &body[end + "</tool_result>".len()..];
+    }
+}
+
+/// Render `items` for session `id` in the requested format.
+pub fn render(
+    id: &str,
+    items: &[TranscriptItem],
+    format: TranscriptFormat,
+    options: &TranscriptOptions,
+) -> String {
+    let clean = |text: &str| clean_text(text, options);
+    match format {
+        TranscriptFormat::Markdown => render_markdown(id, items, &clean),
+        TranscriptFormat::Html => render_html(id, items, &clean),
+    }
+}
+
+fn clean_text(text: &str, options: &TranscriptOptions) -> String {
+    if !options.redact {
+        return text.to_string();
+    }
+    let mut text = scrub_secret_patterns(text);
+    if let Some(home) = options.home_dir.as_deref().filter(|h| h.len() > 1) {
+        text = text.replace(home, "~");
+    }
+    text
+}
+
+fn tool_output(output: &str) -> String {
+    truncate_with_ellipsis(output.trim(), TOOL_OUTPUT_MAX_CHARS)
+}
+
+fn pretty_arguments(arguments: &str) -> String {
+    serde_json::from_str::<serde_json::Value>(arguments)
+        .ok()
+        .and_then(|v| serde_json::to_string_pretty(&v).ok())
+        .unwrap_or_else(|| arguments.to_string())
+}
+
+fn role_title(role: &str) -> String {
+    let mut chars = role.chars();
+    chars.next().map_or_else(String::new, |first| {
+        first.to_uppercase().chain(chars).collect()
+    })
+}
+
+fn summary_line(items: &[TranscriptItem]) -> String {
+    let messages = items
+        .iter()
+        .filter(|i| matches!(i, TranscriptItem::Message { .. }))
+        .count();
+    let calls = items
+        .iter()
+        .filter(|i| matches!(i, TranscriptItem::ToolCall { .. }))
+        .count();
+    format!("{messages} messages, {calls} tool calls")
+}
+
+/// A backtick fence longer than any run inside `content`.
+fn fence_for(content: &str) -> String {
+    let mut longest = 0;
+    let mut run = 0;
+    for c in content.chars() {
+        if c == '`' {
+            run += 1;
+            longest = longest.max(run);
+        } else {
+            run = 0;
+        }
+    }
+    "`".repeat((longest + 1).max(3))
+}
+
+fn render_markdown(id: &str, items: &[TranscriptItem], clean: &dyn Fn(&str) -> String) -> String {
+    let mut out = String::new();
+    let _ = writeln!(out, "# Session `{}`\n", clean(id));
+    let _ = writeln!(out, "_{}, exported from ZeroClaw._\n", summary_line(items));
+
+    for item in items {
+        match item {
+            TranscriptItem::Message { role, text } => {
+                let _ = writeln!(out, "### {}\n\n{}\n", role_title(role), clean(text));
+            }
+            TranscriptItem::ToolCall { name, arguments } => {
+                let body = clean(&pretty_arguments(arguments));
+                let fence = fence_for(&body);
+                let _ = writeln!(
+                    out,
+                    "**Tool call:** `{name}`\n\n{fence}json\n{body}\n{fence}\n"
+                );
+            }
+            TranscriptItem::ToolResult { name, output } => {
+                let body = clean(&tool_output(output));
+                let fence = fence_for(&body);
+                let label = name
+                    .as_deref()
+                    .map_or_else(String::new, |n| format!(" `{n}`"));
+                let _ = writeln!(
+                    out,
+                    "**Tool result:**{label}\n\n{fence}text\n{body}\n{fence}\n"
+                );
+            }
+        }
+    }
+    out
+}
+
+fn escape_html(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+const HTML_STYLE: &str = "body{font-family:system-ui,sans-serif;max-width:52rem;margin:2rem auto;\
+padding:0 1rem;line-height:1.5;color:#1f2328}\
+.msg{border-left:4px solid #d0d7de;padding:.25rem 1rem;margin:1rem 0}\
+.msg.user{border-color:#0969da}.msg.assistant{border-color:#1a7f37}\
+.role{font-weight:600;text-transform:capitalize}\
+.text{white-space:pre-wrap}\
+details{margin:.5rem 0 .5rem 1.25rem}summary{cursor:pointer;color:#57606a}\
+pre{background:#f6f8fa;padding:.75rem;overflow-x:auto;white-space:pre-wrap}";
+
+fn render_html(id: &str, items: &[TranscriptItem], clean: &dyn Fn(&str) -> String) -> String {
+    let title = escape_html(&clean(id));
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+         <title>Session {title}</title>\n<style>{HTML_STYLE}</style>\n</head>\n<body>"
+    );
+    let _ = writeln!(out, "<h1>Session <code>{title}</code></h1>");
+    let _ = writeln!(
+        out,
+        "<p><em>{}, exported from ZeroClaw.</em></p>",
+        summary_line(items)
+    );
+
+    for item in items {
+        match item {
+            TranscriptItem::Message { role, text } => {
+                let role = escape_html(role);
+                let _ = writeln!(
+                    out,
+                    "<section class=\"msg {role}\"><div class=\"role\">{role}</div>\
+                     <div class=\"text\">{}</div></section>",
+                    escape_html(&clean(text))
+                );
+            }
+            TranscriptItem::ToolCall { name, arguments } => {
+                let _ = writeln!(
+                    out,
+                    "<details><summary>Tool call: <code>{}</code></summary><pre>{}</pre></details>",
+                    escape_html(name),
+                    escape_html(&clean(&pretty_arguments(arguments)))
+                );
+            }
+            TranscriptItem::ToolResult { name, output } => {
+                let label = name
+                    .as_deref()
+                    .map_or_else(String::new, |n| format!(" <code>{}</code>", escape_html(n)));
+                let _ = writeln!(
+                    out,
+                    "<details><summary>Tool result:{label}</summary><pre>{}</pre></details>",
+                    escape_html(&clean(&tool_output(output)))
+                );
+            }
+        }
+    }
+    out.push_str("</body>\n</html>\n");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn native_history() -> Vec<ChatMessage> {
+        vec![
+            ChatMessage::system("You are ZeroClaw."),
+            ChatMessage::user("Check the deploy log"),
+            ChatMessage::assistant(
+                r#"{"content":"Reading it now.","tool_calls":[{"id":"call_1","name":"file_read","arguments":"{\"path\":\"/home/ops/deploy.log\"}"}]}"#,
+            ),
+            ChatMessage::tool(r#"{"tool_call_id":"call_1","content":"token=abcd1234efgh5678 ok"}"#),
+            ChatMessage::assistant("The deploy finished <cleanly>."),
+        ]
+    }
+
+    #[test]
+    fn parse_history_folds_native_tool_activity() {
+        let items = parse_history(&native_history());
+        assert_eq!(
+            items,
+            vec![
+                TranscriptItem::Message {
+                    role: "user".into(),
+                    text: "Check the deploy log".into(),
+                },
+                TranscriptItem::Message {
+                    role: "assistant".into(),
+                    text: "Reading it now.".into(),
+                },
+                TranscriptItem::ToolCall {
+                    name: "file_read".into(),
+                    arguments: r#"{"path":"/home/ops/deploy.log"}"#.into(),
+                },
+                TranscriptItem::ToolResult {
+                    name: Some("file_read".into()),
+                    output: "token=abcd1234efgh5678 ok".into(),
+                },
+                TranscriptItem::Message {
+                    role: "assistant".into(),
+                    text: "The deploy finished <cleanly>.".into(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_history_reads_prompt_mode_tags() {
+        let items = parse_history(&[
+            ChatMessage::assistant(
+                "Let me look.\n<tool_call>{\"name\":\"shell\",\"arguments\":{\"command\":\"ls\"}}</tool_call>",
+            ),
+            ChatMessage::user(
+                "[Tool results]\n<tool_result name=\"shell\">\nREADME.md\n</tool_result>\n",
+            ),
+        ]);
+        assert_eq!(items.len(), 3);
+        assert!(matches!(&items[1], TranscriptItem::ToolCall { name, .. } if name == "shell"));
+        assert_eq!(
+            items[2],
+            TranscriptItem::ToolResult {
+                name: Some("shell".into()),
+                output: "README.md".into(),
+            }
+        );
+    }
+
+    #[test]
+    fn markdown_redacts_secrets_and_home_paths() {
+        let items = parse_history(&native_history());
+        let options = TranscriptOptions {
+            redact: true,
+            home_dir: Some("/home/ops".into()),
+        };
+        let md = render("main", &items, TranscriptFormat::Markdown, &options);
+        assert!(md.starts_with("# Session `main`"));
+        assert!(!md.contains("You are ZeroClaw"));
+        assert!(md.contains("**Tool call:** `file_read`"));
+        assert!(md.contains("~/deploy.log"));
+        assert!(!md.contains("abcd1234efgh5678"));
+
+        let plain = render(
+            "main",
+            &items,
+            TranscriptFormat::Markdown,
+            &TranscriptOptions::default(),
+        );
+        assert!(plain.contains("abcd1234efgh5678"));
+    }
+
+    #[test]
+    fn html_escapes_content() {
+        let items = parse_history(&native_history());
+        let html = render(
+            "a<b",
+            &items,
+            TranscriptFormat::Html,
+            &TranscriptOptions::default(),
+        );
+        assert!(html.contains("<title>Session a&lt;b</title>"));
+        assert!(html.contains("finished &lt;cleanly&gt;."));
+        assert!(html.trim_end().ends_with("</html>"));
+    }
+
+    #[test]
+    fn fence_outgrows_embedded_backticks() {
+        assert_eq!(fence_for("plain"), "```");
+        assert_eq!(fence_for("has ``` inside"), "````");
+        assert!(TranscriptFormat::parse("pdf").is_err());
+        assert_eq!(TranscriptFormat::parse("MD").unwrap().extension(), "md");
+    }
+}
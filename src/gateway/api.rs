@@ -1045,6 +1045,7 @@ fn mask_sensitive_fields(config: &crate::config::Config) -> crate::config::Confi
     mask_optional_secret(&mut masked.transcription.api_key);
     mask_optional_secret(&mut masked.browser.computer_use.api_key);
     mask_optional_secret(&mut masked.web_fetch.api_key);
+    mask_optional_secret(&mut masked.home_assistant.token);
     mask_optional_secret(&mut masked.web_search.api_key);
     mask_optional_secret(&mut masked.web_search.brave_api_key);
     mask_optional_secret(&mut masked.web_search.perplexity_api_key);
@@ -1161,6 +1162,10 @@ fn restore_masked_sensitive_fields(
         &current.browser.computer_use.api_key,
     );
     restore_optional_secret(&mut incoming.web_fetch.api_key, &current.web_fetch.api_key);
+    restore_optional_secret(
+        &mut incoming.home_assistant.token,
+        &current.home_assistant.token,
+    );
     restore_optional_secret(
         &mut incoming.web_search.api_key,
         &current.web_search.api_key,
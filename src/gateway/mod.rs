@@ -458,10 +458,7 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
     )?);
     let runtime: Arc<dyn runtime::RuntimeAdapter> =
         Arc::from(runtime::create_runtime(&config.runtime)?);
-    let security = Arc::new(SecurityPolicy::from_config(
-        &config.autonomy,
-        &config.workspace_dir,
-    ));
+    let security = Arc::new(SecurityPolicy::from_root_config(&config));
 
     let (composio_key, composio_entity_id) = if config.composio.enabled {
         (
@@ -495,7 +492,11 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
     // Cost tracker (optional)
     let cost_tracker = if config.cost.enabled {
         match CostTracker::new(config.cost.clone(), &config.workspace_dir) {
-            Ok(ct) => Some(Arc::new(ct)),
+            Ok(ct) => {
+                let ct = Arc::new(ct);
+                ct.spawn_flush_task();
+                Some(ct)
+            }
             Err(e) => {
                 tracing::warn!("Failed to initialize cost tracker: {e}");
                 None
@@ -780,9 +781,13 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
     // Wrap observer with broadcast capability for SSE
     // Use cost-tracking observer when cost tracking is enabled.
     // Wrap it in ObserverBridge so plugin hooks can observe a stable interface.
-    let base_observer = crate::observability::create_observer_with_cost_tracking(
-        &config.observability,
-        cost_tracker.clone(),
+    let base_observer = crate::observability::with_security_budget(
+        crate::observability::create_observer_with_cost_tracking(
+            &config.observability,
+            cost_tracker.clone(),
+            &config.cost,
+        ),
+        &security,
         &config.cost,
     );
     let bridged_observer = crate::plugins::bridge::observer::ObserverBridge::new_box(base_observer);
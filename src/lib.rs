@@ -1,3 +1,35 @@
+//! ZeroClaw as a library.
+//!
+//! The `zeroclaw` binary is a thin CLI over this crate. Programs that want an
+//! agent without the CLI should depend on the types re-exported at the crate
+//! root, which form the supported embedding surface:
+//!
+//! - [`Agent`] / [`AgentBuilder`] — a conversation loop over one provider.
+//! - [`Provider`] — the LLM backend trait (see [`providers`] for built-ins).
+//! - [`Tool`] and [`ToolRegistry`] — agent-callable capabilities and the
+//!   name-keyed set handed to the builder.
+//! - [`SecurityPolicy`] / [`SecurityPolicyBuilder`] — autonomy, workspace,
+//!   and command rules that the built-in tools enforce.
+//! - [`Memory`] — the long-term memory store trait (see [`memory`] for
+//!   backends).
+//! - [`Config`] — the full runtime configuration, for [`Agent::from_config`].
+//!
+//! ```ignore
+//! use std::sync::Arc;
+//! use zeroclaw::{Agent, SecurityPolicy, ToolRegistry};
+//!
+//! let security = Arc::new(SecurityPolicy::builder().workspace_dir(".").build()?);
+//! let mut agent = Agent::builder()
+//!     .provider(zeroclaw::providers::create_provider("openrouter", Some(&api_key))?)
+//!     .memory(Arc::new(zeroclaw::memory::NoneMemory::new()))
+//!     .tool_registry(ToolRegistry::with_defaults(security).with(MyTool))
+//!     .build()?;
+//! let reply = agent.turn("Summarize README.md").await?;
+//! ```
+//!
+//! Other public modules are reachable for advanced wiring, but only the
+//! root re-exports are kept source-compatible across minor releases.
+
 #![warn(clippy::all, clippy::pedantic)]
 #![forbid(unsafe_code)]
 #![recursion_limit = "256"]
@@ -75,7 +107,7 @@ use clap::Subcommand;
 use serde::{Deserialize, Serialize};
 
 pub mod agent;
-pub(crate) mod approval;
+pub mod approval;
 pub(crate) mod auth;
 pub mod channels;
 pub mod config;
@@ -85,6 +117,7 @@ pub(crate) mod cron;
 pub(crate) mod daemon;
 pub(crate) mod doctor;
 pub mod economic;
+pub(crate) mod export;
 pub mod gateway;
 pub mod goals;
 pub(crate) mod hardware;
@@ -105,7 +138,7 @@ pub(crate) mod plugins;
 pub mod providers;
 pub mod rag;
 pub mod runtime;
-pub(crate) mod security;
+pub mod security;
 pub(crate) mod service;
 pub(crate) mod skills;
 #[cfg(test)]
@@ -115,7 +148,12 @@ pub(crate) mod tunnel;
 pub mod update;
 pub(crate) mod util;
 
+pub use agent::{Agent, AgentBuilder};
 pub use config::Config;
+pub use memory::{Memory, MemoryCategory, MemoryEntry};
+pub use providers::{ChatMessage, Provider};
+pub use security::{AutonomyLevel, SecurityPolicy, SecurityPolicyBuilder};
+pub use tools::{Tool, ToolRegistry, ToolResult, ToolSpec};
 
 /// Service management subcommands
 #[derive(Subcommand, Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -368,6 +406,60 @@ Examples:
     },
 }
 
+/// Export subcommands
+#[derive(Subcommand, Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum ExportCommands {
+    /// Render memories and session summaries as an Obsidian vault
+    Obsidian {
+        /// Vault directory (created if missing)
+        dir: String,
+        /// Write into a non-empty directory, overwriting notes with the same names
+        #[arg(long)]
+        force: bool,
+    },
+    /// Dump audit events, memories, and usage records into a SQLite file
+    Sqlite {
+        /// Database file to create
+        file: String,
+        /// Replace the file if it already exists
+        #[arg(long)]
+        force: bool,
+    },
+}
+
+/// Session subcommands
+#[derive(Subcommand, Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum SessionCommands {
+    /// Export a stored session as a shareable Markdown or HTML transcript
+    Export {
+        /// Session ID (as stored by the sqlite session backend)
+        id: String,
+        /// Output format: md, html
+        #[arg(long, default_value = "md")]
+        format: String,
+        /// Scrub API keys/tokens and replace the home directory with `~`
+        #[arg(long)]
+        redact: bool,
+        /// Write to this file instead of stdout
+        #[arg(long, short)]
+        output: Option<String>,
+    },
+}
+
+/// Tool subcommands
+#[derive(Subcommand, Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum ToolCommands {
+    /// Print the agent's tool schemas for use by external orchestrators
+    Export {
+        /// Schema format: openai, mcp
+        #[arg(long, default_value = "openai")]
+        format: String,
+        /// Write to this file instead of stdout
+        #[arg(long, short)]
+        output: Option<String>,
+    },
+}
+
 /// Memory management subcommands
 #[derive(Subcommand, Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub enum MemoryCommands {
@@ -414,6 +506,41 @@ pub enum MemoryCommands {
         #[arg(long, default_value = "true")]
         progress: bool,
     },
+    /// Write every memory entry to a JSON dump (for migration or analysis)
+    Export {
+        /// Write to this file instead of stdout
+        #[arg(long, short)]
+        output: Option<String>,
+    },
+    /// Import Markdown/text notes, or a JSON dump from `memory export`
+    Import {
+        /// Note file or directory to import (searched recursively), or a `.json` dump
+        path: String,
+        /// Category for imported entries (core, daily, conversation, or custom name)
+        #[arg(long, default_value = "notes")]
+        category: String,
+        /// Show what would be imported without storing anything
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Migrate free-form legacy notes.md files into the memory store
+    Migrate {
+        /// Notes file or directory to migrate (searched recursively)
+        path: String,
+        /// Category for migrated entries (core, daily, conversation, or custom name)
+        #[arg(long, default_value = "notes")]
+        category: String,
+        /// Show what would be migrated and skipped without storing anything
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Show entries added, removed, redacted, or changed between two dumps
+    Diff {
+        /// Earlier state: a JSON dump from `memory export` or a MEMORY_SNAPSHOT.md
+        from: String,
+        /// Later state, in the same formats (default: current memory)
+        to: Option<String>,
+    },
 }
 
 /// Integration subcommands
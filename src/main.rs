@@ -72,7 +72,7 @@ use anyhow::{bail, Context, Result};
 use clap::{CommandFactory, Parser, Subcommand, ValueEnum};
 use dialoguer::{Input, Password};
 use serde::{Deserialize, Serialize};
-use std::io::Write;
+use std::io::{IsTerminal, Read, Write};
 use tracing::{info, warn};
 use tracing_subscriber::{fmt, EnvFilter};
 
@@ -151,6 +151,7 @@ mod cost;
 mod cron;
 mod daemon;
 mod doctor;
+mod export;
 mod gateway;
 mod goals;
 mod hardware;
@@ -184,8 +185,9 @@ use config::Config;
 
 // Re-export so binary modules can use crate::<CommandEnum> while keeping a single source of truth.
 pub use zeroclaw::{
-    ChannelCommands, CronCommands, HardwareCommands, IntegrationCommands, MigrateCommands,
-    PeripheralCommands, ServiceCommands, SkillCommands,
+    ChannelCommands, CronCommands, ExportCommands, HardwareCommands, IntegrationCommands,
+    MigrateCommands, PeripheralCommands, ServiceCommands, SessionCommands, SkillCommands,
+    ToolCommands,
 };
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq, ValueEnum)]
@@ -224,6 +226,14 @@ struct Cli {
     #[arg(long, global = true)]
     config_dir: Option<String>,
 
+    /// Report where startup time goes (printed to stderr after the command finishes)
+    #[arg(long, global = true)]
+    timing: bool,
+
+    /// Emit machine-readable JSON instead of text (also enabled by ZEROCLAW_JSON=1)
+    #[arg(long, global = true)]
+    json: bool,
+
     #[command(subcommand)]
     command: Commands,
 }
@@ -488,6 +498,82 @@ Examples:
         security_command: SecurityCommands,
     },
 
+    /// Validate standalone security policy files
+    #[command(long_about = "\
+Validate standalone security policy files.
+
+A policy file holds the keys of an [autonomy] section plus \
+`workspace_dir`; keys left out take their defaults. `check` parses and \
+validates the file without starting the agent and exits non-zero on \
+the first problem found.
+
+`simulate` replays the tool calls of a stored session, or a file of \
+synthetic calls, against a candidate policy file and reports which ones \
+it would allow or deny. Nothing is executed.
+
+Examples:
+  zeroclaw policy check ./policy.toml
+  zeroclaw policy simulate ./policy.toml --session main
+  zeroclaw policy simulate ./policy.toml --calls ./calls.jsonl")]
+    Policy {
+        #[command(subcommand)]
+        policy_command: PolicyCommands,
+    },
+
+    /// Answer the agent's escalation requests
+    #[command(long_about = "\
+Answer the agent's escalation requests.
+
+The `request_escalation` tool files requests for a higher autonomy level \
+or extra actions on the hourly rate budget. A grant takes effect in the \
+running agent for the given number of minutes and is recorded in the \
+audit log; nothing changes for requests that are denied or left pending.
+
+Examples:
+  zeroclaw escalation list
+  zeroclaw escalation grant 3f9a1c2e --minutes 30
+  zeroclaw escalation deny 3f9a1c2e")]
+    Escalation {
+        #[command(subcommand)]
+        escalation_command: EscalationCommands,
+    },
+
+    /// Check the security audit log for tampering
+    #[command(long_about = "\
+Check the security audit log for tampering.
+
+Each audit record carries the hash of the record before it, and the \
+chain head is periodically anchored in a separate file. `verify` walks \
+the log and its rotations, oldest first, and reports any record whose \
+link does not match and any anchored record missing from the log. \
+Exits non-zero when a problem is found.
+
+Examples:
+  zeroclaw audit verify")]
+    Audit {
+        #[command(subcommand)]
+        audit_command: AuditCommands,
+    },
+
+    /// Store secrets referenced from config outside the config file
+    #[command(long_about = "\
+Store secrets referenced from config outside the config file.
+
+Any secret field in config.toml may hold a reference instead of the \
+value: `${keychain:<name>}` (OS keychain), `${secretfile:<name>}` \
+(encrypted ~/.zeroclaw/secrets.json), or `${env:<VAR>}` (environment \
+variable, set outside ZeroClaw). Here the `${}` may be left out. `set` \
+prompts for the value, or reads it from stdin when piped.
+
+Examples:
+  zeroclaw secrets set keychain:openrouter
+  printf %s \"$TOKEN\" | zeroclaw secrets set secretfile:telegram
+  zeroclaw secrets delete keychain:openrouter")]
+    Secrets {
+        #[command(subcommand)]
+        secrets_command: SecretsCommands,
+    },
+
     /// Configure and manage scheduled tasks
     #[command(long_about = "\
 Configure and manage scheduled tasks.
@@ -640,12 +726,65 @@ Examples:
   zeroclaw memory list
   zeroclaw memory list --category core --limit 10
   zeroclaw memory get <key>
-  zeroclaw memory clear --category conversation --yes")]
+  zeroclaw memory clear --category conversation --yes
+  zeroclaw memory export -o memory.json
+  zeroclaw memory import memory.json")]
     Memory {
         #[command(subcommand)]
         memory_command: MemoryCommands,
     },
 
+    /// Export agent state for external tools
+    #[command(long_about = "\
+Export agent state for browsing in external tools.
+
+Examples:
+  zeroclaw export obsidian ~/vaults/zeroclaw
+  zeroclaw export obsidian ./vault --force
+  zeroclaw export sqlite ./zeroclaw.sqlite")]
+    Export {
+        #[command(subcommand)]
+        export_command: ExportCommands,
+    },
+
+    /// Inspect and export stored conversation sessions
+    #[command(long_about = "\
+Inspect and export conversation sessions persisted by the sqlite session \
+backend ([agent.session] backend = \"sqlite\").
+
+Exports fold tool calls and results into readable blocks and drop system \
+prompts. Pass --redact to scrub API keys and tokens and hide your home \
+directory before sharing.
+
+Examples:
+  zeroclaw sessions export main
+  zeroclaw sessions export telegram%3A42 --format html --redact -o incident.html")]
+    Sessions {
+        #[command(subcommand)]
+        session_command: SessionCommands,
+    },
+
+    /// Inspect the agent's tools
+    #[command(long_about = "\
+Inspect the tools the agent exposes.
+
+`tools export` prints the JSON schemas of every tool the agent would offer \
+(after config gating and [agent] allowed_tools/denied_tools filters) so \
+external orchestrators such as LangChain can describe zeroclaw's tools to \
+their own models.
+
+Formats:
+  openai   OpenAI function-calling definitions ({\"type\": \"function\", ...})
+  mcp      MCP tools/list payload ({\"tools\": [{name, description, inputSchema}]})
+
+Examples:
+  zeroclaw tools export
+  zeroclaw tools export --format mcp -o tools.json")]
+    Tools {
+        #[command(subcommand)]
+        tool_command: ToolCommands,
+    },
+
     /// Manage configuration
     #[command(long_about = "\
 Manage ZeroClaw configuration.
@@ -733,6 +872,65 @@ enum SecurityCommands {
     },
 }
 
+#[derive(Subcommand, Debug)]
+enum PolicyCommands {
+    /// Parse and validate a policy file, then print the effective policy
+    Check {
+        /// Path to the policy TOML file
+        path: std::path::PathBuf,
+    },
+    /// Replay tool calls against a policy file and report each decision
+    Simulate {
+        /// Path to the candidate policy TOML file
+        path: std::path::PathBuf,
+        /// Stored session whose tool calls to replay
+        #[arg(long, required_unless_present = "calls", conflicts_with = "calls")]
+        session: Option<String>,
+        /// JSON array or JSONL file of `{"tool": ..., "arguments": ...}` calls
+        #[arg(long)]
+        calls: Option<std::path::PathBuf>,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum EscalationCommands {
+    /// List escalation requests and their status
+    List,
+    /// Grant a pending request for a limited time
+    Grant {
+        /// Request id, as shown by `zeroclaw escalation list`
+        id: String,
+        /// How long the grant lasts, in minutes
+        #[arg(long, default_value_t = 60)]
+        minutes: u32,
+    },
+    /// Deny a pending request
+    Deny {
+        /// Request id, as shown by `zeroclaw escalation list`
+        id: String,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum AuditCommands {
+    /// Verify the audit log's hash chain and anchors
+    Verify,
+}
+
+#[derive(Subcommand, Debug)]
+enum SecretsCommands {
+    /// Store a secret under a `${keychain:...}` or `${secretfile:...}` reference
+    Set {
+        /// Reference to store, as written in config.toml
+        reference: String,
+    },
+    /// Remove the secret behind a reference
+    Delete {
+        /// Reference to remove, as written in config.toml
+        reference: String,
+    },
+}
+
 #[derive(Subcommand, Debug)]
 enum AuthCommands {
     /// Login with OAuth (OpenAI Codex or Gemini)
@@ -913,11 +1111,35 @@ enum MemoryCommands {
         #[arg(long, default_value = "true")]
         progress: bool,
     },
+    /// Write every memory entry to a JSON dump (for migration or analysis)
+    Export {
+        /// Write to this file instead of stdout
+        #[arg(long, short)]
+        output: Option<String>,
+    },
+    /// Import Markdown/text notes, or a JSON dump from `memory export`
+    Import {
+        path: String,
+        #[arg(long, default_value = "notes")]
+        category: String,
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Migrate free-form legacy notes.md files into the memory store
+    Migrate {
+        path: String,
+        #[arg(long, default_value = "notes")]
+        category: String,
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Show entries added, removed, redacted, or changed between two dumps
+    Diff { from: String, to: Option<String> },
 }
 
-#[tokio::main]
-#[allow(clippy::too_many_lines)]
-async fn main() -> Result<()> {
+fn main() -> Result<std::process::ExitCode> {
+    let started = std::time::Instant::now();
+
     // Install default crypto provider for Rustls TLS.
     // This prevents the error: "could not automatically determine the process-level CryptoProvider"
     // when both aws-lc-rs and ring features are available (or neither is explicitly selected).
@@ -926,6 +1148,11 @@ async fn main() -> Result<()> {
     }
 
     let cli = Cli::parse();
+    let mut timer = StartupTimer::new(cli.timing, started);
+    util::set_json_output(
+        cli.json || util::env_flag_enabled(std::env::var(util::JSON_OUTPUT_ENV).ok().as_deref()),
+    );
+    timer.mark("parse args");
 
     if let Some(config_dir) = &cli.config_dir {
         if config_dir.trim().is_empty() {
@@ -934,6 +1161,35 @@ async fn main() -> Result<()> {
         std::env::set_var("ZEROCLAW_CONFIG_DIR", config_dir);
     }
 
+    // `[runtime.tokio]` must be known before the main runtime is built, so
+    // read just that section on a throwaway single-threaded runtime.
+    let tuning = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .context("Failed to build bootstrap runtime")?
+        .block_on(Config::load_runtime_tuning());
+    let runtime = runtime::executor::build_main_runtime(&tuning)
+        .context("Failed to build Tokio runtime from [runtime.tokio]")?;
+    runtime::executor::init_fs_runtime(&tuning)
+        .context("Failed to build filesystem runtime from [runtime.tokio]")?;
+    timer.mark("runtime build");
+
+    let result = runtime.block_on(Box::pin(run(cli, timer)));
+    // Shut the runtime down before reporting, so buffered audit and usage
+    // records held by its tasks are flushed on every exit path.
+    drop(runtime);
+    match result {
+        Err(error) if util::json_output() => {
+            // Keep stdout parseable: scripts read `{"error": ...}` instead of anyhow's report.
+            let _ = util::print_json(&serde_json::json!({ "error": format!("{error:#}") }));
+            Ok(std::process::ExitCode::FAILURE)
+        }
+        result => result.map(|()| std::process::ExitCode::SUCCESS),
+    }
+}
+
+#[allow(clippy::too_many_lines)]
+async fn run(cli: Cli, mut timer: StartupTimer) -> Result<()> {
     // Completions must remain stdout-only and should not load config or initialize logging.
     // This avoids warnings/log lines corrupting sourced completion scripts.
     if let Commands::Completions { shell } = &cli.command {
@@ -942,15 +1198,25 @@ async fn main() -> Result<()> {
         return Ok(());
     }
 
-    // Initialize logging - respects RUST_LOG env var, defaults to INFO
+    // Initialize logging - respects RUST_LOG env var, defaults to INFO.
+    // JSON mode moves logs to stderr so stdout stays a single JSON document.
+    let json = util::json_output();
     let subscriber = fmt::Subscriber::builder()
         .with_timer(tracing_subscriber::fmt::time::ChronoLocal::rfc_3339())
         .with_env_filter(
             EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info")),
         )
+        .with_writer(move || -> Box<dyn std::io::Write> {
+            if json {
+                Box::new(std::io::stderr())
+            } else {
+                Box::new(std::io::stdout())
+            }
+        })
         .finish();
 
     tracing::subscriber::set_global_default(subscriber).expect("setting default subscriber failed");
+    timer.mark("logging");
 
     // Onboard runs quick setup by default, interactive wizard with --interactive,
     // or full-screen TUI with --interactive-ui.
@@ -1072,22 +1338,143 @@ async fn main() -> Result<()> {
     // All other commands need config loaded first
     let mut config = Config::load_or_init().await?;
     config.apply_env_overrides();
-    observability::runtime_trace::init_from_config(&config.observability, &config.workspace_dir);
-    if config.security.otp.enabled {
-        let config_dir = config
-            .config_path
-            .parent()
-            .context("Config path must have a parent directory")?;
-        let store = security::SecretStore::new(config_dir, config.secrets.encrypt);
-        let (_validator, enrollment_uri) =
-            security::OtpValidator::from_config(&config.security.otp, config_dir, &store)?;
-        if let Some(uri) = enrollment_uri {
-            println!("Initialized OTP secret for ZeroClaw.");
-            println!("Enrollment URI: {uri}");
+    timer.mark("config load");
+
+    // Local management commands never reach a provider, channel, or tool, so
+    // they skip runtime tracing and OTP setup to keep one-shot calls fast.
+    if !is_lightweight_command(&cli.command) {
+        observability::runtime_trace::init_from_config(
+            &config.observability,
+            &config.workspace_dir,
+        );
+        security::events::init_from_config(&config.security.webhook);
+        if config.security.otp.enabled {
+            let config_dir = config
+                .config_path
+                .parent()
+                .context("Config path must have a parent directory")?;
+            let store = security::SecretStore::new(config_dir, config.secrets.encrypt);
+            let (_validator, enrollment_uri) =
+                security::OtpValidator::from_config(&config.security.otp, config_dir, &store)?;
+            if let Some(uri) = enrollment_uri {
+                println!("Initialized OTP secret for ZeroClaw.");
+                println!("Enrollment URI: {uri}");
+            }
+        }
+        timer.mark("runtime init");
+    }
+
+    let result = Box::pin(run_command(cli.command, config)).await;
+    timer.mark("command");
+    timer.report();
+    result
+}
+
+/// Commands that only read or edit local state (memory store, config file)
+/// and therefore do not need runtime tracing or OTP initialization.
+fn is_lightweight_command(command: &Commands) -> bool {
+    matches!(
+        command,
+        Commands::Memory { .. }
+            | Commands::Export { .. }
+            | Commands::Sessions { .. }
+            | Commands::Config { .. }
+            | Commands::Policy { .. }
+            | Commands::Escalation { .. }
+            | Commands::Audit { .. }
+            | Commands::Secrets { .. }
+            | Commands::Status
+    )
+}
+
+/// Machine-readable form of `zeroclaw status` for `--json`.
+fn status_json(config: &Config) -> serde_json::Value {
+    let channels: serde_json::Map<String, serde_json::Value> = config
+        .channels_config
+        .channels()
+        .into_iter()
+        .map(|(channel, configured)| (channel.name().to_string(), configured.into()))
+        .collect();
+    serde_json::json!({
+        "version": ZEROCLAW_BUILD_VERSION,
+        "workspace": config.workspace_dir,
+        "config": config.config_path,
+        "provider": config.default_provider.as_deref().unwrap_or("openrouter"),
+        "model": config.default_model,
+        "observability": config.observability.backend,
+        "autonomy": format!("{:?}", config.autonomy.level),
+        "runtime": config.runtime.kind,
+        "heartbeat_minutes": config
+            .heartbeat
+            .enabled
+            .then_some(config.heartbeat.interval_minutes),
+        "memory": {
+            "backend": memory::effective_memory_backend_name(
+                &config.memory.backend,
+                Some(&config.storage.provider.config),
+            ),
+            "auto_save": config.memory.auto_save,
+        },
+        "security": {
+            "workspace_only": config.autonomy.workspace_only,
+            "allowed_roots": config.autonomy.allowed_roots,
+            "allowed_commands": config.autonomy.allowed_commands,
+            "max_actions_per_hour": config.autonomy.max_actions_per_hour,
+            "max_cost_per_day_cents": config.autonomy.max_cost_per_day_cents,
+            "otp_enabled": config.security.otp.enabled,
+            "estop_enabled": config.security.estop.enabled,
+        },
+        "channels": channels,
+        "peripherals": {
+            "enabled": config.peripherals.enabled,
+            "boards": config.peripherals.boards.len(),
+        },
+    })
+}
+
+/// Wall-clock breakdown of CLI startup phases, reported with `--timing`.
+struct StartupTimer {
+    enabled: bool,
+    started: std::time::Instant,
+    last: std::time::Instant,
+    phases: Vec<(&'static str, std::time::Duration)>,
+}
+
+impl StartupTimer {
+    fn new(enabled: bool, started: std::time::Instant) -> Self {
+        Self {
+            enabled,
+            started,
+            last: started,
+            phases: Vec::new(),
         }
     }
 
-    match cli.command {
+    /// Close the current phase under `phase` and start timing the next one.
+    fn mark(&mut self, phase: &'static str) {
+        let now = std::time::Instant::now();
+        self.phases.push((phase, now.duration_since(self.last)));
+        self.last = now;
+    }
+
+    fn report(&self) {
+        if !self.enabled {
+            return;
+        }
+        eprintln!("Startup timing:");
+        for (phase, elapsed) in &self.phases {
+            eprintln!("  {phase:<14} {:>9.1} ms", elapsed.as_secs_f64() * 1000.0);
+        }
+        eprintln!(
+            "  {:<14} {:>9.1} ms",
+            "total",
+            self.last.duration_since(self.started).as_secs_f64() * 1000.0
+        );
+    }
+}
+
+async fn run_command(command: Commands, mut config: Config) -> Result<()> {
+    match command {
         Commands::Onboard { .. } | Commands::Completions { .. } => unreachable!(),
 
         Commands::Agent {
@@ -1193,6 +1580,8 @@ async fn main() -> Result<()> {
             daemon::run(config, host, port).await
         }
 
+        Commands::Status if util::json_output() => util::print_json(&status_json(&config)),
+
         Commands::Status => {
             println!("🦀 ZeroClaw Status");
             println!();
@@ -1312,6 +1701,16 @@ async fn main() -> Result<()> {
             handle_security_command(&config, security_command).await
         }
 
+        Commands::Policy { policy_command } => handle_policy_command(&config, policy_command).await,
+
+        Commands::Escalation { escalation_command } => {
+            handle_escalation_command(&config, escalation_command)
+        }
+
+        Commands::Audit { audit_command } => handle_audit_command(&config, audit_command),
+
+        Commands::Secrets { secrets_command } => handle_secrets_command(&config, secrets_command),
+
         Commands::Cron { cron_command } => cron::handle_command(cron_command, &config),
 
         Commands::Models { model_command } => match model_command {
@@ -1338,6 +1737,7 @@ async fn main() -> Result<()> {
 
         Commands::ProvidersQuota { provider, format } => {
             let format_str = match format {
+                _ if util::json_output() => "json",
                 QuotaFormat::Text => "text",
                 QuotaFormat::Json => "json",
             };
@@ -1352,15 +1752,32 @@ async fn main() -> Result<()> {
                 .unwrap_or("openrouter")
                 .trim()
                 .to_ascii_lowercase();
+            let is_active = |p: &providers::ProviderInfo| {
+                p.name.eq_ignore_ascii_case(&current)
+                    || p.aliases
+                        .iter()
+                        .any(|alias| alias.eq_ignore_ascii_case(&current))
+            };
+            if util::json_output() {
+                let rows: Vec<_> = providers
+                    .iter()
+                    .map(|p| {
+                        serde_json::json!({
+                            "name": p.name,
+                            "display_name": p.display_name,
+                            "aliases": p.aliases,
+                            "local": p.local,
+                            "active": is_active(p),
+                        })
+                    })
+                    .collect();
+                return util::print_json(&rows);
+            }
             println!("Supported providers ({} total):\n", providers.len());
             println!("  ID (use in config)  DESCRIPTION");
             println!("  ─────────────────── ───────────");
             for p in &providers {
-                let is_active = p.name.eq_ignore_ascii_case(&current)
-                    || p.aliases
-                        .iter()
-                        .any(|alias| alias.eq_ignore_ascii_case(&current));
-                let marker = if is_active { " (active)" } else { "" };
+                let marker = if is_active(p) { " (active)" } else { "" };
                 let local_tag = if p.local { " [local]" } else { "" };
                 let aliases = if p.aliases.is_empty() {
                     String::new()
@@ -1425,6 +1842,18 @@ async fn main() -> Result<()> {
             memory::cli::handle_command(memory_command, &config).await
         }
 
+        Commands::Export { export_command } => {
+            export::handle_command(export_command, &config).await
+        }
+
+        Commands::Sessions { session_command } => {
+            export::handle_session_command(session_command, &config).await
+        }
+
+        Commands::Tools { tool_command } => {
+            tools::schema_export::handle_command(tool_command, &config)
+        }
+
         Commands::Auth { auth_command } => handle_auth_command(auth_command, &config).await,
 
         Commands::Hardware { hardware_command } => {
@@ -1789,6 +2218,298 @@ async fn handle_security_command(
     }
 }
 
+async fn handle_policy_command(config: &Config, policy_command: PolicyCommands) -> Result<()> {
+    match policy_command {
+        PolicyCommands::Check { path } => {
+            let policy = security::SecurityPolicy::from_file(&path)?;
+            if util::json_output() {
+                return util::print_json(&serde_json::json!({
+                    "path": path,
+                    "valid": true,
+                    "autonomy": policy.autonomy,
+                    "workspace_dir": policy.workspace_dir,
+                    "max_actions_per_hour": policy.max_actions_per_hour,
+                    "per_tool_limits": policy.per_tool_limits.len(),
+                    "operating_window": policy.operating_window.as_ref().map(ToString::to_string),
+                    "allowed_commands": policy.allowed_commands,
+                    "allowed_tools": policy.allowed_tools,
+                    "denied_tools": policy.denied_tools,
+                }));
+            }
+
+            println!("Policy file {} is valid.", path.display());
+            println!("  Autonomy:         {:?}", policy.autonomy);
+            println!("  Workspace:        {}", policy.workspace_dir.display());
+            println!("  Actions per hour: {}", policy.max_actions_per_hour);
+            println!("  Per-tool limits:  {}", policy.per_tool_limits.len());
+            println!(
+                "  Operating window: {}",
+                policy
+                    .operating_window
+                    .as_ref()
+                    .map_or_else(|| "(always)".to_string(), ToString::to_string)
+            );
+            println!("  Allowed commands: {}", policy.allowed_commands.len());
+            println!(
+                "  Allowed tools:    {}",
+                list_or(&policy.allowed_tools, "(all)")
+            );
+            println!(
+                "  Denied tools:     {}",
+                list_or(&policy.denied_tools, "(none)")
+            );
+            Ok(())
+        }
+        PolicyCommands::Simulate {
+            path,
+            session,
+            calls,
+        } => {
+            use security::simulate;
+
+            let policy = simulate::load_policy(&path)?;
+            let calls = match (session, calls) {
+                (Some(id), _) => {
+                    simulate::calls_from_history(&export::load_session_history(config, &id).await?)
+                }
+                (None, Some(file)) => simulate::parse_calls(
+                    &std::fs::read_to_string(&file)
+                        .with_context(|| format!("Failed to read {}", file.display()))?,
+                )?,
+                (None, None) => bail!("pass --session or --calls"),
+            };
+            // Operations come from the tools the agent would register, so
+            // read-only calls do not spend the simulated action budget.
+            let registry = tools::all_tools(
+                std::sync::Arc::new(config.clone()),
+                &std::sync::Arc::new(security::SecurityPolicy::from_config(
+                    &config.autonomy,
+                    &config.workspace_dir,
+                )),
+                std::sync::Arc::new(memory::NoneMemory::new()),
+                None,
+                None,
+                &config.browser,
+                &config.http_request,
+                &config.web_fetch,
+                &config.workspace_dir,
+                &config.agents,
+                config.api_key.as_deref(),
+                config,
+            );
+            let outcomes = simulate::simulate(&policy, calls, &registry).await;
+            if util::json_output() {
+                return util::print_json(&outcomes);
+            }
+
+            for (index, outcome) in outcomes.iter().enumerate() {
+                let verdict = if outcome.allowed { "allow" } else { "deny " };
+                println!(
+                    "{:>4}  {verdict}  {}  {}",
+                    index + 1,
+                    outcome.call.tool,
+                    util::truncate_with_ellipsis(&outcome.call.arguments.to_string(), 80)
+                );
+                if let Some(reason) = &outcome.reason {
+                    println!("            {reason}");
+                }
+            }
+            let denied = outcomes.iter().filter(|outcome| !outcome.allowed).count();
+            println!(
+                "{} calls: {} allowed, {denied} denied by {}",
+                outcomes.len(),
+                outcomes.len() - denied,
+                path.display()
+            );
+            Ok(())
+        }
+    }
+}
+
+fn handle_escalation_command(
+    config: &Config,
+    escalation_command: EscalationCommands,
+) -> Result<()> {
+    let store = security::escalation::EscalationStore::new(
+        &security::SecurityPolicy::state_dir_for(config),
+    );
+    let decided = match escalation_command {
+        EscalationCommands::List => {
+            let requests = store.list()?;
+            if util::json_output() {
+                let now = chrono::Utc::now();
+                let requests: Vec<serde_json::Value> = requests
+                    .iter()
+                    .map(|request| {
+                        let mut value = serde_json::to_value(request)?;
+                        value["state"] = request.state_label(now).into();
+                        Ok(value)
+                    })
+                    .collect::<Result<_>>()?;
+                return util::print_json(&requests);
+            }
+            if requests.is_empty() {
+                println!("No escalation requests.");
+                return Ok(());
+            }
+            let now = chrono::Utc::now();
+            for request in requests {
+                let mut line = format!(
+                    "{}  {:<8} {}  requested {}",
+                    request.id,
+                    request.state_label(now),
+                    request.kind,
+                    request.requested_at.format("%Y-%m-%d %H:%M UTC")
+                );
+                if let Some(minutes) = request.requested_minutes {
+                    line.push_str(&format!(" for {minutes}m"));
+                }
+                if let Some(expires_at) = request.expires_at {
+                    line.push_str(&format!(
+                        ", expires {}",
+                        expires_at.format("%Y-%m-%d %H:%M UTC")
+                    ));
+                }
+                println!("{line}");
+                println!("    reason: {}", request.reason);
+            }
+            return Ok(());
+        }
+        EscalationCommands::Grant { id, minutes } => {
+            let request = store.grant(&id, minutes)?;
+            if !util::json_output() {
+                println!(
+                    "Granted {} ({}) for {minutes} minutes.",
+                    request.id, request.kind
+                );
+            }
+            request
+        }
+        EscalationCommands::Deny { id } => {
+            let request = store.deny(&id)?;
+            if !util::json_output() {
+                println!("Denied {} ({}).", request.id, request.kind);
+            }
+            request
+        }
+    };
+
+    if config.security.audit.enabled {
+        let zeroclaw_dir = config
+            .config_path
+            .parent()
+            .context("Config path must have a parent directory")?;
+        let operator = std::env::var("USER").unwrap_or_else(|_| "operator".to_string());
+        security::AuditLogger::shared(&config.security.audit, zeroclaw_dir)?
+            .log_escalation_decision(&operator, &decided)?;
+    }
+    if util::json_output() {
+        return util::print_json(&decided);
+    }
+    Ok(())
+}
+
+fn handle_audit_command(config: &Config, audit_command: AuditCommands) -> Result<()> {
+    match audit_command {
+        AuditCommands::Verify => {
+            let zeroclaw_dir = config
+                .config_path
+                .parent()
+                .context("Config path must have a parent directory")?;
+            let report = security::audit::verify_audit_log(&config.security.audit, zeroclaw_dir)?;
+            if util::json_output() {
+                // A failed check is reported through the error document, so
+                // stdout still holds exactly one JSON value.
+                if !report.is_intact() {
+                    bail!(
+                        "Audit log failed verification ({} problems): {}",
+                        report.problems.len(),
+                        report.problems.join("; ")
+                    );
+                }
+                return util::print_json(&report);
+            }
+            if report.files.is_empty() {
+                println!("No audit log to verify.");
+                return Ok(());
+            }
+            println!(
+                "Checked {} records in {} files against {} anchors.",
+                report.records,
+                report.files.len(),
+                report.anchors_checked
+            );
+            if report.unchained > 0 {
+                println!(
+                    "{} leading records predate hash chaining and were not checked.",
+                    report.unchained
+                );
+            }
+            for problem in &report.problems {
+                println!("  {problem}");
+            }
+            if !report.is_intact() {
+                bail!(
+                    "Audit log failed verification ({} problems)",
+                    report.problems.len()
+                );
+            }
+            println!("Audit log intact.");
+        }
+    }
+    Ok(())
+}
+
+fn handle_secrets_command(config: &Config, secrets_command: SecretsCommands) -> Result<()> {
+    let zeroclaw_dir = config
+        .config_path
+        .parent()
+        .context("Config path must have a parent directory")?;
+    let providers = security::SecretsProviders::new(zeroclaw_dir, &config.secrets.keychain_service);
+    let parse = |reference: &str| {
+        security::SecretRef::parse(reference)
+            .or_else(|| security::SecretRef::parse_bare(reference))
+            .with_context(|| {
+                format!("'{reference}' is not a keychain:, secretfile:, or env: reference")
+            })
+    };
+    match secrets_command {
+        SecretsCommands::Set { reference } => {
+            let reference = parse(&reference)?;
+            let value = if std::io::stdin().is_terminal() {
+                read_auth_input(&format!("Value for {reference}"))?
+            } else {
+                let mut value = String::new();
+                std::io::stdin().read_to_string(&mut value)?;
+                value.trim_end_matches(['\r', '\n']).to_string()
+            };
+            if value.is_empty() {
+                bail!("Refusing to store an empty secret");
+            }
+            providers
+                .provider(reference.backend)
+                .set(&reference.key, &value)?;
+            println!("Stored {reference}; use it as a value in config.toml.");
+        }
+        SecretsCommands::Delete { reference } => {
+            let reference = parse(&reference)?;
+            providers
+                .provider(reference.backend)
+                .delete(&reference.key)?;
+            println!("Deleted {reference}.");
+        }
+    }
+    Ok(())
+}
+
+fn list_or(entries: &[String], empty: &str) -> String {
+    if entries.is_empty() {
+        empty.to_string()
+    } else {
+        entries.join(", ")
+    }
+}
+
 // ─── Generic Pending OAuth Login ────────────────────────────────────────────
 
 /// Generic pending OAuth login state, shared across providers.
@@ -2524,6 +3245,47 @@ mod tests {
         Cli::command().debug_assert();
     }
 
+    #[test]
+    fn timing_flag_is_global() {
+        let cli = Cli::try_parse_from(["zeroclaw", "memory", "stats", "--timing"])
+            .expect("--timing should be accepted after a subcommand");
+        assert!(cli.timing);
+        assert!(is_lightweight_command(&cli.command));
+    }
+
+    #[test]
+    fn json_flag_is_global() {
+        let cli = Cli::try_parse_from(["zeroclaw", "doctor", "--json"])
+            .expect("--json should be accepted after a subcommand");
+        assert!(cli.json);
+    }
+
+    #[test]
+    fn status_json_reports_core_settings() {
+        let config = Config::default();
+        let status = status_json(&config);
+        assert_eq!(status["version"], ZEROCLAW_BUILD_VERSION);
+        assert_eq!(status["memory"]["auto_save"], config.memory.auto_save);
+        assert!(status["channels"].is_object());
+        assert!(status["security"]["allowed_commands"].is_array());
+    }
+
+    #[test]
+    fn agent_command_is_not_lightweight() {
+        let cli = Cli::try_parse_from(["zeroclaw", "agent", "-m", "hi"]).expect("parse agent");
+        assert!(!cli.timing);
+        assert!(!is_lightweight_command(&cli.command));
+    }
+
+    #[test]
+    fn startup_timer_records_phases_in_order() {
+        let mut timer = StartupTimer::new(false, std::time::Instant::now());
+        timer.mark("parse args");
+        timer.mark("config load");
+        let names: Vec<&str> = timer.phases.iter().map(|(name, _)| *name).collect();
+        assert_eq!(names, vec!["parse args", "config load"]);
+    }
+
     #[test]
     fn onboard_help_includes_model_flag() {
         let cmd = Cli::command();
@@ -2806,6 +3568,81 @@ mod tests {
         }
     }
 
+    #[test]
+    fn cli_policy_simulate_takes_exactly_one_call_source() {
+        let cli = Cli::try_parse_from([
+            "zeroclaw",
+            "policy",
+            "simulate",
+            "policy.toml",
+            "--calls",
+            "calls.jsonl",
+        ])
+        .expect("policy simulate --calls should parse");
+        match cli.command {
+            Commands::Policy {
+                policy_command: PolicyCommands::Simulate { session, calls, .. },
+            } => {
+                assert!(session.is_none());
+                assert_eq!(calls.as_deref(), Some(std::path::Path::new("calls.jsonl")));
+            }
+            other => panic!("expected policy simulate, got {other:?}"),
+        }
+
+        assert!(Cli::try_parse_from(["zeroclaw", "policy", "simulate", "policy.toml"]).is_err());
+        assert!(Cli::try_parse_from([
+            "zeroclaw",
+            "policy",
+            "simulate",
+            "policy.toml",
+            "--session",
+            "main",
+            "--calls",
+            "calls.jsonl",
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn cli_parses_audit_verify() {
+        let cli = Cli::try_parse_from(["zeroclaw", "audit", "verify"])
+            .expect("audit verify should parse");
+        assert!(matches!(
+            cli.command,
+            Commands::Audit {
+                audit_command: AuditCommands::Verify
+            }
+        ));
+    }
+
+    #[test]
+    fn cli_parses_secrets_set_reference() {
+        let cli = Cli::try_parse_from(["zeroclaw", "secrets", "set", "keychain:openrouter"])
+            .expect("secrets set should parse");
+        match cli.command {
+            Commands::Secrets {
+                secrets_command: SecretsCommands::Set { reference },
+            } => assert_eq!(reference, "keychain:openrouter"),
+            other => panic!("expected secrets set, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn cli_parses_escalation_grant_with_default_minutes() {
+        let cli = Cli::try_parse_from(["zeroclaw", "escalation", "grant", "3f9a1c2e"])
+            .expect("escalation grant should parse");
+
+        match cli.command {
+            Commands::Escalation {
+                escalation_command: EscalationCommands::Grant { id, minutes },
+            } => {
+                assert_eq!(id, "3f9a1c2e");
+                assert_eq!(minutes, 60);
+            }
+            other => panic!("expected escalation grant, got {other:?}"),
+        }
+    }
+
     #[test]
     fn cli_parses_estop_default_engage() {
         let cli = Cli::try_parse_from(["zeroclaw", "estop"]).expect("estop command should parse");
@@ -4,9 +4,8 @@ use super::{
     MemoryBackendKind,
 };
 use crate::config::Config;
-#[cfg(feature = "memory-postgres")]
-use anyhow::Context;
-use anyhow::{bail, Result};
+use crate::util::{json_output, print_json};
+use anyhow::{bail, Context, Result};
 use console::style;
 
 /// Handle `zeroclaw memory <subcommand>` CLI commands.
@@ -26,6 +25,18 @@ pub async fn handle_command(command: crate::MemoryCommands, config: &Config) ->
         crate::MemoryCommands::Reindex { yes, progress } => {
             handle_reindex(config, yes, progress).await
         }
+        crate::MemoryCommands::Export { output } => handle_export(config, output.as_deref()).await,
+        crate::MemoryCommands::Import {
+            path,
+            category,
+            dry_run,
+        } => handle_import(config, &path, &category, dry_run).await,
+        crate::MemoryCommands::Migrate {
+            path,
+            category,
+            dry_run,
+        } => handle_migrate(config, &path, &category, dry_run).await,
+        crate::MemoryCommands::Diff { from, to } => handle_diff(config, &from, to.as_deref()).await,
     }
 }
 
@@ -34,13 +45,13 @@ pub async fn handle_command(command: crate::MemoryCommands, config: &Config) ->
 /// CLI commands (list/get/stats/clear) never use vector search, so we skip
 /// embedding provider initialisation for local backends by using the
 /// migration factory.  Postgres still needs its full connection config.
-fn create_cli_memory(config: &Config) -> Result<Box<dyn Memory>> {
+pub(crate) fn create_cli_memory(config: &Config) -> Result<Box<dyn Memory>> {
     let backend = effective_memory_backend_name(
         &config.memory.backend,
         Some(&config.storage.provider.config),
     );
 
-    match classify_memory_backend(&backend) {
+    let mem: Box<dyn Memory> = match classify_memory_backend(&backend) {
         MemoryBackendKind::None => {
             bail!("Memory backend is 'none' (disabled). No entries to manage.");
         }
@@ -64,7 +75,7 @@ fn create_cli_memory(config: &Config) -> Result<Box<dyn Memory>> {
                     sp.connect_timeout_secs,
                     sp.tls,
                 )?;
-                Ok(Box::new(mem))
+                Box::new(mem)
             }
             #[cfg(not(feature = "memory-postgres"))]
             {
@@ -75,8 +86,9 @@ fn create_cli_memory(config: &Config) -> Result<Box<dyn Memory>> {
         MemoryBackendKind::Postgres => {
             bail!("memory backend 'postgres' requires the 'memory-postgres' feature to be enabled");
         }
-        _ => create_memory_for_migration(&backend, &config.workspace_dir),
-    }
+        _ => create_memory_for_migration(&backend, &config.workspace_dir)?,
+    };
+    super::encrypted::wrap_if_enabled(&config.memory, &config.workspace_dir, mem)
 }
 
 async fn handle_list(
@@ -90,14 +102,22 @@ async fn handle_list(
     let cat = category.as_deref().map(parse_category);
     let entries = mem.list(cat.as_ref(), session.as_deref()).await?;
 
-    if entries.is_empty() {
+    let total = entries.len();
+    let page: Vec<_> = entries.into_iter().skip(offset).take(limit).collect();
+
+    if json_output() {
+        return print_json(&serde_json::json!({
+            "total": total,
+            "offset": offset,
+            "entries": page.iter().map(entry_json).collect::<Vec<_>>(),
+        }));
+    }
+
+    if total == 0 {
         println!("No memory entries found.");
         return Ok(());
     }
 
-    let total = entries.len();
-    let page: Vec<_> = entries.into_iter().skip(offset).take(limit).collect();
-
     if page.is_empty() {
         println!("No entries at offset {offset} (total: {total}).");
         return Ok(());
@@ -130,6 +150,9 @@ async fn handle_get(config: &Config, key: &str) -> Result<()> {
 
     // Try exact match first.
     if let Some(entry) = mem.get(key).await? {
+        if json_output() {
+            return print_json(&entry_json(&entry));
+        }
         print_entry(&entry);
         return Ok(());
     }
@@ -138,6 +161,17 @@ async fn handle_get(config: &Config, key: &str) -> Result<()> {
     let all = mem.list(None, None).await?;
     let matches: Vec<_> = all.iter().filter(|e| e.key.starts_with(key)).collect();
 
+    if json_output() {
+        return match matches.as_slice() {
+            [] => bail!("No memory entry found for key: {key}"),
+            [entry] => print_json(&entry_json(entry)),
+            _ => print_json(&serde_json::json!({
+                "ambiguous": true,
+                "matches": matches.iter().map(|e| e.key.as_str()).collect::<Vec<_>>(),
+            })),
+        };
+    }
+
     match matches.len() {
         0 => println!("No memory entry found for key: {key}"),
         1 => print_entry(matches[0]),
@@ -157,6 +191,18 @@ async fn handle_get(config: &Config, key: &str) -> Result<()> {
     Ok(())
 }
 
+/// JSON form of an entry with the category rendered as its display name.
+fn entry_json(entry: &super::traits::MemoryEntry) -> serde_json::Value {
+    serde_json::json!({
+        "id": entry.id,
+        "key": entry.key,
+        "content": entry.content,
+        "category": entry.category.to_string(),
+        "timestamp": entry.timestamp,
+        "session_id": entry.session_id,
+    })
+}
+
 fn print_entry(entry: &super::traits::MemoryEntry) {
     println!("Key:       {}", style(&entry.key).white().bold());
     println!("Category:  {}", entry.category);
@@ -172,6 +218,21 @@ async fn handle_stats(config: &Config) -> Result<()> {
     let healthy = mem.health_check().await;
     let total = mem.count().await.unwrap_or(0);
 
+    if json_output() {
+        let all = mem.list(None, None).await.unwrap_or_default();
+        let mut counts: std::collections::BTreeMap<String, usize> =
+            std::collections::BTreeMap::new();
+        for entry in &all {
+            *counts.entry(entry.category.to_string()).or_default() += 1;
+        }
+        return print_json(&serde_json::json!({
+            "backend": mem.name(),
+            "healthy": healthy,
+            "total": total,
+            "by_category": counts,
+        }));
+    }
+
     println!("Memory Statistics:\n");
     println!("  Backend:  {}", style(mem.name()).white().bold());
     println!(
@@ -208,6 +269,9 @@ async fn handle_clear(
     category: Option<String>,
     yes: bool,
 ) -> Result<()> {
+    if json_output() && !yes {
+        bail!("--json cannot prompt for confirmation; pass --yes to clear memories");
+    }
     let mem = create_cli_memory(config)?;
 
     // Single-key deletion (exact or prefix match).
@@ -220,12 +284,17 @@ async fn handle_clear(
     let entries = mem.list(cat.as_ref(), None).await?;
 
     if entries.is_empty() {
+        if json_output() {
+            return print_json(&serde_json::json!({ "deleted": 0, "matched": 0 }));
+        }
         println!("No entries to clear.");
         return Ok(());
     }
 
-    let scope = category.as_deref().unwrap_or("all categories");
-    println!("Found {} entries in '{scope}'.", entries.len());
+    if !json_output() {
+        let scope = category.as_deref().unwrap_or("all categories");
+        println!("Found {} entries in '{scope}'.", entries.len());
+    }
 
     if !yes {
         let confirmed = dialoguer::Confirm::new()
@@ -245,6 +314,9 @@ async fn handle_clear(
         }
     }
 
+    if json_output() {
+        return print_json(&serde_json::json!({ "deleted": deleted, "matched": entries.len() }));
+    }
     println!(
         "{} Cleared {deleted}/{} entries.",
         style("✓").green().bold(),
@@ -263,11 +335,15 @@ async fn handle_clear_key(mem: &dyn Memory, key: &str, yes: bool) -> Result<()>
         let all = mem.list(None, None).await?;
         let matches: Vec<_> = all.iter().filter(|e| e.key.starts_with(key)).collect();
         match matches.len() {
+            0 if json_output() => bail!("No memory entry found for key: {key}"),
             0 => {
                 println!("No memory entry found for key: {key}");
                 return Ok(());
             }
             1 => matches[0].key.clone(),
+            n if json_output() => {
+                bail!("Prefix '{key}' matched {n} entries; specify a longer prefix")
+            }
             n => {
                 println!("Prefix '{key}' matched {n} entries:\n");
                 for entry in matches {
@@ -294,7 +370,11 @@ async fn handle_clear_key(mem: &dyn Memory, key: &str, yes: bool) -> Result<()>
         }
     }
 
-    if mem.forget(&target).await? {
+    let deleted = mem.forget(&target).await?;
+    if json_output() {
+        return print_json(&serde_json::json!({ "deleted": usize::from(deleted), "key": target }));
+    }
+    if deleted {
         println!("{} Deleted key: {target}", style("✓").green().bold());
     }
 
@@ -306,6 +386,10 @@ async fn handle_reindex(config: &Config, yes: bool, progress: bool) -> Result<()
     use std::sync::atomic::{AtomicUsize, Ordering};
     use std::sync::Arc;
 
+    if json_output() && !yes {
+        bail!("--json cannot prompt for confirmation; pass --yes to reindex");
+    }
+
     // Reindex requires full memory backend with embeddings
     let mem = super::create_memory(&config.memory, &config.workspace_dir, None)?;
 
@@ -313,18 +397,23 @@ async fn handle_reindex(config: &Config, yes: bool, progress: bool) -> Result<()
     let total = mem.count().await?;
 
     if total == 0 {
+        if json_output() {
+            return print_json(&serde_json::json!({ "reindexed": 0 }));
+        }
         println!("No memories to reindex.");
         return Ok(());
     }
 
-    println!(
-        "\n{} Found {} memories to reindex.",
-        style("ℹ").blue().bold(),
-        style(total).cyan().bold()
-    );
-    println!(
-        "  This will clear the embedding cache and recompute all embeddings\n  using the current embedding provider configuration.\n"
-    );
+    if !json_output() {
+        println!(
+            "\n{} Found {} memories to reindex.",
+            style("ℹ").blue().bold(),
+            style(total).cyan().bold()
+        );
+        println!(
+            "  This will clear the embedding cache and recompute all embeddings\n  using the current embedding provider configuration.\n"
+        );
+    }
 
     if !yes {
         let confirmed = dialoguer::Confirm::new()
@@ -337,7 +426,9 @@ async fn handle_reindex(config: &Config, yes: bool, progress: bool) -> Result<()
         }
     }
 
-    println!("\n{} Reindexing memories...\n", style("⟳").yellow().bold());
+    if !json_output() {
+        println!("\n{} Reindexing memories...\n", style("⟳").yellow().bold());
+    }
 
     // Create progress callback if enabled
     let callback: Option<Box<dyn Fn(usize, usize) + Send + Sync>> = if progress {
@@ -361,6 +452,9 @@ async fn handle_reindex(config: &Config, yes: bool, progress: bool) -> Result<()
     // Perform reindex
     let reindexed = mem.reindex(callback).await?;
 
+    if json_output() {
+        return print_json(&serde_json::json!({ "reindexed": reindexed }));
+    }
     println!(
         "\n{} Reindexed {} memories successfully.",
         style("✓").green().bold(),
@@ -370,7 +464,315 @@ async fn handle_reindex(config: &Config, yes: bool, progress: bool) -> Result<()
     Ok(())
 }
 
-fn parse_category(s: &str) -> MemoryCategory {
+/// Import Markdown/text notes, embedding them when a provider is configured.
+async fn handle_export(config: &Config, output: Option<&str>) -> Result<()> {
+    let mem = create_cli_memory(config)?;
+    let dump = super::dump::export_memory(&*mem).await?;
+    let document = serde_json::to_string_pretty(&dump)?;
+
+    let Some(path) = output else {
+        println!("{document}");
+        return Ok(());
+    };
+    std::fs::write(path, document + "\n").with_context(|| format!("Failed to write {path}"))?;
+    if json_output() {
+        return print_json(&serde_json::json!({
+            "entries": dump.entries.len(),
+            "backend": dump.backend,
+            "path": path,
+        }));
+    }
+    println!(
+        "{} Exported {} entries from the {} backend to {path}",
+        style("✓").green().bold(),
+        dump.entries.len(),
+        dump.backend,
+    );
+    Ok(())
+}
+
+async fn handle_migrate(config: &Config, path: &str, category: &str, dry_run: bool) -> Result<()> {
+    let plans = super::migrate::plan_migration(std::path::Path::new(path))?;
+    let category = parse_category(category);
+    let planned: usize = plans.iter().map(|plan| plan.notes.len()).sum();
+
+    let stored = if dry_run {
+        0
+    } else {
+        // Use the full backend so `store` embeds entries when the index is enabled.
+        let mem = super::create_memory_with_storage_and_routes(
+            &config.memory,
+            &config.embedding_routes,
+            Some(&config.storage.provider.config),
+            &config.workspace_dir,
+            config.api_key.as_deref(),
+        )?;
+        super::migrate::migrate_notes(&*mem, &plans, &category).await?
+    };
+
+    if json_output() {
+        let files: Vec<_> = plans
+            .iter()
+            .map(|plan| {
+                serde_json::json!({
+                    "path": plan.file,
+                    "keys": plan.notes.iter().map(|n| n.key.as_str()).collect::<Vec<_>>(),
+                    "skipped": plan
+                        .skipped
+                        .iter()
+                        .map(|s| serde_json::json!({ "line": s.line, "reason": s.reason }))
+                        .collect::<Vec<_>>(),
+                })
+            })
+            .collect();
+        return print_json(&serde_json::json!({
+            "dry_run": dry_run,
+            "entries": if dry_run { planned } else { stored },
+            "category": category.to_string(),
+            "files": files,
+        }));
+    }
+
+    for plan in &plans {
+        println!("{}: {} notes", plan.file, plan.notes.len());
+        if dry_run {
+            for note in &plan.notes {
+                println!("  - {} (line {})", note.key, note.line);
+            }
+        }
+        for skipped in &plan.skipped {
+            println!(
+                "  {} skipped line {}: {}",
+                style("!").yellow().bold(),
+                skipped.line,
+                skipped.reason
+            );
+        }
+    }
+    let skipped: usize = plans.iter().map(|plan| plan.skipped.len()).sum();
+    if dry_run {
+        println!(
+            "\nDry run: {planned} entries from {} files would be stored in '{category}' ({skipped} skipped).",
+            plans.len()
+        );
+    } else {
+        println!(
+            "{} Migrated {stored} entries from {} files into '{category}' ({skipped} skipped).",
+            style("✓").green().bold(),
+            plans.len()
+        );
+    }
+    Ok(())
+}
+
+async fn handle_diff(config: &Config, from: &str, to: Option<&str>) -> Result<()> {
+    let before = super::dump::load_dump(std::path::Path::new(from))?;
+    let after = match to {
+        Some(path) => super::dump::load_dump(std::path::Path::new(path))?,
+        None => super::dump::export_memory(&*create_cli_memory(config)?).await?,
+    };
+    let diff = super::dump::diff_dumps(&before, &after);
+    let to_label = to.unwrap_or("current memory");
+
+    if json_output() {
+        return print_json(&serde_json::json!({
+            "from": from,
+            "to": to_label,
+            "diff": diff,
+        }));
+    }
+
+    println!("Memory diff: {from} → {to_label}\n");
+    if diff.is_empty() {
+        println!("No differences.");
+        return Ok(());
+    }
+    for entry in &diff.added {
+        println!(
+            "  {} {} [{}] {}",
+            style("+").green().bold(),
+            entry.key,
+            entry.category,
+            truncate_content(&entry.content, 60)
+        );
+    }
+    for entry in &diff.removed {
+        println!(
+            "  {} {} [{}] {}",
+            style("-").red().bold(),
+            entry.key,
+            entry.category,
+            truncate_content(&entry.content, 60)
+        );
+    }
+    for change in &diff.redacted {
+        println!(
+            "  {} {} [{}] redacted",
+            style("!").yellow().bold(),
+            change.key,
+            change.after.category
+        );
+    }
+    for change in &diff.changed {
+        println!(
+            "  {} {} [{}] {}",
+            style("~").cyan().bold(),
+            change.key,
+            change.after.category,
+            truncate_content(&change.after.content, 60)
+        );
+    }
+    println!(
+        "\n{} added, {} removed, {} redacted, {} changed.",
+        diff.added.len(),
+        diff.removed.len(),
+        diff.redacted.len(),
+        diff.changed.len()
+    );
+    Ok(())
+}
+
+/// Restore a JSON dump written by `memory export`.
+async fn handle_import_dump(config: &Config, path: &std::path::Path, dry_run: bool) -> Result<()> {
+    let raw = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    let dump = super::dump::parse_dump(&raw)
+        .with_context(|| format!("{} is not a zeroclaw memory dump", path.display()))?;
+
+    if dry_run {
+        if json_output() {
+            return print_json(&serde_json::json!({
+                "dry_run": true,
+                "entries": dump.entries.len(),
+                "keys": dump.entries.iter().map(|e| e.key.as_str()).collect::<Vec<_>>(),
+            }));
+        }
+        for entry in &dump.entries {
+            println!("- {} [{}]", entry.key, entry.category);
+        }
+        println!(
+            "\nDry run: {} entries from a {} dump would be stored.",
+            dump.entries.len(),
+            dump.backend
+        );
+        return Ok(());
+    }
+
+    // Use the full backend so `store` embeds entries when the index is enabled.
+    let mem = super::create_memory_with_storage_and_routes(
+        &config.memory,
+        &config.embedding_routes,
+        Some(&config.storage.provider.config),
+        &config.workspace_dir,
+        config.api_key.as_deref(),
+    )?;
+    let stored = super::dump::import_memory(&*mem, &dump).await?;
+
+    if json_output() {
+        return print_json(&serde_json::json!({ "entries": stored }));
+    }
+    println!(
+        "{} Imported {stored} entries from {}.",
+        style("✓").green().bold(),
+        path.display()
+    );
+    Ok(())
+}
+
+async fn handle_import(config: &Config, path: &str, category: &str, dry_run: bool) -> Result<()> {
+    let root = std::path::Path::new(path);
+    if root.is_file()
+        && root
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("json"))
+    {
+        return handle_import_dump(config, root, dry_run).await;
+    }
+    let category = parse_category(category);
+
+    if dry_run {
+        let plan = super::import::plan_import(root, super::import::DEFAULT_IMPORT_CHUNK_TOKENS)?;
+        if json_output() {
+            let files: Vec<_> = plan
+                .iter()
+                .map(|(relative, chunks)| {
+                    serde_json::json!({
+                        "path": relative,
+                        "entries": chunks.as_ref().map(Vec::len),
+                        "keys": chunks.iter().flatten().map(|c| c.key.as_str()).collect::<Vec<_>>(),
+                    })
+                })
+                .collect();
+            return print_json(&serde_json::json!({ "dry_run": true, "files": files }));
+        }
+        let mut entries = 0usize;
+        for (relative, chunks) in &plan {
+            match chunks {
+                Some(chunks) => {
+                    entries += chunks.len();
+                    let tags = chunks
+                        .first()
+                        .map(|c| c.tags.join(", "))
+                        .unwrap_or_default();
+                    println!("- {relative} → {} entries  {tags}", chunks.len());
+                }
+                None => println!("- {relative} (unreadable, skipped)"),
+            }
+        }
+        println!(
+            "
+Dry run: {entries} entries from {} files would be stored as '{category}'.",
+            plan.len()
+        );
+        return Ok(());
+    }
+
+    // Use the full backend so `store` embeds entries when the index is enabled.
+    let mem = super::create_memory_with_storage_and_routes(
+        &config.memory,
+        &config.embedding_routes,
+        Some(&config.storage.provider.config),
+        &config.workspace_dir,
+        config.api_key.as_deref(),
+    )?;
+    let embedding = config.memory.embedding_provider.trim();
+    if !json_output() && (embedding.is_empty() || embedding == "none") {
+        println!(
+            "{} No embedding provider configured; entries will be keyword-searchable only.",
+            style("ℹ").blue().bold()
+        );
+    }
+
+    let report = super::import::import_notes(
+        &*mem,
+        root,
+        &category,
+        super::import::DEFAULT_IMPORT_CHUNK_TOKENS,
+    )
+    .await?;
+
+    if json_output() {
+        return print_json(&serde_json::json!({
+            "files": report.files,
+            "entries": report.entries,
+            "skipped_files": report.skipped_files,
+            "category": category.to_string(),
+        }));
+    }
+    println!(
+        "{} Imported {} entries from {} files into '{category}'.",
+        style("✓").green().bold(),
+        report.entries,
+        report.files,
+    );
+    if report.skipped_files > 0 {
+        println!("  Skipped {} unreadable files.", report.skipped_files);
+    }
+
+    Ok(())
+}
+
+pub(super) fn parse_category(s: &str) -> MemoryCategory {
     match s.trim().to_ascii_lowercase().as_str() {
         "core" => MemoryCategory::Core,
         "daily" => MemoryCategory::Daily,
@@ -0,0 +1,311 @@
+//! JSON memory dump — a backend-neutral export of every memory entry.
+//!
+//! `zeroclaw memory export` writes a [`MemoryDump`]; `zeroclaw memory import`
+//! on a `.json` file stores its entries back through the [`Memory`] trait, so
+//! a dump taken from one backend or workspace can be loaded into another.
+//! Re-importing updates entries in place by key. `zeroclaw memory diff`
+//! compares two dumps (or a `MEMORY_SNAPSHOT.md`) to audit what changed.
+
+use super::cli::parse_category;
+use super::import::inline_tags;
+use super::traits::Memory;
+use crate::tools::redact_memory::REDACTION_MARKER;
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// Dump format version written to [`MemoryDump::version`].
+pub const MEMORY_DUMP_VERSION: u32 = 1;
+
+/// Top-level JSON document produced by `zeroclaw memory export`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MemoryDump {
+    pub version: u32,
+    pub exported_at: String,
+    /// Backend the entries were read from (informational).
+    pub backend: String,
+    pub entries: Vec<DumpEntry>,
+}
+
+/// One memory entry in a dump.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DumpEntry {
+    pub key: String,
+    /// `core`, `daily`, `conversation`, or a custom category name.
+    pub category: String,
+    pub content: String,
+    /// When the entry was last written. Informational: importing stamps
+    /// entries with the import time.
+    pub timestamp: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session_id: Option<String>,
+    /// `#tags` found in the content (informational; derived on export).
+    #[serde(default)]
+    pub tags: Vec<String>,
+}
+
+/// Read every entry from `mem` into a dump.
+pub async fn export_memory(mem: &dyn Memory) -> Result<MemoryDump> {
+    let entries = mem
+        .list(None, None)
+        .await?
+        .into_iter()
+        .map(|entry| DumpEntry {
+            tags: inline_tags(&entry.content),
+            key: entry.key,
+            category: entry.category.to_string(),
+            content: entry.content,
+            timestamp: entry.timestamp,
+            session_id: entry.session_id,
+        })
+        .collect();
+    Ok(MemoryDump {
+        version: MEMORY_DUMP_VERSION,
+        exported_at: chrono::Local::now().to_rfc3339(),
+        backend: mem.name().to_string(),
+        entries,
+    })
+}
+
+/// Parse a dump, rejecting versions this build does not understand.
+pub fn parse_dump(raw: &str) -> Result<MemoryDump> {
+    let dump: MemoryDump = serde_json::from_str(raw)?;
+    if dump.version > MEMORY_DUMP_VERSION {
+        bail!(
+            "Memory dump version {} is newer than this zeroclaw supports ({MEMORY_DUMP_VERSION}); upgrade zeroclaw",
+            dump.version
+        );
+    }
+    Ok(dump)
+}
+
+/// Read a dump from `path`: a JSON dump from `memory export`, or a
+/// `MEMORY_SNAPSHOT.md` (whose entries are all `core`).
+pub fn load_dump(path: &Path) -> Result<MemoryDump> {
+    let raw = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    let is_markdown = path
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("md"));
+    if !is_markdown {
+        return parse_dump(&raw)
+            .with_context(|| format!("{} is not a zeroclaw memory dump", path.display()));
+    }
+    let entries = super::snapshot::parse_snapshot(&raw)
+        .into_iter()
+        .map(|(key, content)| DumpEntry {
+            tags: inline_tags(&content),
+            key,
+            category: "core".into(),
+            content,
+            timestamp: String::new(),
+            session_id: None,
+        })
+        .collect();
+    Ok(MemoryDump {
+        version: MEMORY_DUMP_VERSION,
+        exported_at: String::new(),
+        backend: "snapshot".into(),
+        entries,
+    })
+}
+
+/// An entry whose content or category differs between two dumps.
+#[derive(Debug, Clone, Serialize)]
+pub struct ChangedEntry {
+    pub key: String,
+    pub before: DumpEntry,
+    pub after: DumpEntry,
+}
+
+/// Entry-level differences between two dumps, each list sorted by key.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct MemoryDiff {
+    pub added: Vec<DumpEntry>,
+    pub removed: Vec<DumpEntry>,
+    /// Entries whose content was replaced by the `redact_memory` marker.
+    /// `before` holds the original content.
+    pub redacted: Vec<ChangedEntry>,
+    pub changed: Vec<ChangedEntry>,
+}
+
+impl MemoryDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty()
+            && self.removed.is_empty()
+            && self.redacted.is_empty()
+            && self.changed.is_empty()
+    }
+}
+
+/// Compare two dumps by key. Timestamps alone do not count as a change.
+pub fn diff_dumps(before: &MemoryDump, after: &MemoryDump) -> MemoryDiff {
+    let old: BTreeMap<&str, &DumpEntry> =
+        before.entries.iter().map(|e| (e.key.as_str(), e)).collect();
+    let new: BTreeMap<&str, &DumpEntry> =
+        after.entries.iter().map(|e| (e.key.as_str(), e)).collect();
+
+    let mut diff = MemoryDiff::default();
+    for (key, entry) in &new {
+        let Some(previous) = old.get(key) else {
+            diff.added.push((*entry).clone());
+            continue;
+        };
+        if previous.content == entry.content && previous.category == entry.category {
+            continue;
+        }
+        let change = ChangedEntry {
+            key: (*key).to_string(),
+            before: (*previous).clone(),
+            after: (*entry).clone(),
+        };
+        if entry.content == REDACTION_MARKER && previous.content != REDACTION_MARKER {
+            diff.redacted.push(change);
+        } else {
+            diff.changed.push(change);
+        }
+    }
+    diff.removed = old
+        .iter()
+        .filter(|(key, _)| !new.contains_key(*key))
+        .map(|(_, entry)| (*entry).clone())
+        .collect();
+    diff
+}
+
+/// Store every entry of `dump` into `mem`. Returns the number stored.
+pub async fn import_memory(mem: &dyn Memory, dump: &MemoryDump) -> Result<usize> {
+    for entry in &dump.entries {
+        mem.store(
+            &entry.key,
+            &entry.content,
+            parse_category(&entry.category),
+            entry.session_id.as_deref(),
+        )
+        .await?;
+    }
+    Ok(dump.entries.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::{MemoryCategory, SqliteMemory};
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn export_then_import_round_trips_entries() {
+        let src_dir = TempDir::new().unwrap();
+        let src = SqliteMemory::new(src_dir.path()).unwrap();
+        src.store(
+            "deploy",
+            "Blue/green rollout\n\nTags: #ops",
+            MemoryCategory::Core,
+            None,
+        )
+        .await
+        .unwrap();
+        src.store(
+            "standup",
+            "Release blocked",
+            MemoryCategory::Custom("meetings".into()),
+            Some("telegram:42"),
+        )
+        .await
+        .unwrap();
+
+        let dump = export_memory(&src).await.unwrap();
+        assert_eq!(dump.version, MEMORY_DUMP_VERSION);
+        assert_eq!(dump.backend, "sqlite");
+        let deploy = dump.entries.iter().find(|e| e.key == "deploy").unwrap();
+        assert_eq!(deploy.tags, vec!["ops".to_string()]);
+
+        let json = serde_json::to_string(&dump).unwrap();
+        let parsed = parse_dump(&json).unwrap();
+
+        let dst_dir = TempDir::new().unwrap();
+        let dst = SqliteMemory::new(dst_dir.path()).unwrap();
+        assert_eq!(import_memory(&dst, &parsed).await.unwrap(), 2);
+
+        let standup = dst.get("standup").await.unwrap().unwrap();
+        assert_eq!(standup.content, "Release blocked");
+        assert_eq!(standup.category, MemoryCategory::Custom("meetings".into()));
+        assert_eq!(standup.session_id.as_deref(), Some("telegram:42"));
+    }
+
+    fn dump_of(entries: &[(&str, &str)]) -> MemoryDump {
+        MemoryDump {
+            version: MEMORY_DUMP_VERSION,
+            exported_at: String::new(),
+            backend: "sqlite".into(),
+            entries: entries
+                .iter()
+                .map(|(key, content)| DumpEntry {
+                    key: (*key).into(),
+                    category: "core".into(),
+                    content: (*content).into(),
+                    timestamp: String::new(),
+                    session_id: None,
+                    tags: Vec::new(),
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn diff_reports_added_removed_redacted_and_changed() {
+        let before = dump_of(&[
+            ("kept", "same"),
+            ("gone", "old note"),
+            ("wifi", "password is hunter2"),
+            ("lang", "User prefers Go"),
+        ]);
+        let after = dump_of(&[
+            ("kept", "same"),
+            ("wifi", REDACTION_MARKER),
+            ("lang", "User prefers Rust"),
+            ("new", "fresh note"),
+        ]);
+        let diff = diff_dumps(&before, &after);
+
+        assert_eq!(diff.added.len(), 1);
+        assert_eq!(diff.added[0].key, "new");
+        assert_eq!(diff.removed.len(), 1);
+        assert_eq!(diff.removed[0].key, "gone");
+        assert_eq!(diff.redacted.len(), 1);
+        assert_eq!(diff.redacted[0].before.content, "password is hunter2");
+        assert_eq!(diff.changed.len(), 1);
+        assert_eq!(diff.changed[0].key, "lang");
+        assert!(diff_dumps(&after, &after).is_empty());
+    }
+
+    #[test]
+    fn load_dump_reads_markdown_snapshots() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("MEMORY_SNAPSHOT.md");
+        std::fs::write(
+            &path,
+            "# Snapshot\n\n---\n\n### 🔑 `identity`\n\nI am ZeroClaw\n\n*Created: x | Updated: y*\n\n---\n",
+        )
+        .unwrap();
+        let dump = load_dump(&path).unwrap();
+        assert_eq!(dump.backend, "snapshot");
+        assert_eq!(dump.entries.len(), 1);
+        assert_eq!(dump.entries[0].key, "identity");
+        assert_eq!(dump.entries[0].content, "I am ZeroClaw");
+    }
+
+    #[test]
+    fn parse_dump_rejects_newer_versions() {
+        let raw = serde_json::json!({
+            "version": MEMORY_DUMP_VERSION + 1,
+            "exported_at": "2025-01-01T00:00:00Z",
+            "backend": "sqlite",
+            "entries": [],
+        })
+        .to_string();
+        let err = parse_dump(&raw).unwrap_err();
+        assert!(err.to_string().contains("upgrade zeroclaw"));
+    }
+}
@@ -1,4 +1,5 @@
 use async_trait::async_trait;
+use futures_util::stream::{FuturesOrdered, StreamExt};
 
 /// Trait for embedding providers — convert text to vectors
 #[async_trait]
@@ -154,6 +155,64 @@ impl EmbeddingProvider for OpenAiEmbedding {
     }
 }
 
+// ── Batched embedding ────────────────────────────────────────
+
+/// Texts sent per embedding request during bulk indexing.
+pub const DEFAULT_EMBED_BATCH_SIZE: usize = 64;
+
+/// Embedding requests allowed in flight at once during bulk indexing.
+pub const DEFAULT_EMBED_MAX_CONCURRENT_BATCHES: usize = 4;
+
+/// Embed many texts by splitting them into batches of `batch_size` and running
+/// up to `max_concurrent` batch requests at once.
+///
+/// Output order matches `texts`. Fails if any batch fails or a provider
+/// returns a different number of vectors than it was given texts.
+pub async fn embed_in_batches(
+    embedder: &dyn EmbeddingProvider,
+    texts: &[&str],
+    batch_size: usize,
+    max_concurrent: usize,
+) -> anyhow::Result<Vec<Vec<f32>>> {
+    if texts.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    // Drive the batches with an explicit queue rather than a stream combinator:
+    // a closure over `&[&str]` here is not general enough once this future is
+    // boxed by `async_trait` callers such as `Memory::reindex`.
+    let max_concurrent = max_concurrent.max(1);
+    let mut in_flight = FuturesOrdered::new();
+    let mut embeddings = Vec::with_capacity(texts.len());
+    for chunk in texts.chunks(batch_size.max(1)) {
+        if in_flight.len() >= max_concurrent {
+            if let Some(vectors) = in_flight.next().await {
+                embeddings.extend(vectors?);
+            }
+        }
+        in_flight.push_back(embed_batch(embedder, chunk));
+    }
+    while let Some(vectors) = in_flight.next().await {
+        embeddings.extend(vectors?);
+    }
+
+    Ok(embeddings)
+}
+
+async fn embed_batch(
+    embedder: &dyn EmbeddingProvider,
+    chunk: &[&str],
+) -> anyhow::Result<Vec<Vec<f32>>> {
+    let vectors = embedder.embed(chunk).await?;
+    anyhow::ensure!(
+        vectors.len() == chunk.len(),
+        "Embedding provider returned {} vectors for {} texts",
+        vectors.len(),
+        chunk.len()
+    );
+    Ok(vectors)
+}
+
 // ── Factory ──────────────────────────────────────────────────
 
 pub fn create_embedding_provider(
@@ -257,6 +316,52 @@ mod tests {
         assert!(result.is_empty());
     }
 
+    /// Records batch sizes and returns the text length as a 1-d vector.
+    struct RecordingEmbedding {
+        batches: parking_lot::Mutex<Vec<usize>>,
+    }
+
+    #[async_trait]
+    impl EmbeddingProvider for RecordingEmbedding {
+        fn name(&self) -> &str {
+            "recording"
+        }
+
+        fn dimensions(&self) -> usize {
+            1
+        }
+
+        async fn embed(&self, texts: &[&str]) -> anyhow::Result<Vec<Vec<f32>>> {
+            self.batches.lock().push(texts.len());
+            #[allow(clippy::cast_precision_loss)]
+            Ok(texts.iter().map(|t| vec![t.len() as f32]).collect())
+        }
+    }
+
+    #[tokio::test]
+    async fn embed_in_batches_splits_and_preserves_order() {
+        let p = RecordingEmbedding {
+            batches: parking_lot::Mutex::new(Vec::new()),
+        };
+        let texts = ["a", "bb", "ccc", "dddd", "eeeee"];
+        let vectors = embed_in_batches(&p, &texts, 2, 2).await.unwrap();
+
+        assert_eq!(
+            vectors,
+            vec![vec![1.0], vec![2.0], vec![3.0], vec![4.0], vec![5.0]]
+        );
+        let mut sizes = p.batches.lock().clone();
+        sizes.sort_unstable();
+        assert_eq!(sizes, vec![1, 2, 2]);
+    }
+
+    #[tokio::test]
+    async fn embed_in_batches_rejects_short_provider_response() {
+        let p = NoopEmbedding;
+        let err = embed_in_batches(&p, &["a", "b"], 8, 2).await.unwrap_err();
+        assert!(err.to_string().contains("returned 0 vectors for 2 texts"));
+    }
+
     #[tokio::test]
     async fn noop_embed_multiple_texts() {
         let p = NoopEmbedding;
@@ -0,0 +1,494 @@
+//! At-rest encryption wrapper for any memory backend.
+//!
+//! With `[memory] encrypt_at_rest = true`, entry content is encrypted with
+//! ChaCha20-Poly1305 (the same [`SecretStore`] format used for config
+//! secrets, `enc2:<hex>`) before it reaches the backend, and decrypted on
+//! read. Keys, categories, and timestamps stay in the clear so listing and
+//! filtering still work.
+//!
+//! Backends only ever see ciphertext, so their full-text and vector search
+//! cannot match it. `recall` therefore decrypts entries and ranks them by
+//! keyword overlap in process. That costs one decryption per entry, so recall
+//! only scans the newest [`MAX_RECALL_SCAN`] entries; older ones are still
+//! reachable with `get` and `list`. Entries written before encryption was
+//! enabled are read back as plaintext.
+//!
+//! The key is dedicated to memory: by default `state/memory.key` next to the
+//! config, or derived from a passphrase with `encryption_passphrase_env`.
+//! [`SecurityPolicy`](crate::security::SecurityPolicy) refuses the key file to
+//! every tool. Entries encrypted under the config secret key, the default
+//! before the dedicated key, still decrypt.
+
+use super::traits::{Memory, MemoryCategory, MemoryEntry};
+use crate::config::MemoryConfig;
+use crate::security::SecretStore;
+use async_trait::async_trait;
+use std::path::{Path, PathBuf};
+
+/// Entries `recall` decrypts and scores per call, newest first.
+pub const MAX_RECALL_SCAN: usize = 5_000;
+
+/// Default memory key file, in the policy state directory next to the config.
+pub const MEMORY_KEY_FILE: &str = "state/memory.key";
+
+/// PBKDF2-HMAC-SHA256 rounds for `encryption_passphrase_env`.
+const PASSPHRASE_ROUNDS: u32 = 600_000;
+
+/// Salt length for passphrase derivation, in bytes.
+const SALT_LEN: usize = 16;
+
+/// Memory decorator that encrypts entry content at rest.
+pub struct EncryptedMemory {
+    inner: Box<dyn Memory>,
+    secrets: SecretStore,
+    /// Key entries may have been encrypted under before the dedicated
+    /// memory key; read-only.
+    legacy: Option<SecretStore>,
+    recall_scan: usize,
+}
+
+impl EncryptedMemory {
+    pub fn new(inner: Box<dyn Memory>, secrets: SecretStore) -> Self {
+        Self {
+            inner,
+            secrets,
+            legacy: None,
+            recall_scan: MAX_RECALL_SCAN,
+        }
+    }
+
+    /// Also decrypt entries written under `legacy`. New entries always use
+    /// the primary key.
+    pub fn with_legacy_key(mut self, legacy: SecretStore) -> Self {
+        self.legacy = Some(legacy);
+        self
+    }
+
+    fn decrypt_entry(&self, mut entry: MemoryEntry) -> anyhow::Result<MemoryEntry> {
+        let decrypted = match self.secrets.decrypt(&entry.content) {
+            Err(error) => match &self.legacy {
+                Some(legacy) => legacy.decrypt(&entry.content).map_err(|_| error),
+                None => Err(error),
+            },
+            ok => ok,
+        };
+        entry.content = decrypted
+            .map_err(|e| anyhow::anyhow!("Failed to decrypt memory '{}': {e}", entry.key))?;
+        Ok(entry)
+    }
+}
+
+/// Directory holding the config file, taken as the workspace's parent.
+fn config_dir(workspace_dir: &Path) -> &Path {
+    workspace_dir.parent().unwrap_or(workspace_dir)
+}
+
+/// Key file for memory encryption: `encryption_key_file` when set, otherwise
+/// [`MEMORY_KEY_FILE`] next to the workspace (`~/.zeroclaw/state/memory.key`).
+/// With a passphrase, this path only locates the salt.
+pub fn resolve_key_path(config: &MemoryConfig, workspace_dir: &Path) -> PathBuf {
+    match config
+        .encryption_key_file
+        .as_deref()
+        .map(str::trim)
+        .filter(|p| !p.is_empty())
+    {
+        Some(path) => shellexpand::tilde(path).into_owned().into(),
+        None => config_dir(workspace_dir).join(MEMORY_KEY_FILE),
+    }
+}
+
+/// Derive a 32-byte key from `passphrase` with PBKDF2-HMAC-SHA256.
+fn derive_key(passphrase: &str, salt: &[u8], rounds: u32) -> Vec<u8> {
+    let mut key = vec![0u8; 32];
+    ring::pbkdf2::derive(
+        ring::pbkdf2::PBKDF2_HMAC_SHA256,
+        std::num::NonZeroU32::new(rounds.max(1)).expect("rounds is at least 1"),
+        salt,
+        passphrase.as_bytes(),
+        &mut key,
+    );
+    key
+}
+
+/// Read the hex salt at `path`, creating a random one if it is missing.
+fn load_or_create_salt(path: &Path) -> anyhow::Result<Vec<u8>> {
+    if let Ok(existing) = std::fs::read_to_string(path) {
+        return hex::decode(existing.trim())
+            .map_err(|e| anyhow::anyhow!("Memory key salt {} is corrupt: {e}", path.display()));
+    }
+    let mut salt = vec![0u8; SALT_LEN];
+    ring::rand::SecureRandom::fill(&ring::rand::SystemRandom::new(), &mut salt)
+        .map_err(|_| anyhow::anyhow!("Failed to generate a memory key salt"))?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, hex::encode(&salt))?;
+    Ok(salt)
+}
+
+/// The store holding the memory key: derived from the passphrase in
+/// `encryption_passphrase_env` when set, otherwise the key file.
+fn memory_secret_store(
+    config: &MemoryConfig,
+    workspace_dir: &Path,
+    rounds: u32,
+) -> anyhow::Result<SecretStore> {
+    let key_path = resolve_key_path(config, workspace_dir);
+    let Some(var) = config
+        .encryption_passphrase_env
+        .as_deref()
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+    else {
+        return Ok(SecretStore::with_key_path(key_path));
+    };
+    let passphrase = std::env::var(var).unwrap_or_default();
+    anyhow::ensure!(
+        !passphrase.is_empty(),
+        "memory.encryption_passphrase_env names {var}, which is not set"
+    );
+    let salt = load_or_create_salt(&key_path.with_extension("salt"))?;
+    SecretStore::with_key(derive_key(&passphrase, &salt, rounds))
+}
+
+/// Wrap `memory` in [`EncryptedMemory`] when `encrypt_at_rest` is enabled.
+pub fn wrap_if_enabled(
+    config: &MemoryConfig,
+    workspace_dir: &Path,
+    memory: Box<dyn Memory>,
+) -> anyhow::Result<Box<dyn Memory>> {
+    wrap_with_rounds(config, workspace_dir, memory, PASSPHRASE_ROUNDS)
+}
+
+fn wrap_with_rounds(
+    config: &MemoryConfig,
+    workspace_dir: &Path,
+    memory: Box<dyn Memory>,
+    rounds: u32,
+) -> anyhow::Result<Box<dyn Memory>> {
+    if !config.encrypt_at_rest {
+        return Ok(memory);
+    }
+    let mut encrypted =
+        EncryptedMemory::new(memory, memory_secret_store(config, workspace_dir, rounds)?);
+    // Before the dedicated key, memory was encrypted under the config
+    // secret key by default.
+    let legacy_key = config_dir(workspace_dir).join(".secret_key");
+    if config.encryption_key_file.is_none() && legacy_key.exists() {
+        encrypted = encrypted.with_legacy_key(SecretStore::with_key_path(legacy_key));
+    }
+    Ok(Box::new(encrypted))
+}
+
+/// Fraction of query terms found in `content` (0.0 when none match).
+fn keyword_score(terms: &[String], content: &str) -> f64 {
+    if terms.is_empty() {
+        return 0.0;
+    }
+    let haystack = content.to_lowercase();
+    let hits = terms
+        .iter()
+        .filter(|t| haystack.contains(t.as_str()))
+        .count();
+    #[allow(clippy::cast_precision_loss)]
+    let score = hits as f64 / terms.len() as f64;
+    score
+}
+
+#[async_trait]
+impl Memory for EncryptedMemory {
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    async fn store(
+        &self,
+        key: &str,
+        content: &str,
+        category: MemoryCategory,
+        session_id: Option<&str>,
+    ) -> anyhow::Result<()> {
+        let ciphertext = self.secrets.encrypt(content)?;
+        self.inner
+            .store(key, &ciphertext, category, session_id)
+            .await
+    }
+
+    async fn store_from(
+        &self,
+        key: &str,
+        content: &str,
+        category: MemoryCategory,
+        session_id: Option<&str>,
+        source: &str,
+    ) -> anyhow::Result<()> {
+        let ciphertext = self.secrets.encrypt(content)?;
+        self.inner
+            .store_from(key, &ciphertext, category, session_id, source)
+            .await
+    }
+
+    async fn recall(
+        &self,
+        query: &str,
+        limit: usize,
+        session_id: Option<&str>,
+    ) -> anyhow::Result<Vec<MemoryEntry>> {
+        let terms: Vec<String> = query.split_whitespace().map(str::to_lowercase).collect();
+        let mut candidates = self.inner.list(None, session_id).await?;
+        if candidates.len() > self.recall_scan {
+            tracing::warn!(
+                "Encrypted recall scanned the newest {} of {} entries; older entries are skipped",
+                self.recall_scan,
+                candidates.len()
+            );
+            candidates.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
+            candidates.truncate(self.recall_scan);
+        }
+        let mut scored = Vec::new();
+        for entry in candidates {
+            let entry = self.decrypt_entry(entry)?;
+            let score = keyword_score(&terms, &format!("{} {}", entry.key, entry.content));
+            if score > 0.0 {
+                scored.push(MemoryEntry {
+                    score: Some(score),
+                    ..entry
+                });
+            }
+        }
+        scored.sort_by(|a, b| {
+            b.score
+                .partial_cmp(&a.score)
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then_with(|| b.timestamp.cmp(&a.timestamp))
+        });
+        scored.truncate(limit);
+        Ok(scored)
+    }
+
+    async fn get(&self, key: &str) -> anyhow::Result<Option<MemoryEntry>> {
+        self.inner
+            .get(key)
+            .await?
+            .map(|entry| self.decrypt_entry(entry))
+            .transpose()
+    }
+
+    async fn list(
+        &self,
+        category: Option<&MemoryCategory>,
+        session_id: Option<&str>,
+    ) -> anyhow::Result<Vec<MemoryEntry>> {
+        self.inner
+            .list(category, session_id)
+            .await?
+            .into_iter()
+            .map(|entry| self.decrypt_entry(entry))
+            .collect()
+    }
+
+    async fn entries_between(
+        &self,
+        start: chrono::DateTime<chrono::Utc>,
+        end: chrono::DateTime<chrono::Utc>,
+        category: Option<&MemoryCategory>,
+    ) -> anyhow::Result<Vec<MemoryEntry>> {
+        self.inner
+            .entries_between(start, end, category)
+            .await?
+            .into_iter()
+            .map(|entry| self.decrypt_entry(entry))
+            .collect()
+    }
+
+    async fn forget(&self, key: &str) -> anyhow::Result<bool> {
+        self.inner.forget(key).await
+    }
+
+    async fn count(&self) -> anyhow::Result<usize> {
+        self.inner.count().await
+    }
+
+    async fn redact(&self, key: &str, replacement: &str) -> anyhow::Result<bool> {
+        let ciphertext = self.secrets.encrypt(replacement)?;
+        self.inner.redact(key, &ciphertext).await
+    }
+
+    async fn health_check(&self) -> bool {
+        self.inner.health_check().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::SqliteMemory;
+    use tempfile::TempDir;
+
+    fn encrypted(tmp: &TempDir) -> EncryptedMemory {
+        let inner = SqliteMemory::new(tmp.path()).unwrap();
+        let secrets = SecretStore::with_key_path(tmp.path().join("memory.key"));
+        EncryptedMemory::new(Box::new(inner), secrets)
+    }
+
+    #[tokio::test]
+    async fn content_is_ciphertext_in_backend_and_plaintext_through_wrapper() {
+        let tmp = TempDir::new().unwrap();
+        let mem = encrypted(&tmp);
+        mem.store("vpn", "VPN password is hunter2", MemoryCategory::Core, None)
+            .await
+            .unwrap();
+
+        let entry = mem.get("vpn").await.unwrap().unwrap();
+        assert_eq!(entry.content, "VPN password is hunter2");
+
+        let raw = SqliteMemory::new(tmp.path()).unwrap();
+        let stored = raw.get("vpn").await.unwrap().unwrap();
+        assert!(stored.content.starts_with("enc2:"));
+        assert!(!stored.content.contains("hunter2"));
+    }
+
+    #[tokio::test]
+    async fn recall_matches_decrypted_content() {
+        let tmp = TempDir::new().unwrap();
+        let mem = encrypted(&tmp);
+        mem.store("lang", "User prefers Rust", MemoryCategory::Core, None)
+            .await
+            .unwrap();
+        mem.store("tz", "Timezone is EST", MemoryCategory::Core, None)
+            .await
+            .unwrap();
+
+        let hits = mem.recall("rust", 5, None).await.unwrap();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].key, "lang");
+        assert_eq!(hits[0].content, "User prefers Rust");
+    }
+
+    #[tokio::test]
+    async fn recall_scans_only_the_newest_entries() {
+        let tmp = TempDir::new().unwrap();
+        let mut mem = encrypted(&tmp);
+        mem.recall_scan = 1;
+        mem.store("old", "deploy window friday", MemoryCategory::Core, None)
+            .await
+            .unwrap();
+        tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+        mem.store("new", "deploy window monday", MemoryCategory::Core, None)
+            .await
+            .unwrap();
+
+        let hits = mem.recall("deploy", 5, None).await.unwrap();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].key, "new");
+        assert!(mem.get("old").await.unwrap().is_some());
+    }
+
+    #[tokio::test]
+    async fn legacy_plaintext_entries_still_read() {
+        let tmp = TempDir::new().unwrap();
+        SqliteMemory::new(tmp.path())
+            .unwrap()
+            .store(
+                "old",
+                "written before encryption",
+                MemoryCategory::Core,
+                None,
+            )
+            .await
+            .unwrap();
+
+        let mem = encrypted(&tmp);
+        let listed = mem.list(None, None).await.unwrap();
+        assert_eq!(listed[0].content, "written before encryption");
+    }
+
+    #[tokio::test]
+    async fn passphrase_key_reads_back_and_rejects_other_passphrases() {
+        let tmp = TempDir::new().unwrap();
+        let workspace = tmp.path().join("workspace");
+        std::fs::create_dir_all(&workspace).unwrap();
+        let config = MemoryConfig {
+            encrypt_at_rest: true,
+            encryption_passphrase_env: Some("ZEROCLAW_TEST_MEMORY_PASSPHRASE".into()),
+            ..MemoryConfig::default()
+        };
+        let open = |passphrase: &str| {
+            std::env::set_var("ZEROCLAW_TEST_MEMORY_PASSPHRASE", passphrase);
+            let inner = SqliteMemory::new(&workspace).unwrap();
+            wrap_with_rounds(&config, &workspace, Box::new(inner), 1_000).unwrap()
+        };
+
+        open("correct horse")
+            .store("vpn", "VPN password is hunter2", MemoryCategory::Core, None)
+            .await
+            .unwrap();
+        assert!(tmp.path().join("state/memory.salt").exists());
+        assert!(!tmp.path().join("state/memory.key").exists());
+
+        let entry = open("correct horse").get("vpn").await.unwrap().unwrap();
+        assert_eq!(entry.content, "VPN password is hunter2");
+        assert!(open("battery staple").get("vpn").await.is_err());
+
+        std::env::remove_var("ZEROCLAW_TEST_MEMORY_PASSPHRASE");
+        let inner = SqliteMemory::new(&workspace).unwrap();
+        assert!(wrap_with_rounds(&config, &workspace, Box::new(inner), 1_000).is_err());
+    }
+
+    #[tokio::test]
+    async fn entries_under_the_config_secret_key_still_decrypt() {
+        let tmp = TempDir::new().unwrap();
+        let workspace = tmp.path().join("workspace");
+        std::fs::create_dir_all(&workspace).unwrap();
+        let legacy = SecretStore::with_key_path(tmp.path().join(".secret_key"));
+        SqliteMemory::new(&workspace)
+            .unwrap()
+            .store(
+                "old",
+                &legacy.encrypt("written under the old key").unwrap(),
+                MemoryCategory::Core,
+                None,
+            )
+            .await
+            .unwrap();
+
+        let config = MemoryConfig {
+            encrypt_at_rest: true,
+            ..MemoryConfig::default()
+        };
+        let inner = SqliteMemory::new(&workspace).unwrap();
+        let mem = wrap_if_enabled(&config, &workspace, Box::new(inner)).unwrap();
+        assert_eq!(
+            mem.get("old").await.unwrap().unwrap().content,
+            "written under the old key"
+        );
+
+        mem.store(
+            "new",
+            "written under the memory key",
+            MemoryCategory::Core,
+            None,
+        )
+        .await
+        .unwrap();
+        assert!(tmp.path().join("state/memory.key").exists());
+        let raw = SqliteMemory::new(&workspace).unwrap();
+        let stored = raw.get("new").await.unwrap().unwrap().content;
+        assert!(legacy.decrypt(&stored).is_err());
+    }
+
+    #[test]
+    fn key_path_defaults_to_dedicated_memory_key() {
+        let config = MemoryConfig::default();
+        let path = resolve_key_path(&config, Path::new("/home/u/.zeroclaw/workspace"));
+        assert_eq!(path, PathBuf::from("/home/u/.zeroclaw/state/memory.key"));
+
+        let config = MemoryConfig {
+            encryption_key_file: Some("/etc/zeroclaw/memory.key".into()),
+            ..MemoryConfig::default()
+        };
+        let path = resolve_key_path(&config, Path::new("/home/u/.zeroclaw/workspace"));
+        assert_eq!(path, PathBuf::from("/etc/zeroclaw/memory.key"));
+    }
+}
@@ -0,0 +1,477 @@
+//! Memory backup to a git repository (`[memory.git_sync]`).
+//!
+//! The daemon's `memory_sync` worker exports every entry through the
+//! [`Memory`] trait, so any backend can be synced, to `memory.json` in
+//! [`SYNC_DIR`] under the policy state directory. That directory is a git
+//! repository of its own: each sync that changed entries becomes one commit,
+//! giving operators a history of what the agent remembered. It lives outside
+//! the workspace so the agent cannot plant hooks or config in it, and git
+//! runs with hooks off and a scrubbed environment all the same.
+//!
+//! The policy is resolved again before every sync, so a `[security]
+//! policy_file` edit or the kill switch takes effect on the next one.
+//! Commits are pushed to the configured remote only when that policy allows
+//! it (`[autonomy] allow_memory_push`). The export is plaintext, so the
+//! worker refuses to run while `[memory] encrypt_at_rest` is on.
+
+use super::dump::{export_memory, parse_dump, DumpEntry};
+use super::traits::Memory;
+use crate::config::{Config, MemoryGitSyncConfig};
+use crate::security::{EnvFilter, LiveSecurityPolicy, SecurityPolicy};
+use anyhow::{bail, Context, Result};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::process::Command;
+
+/// Sync repository, relative to the policy state directory.
+pub const SYNC_DIR: &str = "memory-sync";
+/// Where the sync repository used to live, relative to the workspace.
+const LEGACY_SYNC_DIR: &str = "memory/sync";
+const DUMP_FILENAME: &str = "memory.json";
+/// How often the worker checks whether a sync is due.
+const WORKER_TICK_SECS: u64 = 60;
+
+/// What one [`MemoryGitSync::sync`] call did.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct SyncOutcome {
+    /// Entries added, removed, or changed since the last export.
+    pub changed: usize,
+    pub committed: bool,
+    pub pushed: bool,
+}
+
+pub struct MemoryGitSync {
+    dir: PathBuf,
+    config: MemoryGitSyncConfig,
+}
+
+impl MemoryGitSync {
+    pub fn new(state_dir: &Path, config: MemoryGitSyncConfig) -> Self {
+        Self {
+            dir: state_dir.join(SYNC_DIR),
+            config,
+        }
+    }
+
+    fn dump_path(&self) -> PathBuf {
+        self.dir.join(DUMP_FILENAME)
+    }
+
+    fn last_export(&self) -> Vec<DumpEntry> {
+        std::fs::read_to_string(self.dump_path())
+            .ok()
+            .and_then(|raw| parse_dump(&raw).ok())
+            .map(|dump| dump.entries)
+            .unwrap_or_default()
+    }
+
+    /// Entries that differ between the last export and `mem` now.
+    pub async fn pending_changes(&self, mem: &dyn Memory) -> Result<usize> {
+        let current = export_memory(mem).await?.entries;
+        Ok(changed_entries(&self.last_export(), &current))
+    }
+
+    /// Export `mem`, commit if any entry changed, and push when a remote is
+    /// configured and `security` allows pushing. Nothing is written while
+    /// the kill switch is engaged.
+    pub async fn sync(&self, mem: &dyn Memory, security: &SecurityPolicy) -> Result<SyncOutcome> {
+        if security.kill_switch_engaged() {
+            tracing::warn!("Memory sync skipped: kill switch engaged");
+            return Ok(SyncOutcome::default());
+        }
+        self.ensure_repo().await?;
+
+        let mut dump = export_memory(mem).await?;
+        dump.entries.sort_by(|a, b| a.key.cmp(&b.key));
+        let changed = changed_entries(&self.last_export(), &dump.entries);
+        let mut outcome = SyncOutcome {
+            changed,
+            ..SyncOutcome::default()
+        };
+
+        if changed > 0 {
+            tokio::fs::write(
+                self.dump_path(),
+                serde_json::to_string_pretty(&dump)? + "\n",
+            )
+            .await
+            .with_context(|| format!("Failed to write {}", self.dump_path().display()))?;
+            self.git(&["add", DUMP_FILENAME]).await?;
+            self.git(&[
+                "-c",
+                "user.name=ZeroClaw",
+                "-c",
+                "user.email=zeroclaw@localhost",
+                "-c",
+                "commit.gpgsign=false",
+                "commit",
+                "-q",
+                "-m",
+                &format!("Sync memory: {changed} entries changed"),
+            ])
+            .await?;
+            outcome.committed = true;
+        }
+
+        let Some(remote) = self
+            .config
+            .remote
+            .as_deref()
+            .filter(|r| !r.trim().is_empty())
+        else {
+            return Ok(outcome);
+        };
+        if security.kill_switch_engaged() {
+            tracing::warn!("Memory sync committed locally; push skipped: kill switch engaged");
+            return Ok(outcome);
+        }
+        if !security.can_push_memory() {
+            tracing::info!(
+                "Memory sync committed locally; push skipped ([autonomy] allow_memory_push = false)"
+            );
+            return Ok(outcome);
+        }
+
+        self.set_remote(remote).await?;
+        if !self.has_unpushed_commits().await {
+            return Ok(outcome);
+        }
+        self.git(&[
+            "push",
+            "-q",
+            "origin",
+            &format!("HEAD:refs/heads/{}", self.config.branch),
+        ])
+        .await?;
+        outcome.pushed = true;
+        Ok(outcome)
+    }
+
+    async fn ensure_repo(&self) -> Result<()> {
+        if self.dir.join(".git").exists() {
+            return Ok(());
+        }
+        tokio::fs::create_dir_all(&self.dir)
+            .await
+            .with_context(|| format!("Failed to create {}", self.dir.display()))?;
+        self.git(&["init", "-q"]).await?;
+        self.git(&[
+            "symbolic-ref",
+            "HEAD",
+            &format!("refs/heads/{}", self.config.branch),
+        ])
+        .await?;
+        Ok(())
+    }
+
+    /// Whether HEAD is ahead of the last push. Pushing updates
+    /// `origin/<branch>`, so commits left behind by a failed or denied push
+    /// are found again after a restart.
+    async fn has_unpushed_commits(&self) -> bool {
+        let Ok(head) = self.git(&["rev-parse", "-q", "--verify", "HEAD"]).await else {
+            return false;
+        };
+        let tracking = format!("refs/remotes/origin/{}", self.config.branch);
+        match self.git(&["rev-parse", "-q", "--verify", &tracking]).await {
+            Ok(pushed) => pushed != head,
+            Err(_) => true,
+        }
+    }
+
+    async fn set_remote(&self, remote: &str) -> Result<()> {
+        match self.git(&["remote", "get-url", "origin"]).await {
+            Ok(url) if url.trim() == remote => Ok(()),
+            Ok(_) => self
+                .git(&["remote", "set-url", "origin", remote])
+                .await
+                .map(drop),
+            Err(_) => self
+                .git(&["remote", "add", "origin", remote])
+                .await
+                .map(drop),
+        }
+    }
+
+    async fn git(&self, args: &[&str]) -> Result<String> {
+        let mut cmd = Command::new("git");
+        cmd.arg("-C")
+            .arg(&self.dir)
+            .args(["-c", "core.hooksPath=/dev/null"])
+            .args(args);
+        EnvFilter::baseline().apply(cmd.as_std_mut());
+        let output = cmd.output().await.context("Failed to run git")?;
+        if !output.status.success() {
+            bail!(
+                "git {} failed: {}",
+                args.iter()
+                    .find(|a| !a.starts_with('-') && !a.contains('='))
+                    .unwrap_or(&""),
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+    }
+}
+
+/// Keys added, removed, or whose category or content changed. Timestamps
+/// are ignored: storing identical content again is not a change.
+fn changed_entries(old: &[DumpEntry], new: &[DumpEntry]) -> usize {
+    let old: HashMap<&str, (&str, &str)> = old
+        .iter()
+        .map(|e| (e.key.as_str(), (e.category.as_str(), e.content.as_str())))
+        .collect();
+    let new: HashMap<&str, (&str, &str)> = new
+        .iter()
+        .map(|e| (e.key.as_str(), (e.category.as_str(), e.content.as_str())))
+        .collect();
+    let changed_or_added = new.iter().filter(|(k, v)| old.get(*k) != Some(v)).count();
+    let removed = old.keys().filter(|k| !new.contains_key(*k)).count();
+    changed_or_added + removed
+}
+
+/// The policy the worker syncs under: `[security] policy_file` when set,
+/// re-read whenever it changes, else `[autonomy]`.
+enum WorkerPolicy {
+    Static(Arc<SecurityPolicy>),
+    Live(LiveSecurityPolicy),
+}
+
+impl WorkerPolicy {
+    fn load(config: &Config) -> Result<Self> {
+        let security = SecurityPolicy::from_root_config(config);
+        let Some(policy_file) = config.security.policy_file.as_deref() else {
+            return Ok(Self::Static(Arc::new(security)));
+        };
+        let zeroclaw_dir = config.config_path.parent().unwrap_or(Path::new("."));
+        LiveSecurityPolicy::load(&zeroclaw_dir.join(policy_file), &security)
+            .map(Self::Live)
+            .context("security.policy_file")
+    }
+
+    fn current(&self) -> Arc<SecurityPolicy> {
+        match self {
+            Self::Static(security) => Arc::clone(security),
+            Self::Live(live) => {
+                if let Err(e) = live.reload_if_changed() {
+                    tracing::warn!("security.policy_file: {e:#}; keeping the previous version");
+                }
+                live.current()
+            }
+        }
+    }
+}
+
+/// Daemon worker: sync when `after_changes` entries changed or
+/// `interval_minutes` passed with changes pending.
+pub async fn run_worker(config: Config) -> Result<()> {
+    if config.memory.encrypt_at_rest {
+        bail!(
+            "memory.git_sync exports plaintext and is unavailable while \
+             memory.encrypt_at_rest is on"
+        );
+    }
+    let mem = super::create_memory_with_storage(
+        &config.memory,
+        Some(&config.storage.provider.config),
+        &config.workspace_dir,
+        config.api_key.as_deref(),
+    )?;
+    let policy = WorkerPolicy::load(&config)?;
+    let legacy_dir = config.workspace_dir.join(LEGACY_SYNC_DIR);
+    if legacy_dir.join(".git").exists() {
+        tracing::warn!(
+            "Ignoring the memory sync repository in the workspace ({}); it may hold \
+             agent-written hooks or config. Syncing to the state directory instead.",
+            legacy_dir.display()
+        );
+    }
+    let sync_config = config.memory.git_sync.clone();
+    let interval = Duration::from_secs(u64::from(sync_config.interval_minutes) * 60);
+    let after_changes = sync_config.after_changes as usize;
+    let sync = MemoryGitSync::new(&SecurityPolicy::state_dir_for(&config), sync_config);
+
+    let mut last_sync = Instant::now();
+    let mut ticker = tokio::time::interval(Duration::from_secs(WORKER_TICK_SECS));
+    loop {
+        ticker.tick().await;
+        let due_by_time = !interval.is_zero() && last_sync.elapsed() >= interval;
+        let due_by_changes =
+            after_changes > 0 && sync.pending_changes(mem.as_ref()).await? >= after_changes;
+        if !(due_by_time || due_by_changes) {
+            continue;
+        }
+
+        match sync.sync(mem.as_ref(), &policy.current()).await {
+            Ok(outcome) if outcome.committed || outcome.pushed => tracing::info!(
+                changed = outcome.changed,
+                pushed = outcome.pushed,
+                "Memory synced to git"
+            ),
+            Ok(_) => {}
+            Err(e) => tracing::warn!("Memory git sync failed: {e}"),
+        }
+        last_sync = Instant::now();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::{MemoryCategory, SqliteMemory};
+    use tempfile::TempDir;
+
+    fn git_log_count(dir: &Path) -> usize {
+        let out = std::process::Command::new("git")
+            .arg("-C")
+            .arg(dir)
+            .args(["rev-list", "--count", "refs/heads/main"])
+            .output()
+            .unwrap();
+        String::from_utf8_lossy(&out.stdout)
+            .trim()
+            .parse()
+            .unwrap_or(0)
+    }
+
+    fn sync_config(remote: Option<&Path>) -> MemoryGitSyncConfig {
+        MemoryGitSyncConfig {
+            enabled: true,
+            remote: remote.map(|p| p.display().to_string()),
+            ..MemoryGitSyncConfig::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn commits_only_when_entries_change() {
+        let tmp = TempDir::new().unwrap();
+        let mem = SqliteMemory::new(tmp.path()).unwrap();
+        mem.store("lang", "User prefers Rust", MemoryCategory::Core, None)
+            .await
+            .unwrap();
+        let sync = MemoryGitSync::new(tmp.path(), sync_config(None));
+        let security = SecurityPolicy::default();
+
+        assert_eq!(sync.pending_changes(&mem).await.unwrap(), 1);
+        let outcome = sync.sync(&mem, &security).await.unwrap();
+        assert_eq!(outcome.changed, 1);
+        assert!(outcome.committed);
+        assert!(!outcome.pushed);
+
+        let outcome = sync.sync(&mem, &security).await.unwrap();
+        assert!(!outcome.committed);
+        assert_eq!(git_log_count(&tmp.path().join(SYNC_DIR)), 1);
+
+        mem.store("lang", "User prefers Rust 2024", MemoryCategory::Core, None)
+            .await
+            .unwrap();
+        mem.forget("missing").await.unwrap();
+        assert_eq!(sync.pending_changes(&mem).await.unwrap(), 1);
+        sync.sync(&mem, &security).await.unwrap();
+        assert_eq!(git_log_count(&tmp.path().join(SYNC_DIR)), 2);
+
+        let exported =
+            std::fs::read_to_string(tmp.path().join(SYNC_DIR).join(DUMP_FILENAME)).unwrap();
+        assert!(exported.contains("User prefers Rust 2024"));
+    }
+
+    #[tokio::test]
+    async fn pushes_only_when_policy_allows() {
+        let tmp = TempDir::new().unwrap();
+        let remote = tmp.path().join("remote.git");
+        std::process::Command::new("git")
+            .args(["init", "-q", "--bare"])
+            .arg(&remote)
+            .status()
+            .unwrap();
+        let workspace = tmp.path().join("workspace");
+        std::fs::create_dir_all(&workspace).unwrap();
+        let mem = SqliteMemory::new(&workspace).unwrap();
+        mem.store("lang", "User prefers Rust", MemoryCategory::Core, None)
+            .await
+            .unwrap();
+
+        let sync = MemoryGitSync::new(&tmp.path().join("state"), sync_config(Some(&remote)));
+        let denied = SecurityPolicy::default();
+        let outcome = sync.sync(&mem, &denied).await.unwrap();
+        assert!(outcome.committed);
+        assert!(!outcome.pushed);
+        assert_eq!(git_log_count(&remote), 0);
+
+        // The commit made while pushing was denied goes out once allowed.
+        let allowed = SecurityPolicy {
+            allow_memory_push: true,
+            ..SecurityPolicy::default()
+        };
+        let outcome = sync.sync(&mem, &allowed).await.unwrap();
+        assert!(!outcome.committed);
+        assert!(outcome.pushed);
+        assert_eq!(git_log_count(&remote), 1);
+
+        let outcome = sync.sync(&mem, &allowed).await.unwrap();
+        assert!(!outcome.pushed);
+    }
+
+    #[tokio::test]
+    async fn kill_switch_stops_commits() {
+        let tmp = TempDir::new().unwrap();
+        let workspace = tmp.path().join("workspace");
+        std::fs::create_dir_all(workspace.join("ariadne")).unwrap();
+        std::fs::write(workspace.join("ariadne").join("KILL"), "").unwrap();
+        let mem = SqliteMemory::new(&workspace).unwrap();
+        mem.store("lang", "User prefers Rust", MemoryCategory::Core, None)
+            .await
+            .unwrap();
+        let security = SecurityPolicy {
+            workspace_dir: workspace,
+            ..SecurityPolicy::default()
+        };
+        assert!(security.kill_switch_engaged());
+
+        let sync = MemoryGitSync::new(&tmp.path().join("state"), sync_config(None));
+        let outcome = sync.sync(&mem, &security).await.unwrap();
+        assert!(!outcome.committed);
+        assert!(!tmp.path().join("state").join(SYNC_DIR).exists());
+    }
+
+    #[tokio::test]
+    async fn repository_hooks_never_run() {
+        let tmp = TempDir::new().unwrap();
+        let mem = SqliteMemory::new(tmp.path()).unwrap();
+        mem.store("lang", "User prefers Rust", MemoryCategory::Core, None)
+            .await
+            .unwrap();
+        let sync = MemoryGitSync::new(tmp.path(), sync_config(None));
+        sync.ensure_repo().await.unwrap();
+        let marker = tmp.path().join("hook-ran");
+        let hook = tmp.path().join(SYNC_DIR).join(".git/hooks/pre-commit");
+        std::fs::write(&hook, format!("#!/bin/sh\ntouch {}\n", marker.display())).unwrap();
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            std::fs::set_permissions(&hook, std::fs::Permissions::from_mode(0o755)).unwrap();
+        }
+
+        let outcome = sync.sync(&mem, &SecurityPolicy::default()).await.unwrap();
+        assert!(outcome.committed);
+        assert!(!marker.exists());
+    }
+
+    #[test]
+    fn can_push_memory_requires_flag_and_write_autonomy() {
+        use crate::security::AutonomyLevel;
+        let policy = SecurityPolicy {
+            allow_memory_push: true,
+            ..SecurityPolicy::default()
+        };
+        assert!(policy.can_push_memory());
+        assert!(!SecurityPolicy::default().can_push_memory());
+        let readonly = SecurityPolicy {
+            allow_memory_push: true,
+            autonomy: AutonomyLevel::ReadOnly,
+            ..SecurityPolicy::default()
+        };
+        assert!(!readonly.can_push_memory());
+    }
+}
@@ -132,6 +132,15 @@ impl Memory for SqliteQdrantHybridMemory {
         self.sqlite.list(category, session_id).await
     }
 
+    async fn entries_between(
+        &self,
+        start: chrono::DateTime<chrono::Utc>,
+        end: chrono::DateTime<chrono::Utc>,
+        category: Option<&MemoryCategory>,
+    ) -> Result<Vec<MemoryEntry>> {
+        self.sqlite.entries_between(start, end, category).await
+    }
+
     async fn forget(&self, key: &str) -> Result<bool> {
         let removed = self.sqlite.forget(key).await?;
         if let Err(err) = self.qdrant.forget(key).await {
@@ -10,6 +10,10 @@ use std::time::{Duration as StdDuration, SystemTime};
 const HYGIENE_INTERVAL_HOURS: i64 = 12;
 const STATE_FILE: &str = "memory_hygiene_state.json";
 
+/// Trailer line marking the last day an entry is kept, e.g.
+/// `Expires: 2025-03-01`. Written by `memory_store` (`expires_after_days`).
+pub(crate) const EXPIRES_PREFIX: &str = "Expires: ";
+
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 struct HygieneReport {
     archived_memory_files: u64,
@@ -17,6 +21,8 @@ struct HygieneReport {
     purged_memory_archives: u64,
     purged_session_archives: u64,
     pruned_conversation_rows: u64,
+    #[serde(default)]
+    pruned_expired_entries: u64,
 }
 
 impl HygieneReport {
@@ -26,6 +32,7 @@ impl HygieneReport {
             + self.purged_memory_archives
             + self.purged_session_archives
             + self.pruned_conversation_rows
+            + self.pruned_expired_entries
     }
 }
 
@@ -59,18 +66,20 @@ pub fn run_if_due(config: &MemoryConfig, workspace_dir: &Path) -> Result<()> {
             workspace_dir,
             config.conversation_retention_days,
         )?,
+        pruned_expired_entries: prune_expired_entries(workspace_dir, Local::now().date_naive())?,
     };
 
     write_state(workspace_dir, &report)?;
 
     if report.total_actions() > 0 {
         tracing::info!(
-            "memory hygiene complete: archived_memory={} archived_sessions={} purged_memory={} purged_sessions={} pruned_conversation_rows={}",
+            "memory hygiene complete: archived_memory={} archived_sessions={} purged_memory={} purged_sessions={} pruned_conversation_rows={} pruned_expired_entries={}",
             report.archived_memory_files,
             report.archived_session_files,
             report.purged_memory_archives,
             report.purged_session_archives,
             report.pruned_conversation_rows,
+            report.pruned_expired_entries,
         );
     }
 
@@ -318,6 +327,47 @@ fn prune_conversation_rows(workspace_dir: &Path, retention_days: u32) -> Result<
     Ok(u64::try_from(affected).unwrap_or(0))
 }
 
+/// Last day `content` should be kept, from its `Expires:` trailer line.
+pub(crate) fn entry_expiry(content: &str) -> Option<NaiveDate> {
+    content.lines().rev().find_map(|line| {
+        let date = line.trim().strip_prefix(EXPIRES_PREFIX)?;
+        NaiveDate::parse_from_str(date.trim(), "%Y-%m-%d").ok()
+    })
+}
+
+/// Delete SQLite memory entries whose `Expires:` date is before `today`.
+fn prune_expired_entries(workspace_dir: &Path, today: NaiveDate) -> Result<u64> {
+    let db_path = workspace_dir.join("memory").join("brain.db");
+    if !db_path.exists() {
+        return Ok(0);
+    }
+
+    let conn = Connection::open(db_path)?;
+    conn.execute_batch("PRAGMA journal_mode = WAL; PRAGMA synchronous = NORMAL;")?;
+    let pattern = format!("%{EXPIRES_PREFIX}%");
+    let expired: Vec<String> = {
+        let mut stmt = conn.prepare("SELECT id, content FROM memories WHERE content LIKE ?1")?;
+        let rows = stmt.query_map(params![pattern], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+        })?;
+        let mut expired = Vec::new();
+        for row in rows {
+            let (id, content) = row?;
+            if entry_expiry(&content).is_some_and(|last_day| last_day < today) {
+                expired.push(id);
+            }
+        }
+        expired
+    };
+
+    let mut removed = 0_u64;
+    for id in expired {
+        removed += u64::try_from(conn.execute("DELETE FROM memories WHERE id = ?1", params![id])?)
+            .unwrap_or(0);
+    }
+    Ok(removed)
+}
+
 fn memory_date_from_filename(filename: &str) -> Option<NaiveDate> {
     let stem = filename.strip_suffix(".md")?;
     let date_part = stem.split('_').next().unwrap_or(stem);
@@ -536,4 +586,53 @@ mod tests {
             "core memory should remain"
         );
     }
+
+    #[tokio::test]
+    async fn prunes_expired_entries_in_sqlite_backend() {
+        let tmp = TempDir::new().unwrap();
+        let workspace = tmp.path();
+
+        let mem = SqliteMemory::new(workspace).unwrap();
+        mem.store(
+            "reminder",
+            "Call the plumber\n\nExpires: 2020-01-01",
+            MemoryCategory::Core,
+            None,
+        )
+        .await
+        .unwrap();
+        mem.store(
+            "trip",
+            "Flight on Friday\n\nExpires: 2999-01-01",
+            MemoryCategory::Core,
+            None,
+        )
+        .await
+        .unwrap();
+        mem.store("core_keep", "durable", MemoryCategory::Core, None)
+            .await
+            .unwrap();
+        drop(mem);
+
+        let mut cfg = default_cfg();
+        cfg.archive_after_days = 0;
+        cfg.purge_after_days = 0;
+        cfg.conversation_retention_days = 0;
+
+        run_if_due(&cfg, workspace).unwrap();
+
+        let mem2 = SqliteMemory::new(workspace).unwrap();
+        assert!(mem2.get("reminder").await.unwrap().is_none());
+        assert!(mem2.get("trip").await.unwrap().is_some());
+        assert!(mem2.get("core_keep").await.unwrap().is_some());
+    }
+
+    #[test]
+    fn entry_expiry_reads_trailer_line() {
+        assert_eq!(
+            entry_expiry("note\n\nTags: #ops\nExpires: 2025-03-01"),
+            NaiveDate::from_ymd_opt(2025, 3, 1)
+        );
+        assert_eq!(entry_expiry("Expires soon, maybe"), None);
+    }
 }
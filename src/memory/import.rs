@@ -0,0 +1,433 @@
+//! Note import — ingest an existing Markdown/text knowledge base into memory.
+//!
+//! Each file is split into heading-scoped chunks (see [`super::chunker`]) and
+//! stored as one memory entry per chunk. Keys are derived from the file's path
+//! relative to the import root, so re-importing the same directory updates
+//! entries in place instead of duplicating them. Tags come from YAML
+//! frontmatter (`tags:`) and inline `#hashtags`, and are appended to the entry
+//! content so keyword and vector recall both see them.
+
+use super::chunker::chunk_markdown;
+use super::traits::{Memory, MemoryCategory};
+use anyhow::{bail, Context, Result};
+use std::path::{Path, PathBuf};
+
+/// Approximate token budget per imported entry.
+pub const DEFAULT_IMPORT_CHUNK_TOKENS: usize = 384;
+
+/// Key prefix shared by every imported entry.
+pub const IMPORT_KEY_PREFIX: &str = "import:";
+
+/// File extensions treated as notes.
+const NOTE_EXTENSIONS: &[&str] = &["md", "markdown", "txt"];
+
+/// One memory entry produced from a note.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportChunk {
+    pub key: String,
+    pub content: String,
+    pub tags: Vec<String>,
+}
+
+/// Counts reported after an import.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ImportReport {
+    pub files: usize,
+    pub entries: usize,
+    pub skipped_files: usize,
+}
+
+/// Collect note files under `root` (or `root` itself when it is a file).
+///
+/// Hidden files and directories (`.git`, `.obsidian`, ...) are skipped, and
+/// symlinked directories are not followed, so a link cycle cannot loop the
+/// walk. Results are sorted so imports are deterministic.
+pub fn collect_note_files(root: &Path) -> Result<Vec<PathBuf>> {
+    if root.is_file() {
+        return Ok(vec![root.to_path_buf()]);
+    }
+    if !root.is_dir() {
+        bail!("Import path {} does not exist", root.display());
+    }
+
+    let mut files = Vec::new();
+    let mut pending = vec![root.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        let entries =
+            std::fs::read_dir(&dir).with_context(|| format!("Failed to read {}", dir.display()))?;
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let hidden = entry.file_name().to_string_lossy().starts_with('.');
+            if hidden {
+                continue;
+            }
+            let Ok(file_type) = entry.file_type() else {
+                continue;
+            };
+            if file_type.is_dir() {
+                pending.push(path);
+            } else if (file_type.is_file() || (file_type.is_symlink() && path.is_file()))
+                && is_note_file(&path)
+            {
+                files.push(path);
+            }
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+fn is_note_file(path: &Path) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|ext| NOTE_EXTENSIONS.iter().any(|n| ext.eq_ignore_ascii_case(n)))
+}
+
+/// Split one note into memory entries keyed under `relative`.
+///
+/// A note that fits in a single chunk keeps the bare `import:<relative>` key;
+/// longer notes get `#1`, `#2`, ... suffixes in document order.
+pub fn split_note(relative: &str, text: &str, max_tokens: usize) -> Vec<ImportChunk> {
+    let (frontmatter_tags, body) = split_frontmatter(text);
+    let mut tags = frontmatter_tags;
+    for tag in inline_tags(body) {
+        if !tags.contains(&tag) {
+            tags.push(tag);
+        }
+    }
+
+    let chunks = chunk_markdown(body, max_tokens.max(1));
+    let single = chunks.len() == 1;
+    let tag_line = (!tags.is_empty()).then(|| {
+        tags.iter()
+            .map(|t| format!("#{t}"))
+            .collect::<Vec<_>>()
+            .join(" ")
+    });
+
+    chunks
+        .into_iter()
+        .map(|chunk| {
+            let key = if single {
+                format!("{IMPORT_KEY_PREFIX}{relative}")
+            } else {
+                format!("{IMPORT_KEY_PREFIX}{relative}#{}", chunk.index + 1)
+            };
+            let mut content = chunk.content;
+            content.push_str("\n\nSource: ");
+            content.push_str(relative);
+            if let Some(line) = &tag_line {
+                content.push_str("\nTags: ");
+                content.push_str(line);
+            }
+            ImportChunk {
+                key,
+                content,
+                tags: tags.clone(),
+            }
+        })
+        .collect()
+}
+
+/// Separate a leading `---` YAML frontmatter block, returning its tags and
+/// the remaining body. Only the `tags` field is interpreted.
+pub(super) fn split_frontmatter(text: &str) -> (Vec<String>, &str) {
+    let Some(rest) = text
+        .strip_prefix("---\n")
+        .or_else(|| text.strip_prefix("---\r\n"))
+    else {
+        return (Vec::new(), text);
+    };
+    let Some(end) = rest.find("\n---") else {
+        return (Vec::new(), text);
+    };
+    let yaml = &rest[..end];
+    let body = rest[end + 4..].trim_start_matches(['\r', '\n']);
+
+    let mut tags = Vec::new();
+    let mut in_tag_list = false;
+    for line in yaml.lines() {
+        let trimmed = line.trim();
+        if in_tag_list {
+            if let Some(item) = trimmed.strip_prefix("- ") {
+                push_tag(&mut tags, item);
+                continue;
+            }
+            in_tag_list = false;
+        }
+        let Some(value) = trimmed.strip_prefix("tags:") else {
+            continue;
+        };
+        let value = value.trim();
+        if value.is_empty() {
+            in_tag_list = true;
+        } else {
+            let value = value.trim_start_matches('[').trim_end_matches(']');
+            for item in value.split(',') {
+                push_tag(&mut tags, item);
+            }
+        }
+    }
+    (tags, body)
+}
+
+fn push_tag(tags: &mut Vec<String>, raw: &str) {
+    let tag = raw.trim().trim_matches(['"', '\'']).trim_start_matches('#');
+    if !tag.is_empty() && !tags.iter().any(|t| t == tag) {
+        tags.push(tag.to_string());
+    }
+}
+
+/// Inline `#tag` tokens outside code fences. Headings (`# Title`) and
+/// numeric references (`#42`) are not tags.
+pub(crate) fn inline_tags(body: &str) -> Vec<String> {
+    let mut tags = Vec::new();
+    let mut in_fence = false;
+    for line in body.lines() {
+        if line.trim_start().starts_with("```") {
+            in_fence = !in_fence;
+            continue;
+        }
+        if in_fence {
+            continue;
+        }
+        for token in line.split_whitespace() {
+            let Some(tag) = token.strip_prefix('#') else {
+                continue;
+            };
+            let tag = tag.trim_end_matches(|c: char| {
+                !(c.is_alphanumeric() || c == '_' || c == '-' || c == '/')
+            });
+            let valid = tag.chars().next().is_some_and(char::is_alphabetic)
+                && tag
+                    .chars()
+                    .all(|c| c.is_alphanumeric() || c == '_' || c == '-' || c == '/');
+            if valid {
+                push_tag(&mut tags, tag);
+            }
+        }
+    }
+    tags
+}
+
+/// Import every note under `root` into `mem` with the given category.
+///
+/// Entries left over from an earlier import of the same note (a chunk that
+/// no longer exists after the note shrank) are removed. Unreadable
+/// (non-UTF-8) files are skipped and counted in
+/// [`ImportReport::skipped_files`]. Embeddings are computed by the backend's
+/// own `store`, so they are only produced when an embedding provider is
+/// configured.
+pub async fn import_notes(
+    mem: &dyn Memory,
+    root: &Path,
+    category: &MemoryCategory,
+    max_tokens: usize,
+) -> Result<ImportReport> {
+    let mut report = ImportReport::default();
+    let plan = plan_import(root, max_tokens)?;
+    let imported_keys: Vec<String> = mem
+        .list(None, None)
+        .await?
+        .into_iter()
+        .map(|entry| entry.key)
+        .filter(|key| key.starts_with(IMPORT_KEY_PREFIX))
+        .collect();
+    for (relative, chunks) in plan {
+        let Some(chunks) = chunks else {
+            tracing::warn!("memory import: skipping unreadable file {relative}");
+            report.skipped_files += 1;
+            continue;
+        };
+        for stale in imported_keys.iter().filter(|key| {
+            is_chunk_key_of(key, &relative) && !chunks.iter().any(|chunk| &chunk.key == *key)
+        }) {
+            mem.forget(stale)
+                .await
+                .with_context(|| format!("Failed to remove stale entry {stale}"))?;
+        }
+        for chunk in &chunks {
+            mem.store(&chunk.key, &chunk.content, category.clone(), None)
+                .await
+                .with_context(|| format!("Failed to store {}", chunk.key))?;
+        }
+        report.files += 1;
+        report.entries += chunks.len();
+    }
+    Ok(report)
+}
+
+/// Whether `key` is `import:<relative>` or one of its `#N` chunk keys.
+fn is_chunk_key_of(key: &str, relative: &str) -> bool {
+    let Some(rest) = key
+        .strip_prefix(IMPORT_KEY_PREFIX)
+        .and_then(|rest| rest.strip_prefix(relative))
+    else {
+        return false;
+    };
+    rest.is_empty()
+        || rest
+            .strip_prefix('#')
+            .is_some_and(|n| !n.is_empty() && n.bytes().all(|b| b.is_ascii_digit()))
+}
+
+/// Read and split every note under `root` without storing anything.
+///
+/// Returns `(relative path, chunks)` pairs; `None` marks a file that could
+/// not be read as UTF-8 text.
+pub fn plan_import(
+    root: &Path,
+    max_tokens: usize,
+) -> Result<Vec<(String, Option<Vec<ImportChunk>>)>> {
+    let base = if root.is_file() {
+        root.parent().unwrap_or(root)
+    } else {
+        root
+    };
+    let mut plan = Vec::new();
+    for path in collect_note_files(root)? {
+        let relative = path
+            .strip_prefix(base)
+            .unwrap_or(&path)
+            .to_string_lossy()
+            .replace('\\', "/");
+        let chunks = std::fs::read_to_string(&path)
+            .ok()
+            .map(|text| split_note(&relative, &text, max_tokens));
+        plan.push((relative, chunks));
+    }
+    Ok(plan)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::SqliteMemory;
+    use tempfile::TempDir;
+
+    #[test]
+    fn split_note_reads_frontmatter_and_inline_tags() {
+        let text = "---\ntitle: Deploy\ntags: [ops, \"release\"]\n---\n# Deploy\n\nShip on #friday only, see #ops.\n";
+        let chunks = split_note("runbooks/deploy.md", text, 512);
+        assert_eq!(chunks.len(), 1);
+        assert_eq!(chunks[0].key, "import:runbooks/deploy.md");
+        assert_eq!(chunks[0].tags, vec!["ops", "release", "friday"]);
+        assert!(chunks[0].content.starts_with("# Deploy"));
+        assert!(!chunks[0].content.contains("title: Deploy"));
+        assert!(chunks[0]
+            .content
+            .ends_with("Source: runbooks/deploy.md\nTags: #ops #release #friday"));
+    }
+
+    #[test]
+    fn split_note_handles_block_tag_lists_and_ignores_code() {
+        let text = "---\ntags:\n  - home\n  - '#lab'\n---\nBody text.\n\n```sh\necho #notatag\n```\nIssue #42 is open.\n";
+        let chunks = split_note("lab.md", text, 512);
+        assert_eq!(chunks[0].tags, vec!["home", "lab"]);
+    }
+
+    #[test]
+    fn split_note_numbers_multi_chunk_keys() {
+        let text = "# One\n\nAlpha section.\n\n# Two\n\nBeta section.\n";
+        let chunks = split_note("notes.txt", text, 4);
+        assert!(chunks.len() >= 2);
+        assert_eq!(chunks[0].key, "import:notes.txt#1");
+        assert_eq!(chunks[1].key, "import:notes.txt#2");
+        assert!(chunks.iter().all(|c| c.tags.is_empty()));
+    }
+
+    #[test]
+    fn collect_note_files_skips_hidden_and_non_notes() {
+        let tmp = TempDir::new().unwrap();
+        std::fs::create_dir_all(tmp.path().join(".obsidian")).unwrap();
+        std::fs::create_dir_all(tmp.path().join("sub")).unwrap();
+        std::fs::write(tmp.path().join(".obsidian/app.md"), "x").unwrap();
+        std::fs::write(tmp.path().join("a.md"), "x").unwrap();
+        std::fs::write(tmp.path().join("sub/b.TXT"), "x").unwrap();
+        std::fs::write(tmp.path().join("image.png"), "x").unwrap();
+
+        let files = collect_note_files(tmp.path()).unwrap();
+        let names: Vec<_> = files
+            .iter()
+            .map(|p| p.strip_prefix(tmp.path()).unwrap().to_path_buf())
+            .collect();
+        assert_eq!(
+            names,
+            vec![PathBuf::from("a.md"), PathBuf::from("sub/b.TXT")]
+        );
+        assert!(collect_note_files(&tmp.path().join("missing")).is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn collect_note_files_does_not_follow_directory_symlinks() {
+        let tmp = TempDir::new().unwrap();
+        std::fs::create_dir_all(tmp.path().join("sub")).unwrap();
+        std::fs::write(tmp.path().join("sub/a.md"), "x").unwrap();
+        std::os::unix::fs::symlink(tmp.path(), tmp.path().join("sub/loop")).unwrap();
+
+        let files = collect_note_files(tmp.path()).unwrap();
+        assert_eq!(files, vec![tmp.path().join("sub/a.md")]);
+    }
+
+    #[tokio::test]
+    async fn import_notes_is_idempotent() {
+        let notes = TempDir::new().unwrap();
+        std::fs::write(
+            notes.path().join("vpn.md"),
+            "# VPN\n\nUse WireGuard. #network\n",
+        )
+        .unwrap();
+        std::fs::write(notes.path().join("binary.md"), [0xff, 0xfe, 0x00]).unwrap();
+
+        let workspace = TempDir::new().unwrap();
+        let mem = SqliteMemory::new(workspace.path()).unwrap();
+        let category = MemoryCategory::Custom("notes".into());
+
+        let report = import_notes(&mem, notes.path(), &category, 512)
+            .await
+            .unwrap();
+        assert_eq!(report.files, 1);
+        assert_eq!(report.entries, 1);
+        assert_eq!(report.skipped_files, 1);
+
+        import_notes(&mem, notes.path(), &category, 512)
+            .await
+            .unwrap();
+        assert_eq!(mem.count().await.unwrap(), 1);
+        let entry = mem.get("import:vpn.md").await.unwrap().unwrap();
+        assert_eq!(entry.category, category);
+        assert!(entry.content.contains("Tags: #network"));
+    }
+
+    #[tokio::test]
+    async fn reimport_removes_chunks_a_shrunk_note_no_longer_has() {
+        let notes = TempDir::new().unwrap();
+        let note = notes.path().join("notes.md");
+        std::fs::write(&note, "# One\n\nAlpha section.\n\n# Two\n\nBeta section.\n").unwrap();
+        std::fs::write(notes.path().join("notes.md.md"), "Neighbour.\n").unwrap();
+
+        let workspace = TempDir::new().unwrap();
+        let mem = SqliteMemory::new(workspace.path()).unwrap();
+        let category = MemoryCategory::Core;
+        import_notes(&mem, notes.path(), &category, 4)
+            .await
+            .unwrap();
+        assert!(mem.get("import:notes.md#2").await.unwrap().is_some());
+
+        std::fs::write(&note, "Alpha.\n").unwrap();
+        import_notes(&mem, notes.path(), &category, 4)
+            .await
+            .unwrap();
+        let keys: Vec<String> = mem
+            .list(None, None)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|e| e.key)
+            .collect();
+        assert_eq!(keys.len(), 2, "{keys:?}");
+        assert!(keys.contains(&"import:notes.md".to_string()));
+        assert!(keys.contains(&"import:notes.md.md".to_string()));
+    }
+}
@@ -378,6 +378,15 @@ impl Memory for LucidMemory {
         self.local.list(category, session_id).await
     }
 
+    async fn entries_between(
+        &self,
+        start: chrono::DateTime<chrono::Utc>,
+        end: chrono::DateTime<chrono::Utc>,
+        category: Option<&MemoryCategory>,
+    ) -> anyhow::Result<Vec<MemoryEntry>> {
+        self.local.entries_between(start, end, category).await
+    }
+
     async fn forget(&self, key: &str) -> anyhow::Result<bool> {
         self.local.forget(key).await
     }
@@ -0,0 +1,396 @@
+//! Legacy notes migration — convert free-form `notes.md` files into entries.
+//!
+//! Older setups kept memory as one hand-edited Markdown file: notes separated
+//! by horizontal rules, with some written as `- **key**: value` lines. Unlike
+//! [`super::import`], which chunks a knowledge base by heading, migration
+//! treats each separated block (or legacy line) as one note and reports every
+//! block it could not turn into an entry, so nothing is dropped silently.
+//!
+//! Separators are parsed leniently: `---`, `***`, `___`, longer runs, spaced
+//! runs (`- - -`), and runs with trailing text glued on (`---Next note`) all
+//! split notes. A run of `=` under a line of text is a Setext heading
+//! underline, not a separator.
+
+use super::import::{collect_note_files, inline_tags, split_frontmatter};
+use super::traits::{Memory, MemoryCategory};
+use anyhow::{Context, Result};
+use std::path::Path;
+
+/// Key prefix shared by every migrated entry.
+pub const MIGRATE_KEY_PREFIX: &str = "notes:";
+
+/// One memory entry produced from a legacy note.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MigratedNote {
+    pub key: String,
+    pub content: String,
+    /// 1-based line where the note starts in its file.
+    pub line: usize,
+}
+
+/// A block that was not migrated, with the reason.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SkippedNote {
+    /// 1-based line where the block starts in its file.
+    pub line: usize,
+    pub reason: String,
+}
+
+/// Notes parsed from one file.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MigrationPlan {
+    /// File path relative to the migration root.
+    pub file: String,
+    pub notes: Vec<MigratedNote>,
+    pub skipped: Vec<SkippedNote>,
+}
+
+/// A block of lines between separators.
+struct Block {
+    /// 1-based line of `lines[0]`.
+    line: usize,
+    lines: Vec<String>,
+}
+
+/// If `line` is a separator, the text glued after it (usually empty).
+fn separator(line: &str) -> Option<&str> {
+    let trimmed = line.trim();
+    let rule = trimmed.chars().next().filter(|c| "-*_=".contains(*c))?;
+    let mut count = 0;
+    let mut end = 0;
+    for (idx, c) in trimmed.char_indices() {
+        if c == rule {
+            count += 1;
+        } else if c != ' ' {
+            break;
+        }
+        end = idx + c.len_utf8();
+    }
+    if count < 3 {
+        return None;
+    }
+    let rest = trimmed[end..].trim();
+    // `***bold***` and `___init___` are emphasis, not rules with text.
+    if !rest.is_empty() && (rule == '*' || rule == '_') {
+        return None;
+    }
+    Some(rest)
+}
+
+/// `- **key**: value`, the line format of the old Markdown backend.
+fn legacy_line(line: &str) -> Option<(&str, &str)> {
+    let rest = line.trim().strip_prefix("- **")?;
+    let (key, value) = rest.split_once("**:")?;
+    let key = key.trim();
+    (!key.is_empty()).then_some((key, value.trim()))
+}
+
+fn split_blocks(body: &str, first_line: usize) -> Vec<Block> {
+    let mut blocks = vec![Block {
+        line: first_line,
+        lines: Vec::new(),
+    }];
+    let mut previous_blank = true;
+    for (offset, line) in body.lines().enumerate() {
+        let number = first_line + offset;
+        let current = blocks.last_mut().expect("blocks is never empty");
+        match separator(line) {
+            Some(_) if line.trim_start().starts_with('=') && !previous_blank => {
+                current.lines.push(line.to_string());
+            }
+            Some("") => blocks.push(Block {
+                line: number + 1,
+                lines: Vec::new(),
+            }),
+            Some(rest) => blocks.push(Block {
+                line: number,
+                lines: vec![rest.to_string()],
+            }),
+            None => current.lines.push(line.to_string()),
+        }
+        previous_blank = line.trim().is_empty();
+    }
+    blocks
+}
+
+/// Split a block on legacy `- **key**: value` lines. Returns
+/// `(legacy key, start line, text)`; text before the first legacy line has
+/// no key.
+fn split_legacy(block: &Block) -> Vec<(Option<String>, usize, String)> {
+    let mut parts: Vec<(Option<String>, usize, Vec<&str>)> = vec![(None, block.line, Vec::new())];
+    for (offset, line) in block.lines.iter().enumerate() {
+        if let Some((key, value)) = legacy_line(line) {
+            parts.push((Some(key.to_string()), block.line + offset, vec![value]));
+        } else {
+            parts.last_mut().expect("parts is never empty").2.push(line);
+        }
+    }
+    parts
+        .into_iter()
+        .map(|(key, line, lines)| {
+            let leading_blank = if key.is_some() {
+                0
+            } else {
+                lines.iter().take_while(|l| l.trim().is_empty()).count()
+            };
+            (
+                key,
+                line + leading_blank,
+                lines.join("\n").trim().to_string(),
+            )
+        })
+        .filter(|(key, _, text)| key.is_some() || !text.is_empty())
+        .collect()
+}
+
+/// Leading YAML frontmatter tags and the body after it. A file that merely
+/// opens with a separator keeps its first note.
+fn frontmatter(text: &str) -> (Vec<String>, &str) {
+    let (tags, body) = split_frontmatter(text);
+    let yaml = &text[..text.len() - body.len()];
+    let is_yaml = yaml.lines().all(|line| {
+        let line = line.trim();
+        line.is_empty()
+            || line == "---"
+            || line.starts_with("- ")
+            || line.split_once(':').is_some_and(|(field, _)| {
+                !field.is_empty()
+                    && field
+                        .chars()
+                        .all(|c| c.is_alphanumeric() || c == '_' || c == '-')
+            })
+    });
+    if is_yaml {
+        (tags, body)
+    } else {
+        (Vec::new(), text)
+    }
+}
+
+/// Why `text` cannot be stored as a note, if it cannot.
+fn skip_reason(text: &str) -> Option<&'static str> {
+    if text.is_empty() {
+        return Some("empty note");
+    }
+    if text
+        .chars()
+        .any(|c| c.is_control() && !matches!(c, '\n' | '\t'))
+    {
+        return Some("contains control characters (binary data?)");
+    }
+    if text
+        .lines()
+        .all(|l| l.trim().is_empty() || l.trim_start().starts_with('#'))
+        && inline_tags(text).is_empty()
+    {
+        return Some("heading without a body");
+    }
+    None
+}
+
+/// Parse one legacy notes file. `relative` names the file in keys and in
+/// the `Source:` line of each entry.
+pub fn parse_legacy_notes(relative: &str, text: &str) -> MigrationPlan {
+    let text = text.replace("\r\n", "\n");
+    let (file_tags, body) = frontmatter(&text);
+    let first_line = text[..text.len() - body.len()].lines().count() + 1;
+
+    let mut plan = MigrationPlan {
+        file: relative.to_string(),
+        ..MigrationPlan::default()
+    };
+    let blocks = split_blocks(body, first_line);
+    let last = blocks.len() - 1;
+    let mut index = 0;
+    for (position, block) in blocks.iter().enumerate() {
+        let parts = split_legacy(block);
+        if parts.is_empty() {
+            // Blank space before the first or after the last separator is
+            // layout; an empty block between two separators is a lost note.
+            if position != 0 && position != last {
+                plan.skipped.push(SkippedNote {
+                    line: block.line,
+                    reason: "empty note between separators".into(),
+                });
+            }
+            continue;
+        }
+        for (legacy_key, line, text) in parts {
+            if let Some(reason) = skip_reason(&text) {
+                plan.skipped.push(SkippedNote {
+                    line,
+                    reason: match &legacy_key {
+                        Some(key) => format!("{reason} (key '{key}')"),
+                        None => reason.to_string(),
+                    },
+                });
+                continue;
+            }
+            index += 1;
+            let key = match legacy_key {
+                Some(key) => format!("{MIGRATE_KEY_PREFIX}{key}"),
+                None => format!("{MIGRATE_KEY_PREFIX}{relative}#{index}"),
+            };
+            let mut tags = file_tags.clone();
+            for tag in inline_tags(&text) {
+                if !tags.contains(&tag) {
+                    tags.push(tag);
+                }
+            }
+            let mut content = format!("{text}\n\nSource: {relative}");
+            if !tags.is_empty() {
+                let tag_line: Vec<String> = tags.iter().map(|t| format!("#{t}")).collect();
+                content.push_str("\nTags: ");
+                content.push_str(&tag_line.join(" "));
+            }
+            plan.notes.push(MigratedNote { key, content, line });
+        }
+    }
+    plan
+}
+
+/// Read and parse every notes file under `root` without storing anything.
+///
+/// Files that are not UTF-8 text come back as a plan with no notes and one
+/// skipped entry at line 0.
+pub fn plan_migration(root: &Path) -> Result<Vec<MigrationPlan>> {
+    let base = if root.is_file() {
+        root.parent().unwrap_or(root)
+    } else {
+        root
+    };
+    let mut plans = Vec::new();
+    for path in collect_note_files(root)? {
+        let relative = path
+            .strip_prefix(base)
+            .unwrap_or(&path)
+            .to_string_lossy()
+            .replace('\\', "/");
+        match std::fs::read_to_string(&path) {
+            Ok(text) => plans.push(parse_legacy_notes(&relative, &text)),
+            Err(e) => plans.push(MigrationPlan {
+                file: relative,
+                notes: Vec::new(),
+                skipped: vec![SkippedNote {
+                    line: 0,
+                    reason: format!("unreadable file: {e}"),
+                }],
+            }),
+        }
+    }
+    Ok(plans)
+}
+
+/// Store every planned note into `mem`. Returns the number stored.
+pub async fn migrate_notes(
+    mem: &dyn Memory,
+    plans: &[MigrationPlan],
+    category: &MemoryCategory,
+) -> Result<usize> {
+    let mut stored = 0;
+    for note in plans.iter().flat_map(|plan| &plan.notes) {
+        mem.store(&note.key, &note.content, category.clone(), None)
+            .await
+            .with_context(|| format!("Failed to store {}", note.key))?;
+        stored += 1;
+    }
+    Ok(stored)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::SqliteMemory;
+    use tempfile::TempDir;
+
+    #[test]
+    fn splits_on_lenient_separators() {
+        let text = "First note #ops\n---\nSecond note\n\n- - -\nThird note\n*****   \nFourth\n---Fifth glued\n";
+        let plan = parse_legacy_notes("notes.md", text);
+        let bodies: Vec<&str> = plan
+            .notes
+            .iter()
+            .map(|n| n.content.split("\n\nSource:").next().unwrap())
+            .collect();
+        assert_eq!(
+            bodies,
+            vec![
+                "First note #ops",
+                "Second note",
+                "Third note",
+                "Fourth",
+                "Fifth glued"
+            ]
+        );
+        assert_eq!(plan.notes[0].key, "notes:notes.md#1");
+        assert!(plan.notes[0]
+            .content
+            .ends_with("Source: notes.md\nTags: #ops"));
+        assert!(plan.skipped.is_empty());
+    }
+
+    #[test]
+    fn keeps_setext_headings_and_emphasis() {
+        let text = "Title\n=====\nBody text\n---\n***bold*** claim\n";
+        let plan = parse_legacy_notes("notes.md", text);
+        assert_eq!(plan.notes.len(), 2);
+        assert!(plan.notes[0].content.starts_with("Title\n=====\nBody text"));
+        assert!(plan.notes[1].content.starts_with("***bold*** claim"));
+
+        // A leading separator is not frontmatter.
+        let plan = parse_legacy_notes("notes.md", "---\nNote one\n---\nNote two\n");
+        assert_eq!(plan.notes.len(), 2);
+        assert_eq!(plan.notes[0].line, 2);
+    }
+
+    #[test]
+    fn legacy_lines_become_keyed_entries() {
+        let text = "# Long-Term Memory\n\n- **lang**: Prefers Rust\n- **deploy**: Blue/green\n  on Fridays\n- **empty**:\n";
+        let plan = parse_legacy_notes("notes.md", text);
+        let keys: Vec<&str> = plan.notes.iter().map(|n| n.key.as_str()).collect();
+        assert_eq!(keys, vec!["notes:lang", "notes:deploy"]);
+        assert!(plan.notes[1]
+            .content
+            .starts_with("Blue/green\n  on Fridays"));
+        let reasons: Vec<&str> = plan.skipped.iter().map(|s| s.reason.as_str()).collect();
+        assert_eq!(
+            reasons,
+            vec!["heading without a body", "empty note (key 'empty')"]
+        );
+        assert_eq!(plan.skipped[1].line, 6);
+    }
+
+    #[test]
+    fn reports_empty_and_binary_blocks_with_line_numbers() {
+        let text = "---\ntags: [home]\n---\nOne\n---\n\n---\nbad \u{0}\n---\nTwo\n---\n";
+        let plan = parse_legacy_notes("notes.md", text);
+        assert_eq!(plan.notes.len(), 2);
+        assert!(plan.notes[0].content.ends_with("Tags: #home"));
+        assert_eq!(plan.skipped.len(), 2);
+        assert_eq!(plan.skipped[0].line, 6);
+        assert!(plan.skipped[0].reason.contains("empty"));
+        assert_eq!(plan.skipped[1].line, 8);
+        assert!(plan.skipped[1].reason.contains("control characters"));
+    }
+
+    #[tokio::test]
+    async fn migrate_is_idempotent() {
+        let notes = TempDir::new().unwrap();
+        std::fs::write(
+            notes.path().join("notes.md"),
+            "Use WireGuard\n---\n- **lang**: Rust\n",
+        )
+        .unwrap();
+        let tmp = TempDir::new().unwrap();
+        let mem = SqliteMemory::new(tmp.path()).unwrap();
+        let category = MemoryCategory::Custom("notes".into());
+
+        let plans = plan_migration(&notes.path().join("notes.md")).unwrap();
+        assert_eq!(migrate_notes(&mem, &plans, &category).await.unwrap(), 2);
+        assert_eq!(migrate_notes(&mem, &plans, &category).await.unwrap(), 2);
+        assert_eq!(mem.count().await.unwrap(), 2);
+        let entry = mem.get("notes:lang").await.unwrap().unwrap();
+        assert_eq!(entry.content, "Rust\n\nSource: notes.md");
+        assert_eq!(entry.category, category);
+    }
+}
@@ -3,11 +3,16 @@ pub mod chunker;
 pub mod cli;
 pub mod cortex;
 pub mod decay;
+pub mod dump;
 pub mod embeddings;
+pub mod encrypted;
+pub mod git_sync;
 pub mod hybrid;
 pub mod hygiene;
+pub mod import;
 pub mod lucid;
 pub mod markdown;
+pub mod migrate;
 pub mod none;
 #[cfg(feature = "memory-postgres")]
 pub mod postgres;
@@ -16,6 +21,7 @@ pub mod response_cache;
 pub mod retrieval;
 pub mod snapshot;
 pub mod sqlite;
+pub mod tag_suggest;
 pub mod traits;
 pub mod vector;
 
@@ -36,7 +42,7 @@ pub use response_cache::ResponseCache;
 pub use sqlite::SqliteMemory;
 pub use traits::Memory;
 #[allow(unused_imports)]
-pub use traits::{MemoryCategory, MemoryEntry};
+pub use traits::{entry_time, MemoryCategory, MemoryEntry};
 
 use crate::config::{EmbeddingRouteConfig, MemoryConfig, StorageProviderConfig};
 use anyhow::Context;
@@ -94,6 +100,94 @@ pub fn effective_memory_backend_name(
     memory_backend.trim().to_ascii_lowercase()
 }
 
+/// Category of pinned entries: always injected into the system prompt
+/// instead of waiting for recall. Only `memory_store` with `pinned: true`
+/// writes it; entry content never pins anything, so text arriving through a
+/// channel cannot pin itself.
+pub const PINNED_CATEGORY: &str = "pinned";
+
+/// The [`PINNED_CATEGORY`] category.
+pub fn pinned_category() -> MemoryCategory {
+    MemoryCategory::Custom(PINNED_CATEGORY.to_string())
+}
+
+/// Whether `entry` is stored in the [`PINNED_CATEGORY`] category.
+pub fn is_pinned(entry: &MemoryEntry) -> bool {
+    matches!(&entry.category, MemoryCategory::Custom(name) if name == PINNED_CATEGORY)
+}
+
+/// Trailer prefix listing the keys of entries a note relates to, e.g.
+/// `Relates-To: deploy_policy, incident_0412`.
+pub const RELATES_TO_PREFIX: &str = "Relates-To: ";
+
+/// Keys on the [`RELATES_TO_PREFIX`] trailer line of `content`.
+pub fn related_keys(content: &str) -> Vec<String> {
+    content
+        .lines()
+        .filter_map(|line| line.trim().strip_prefix(RELATES_TO_PREFIX))
+        .flat_map(|keys| keys.split(','))
+        .map(str::trim)
+        .filter(|key| !key.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Trailer prefix recording who wrote an entry, e.g.
+/// `Provenance: session=telegram_alice; call=call_1; model=gpt-4o`.
+pub const PROVENANCE_PREFIX: &str = "Provenance: ";
+
+/// [`PROVENANCE_PREFIX`] trailer line for an entry written by a tool call
+/// with `context`, or `None` when the context is empty.
+pub fn provenance_line(context: &crate::tools::ToolExecutionContext) -> Option<String> {
+    let fields: Vec<String> = [
+        ("session", &context.session_id),
+        ("call", &context.correlation_id),
+        ("model", &context.model),
+    ]
+    .into_iter()
+    .filter_map(|(name, value)| Some(format!("{name}={}", value.as_deref()?)))
+    .collect();
+    (!fields.is_empty()).then(|| format!("{PROVENANCE_PREFIX}{}", fields.join("; ")))
+}
+
+/// Provenance recorded on the [`PROVENANCE_PREFIX`] trailer line of `content`.
+pub fn entry_provenance(content: &str) -> Option<crate::tools::ToolExecutionContext> {
+    let line = content
+        .lines()
+        .find_map(|line| line.trim().strip_prefix(PROVENANCE_PREFIX))?;
+    let mut context = crate::tools::ToolExecutionContext::default();
+    for (name, value) in line.split(';').filter_map(|field| field.split_once('=')) {
+        let value = Some(value.trim().to_string());
+        match name.trim() {
+            "session" => context.session_id = value,
+            "call" => context.correlation_id = value,
+            "model" => context.model = value,
+            _ => {}
+        }
+    }
+    Some(context)
+}
+
+/// Entries that `entries` link to (one hop) and that are not already among
+/// them. Links to entries that no longer exist are skipped.
+pub async fn linked_entries(
+    memory: &dyn Memory,
+    entries: &[MemoryEntry],
+) -> anyhow::Result<Vec<MemoryEntry>> {
+    let mut seen: std::collections::HashSet<String> =
+        entries.iter().map(|e| e.key.clone()).collect();
+    let mut linked = Vec::new();
+    for key in entries.iter().flat_map(|e| related_keys(&e.content)) {
+        if !seen.insert(key.clone()) {
+            continue;
+        }
+        if let Some(entry) = memory.get(&key).await?.filter(|e| e.key == key) {
+            linked.push(entry);
+        }
+    }
+    Ok(linked)
+}
+
 /// Legacy auto-save key used for model-authored assistant summaries.
 /// These entries are treated as untrusted context and should not be re-injected.
 pub fn is_assistant_autosave_key(key: &str) -> bool {
@@ -207,6 +301,23 @@ pub fn create_memory_with_storage_and_routes(
     storage_provider: Option<&StorageProviderConfig>,
     workspace_dir: &Path,
     api_key: Option<&str>,
+) -> anyhow::Result<Box<dyn Memory>> {
+    let memory = create_backend_memory(
+        config,
+        embedding_routes,
+        storage_provider,
+        workspace_dir,
+        api_key,
+    )?;
+    encrypted::wrap_if_enabled(config, workspace_dir, memory)
+}
+
+fn create_backend_memory(
+    config: &MemoryConfig,
+    embedding_routes: &[EmbeddingRouteConfig],
+    storage_provider: Option<&StorageProviderConfig>,
+    workspace_dir: &Path,
+    api_key: Option<&str>,
 ) -> anyhow::Result<Box<dyn Memory>> {
     let backend_name = effective_memory_backend_name(&config.backend, storage_provider);
     let backend_kind = classify_memory_backend(&backend_name);
@@ -366,6 +477,12 @@ pub fn create_memory_with_storage_and_routes(
         return Ok(Box::new(build_qdrant_memory(config, &resolved_embedding)?));
     }
 
+    if matches!(backend_kind, MemoryBackendKind::Markdown) {
+        return Ok(Box::new(
+            MarkdownMemory::new(workspace_dir).with_rotate_bytes(config.markdown_rotate_bytes),
+        ));
+    }
+
     if matches!(backend_kind, MemoryBackendKind::SqliteQdrantHybrid) {
         let sqlite: Arc<dyn Memory> = Arc::new(build_sqlite_memory(
             config,
@@ -465,6 +582,36 @@ mod tests {
         assert!(!is_assistant_autosave_key("user_msg_1234"));
     }
 
+    #[test]
+    fn related_keys_reads_trailer_line() {
+        let content = "Switched to blue-green deploys\n\nTags: #ops\nRelates-To: deploy_policy, incident_0412";
+        assert_eq!(
+            related_keys(content),
+            vec!["deploy_policy".to_string(), "incident_0412".to_string()]
+        );
+        assert!(related_keys("no links here").is_empty());
+    }
+
+    #[test]
+    fn provenance_line_round_trips() {
+        let context = crate::tools::ToolExecutionContext {
+            session_id: Some("telegram_alice".into()),
+            correlation_id: None,
+            model: Some("gpt-4o".into()),
+        };
+        let line = provenance_line(&context).unwrap();
+        assert_eq!(line, "Provenance: session=telegram_alice; model=gpt-4o");
+        assert_eq!(
+            entry_provenance(&format!("Note\n\nTags: #ops\n{line}")),
+            Some(context)
+        );
+        assert_eq!(
+            provenance_line(&crate::tools::ToolExecutionContext::default()),
+            None
+        );
+        assert_eq!(entry_provenance("no provenance"), None);
+    }
+
     #[test]
     fn factory_markdown() {
         let tmp = TempDir::new().unwrap();
@@ -204,7 +204,7 @@ fn snapshot_path(workspace_dir: &Path) -> PathBuf {
 }
 
 /// Parse the structured markdown snapshot back into (key, content) pairs.
-fn parse_snapshot(input: &str) -> Vec<(String, String)> {
+pub(super) fn parse_snapshot(input: &str) -> Vec<(String, String)> {
     let mut entries = Vec::new();
     let mut current_key: Option<String> = None;
     let mut current_content = String::new();
@@ -3,9 +3,10 @@ use super::traits::{Memory, MemoryCategory, MemoryEntry};
 use super::vector;
 use anyhow::Context;
 use async_trait::async_trait;
-use chrono::Local;
+use chrono::{DateTime, Local, Utc};
 use parking_lot::Mutex;
 use rusqlite::{params, Connection};
+use std::collections::{HashMap, HashSet};
 use std::fmt::Write as _;
 use std::path::{Path, PathBuf};
 use std::sync::mpsc;
@@ -17,6 +18,32 @@ use uuid::Uuid;
 /// Maximum allowed open timeout (seconds) to avoid unreasonable waits.
 const SQLITE_OPEN_TIMEOUT_CAP_SECS: u64 = 300;
 
+/// Consistency snapshot of `brain.db` search indexes, reported by `zeroclaw doctor`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IndexHealth {
+    /// Rows in the `memories` table.
+    pub memories: usize,
+    /// `None` when the FTS5 index matches the memories table; otherwise the
+    /// error reported by the FTS5 checksum-based `integrity-check`.
+    pub fts_error: Option<String>,
+    /// Memories stored without an embedding (embedding request failed or the
+    /// provider was configured after they were written).
+    pub missing_embeddings: usize,
+    /// Embeddings whose length does not match the configured dimensions.
+    pub mismatched_embeddings: usize,
+}
+
+impl IndexHealth {
+    pub fn is_consistent(&self) -> bool {
+        self.fts_error.is_none() && self.missing_embeddings == 0 && self.mismatched_embeddings == 0
+    }
+}
+
+/// Entries embedded per reindex step: enough to keep every concurrent batch
+/// busy, small enough that no single step sends the whole table.
+const REINDEX_CHUNK_SIZE: usize = super::embeddings::DEFAULT_EMBED_BATCH_SIZE
+    * super::embeddings::DEFAULT_EMBED_MAX_CONCURRENT_BATCHES;
+
 /// SQLite-backed persistent memory — the brain
 ///
 /// Full-stack search engine:
@@ -254,6 +281,155 @@ impl SqliteMemory {
         )
     }
 
+    /// Bulk variant of [`Self::get_or_compute_embedding`] used by reindexing.
+    ///
+    /// Cache hits are resolved in one query; misses are deduplicated and sent
+    /// to the provider in concurrent batches, then written back to the cache
+    /// in a single transaction. The result is aligned with `texts`.
+    /// Embeddings for one reindex chunk. When the batch request fails, each
+    /// text is retried on its own, so one bad entry is left unembedded (for
+    /// the next incremental reindex) instead of aborting the whole reindex.
+    async fn embed_chunk_tolerant(&self, contents: &[String]) -> Vec<Option<Vec<f32>>> {
+        match self.get_or_compute_embeddings(contents).await {
+            Ok(embeddings) => embeddings,
+            Err(batch_error) => {
+                tracing::warn!(
+                    "Reindex batch of {} entries failed, retrying one at a time: {batch_error}",
+                    contents.len()
+                );
+                let mut embeddings = Vec::with_capacity(contents.len());
+                for content in contents {
+                    embeddings.push(match self.get_or_compute_embedding(content).await {
+                        Ok(embedding) => embedding,
+                        Err(e) => {
+                            tracing::warn!("Reindex left an entry without a vector: {e}");
+                            None
+                        }
+                    });
+                }
+                embeddings
+            }
+        }
+    }
+
+    async fn get_or_compute_embeddings(
+        &self,
+        texts: &[String],
+    ) -> anyhow::Result<Vec<Option<Vec<f32>>>> {
+        if self.embedder.dimensions() == 0 || texts.is_empty() {
+            return Ok(vec![None; texts.len()]);
+        }
+
+        let hashes: Vec<String> = texts.iter().map(|t| Self::content_hash(t)).collect();
+        let now = Local::now().to_rfc3339();
+
+        // Resolve cache hits (offloaded to blocking thread)
+        let conn = self.conn.clone();
+        let hashes_c = hashes.clone();
+        let now_c = now.clone();
+        let mut resolved: HashMap<String, Vec<f32>> =
+            tokio::task::spawn_blocking(move || -> anyhow::Result<HashMap<String, Vec<f32>>> {
+                let conn = conn.lock();
+                let mut stmt =
+                    conn.prepare("SELECT embedding FROM embedding_cache WHERE content_hash = ?1")?;
+                let mut touch = conn.prepare(
+                    "UPDATE embedding_cache SET accessed_at = ?1 WHERE content_hash = ?2",
+                )?;
+                let mut hits = HashMap::new();
+                for hash in hashes_c {
+                    if hits.contains_key(&hash) {
+                        continue;
+                    }
+                    let blob: Option<Vec<u8>> =
+                        stmt.query_row(params![hash], |row| row.get(0)).ok();
+                    if let Some(bytes) = blob {
+                        touch.execute(params![now_c, hash])?;
+                        hits.insert(hash, vector::bytes_to_vec(&bytes));
+                    }
+                }
+                Ok(hits)
+            })
+            .await??;
+
+        // Compute misses in concurrent batches (async I/O)
+        let mut queued = HashSet::new();
+        let mut missing: Vec<(String, &str)> = Vec::new();
+        for (hash, text) in hashes.iter().zip(texts) {
+            if !resolved.contains_key(hash) && queued.insert(hash.as_str()) {
+                missing.push((hash.clone(), text.as_str()));
+            }
+        }
+        if !missing.is_empty() {
+            let missing_texts: Vec<&str> = missing.iter().map(|(_, text)| *text).collect();
+            let computed = super::embeddings::embed_in_batches(
+                self.embedder.as_ref(),
+                &missing_texts,
+                super::embeddings::DEFAULT_EMBED_BATCH_SIZE,
+                super::embeddings::DEFAULT_EMBED_MAX_CONCURRENT_BATCHES,
+            )
+            .await?;
+
+            // Store in cache + LRU eviction (offloaded to blocking thread)
+            let rows: Vec<(String, Vec<u8>)> = missing
+                .iter()
+                .zip(&computed)
+                .map(|((hash, _), emb)| (hash.clone(), vector::vec_to_bytes(emb)))
+                .collect();
+            let conn = self.conn.clone();
+            #[allow(clippy::cast_possible_wrap)]
+            let cache_max = self.cache_max as i64;
+            tokio::task::spawn_blocking(move || -> anyhow::Result<()> {
+                let mut conn = conn.lock();
+                let tx = conn.transaction()?;
+                for (hash, bytes) in rows {
+                    tx.execute(
+                        "INSERT OR REPLACE INTO embedding_cache (content_hash, embedding, created_at, accessed_at)
+                         VALUES (?1, ?2, ?3, ?4)",
+                        params![hash, bytes, now, now],
+                    )?;
+                }
+                tx.execute(
+                    "DELETE FROM embedding_cache WHERE content_hash IN (
+                        SELECT content_hash FROM embedding_cache
+                        ORDER BY accessed_at ASC
+                        LIMIT MAX(0, (SELECT COUNT(*) FROM embedding_cache) - ?1)
+                    )",
+                    params![cache_max],
+                )?;
+                tx.commit()?;
+                Ok(())
+            })
+            .await??;
+
+            for ((hash, _), emb) in missing.into_iter().zip(computed) {
+                resolved.insert(hash, emb);
+            }
+        }
+
+        Ok(hashes.iter().map(|h| resolved.get(h).cloned()).collect())
+    }
+
+    /// Write freshly computed embeddings back to `memories` in one transaction.
+    async fn store_embeddings(&self, updates: Vec<(String, Vec<u8>)>) -> anyhow::Result<()> {
+        if updates.is_empty() {
+            return Ok(());
+        }
+        let conn = self.conn.clone();
+        tokio::task::spawn_blocking(move || -> anyhow::Result<()> {
+            let mut conn = conn.lock();
+            let tx = conn.transaction()?;
+            for (id, bytes) in updates {
+                tx.execute(
+                    "UPDATE memories SET embedding = ?1 WHERE id = ?2",
+                    params![bytes, id],
+                )?;
+            }
+            tx.commit()?;
+            Ok(())
+        })
+        .await?
+    }
+
     /// Get embedding from cache, or compute + cache it
     async fn get_or_compute_embedding(&self, text: &str) -> anyhow::Result<Option<Vec<f32>>> {
         if self.embedder.dimensions() == 0 {
@@ -409,6 +585,59 @@ impl SqliteMemory {
         Ok(scored)
     }
 
+    /// Inspect the FTS5 and embedding indexes of an existing `brain.db`
+    /// without modifying it.
+    ///
+    /// `expected_dims` is the configured embedding size; pass `0` when
+    /// embeddings are disabled to skip the embedding checks.
+    pub fn index_health(db_path: &Path, expected_dims: usize) -> anyhow::Result<IndexHealth> {
+        let conn = Connection::open_with_flags(
+            db_path,
+            rusqlite::OpenFlags::SQLITE_OPEN_READ_WRITE | rusqlite::OpenFlags::SQLITE_OPEN_URI,
+        )
+        .with_context(|| format!("Failed to open {}", db_path.display()))?;
+        conn.busy_timeout(Duration::from_secs(5))?;
+
+        let count = |sql: &str, params: &[&dyn rusqlite::ToSql]| -> anyhow::Result<usize> {
+            let n: i64 = conn.query_row(sql, params, |row| row.get(0))?;
+            Ok(usize::try_from(n).unwrap_or(0))
+        };
+
+        let memories = count("SELECT COUNT(*) FROM memories", &[])?;
+
+        // `rank = 1` makes FTS5 also compare its checksums against the
+        // external content table, catching rows the triggers missed.
+        let fts_error = conn
+            .execute(
+                "INSERT INTO memories_fts(memories_fts, rank) VALUES('integrity-check', 1)",
+                [],
+            )
+            .err()
+            .map(|e| e.to_string());
+
+        let (missing_embeddings, mismatched_embeddings) = if expected_dims == 0 {
+            (0, 0)
+        } else {
+            #[allow(clippy::cast_possible_wrap)]
+            let expected_len = (expected_dims * 4) as i64;
+            (
+                count("SELECT COUNT(*) FROM memories WHERE embedding IS NULL", &[])?,
+                count(
+                    "SELECT COUNT(*) FROM memories
+                     WHERE embedding IS NOT NULL AND length(embedding) != ?1",
+                    &[&expected_len],
+                )?,
+            )
+        };
+
+        Ok(IndexHealth {
+            memories,
+            fts_error,
+            missing_embeddings,
+            mismatched_embeddings,
+        })
+    }
+
     /// Safe reindex: rebuild FTS5 + embeddings with rollback on failure
     #[allow(dead_code)]
     pub async fn reindex(&self) -> anyhow::Result<usize> {
@@ -441,22 +670,16 @@ impl SqliteMemory {
         .await??;
 
         let mut count = 0;
-        for (id, content) in &entries {
-            if let Ok(Some(emb)) = self.get_or_compute_embedding(content).await {
-                let bytes = vector::vec_to_bytes(&emb);
-                let conn = self.conn.clone();
-                let id = id.clone();
-                tokio::task::spawn_blocking(move || -> anyhow::Result<()> {
-                    let conn = conn.lock();
-                    conn.execute(
-                        "UPDATE memories SET embedding = ?1 WHERE id = ?2",
-                        params![bytes, id],
-                    )?;
-                    Ok(())
-                })
-                .await??;
-                count += 1;
-            }
+        for chunk in entries.chunks(REINDEX_CHUNK_SIZE) {
+            let contents: Vec<String> = chunk.iter().map(|(_, content)| content.clone()).collect();
+            let embeddings = self.embed_chunk_tolerant(&contents).await;
+            let updates: Vec<(String, Vec<u8>)> = chunk
+                .iter()
+                .zip(embeddings)
+                .filter_map(|((id, _), emb)| Some((id.clone(), vector::vec_to_bytes(&emb?))))
+                .collect();
+            count += updates.len();
+            self.store_embeddings(updates).await?;
         }
 
         Ok(count)
@@ -476,11 +699,16 @@ impl Memory for SqliteMemory {
         category: MemoryCategory,
         session_id: Option<&str>,
     ) -> anyhow::Result<()> {
-        // Compute embedding (async, before blocking work)
-        let embedding_bytes = self
-            .get_or_compute_embedding(content)
-            .await?
-            .map(|emb| vector::vec_to_bytes(&emb));
+        // Compute embedding (async, before blocking work). A failed embedding
+        // request must not lose the memory: store it keyword-only and let the
+        // next incremental `reindex` (which only embeds NULL rows) fill it in.
+        let embedding_bytes = match self.get_or_compute_embedding(content).await {
+            Ok(embedding) => embedding.map(|emb| vector::vec_to_bytes(&emb)),
+            Err(e) => {
+                tracing::warn!("Embedding failed for memory '{key}', storing without vector: {e}");
+                None
+            }
+        };
 
         let conn = self.conn.clone();
         let key = key.to_string();
@@ -781,6 +1009,43 @@ impl Memory for SqliteMemory {
         .await?
     }
 
+    /// Range query on `created_at`, so the result is not capped by the
+    /// `list` limit. `julianday` normalizes the stored UTC offsets.
+    async fn entries_between(
+        &self,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+        category: Option<&MemoryCategory>,
+    ) -> anyhow::Result<Vec<MemoryEntry>> {
+        let conn = self.conn.clone();
+        let category = category.map(Self::category_to_str);
+        let start = start.to_rfc3339();
+        let end = end.to_rfc3339();
+
+        tokio::task::spawn_blocking(move || -> anyhow::Result<Vec<MemoryEntry>> {
+            let conn = conn.lock();
+            let mut stmt = conn.prepare(
+                "SELECT id, key, content, category, created_at, session_id FROM memories
+                 WHERE julianday(created_at) BETWEEN julianday(?1) AND julianday(?2)
+                   AND (?3 IS NULL OR category = ?3)
+                 ORDER BY julianday(created_at) ASC",
+            )?;
+            let rows = stmt.query_map(params![start, end, category], |row| {
+                Ok(MemoryEntry {
+                    id: row.get(0)?,
+                    key: row.get(1)?,
+                    content: row.get(2)?,
+                    category: Self::str_to_category(&row.get::<_, String>(3)?),
+                    timestamp: row.get(4)?,
+                    session_id: row.get(5)?,
+                    score: None,
+                })
+            })?;
+            Ok(rows.collect::<rusqlite::Result<Vec<_>>>()?)
+        })
+        .await?
+    }
+
     async fn forget(&self, key: &str) -> anyhow::Result<bool> {
         let conn = self.conn.clone();
         let key = key.to_string();
@@ -836,34 +1101,27 @@ impl Memory for SqliteMemory {
             .await??;
         }
 
-        // Step 3: Recompute embeddings for each memory
+        // Step 3: Recompute embeddings in concurrent batches, one chunk at a
+        // time so progress is still reported during large rebuilds
         let mut reindexed = 0;
-        for (idx, entry) in entries.iter().enumerate() {
-            // Compute new embedding
-            let embedding = self.get_or_compute_embedding(&entry.content).await?;
-
-            if let Some(ref emb) = embedding {
-                // Update the embedding in the memories table
-                let conn = self.conn.clone();
-                let entry_id = entry.id.clone();
-                let emb_bytes = vector::vec_to_bytes(emb);
-
-                tokio::task::spawn_blocking(move || -> anyhow::Result<()> {
-                    let conn = conn.lock();
-                    conn.execute(
-                        "UPDATE memories SET embedding = ?1 WHERE id = ?2",
-                        params![emb_bytes, entry_id],
-                    )?;
-                    Ok(())
-                })
-                .await??;
-
-                reindexed += 1;
-            }
+        let mut done = 0;
+        for chunk in entries.chunks(REINDEX_CHUNK_SIZE) {
+            let contents: Vec<String> = chunk.iter().map(|e| e.content.clone()).collect();
+            let embeddings = self.embed_chunk_tolerant(&contents).await;
+
+            // Update the embeddings in the memories table
+            let updates: Vec<(String, Vec<u8>)> = chunk
+                .iter()
+                .zip(embeddings)
+                .filter_map(|(entry, emb)| Some((entry.id.clone(), vector::vec_to_bytes(&emb?))))
+                .collect();
+            reindexed += updates.len();
+            self.store_embeddings(updates).await?;
 
             // Report progress
+            done += chunk.len();
             if let Some(ref cb) = progress_callback {
-                cb(idx + 1, total);
+                cb(done, total);
             }
         }
 
@@ -1676,6 +1934,182 @@ mod tests {
         assert_eq!(results.len(), 1);
     }
 
+    /// Deterministic 2-d embedder that records every batch it receives.
+    struct BatchRecordingEmbedding {
+        batches: parking_lot::Mutex<Vec<usize>>,
+    }
+
+    #[async_trait]
+    impl EmbeddingProvider for BatchRecordingEmbedding {
+        fn name(&self) -> &str {
+            "batch-recording"
+        }
+
+        fn dimensions(&self) -> usize {
+            2
+        }
+
+        async fn embed(&self, texts: &[&str]) -> anyhow::Result<Vec<Vec<f32>>> {
+            self.batches.lock().push(texts.len());
+            #[allow(clippy::cast_precision_loss)]
+            Ok(texts.iter().map(|t| vec![t.len() as f32, 1.0]).collect())
+        }
+    }
+
+    #[tokio::test]
+    async fn trait_reindex_embeds_entries_in_batches() {
+        let tmp = TempDir::new().unwrap();
+        let embedder = Arc::new(BatchRecordingEmbedding {
+            batches: parking_lot::Mutex::new(Vec::new()),
+        });
+        let mem =
+            SqliteMemory::with_embedder(tmp.path(), embedder.clone(), 0.7, 0.3, 100, None).unwrap();
+        for i in 0..5 {
+            mem.store(
+                &format!("k{i}"),
+                &format!("entry number {i}"),
+                MemoryCategory::Core,
+                None,
+            )
+            .await
+            .unwrap();
+        }
+        embedder.batches.lock().clear();
+
+        let reindexed = Memory::reindex(&mem, None).await.unwrap();
+
+        assert_eq!(reindexed, 5);
+        assert_eq!(*embedder.batches.lock(), vec![5]);
+    }
+
+    struct FailingEmbedding;
+
+    #[async_trait]
+    impl EmbeddingProvider for FailingEmbedding {
+        fn name(&self) -> &str {
+            "failing"
+        }
+
+        fn dimensions(&self) -> usize {
+            2
+        }
+
+        async fn embed(&self, _texts: &[&str]) -> anyhow::Result<Vec<Vec<f32>>> {
+            anyhow::bail!("embedding endpoint unavailable")
+        }
+    }
+
+    /// Embedder that rejects any batch containing a "poison" text.
+    struct PoisonedEmbedding;
+
+    #[async_trait]
+    impl EmbeddingProvider for PoisonedEmbedding {
+        fn name(&self) -> &str {
+            "poisoned"
+        }
+
+        fn dimensions(&self) -> usize {
+            2
+        }
+
+        async fn embed(&self, texts: &[&str]) -> anyhow::Result<Vec<Vec<f32>>> {
+            anyhow::ensure!(
+                !texts.iter().any(|t| t.contains("poison")),
+                "provider rejected the batch"
+            );
+            Ok(texts.iter().map(|_| vec![1.0, 0.0]).collect())
+        }
+    }
+
+    #[tokio::test]
+    async fn reindex_skips_entries_whose_embedding_fails() {
+        let tmp = TempDir::new().unwrap();
+        let mem = SqliteMemory::with_embedder(
+            tmp.path(),
+            Arc::new(PoisonedEmbedding),
+            0.7,
+            0.3,
+            100,
+            None,
+        )
+        .unwrap();
+        for (key, content) in [("a", "alpha"), ("b", "poison pill"), ("c", "gamma")] {
+            mem.store(key, content, MemoryCategory::Core, None)
+                .await
+                .unwrap();
+        }
+
+        assert_eq!(Memory::reindex(&mem, None).await.unwrap(), 2);
+        let health = SqliteMemory::index_health(&mem.db_path, 2).unwrap();
+        assert_eq!(health.missing_embeddings, 1);
+    }
+
+    #[tokio::test]
+    async fn store_keeps_memory_when_embedding_fails() {
+        let tmp = TempDir::new().unwrap();
+        let mem = SqliteMemory::with_embedder(
+            tmp.path(),
+            Arc::new(FailingEmbedding),
+            0.7,
+            0.3,
+            100,
+            None,
+        )
+        .unwrap();
+        mem.store(
+            "offline",
+            "written while offline",
+            MemoryCategory::Core,
+            None,
+        )
+        .await
+        .unwrap();
+        assert!(mem.get("offline").await.unwrap().is_some());
+
+        let health = SqliteMemory::index_health(&mem.db_path, 2).unwrap();
+        assert_eq!(health.memories, 1);
+        assert_eq!(health.missing_embeddings, 1);
+        assert!(!health.is_consistent());
+    }
+
+    #[tokio::test]
+    async fn index_health_reports_consistent_fts() {
+        let (_tmp, mem) = temp_sqlite();
+        mem.store("a", "alpha", MemoryCategory::Core, None)
+            .await
+            .unwrap();
+        mem.store("a", "alpha updated", MemoryCategory::Core, None)
+            .await
+            .unwrap();
+        mem.forget("a").await.unwrap();
+        mem.store("b", "beta", MemoryCategory::Daily, None)
+            .await
+            .unwrap();
+
+        let health = SqliteMemory::index_health(&mem.db_path, 0).unwrap();
+        assert_eq!(health.memories, 1);
+        assert_eq!(health.fts_error, None);
+        assert!(health.is_consistent());
+    }
+
+    #[tokio::test]
+    async fn index_health_flags_embedding_dimension_drift() {
+        let tmp = TempDir::new().unwrap();
+        let embedder = Arc::new(BatchRecordingEmbedding {
+            batches: parking_lot::Mutex::new(Vec::new()),
+        });
+        let mem = SqliteMemory::with_embedder(tmp.path(), embedder, 0.7, 0.3, 100, None).unwrap();
+        mem.store("k", "two dims", MemoryCategory::Core, None)
+            .await
+            .unwrap();
+
+        assert!(SqliteMemory::index_health(&mem.db_path, 2)
+            .unwrap()
+            .is_consistent());
+        let drifted = SqliteMemory::index_health(&mem.db_path, 3).unwrap();
+        assert_eq!(drifted.mismatched_embeddings, 1);
+    }
+
     // ── Edge cases: content_hash ─────────────────────────────────
 
     #[test]
@@ -1753,6 +2187,45 @@ mod tests {
         assert_eq!(project.len(), 2);
     }
 
+    #[tokio::test]
+    async fn entries_between_filters_by_created_at() {
+        let (_tmp, mem) = temp_sqlite();
+        mem.store("old", "last week", MemoryCategory::Daily, None)
+            .await
+            .unwrap();
+        mem.store("new", "today", MemoryCategory::Daily, None)
+            .await
+            .unwrap();
+        mem.store("fact", "core fact", MemoryCategory::Core, None)
+            .await
+            .unwrap();
+        // Offsets and fractional seconds as written by `Local::now().to_rfc3339()`.
+        mem.conn.lock().execute(
+            "UPDATE memories SET created_at = '2025-03-03T23:30:00.123456789-05:00' WHERE key = 'old'",
+            [],
+        )
+        .unwrap();
+
+        let at = |s: &str| DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc);
+        let week = mem
+            .entries_between(at("2025-03-04T00:00:00Z"), at("2025-03-05T00:00:00Z"), None)
+            .await
+            .unwrap();
+        assert_eq!(week.len(), 1);
+        assert_eq!(week[0].key, "old");
+
+        let daily = mem
+            .entries_between(
+                at("2025-01-01T00:00:00Z"),
+                Utc::now(),
+                Some(&MemoryCategory::Daily),
+            )
+            .await
+            .unwrap();
+        let keys: Vec<&str> = daily.iter().map(|e| e.key.as_str()).collect();
+        assert_eq!(keys, vec!["old", "new"]);
+    }
+
     #[tokio::test]
     async fn list_empty_db() {
         let (_tmp, mem) = temp_sqlite();
@@ -0,0 +1,84 @@
+//! Keyword-rule tag suggestions for untagged memory notes.
+//!
+//! Suggested tags live on their own trailer line, without `#`, so they are
+//! never mistaken for tags the author wrote:
+//!
+//! ```text
+//! We agreed to ship on Tuesdays
+//!
+//! Suggested-Tags: decision, release
+//! ```
+
+use crate::config::TagSuggestionRule;
+
+/// Trailer prefix for tags suggested by `[[memory.tag_suggestions]]` rules.
+pub const SUGGESTED_TAGS_PREFIX: &str = "Suggested-Tags: ";
+
+/// Tags from `rules` whose keywords appear in `content`, in rule order.
+pub fn suggest_tags(rules: &[TagSuggestionRule], content: &str) -> Vec<String> {
+    let lower = content.to_lowercase();
+    let mut tags: Vec<String> = Vec::new();
+    for rule in rules {
+        let tag = rule.tag.trim().trim_start_matches('#');
+        if tag.is_empty() || tags.iter().any(|t| t == tag) {
+            continue;
+        }
+        if rule
+            .keywords
+            .iter()
+            .any(|kw| !kw.is_empty() && lower.contains(&kw.to_lowercase()))
+        {
+            tags.push(tag.to_string());
+        }
+    }
+    tags
+}
+
+/// Tags on the [`SUGGESTED_TAGS_PREFIX`] trailer line of `content`.
+pub fn suggested_tags(content: &str) -> Vec<String> {
+    content
+        .lines()
+        .filter_map(|line| line.trim().strip_prefix(SUGGESTED_TAGS_PREFIX))
+        .flat_map(|tags| tags.split(','))
+        .map(str::trim)
+        .filter(|tag| !tag.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(tag: &str, keywords: &[&str]) -> TagSuggestionRule {
+        TagSuggestionRule {
+            tag: tag.into(),
+            keywords: keywords.iter().map(|k| (*k).to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn suggests_tags_for_matching_keywords() {
+        let rules = vec![
+            rule("decision", &["agreed", "decided"]),
+            rule("release", &["ship"]),
+            rule("#decision", &["tuesday"]),
+            rule("incident", &["outage"]),
+        ];
+        assert_eq!(
+            suggest_tags(&rules, "We AGREED to ship on Tuesdays"),
+            vec!["decision".to_string(), "release".to_string()]
+        );
+        assert!(suggest_tags(&rules, "Nothing relevant").is_empty());
+    }
+
+    #[test]
+    fn reads_suggested_tags_trailer() {
+        let content = "Ship on Tuesdays\n\nSuggested-Tags: decision, release";
+        assert_eq!(
+            suggested_tags(content),
+            vec!["decision".to_string(), "release".to_string()]
+        );
+        assert!(suggested_tags("Ship #release").is_empty());
+    }
+}
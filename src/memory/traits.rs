@@ -1,4 +1,5 @@
 use async_trait::async_trait;
+use chrono::{DateTime, Local, NaiveDate, TimeZone, Utc};
 use serde::{Deserialize, Serialize};
 
 /// A single memory entry
@@ -51,6 +52,20 @@ impl std::fmt::Display for MemoryCategory {
     }
 }
 
+/// Instant of a stored entry timestamp. RFC 3339 timestamps are exact;
+/// date-only timestamps (markdown daily files) count as local midnight.
+pub fn entry_time(timestamp: &str) -> Option<DateTime<Utc>> {
+    let timestamp = timestamp.trim();
+    if let Ok(parsed) = DateTime::parse_from_rfc3339(timestamp) {
+        return Some(parsed.with_timezone(&Utc));
+    }
+    let date = NaiveDate::parse_from_str(timestamp.get(..10)?, "%Y-%m-%d").ok()?;
+    Local
+        .from_local_datetime(&date.and_hms_opt(0, 0, 0)?)
+        .earliest()
+        .map(|t| t.with_timezone(&Utc))
+}
+
 /// Core memory trait — implement for any persistence backend
 #[async_trait]
 pub trait Memory: Send + Sync {
@@ -66,6 +81,21 @@ pub trait Memory: Send + Sync {
         session_id: Option<&str>,
     ) -> anyhow::Result<()>;
 
+    /// Store an entry written by the tool named `source`. Backends with a
+    /// place to record the writer (markdown frontmatter) override this; the
+    /// default stores the entry without it.
+    async fn store_from(
+        &self,
+        key: &str,
+        content: &str,
+        category: MemoryCategory,
+        session_id: Option<&str>,
+        source: &str,
+    ) -> anyhow::Result<()> {
+        let _ = source;
+        self.store(key, content, category, session_id).await
+    }
+
     /// Recall memories matching a query (keyword search), optionally scoped to a session
     async fn recall(
         &self,
@@ -84,6 +114,28 @@ pub trait Memory: Send + Sync {
         session_id: Option<&str>,
     ) -> anyhow::Result<Vec<MemoryEntry>>;
 
+    /// Entries written between `start` and `end` (inclusive), oldest first,
+    /// optionally filtered by category. The default filters [`Memory::list`];
+    /// backends that can query by time should override it.
+    async fn entries_between(
+        &self,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+        category: Option<&MemoryCategory>,
+    ) -> anyhow::Result<Vec<MemoryEntry>> {
+        let mut entries: Vec<(DateTime<Utc>, MemoryEntry)> = self
+            .list(category, None)
+            .await?
+            .into_iter()
+            .filter_map(|entry| {
+                let time = entry_time(&entry.timestamp)?;
+                (time >= start && time <= end).then_some((time, entry))
+            })
+            .collect();
+        entries.sort_by_key(|(time, _)| *time);
+        Ok(entries.into_iter().map(|(_, entry)| entry).collect())
+    }
+
     /// Remove a memory by key
     async fn forget(&self, key: &str) -> anyhow::Result<bool>;
 
@@ -93,6 +145,24 @@ pub trait Memory: Send + Sync {
     /// Health check
     async fn health_check(&self) -> bool;
 
+    /// Replace the content of the entry stored under `key` with `replacement`,
+    /// keeping its category and session. Returns `false` if there is no such
+    /// entry. Backends that keep earlier versions of an entry (append-only
+    /// files) must overwrite them rather than append.
+    async fn redact(&self, key: &str, replacement: &str) -> anyhow::Result<bool> {
+        let Some(entry) = self.get(key).await?.filter(|entry| entry.key == key) else {
+            return Ok(false);
+        };
+        self.store(
+            key,
+            replacement,
+            entry.category,
+            entry.session_id.as_deref(),
+        )
+        .await?;
+        Ok(true)
+    }
+
     /// Rebuild embeddings for all memories using the current embedding provider.
     /// Returns the number of memories reindexed, or an error if not supported.
     ///
@@ -133,6 +203,19 @@ mod tests {
         assert_eq!(conversation, "\"conversation\"");
     }
 
+    #[test]
+    fn entry_time_reads_rfc3339_and_daily_file_names() {
+        assert_eq!(
+            entry_time("2025-01-31T23:10:00+02:00")
+                .unwrap()
+                .to_rfc3339(),
+            "2025-01-31T21:10:00+00:00"
+        );
+        let midnight = Local.with_ymd_and_hms(2025, 1, 31, 0, 0, 0).unwrap();
+        assert_eq!(entry_time("2025-01-31"), Some(midnight.with_timezone(&Utc)));
+        assert_eq!(entry_time("MEMORY"), None);
+    }
+
     #[test]
     fn memory_entry_roundtrip_preserves_optional_fields() {
         let entry = MemoryEntry {
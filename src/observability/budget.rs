@@ -0,0 +1,176 @@
+//! Observer that charges provider token usage to the security policy's
+//! daily budgets.
+//!
+//! Intercepts `LlmResponse` events and records usage with
+//! [`SecurityPolicy::record_llm_usage`]. When a call exhausts
+//! `max_tokens_per_day` or `max_cost_usd_per_day`, the policy stops
+//! permitting actions and this observer reports it once to the operator.
+
+use super::cost::{lookup_model_pricing, DEFAULT_MODEL_PRICING};
+use super::runtime_trace;
+use super::traits::{Observer, ObserverEvent, ObserverMetric};
+use crate::config::schema::ModelPricing;
+use crate::cost::TokenUsage;
+use crate::security::SecurityPolicy;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Observer that records token usage against a [`SecurityPolicy`]'s daily
+/// token and cost budgets.
+pub struct SecurityBudgetObserver {
+    security: Arc<SecurityPolicy>,
+    prices: HashMap<String, ModelPricing>,
+}
+
+impl SecurityBudgetObserver {
+    /// Create a budget observer pricing usage with `prices` (`[cost.prices]`).
+    pub fn new(security: Arc<SecurityPolicy>, prices: HashMap<String, ModelPricing>) -> Self {
+        Self { security, prices }
+    }
+}
+
+impl Observer for SecurityBudgetObserver {
+    fn record_event(&self, event: &ObserverEvent) {
+        let ObserverEvent::LlmResponse {
+            provider,
+            model,
+            success: true,
+            input_tokens,
+            output_tokens,
+            ..
+        } = event
+        else {
+            return;
+        };
+
+        let input = input_tokens.unwrap_or(0);
+        let output = output_tokens.unwrap_or(0);
+        if input == 0 && output == 0 {
+            return;
+        }
+
+        let (input_price, output_price) =
+            lookup_model_pricing(&self.prices, provider, model).unwrap_or(DEFAULT_MODEL_PRICING);
+        let usage = TokenUsage::new(
+            format!("{provider}/{model}"),
+            input,
+            output,
+            input_price,
+            output_price,
+        );
+
+        if let Some(reason) = self
+            .security
+            .record_llm_usage(usage.total_tokens, usage.cost_usd)
+        {
+            tracing::warn!(
+                "Security budget exceeded: {reason}; tools are limited to read-only operations"
+            );
+            runtime_trace::record_event(
+                "security_budget_exceeded",
+                None,
+                Some(provider),
+                Some(model),
+                None,
+                Some(false),
+                Some(&reason),
+                serde_json::json!({
+                    "max_tokens_per_day": self.security.max_tokens_per_day,
+                    "max_cost_usd_per_day": self.security.max_cost_usd_per_day,
+                }),
+            );
+        }
+    }
+
+    fn record_metric(&self, _metric: &ObserverMetric) {
+        // Budget observer doesn't handle metrics
+    }
+
+    fn name(&self) -> &str {
+        "security-budget"
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn response(input_tokens: u64, output_tokens: u64) -> ObserverEvent {
+        ObserverEvent::LlmResponse {
+            provider: "anthropic".into(),
+            model: "claude-sonnet-4".into(),
+            duration: Duration::from_millis(100),
+            success: true,
+            error_message: None,
+            input_tokens: Some(input_tokens),
+            output_tokens: Some(output_tokens),
+        }
+    }
+
+    #[test]
+    fn token_budget_degrades_policy_to_read_only() {
+        let security = Arc::new(SecurityPolicy {
+            max_tokens_per_day: 1_000,
+            ..SecurityPolicy::default()
+        });
+        let observer = SecurityBudgetObserver::new(Arc::clone(&security), HashMap::new());
+
+        observer.record_event(&response(400, 200));
+        assert!(security.can_act());
+
+        observer.record_event(&response(300, 100));
+        assert!(!security.can_act());
+        assert!(security
+            .budget_exceeded()
+            .unwrap()
+            .contains("daily token budget"));
+    }
+
+    #[test]
+    fn cost_budget_uses_configured_prices() {
+        let security = Arc::new(SecurityPolicy {
+            max_cost_usd_per_day: 0.01,
+            ..SecurityPolicy::default()
+        });
+        let prices = HashMap::from([(
+            "anthropic/claude-sonnet-4".to_string(),
+            ModelPricing {
+                input: 10.0,
+                output: 10.0,
+            },
+        )]);
+        let observer = SecurityBudgetObserver::new(Arc::clone(&security), prices);
+
+        // 1200 tokens at $10 per 1M tokens = $0.012.
+        observer.record_event(&response(600, 600));
+        assert!(security
+            .budget_exceeded()
+            .unwrap()
+            .contains("daily cost budget"));
+    }
+
+    #[test]
+    fn failed_responses_are_not_charged() {
+        let security = Arc::new(SecurityPolicy {
+            max_tokens_per_day: 10,
+            ..SecurityPolicy::default()
+        });
+        let observer = SecurityBudgetObserver::new(Arc::clone(&security), HashMap::new());
+
+        observer.record_event(&ObserverEvent::LlmResponse {
+            provider: "anthropic".into(),
+            model: "claude-sonnet-4".into(),
+            duration: Duration::from_millis(100),
+            success: false,
+            error_message: Some("API error".into()),
+            input_tokens: Some(100),
+            output_tokens: Some(100),
+        });
+        assert!(security.can_act());
+    }
+}
@@ -27,43 +27,15 @@ impl CostObserver {
             tracker,
             prices,
             // Conservative defaults for unknown models
-            default_input_price: 3.0,
-            default_output_price: 15.0,
+            default_input_price: DEFAULT_MODEL_PRICING.0,
+            default_output_price: DEFAULT_MODEL_PRICING.1,
         }
     }
 
     /// Look up pricing for a model, trying various name formats.
     fn get_pricing(&self, provider: &str, model: &str) -> (f64, f64) {
-        // Try exact match first: "provider/model"
-        let full_name = format!("{provider}/{model}");
-        if let Some(pricing) = self.prices.get(&full_name) {
-            return (pricing.input, pricing.output);
-        }
-
-        // Try just the model name
-        if let Some(pricing) = self.prices.get(model) {
-            return (pricing.input, pricing.output);
-        }
-
-        // Try model family matching (e.g., "claude-sonnet-4" matches any claude-sonnet-4-*)
-        for (key, pricing) in &self.prices {
-            // Strip provider prefix if present
-            let key_model = key.split('/').next_back().unwrap_or(key);
-
-            // Check if model starts with the key (family match)
-            if model.starts_with(key_model) || key_model.starts_with(model) {
-                return (pricing.input, pricing.output);
-            }
-
-            // Check for common model name patterns
-            // e.g., "claude-3-5-sonnet-20241022" should match "claude-3.5-sonnet"
-            let normalized_model = model.replace('-', ".");
-            let normalized_key = key_model.replace('-', ".");
-            if normalized_model.contains(&normalized_key)
-                || normalized_key.contains(&normalized_model)
-            {
-                return (pricing.input, pricing.output);
-            }
+        if let Some(pricing) = lookup_model_pricing(&self.prices, provider, model) {
+            return pricing;
         }
 
         // Fall back to defaults
@@ -78,6 +50,51 @@ impl CostObserver {
     }
 }
 
+/// Conservative (input, output) USD per 1M tokens for models without an
+/// entry in `[cost.prices]`.
+pub(crate) const DEFAULT_MODEL_PRICING: (f64, f64) = (3.0, 15.0);
+
+/// Find the (input, output) price of `provider`/`model` in `prices`, trying
+/// various name formats.
+pub(crate) fn lookup_model_pricing(
+    prices: &HashMap<String, ModelPricing>,
+    provider: &str,
+    model: &str,
+) -> Option<(f64, f64)> {
+    // Try exact match first: "provider/model"
+    let full_name = format!("{provider}/{model}");
+    if let Some(pricing) = prices.get(&full_name) {
+        return Some((pricing.input, pricing.output));
+    }
+
+    // Try just the model name
+    if let Some(pricing) = prices.get(model) {
+        return Some((pricing.input, pricing.output));
+    }
+
+    // Try model family matching (e.g., "claude-sonnet-4" matches any claude-sonnet-4-*)
+    for (key, pricing) in prices {
+        // Strip provider prefix if present
+        let key_model = key.split('/').next_back().unwrap_or(key);
+
+        // Check if model starts with the key (family match)
+        if model.starts_with(key_model) || key_model.starts_with(model) {
+            return Some((pricing.input, pricing.output));
+        }
+
+        // Check for common model name patterns
+        // e.g., "claude-3-5-sonnet-20241022" should match "claude-3.5-sonnet"
+        let normalized_model = model.replace('-', ".");
+        let normalized_key = key_model.replace('-', ".");
+        if normalized_model.contains(&normalized_key) || normalized_key.contains(&normalized_model)
+        {
+            return Some((pricing.input, pricing.output));
+        }
+    }
+
+    None
+}
+
 impl Observer for CostObserver {
     fn record_event(&self, event: &ObserverEvent) {
         if let ObserverEvent::LlmResponse {
@@ -1,3 +1,4 @@
+pub mod budget;
 pub mod cost;
 pub mod log;
 pub mod multi;
@@ -13,6 +14,7 @@ pub mod verbose;
 pub use self::log::LogObserver;
 #[allow(unused_imports)]
 pub use self::multi::MultiObserver;
+pub use budget::SecurityBudgetObserver;
 pub use cost::CostObserver;
 pub use noop::NoopObserver;
 #[cfg(feature = "observability-otel")]
@@ -25,6 +27,7 @@ pub use verbose::VerboseObserver;
 use crate::config::schema::CostConfig;
 use crate::config::ObservabilityConfig;
 use crate::cost::CostTracker;
+use crate::security::SecurityPolicy;
 use std::sync::Arc;
 
 /// Factory: create the right observer from config
@@ -55,6 +58,26 @@ pub fn create_observer_with_cost_tracking(
     }
 }
 
+/// Add a [`SecurityBudgetObserver`] to `observer` when `security` sets a
+/// daily token or cost budget, so provider usage priced with `[cost.prices]`
+/// is charged against it.
+pub fn with_security_budget(
+    observer: Box<dyn Observer>,
+    security: &Arc<SecurityPolicy>,
+    cost_config: &CostConfig,
+) -> Box<dyn Observer> {
+    if security.max_tokens_per_day == 0 && security.max_cost_usd_per_day <= 0.0 {
+        return observer;
+    }
+    Box::new(MultiObserver::new(vec![
+        observer,
+        Box::new(SecurityBudgetObserver::new(
+            Arc::clone(security),
+            cost_config.prices.clone(),
+        )),
+    ]))
+}
+
 fn create_observer_internal(config: &ObservabilityConfig) -> Box<dyn Observer> {
     match config.backend.as_str() {
         "log" => Box::new(LogObserver::new()),
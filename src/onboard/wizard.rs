@@ -239,6 +239,7 @@ pub async fn run_wizard_with_migration(
     let mut config = Config {
         workspace_dir: workspace_dir.clone(),
         config_path: config_path.clone(),
+        secret_refs: std::collections::HashMap::new(),
         api_key: if api_key.is_empty() {
             None
         } else {
@@ -278,6 +279,7 @@ pub async fn run_wizard_with_migration(
         multimodal: crate::config::MultimodalConfig::default(),
         web_fetch: web_fetch_config,
         web_search: web_search_config,
+        home_assistant: crate::config::HomeAssistantConfig::default(),
         proxy: crate::config::ProxyConfig::default(),
         identity: identity_config,
         cost: crate::config::CostConfig::default(),
@@ -515,6 +517,14 @@ fn memory_config_defaults_for_backend(backend: &str) -> MemoryConfig {
         auto_hydrate: true,
         sqlite_open_timeout_secs: None,
         sqlite_journal_mode: "wal".to_string(),
+        markdown_rotate_bytes: 0,
+        encrypt_at_rest: false,
+        encryption_key_file: None,
+        encryption_passphrase_env: None,
+        tag_suggestions: Vec::new(),
+        search_ranking: crate::config::MemorySearchRankingConfig::default(),
+        git_sync: crate::config::MemoryGitSyncConfig::default(),
+        digest: crate::config::MemoryDigestConfig::default(),
         qdrant: crate::config::QdrantConfig::default(),
     }
 }
@@ -755,6 +765,7 @@ async fn run_quick_setup_with_home(
     let mut config = Config {
         workspace_dir: workspace_dir.clone(),
         config_path: config_path.clone(),
+        secret_refs: std::collections::HashMap::new(),
         api_key: credential_override.map(|c| {
             let mut s = String::with_capacity(c.len());
             s.push_str(c);
@@ -794,6 +805,7 @@ async fn run_quick_setup_with_home(
         multimodal: crate::config::MultimodalConfig::default(),
         web_fetch: crate::config::WebFetchConfig::default(),
         web_search: crate::config::WebSearchConfig::default(),
+        home_assistant: crate::config::HomeAssistantConfig::default(),
         proxy: crate::config::ProxyConfig::default(),
         identity: crate::config::IdentityConfig::default(),
         cost: crate::config::CostConfig::default(),
@@ -3951,7 +3963,10 @@ fn setup_tool_mode() -> Result<(ComposioConfig, SecretsConfig)> {
         .default(true)
         .interact()?;
 
-    let secrets_config = SecretsConfig { encrypt };
+    let secrets_config = SecretsConfig {
+        encrypt,
+        ..SecretsConfig::default()
+    };
 
     if encrypt {
         println!(
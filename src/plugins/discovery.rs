@@ -5,6 +5,7 @@
 
 use std::path::{Path, PathBuf};
 
+use super::handshake::check_handshake;
 use super::manifest::{
     load_manifest, ManifestLoadResult, PluginManifest, PLUGIN_MANIFEST_FILENAME,
 };
@@ -53,7 +54,21 @@ fn scan_dir(dir: &Path, origin: PluginOrigin) -> (Vec<DiscoveredPlugin>, Vec<Plu
         }
 
         match load_manifest(&path) {
-            ManifestLoadResult::Ok { manifest, .. } => {
+            ManifestLoadResult::Ok { manifest, path: mp } => {
+                if let Err(error) = check_handshake(
+                    &manifest.id,
+                    manifest.kind(),
+                    manifest.api_version,
+                    &manifest.capabilities,
+                ) {
+                    diagnostics.push(PluginDiagnostic {
+                        level: DiagnosticLevel::Error,
+                        plugin_id: Some(manifest.id),
+                        source: Some(mp.display().to_string()),
+                        message: error.to_string(),
+                    });
+                    continue;
+                }
                 plugins.push(DiscoveredPlugin {
                     manifest,
                     dir: path,
@@ -206,6 +221,28 @@ version = "0.1.0"
         assert!(ids.contains("custom-two"));
     }
 
+    #[test]
+    fn discover_rejects_incompatible_api_version_with_diagnostic() {
+        let tmp = tempfile::tempdir().unwrap();
+        let ext_dir = tmp.path().join("ext");
+        let dir = ext_dir.join("future");
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(
+            dir.join(PLUGIN_MANIFEST_FILENAME),
+            "id = \"future\"\napi_version = 2\n",
+        )
+        .unwrap();
+        make_plugin_dir(&ext_dir, "current");
+
+        let (plugins, diagnostics) = super::scan_dir(&ext_dir, PluginOrigin::Workspace);
+        assert_eq!(plugins.len(), 1);
+        assert_eq!(plugins[0].manifest.id, "current");
+        assert_eq!(diagnostics.len(), 1);
+        assert!(matches!(diagnostics[0].level, DiagnosticLevel::Error));
+        assert_eq!(diagnostics[0].plugin_id.as_deref(), Some("future"));
+        assert!(diagnostics[0].message.contains("plugin API v2"));
+    }
+
     #[test]
     fn discover_skips_hidden_dirs() {
         let tmp = tempfile::tempdir().unwrap();
@@ -0,0 +1,199 @@
+//! Plugin API handshake — interface version and capability checks.
+//!
+//! Every plugin declares the host interface version it was built against
+//! (`api_version`) and the capabilities it needs. The host checks both at load
+//! time, before any plugin code runs, so a plugin built for a different
+//! interface is rejected with an actionable error instead of failing later on
+//! a missing export or a payload it cannot parse.
+//!
+//! Manifests that predate `api_version` are treated as version 1, the
+//! interface they were written against. Capabilities a plugin kind cannot use
+//! are only rejected once a manifest declares `api_version`; older manifests
+//! load with a warning and the unusable capabilities are ignored.
+
+use super::traits::PluginCapability;
+
+/// Plugin interface version implemented by this host.
+pub const PLUGIN_API_VERSION: u32 = 1;
+
+/// Oldest plugin interface version this host still loads.
+pub const MIN_PLUGIN_API_VERSION: u32 = 1;
+
+/// How a plugin's code is executed, which bounds what it may declare.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PluginKind {
+    /// WASM module run inside the host's sandbox.
+    Wasm,
+    /// External process speaking the JSON tool protocol over stdio.
+    Process,
+}
+
+impl PluginKind {
+    /// Capabilities the host can honor for this kind of plugin.
+    ///
+    /// Hooks run inline in the agent loop, so only in-host (WASM) plugins may
+    /// register them.
+    pub fn supported_capabilities(self) -> &'static [PluginCapability] {
+        match self {
+            Self::Wasm => &[
+                PluginCapability::Hooks,
+                PluginCapability::Tools,
+                PluginCapability::Providers,
+                PluginCapability::ModifyToolResults,
+            ],
+            Self::Process => &[PluginCapability::Tools, PluginCapability::Providers],
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            Self::Wasm => "WASM",
+            Self::Process => "external-process",
+        }
+    }
+}
+
+impl std::fmt::Display for PluginKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.label())
+    }
+}
+
+/// Reason a plugin failed the load-time handshake.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum HandshakeError {
+    #[error(
+        "plugin '{plugin_id}' targets plugin API v{requested}, but this host supports \
+         v{MIN_PLUGIN_API_VERSION}..=v{PLUGIN_API_VERSION}; upgrade zeroclaw or use a plugin \
+         build for v{PLUGIN_API_VERSION}"
+    )]
+    ApiVersionTooNew { plugin_id: String, requested: u32 },
+    #[error(
+        "plugin '{plugin_id}' targets plugin API v{requested}, which this host no longer \
+         supports (minimum v{MIN_PLUGIN_API_VERSION}); rebuild the plugin against \
+         v{PLUGIN_API_VERSION}"
+    )]
+    ApiVersionTooOld { plugin_id: String, requested: u32 },
+    #[error(
+        "plugin '{plugin_id}' declares capability '{capability:?}', which {kind} plugins \
+         cannot use (supported: {supported})"
+    )]
+    UnsupportedCapability {
+        plugin_id: String,
+        capability: PluginCapability,
+        kind: PluginKind,
+        supported: String,
+    },
+}
+
+/// Check a plugin's declared interface version and capabilities.
+///
+/// Returns the negotiated API version on success. `api_version: None` means
+/// the manifest predates versioning: it is treated as version 1, and
+/// unsupported capabilities are logged instead of rejected so manifests that
+/// loaded before the handshake existed keep loading.
+pub fn check_handshake(
+    plugin_id: &str,
+    kind: PluginKind,
+    api_version: Option<u32>,
+    capabilities: &[PluginCapability],
+) -> Result<u32, HandshakeError> {
+    let requested = api_version.unwrap_or(1);
+    if requested > PLUGIN_API_VERSION {
+        return Err(HandshakeError::ApiVersionTooNew {
+            plugin_id: plugin_id.to_string(),
+            requested,
+        });
+    }
+    if requested < MIN_PLUGIN_API_VERSION {
+        return Err(HandshakeError::ApiVersionTooOld {
+            plugin_id: plugin_id.to_string(),
+            requested,
+        });
+    }
+
+    let supported = kind.supported_capabilities();
+    if let Some(capability) = capabilities.iter().find(|c| !supported.contains(c)) {
+        let error = HandshakeError::UnsupportedCapability {
+            plugin_id: plugin_id.to_string(),
+            capability: capability.clone(),
+            kind,
+            supported: supported
+                .iter()
+                .map(|c| format!("{c:?}"))
+                .collect::<Vec<_>>()
+                .join(", "),
+        };
+        if api_version.is_some() {
+            return Err(error);
+        }
+        tracing::warn!(
+            "{error}; the capability is ignored. Declare api_version = {PLUGIN_API_VERSION} \
+             and drop it to silence this warning"
+        );
+    }
+    Ok(requested)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn missing_version_is_treated_as_v1() {
+        let negotiated =
+            check_handshake("legacy", PluginKind::Wasm, None, &[PluginCapability::Tools]).unwrap();
+        assert_eq!(negotiated, 1);
+    }
+
+    #[test]
+    fn newer_api_version_is_rejected_with_upgrade_hint() {
+        let err = check_handshake(
+            "future",
+            PluginKind::Wasm,
+            Some(PLUGIN_API_VERSION + 1),
+            &[],
+        )
+        .unwrap_err();
+        assert!(matches!(err, HandshakeError::ApiVersionTooNew { .. }));
+        let message = err.to_string();
+        assert!(message.contains("'future'"));
+        assert!(message.contains("upgrade zeroclaw"));
+    }
+
+    #[test]
+    fn older_api_version_is_rejected() {
+        let err = check_handshake("ancient", PluginKind::Process, Some(0), &[]).unwrap_err();
+        assert!(matches!(
+            err,
+            HandshakeError::ApiVersionTooOld { requested: 0, .. }
+        ));
+    }
+
+    #[test]
+    fn process_plugins_cannot_declare_hooks() {
+        let err = check_handshake(
+            "proc",
+            PluginKind::Process,
+            Some(1),
+            &[PluginCapability::Tools, PluginCapability::Hooks],
+        )
+        .unwrap_err();
+        let message = err.to_string();
+        assert!(message.contains("'Hooks'"));
+        assert!(message.contains("external-process"));
+        assert!(message.contains("Tools, Providers"));
+    }
+
+    #[test]
+    fn unversioned_process_plugins_with_hooks_still_load() {
+        let negotiated = check_handshake(
+            "legacy-proc",
+            PluginKind::Process,
+            None,
+            &[PluginCapability::Tools, PluginCapability::Hooks],
+        )
+        .unwrap();
+        assert_eq!(negotiated, 1);
+    }
+}
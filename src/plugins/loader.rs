@@ -288,6 +288,7 @@ mod tests {
             id: id.into(),
             name: Some(id.into()),
             version: Some("0.1.0".into()),
+            api_version: None,
             description: None,
             config_schema: None,
             capabilities: vec![],
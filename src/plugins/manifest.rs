@@ -9,6 +9,7 @@ use std::collections::HashSet;
 use std::fs;
 use std::path::Path;
 
+use super::handshake::{check_handshake, PluginKind};
 use super::traits::PluginCapability;
 
 const SUPPORTED_WIT_MAJOR: u64 = 1;
@@ -55,6 +56,11 @@ pub struct PluginManifest {
     pub description: Option<String>,
     /// SemVer version string.
     pub version: Option<String>,
+    /// Plugin API version the plugin was built against (see
+    /// [`PLUGIN_API_VERSION`](super::handshake::PLUGIN_API_VERSION)).
+    /// Omitted means version 1.
+    #[serde(default)]
+    pub api_version: Option<u32>,
     /// Optional JSON-Schema-style config descriptor (stored as TOML table).
     pub config_schema: Option<toml::Value>,
     /// Declared capability set for this plugin.
@@ -167,6 +173,12 @@ pub fn validate_manifest_with_profile(
     {
         anyhow::bail!("plugin module_path cannot be empty");
     }
+    check_handshake(
+        &manifest.id,
+        manifest.kind(),
+        manifest.api_version,
+        &manifest.capabilities,
+    )?;
     let mut declared_wit_packages = HashSet::new();
     for wit_pkg in &manifest.wit_packages {
         let (package, major) = parse_wit_package_version(wit_pkg)?;
@@ -229,6 +241,19 @@ impl PluginManifest {
     pub fn is_valid(&self) -> bool {
         validate_manifest(self).is_ok()
     }
+
+    /// How this plugin runs: a `.wasm` module, or an external process for any
+    /// other (or no) module path.
+    pub fn kind(&self) -> PluginKind {
+        let is_wasm = Path::new(self.module_path.trim())
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("wasm"));
+        if is_wasm {
+            PluginKind::Wasm
+        } else {
+            PluginKind::Process
+        }
+    }
 }
 
 #[cfg(test)]
@@ -320,6 +345,7 @@ id = "  "
             name: Some("Demo".into()),
             description: None,
             version: Some("1.0.0".into()),
+            api_version: None,
             config_schema: None,
             capabilities: vec![],
             module_path: "plugins/demo.wasm".into(),
@@ -330,6 +356,18 @@ id = "  "
         assert!(valid.is_valid());
     }
 
+    #[test]
+    fn manifest_rejects_unsupported_api_version() {
+        let manifest = PluginManifest {
+            id: "demo".into(),
+            api_version: Some(super::super::handshake::PLUGIN_API_VERSION + 1),
+            module_path: "plugins/demo.wasm".into(),
+            ..PluginManifest::default()
+        };
+        let err = validate_manifest(&manifest).unwrap_err().to_string();
+        assert!(err.contains("plugin 'demo' targets plugin API v2"));
+    }
+
     #[test]
     fn manifest_rejects_unknown_wit_package() {
         let manifest = PluginManifest {
@@ -337,6 +375,7 @@ id = "  "
             name: None,
             description: None,
             version: Some("1.0.0".into()),
+            api_version: None,
             config_schema: None,
             capabilities: vec![],
             module_path: "plugins/demo.wasm".into(),
@@ -354,6 +393,7 @@ id = "  "
             name: None,
             description: None,
             version: Some("1.0.0".into()),
+            api_version: None,
             config_schema: None,
             capabilities: vec![],
             module_path: "   ".into(),
@@ -371,6 +411,7 @@ id = "  "
             name: None,
             description: None,
             version: Some("1.0.0".into()),
+            api_version: None,
             config_schema: None,
             capabilities: vec![],
             module_path: "   ".into(),
@@ -391,6 +432,7 @@ id = "  "
             name: None,
             description: None,
             version: Some("1.0.0".into()),
+            api_version: None,
             config_schema: None,
             capabilities: vec![PluginCapability::Tools],
             module_path: "plugins/demo.wasm".into(),
@@ -408,6 +450,7 @@ id = "  "
             name: None,
             description: None,
             version: Some("1.0.0".into()),
+            api_version: None,
             config_schema: None,
             capabilities: vec![PluginCapability::ModifyToolResults],
             module_path: "plugins/demo.wasm".into(),
@@ -425,6 +468,7 @@ id = "  "
             name: None,
             description: None,
             version: Some("1.0.0".into()),
+            api_version: None,
             config_schema: None,
             capabilities: vec![],
             module_path: "plugins/demo.wasm".into(),
@@ -449,6 +493,7 @@ id = "  "
             name: None,
             description: None,
             version: Some("1.0.0".into()),
+            api_version: None,
             config_schema: None,
             capabilities: vec![],
             module_path: "plugins/demo.wasm".into(),
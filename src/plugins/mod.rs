@@ -3,6 +3,8 @@
 //! Modeled after OpenClaw's plugin architecture, adapted for Rust:
 //!
 //! - **Manifest**: each plugin has a `zeroclaw.plugin.toml` descriptor
+//! - **Handshake**: plugins declare the API version and capabilities they
+//!   need; incompatible plugins are rejected at load time
 //! - **Discovery**: scans bundled, global (`~/.zeroclaw/extensions/`), and
 //!   workspace (`.zeroclaw/extensions/`) directories
 //! - **Registry**: collects loaded plugins, their tools, hooks, and diagnostics
@@ -39,6 +41,7 @@
 
 pub mod bridge;
 pub mod discovery;
+pub mod handshake;
 pub mod loader;
 pub mod manifest;
 pub mod registry;
@@ -48,6 +51,8 @@ pub mod traits;
 #[allow(unused_imports)]
 pub use discovery::discover_plugins;
 #[allow(unused_imports)]
+pub use handshake::{check_handshake, HandshakeError, PluginKind, PLUGIN_API_VERSION};
+#[allow(unused_imports)]
 pub use loader::load_plugins;
 #[allow(unused_imports)]
 pub use manifest::{PluginManifest, PLUGIN_MANIFEST_FILENAME};
@@ -70,6 +75,7 @@ mod tests {
             name: None,
             description: None,
             version: None,
+            api_version: None,
             config_schema: None,
             capabilities: vec![],
             module_path: String::new(),
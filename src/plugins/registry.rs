@@ -212,6 +212,7 @@ mod tests {
             name: None,
             description: None,
             version: Some("1.0.0".to_string()),
+            api_version: None,
             config_schema: None,
             capabilities: Vec::new(),
             module_path: "plugins/demo.wasm".to_string(),
@@ -9,7 +9,7 @@ use tokio::sync::Semaphore;
 use tokio::time::{timeout, Duration};
 use wasmtime::{Engine, Extern, Instance, Memory, Module, Store, TypedFunc};
 
-use super::manifest::PluginManifest;
+use super::manifest::{validate_manifest, PluginManifest};
 use super::registry::PluginRegistry;
 use crate::config::PluginsConfig;
 use crate::tools::ToolResult;
@@ -36,9 +36,8 @@ impl PluginRuntime {
     }
 
     pub fn load_manifest(&self, manifest: PluginManifest) -> Result<PluginManifest> {
-        if !manifest.is_valid() {
-            anyhow::bail!("invalid plugin manifest")
-        }
+        validate_manifest(&manifest)
+            .with_context(|| format!("invalid plugin manifest '{}'", manifest.id))?;
         Ok(manifest)
     }
 
@@ -518,6 +517,34 @@ description = "{tool} description"
         assert!(reg.provider_module_path("demo-provider").is_some());
     }
 
+    #[test]
+    fn runtime_rejects_plugin_built_for_newer_api() {
+        let dir = TempDir::new().expect("temp dir");
+        std::fs::write(
+            dir.path().join("future.plugin.toml"),
+            r#"
+id = "future"
+api_version = 99
+module_path = "plugins/future.wasm"
+wit_packages = ["zeroclaw:tools@1.0.0"]
+capabilities = ["Tools"]
+"#,
+        )
+        .expect("write manifest");
+
+        let cfg = PluginsConfig {
+            enabled: true,
+            load_paths: vec![dir.path().to_string_lossy().to_string()],
+            ..PluginsConfig::default()
+        };
+        let Err(err) = PluginRuntime::new().load_registry_from_config(&cfg) else {
+            panic!("incompatible plugin should be rejected");
+        };
+        let message = format!("{err:#}");
+        assert!(message.contains("invalid plugin manifest 'future'"));
+        assert!(message.contains("plugin API v99"));
+    }
+
     #[test]
     fn unpack_ptr_len_roundtrip() {
         let ptr: u32 = 0x1234_5678;
@@ -130,6 +130,7 @@ mod tests {
                 name: None,
                 description: None,
                 version: None,
+                api_version: None,
                 config_schema: None,
                 capabilities: vec![],
                 module_path: String::new(),
@@ -0,0 +1,261 @@
+//! A provider built the first time it is used.
+//!
+//! Fallback and secondary route providers often go unused for a whole
+//! session, so [`create_resilient_provider_with_options`](super::create_resilient_provider_with_options)
+//! and [`create_routed_provider_with_options`](super::create_routed_provider_with_options)
+//! wrap them in [`LazyProvider`] instead of resolving credentials and
+//! building HTTP clients for every one of them at startup.
+
+use super::traits::{
+    ChatMessage, ChatRequest, ChatResponse, ProviderCapabilities, StreamChunk, StreamOptions,
+    StreamResult, ToolsPayload,
+};
+use super::Provider;
+use crate::tools::ToolSpec;
+use async_trait::async_trait;
+use futures_util::{stream, StreamExt};
+use std::sync::{Mutex, OnceLock};
+
+type Init = Box<dyn FnOnce() -> anyhow::Result<Box<dyn Provider>> + Send>;
+
+/// Returned by every call on a [`LazyProvider`] whose provider failed to build.
+///
+/// [`ReliableProvider`](super::reliable::ReliableProvider) treats it as
+/// non-retryable and moves straight on to the next provider.
+#[derive(Debug, thiserror::Error)]
+#[error("provider `{name}` failed to initialize: {reason}")]
+pub struct ProviderInitError {
+    pub name: String,
+    pub reason: String,
+}
+
+pub struct LazyProvider {
+    name: String,
+    init: Mutex<Option<Init>>,
+    inner: OnceLock<Result<Box<dyn Provider>, String>>,
+}
+
+impl LazyProvider {
+    pub fn new(
+        name: impl Into<String>,
+        init: impl FnOnce() -> anyhow::Result<Box<dyn Provider>> + Send + 'static,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            init: Mutex::new(Some(Box::new(init))),
+            inner: OnceLock::new(),
+        }
+    }
+
+    /// Whether the provider has been built (successfully or not).
+    pub fn is_initialized(&self) -> bool {
+        self.inner.get().is_some()
+    }
+
+    fn get(&self) -> Result<&dyn Provider, ProviderInitError> {
+        let inner = self.inner.get_or_init(|| {
+            let init = self
+                .init
+                .lock()
+                .unwrap_or_else(std::sync::PoisonError::into_inner)
+                .take();
+            let Some(init) = init else {
+                return Err("initializer already ran".to_string());
+            };
+            init().map_err(|error| {
+                tracing::warn!(
+                    provider = self.name.as_str(),
+                    "Provider failed to initialize on first use: {error}"
+                );
+                error.to_string()
+            })
+        });
+        match inner {
+            Ok(provider) => Ok(provider.as_ref()),
+            Err(reason) => Err(ProviderInitError {
+                name: self.name.clone(),
+                reason: reason.clone(),
+            }),
+        }
+    }
+}
+
+#[async_trait]
+impl Provider for LazyProvider {
+    fn capabilities(&self) -> ProviderCapabilities {
+        self.get()
+            .map(|provider| provider.capabilities())
+            .unwrap_or_default()
+    }
+
+    fn convert_tools(&self, tools: &[ToolSpec]) -> ToolsPayload {
+        match self.get() {
+            Ok(provider) => provider.convert_tools(tools),
+            Err(_) => ToolsPayload::PromptGuided {
+                instructions: super::traits::build_tool_instructions_text(tools),
+            },
+        }
+    }
+
+    async fn simple_chat(
+        &self,
+        message: &str,
+        model: &str,
+        temperature: f64,
+    ) -> anyhow::Result<String> {
+        self.get()?.simple_chat(message, model, temperature).await
+    }
+
+    async fn chat_with_system(
+        &self,
+        system_prompt: Option<&str>,
+        message: &str,
+        model: &str,
+        temperature: f64,
+    ) -> anyhow::Result<String> {
+        self.get()?
+            .chat_with_system(system_prompt, message, model, temperature)
+            .await
+    }
+
+    async fn chat_with_history(
+        &self,
+        messages: &[ChatMessage],
+        model: &str,
+        temperature: f64,
+    ) -> anyhow::Result<String> {
+        self.get()?
+            .chat_with_history(messages, model, temperature)
+            .await
+    }
+
+    async fn chat(
+        &self,
+        request: ChatRequest<'_>,
+        model: &str,
+        temperature: f64,
+    ) -> anyhow::Result<ChatResponse> {
+        self.get()?.chat(request, model, temperature).await
+    }
+
+    fn supports_native_tools(&self) -> bool {
+        self.get()
+            .map(|provider| provider.supports_native_tools())
+            .unwrap_or(false)
+    }
+
+    fn supports_vision(&self) -> bool {
+        self.get()
+            .map(|provider| provider.supports_vision())
+            .unwrap_or(false)
+    }
+
+    async fn warmup(&self) -> anyhow::Result<()> {
+        self.get()?.warmup().await
+    }
+
+    async fn chat_with_tools(
+        &self,
+        messages: &[ChatMessage],
+        tools: &[serde_json::Value],
+        model: &str,
+        temperature: f64,
+    ) -> anyhow::Result<ChatResponse> {
+        self.get()?
+            .chat_with_tools(messages, tools, model, temperature)
+            .await
+    }
+
+    fn supports_streaming(&self) -> bool {
+        self.get()
+            .map(|provider| provider.supports_streaming())
+            .unwrap_or(false)
+    }
+
+    fn stream_chat_with_system(
+        &self,
+        system_prompt: Option<&str>,
+        message: &str,
+        model: &str,
+        temperature: f64,
+        options: StreamOptions,
+    ) -> stream::BoxStream<'static, StreamResult<StreamChunk>> {
+        match self.get() {
+            Ok(provider) => provider.stream_chat_with_system(
+                system_prompt,
+                message,
+                model,
+                temperature,
+                options,
+            ),
+            Err(_) => stream::empty().boxed(),
+        }
+    }
+
+    fn stream_chat_with_history(
+        &self,
+        messages: &[ChatMessage],
+        model: &str,
+        temperature: f64,
+        options: StreamOptions,
+    ) -> stream::BoxStream<'static, StreamResult<StreamChunk>> {
+        match self.get() {
+            Ok(provider) => {
+                provider.stream_chat_with_history(messages, model, temperature, options)
+            }
+            Err(_) => stream::empty().boxed(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    struct Echo;
+
+    #[async_trait]
+    impl Provider for Echo {
+        async fn chat_with_system(
+            &self,
+            _system_prompt: Option<&str>,
+            message: &str,
+            _model: &str,
+            _temperature: f64,
+        ) -> anyhow::Result<String> {
+            Ok(message.to_string())
+        }
+    }
+
+    #[tokio::test]
+    async fn builds_once_on_first_use() {
+        let builds = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&builds);
+        let provider = LazyProvider::new("echo", move || {
+            counter.fetch_add(1, Ordering::SeqCst);
+            Ok(Box::new(Echo) as Box<dyn Provider>)
+        });
+        assert!(!provider.is_initialized());
+        assert_eq!(builds.load(Ordering::SeqCst), 0);
+
+        assert_eq!(provider.simple_chat("hi", "m", 0.0).await.unwrap(), "hi");
+        assert_eq!(
+            provider.simple_chat("again", "m", 0.0).await.unwrap(),
+            "again"
+        );
+        assert_eq!(builds.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn init_failure_surfaces_on_every_call() {
+        let provider = LazyProvider::new("broken", || anyhow::bail!("no credentials"));
+        for _ in 0..2 {
+            let error = provider.simple_chat("hi", "m", 0.0).await.unwrap_err();
+            assert!(error.downcast_ref::<ProviderInitError>().is_some());
+            assert!(error.to_string().contains("no credentials"));
+        }
+        assert!(!provider.supports_native_tools());
+    }
+}
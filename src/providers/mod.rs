@@ -9,7 +9,9 @@
 //! The subsystem supports resilient multi-provider configurations through the
 //! [`ReliableProvider`](reliable::ReliableProvider) wrapper, which handles fallback
 //! chains and automatic retry. Model routing across providers is available via
-//! [`create_routed_provider`].
+//! [`create_routed_provider`]. Fallback providers and routes other than the
+//! default are wrapped in [`LazyProvider`](lazy::LazyProvider) and only built
+//! when a request first reaches them.
 //!
 //! # Extension
 //!
@@ -24,6 +26,7 @@ pub mod copilot;
 pub mod cursor;
 pub mod gemini;
 pub mod health;
+pub mod lazy;
 pub mod ollama;
 pub mod openai;
 pub mod openai_codex;
@@ -1689,15 +1692,21 @@ pub fn create_resilient_provider_with_options(
             None => options.clone(),
         };
 
-        match create_provider_with_options(provider_name, fallback_api_key, &fallback_options) {
-            Ok(provider) => providers.push((fallback.clone(), provider)),
-            Err(_error) => {
-                tracing::warn!(
-                    fallback_provider = provider_name,
-                    "Ignoring invalid fallback provider during initialization"
-                );
-            }
-        }
+        // Built on first use: most sessions never fall back, so there is no
+        // reason to resolve credentials or build clients for these up front.
+        // A fallback that fails to build is skipped when it is reached.
+        let provider_name = provider_name.to_string();
+        let fallback_api_key = fallback_api_key.map(str::to_string);
+        providers.push((
+            fallback.clone(),
+            Box::new(lazy::LazyProvider::new(fallback.clone(), move || {
+                create_provider_with_options(
+                    &provider_name,
+                    fallback_api_key.as_deref(),
+                    &fallback_options,
+                )
+            })),
+        ));
     }
 
     let reliable = ReliableProvider::new(
@@ -1820,13 +1829,37 @@ pub fn create_routed_provider_with_options(
             route_options.provider_transport = Some(transport.to_string());
         }
 
-        match create_resilient_provider_with_options(
-            &route.provider,
-            key,
-            url,
-            reliability,
-            &route_options,
-        ) {
+        // Only the route the default model resolves to is needed for the
+        // first request; the rest are built when a request first asks for
+        // their hint, and a route that fails to build fails that request.
+        let built = if default_hint == Some(route_hint) {
+            create_resilient_provider_with_options(
+                &route.provider,
+                key,
+                url,
+                reliability,
+                &route_options,
+            )
+        } else {
+            let provider = route.provider.clone();
+            let key = key.map(str::to_string);
+            let url = url.map(str::to_string);
+            let reliability = reliability.clone();
+            Ok(Box::new(lazy::LazyProvider::new(
+                format!("{}#{}", route.provider, route_hint),
+                move || {
+                    create_resilient_provider_with_options(
+                        &provider,
+                        key.as_deref(),
+                        url.as_deref(),
+                        &reliability,
+                        &route_options,
+                    )
+                },
+            )) as Box<dyn Provider>)
+        };
+
+        match built {
             Ok(provider) => {
                 let provider_id = format!("{}#{}", route.provider, route_hint);
                 providers.push((provider_id.clone(), provider));
@@ -3760,6 +3793,36 @@ providers = ["demo-plugin-provider"]
             .contains("default_model uses hint 'reasoning'"));
     }
 
+    #[tokio::test]
+    async fn routed_provider_builds_secondary_routes_on_first_use() {
+        let reliability = crate::config::ReliabilityConfig::default();
+        let routes = vec![crate::config::ModelRouteConfig {
+            hint: "fast".to_string(),
+            provider: "provider-that-does-not-exist".to_string(),
+            model: "tiny".to_string(),
+            max_tokens: None,
+            api_key: None,
+            transport: None,
+        }];
+
+        let provider = create_routed_provider_with_options(
+            "lmstudio",
+            None,
+            None,
+            &reliability,
+            &routes,
+            "qwen2.5-coder",
+            &ProviderRuntimeOptions::default(),
+        )
+        .expect("an unknown secondary route is not built at startup");
+
+        let err = provider
+            .simple_chat("hi", "hint:fast", 0.0)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("failed to initialize"), "{err}");
+    }
+
     // --- parse_provider_profile ---
 
     #[test]
@@ -20,6 +20,15 @@ fn is_non_retryable(err: &anyhow::Error) -> bool {
         return true;
     }
 
+    // A lazily built provider that failed to build will fail the same way
+    // on every retry.
+    if err
+        .downcast_ref::<super::lazy::ProviderInitError>()
+        .is_some()
+    {
+        return true;
+    }
+
     let msg = err.to_string();
     let msg_lower = msg.to_lowercase();
 
@@ -0,0 +1,102 @@
+//! Tokio executor construction from `[runtime.tokio]`.
+//!
+//! The main runtime is built by the binary before any async code runs. An
+//! optional second runtime isolates filesystem-heavy tool work (document
+//! extraction, large parses) so it cannot starve the main blocking pool that
+//! channels and providers depend on.
+
+use crate::config::TokioRuntimeConfig;
+use std::sync::OnceLock;
+use tokio::runtime::{Builder, Runtime};
+use tokio::task::JoinHandle;
+
+static FS_RUNTIME: OnceLock<Runtime> = OnceLock::new();
+
+/// Build the main multi-threaded runtime with the configured thread limits.
+///
+/// Zero values are treated as unset, since Tokio rejects zero-sized pools.
+pub fn build_main_runtime(config: &TokioRuntimeConfig) -> std::io::Result<Runtime> {
+    let mut builder = Builder::new_multi_thread();
+    builder.enable_all().thread_name("zeroclaw-worker");
+    if let Some(threads) = config.worker_threads.filter(|n| *n > 0) {
+        builder.worker_threads(threads);
+    }
+    if let Some(threads) = config.max_blocking_threads.filter(|n| *n > 0) {
+        builder.max_blocking_threads(threads);
+    }
+    builder.build()
+}
+
+/// Start the dedicated filesystem runtime when `fs_worker_threads > 0`.
+///
+/// Idempotent: only the first successful call creates the runtime.
+pub fn init_fs_runtime(config: &TokioRuntimeConfig) -> std::io::Result<()> {
+    if config.fs_worker_threads == 0 || FS_RUNTIME.get().is_some() {
+        return Ok(());
+    }
+    let mut builder = Builder::new_multi_thread();
+    builder
+        .enable_all()
+        .thread_name("zeroclaw-fs")
+        .worker_threads(config.fs_worker_threads);
+    if let Some(threads) = config.fs_max_blocking_threads.filter(|n| *n > 0) {
+        builder.max_blocking_threads(threads);
+    }
+    let runtime = builder.build()?;
+    // A concurrent initializer may have won; dropping the loser here is fine
+    // because it is outside any async context and has no tasks.
+    let _ = FS_RUNTIME.set(runtime);
+    Ok(())
+}
+
+/// Whether a dedicated filesystem runtime is active.
+pub fn has_fs_runtime() -> bool {
+    FS_RUNTIME.get().is_some()
+}
+
+/// Run blocking filesystem-heavy work off the async executor.
+///
+/// Uses the dedicated filesystem runtime's blocking pool when configured and
+/// falls back to [`tokio::task::spawn_blocking`] otherwise.
+pub fn spawn_fs_blocking<F, R>(f: F) -> JoinHandle<R>
+where
+    F: FnOnce() -> R + Send + 'static,
+    R: Send + 'static,
+{
+    match FS_RUNTIME.get() {
+        Some(runtime) => runtime.spawn_blocking(f),
+        None => tokio::task::spawn_blocking(f),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn main_runtime_honors_worker_threads() {
+        let config = TokioRuntimeConfig {
+            worker_threads: Some(2),
+            max_blocking_threads: Some(4),
+            ..TokioRuntimeConfig::default()
+        };
+        let runtime = build_main_runtime(&config).unwrap();
+        assert_eq!(runtime.metrics().num_workers(), 2);
+    }
+
+    #[test]
+    fn main_runtime_treats_zero_as_default() {
+        let config = TokioRuntimeConfig {
+            worker_threads: Some(0),
+            max_blocking_threads: Some(0),
+            ..TokioRuntimeConfig::default()
+        };
+        assert!(build_main_runtime(&config).is_ok());
+    }
+
+    #[tokio::test]
+    async fn spawn_fs_blocking_runs_without_dedicated_runtime() {
+        let value = spawn_fs_blocking(|| 21 * 2).await.unwrap();
+        assert_eq!(value, 42);
+    }
+}
@@ -1,9 +1,11 @@
 pub mod docker;
+pub mod executor;
 pub mod native;
 pub mod traits;
 pub mod wasm;
 
 pub use docker::DockerRuntime;
+pub use executor::spawn_fs_blocking;
 pub use native::NativeRuntime;
 pub use traits::RuntimeAdapter;
 pub use wasm::{WasmCapabilities, WasmRuntime};
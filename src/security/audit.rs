@@ -1,13 +1,35 @@
 //! Audit logging for security events
+//!
+//! Records are hash-chained: each line carries `prev_hash`, the SHA-256 of the
+//! line before it (across rotations), so editing, removing, or inserting a
+//! record breaks the chain at the next one. Every `anchor_every` records the
+//! chain head is also appended to a separate anchor file, which catches a
+//! chain rewritten from scratch or cut short. [`verify_audit_log`] checks both
+//! (`zeroclaw audit verify`).
+//!
+//! Writers in one process share one logger ([`AuditLogger::shared`]), and
+//! every write holds an advisory lock on `<log>.lock` while it reads the
+//! chain head and appends, so the daemon and CLI commands writing the same
+//! log cannot link two records to the same head.
+//!
+//! The log defaults to `ariadne/audit/audit.log` under the zeroclaw dir; one
+//! left at the old default, `audit.log`, is moved there with its rotations
+//! the first time it is opened.
 
+use super::content_scanner::ContentFinding;
+use super::escalation::{EscalationRequest, EscalationStatus};
 use crate::config::AuditConfig;
 use anyhow::Result;
 use chrono::{DateTime, Utc};
 use parking_lot::Mutex;
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, HashSet};
 use std::fs::OpenOptions;
-use std::io::Write;
-use std::path::PathBuf;
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, LazyLock, Weak};
+use std::time::{Duration, Instant};
 use uuid::Uuid;
 
 /// Audit event types
@@ -21,6 +43,7 @@ pub enum AuditEventType {
     AuthFailure,
     PolicyViolation,
     SecurityEvent,
+    ToolInvocation,
 }
 
 /// Actor information (who performed the action)
@@ -38,6 +61,10 @@ pub struct Action {
     pub risk_level: Option<String>,
     pub approved: bool,
     pub allowed: bool,
+    /// SHA-256 of the tool call's JSON arguments, so calls can be correlated
+    /// without writing their contents to the log.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub args_sha256: Option<String>,
 }
 
 /// Execution result
@@ -55,6 +82,9 @@ pub struct SecurityContext {
     pub policy_violation: bool,
     pub rate_limit_remaining: Option<u32>,
     pub sandbox_backend: Option<String>,
+    /// Content scanner rules that matched the call, as `rule (excerpt)`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub content_findings: Vec<String>,
 }
 
 /// Complete audit event
@@ -67,6 +97,10 @@ pub struct AuditEvent {
     pub action: Option<Action>,
     pub result: Option<ExecutionResult>,
     pub security: SecurityContext,
+    /// SHA-256 of the previous line of the log, set when the record is
+    /// written. Absent only on records written before chaining existed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prev_hash: Option<String>,
 }
 
 impl AuditEvent {
@@ -83,7 +117,9 @@ impl AuditEvent {
                 policy_violation: false,
                 rate_limit_remaining: None,
                 sandbox_backend: None,
+                content_findings: Vec::new(),
             },
+            prev_hash: None,
         }
     }
 
@@ -115,6 +151,7 @@ impl AuditEvent {
             risk_level: Some(risk_level),
             approved,
             allowed,
+            args_sha256: None,
         });
         self
     }
@@ -144,10 +181,48 @@ impl AuditEvent {
 }
 
 /// Audit logger
+///
+/// With the default `batch_size = 1` every event is written and fsync'd
+/// immediately. Larger batch sizes buffer events in memory and write them
+/// with a single fsync once the batch fills or `flush_interval_ms` elapses.
+/// [`AuditLogger::spawn_flush_task`] keeps that interval while no new events
+/// arrive; pending events are flushed when the logger is dropped.
 pub struct AuditLogger {
     log_path: PathBuf,
+    anchor_path: PathBuf,
     config: AuditConfig,
     buffer: Mutex<Vec<AuditEvent>>,
+    last_flush: Mutex<Instant>,
+    chain: Mutex<ChainState>,
+}
+
+impl std::fmt::Debug for AuditLogger {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AuditLogger")
+            .field("log_path", &self.log_path)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Where the hash chain stands. Held across a whole write so concurrent
+/// writers in one process link their records in order.
+#[derive(Debug, Default)]
+struct ChainState {
+    /// Hash of the log's last line.
+    head: Option<String>,
+    /// Log length `head` was taken at; any other length means the log moved
+    /// on without us (another process, a rotation) and `head` is re-read.
+    log_len: Option<u64>,
+    /// Records written since the last anchor.
+    since_anchor: u32,
+}
+
+/// A chain head recorded in the anchor file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditAnchor {
+    pub timestamp: DateTime<Utc>,
+    /// Hash of the log line that was last when the anchor was taken.
+    pub head: String,
 }
 
 /// Structured command execution details for audit logging.
@@ -162,17 +237,57 @@ pub struct CommandExecutionLog<'a> {
     pub duration_ms: u64,
 }
 
+/// Structured tool call details for audit logging.
+#[derive(Debug, Clone)]
+pub struct ToolInvocationLog<'a> {
+    pub tool: &'a str,
+    pub arguments: &'a serde_json::Value,
+    /// `false` when the security policy refused the call.
+    pub allowed: bool,
+    pub success: bool,
+    pub duration_ms: u64,
+    pub error: Option<String>,
+}
+
+/// Loggers handed out by [`AuditLogger::shared`], by log path.
+static SHARED_LOGGERS: LazyLock<Mutex<HashMap<PathBuf, Weak<AuditLogger>>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
 impl AuditLogger {
-    /// Create a new audit logger
+    /// The process-wide logger for `config`'s log under `zeroclaw_dir`,
+    /// created (with its flush task) on first use and kept while anyone
+    /// holds it. The first caller's batching and anchor settings apply.
+    pub fn shared(config: &AuditConfig, zeroclaw_dir: &Path) -> Result<Arc<Self>> {
+        let log_path = zeroclaw_dir.join(&config.log_path);
+        let mut loggers = SHARED_LOGGERS.lock();
+        if let Some(logger) = loggers.get(&log_path).and_then(Weak::upgrade) {
+            return Ok(logger);
+        }
+        let logger = Arc::new(Self::new(config.clone(), zeroclaw_dir.to_path_buf())?);
+        logger.spawn_flush_task();
+        loggers.retain(|_, logger| logger.strong_count() > 0);
+        loggers.insert(log_path, Arc::downgrade(&logger));
+        Ok(logger)
+    }
+
+    /// Create a new audit logger. Prefer [`Self::shared`]: separate loggers
+    /// on one log keep separate batches and flush out of order.
     pub fn new(config: AuditConfig, zeroclaw_dir: PathBuf) -> Result<Self> {
         let log_path = zeroclaw_dir.join(&config.log_path);
+        if config.enabled {
+            migrate_legacy_log(&config, &zeroclaw_dir)?;
+        }
+        let anchor_path = zeroclaw_dir.join(&config.anchor_path);
         if config.enabled {
             initialize_audit_log_file(&log_path)?;
         }
         Ok(Self {
             log_path,
+            anchor_path,
             config,
             buffer: Mutex::new(Vec::new()),
+            last_flush: Mutex::new(Instant::now()),
+            chain: Mutex::new(ChainState::default()),
         })
     }
 
@@ -182,24 +297,140 @@ impl AuditLogger {
             return Ok(());
         }
 
+        if self.config.batch_size <= 1 {
+            return self.write_events(std::slice::from_ref(event));
+        }
+
+        let batch_full = {
+            let mut buffer = self.buffer.lock();
+            buffer.push(event.clone());
+            buffer.len() >= self.config.batch_size
+        };
+        let interval_elapsed = self.last_flush.lock().elapsed()
+            >= Duration::from_millis(self.config.flush_interval_ms);
+
+        if batch_full || interval_elapsed {
+            self.flush()?;
+        }
+
+        Ok(())
+    }
+
+    /// Write all buffered events to disk with a single fsync.
+    pub fn flush(&self) -> Result<()> {
+        let pending = std::mem::take(&mut *self.buffer.lock());
+        *self.last_flush.lock() = Instant::now();
+        if pending.is_empty() {
+            return Ok(());
+        }
+        self.write_events(&pending)
+    }
+
+    /// Flush buffered events every `flush_interval_ms` from a background
+    /// task, so records do not sit in memory while the agent is idle. The
+    /// task ends once the last handle to the logger is dropped. Does nothing
+    /// when events are not buffered or no Tokio runtime is running.
+    pub fn spawn_flush_task(self: &Arc<Self>) {
+        if !self.config.enabled || self.config.batch_size <= 1 {
+            return;
+        }
+        let Ok(runtime) = tokio::runtime::Handle::try_current() else {
+            return;
+        };
+        let logger = Arc::downgrade(self);
+        let period = Duration::from_millis(self.config.flush_interval_ms.max(1));
+        runtime.spawn(async move {
+            let mut ticker = tokio::time::interval(period);
+            ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            ticker.tick().await;
+            loop {
+                ticker.tick().await;
+                let Some(logger) = logger.upgrade() else {
+                    break;
+                };
+                if logger.pending_events() == 0 {
+                    continue;
+                }
+                match tokio::task::spawn_blocking(move || logger.flush()).await {
+                    Ok(Ok(())) => {}
+                    Ok(Err(e)) => tracing::warn!("audit: periodic flush failed: {e}"),
+                    Err(e) => tracing::warn!("audit: periodic flush task failed: {e}"),
+                }
+            }
+        });
+    }
+
+    /// Number of events waiting in the in-memory buffer.
+    pub fn pending_events(&self) -> usize {
+        self.buffer.lock().len()
+    }
+
+    fn write_events(&self, events: &[AuditEvent]) -> Result<()> {
+        let mut chain = self.chain.lock();
+
+        initialize_audit_log_file(&self.log_path)?;
+        let lock_file = OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(lock_path(&self.log_path))?;
+        let mut lock = fd_lock::RwLock::new(lock_file);
+        let _guard = lock.write()?;
+
         // Check log size and rotate if needed
         self.rotate_if_needed()?;
 
         initialize_audit_log_file(&self.log_path)?;
 
-        // Serialize and write
-        let line = serde_json::to_string(event)?;
+        let log_len = std::fs::metadata(&self.log_path)?.len();
+        if chain.log_len != Some(log_len) {
+            chain.head = chain_head(&self.log_path)?;
+        }
+
+        let mut payload = String::new();
+        for event in events {
+            let mut event = event.clone();
+            event.prev_hash = chain.head.take();
+            let line = serde_json::to_string(&event)?;
+            chain.head = Some(line_hash(&line));
+            payload.push_str(&line);
+            payload.push('\n');
+        }
+
         let mut file = OpenOptions::new()
             .create(true)
             .append(true)
             .open(&self.log_path)?;
 
-        writeln!(file, "{}", line)?;
+        file.write_all(payload.as_bytes())?;
         file.sync_all()?;
+        chain.log_len = Some(log_len + payload.len() as u64);
+
+        let written = u32::try_from(events.len()).unwrap_or(u32::MAX);
+        chain.since_anchor = chain.since_anchor.saturating_add(written);
+        if self.config.anchor_every > 0 && chain.since_anchor >= self.config.anchor_every {
+            if let Some(head) = chain.head.clone() {
+                self.write_anchor(head)?;
+            }
+            chain.since_anchor = 0;
+        }
 
         Ok(())
     }
 
+    fn write_anchor(&self, head: String) -> Result<()> {
+        initialize_audit_log_file(&self.anchor_path)?;
+        let mut line = serde_json::to_string(&AuditAnchor {
+            timestamp: Utc::now(),
+            head,
+        })?;
+        line.push('\n');
+        let mut file = OpenOptions::new().append(true).open(&self.anchor_path)?;
+        file.write_all(line.as_bytes())?;
+        file.sync_all()?;
+        Ok(())
+    }
+
     /// Log a command execution event.
     pub fn log_command_event(&self, entry: CommandExecutionLog<'_>) -> Result<()> {
         let event = AuditEvent::new(AuditEventType::CommandExecution)
@@ -215,6 +446,93 @@ impl AuditLogger {
         self.log(&event)
     }
 
+    /// Log one tool call, recording a hash of its arguments instead of
+    /// their contents.
+    pub fn log_tool_invocation(&self, entry: ToolInvocationLog<'_>) -> Result<()> {
+        let mut event = AuditEvent::new(AuditEventType::ToolInvocation).with_result(
+            entry.success,
+            None,
+            entry.duration_ms,
+            entry.error,
+        );
+        event.action = Some(Action {
+            command: Some(entry.tool.to_string()),
+            risk_level: None,
+            approved: false,
+            allowed: entry.allowed,
+            args_sha256: Some(hex::encode(Sha256::digest(
+                entry.arguments.to_string().as_bytes(),
+            ))),
+        });
+        event.security.policy_violation = !entry.allowed;
+
+        self.log(&event)
+    }
+
+    /// Log the content scanner's findings for a tool call. Blocked calls are
+    /// recorded as policy violations.
+    pub fn log_content_findings(
+        &self,
+        tool: &str,
+        arguments: &serde_json::Value,
+        findings: &[ContentFinding],
+        blocked: bool,
+    ) -> Result<()> {
+        let event_type = if blocked {
+            AuditEventType::PolicyViolation
+        } else {
+            AuditEventType::SecurityEvent
+        };
+        let mut event = AuditEvent::new(event_type);
+        event.action = Some(Action {
+            command: Some(tool.to_string()),
+            risk_level: None,
+            approved: false,
+            allowed: !blocked,
+            args_sha256: Some(hex::encode(Sha256::digest(
+                arguments.to_string().as_bytes(),
+            ))),
+        });
+        event.security.policy_violation = blocked;
+        event.security.content_findings = findings.iter().map(ToString::to_string).collect();
+
+        self.log(&event)
+    }
+
+    /// Log an operator's answer to an escalation request: what was asked
+    /// for, why, and until when a grant lasts.
+    pub fn log_escalation_decision(
+        &self,
+        operator: &str,
+        request: &EscalationRequest,
+    ) -> Result<()> {
+        let granted = request.status == EscalationStatus::Granted;
+        let mut summary = format!(
+            "escalation {} {}: {} (reason: {})",
+            if granted { "grant" } else { "deny" },
+            request.id,
+            request.kind,
+            request.reason
+        );
+        if let Some(expires_at) = request.expires_at {
+            summary.push_str(&format!(", expires {}", expires_at.to_rfc3339()));
+        }
+        let mut event = AuditEvent::new(AuditEventType::SecurityEvent).with_actor(
+            "cli".to_string(),
+            None,
+            Some(operator.to_string()),
+        );
+        event.action = Some(Action {
+            command: Some(summary),
+            risk_level: None,
+            approved: granted,
+            allowed: granted,
+            args_sha256: None,
+        });
+
+        self.log(&event)
+    }
+
     /// Backward-compatible helper to log a command execution event.
     #[allow(clippy::too_many_arguments)]
     pub fn log_command(
@@ -251,18 +569,224 @@ impl AuditLogger {
 
     /// Rotate the log file
     fn rotate(&self) -> Result<()> {
-        for i in (1..10).rev() {
-            let old_name = format!("{}.{}.log", self.log_path.display(), i);
-            let new_name = format!("{}.{}.log", self.log_path.display(), i + 1);
-            let _ = std::fs::rename(&old_name, &new_name);
+        for i in (1..MAX_ROTATED_LOGS).rev() {
+            let _ = std::fs::rename(
+                rotated_log_path(&self.log_path, i),
+                rotated_log_path(&self.log_path, i + 1),
+            );
         }
 
-        let rotated = format!("{}.1.log", self.log_path.display());
-        std::fs::rename(&self.log_path, &rotated)?;
+        std::fs::rename(&self.log_path, rotated_log_path(&self.log_path, 1))?;
         Ok(())
     }
 }
 
+/// Rotated logs kept: `<log>.1.log` (newest) to `<log>.10.log`.
+const MAX_ROTATED_LOGS: usize = 10;
+
+fn rotated_log_path(log_path: &Path, index: usize) -> PathBuf {
+    PathBuf::from(format!("{}.{index}.log", log_path.display()))
+}
+
+/// Where the log was kept before it moved under `ariadne/audit/`.
+const LEGACY_LOG_PATH: &str = "audit.log";
+
+/// Move a log, and its rotations, from the old default path to the new one
+/// when the config still uses the default. A log already at the new path is
+/// kept, and the old one is left alone with a warning rather than merged
+/// into a chain it does not belong to.
+pub(crate) fn migrate_legacy_log(config: &AuditConfig, zeroclaw_dir: &Path) -> Result<()> {
+    if config.log_path != AuditConfig::default().log_path {
+        return Ok(());
+    }
+    let legacy = zeroclaw_dir.join(LEGACY_LOG_PATH);
+    if !legacy.exists() {
+        return Ok(());
+    }
+    let log_path = zeroclaw_dir.join(&config.log_path);
+    if std::fs::metadata(&log_path).is_ok_and(|meta| meta.len() > 0) {
+        tracing::warn!(
+            "Ignoring old audit log {}; the audit log is now {}",
+            legacy.display(),
+            log_path.display()
+        );
+        return Ok(());
+    }
+    if let Some(parent) = log_path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    for index in 1..=MAX_ROTATED_LOGS {
+        let rotated = rotated_log_path(&legacy, index);
+        if rotated.exists() {
+            std::fs::rename(&rotated, rotated_log_path(&log_path, index))?;
+        }
+    }
+    std::fs::rename(&legacy, &log_path)?;
+    let _ = std::fs::remove_file(lock_path(&legacy));
+    tracing::info!(
+        "Moved audit log {} to {}",
+        legacy.display(),
+        log_path.display()
+    );
+    Ok(())
+}
+
+/// File writers lock while they append; the log itself is renamed on
+/// rotation, so it cannot carry the lock.
+fn lock_path(log_path: &Path) -> PathBuf {
+    PathBuf::from(format!("{}.lock", log_path.display()))
+}
+
+fn line_hash(line: &str) -> String {
+    hex::encode(Sha256::digest(line.as_bytes()))
+}
+
+/// Hash of the last line of the log, or of the newest rotated log when the
+/// current one is empty, so the chain carries on across rotations.
+fn chain_head(log_path: &Path) -> Result<Option<String>> {
+    for path in [log_path.to_path_buf(), rotated_log_path(log_path, 1)] {
+        if let Some(line) = last_line(&path)? {
+            return Ok(Some(line_hash(&line)));
+        }
+    }
+    Ok(None)
+}
+
+/// Last non-empty line of `path`, read backwards from the end.
+fn last_line(path: &Path) -> Result<Option<String>> {
+    const CHUNK: u64 = 8 * 1024;
+    let mut file = match std::fs::File::open(path) {
+        Ok(file) => file,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(e.into()),
+    };
+    let len = file.metadata()?.len();
+    let mut start = len;
+    let mut tail = Vec::new();
+    loop {
+        let trimmed = tail.strip_suffix(b"\n").unwrap_or(&tail);
+        if let Some(newline) = trimmed.iter().rposition(|&b| b == b'\n') {
+            return Ok(Some(
+                String::from_utf8_lossy(&trimmed[newline + 1..]).into_owned(),
+            ));
+        }
+        if start == 0 {
+            return Ok((!trimmed.is_empty()).then(|| String::from_utf8_lossy(trimmed).into_owned()));
+        }
+        let read_from = start.saturating_sub(CHUNK);
+        let mut chunk = vec![0; usize::try_from(start - read_from)?];
+        file.seek(SeekFrom::Start(read_from))?;
+        file.read_exact(&mut chunk)?;
+        chunk.extend_from_slice(&tail);
+        tail = chunk;
+        start = read_from;
+    }
+}
+
+/// Result of [`verify_audit_log`].
+#[derive(Debug, Default, Serialize)]
+pub struct AuditVerification {
+    /// Log files checked, oldest first.
+    pub files: Vec<PathBuf>,
+    pub records: usize,
+    /// Leading records written before chaining existed; they cannot be checked.
+    pub unchained: usize,
+    pub anchors_checked: usize,
+    pub problems: Vec<String>,
+}
+
+impl AuditVerification {
+    pub fn is_intact(&self) -> bool {
+        self.problems.is_empty()
+    }
+}
+
+/// Check the hash chain through the log and its rotations, oldest first, and
+/// that every anchor taken since the oldest retained record names a record
+/// still in the log. Records after the last anchor are only covered by the
+/// chain, so removing them from the end goes unnoticed until the next anchor.
+pub fn verify_audit_log(config: &AuditConfig, zeroclaw_dir: &Path) -> Result<AuditVerification> {
+    migrate_legacy_log(config, zeroclaw_dir)?;
+    let log_path = zeroclaw_dir.join(&config.log_path);
+    let mut report = AuditVerification {
+        files: (1..=MAX_ROTATED_LOGS)
+            .rev()
+            .map(|index| rotated_log_path(&log_path, index))
+            .chain(std::iter::once(log_path.clone()))
+            .filter(|path| path.exists())
+            .collect(),
+        ..AuditVerification::default()
+    };
+
+    let mut hashes = HashSet::new();
+    let mut prev: Option<String> = None;
+    let mut chained = false;
+    let mut oldest: Option<DateTime<Utc>> = None;
+    for path in &report.files {
+        let content = std::fs::read_to_string(path)?;
+        for (index, line) in content.lines().enumerate() {
+            let at = format!("{}:{}", path.display(), index + 1);
+            let hash = line_hash(line);
+            report.records += 1;
+            match serde_json::from_str::<AuditEvent>(line) {
+                Err(_) => report.problems.push(format!("{at}: not an audit record")),
+                Ok(event) => {
+                    oldest.get_or_insert(event.timestamp);
+                    match (event.prev_hash, &prev) {
+                        (None, _) if chained => report
+                            .problems
+                            .push(format!("{at}: record has no chain link")),
+                        (None, _) => report.unchained += 1,
+                        (Some(link), Some(prev)) if link != *prev => {
+                            chained = true;
+                            report.problems.push(format!(
+                                "{at}: chain broken; the record before it was edited, removed, or inserted"
+                            ));
+                        }
+                        (Some(_), _) => chained = true,
+                    }
+                }
+            }
+            hashes.insert(hash.clone());
+            prev = Some(hash);
+        }
+    }
+
+    let anchor_path = zeroclaw_dir.join(&config.anchor_path);
+    if anchor_path.exists() {
+        let content = std::fs::read_to_string(&anchor_path)?;
+        for (index, line) in content.lines().enumerate() {
+            let at = format!("{}:{}", anchor_path.display(), index + 1);
+            let Ok(anchor) = serde_json::from_str::<AuditAnchor>(line) else {
+                report.problems.push(format!("{at}: not an anchor"));
+                continue;
+            };
+            // Anchors older than the oldest retained record may name a
+            // record that was rotated away.
+            if oldest.is_none_or(|oldest| anchor.timestamp < oldest) {
+                continue;
+            }
+            report.anchors_checked += 1;
+            if !hashes.contains(&anchor.head) {
+                report.problems.push(format!(
+                    "{at}: anchored record from {} is missing from the log",
+                    anchor.timestamp.format("%Y-%m-%d %H:%M:%S UTC")
+                ));
+            }
+        }
+    }
+
+    Ok(report)
+}
+
+impl Drop for AuditLogger {
+    fn drop(&mut self) {
+        if let Err(error) = self.flush() {
+            tracing::warn!("audit: failed to flush buffered events on shutdown: {error}");
+        }
+    }
+}
+
 fn initialize_audit_log_file(log_path: &std::path::Path) -> Result<()> {
     if let Some(parent) = log_path.parent() {
         if !parent.as_os_str().is_empty() {
@@ -348,7 +872,7 @@ mod tests {
         logger.log(&event)?;
 
         // File should not exist since logging is disabled
-        assert!(!tmp.path().join("audit.log").exists());
+        assert!(!tmp.path().join("ariadne/audit/audit.log").exists());
         Ok(())
     }
 
@@ -362,7 +886,7 @@ mod tests {
 
         let _logger = AuditLogger::new(config, tmp.path().to_path_buf())?;
         assert!(
-            tmp.path().join("audit.log").exists(),
+            tmp.path().join("ariadne/audit/audit.log").exists(),
             "audit log file should be created when audit logging is enabled"
         );
         Ok(())
@@ -402,7 +926,7 @@ mod tests {
 
         logger.log(&event)?;
 
-        let log_path = tmp.path().join("audit.log");
+        let log_path = tmp.path().join("ariadne/audit/audit.log");
         assert!(log_path.exists(), "audit log file must be created");
 
         let content = tokio::fs::read_to_string(&log_path).await?;
@@ -433,7 +957,7 @@ mod tests {
             duration_ms: 42,
         })?;
 
-        let log_path = tmp.path().join("audit.log");
+        let log_path = tmp.path().join("ariadne/audit/audit.log");
         let content = tokio::fs::read_to_string(&log_path).await?;
         let parsed: AuditEvent = serde_json::from_str(content.trim())?;
 
@@ -448,6 +972,218 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn audit_log_tool_invocation_hashes_arguments() -> Result<()> {
+        let tmp = TempDir::new()?;
+        let logger = AuditLogger::new(AuditConfig::default(), tmp.path().to_path_buf())?;
+        let arguments = serde_json::json!({"key": "secret-ish", "content": "hello"});
+
+        logger.log_tool_invocation(ToolInvocationLog {
+            tool: "memory_store",
+            arguments: &arguments,
+            allowed: false,
+            success: false,
+            duration_ms: 3,
+            error: Some("Rate limit exceeded: action budget exhausted".into()),
+        })?;
+
+        let content = tokio::fs::read_to_string(tmp.path().join("ariadne/audit/audit.log")).await?;
+        assert!(!content.contains("secret-ish"));
+        let parsed: AuditEvent = serde_json::from_str(content.trim())?;
+        assert!(matches!(parsed.event_type, AuditEventType::ToolInvocation));
+        assert!(parsed.security.policy_violation);
+
+        let action = parsed.action.unwrap();
+        assert_eq!(action.command.as_deref(), Some("memory_store"));
+        assert!(!action.allowed);
+        assert_eq!(action.args_sha256.map(|hash| hash.len()), Some(64));
+        assert_eq!(parsed.result.unwrap().duration_ms, Some(3));
+        Ok(())
+    }
+
+    #[test]
+    fn audit_logger_batches_until_batch_size_reached() -> Result<()> {
+        let tmp = TempDir::new()?;
+        let config = AuditConfig {
+            enabled: true,
+            batch_size: 3,
+            flush_interval_ms: 60_000,
+            ..Default::default()
+        };
+        let logger = AuditLogger::new(config, tmp.path().to_path_buf())?;
+        let log_path = tmp.path().join("ariadne/audit/audit.log");
+
+        logger.log(&AuditEvent::new(AuditEventType::CommandExecution))?;
+        logger.log(&AuditEvent::new(AuditEventType::FileAccess))?;
+        assert_eq!(logger.pending_events(), 2);
+        assert!(std::fs::read_to_string(&log_path)?.is_empty());
+
+        logger.log(&AuditEvent::new(AuditEventType::ConfigChange))?;
+        assert_eq!(logger.pending_events(), 0);
+        assert_eq!(std::fs::read_to_string(&log_path)?.lines().count(), 3);
+        Ok(())
+    }
+
+    #[test]
+    fn audit_logger_flushes_pending_events_on_drop() -> Result<()> {
+        let tmp = TempDir::new()?;
+        let config = AuditConfig {
+            enabled: true,
+            batch_size: 100,
+            flush_interval_ms: 60_000,
+            ..Default::default()
+        };
+        let logger = AuditLogger::new(config, tmp.path().to_path_buf())?;
+        logger.log(&AuditEvent::new(AuditEventType::SecurityEvent))?;
+        drop(logger);
+
+        let content = std::fs::read_to_string(tmp.path().join("ariadne/audit/audit.log"))?;
+        assert_eq!(content.lines().count(), 1);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn audit_flush_task_writes_idle_buffer() -> Result<()> {
+        let tmp = TempDir::new()?;
+        let config = AuditConfig {
+            enabled: true,
+            batch_size: 100,
+            flush_interval_ms: 20,
+            ..Default::default()
+        };
+        let logger = Arc::new(AuditLogger::new(config, tmp.path().to_path_buf())?);
+        logger.spawn_flush_task();
+        logger.log(&AuditEvent::new(AuditEventType::SecurityEvent))?;
+        assert_eq!(logger.pending_events(), 1);
+
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        assert_eq!(logger.pending_events(), 0);
+        let content = std::fs::read_to_string(tmp.path().join("ariadne/audit/audit.log"))?;
+        assert_eq!(content.lines().count(), 1);
+        Ok(())
+    }
+
+    #[test]
+    fn audit_chain_detects_edits_and_anchors_detect_truncation() -> Result<()> {
+        let tmp = TempDir::new()?;
+        let config = AuditConfig {
+            enabled: true,
+            anchor_every: 2,
+            ..Default::default()
+        };
+        let logger = AuditLogger::new(config.clone(), tmp.path().to_path_buf())?;
+        for _ in 0..4 {
+            logger.log(&AuditEvent::new(AuditEventType::ToolInvocation))?;
+        }
+        drop(logger);
+        let report = verify_audit_log(&config, tmp.path())?;
+        assert!(report.is_intact(), "{:?}", report.problems);
+        assert_eq!((report.records, report.anchors_checked), (4, 2));
+
+        let log_path = tmp.path().join("ariadne/audit/audit.log");
+        let original = std::fs::read_to_string(&log_path)?;
+        std::fs::write(
+            &log_path,
+            original.replacen("tool_invocation", "config_change", 1),
+        )?;
+        let report = verify_audit_log(&config, tmp.path())?;
+        assert_eq!(report.problems.len(), 1);
+        assert!(report.problems[0].ends_with(
+            "audit.log:2: chain broken; the record before it was edited, removed, or inserted"
+        ));
+
+        let kept: Vec<&str> = original.lines().take(3).collect();
+        std::fs::write(&log_path, kept.join("\n") + "\n")?;
+        let report = verify_audit_log(&config, tmp.path())?;
+        assert_eq!(report.problems.len(), 1);
+        assert!(report.problems[0].contains("is missing from the log"));
+        Ok(())
+    }
+
+    #[test]
+    fn audit_shared_logger_is_one_per_log() -> Result<()> {
+        let tmp = TempDir::new()?;
+        let config = AuditConfig {
+            enabled: true,
+            ..Default::default()
+        };
+        let first = AuditLogger::shared(&config, tmp.path())?;
+        let second = AuditLogger::shared(&config, tmp.path())?;
+        assert!(Arc::ptr_eq(&first, &second));
+
+        let other = TempDir::new()?;
+        assert!(!Arc::ptr_eq(
+            &first,
+            &AuditLogger::shared(&config, other.path())?
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn audit_chain_stays_intact_with_independent_writers() -> Result<()> {
+        let tmp = TempDir::new()?;
+        let config = AuditConfig {
+            enabled: true,
+            anchor_every: 5,
+            ..Default::default()
+        };
+        // Separate loggers stand in for separate processes: only the file
+        // lock orders their writes.
+        let writers: Vec<_> = (0..4)
+            .map(|_| {
+                let logger = AuditLogger::new(config.clone(), tmp.path().to_path_buf()).unwrap();
+                std::thread::spawn(move || {
+                    for _ in 0..25 {
+                        logger
+                            .log(&AuditEvent::new(AuditEventType::ToolInvocation))
+                            .unwrap();
+                    }
+                })
+            })
+            .collect();
+        for writer in writers {
+            writer.join().unwrap();
+        }
+
+        let report = verify_audit_log(&config, tmp.path())?;
+        assert!(report.is_intact(), "{:?}", report.problems);
+        assert_eq!(report.records, 100);
+        Ok(())
+    }
+
+    #[test]
+    fn audit_log_at_the_old_default_path_is_moved_under_ariadne() -> Result<()> {
+        let tmp = TempDir::new()?;
+        let config = AuditConfig {
+            enabled: true,
+            ..Default::default()
+        };
+        let old = AuditConfig {
+            log_path: LEGACY_LOG_PATH.into(),
+            ..config.clone()
+        };
+        let logger = AuditLogger::new(old.clone(), tmp.path().to_path_buf())?;
+        logger.log(&AuditEvent::new(AuditEventType::ToolInvocation))?;
+        logger.rotate()?;
+        logger.log(&AuditEvent::new(AuditEventType::ToolInvocation))?;
+        drop(logger);
+
+        let logger = AuditLogger::new(config.clone(), tmp.path().to_path_buf())?;
+        logger.log(&AuditEvent::new(AuditEventType::ToolInvocation))?;
+        assert!(!tmp.path().join(LEGACY_LOG_PATH).exists());
+        assert!(tmp.path().join("ariadne/audit/audit.log.1.log").exists());
+        let report = verify_audit_log(&config, tmp.path())?;
+        assert!(report.is_intact(), "{:?}", report.problems);
+        assert_eq!(report.records, 3);
+
+        // Once the new log has records, an old one is left where it is.
+        std::fs::write(tmp.path().join(LEGACY_LOG_PATH), "{}\n")?;
+        AuditLogger::new(config.clone(), tmp.path().to_path_buf())?;
+        assert!(tmp.path().join(LEGACY_LOG_PATH).exists());
+        assert_eq!(verify_audit_log(&config, tmp.path())?.records, 3);
+        Ok(())
+    }
+
     #[test]
     fn audit_rotation_creates_numbered_backup() -> Result<()> {
         let tmp = TempDir::new()?;
@@ -459,7 +1195,7 @@ mod tests {
         let logger = AuditLogger::new(config, tmp.path().to_path_buf())?;
 
         // Write initial content that triggers rotation
-        let log_path = tmp.path().join("audit.log");
+        let log_path = tmp.path().join("ariadne/audit/audit.log");
         std::fs::write(&log_path, "initial content\n")?;
 
         let event = AuditEvent::new(AuditEventType::CommandExecution);
@@ -0,0 +1,255 @@
+//! Scoped capability tokens for delegated tool access.
+//!
+//! A [`CapabilityToken`] is minted from a parent [`SecurityPolicy`] and
+//! handed to a sub-agent (or any other delegate) together with the tools it
+//! may call. The token names a subset of the tools the parent may run, an
+//! expiry, and an action budget no larger than what the parent has left, so
+//! delegated work can only ever narrow the parent's permissions. The parent
+//! policy still applies on every call: the token is checked in addition to
+//! it, never instead of it.
+
+use super::policy::{PolicyDenial, RateLimitScope, SecurityPolicy, ToolOperation};
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Delegated, time-limited grant of a set of tools.
+#[derive(Debug)]
+pub struct CapabilityToken {
+    id: String,
+    /// Lowercase names of the tools the holder may call.
+    tools: HashSet<String>,
+    expires_at: Instant,
+    /// `Act` operations the holder may perform over the token's lifetime.
+    max_actions: u32,
+    used_actions: AtomicU32,
+    parent: Arc<SecurityPolicy>,
+}
+
+impl CapabilityToken {
+    /// Mint a token for `tools` from `parent`. Tools the parent may not run
+    /// are dropped from the scope, and the action budget is capped at the
+    /// tokens left in the parent's action bucket. Fails if no tool survives.
+    pub fn mint(
+        parent: &Arc<SecurityPolicy>,
+        tools: &[String],
+        ttl: Duration,
+        max_actions: Option<u32>,
+    ) -> Result<Self, String> {
+        let scope: HashSet<String> = tools
+            .iter()
+            .map(|tool| tool.trim().to_ascii_lowercase())
+            .filter(|tool| !tool.is_empty() && parent.is_tool_allowed(tool))
+            .collect();
+        if scope.is_empty() {
+            return Err(format!(
+                "Security policy: none of the requested tools ({}) are permitted to delegate",
+                tools.join(", ")
+            ));
+        }
+
+        let remaining = parent.remaining_actions();
+        Ok(Self {
+            id: uuid::Uuid::new_v4().to_string(),
+            tools: scope,
+            expires_at: Instant::now() + ttl,
+            max_actions: max_actions.map_or(remaining, |cap| cap.min(remaining)),
+            used_actions: AtomicU32::new(0),
+            parent: Arc::clone(parent),
+        })
+    }
+
+    /// Mint a narrower token from this one for a further delegate. The
+    /// child's tools, lifetime, and budget are each bounded by this token's.
+    pub fn attenuate(
+        &self,
+        tools: &[String],
+        ttl: Duration,
+        max_actions: Option<u32>,
+    ) -> Result<Self, String> {
+        let scope: Vec<String> = tools
+            .iter()
+            .filter(|tool| self.permits_tool(tool))
+            .cloned()
+            .collect();
+        let mut child = Self::mint(&self.parent, &scope, ttl, max_actions)?;
+        child.expires_at = child.expires_at.min(self.expires_at);
+        child.max_actions = child.max_actions.min(self.remaining_actions());
+        Ok(child)
+    }
+
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    pub fn parent(&self) -> &Arc<SecurityPolicy> {
+        &self.parent
+    }
+
+    pub fn is_expired(&self) -> bool {
+        Instant::now() >= self.expires_at
+    }
+
+    /// Whether `tool_name` is within the token's scope.
+    pub fn permits_tool(&self, tool_name: &str) -> bool {
+        self.tools.contains(&tool_name.trim().to_ascii_lowercase())
+    }
+
+    /// `Act` operations left on the token.
+    pub fn remaining_actions(&self) -> u32 {
+        self.max_actions
+            .saturating_sub(self.used_actions.load(Ordering::Relaxed))
+    }
+
+    /// Check a call against the token's scope and expiry, and charge `Act`
+    /// operations to its budget. The tool's own check against the parent
+    /// policy still runs afterwards.
+    pub fn authorize(&self, operation: ToolOperation, tool_name: &str) -> Result<(), String> {
+        let deny = |reason: String| Err(PolicyDenial::CapabilityDenied { reason }.surface().into());
+        if self.is_expired() {
+            return deny(format!(
+                "capability token {} has expired, cannot perform '{tool_name}'",
+                self.id
+            ));
+        }
+        if !self.permits_tool(tool_name) || !self.parent.is_tool_allowed(tool_name) {
+            return deny(format!(
+                "tool '{tool_name}' is outside the delegated capability scope"
+            ));
+        }
+        if operation == ToolOperation::Act {
+            if !self.parent.can_act() {
+                return deny(format!(
+                    "parent policy does not permit actions, cannot perform '{tool_name}'"
+                ));
+            }
+            let previous =
+                self.used_actions
+                    .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |used| {
+                        (used < self.max_actions).then_some(used + 1)
+                    });
+            if previous.is_err() {
+                return Err(PolicyDenial::RateLimited {
+                    scope: RateLimitScope::Capability(self.id.clone()),
+                    limit: self.max_actions,
+                    burst: None,
+                    retry_after: None,
+                }
+                .surface()
+                .into());
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::security::AutonomyLevel;
+
+    fn parent() -> Arc<SecurityPolicy> {
+        Arc::new(SecurityPolicy {
+            autonomy: AutonomyLevel::Full,
+            max_actions_per_hour: 5,
+            denied_tools: vec!["shell".into()],
+            ..SecurityPolicy::default()
+        })
+    }
+
+    fn names(tools: &[&str]) -> Vec<String> {
+        tools.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn mint_drops_tools_the_parent_denies() {
+        let token = CapabilityToken::mint(
+            &parent(),
+            &names(&["file_read", "shell"]),
+            Duration::from_secs(60),
+            None,
+        )
+        .unwrap();
+        assert!(token.permits_tool("file_read"));
+        assert!(!token.permits_tool("shell"));
+        assert!(token
+            .authorize(ToolOperation::Read, "shell")
+            .unwrap_err()
+            .contains("outside the delegated capability scope"));
+
+        assert!(CapabilityToken::mint(
+            &parent(),
+            &names(&["shell"]),
+            Duration::from_secs(60),
+            None
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn budget_is_capped_by_parent_and_consumed_by_actions() {
+        let parent = parent();
+        parent.record_action();
+        parent.record_action();
+        let token = CapabilityToken::mint(
+            &parent,
+            &names(&["file_write"]),
+            Duration::from_secs(60),
+            Some(100),
+        )
+        .unwrap();
+        assert_eq!(token.remaining_actions(), 3);
+
+        for _ in 0..3 {
+            token.authorize(ToolOperation::Act, "file_write").unwrap();
+        }
+        // Reads do not spend the budget.
+        token.authorize(ToolOperation::Read, "file_write").unwrap();
+        let err = token
+            .authorize(ToolOperation::Act, "file_write")
+            .unwrap_err();
+        assert!(err.contains("capability token budget exhausted (3 actions)"));
+    }
+
+    #[test]
+    fn expired_tokens_and_children_never_outlive_the_parent_token() {
+        let token = CapabilityToken::mint(
+            &parent(),
+            &names(&["file_read", "file_write"]),
+            Duration::ZERO,
+            None,
+        )
+        .unwrap();
+        assert!(token
+            .authorize(ToolOperation::Read, "file_read")
+            .unwrap_err()
+            .contains("has expired"));
+
+        let child = token
+            .attenuate(
+                &names(&["file_read", "memory_store"]),
+                Duration::from_secs(3600),
+                None,
+            )
+            .unwrap();
+        assert!(child.is_expired());
+        assert!(!child.permits_tool("memory_store"));
+    }
+
+    #[test]
+    fn read_only_parent_blocks_delegated_actions() {
+        let parent = Arc::new(SecurityPolicy {
+            autonomy: AutonomyLevel::ReadOnly,
+            ..SecurityPolicy::default()
+        });
+        let token = CapabilityToken::mint(
+            &parent,
+            &names(&["file_write"]),
+            Duration::from_secs(60),
+            None,
+        )
+        .unwrap();
+        assert!(token.authorize(ToolOperation::Act, "file_write").is_err());
+    }
+}
@@ -62,13 +62,7 @@ impl DomainMatcher {
     }
 
     pub fn is_gated(&self, domain: &str) -> bool {
-        let Some(normalized_domain) = normalize_domain(domain) else {
-            return false;
-        };
-
-        self.patterns
-            .iter()
-            .any(|pattern| domain_matches_pattern(pattern, &normalized_domain))
+        matches_any(&self.patterns, domain)
     }
 
     pub fn expand_categories(categories: &[String]) -> Result<Vec<String>> {
@@ -97,6 +91,17 @@ impl DomainMatcher {
     }
 }
 
+/// Whether `domain` (a bare host or a URL) matches any of `patterns`, which
+/// must already be normalized (see [`DomainMatcher::validate_pattern`]).
+pub(crate) fn matches_any(patterns: &[String], domain: &str) -> bool {
+    let Some(normalized_domain) = normalize_domain(domain) else {
+        return false;
+    };
+    patterns
+        .iter()
+        .any(|pattern| domain_matches_pattern(pattern, &normalized_domain))
+}
+
 fn normalize_domain(raw: &str) -> Option<String> {
     let mut domain = raw.trim().to_ascii_lowercase();
     if domain.is_empty() {
@@ -0,0 +1,116 @@
+//! Network egress allowlist shared by every tool that opens connections.
+//!
+//! [`SecurityPolicy::egress`] hands out an [`EgressGuard`] over the policy's
+//! `allowed_hosts` and `denied_hosts`. Tools that reach the network check
+//! each target with it before connecting, on top of their own per-tool
+//! domain settings, so a tool added later is bound by the same host rules
+//! as the existing ones.
+
+use super::domain_matcher::matches_any;
+use super::policy::{PolicyDenial, SecurityPolicy};
+
+/// Host rules from a [`SecurityPolicy`], checked before outbound connections.
+#[derive(Debug, Clone, Copy)]
+pub struct EgressGuard<'a> {
+    allowed_hosts: &'a [String],
+    denied_hosts: &'a [String],
+}
+
+impl<'a> EgressGuard<'a> {
+    pub fn new(policy: &'a SecurityPolicy) -> Self {
+        Self {
+            allowed_hosts: &policy.allowed_hosts,
+            denied_hosts: &policy.denied_hosts,
+        }
+    }
+
+    /// Whether the policy lets tools connect to `host`.
+    pub fn is_host_allowed(&self, host: &str) -> bool {
+        if matches_any(self.denied_hosts, host) {
+            return false;
+        }
+        self.allowed_hosts.is_empty() || matches_any(self.allowed_hosts, host)
+    }
+
+    /// Check a bare host name against the policy.
+    pub fn check_host(&self, host: &str) -> Result<(), String> {
+        if matches_any(self.denied_hosts, host) {
+            return Err(PolicyDenial::HostDenied {
+                host: host.to_string(),
+                listed: true,
+            }
+            .surface()
+            .into());
+        }
+        if !self.allowed_hosts.is_empty() && !matches_any(self.allowed_hosts, host) {
+            return Err(PolicyDenial::HostDenied {
+                host: host.to_string(),
+                listed: false,
+            }
+            .surface()
+            .into());
+        }
+        Ok(())
+    }
+
+    /// Check the host of `url` against the policy. URLs without a host are
+    /// refused.
+    pub fn check_url(&self, url: &str) -> Result<(), String> {
+        let host = reqwest::Url::parse(url.trim())
+            .ok()
+            .and_then(|parsed| parsed.host_str().map(str::to_ascii_lowercase))
+            .ok_or_else(|| {
+                String::from(
+                    PolicyDenial::UnknownHost {
+                        url: url.to_string(),
+                    }
+                    .surface(),
+                )
+            })?;
+        self.check_host(&host)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy(allowed: &[&str], denied: &[&str]) -> SecurityPolicy {
+        SecurityPolicy {
+            allowed_hosts: allowed.iter().map(ToString::to_string).collect(),
+            denied_hosts: denied.iter().map(ToString::to_string).collect(),
+            ..SecurityPolicy::default()
+        }
+    }
+
+    #[test]
+    fn empty_rules_allow_every_host() {
+        let policy = SecurityPolicy::default();
+        assert!(policy.egress().check_url("https://example.com/a").is_ok());
+    }
+
+    #[test]
+    fn allowlist_restricts_hosts_and_denylist_wins() {
+        let policy = policy(&["api.github.com", "*.example.com"], &["evil.example.com"]);
+        let egress = policy.egress();
+
+        assert!(egress.check_url("https://api.github.com/repos").is_ok());
+        assert!(egress.check_url("https://docs.example.com:8443/x").is_ok());
+        assert!(egress
+            .check_url("https://github.com")
+            .unwrap_err()
+            .contains("not in autonomy.allowed_hosts"));
+        assert!(egress
+            .check_url("https://EVIL.example.com/")
+            .unwrap_err()
+            .contains("autonomy.denied_hosts"));
+        assert!(!egress.is_host_allowed("evil.example.com"));
+    }
+
+    #[test]
+    fn urls_without_a_host_are_refused() {
+        let policy = SecurityPolicy::default();
+        assert!(policy.egress().check_url("not a url").is_err());
+        assert!(policy.egress().check_url("file:///etc/passwd").is_err());
+    }
+}
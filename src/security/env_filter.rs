@@ -0,0 +1,154 @@
+//! Environment filtering for subprocesses spawned by tools.
+//!
+//! [`SecurityPolicy::env_filter`] hands out an [`EnvFilter`] over the
+//! policy's `shell_env_passthrough` allowlist and `env_secret_suffixes`.
+//! Tools apply it to every command before spawning, so a child process sees
+//! only the safe baseline plus the allowlisted names, and never a variable
+//! that looks like a credential.
+
+use super::policy::SecurityPolicy;
+
+/// Environment variables safe to pass to tool subprocesses.
+/// Only functional variables are included — never API keys or secrets.
+pub const SAFE_ENV_VARS: &[&str] = &[
+    "PATH",
+    "HOME",
+    "TERM",
+    "LANG",
+    "LC_ALL",
+    "LC_CTYPE",
+    "USER",
+    "SHELL",
+    "TMPDIR",
+    // Display access for screenshot and browser launchers.
+    "DISPLAY",
+    "WAYLAND_DISPLAY",
+    "XDG_RUNTIME_DIR",
+    // Windows runtime essentials when env is cleared before shell spawn.
+    "USERPROFILE",
+    "APPDATA",
+    "LOCALAPPDATA",
+    "PROGRAMDATA",
+    "SYSTEMROOT",
+    "WINDIR",
+    "COMSPEC",
+    "TEMP",
+    "TMP",
+    "PATHEXT",
+];
+
+/// Name suffixes that mark a variable as a credential by default.
+pub const DEFAULT_SECRET_ENV_SUFFIXES: &[&str] = &["_TOKEN", "_KEY", "_SECRET"];
+
+pub(crate) fn is_valid_env_var_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    match chars.next() {
+        Some(first) if first.is_ascii_alphabetic() || first == '_' => {}
+        _ => return false,
+    }
+    chars.all(|ch| ch.is_ascii_alphanumeric() || ch == '_')
+}
+
+/// Environment rules from a [`SecurityPolicy`], applied before spawning.
+#[derive(Debug, Clone, Copy)]
+pub struct EnvFilter<'a> {
+    passthrough: &'a [String],
+    secret_suffixes: &'a [String],
+}
+
+impl<'a> EnvFilter<'a> {
+    pub fn new(policy: &'a SecurityPolicy) -> Self {
+        Self {
+            passthrough: &policy.shell_env_passthrough,
+            secret_suffixes: &policy.env_secret_suffixes,
+        }
+    }
+
+    /// Filter with only [`SAFE_ENV_VARS`], for spawn sites that have no
+    /// policy at hand.
+    pub fn baseline() -> EnvFilter<'static> {
+        EnvFilter {
+            passthrough: &[],
+            secret_suffixes: &[],
+        }
+    }
+
+    /// Whether `name` looks like a credential and is always withheld.
+    pub fn is_secret(&self, name: &str) -> bool {
+        let name = name.to_ascii_uppercase();
+        self.secret_suffixes
+            .iter()
+            .any(|suffix| name.ends_with(suffix.as_str()))
+    }
+
+    /// Variable names a child process may inherit, in order and without
+    /// duplicates.
+    pub fn allowed_vars(&self) -> Vec<String> {
+        let mut out: Vec<String> = Vec::new();
+        for key in SAFE_ENV_VARS
+            .iter()
+            .copied()
+            .chain(self.passthrough.iter().map(String::as_str))
+        {
+            let candidate = key.trim();
+            if candidate.is_empty() || !is_valid_env_var_name(candidate) {
+                continue;
+            }
+            if self.is_secret(candidate) {
+                tracing::debug!("env filter: withholding secret-looking variable {candidate}");
+                continue;
+            }
+            if !out.iter().any(|existing| existing == candidate) {
+                out.push(candidate.to_string());
+            }
+        }
+        out
+    }
+
+    /// Clear `cmd`'s environment and re-add only the allowed variables that
+    /// are set in the current process. Use `as_std_mut()` for tokio commands.
+    pub fn apply(&self, cmd: &mut std::process::Command) {
+        cmd.env_clear();
+        for var in self.allowed_vars() {
+            if let Some(value) = std::env::var_os(&var) {
+                cmd.env(&var, value);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn secret_suffixes_win_over_passthrough() {
+        let policy = SecurityPolicy {
+            shell_env_passthrough: vec![
+                "DATABASE_URL".into(),
+                "GITHUB_TOKEN".into(),
+                "aws_secret".into(),
+                "BAD-NAME".into(),
+                "PATH".into(),
+            ],
+            ..SecurityPolicy::default()
+        };
+        let vars = policy.env_filter().allowed_vars();
+        assert!(vars.contains(&"DATABASE_URL".to_string()));
+        assert!(vars.contains(&"PATH".to_string()));
+        assert!(!vars.contains(&"GITHUB_TOKEN".to_string()));
+        assert!(!vars.contains(&"aws_secret".to_string()));
+        assert!(!vars.contains(&"BAD-NAME".to_string()));
+        assert_eq!(vars.iter().filter(|var| *var == "PATH").count(), 1);
+    }
+
+    #[test]
+    fn apply_clears_inherited_environment() {
+        let mut cmd = std::process::Command::new("env");
+        cmd.env("OPENAI_API_KEY", "sk-test");
+        SecurityPolicy::default().env_filter().apply(&mut cmd);
+        assert!(cmd
+            .get_envs()
+            .all(|(name, _)| SAFE_ENV_VARS.contains(&name.to_str().unwrap())));
+    }
+}
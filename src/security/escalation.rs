@@ -0,0 +1,298 @@
+//! Operator-granted escalations: `escalations.json` in the policy state
+//! directory, next to the config file and out of the tools' reach.
+//!
+//! The `request_escalation` tool files a pending request for a higher
+//! autonomy level or extra actions on the global rate budget. Nothing changes
+//! until an operator runs `zeroclaw escalation grant <id>`, which activates it
+//! for a fixed number of minutes; `zeroclaw escalation deny <id>` drops it.
+//! [`SecurityPolicy`](super::SecurityPolicy) reads the active grants on every
+//! check, so a grant takes effect in a running agent and lapses on its own.
+
+use super::policy::AutonomyLevel;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// State file of escalation requests, under the policy state directory
+/// ([`SecurityPolicy::state_dir`](super::SecurityPolicy::state_dir)).
+pub const ESCALATION_STATE_FILE: &str = "escalations.json";
+
+/// Requests left pending at once; further requests are refused until the
+/// operator answers some.
+pub const MAX_PENDING_ESCALATIONS: usize = 10;
+
+/// Longest grant the CLI accepts, in minutes.
+pub const MAX_ESCALATION_MINUTES: u32 = 24 * 60;
+
+/// What an escalation asks for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum EscalationKind {
+    /// Act at `level` instead of the configured autonomy.
+    Autonomy { level: AutonomyLevel },
+    /// `extra` more tokens in the global action bucket.
+    Actions { extra: u32 },
+}
+
+impl std::fmt::Display for EscalationKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Autonomy { level } => write!(f, "autonomy level {}", level_label(*level)),
+            Self::Actions { extra } => write!(f, "{extra} extra actions"),
+        }
+    }
+}
+
+fn level_label(level: AutonomyLevel) -> &'static str {
+    match level {
+        AutonomyLevel::ReadOnly => "read_only",
+        AutonomyLevel::Supervised => "supervised",
+        AutonomyLevel::Autonomous => "autonomous",
+        AutonomyLevel::Full => "full",
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EscalationStatus {
+    Pending,
+    Granted,
+    Denied,
+}
+
+/// One escalation request and the operator's answer, if any.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EscalationRequest {
+    pub id: String,
+    pub kind: EscalationKind,
+    pub reason: String,
+    /// Grant length the agent asked for; the operator picks the actual one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub requested_minutes: Option<u32>,
+    pub requested_at: DateTime<Utc>,
+    pub status: EscalationStatus,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub decided_at: Option<DateTime<Utc>>,
+    /// End of a granted escalation.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+impl EscalationRequest {
+    /// Whether this is a grant that has not yet expired at `now`.
+    pub fn is_active(&self, now: DateTime<Utc>) -> bool {
+        self.status == EscalationStatus::Granted && self.expires_at.is_some_and(|end| now < end)
+    }
+
+    /// Status as shown by `zeroclaw escalation list`.
+    pub fn state_label(&self, now: DateTime<Utc>) -> &'static str {
+        match self.status {
+            EscalationStatus::Pending => "pending",
+            EscalationStatus::Denied => "denied",
+            EscalationStatus::Granted if self.is_active(now) => "active",
+            EscalationStatus::Granted => "expired",
+        }
+    }
+}
+
+/// What the unexpired grants add up to.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ActiveGrants {
+    /// Highest granted autonomy level, if any.
+    pub autonomy: Option<AutonomyLevel>,
+    /// Sum of granted extra actions.
+    pub extra_actions: u32,
+}
+
+/// Escalation requests of one agent.
+#[derive(Debug, Clone)]
+pub struct EscalationStore {
+    path: PathBuf,
+}
+
+impl EscalationStore {
+    /// Store in the policy state directory `state_dir`.
+    pub fn new(state_dir: &Path) -> Self {
+        Self {
+            path: state_dir.join(ESCALATION_STATE_FILE),
+        }
+    }
+
+    /// All requests, oldest first. A missing file holds none.
+    pub fn list(&self) -> Result<Vec<EscalationRequest>> {
+        let raw = match std::fs::read_to_string(&self.path) {
+            Ok(raw) => raw,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => {
+                return Err(e).with_context(|| format!("reading {}", self.path.display()));
+            }
+        };
+        serde_json::from_str(&raw).with_context(|| format!("parsing {}", self.path.display()))
+    }
+
+    fn save(&self, requests: &[EscalationRequest]) -> Result<()> {
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let tmp = self.path.with_extension("json.tmp");
+        std::fs::write(&tmp, serde_json::to_vec_pretty(requests)?)?;
+        std::fs::rename(&tmp, &self.path)
+            .with_context(|| format!("writing {}", self.path.display()))
+    }
+
+    /// File a pending request. Asking again for something already pending
+    /// returns the existing request.
+    pub fn request(
+        &self,
+        kind: EscalationKind,
+        reason: &str,
+        requested_minutes: Option<u32>,
+    ) -> Result<EscalationRequest> {
+        let mut requests = self.list()?;
+        let pending = || {
+            requests
+                .iter()
+                .filter(|request| request.status == EscalationStatus::Pending)
+        };
+        if let Some(existing) = pending().find(|request| request.kind == kind) {
+            return Ok(existing.clone());
+        }
+        if pending().count() >= MAX_PENDING_ESCALATIONS {
+            anyhow::bail!(
+                "{MAX_PENDING_ESCALATIONS} escalation requests are already pending; wait for the operator to answer them"
+            );
+        }
+
+        let request = EscalationRequest {
+            id: uuid::Uuid::new_v4().simple().to_string()[..8].to_string(),
+            kind,
+            reason: reason.to_string(),
+            requested_minutes,
+            requested_at: Utc::now(),
+            status: EscalationStatus::Pending,
+            decided_at: None,
+            expires_at: None,
+        };
+        requests.push(request.clone());
+        self.save(&requests)?;
+        Ok(request)
+    }
+
+    /// Grant the pending request `id` for `minutes`.
+    pub fn grant(&self, id: &str, minutes: u32) -> Result<EscalationRequest> {
+        if minutes == 0 || minutes > MAX_ESCALATION_MINUTES {
+            anyhow::bail!("grant length must be between 1 and {MAX_ESCALATION_MINUTES} minutes");
+        }
+        let now = Utc::now();
+        self.decide(id, |request| {
+            request.status = EscalationStatus::Granted;
+            request.decided_at = Some(now);
+            request.expires_at = Some(now + chrono::Duration::minutes(minutes.into()));
+        })
+    }
+
+    /// Deny the pending request `id`.
+    pub fn deny(&self, id: &str) -> Result<EscalationRequest> {
+        self.decide(id, |request| {
+            request.status = EscalationStatus::Denied;
+            request.decided_at = Some(Utc::now());
+        })
+    }
+
+    fn decide(
+        &self,
+        id: &str,
+        answer: impl FnOnce(&mut EscalationRequest),
+    ) -> Result<EscalationRequest> {
+        let mut requests = self.list()?;
+        let request = requests
+            .iter_mut()
+            .find(|request| request.id == id)
+            .ok_or_else(|| anyhow::anyhow!("no escalation request with id '{id}'"))?;
+        if request.status != EscalationStatus::Pending {
+            anyhow::bail!("escalation request '{id}' was already answered");
+        }
+        answer(request);
+        let decided = request.clone();
+        self.save(&requests)?;
+        Ok(decided)
+    }
+
+    /// Grants in effect now. An unreadable file grants nothing.
+    pub fn active_grants(&self) -> ActiveGrants {
+        let requests = match self.list() {
+            Ok(requests) => requests,
+            Err(e) => {
+                tracing::warn!("Ignoring unreadable escalation state: {e:#}");
+                return ActiveGrants::default();
+            }
+        };
+        let now = Utc::now();
+        requests
+            .iter()
+            .filter(|request| request.is_active(now))
+            .fold(ActiveGrants::default(), |mut grants, request| {
+                match request.kind {
+                    EscalationKind::Autonomy { level } => {
+                        grants.autonomy = grants.autonomy.max(Some(level));
+                    }
+                    EscalationKind::Actions { extra } => {
+                        grants.extra_actions = grants.extra_actions.saturating_add(extra);
+                    }
+                }
+                grants
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_granted_requests_count_until_they_expire() {
+        let tmp = tempfile::tempdir().unwrap();
+        let store = EscalationStore::new(tmp.path());
+        let full = store
+            .request(
+                EscalationKind::Autonomy {
+                    level: AutonomyLevel::Full,
+                },
+                "deploy the fix",
+                Some(30),
+            )
+            .unwrap();
+        let again = store
+            .request(
+                EscalationKind::Autonomy {
+                    level: AutonomyLevel::Full,
+                },
+                "still deploying",
+                None,
+            )
+            .unwrap();
+        assert_eq!(again.id, full.id);
+        let actions = store
+            .request(EscalationKind::Actions { extra: 20 }, "batch job", None)
+            .unwrap();
+        assert_eq!(store.active_grants(), ActiveGrants::default());
+
+        store.grant(&full.id, 30).unwrap();
+        store.deny(&actions.id).unwrap();
+        assert!(store.grant(&actions.id, 30).is_err());
+        assert_eq!(
+            store.active_grants(),
+            ActiveGrants {
+                autonomy: Some(AutonomyLevel::Full),
+                extra_actions: 0,
+            }
+        );
+
+        let mut requests = store.list().unwrap();
+        requests[0].expires_at = Some(Utc::now() - chrono::Duration::seconds(1));
+        store.save(&requests).unwrap();
+        assert_eq!(store.active_grants(), ActiveGrants::default());
+        assert_eq!(store.list().unwrap()[0].state_label(Utc::now()), "expired");
+    }
+}
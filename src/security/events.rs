@@ -0,0 +1,204 @@
+//! Security event webhook (`[security.webhook]`).
+//!
+//! Policy denials, kill-switch trips, content scanner findings, budget
+//! exhaustion, and denial cooldowns are reported with [`emit`] where they
+//! happen. When a webhook is configured, each event is POSTed to it in the
+//! background, as a JSON object or a Slack message, so operators hear about
+//! blocked behavior as it happens. Without one, [`emit`] does nothing.
+
+use crate::config::{SecurityEventKind, SecurityWebhookConfig, SecurityWebhookFormat};
+use chrono::{DateTime, Utc};
+use parking_lot::Mutex;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::{Arc, LazyLock, RwLock};
+use std::time::{Duration, Instant};
+
+/// One security event, as sent to the webhook.
+#[derive(Debug, Clone, Serialize)]
+pub struct SecurityEvent {
+    pub kind: SecurityEventKind,
+    pub timestamp: DateTime<Utc>,
+    /// Tool the event concerns, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tool: Option<String>,
+    pub message: String,
+}
+
+impl SecurityEvent {
+    pub fn new(kind: SecurityEventKind, tool: Option<&str>, message: impl Into<String>) -> Self {
+        Self {
+            kind,
+            timestamp: Utc::now(),
+            tool: tool.map(str::to_string),
+            message: message.into(),
+        }
+    }
+}
+
+fn kind_label(kind: SecurityEventKind) -> &'static str {
+    match kind {
+        SecurityEventKind::PolicyDenial => "policy_denial",
+        SecurityEventKind::KillSwitch => "kill_switch",
+        SecurityEventKind::ScannerFinding => "scanner_finding",
+        SecurityEventKind::BudgetExceeded => "budget_exceeded",
+        SecurityEventKind::DenialCooldown => "denial_cooldown",
+    }
+}
+
+/// Sends [`SecurityEvent`]s to the configured endpoint.
+pub struct SecurityWebhook {
+    config: SecurityWebhookConfig,
+    /// Last send per kind and tool, for `cooldown_secs`.
+    last_sent: Mutex<HashMap<(SecurityEventKind, Option<String>), Instant>>,
+}
+
+impl SecurityWebhook {
+    pub fn new(config: SecurityWebhookConfig) -> Self {
+        Self {
+            config,
+            last_sent: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Whether `event` passes the `events` filter and is outside the
+    /// cooldown of the last one like it. Marks it sent if so.
+    fn should_send(&self, event: &SecurityEvent) -> bool {
+        if !self.config.events.is_empty() && !self.config.events.contains(&event.kind) {
+            return false;
+        }
+        let cooldown = Duration::from_secs(self.config.cooldown_secs);
+        let now = Instant::now();
+        let mut last_sent = self.last_sent.lock();
+        let key = (event.kind, event.tool.clone());
+        if last_sent
+            .get(&key)
+            .is_some_and(|at| now.duration_since(*at) < cooldown)
+        {
+            return false;
+        }
+        last_sent.insert(key, now);
+        true
+    }
+
+    fn payload(&self, event: &SecurityEvent) -> serde_json::Value {
+        match self.config.format {
+            SecurityWebhookFormat::Json => serde_json::to_value(event).unwrap_or_default(),
+            SecurityWebhookFormat::Slack => {
+                let tool = event
+                    .tool
+                    .as_deref()
+                    .map(|tool| format!(" `{tool}`"))
+                    .unwrap_or_default();
+                serde_json::json!({
+                    "text": format!(
+                        ":rotating_light: zeroclaw security event `{}`{tool}: {}",
+                        kind_label(event.kind),
+                        event.message
+                    ),
+                })
+            }
+        }
+    }
+
+    async fn send(&self, payload: serde_json::Value) {
+        let client =
+            crate::config::build_runtime_proxy_client_with_timeouts("security.webhook", 10, 5);
+        let result = client
+            .post(self.config.url.trim())
+            .json(&payload)
+            .send()
+            .await
+            .and_then(reqwest::Response::error_for_status);
+        if let Err(e) = result {
+            tracing::warn!("security webhook: failed to deliver event: {e}");
+        }
+    }
+}
+
+static WEBHOOK: LazyLock<RwLock<Option<Arc<SecurityWebhook>>>> =
+    LazyLock::new(|| RwLock::new(None));
+
+/// Install (or remove) the process-wide security webhook.
+pub fn init_from_config(config: &SecurityWebhookConfig) {
+    let webhook = (config.enabled && !config.url.trim().is_empty())
+        .then(|| Arc::new(SecurityWebhook::new(config.clone())));
+    let mut guard = WEBHOOK.write().unwrap_or_else(|e| e.into_inner());
+    *guard = webhook;
+}
+
+/// Report a security event to the webhook, if one is installed. Delivery
+/// runs in the background and never blocks or fails the caller.
+pub fn emit(event: SecurityEvent) {
+    let webhook = WEBHOOK.read().unwrap_or_else(|e| e.into_inner()).clone();
+    let Some(webhook) = webhook else {
+        return;
+    };
+    if !webhook.should_send(&event) {
+        return;
+    }
+    let Ok(runtime) = tokio::runtime::Handle::try_current() else {
+        tracing::debug!("security webhook: no async runtime, dropping event");
+        return;
+    };
+    let payload = webhook.payload(&event);
+    runtime.spawn(async move { webhook.send(payload).await });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn webhook(format: SecurityWebhookFormat, events: Vec<SecurityEventKind>) -> SecurityWebhook {
+        SecurityWebhook::new(SecurityWebhookConfig {
+            enabled: true,
+            url: "https://hooks.example.com/security".into(),
+            format,
+            events,
+            cooldown_secs: 60,
+        })
+    }
+
+    #[test]
+    fn filters_by_kind_and_drops_repeats_within_cooldown() {
+        let hook = webhook(
+            SecurityWebhookFormat::Json,
+            vec![
+                SecurityEventKind::KillSwitch,
+                SecurityEventKind::PolicyDenial,
+            ],
+        );
+        let denial = SecurityEvent::new(SecurityEventKind::PolicyDenial, Some("shell"), "denied");
+        assert!(hook.should_send(&denial));
+        assert!(!hook.should_send(&denial));
+        assert!(hook.should_send(&SecurityEvent::new(
+            SecurityEventKind::PolicyDenial,
+            Some("file_write"),
+            "denied"
+        )));
+        assert!(!hook.should_send(&SecurityEvent::new(
+            SecurityEventKind::BudgetExceeded,
+            None,
+            "daily token budget exhausted"
+        )));
+    }
+
+    #[test]
+    fn payload_matches_format() {
+        let event = SecurityEvent::new(
+            SecurityEventKind::KillSwitch,
+            Some("shell"),
+            "kill switch engaged",
+        );
+        let json = webhook(SecurityWebhookFormat::Json, Vec::new()).payload(&event);
+        assert_eq!(json["kind"], "kill_switch");
+        assert_eq!(json["tool"], "shell");
+        assert_eq!(json["message"], "kill switch engaged");
+
+        let slack = webhook(SecurityWebhookFormat::Slack, Vec::new()).payload(&event);
+        assert_eq!(
+            slack["text"],
+            ":rotating_light: zeroclaw security event `kill_switch` `shell`: kill switch engaged"
+        );
+    }
+}
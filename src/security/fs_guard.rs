@@ -0,0 +1,115 @@
+//! Filesystem-level read-only enforcement shared by every tool that touches
+//! files.
+//!
+//! [`SecurityPolicy::fs`] hands out an [`FsGuard`]. Tools open files through
+//! it instead of `std::fs` / `tokio::fs`: read handles are always opened with
+//! read-only flags, and write handles are refused while the effective
+//! autonomy is `ReadOnly`, whatever the tool's own policy check decided. A
+//! tool that forgets `enforce_tool_operation` still cannot write in
+//! read-only mode.
+
+use super::policy::{AutonomyLevel, SecurityPolicy};
+use std::io;
+use std::path::Path;
+
+/// File access bound to a [`SecurityPolicy`]'s autonomy level.
+#[derive(Debug, Clone, Copy)]
+pub struct FsGuard<'a> {
+    policy: &'a SecurityPolicy,
+}
+
+impl<'a> FsGuard<'a> {
+    pub fn new(policy: &'a SecurityPolicy) -> Self {
+        Self { policy }
+    }
+
+    /// Refuse any write to `path` while the agent is read-only.
+    pub fn check_writable(&self, path: &Path) -> io::Result<()> {
+        if self.policy.effective_autonomy() != AutonomyLevel::ReadOnly {
+            return Ok(());
+        }
+        tracing::warn!(
+            path = %path.display(),
+            "Refused a write handle in read-only mode; the calling tool skipped its policy check"
+        );
+        Err(io::Error::new(
+            io::ErrorKind::PermissionDenied,
+            format!(
+                "Security policy: read-only mode, refusing to open {} for writing",
+                path.display()
+            ),
+        ))
+    }
+
+    /// Open `path` with read-only flags.
+    pub async fn open_read(&self, path: &Path) -> io::Result<tokio::fs::File> {
+        tokio::fs::OpenOptions::new().read(true).open(path).await
+    }
+
+    /// Read all of `path` through a read-only handle.
+    pub async fn read(&self, path: &Path) -> io::Result<Vec<u8>> {
+        use tokio::io::AsyncReadExt;
+        let mut bytes = Vec::new();
+        self.open_read(path).await?.read_to_end(&mut bytes).await?;
+        Ok(bytes)
+    }
+
+    /// Read all of `path` as UTF-8 through a read-only handle.
+    pub async fn read_to_string(&self, path: &Path) -> io::Result<String> {
+        String::from_utf8(self.read(path).await?)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    /// Create or truncate `path` and write `contents` to it.
+    pub async fn write(&self, path: &Path, contents: impl AsRef<[u8]>) -> io::Result<()> {
+        self.check_writable(path)?;
+        tokio::fs::write(path, contents).await
+    }
+
+    /// Open `path` for appending, creating it if missing.
+    pub fn open_append(&self, path: &Path) -> io::Result<std::fs::File> {
+        self.check_writable(path)?;
+        std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+    }
+
+    /// Create `path` and any missing parents.
+    pub async fn create_dir_all(&self, path: &Path) -> io::Result<()> {
+        self.check_writable(path)?;
+        tokio::fs::create_dir_all(path).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn read_only_mode_gets_no_write_handles() {
+        let tmp = tempfile::tempdir().unwrap();
+        let target = tmp.path().join("notes.md");
+        std::fs::write(&target, "draft").unwrap();
+        let policy = SecurityPolicy {
+            autonomy: AutonomyLevel::ReadOnly,
+            workspace_dir: tmp.path().to_path_buf(),
+            ..SecurityPolicy::default()
+        };
+
+        let fs = policy.fs();
+        assert_eq!(fs.read_to_string(&target).await.unwrap(), "draft");
+        let err = fs.write(&target, "changed").await.unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::PermissionDenied);
+        assert!(fs.open_append(&target).is_err());
+        assert!(fs.create_dir_all(&tmp.path().join("new")).await.is_err());
+        assert_eq!(std::fs::read_to_string(&target).unwrap(), "draft");
+
+        let supervised = SecurityPolicy {
+            autonomy: AutonomyLevel::Supervised,
+            ..policy
+        };
+        supervised.fs().write(&target, "changed").await.unwrap();
+        assert_eq!(std::fs::read_to_string(&target).unwrap(), "changed");
+    }
+}
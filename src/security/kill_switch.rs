@@ -0,0 +1,28 @@
+//! Emergency kill switch: `<workspace>/ariadne/KILL`.
+//!
+//! While the file exists, [`SecurityPolicy::enforce_tool_operation`]
+//! refuses every `Act` operation whatever the autonomy level, and the agent
+//! tool loop pauses before its next step until the file is removed. Creating
+//! it needs nothing but filesystem access, so `touch` halts a misbehaving
+//! agent without a restart.
+//!
+//! [`SecurityPolicy::enforce_tool_operation`]: super::SecurityPolicy::enforce_tool_operation
+
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Kill switch location, relative to the workspace.
+pub const KILL_SWITCH_FILE: &str = "ariadne/KILL";
+
+/// How often a paused agent loop checks whether the switch was released.
+pub const KILL_SWITCH_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Absolute kill switch path for `workspace_dir`.
+pub fn kill_switch_path(workspace_dir: &Path) -> PathBuf {
+    workspace_dir.join(KILL_SWITCH_FILE)
+}
+
+/// Whether the kill switch for `workspace_dir` is engaged.
+pub fn is_engaged(workspace_dir: &Path) -> bool {
+    kill_switch_path(workspace_dir).exists()
+}
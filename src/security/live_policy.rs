@@ -0,0 +1,244 @@
+//! Hot-reloadable [`SecurityPolicy`] backed by a policy file.
+//!
+//! [`LiveSecurityPolicy`] holds the policy parsed from `[security]
+//! policy_file`, which replaces `[autonomy]` for the tools, and swaps in a
+//! new version as a whole when the file changes, so every reader sees one
+//! consistent policy. Versions share their budgets and denial streaks, so a
+//! reload never refunds or double-charges an action. An edit that fails
+//! validation is logged and the previous policy stays active.
+//!
+//! Tools hold their policy as a [`PolicyHandle`] and resolve it once per
+//! check with [`PolicyHandle::current`], which answers with the version
+//! [`LiveSecurityPolicy::scope`] pinned for the running call.
+
+use super::policy::SecurityPolicy;
+use parking_lot::{Mutex, RwLock};
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+tokio::task_local! {
+    /// Policy version the running tool call answers to; see
+    /// [`LiveSecurityPolicy::scope`].
+    static CALL_VERSION: Arc<SecurityPolicy>;
+}
+
+/// The security policy a tool checks its calls against. A tool keeps the
+/// version it was built with; [`Self::current`] swaps in the newer version
+/// pinned for the running call, so every check of the call, and every rule
+/// the tool reads, follows a reload.
+#[derive(Debug, Clone)]
+pub struct PolicyHandle(Arc<SecurityPolicy>);
+
+impl PolicyHandle {
+    /// The policy in effect for the running call: the version pinned by
+    /// [`LiveSecurityPolicy::scope`] when it is a version of this policy,
+    /// otherwise the policy the handle was built with.
+    pub fn current(&self) -> Arc<SecurityPolicy> {
+        CALL_VERSION
+            .try_with(|pinned| pinned.is_version_of(&self.0).then(|| Arc::clone(pinned)))
+            .ok()
+            .flatten()
+            .unwrap_or_else(|| Arc::clone(&self.0))
+    }
+}
+
+impl From<Arc<SecurityPolicy>> for PolicyHandle {
+    fn from(policy: Arc<SecurityPolicy>) -> Self {
+        Self(policy)
+    }
+}
+
+/// The version pinned for the running call, if any, for work the call
+/// hands to another task; see [`with_call_version`].
+pub fn pinned_call_version() -> Option<Arc<SecurityPolicy>> {
+    CALL_VERSION.try_with(Arc::clone).ok()
+}
+
+/// Run `future` with `pinned`, from [`pinned_call_version`], as the call's
+/// version.
+pub async fn with_call_version<F: Future>(
+    pinned: Option<Arc<SecurityPolicy>>,
+    future: F,
+) -> F::Output {
+    match pinned {
+        Some(version) => CALL_VERSION.scope(version, future).await,
+        None => future.await,
+    }
+}
+
+/// How often [`LiveSecurityPolicy::spawn_watcher`] checks the file.
+pub const POLICY_RELOAD_INTERVAL: Duration = Duration::from_secs(2);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct FileStamp {
+    modified: SystemTime,
+    len: u64,
+}
+
+fn file_stamp(path: &Path) -> Option<FileStamp> {
+    let metadata = std::fs::metadata(path).ok()?;
+    Some(FileStamp {
+        modified: metadata.modified().ok()?,
+        len: metadata.len(),
+    })
+}
+
+/// The active policy for a policy file, swapped atomically on reload.
+#[derive(Debug)]
+pub struct LiveSecurityPolicy {
+    path: PathBuf,
+    current: RwLock<Arc<SecurityPolicy>>,
+    /// Stamp of the file version last loaded, or last rejected.
+    stamp: Mutex<Option<FileStamp>>,
+}
+
+impl LiveSecurityPolicy {
+    /// Load the policy at `path`, sharing the budgets, usage tracker and
+    /// approval hook of `base` (normally the `[autonomy]` policy).
+    pub fn load(path: &Path, base: &SecurityPolicy) -> anyhow::Result<Self> {
+        let stamp = file_stamp(path);
+        let mut policy = SecurityPolicy::from_file(path)?;
+        policy.share_runtime_state(base);
+        Ok(Self {
+            path: path.to_path_buf(),
+            current: RwLock::new(Arc::new(policy)),
+            stamp: Mutex::new(stamp),
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// The policy in effect now. Callers keep using the returned snapshot
+    /// for the whole check; a reload only affects later calls.
+    pub fn current(&self) -> Arc<SecurityPolicy> {
+        Arc::clone(&self.current.read())
+    }
+
+    /// Run `future` (a tool call) so that every [`PolicyHandle`] to a
+    /// version of this policy, including the one the tool was built with,
+    /// resolves to the version in effect now for the whole call.
+    pub async fn scope<F: Future>(&self, future: F) -> F::Output {
+        CALL_VERSION.scope(self.current(), future).await
+    }
+
+    /// Reload the file if it changed since the last attempt. Returns
+    /// whether the active policy was replaced. The new version shares the
+    /// old one's runtime state, so a reload never refunds spent budget.
+    pub fn reload_if_changed(&self) -> anyhow::Result<bool> {
+        let stamp = file_stamp(&self.path);
+        {
+            let mut last = self.stamp.lock();
+            if *last == stamp {
+                return Ok(false);
+            }
+            // Recorded before parsing so a broken edit is reported once.
+            *last = stamp;
+        }
+
+        let mut next = SecurityPolicy::from_file(&self.path)?;
+        let mut current = self.current.write();
+        next.share_runtime_state(&current);
+        *current = Arc::new(next);
+        Ok(true)
+    }
+
+    /// Poll the file every `interval` on the current tokio runtime until
+    /// the last other reference to `self` is dropped.
+    pub fn spawn_watcher(self: &Arc<Self>, interval: Duration) -> tokio::task::JoinHandle<()> {
+        let live = Arc::downgrade(self);
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+            loop {
+                ticker.tick().await;
+                let Some(live) = live.upgrade() else {
+                    break;
+                };
+                match live.reload_if_changed() {
+                    Ok(true) => {
+                        tracing::info!("Reloaded security policy from {}", live.path.display())
+                    }
+                    Ok(false) => {}
+                    Err(e) => tracing::warn!("Keeping previous security policy: {e:#}"),
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::security::AutonomyLevel;
+
+    fn write(path: &Path, contents: &str) {
+        std::fs::write(path, contents).unwrap();
+        // Push the mtime forward so the change is seen even on filesystems
+        // with coarse timestamps.
+        let file = std::fs::File::options().write(true).open(path).unwrap();
+        file.set_modified(SystemTime::now() + Duration::from_secs(5))
+            .unwrap();
+    }
+
+    #[test]
+    fn reload_swaps_policy_and_keeps_spent_budget() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("policy.toml");
+        std::fs::write(&path, "workspace_dir = \".\"\nlevel = \"full\"\n").unwrap();
+        let live = LiveSecurityPolicy::load(&path, &SecurityPolicy::default()).unwrap();
+        assert!(!live.reload_if_changed().unwrap());
+
+        let before = live.current();
+        assert_eq!(before.autonomy, AutonomyLevel::Full);
+        before.record_action();
+
+        write(&path, "workspace_dir = \".\"\nlevel = \"readonly\"\n");
+        assert!(live.reload_if_changed().unwrap());
+        let after = live.current();
+        assert_eq!(after.autonomy, AutonomyLevel::ReadOnly);
+        assert_eq!(after.remaining_actions(), after.max_actions_per_hour - 1);
+        // Snapshots taken before the swap keep their rules but share the
+        // budget with the new version.
+        assert_eq!(before.autonomy, AutonomyLevel::Full);
+        before.record_action();
+        assert_eq!(after.remaining_actions(), after.max_actions_per_hour - 2);
+    }
+
+    #[tokio::test]
+    async fn scoped_calls_follow_the_newest_version() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("policy.toml");
+        std::fs::write(&path, "workspace_dir = \".\"\nlevel = \"full\"\n").unwrap();
+        let live = LiveSecurityPolicy::load(&path, &SecurityPolicy::default()).unwrap();
+        let built_with = PolicyHandle::from(live.current());
+
+        write(&path, "workspace_dir = \".\"\nlevel = \"readonly\"\n");
+        assert!(live.reload_if_changed().unwrap());
+        assert!(built_with.current().can_act());
+        assert!(!live.scope(async { built_with.current().can_act() }).await);
+        // Unrelated policies are not redirected.
+        let other = PolicyHandle::from(Arc::new(SecurityPolicy {
+            autonomy: AutonomyLevel::Full,
+            ..SecurityPolicy::default()
+        }));
+        assert!(live.scope(async { other.current().can_act() }).await);
+    }
+
+    #[test]
+    fn invalid_edit_keeps_previous_policy() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("policy.toml");
+        std::fs::write(&path, "workspace_dir = \".\"\ndenied_tools = [\"shell\"]\n").unwrap();
+        let live = LiveSecurityPolicy::load(&path, &SecurityPolicy::default()).unwrap();
+
+        write(&path, "workspace_dir = \".\"\nmax_actions_per_hour = 0\n");
+        assert!(live.reload_if_changed().is_err());
+        assert!(!live.current().is_tool_allowed("shell"));
+        // The broken version is not re-parsed on every poll.
+        assert!(!live.reload_if_changed().unwrap());
+    }
+}
@@ -22,27 +22,40 @@ pub mod audit;
 #[cfg(feature = "sandbox-bubblewrap")]
 pub mod bubblewrap;
 pub mod canary_guard;
+pub mod capability;
+pub mod content_scanner;
 pub mod detect;
 pub mod docker;
+pub mod egress;
+pub mod env_filter;
+pub mod escalation;
+pub mod events;
 pub mod file_link_guard;
+pub mod fs_guard;
 
 // Prompt injection defense (contributed from RustyClaw, MIT licensed)
 pub mod domain_matcher;
 pub mod estop;
 #[cfg(target_os = "linux")]
 pub mod firejail;
+pub mod kill_switch;
 #[cfg(feature = "sandbox-landlock")]
 pub mod landlock;
 pub mod leak_detector;
+pub mod live_policy;
 pub mod otp;
 pub mod pairing;
+pub mod pending_approvals;
 pub mod perplexity;
 pub mod policy;
+pub mod policy_builder;
 pub mod prompt_guard;
 pub mod roles;
 pub mod secrets;
+pub mod secrets_provider;
 pub mod semantic_guard;
 pub mod sensitive_paths;
+pub mod simulate;
 pub mod syscall_anomaly;
 pub mod traits;
 
@@ -50,22 +63,46 @@ pub mod traits;
 pub use audit::{AuditEvent, AuditEventType, AuditLogger};
 pub use canary_guard::CanaryGuard;
 #[allow(unused_imports)]
+pub use capability::CapabilityToken;
+#[allow(unused_imports)]
+pub use content_scanner::{ContentFinding, ContentScanner};
+#[allow(unused_imports)]
 pub use detect::create_sandbox;
 pub use domain_matcher::DomainMatcher;
 #[allow(unused_imports)]
+pub use egress::EgressGuard;
+#[allow(unused_imports)]
+pub use env_filter::EnvFilter;
+#[allow(unused_imports)]
 pub use estop::{EstopLevel, EstopManager, EstopState, ResumeSelector};
 #[allow(unused_imports)]
+pub use fs_guard::FsGuard;
+#[allow(unused_imports)]
+pub use live_policy::{LiveSecurityPolicy, PolicyHandle};
+#[allow(unused_imports)]
 pub use otp::OtpValidator;
 #[allow(unused_imports)]
 pub use pairing::PairingGuard;
 #[allow(unused_imports)]
+pub use pending_approvals::{
+    ApprovalStatus, ApprovalTicket, DeferredCall, PendingApproval, PendingApprovals,
+};
+#[allow(unused_imports)]
 pub use perplexity::{detect_adversarial_suffix, PerplexityAssessment};
+#[allow(unused_imports)]
+pub use policy::ApprovalHook;
 pub use policy::{AutonomyLevel, SecurityPolicy};
 #[allow(unused_imports)]
+pub use policy::{PolicyDecision, PolicyDenial, RateLimitScope};
+#[allow(unused_imports)]
+pub use policy_builder::SecurityPolicyBuilder;
+#[allow(unused_imports)]
 pub use roles::{RoleRegistry, ToolAccess};
 #[allow(unused_imports)]
 pub use secrets::SecretStore;
 #[allow(unused_imports)]
+pub use secrets_provider::{SecretRef, SecretsProvider, SecretsProviders};
+#[allow(unused_imports)]
 pub use semantic_guard::{GuardCorpusUpdateReport, SemanticGuard, SemanticGuardStartupStatus};
 #[allow(unused_imports)]
 pub use syscall_anomaly::{SyscallAnomalyAlert, SyscallAnomalyDetector, SyscallAnomalyKind};
@@ -0,0 +1,319 @@
+//! Operator approvals that have been requested but not yet answered.
+//!
+//! [`SecurityPolicy::request_approval`](super::SecurityPolicy::request_approval)
+//! hands the question to the policy's [`ApprovalHook`] on a background task
+//! and returns an [`ApprovalTicket`] straight away. The ticket reads
+//! [`ApprovalStatus::Pending`] until the operator answers, and the request is
+//! listed in [`PendingApprovals`] for that long, so a UI or a status command
+//! can show what the agent is waiting on. Dropping the ticket withdraws the
+//! request.
+//!
+//! A tool call held for approval is handed to [`PendingApprovals::defer`],
+//! which runs it once the operator answers and keeps its result until the
+//! agent collects it with [`PendingApprovals::collect`], or for
+//! [`DEFERRED_RESULT_TTL`] after it finished.
+
+use super::policy::ApprovalHook;
+use crate::approval::{ApprovalRequest, ApprovalResponse};
+use crate::tools::ToolResult;
+use chrono::{DateTime, Utc};
+use parking_lot::Mutex;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::sync::Arc;
+use tokio::sync::watch;
+
+/// How long a deferred call's result waits to be collected before it is
+/// dropped.
+pub const DEFERRED_RESULT_TTL: chrono::Duration = chrono::Duration::hours(1);
+
+/// Where an approval request stands.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ApprovalStatus {
+    /// Waiting for the operator.
+    Pending,
+    Approved,
+    /// Declined, or withdrawn before the operator answered.
+    Denied,
+}
+
+/// A request the operator has not answered yet.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PendingApproval {
+    pub id: String,
+    pub tool_name: String,
+    pub arguments: serde_json::Value,
+    pub requested_at: DateTime<Utc>,
+}
+
+/// A tool call held back until the operator answers its approval request.
+#[derive(Debug, Clone)]
+pub struct DeferredCall {
+    pub tool_name: String,
+    pub status: ApprovalStatus,
+    /// How the call ended; `None` while it waits for the operator or runs.
+    pub result: Option<ToolResult>,
+    pub finished_at: Option<DateTime<Utc>>,
+}
+
+/// Drop results nobody collected within [`DEFERRED_RESULT_TTL`].
+fn evict_uncollected(deferred: &mut BTreeMap<String, DeferredCall>) {
+    let cutoff = Utc::now() - DEFERRED_RESULT_TTL;
+    deferred.retain(|_, call| call.finished_at.is_none_or(|finished| finished > cutoff));
+}
+
+/// Unanswered approval requests, and the calls waiting on them, by id.
+/// Clones share both.
+#[derive(Debug, Clone, Default)]
+pub struct PendingApprovals {
+    requests: Arc<Mutex<BTreeMap<String, PendingApproval>>>,
+    deferred: Arc<Mutex<BTreeMap<String, DeferredCall>>>,
+}
+
+impl PendingApprovals {
+    /// Requests waiting for an answer, oldest first.
+    pub fn list(&self) -> Vec<PendingApproval> {
+        let mut pending: Vec<PendingApproval> = self.requests.lock().values().cloned().collect();
+        pending.sort_by_key(|request| request.requested_at);
+        pending
+    }
+
+    pub fn len(&self) -> usize {
+        self.requests.lock().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.requests.lock().is_empty()
+    }
+
+    /// Hold the call `ticket` was requested for: once the operator answers,
+    /// `run` gets the answer and produces the call's result, which stays
+    /// under the ticket's id until [`Self::collect`]ed. Returns that id.
+    /// Must be called within a tokio runtime.
+    pub(crate) fn defer<F, Fut>(&self, ticket: ApprovalTicket, tool_name: &str, run: F) -> String
+    where
+        F: FnOnce(ApprovalStatus) -> Fut + Send + 'static,
+        Fut: Future<Output = ToolResult> + Send,
+    {
+        let id = ticket.id().to_string();
+        let mut calls = self.deferred.lock();
+        evict_uncollected(&mut calls);
+        calls.insert(
+            id.clone(),
+            DeferredCall {
+                tool_name: tool_name.to_string(),
+                status: ApprovalStatus::Pending,
+                result: None,
+                finished_at: None,
+            },
+        );
+        drop(calls);
+        let deferred = Arc::clone(&self.deferred);
+        let key = id.clone();
+        tokio::spawn(async move {
+            let answer = ticket.answer().await;
+            if let Some(call) = deferred.lock().get_mut(&key) {
+                call.status = answer;
+            }
+            let result = run(answer).await;
+            if let Some(call) = deferred.lock().get_mut(&key) {
+                call.result = Some(result);
+                call.finished_at = Some(Utc::now());
+            }
+        });
+        id
+    }
+
+    /// Where the deferred call `id` stands. A finished call is handed out
+    /// once and then forgotten.
+    pub fn collect(&self, id: &str) -> Option<DeferredCall> {
+        let mut deferred = self.deferred.lock();
+        evict_uncollected(&mut deferred);
+        if deferred.get(id)?.result.is_some() {
+            return deferred.remove(id);
+        }
+        deferred.get(id).cloned()
+    }
+
+    /// Ask `hook` about `request` on a background task. Must be called
+    /// within a tokio runtime.
+    pub(crate) fn submit(
+        &self,
+        hook: Arc<dyn ApprovalHook>,
+        request: ApprovalRequest,
+    ) -> ApprovalTicket {
+        let id = uuid::Uuid::new_v4().to_string();
+        self.requests.lock().insert(
+            id.clone(),
+            PendingApproval {
+                id: id.clone(),
+                tool_name: request.tool_name.clone(),
+                arguments: request.arguments.clone(),
+                requested_at: Utc::now(),
+            },
+        );
+        let listed = Listed {
+            requests: Arc::clone(&self.requests),
+            id: id.clone(),
+        };
+        let (status, receiver) = watch::channel(ApprovalStatus::Pending);
+        let task = tokio::spawn(async move {
+            let answer = hook.request_approval(&request).await;
+            // Unlisted before the status changes, so a caller that saw the
+            // answer never finds the request still pending.
+            drop(listed);
+            status.send_replace(match answer {
+                ApprovalResponse::Yes | ApprovalResponse::Always => ApprovalStatus::Approved,
+                ApprovalResponse::No => ApprovalStatus::Denied,
+            });
+        });
+        ApprovalTicket {
+            id,
+            status: receiver,
+            task: task.abort_handle(),
+        }
+    }
+}
+
+/// Removes a request from the list when its task ends, answered or aborted.
+struct Listed {
+    requests: Arc<Mutex<BTreeMap<String, PendingApproval>>>,
+    id: String,
+}
+
+impl Drop for Listed {
+    fn drop(&mut self) {
+        self.requests.lock().remove(&self.id);
+    }
+}
+
+/// Handle on one approval request.
+#[derive(Debug)]
+pub struct ApprovalTicket {
+    id: String,
+    status: watch::Receiver<ApprovalStatus>,
+    task: tokio::task::AbortHandle,
+}
+
+impl ApprovalTicket {
+    /// The id the request is listed under in [`PendingApprovals`].
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// The status now, without waiting.
+    pub fn status(&self) -> ApprovalStatus {
+        *self.status.borrow()
+    }
+
+    /// Wait for the operator's answer. A hook that panics counts as a
+    /// denial.
+    pub async fn answer(mut self) -> ApprovalStatus {
+        match self
+            .status
+            .wait_for(|status| *status != ApprovalStatus::Pending)
+            .await
+        {
+            Ok(status) => *status,
+            Err(_) => ApprovalStatus::Denied,
+        }
+    }
+}
+
+impl Drop for ApprovalTicket {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use tokio::sync::Notify;
+
+    /// Answers once released.
+    #[derive(Debug)]
+    struct GatedHook {
+        release: Notify,
+        answer: ApprovalResponse,
+    }
+
+    #[async_trait]
+    impl ApprovalHook for GatedHook {
+        async fn request_approval(&self, _request: &ApprovalRequest) -> ApprovalResponse {
+            self.release.notified().await;
+            self.answer
+        }
+    }
+
+    fn request() -> ApprovalRequest {
+        ApprovalRequest {
+            tool_name: "shell".into(),
+            arguments: serde_json::json!({"command": "ls"}),
+        }
+    }
+
+    #[tokio::test]
+    async fn request_is_pending_until_the_operator_answers() {
+        let pending = PendingApprovals::default();
+        let hook = Arc::new(GatedHook {
+            release: Notify::new(),
+            answer: ApprovalResponse::Yes,
+        });
+        let ticket = pending.submit(hook.clone(), request());
+
+        assert_eq!(ticket.status(), ApprovalStatus::Pending);
+        let listed = pending.list();
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].id, ticket.id());
+        assert_eq!(listed[0].tool_name, "shell");
+
+        hook.release.notify_one();
+        assert_eq!(ticket.answer().await, ApprovalStatus::Approved);
+        assert!(pending.is_empty());
+    }
+
+    #[tokio::test]
+    async fn uncollected_results_expire() {
+        let pending = PendingApprovals::default();
+        pending.deferred.lock().insert(
+            "stale".into(),
+            DeferredCall {
+                tool_name: "shell".into(),
+                status: ApprovalStatus::Approved,
+                result: Some(ToolResult {
+                    success: true,
+                    output: "ran".into(),
+                    error: None,
+                }),
+                finished_at: Some(Utc::now() - DEFERRED_RESULT_TTL - chrono::Duration::minutes(1)),
+            },
+        );
+        assert!(pending.collect("stale").is_none());
+        assert!(pending.deferred.lock().is_empty());
+    }
+
+    #[tokio::test]
+    async fn dropping_the_ticket_withdraws_the_request() {
+        let pending = PendingApprovals::default();
+        let hook = Arc::new(GatedHook {
+            release: Notify::new(),
+            answer: ApprovalResponse::Yes,
+        });
+        let ticket = pending.submit(hook, request());
+        assert_eq!(pending.len(), 1);
+
+        drop(ticket);
+        // The aborted task drops its listing on the next scheduler turn.
+        for _ in 0..100 {
+            if pending.is_empty() {
+                break;
+            }
+            tokio::task::yield_now().await;
+        }
+        assert!(pending.is_empty());
+    }
+}
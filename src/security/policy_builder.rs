@@ -0,0 +1,289 @@
+//! Fluent construction of a [`SecurityPolicy`] for library embedders.
+//!
+//! [`SecurityPolicy::from_config`] covers the CLI and daemon, which start
+//! from a validated `config.toml`. Programs that build a policy in code use
+//! [`SecurityPolicyBuilder`] instead of struct-update syntax over
+//! [`SecurityPolicy::default`], so mistakes such as a missing workspace or a
+//! zero rate limit surface as errors from [`SecurityPolicyBuilder::build`]
+//! rather than as an agent that refuses every action.
+
+use super::audit::AuditLogger;
+use super::policy::{
+    normalize_host_rules, normalize_path_permissions, normalize_tool_rules, ApprovalHook,
+    AutonomyLevel, CommandPolicy, OperatingWindow, SecurityPolicy,
+};
+use crate::config::PathPermission;
+use anyhow::Result;
+use std::collections::{BTreeSet, HashMap};
+use std::path::PathBuf;
+use std::sync::Arc;
+
+/// Builder for [`SecurityPolicy`], starting from its defaults.
+///
+/// A workspace directory is required and must exist. Limits set to zero are
+/// rejected; use the matching `unlimited_*` method to lift a limit instead.
+pub struct SecurityPolicyBuilder {
+    policy: SecurityPolicy,
+    workspace_dir: Option<PathBuf>,
+    zero_limits: BTreeSet<String>,
+}
+
+impl SecurityPolicyBuilder {
+    pub fn new() -> Self {
+        Self {
+            policy: SecurityPolicy::default(),
+            workspace_dir: None,
+            zero_limits: BTreeSet::new(),
+        }
+    }
+
+    fn limit(&mut self, name: impl Into<String>, value: u64) {
+        let name = name.into();
+        if value == 0 {
+            self.zero_limits.insert(name);
+        } else {
+            self.zero_limits.remove(&name);
+        }
+    }
+
+    pub fn autonomy(mut self, autonomy: AutonomyLevel) -> Self {
+        self.policy.autonomy = autonomy;
+        self
+    }
+
+    pub fn workspace_dir(mut self, workspace_dir: impl Into<PathBuf>) -> Self {
+        self.workspace_dir = Some(workspace_dir.into());
+        self
+    }
+
+    pub fn workspace_only(mut self, workspace_only: bool) -> Self {
+        self.policy.workspace_only = workspace_only;
+        self
+    }
+
+    pub fn allowed_commands(mut self, commands: Vec<String>) -> Self {
+        self.policy.allowed_commands = commands;
+        self
+    }
+
+    pub fn forbidden_paths(mut self, paths: Vec<String>) -> Self {
+        self.policy.forbidden_paths = paths;
+        self
+    }
+
+    pub fn allowed_roots(mut self, roots: Vec<PathBuf>) -> Self {
+        self.policy.allowed_roots = roots;
+        self
+    }
+
+    pub fn require_approval_for_medium_risk(mut self, required: bool) -> Self {
+        self.policy.require_approval_for_medium_risk = required;
+        self
+    }
+
+    pub fn block_high_risk_commands(mut self, block: bool) -> Self {
+        self.policy.block_high_risk_commands = block;
+        self
+    }
+
+    pub fn max_actions_per_hour(mut self, limit: u32) -> Self {
+        self.limit("max_actions_per_hour", limit.into());
+        self.policy.max_actions_per_hour = limit;
+        self
+    }
+
+    pub fn unlimited_actions_per_hour(mut self) -> Self {
+        self.zero_limits.remove("max_actions_per_hour");
+        self.policy.max_actions_per_hour = u32::MAX;
+        self
+    }
+
+    /// Let `burst` actions run back to back before `max_actions_per_hour`
+    /// paces them. Defaults to `max_actions_per_hour`.
+    pub fn max_actions_burst(mut self, burst: u32) -> Self {
+        self.limit("max_actions_burst", burst.into());
+        self.policy.max_actions_burst = burst;
+        self
+    }
+
+    pub fn max_actions_per_session(mut self, limit: u32) -> Self {
+        self.limit("max_actions_per_session", limit.into());
+        self.policy.max_actions_per_session = limit;
+        self
+    }
+
+    pub fn unlimited_actions_per_session(mut self) -> Self {
+        self.zero_limits.remove("max_actions_per_session");
+        self.policy.max_actions_per_session = 0;
+        self
+    }
+
+    pub fn max_tokens_per_day(mut self, limit: u64) -> Self {
+        self.limit("max_tokens_per_day", limit);
+        self.policy.max_tokens_per_day = limit;
+        self
+    }
+
+    pub fn unlimited_tokens_per_day(mut self) -> Self {
+        self.zero_limits.remove("max_tokens_per_day");
+        self.policy.max_tokens_per_day = 0;
+        self
+    }
+
+    pub fn max_memory_bytes(mut self, limit: u64) -> Self {
+        self.limit("max_memory_bytes", limit);
+        self.policy.max_memory_bytes = limit;
+        self
+    }
+
+    pub fn unlimited_memory_bytes(mut self) -> Self {
+        self.zero_limits.remove("max_memory_bytes");
+        self.policy.max_memory_bytes = 0;
+        self
+    }
+
+    /// Cap `tool` at `limit` actions per hour, on top of
+    /// [`Self::max_actions_per_hour`].
+    pub fn per_tool_limit(mut self, tool: &str, limit: u32) -> Self {
+        let tool = tool.trim().to_ascii_lowercase();
+        self.limit(format!("per_tool_limits.{tool}"), limit.into());
+        self.policy.per_tool_limits.insert(tool, limit);
+        self
+    }
+
+    pub fn allowed_tools(mut self, tools: &[String]) -> Self {
+        self.policy.allowed_tools = normalize_tool_rules(tools);
+        self
+    }
+
+    pub fn denied_tools(mut self, tools: &[String]) -> Self {
+        self.policy.denied_tools = normalize_tool_rules(tools);
+        self
+    }
+
+    pub fn allowed_hosts(mut self, hosts: &[String]) -> Self {
+        self.policy.allowed_hosts = normalize_host_rules(hosts);
+        self
+    }
+
+    pub fn denied_hosts(mut self, hosts: &[String]) -> Self {
+        self.policy.denied_hosts = normalize_host_rules(hosts);
+        self
+    }
+
+    pub fn path_permissions(mut self, rules: &HashMap<String, PathPermission>) -> Self {
+        self.policy.path_permissions = normalize_path_permissions(rules);
+        self
+    }
+
+    pub fn operating_window(mut self, window: OperatingWindow) -> Self {
+        self.policy.operating_window = Some(window);
+        self
+    }
+
+    pub fn command_policy(mut self, command_policy: CommandPolicy) -> Self {
+        self.policy.command_policy = command_policy;
+        self
+    }
+
+    pub fn dry_run(mut self, dry_run: bool) -> Self {
+        self.policy.dry_run = dry_run;
+        self
+    }
+
+    pub fn approval_hook(mut self, hook: Arc<dyn ApprovalHook>) -> Self {
+        self.policy.approval_hook = Some(hook);
+        self
+    }
+
+    /// Record every call of the default tool registry in `logger`.
+    pub fn audit_logger(mut self, logger: Arc<AuditLogger>) -> Self {
+        self.policy.audit_logger = Some(logger);
+        self
+    }
+
+    pub fn build(self) -> Result<SecurityPolicy> {
+        let workspace_dir = self
+            .workspace_dir
+            .ok_or_else(|| anyhow::anyhow!("workspace_dir is required"))?;
+        if !workspace_dir.is_dir() {
+            anyhow::bail!(
+                "workspace_dir '{}' does not exist or is not a directory",
+                workspace_dir.display()
+            );
+        }
+        if !self.zero_limits.is_empty() {
+            let limits = self.zero_limits.into_iter().collect::<Vec<_>>();
+            anyhow::bail!(
+                "limits must be non-zero (use the unlimited_* methods to lift a limit): {}",
+                limits.join(", ")
+            );
+        }
+
+        Ok(SecurityPolicy {
+            workspace_dir,
+            ..self.policy
+        })
+    }
+}
+
+impl Default for SecurityPolicyBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn build_requires_an_existing_workspace() {
+        let err = SecurityPolicyBuilder::new().build().unwrap_err();
+        assert!(err.to_string().contains("workspace_dir is required"));
+
+        let tmp = tempfile::tempdir().unwrap();
+        let missing = tmp.path().join("missing");
+        let err = SecurityPolicy::builder()
+            .workspace_dir(&missing)
+            .build()
+            .unwrap_err();
+        assert!(err.to_string().contains("does not exist"));
+
+        let policy = SecurityPolicy::builder()
+            .workspace_dir(tmp.path())
+            .autonomy(AutonomyLevel::Full)
+            .denied_hosts(&[" Evil.Example.com ".into()])
+            .build()
+            .unwrap();
+        assert_eq!(policy.workspace_dir, tmp.path());
+        assert_eq!(policy.autonomy, AutonomyLevel::Full);
+        assert_eq!(policy.denied_hosts, ["evil.example.com"]);
+    }
+
+    #[test]
+    fn zero_limits_are_rejected_unless_explicitly_unlimited() {
+        let tmp = tempfile::tempdir().unwrap();
+        let err = SecurityPolicy::builder()
+            .workspace_dir(tmp.path())
+            .max_actions_per_hour(0)
+            .per_tool_limit("Shell", 0)
+            .build()
+            .unwrap_err();
+        assert!(err
+            .to_string()
+            .ends_with("max_actions_per_hour, per_tool_limits.shell"));
+
+        let policy = SecurityPolicy::builder()
+            .workspace_dir(tmp.path())
+            .max_actions_per_hour(0)
+            .unlimited_actions_per_hour()
+            .max_actions_per_session(0)
+            .max_actions_per_session(5)
+            .build()
+            .unwrap();
+        assert_eq!(policy.max_actions_per_hour, u32::MAX);
+        assert_eq!(policy.max_actions_per_session, 5);
+        assert!(policy.record_action());
+    }
+}
@@ -38,19 +38,57 @@ const NONCE_LEN: usize = 12;
 pub struct SecretStore {
     /// Path to the key file (`~/.zeroclaw/.secret_key`)
     key_path: PathBuf,
+    /// Key held in memory instead of read from `key_path`.
+    inline_key: Option<InlineKey>,
     /// Whether encryption is enabled
     enabled: bool,
 }
 
+/// A key held in memory, kept out of `Debug` output.
+#[derive(Clone)]
+struct InlineKey(Vec<u8>);
+
+impl std::fmt::Debug for InlineKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("InlineKey(<redacted>)")
+    }
+}
+
 impl SecretStore {
     /// Create a new secret store rooted at the given directory.
     pub fn new(zeroclaw_dir: &Path, enabled: bool) -> Self {
         Self {
             key_path: zeroclaw_dir.join(".secret_key"),
+            inline_key: None,
             enabled,
         }
     }
 
+    /// Create an encrypting store backed by an explicit key file, created on
+    /// first use if missing.
+    pub fn with_key_path(key_path: PathBuf) -> Self {
+        Self {
+            key_path,
+            inline_key: None,
+            enabled: true,
+        }
+    }
+
+    /// Create an encrypting store for a key that never touches disk, such as
+    /// one derived from a passphrase. The key must be 32 bytes.
+    pub fn with_key(key: Vec<u8>) -> Result<Self> {
+        anyhow::ensure!(
+            key.len() == KEY_LEN,
+            "Encryption key must be {KEY_LEN} bytes, got {}",
+            key.len()
+        );
+        Ok(Self {
+            key_path: PathBuf::new(),
+            inline_key: Some(InlineKey(key)),
+            enabled: true,
+        })
+    }
+
     /// Encrypt a plaintext secret. Returns hex-encoded ciphertext prefixed with `enc2:`.
     /// Format: `enc2:<hex(nonce ‖ ciphertext ‖ tag)>` (12 + N + 16 bytes).
     /// If encryption is disabled, returns the plaintext as-is.
@@ -170,6 +208,9 @@ impl SecretStore {
 
     /// Load the encryption key from disk, or create one if it doesn't exist.
     fn load_or_create_key(&self) -> Result<Vec<u8>> {
+        if let Some(InlineKey(key)) = &self.inline_key {
+            return Ok(key.clone());
+        }
         if self.key_path.exists() {
             let hex_key =
                 fs::read_to_string(&self.key_path).context("Failed to read secret key file")?;
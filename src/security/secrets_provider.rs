@@ -0,0 +1,497 @@
+//! Pluggable backends for secrets referenced from config.
+//!
+//! Any secret field in `config.toml` (provider API keys, channel tokens, the
+//! security webhook URL, ...) may hold a reference instead of the value:
+//!
+//! - `${keychain:<name>}` — the OS keychain, under the
+//!   `secrets.keychain_service` service: the macOS Keychain via `security`,
+//!   the Windows Credential Manager via PowerShell, or the freedesktop Secret
+//!   Service (GNOME Keyring, KWallet) via `secret-tool` on other Unix systems.
+//! - `${env:<VAR>}` — an environment variable.
+//! - `${secretfile:<name>}` — `~/.zeroclaw/secrets.json`, each value
+//!   encrypted with the same ChaCha20-Poly1305 key as [`SecretStore`].
+//!
+//! Only the whole `${...}` form is a reference, so a literal secret that
+//! happens to start with `env:` or `keychain:` is kept as it is. Secret
+//! values never appear on a command line: the keychain clients read them
+//! from stdin.
+//!
+//! References are resolved when the config loads and written back unchanged
+//! when it is saved, so the secret itself never lands in the config file.
+//! `zeroclaw secrets set <reference>` stores a value in the keychain or file.
+
+use super::secrets::SecretStore;
+use anyhow::{Context, Result};
+use std::collections::BTreeMap;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+/// File of the `secretfile:` backend, next to `config.toml`.
+pub const SECRETS_FILE: &str = "secrets.json";
+
+/// A backend that stores secrets by name.
+///
+/// Implementations must be `Send + Sync`; the config loader and the
+/// `zeroclaw secrets` command share one set of backends.
+pub trait SecretsProvider: Send + Sync {
+    /// Backend name, as used in references (e.g. `"keychain"`).
+    fn name(&self) -> &str;
+
+    /// The secret stored under `key`, or `None` when there is none.
+    fn get(&self, key: &str) -> Result<Option<String>>;
+
+    /// Store `value` under `key`, replacing any previous value.
+    fn set(&self, key: &str, value: &str) -> Result<()>;
+
+    /// Remove the secret stored under `key`; removing a missing one is not an
+    /// error.
+    fn delete(&self, key: &str) -> Result<()>;
+}
+
+/// Which backend a [`SecretRef`] points at.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SecretBackend {
+    Keychain,
+    Env,
+    File,
+}
+
+impl SecretBackend {
+    fn prefix(self) -> &'static str {
+        match self {
+            Self::Keychain => "keychain:",
+            Self::Env => "env:",
+            Self::File => "secretfile:",
+        }
+    }
+}
+
+/// A `${keychain:...}`, `${env:...}`, or `${secretfile:...}` reference held
+/// in a config field.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SecretRef {
+    pub backend: SecretBackend,
+    pub key: String,
+}
+
+impl SecretRef {
+    /// Parse `value` as a `${backend:key}` reference; anything else is a
+    /// literal secret.
+    pub fn parse(value: &str) -> Option<Self> {
+        Self::parse_bare(value.strip_prefix("${")?.strip_suffix('}')?)
+    }
+
+    /// Parse `backend:key` without the `${}`, as the `zeroclaw secrets`
+    /// command also accepts it.
+    pub fn parse_bare(value: &str) -> Option<Self> {
+        [
+            SecretBackend::Keychain,
+            SecretBackend::Env,
+            SecretBackend::File,
+        ]
+        .into_iter()
+        .find_map(|backend| {
+            let key = value.strip_prefix(backend.prefix())?;
+            let valid = !key.is_empty() && !key.chars().any(|c| c.is_whitespace() || c == '}');
+            valid.then(|| Self {
+                backend,
+                key: key.to_string(),
+            })
+        })
+    }
+}
+
+impl std::fmt::Display for SecretRef {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "${{{}{}}}", self.backend.prefix(), self.key)
+    }
+}
+
+/// The three backends, as configured for one ZeroClaw directory.
+pub struct SecretsProviders {
+    keychain: KeychainSecrets,
+    env: EnvSecrets,
+    file: EncryptedFileSecrets,
+}
+
+impl SecretsProviders {
+    pub fn new(zeroclaw_dir: &Path, keychain_service: &str) -> Self {
+        Self {
+            keychain: KeychainSecrets::new(keychain_service),
+            env: EnvSecrets,
+            file: EncryptedFileSecrets::new(zeroclaw_dir),
+        }
+    }
+
+    pub fn provider(&self, backend: SecretBackend) -> &dyn SecretsProvider {
+        match backend {
+            SecretBackend::Keychain => &self.keychain,
+            SecretBackend::Env => &self.env,
+            SecretBackend::File => &self.file,
+        }
+    }
+
+    /// The secret `reference` points at; a missing one is an error.
+    pub fn resolve(&self, reference: &SecretRef) -> Result<String> {
+        self.provider(reference.backend)
+            .get(&reference.key)
+            .with_context(|| format!("reading secret {reference}"))?
+            .ok_or_else(|| anyhow::anyhow!("secret {reference} is not set"))
+    }
+}
+
+/// Environment variables. Read-only: they are set outside ZeroClaw.
+pub struct EnvSecrets;
+
+impl SecretsProvider for EnvSecrets {
+    fn name(&self) -> &str {
+        "env"
+    }
+
+    fn get(&self, key: &str) -> Result<Option<String>> {
+        Ok(std::env::var(key).ok().filter(|value| !value.is_empty()))
+    }
+
+    fn set(&self, key: &str, _value: &str) -> Result<()> {
+        anyhow::bail!("environment secrets are read-only; export {key} in the agent's environment")
+    }
+
+    fn delete(&self, key: &str) -> Result<()> {
+        anyhow::bail!("environment secrets are read-only; unset {key} in the agent's environment")
+    }
+}
+
+/// The OS keychain, driven through the platform's command-line client.
+pub struct KeychainSecrets {
+    service: String,
+}
+
+impl KeychainSecrets {
+    pub fn new(service: &str) -> Self {
+        Self {
+            service: service.to_string(),
+        }
+    }
+
+    fn run(&self, mut cmd: Command, stdin: Option<&str>) -> Result<std::process::Output> {
+        let program = cmd.get_program().to_string_lossy().into_owned();
+        cmd.stdin(if stdin.is_some() {
+            Stdio::piped()
+        } else {
+            Stdio::null()
+        })
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+        let mut child = cmd
+            .spawn()
+            .with_context(|| format!("running `{program}`; is it installed?"))?;
+        if let (Some(input), Some(mut pipe)) = (stdin, child.stdin.take()) {
+            pipe.write_all(input.as_bytes())?;
+        }
+        Ok(child.wait_with_output()?)
+    }
+
+    fn check(output: &std::process::Output, action: &str) -> Result<()> {
+        if output.status.success() {
+            return Ok(());
+        }
+        anyhow::bail!(
+            "keychain {action} failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        )
+    }
+
+    /// PowerShell that opens the Credential Manager vault as `$vault`, with
+    /// the item for `key` as `$item` (or exiting with [`ITEM_NOT_FOUND`]
+    /// when `lookup` is set and there is none), then runs `script`.
+    fn windows_command(&self, key: &str, lookup: bool, script: &str) -> Command {
+        let find = if lookup {
+            format!(
+                "try {{ $item = $vault.Retrieve({}, {}) }} catch {{ exit {ITEM_NOT_FOUND} }}; ",
+                powershell_quote(&self.service),
+                powershell_quote(key)
+            )
+        } else {
+            String::new()
+        };
+        let mut cmd = Command::new("powershell");
+        cmd.args([
+            "-NoProfile",
+            "-NonInteractive",
+            "-Command",
+            &format!(
+                "$ErrorActionPreference = 'Stop'; \
+                 [void][Windows.Security.Credentials.PasswordVault, Windows.Security.Credentials, ContentType = WindowsRuntime]; \
+                 $vault = New-Object Windows.Security.Credentials.PasswordVault; {find}{script}"
+            ),
+        ]);
+        cmd
+    }
+}
+
+/// Exit status of macOS `security`, and of the Windows script, when no item
+/// matches.
+const ITEM_NOT_FOUND: i32 = 44;
+
+/// Quote `arg` for a command line read by `security -i`.
+fn security_quote(arg: &str) -> String {
+    format!("\"{}\"", arg.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// Quote `arg` as a PowerShell string literal.
+fn powershell_quote(arg: &str) -> String {
+    format!("'{}'", arg.replace('\'', "''"))
+}
+
+impl SecretsProvider for KeychainSecrets {
+    fn name(&self) -> &str {
+        "keychain"
+    }
+
+    fn get(&self, key: &str) -> Result<Option<String>> {
+        let output = if cfg!(target_os = "macos") {
+            let mut cmd = Command::new("security");
+            cmd.args([
+                "find-generic-password",
+                "-s",
+                &self.service,
+                "-a",
+                key,
+                "-w",
+            ]);
+            self.run(cmd, None)?
+        } else if cfg!(windows) {
+            let cmd = self.windows_command(
+                key,
+                true,
+                "$item.RetrievePassword(); \
+                 $bytes = [Text.Encoding]::UTF8.GetBytes($item.Password); \
+                 [Console]::OpenStandardOutput().Write($bytes, 0, $bytes.Length)",
+            );
+            self.run(cmd, None)?
+        } else {
+            let mut cmd = Command::new("secret-tool");
+            cmd.args(["lookup", "service", &self.service, "account", key]);
+            let output = self.run(cmd, None)?;
+            // secret-tool exits 1 with no output when nothing matches.
+            if output.stdout.is_empty() {
+                return Ok(None);
+            }
+            output
+        };
+        if output.status.code() == Some(ITEM_NOT_FOUND) {
+            return Ok(None);
+        }
+        Self::check(&output, "lookup")?;
+        let value = String::from_utf8(output.stdout).context("keychain value is not UTF-8")?;
+        // `security -w` ends the value with a newline.
+        if cfg!(target_os = "macos") {
+            return Ok(Some(value.strip_suffix('\n').unwrap_or(&value).to_string()));
+        }
+        Ok(Some(value))
+    }
+
+    fn set(&self, key: &str, value: &str) -> Result<()> {
+        if cfg!(target_os = "macos") {
+            // `security` only takes the password as an argument, so the
+            // command goes through its interactive mode on stdin rather than
+            // the process list.
+            if value.contains(['\n', '\r']) {
+                anyhow::bail!("the macOS keychain backend cannot store multi-line secrets");
+            }
+            let line = [
+                "add-generic-password",
+                "-U",
+                "-s",
+                &self.service,
+                "-a",
+                key,
+                "-w",
+                value,
+            ]
+            .map(security_quote)
+            .join(" ");
+            let mut cmd = Command::new("security");
+            cmd.arg("-i");
+            let output = self.run(cmd, Some(&format!("{line}\n")))?;
+            // Interactive mode exits 0 whatever the command did; failures
+            // only show up on stderr.
+            if !output.stderr.is_empty() {
+                anyhow::bail!(
+                    "keychain store failed: {}",
+                    String::from_utf8_lossy(&output.stderr).trim()
+                );
+            }
+            Self::check(&output, "store")
+        } else if cfg!(windows) {
+            let cmd = self.windows_command(
+                key,
+                false,
+                &format!(
+                    "$vault.Add((New-Object Windows.Security.Credentials.PasswordCredential -ArgumentList {}, {}, \
+                     (New-Object IO.StreamReader([Console]::OpenStandardInput(), [Text.Encoding]::UTF8)).ReadToEnd()))",
+                    powershell_quote(&self.service),
+                    powershell_quote(key)
+                ),
+            );
+            Self::check(&self.run(cmd, Some(value))?, "store")
+        } else {
+            let mut cmd = Command::new("secret-tool");
+            cmd.args(["store", "--label", &format!("{}: {key}", self.service)])
+                .args(["service", &self.service, "account", key]);
+            Self::check(&self.run(cmd, Some(value))?, "store")
+        }
+    }
+
+    fn delete(&self, key: &str) -> Result<()> {
+        let output = if cfg!(target_os = "macos") {
+            let mut cmd = Command::new("security");
+            cmd.args(["delete-generic-password", "-s", &self.service, "-a", key]);
+            self.run(cmd, None)?
+        } else if cfg!(windows) {
+            self.run(
+                self.windows_command(key, true, "$vault.Remove($item)"),
+                None,
+            )?
+        } else {
+            let mut cmd = Command::new("secret-tool");
+            cmd.args(["clear", "service", &self.service, "account", key]);
+            self.run(cmd, None)?
+        };
+        if output.status.code() == Some(ITEM_NOT_FOUND) {
+            return Ok(());
+        }
+        Self::check(&output, "delete")
+    }
+}
+
+/// `~/.zeroclaw/secrets.json`: names mapped to `enc2:` values, always
+/// encrypted whatever `secrets.encrypt` says.
+pub struct EncryptedFileSecrets {
+    path: PathBuf,
+    store: SecretStore,
+}
+
+impl EncryptedFileSecrets {
+    pub fn new(zeroclaw_dir: &Path) -> Self {
+        Self {
+            path: zeroclaw_dir.join(SECRETS_FILE),
+            store: SecretStore::new(zeroclaw_dir, true),
+        }
+    }
+
+    fn load(&self) -> Result<BTreeMap<String, String>> {
+        let raw = match std::fs::read_to_string(&self.path) {
+            Ok(raw) => raw,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(BTreeMap::new()),
+            Err(e) => {
+                return Err(e).with_context(|| format!("reading {}", self.path.display()));
+            }
+        };
+        serde_json::from_str(&raw).with_context(|| format!("parsing {}", self.path.display()))
+    }
+
+    fn save(&self, secrets: &BTreeMap<String, String>) -> Result<()> {
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let tmp = self.path.with_extension("json.tmp");
+        let mut file = std::fs::File::create(&tmp)?;
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            file.set_permissions(std::fs::Permissions::from_mode(0o600))?;
+        }
+        file.write_all(&serde_json::to_vec_pretty(secrets)?)?;
+        file.sync_all()?;
+        std::fs::rename(&tmp, &self.path)
+            .with_context(|| format!("writing {}", self.path.display()))
+    }
+}
+
+impl SecretsProvider for EncryptedFileSecrets {
+    fn name(&self) -> &str {
+        "secretfile"
+    }
+
+    fn get(&self, key: &str) -> Result<Option<String>> {
+        self.load()?
+            .get(key)
+            .map(|value| self.store.decrypt(value))
+            .transpose()
+    }
+
+    fn set(&self, key: &str, value: &str) -> Result<()> {
+        let mut secrets = self.load()?;
+        secrets.insert(key.to_string(), self.store.encrypt(value)?);
+        self.save(&secrets)
+    }
+
+    fn delete(&self, key: &str) -> Result<()> {
+        let mut secrets = self.load()?;
+        if secrets.remove(key).is_some() {
+            self.save(&secrets)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_references_and_leaves_literals_alone() {
+        assert_eq!(
+            SecretRef::parse("${keychain:openrouter}"),
+            Some(SecretRef {
+                backend: SecretBackend::Keychain,
+                key: "openrouter".into(),
+            })
+        );
+        assert_eq!(
+            SecretRef::parse("${env:OPENAI_API_KEY}")
+                .unwrap()
+                .to_string(),
+            "${env:OPENAI_API_KEY}"
+        );
+        assert_eq!(SecretRef::parse("sk-or-v1-abc"), None);
+        // Literal secrets that merely look like a backend prefix.
+        assert_eq!(SecretRef::parse("env:OPENAI_API_KEY"), None);
+        assert_eq!(SecretRef::parse("keychain:hunter2"), None);
+        assert_eq!(SecretRef::parse("${secretfile:}"), None);
+        assert_eq!(SecretRef::parse("${env:has space}"), None);
+        assert_eq!(
+            SecretRef::parse_bare("secretfile:telegram"),
+            SecretRef::parse("${secretfile:telegram}")
+        );
+    }
+
+    #[test]
+    fn keychain_arguments_are_quoted_for_their_client() {
+        assert_eq!(security_quote(r#"a "b" \c"#), r#""a \"b\" \\c""#);
+        assert_eq!(powershell_quote("it's"), "'it''s'");
+    }
+
+    #[test]
+    fn secret_file_stores_values_encrypted() {
+        let tmp = tempfile::tempdir().unwrap();
+        let providers = SecretsProviders::new(tmp.path(), "zeroclaw");
+        let reference = SecretRef::parse("${secretfile:telegram}").unwrap();
+        assert!(providers.resolve(&reference).is_err());
+
+        let file = providers.provider(SecretBackend::File);
+        file.set("telegram", "123:bot-token").unwrap();
+        assert_eq!(providers.resolve(&reference).unwrap(), "123:bot-token");
+        let raw = std::fs::read_to_string(tmp.path().join(SECRETS_FILE)).unwrap();
+        assert!(!raw.contains("bot-token"));
+
+        file.delete("telegram").unwrap();
+        assert_eq!(file.get("telegram").unwrap(), None);
+        assert!(providers
+            .provider(SecretBackend::Env)
+            .set("X", "y")
+            .is_err());
+    }
+}
@@ -0,0 +1,215 @@
+//! Dry-run a candidate policy against recorded tool calls
+//! (`zeroclaw policy simulate`).
+//!
+//! Each call goes through the checks its tool would run before acting:
+//! command validation for `shell`, path rules for the file tools, egress
+//! rules for URL-taking tools, then the generic
+//! [`SecurityPolicy::enforce_tool_operation`] gate with its tool lists,
+//! autonomy level, operating window, and rate limits, for the operation
+//! (`Read` or `Act`) the named tool reports; unknown tools count as `Act`.
+//! Nothing is executed, and the action bucket is an in-memory one, so
+//! replaying a session never spends the real workspace's budget. Calls
+//! replay back to back, so rate limits see the whole batch at once rather
+//! than at its recorded pace.
+
+use super::policy::{ActionBucket, SecurityPolicy, ToolOperation};
+use crate::export::transcript::{parse_history, TranscriptItem};
+use crate::providers::ChatMessage;
+use crate::tools::Tool;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::sync::Arc;
+
+/// One tool call to replay.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SimulatedCall {
+    #[serde(alias = "name")]
+    pub tool: String,
+    #[serde(default)]
+    pub arguments: serde_json::Value,
+}
+
+/// A call and why the policy would refuse it, if it would.
+#[derive(Debug, Clone, Serialize)]
+pub struct SimulationOutcome {
+    #[serde(flatten)]
+    pub call: SimulatedCall,
+    pub allowed: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+/// Load the candidate policy file at `path`, with an empty in-memory action
+/// bucket in place of the workspace's persisted one.
+pub fn load_policy(path: &Path) -> Result<SecurityPolicy> {
+    let policy = SecurityPolicy::from_file(path)?;
+    Ok(SecurityPolicy {
+        action_bucket: Arc::new(ActionBucket::new()),
+        ..policy
+    })
+}
+
+/// Parse synthetic calls: a JSON array, or one JSON object per line, each
+/// `{"tool": "...", "arguments": {...}}`.
+pub fn parse_calls(raw: &str) -> Result<Vec<SimulatedCall>> {
+    if raw.trim_start().starts_with('[') {
+        return serde_json::from_str(raw).context("calls file is not a JSON array of tool calls");
+    }
+    raw.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| {
+            serde_json::from_str(line)
+                .with_context(|| format!("line {}: not a JSON tool call", index + 1))
+        })
+        .collect()
+}
+
+/// The tool calls of a stored session, in order.
+pub fn calls_from_history(messages: &[ChatMessage]) -> Vec<SimulatedCall> {
+    parse_history(messages)
+        .into_iter()
+        .filter_map(|item| match item {
+            TranscriptItem::ToolCall { name, arguments } => Some(SimulatedCall {
+                tool: name,
+                arguments: serde_json::from_str(&arguments)
+                    .unwrap_or(serde_json::Value::String(arguments)),
+            }),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Replay `calls` against `policy` in order, looking tool operations up in
+/// `tools`.
+pub async fn simulate(
+    policy: &SecurityPolicy,
+    calls: Vec<SimulatedCall>,
+    tools: &[Box<dyn Tool>],
+) -> Vec<SimulationOutcome> {
+    let mut outcomes = Vec::with_capacity(calls.len());
+    for call in calls {
+        let operation = tools
+            .iter()
+            .find(|tool| tool.name() == call.tool)
+            .map_or(ToolOperation::Act, |tool| tool.operation());
+        let reason = check_call(policy, &call, operation).await.err();
+        outcomes.push(SimulationOutcome {
+            call,
+            allowed: reason.is_none(),
+            reason,
+        });
+    }
+    outcomes
+}
+
+async fn check_call(
+    policy: &SecurityPolicy,
+    call: &SimulatedCall,
+    operation: ToolOperation,
+) -> Result<(), String> {
+    let arg = |key: &str| call.arguments.get(key).and_then(|v| v.as_str());
+    match call.tool.as_str() {
+        "shell" => {
+            if let Some(command) = arg("command") {
+                let approved = call
+                    .arguments
+                    .get("approved")
+                    .and_then(|v| v.as_bool())
+                    .unwrap_or(false);
+                policy
+                    .validate_command(command, arg("working_dir"), approved)
+                    .await?;
+            }
+        }
+        "file_write" | "file_edit" => {
+            if let Some(path) = arg("path") {
+                policy.resolve_write_target(path).await?;
+            }
+        }
+        "file_read" => {
+            if let Some(path) = arg("path").filter(|path| !policy.is_path_allowed(path)) {
+                return Err(format!("Path not allowed by security policy: {path}"));
+            }
+        }
+        "http_request" | "web_fetch" | "browser_open" => {
+            if let Some(url) = arg("url") {
+                policy.egress().check_url(url)?;
+            }
+        }
+        _ => {}
+    }
+
+    policy
+        .enforce_tool_operation(operation, &call.tool)
+        .map_err(|denial| denial.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::security::AutonomyLevel;
+
+    #[tokio::test]
+    async fn reports_each_call_against_the_candidate_policy() {
+        let tmp = tempfile::tempdir().unwrap();
+        let policy = SecurityPolicy {
+            autonomy: AutonomyLevel::Full,
+            workspace_dir: tmp.path().to_path_buf(),
+            allowed_commands: vec!["ls".into()],
+            denied_tools: vec!["browser_open".into()],
+            denied_hosts: vec!["evil.example.com".into()],
+            max_actions_per_hour: 3,
+            ..SecurityPolicy::default()
+        };
+        let calls = parse_calls(
+            r#"{"tool": "shell", "arguments": {"command": "ls -la"}}
+{"tool": "shell", "arguments": {"command": "curl https://example.com"}}
+{"name": "http_request", "arguments": {"url": "https://evil.example.com/x"}}
+
+{"tool": "browser_open", "arguments": {"url": "https://example.com"}}
+{"tool": "file_read", "arguments": {"path": "notes.md"}}
+{"tool": "memory_store", "arguments": {"key": "a", "content": "b"}}
+{"tool": "memory_store", "arguments": {"key": "c", "content": "d"}}
+{"tool": "memory_store", "arguments": {"key": "e", "content": "f"}}"#,
+        )
+        .unwrap();
+
+        let tools = crate::tools::default_tools(std::sync::Arc::new(SecurityPolicy::default()));
+        let outcomes = simulate(&policy, calls, &tools).await;
+        let allowed: Vec<bool> = outcomes.iter().map(|outcome| outcome.allowed).collect();
+        assert_eq!(
+            allowed,
+            [true, false, false, false, true, true, true, false]
+        );
+        assert!(outcomes[2]
+            .reason
+            .as_deref()
+            .unwrap()
+            .contains("autonomy.denied_hosts"));
+        assert!(outcomes[7]
+            .reason
+            .as_deref()
+            .unwrap()
+            .contains("global action budget exhausted"));
+    }
+
+    #[test]
+    fn session_history_yields_its_tool_calls() {
+        let history = vec![
+            ChatMessage::user("list the files"),
+            ChatMessage::assistant(
+                r#"{"content": "Listing.", "tool_calls": [{"id": "1", "name": "shell", "arguments": "{\"command\": \"ls\"}"}]}"#,
+            ),
+            ChatMessage::tool(r#"{"tool_call_id": "1", "content": "a.txt"}"#),
+        ];
+        assert_eq!(
+            calls_from_history(&history),
+            [SimulatedCall {
+                tool: "shell".into(),
+                arguments: serde_json::json!({ "command": "ls" }),
+            }]
+        );
+    }
+}
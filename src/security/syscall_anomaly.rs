@@ -14,7 +14,7 @@ use std::collections::{HashMap, HashSet, VecDeque};
 use std::fs::OpenOptions;
 use std::io::Write;
 use std::path::{Path, PathBuf};
-use std::sync::OnceLock;
+use std::sync::{Arc, OnceLock};
 use std::time::{Duration, Instant};
 
 const RATE_WINDOW: Duration = Duration::from_secs(60);
@@ -75,7 +75,7 @@ pub struct SyscallAnomalyDetector {
     baseline: HashSet<String>,
     state: Mutex<DetectorState>,
     anomaly_log_path: PathBuf,
-    audit_logger: Option<AuditLogger>,
+    audit_logger: Option<Arc<AuditLogger>>,
 }
 
 impl SyscallAnomalyDetector {
@@ -87,7 +87,7 @@ impl SyscallAnomalyDetector {
     ) -> Self {
         let baseline = normalize_baseline(&config.baseline_syscalls);
         let anomaly_log_path = resolve_log_path(zeroclaw_dir.as_ref(), config.log_path.as_str());
-        let audit_logger = AuditLogger::new(audit_config, zeroclaw_dir.as_ref().to_path_buf()).ok();
+        let audit_logger = AuditLogger::shared(&audit_config, zeroclaw_dir.as_ref()).ok();
 
         Self {
             config,
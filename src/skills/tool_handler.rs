@@ -387,9 +387,10 @@ impl Tool for SkillToolHandler {
             "Executing skill tool"
         );
 
-        let output = tokio::process::Command::new("sh")
-            .arg("-c")
-            .arg(&command)
+        let mut cmd = tokio::process::Command::new("sh");
+        cmd.arg("-c").arg(&command);
+        self.security.env_filter().apply(cmd.as_std_mut());
+        let output = cmd
             .output()
             .await
             .context("Failed to execute skill tool command")?;
@@ -495,6 +496,32 @@ mod tests {
         assert_eq!(schema["properties"]["count"]["type"], "integer");
     }
 
+    #[tokio::test]
+    async fn skill_command_does_not_leak_secrets() {
+        let tool_def = SkillTool {
+            name: "print_env".to_string(),
+            description: "Print the environment".to_string(),
+            kind: "shell".to_string(),
+            command: "env".to_string(),
+            args: HashMap::new(),
+        };
+        let security = Arc::new(SecurityPolicy {
+            allowed_commands: vec!["env".into()],
+            ..SecurityPolicy::default()
+        });
+        let handler = SkillToolHandler::new("test".to_string(), tool_def, security).unwrap();
+
+        let key = "ZEROCLAW_SKILL_TEST_API_KEY";
+        std::env::set_var(key, "sk-skill-secret");
+        let result = handler.execute(serde_json::json!({})).await;
+        std::env::remove_var(key);
+
+        let result = result.unwrap();
+        assert!(result.success, "{}", result.output);
+        assert!(result.output.contains("PATH="));
+        assert!(!result.output.contains("sk-skill-secret"));
+    }
+
     #[test]
     fn render_command_with_all_args() {
         let tool_def = SkillTool {
@@ -7,7 +7,7 @@
 use super::traits::{Tool, ToolResult};
 use crate::config::AgentsIpcConfig;
 use crate::security::policy::ToolOperation;
-use crate::security::SecurityPolicy;
+use crate::security::{PolicyHandle, SecurityPolicy};
 use async_trait::async_trait;
 use rusqlite::Connection;
 use serde_json::json;
@@ -165,6 +165,10 @@ impl Tool for AgentsListTool {
         "agents_list"
     }
 
+    fn operation(&self) -> ToolOperation {
+        ToolOperation::Read
+    }
+
     fn description(&self) -> &str {
         "List online IPC agents on this host. Returns agent IDs, roles, and last-seen timestamps for agents within the staleness window."
     }
@@ -216,12 +220,15 @@ impl Tool for AgentsListTool {
 /// Send a message to another agent (or broadcast with `"*"`).
 pub struct AgentsSendTool {
     ipc_db: Arc<IpcDb>,
-    security: Arc<SecurityPolicy>,
+    security: PolicyHandle,
 }
 
 impl AgentsSendTool {
     pub(crate) fn new(ipc_db: Arc<IpcDb>, security: Arc<SecurityPolicy>) -> Self {
-        Self { ipc_db, security }
+        Self {
+            ipc_db,
+            security: security.into(),
+        }
     }
 }
 
@@ -255,12 +262,13 @@ impl Tool for AgentsSendTool {
     async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
         if let Err(error) = self
             .security
+            .current()
             .enforce_tool_operation(ToolOperation::Act, "agents_send")
         {
             return Ok(ToolResult {
                 success: false,
                 output: String::new(),
-                error: Some(error),
+                error: Some(error.to_string()),
             });
         }
 
@@ -326,6 +334,10 @@ impl Tool for AgentsInboxTool {
         "agents_inbox"
     }
 
+    fn operation(&self) -> ToolOperation {
+        ToolOperation::Read
+    }
+
     fn description(&self) -> &str {
         "Read unread messages in this agent's inbox (including broadcasts to '*'). Direct messages are marked as read after retrieval; broadcast messages remain unread."
     }
@@ -397,6 +409,10 @@ impl Tool for StateGetTool {
         "state_get"
     }
 
+    fn operation(&self) -> ToolOperation {
+        ToolOperation::Read
+    }
+
     fn description(&self) -> &str {
         "Get a value from the shared inter-agent key-value store."
     }
@@ -468,12 +484,15 @@ impl Tool for StateGetTool {
 /// Set a value in the shared key-value store.
 pub struct StateSetTool {
     ipc_db: Arc<IpcDb>,
-    security: Arc<SecurityPolicy>,
+    security: PolicyHandle,
 }
 
 impl StateSetTool {
     pub(crate) fn new(ipc_db: Arc<IpcDb>, security: Arc<SecurityPolicy>) -> Self {
-        Self { ipc_db, security }
+        Self {
+            ipc_db,
+            security: security.into(),
+        }
     }
 }
 
@@ -507,12 +526,13 @@ impl Tool for StateSetTool {
     async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
         if let Err(error) = self
             .security
+            .current()
             .enforce_tool_operation(ToolOperation::Act, "state_set")
         {
             return Ok(ToolResult {
                 success: false,
                 output: String::new(),
-                error: Some(error),
+                error: Some(error.to_string()),
             });
         }
 
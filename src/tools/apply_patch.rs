@@ -1,9 +1,12 @@
+use crate::security::policy::{PolicyDenial, ToolOperation};
+use crate::security::{EnvFilter, PolicyHandle, SecurityPolicy};
 use crate::tools::traits::{Tool, ToolResult};
-use anyhow::{anyhow, Context, Result};
+use anyhow::{anyhow, bail, Context, Result};
 use async_trait::async_trait;
 use serde_json::json;
 use std::fmt::Write as _;
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
 use tempfile::NamedTempFile;
 use tokio::process::Command;
 
@@ -16,15 +19,21 @@ use tokio::process::Command;
 /// - Optionally stages + commits when `commit_message` is provided
 ///
 /// Notes:
-/// - This tool assumes it is running inside a git repo.
+/// - This tool assumes the workspace is inside a git repo.
+/// - Every file the patch touches must be writable under the security
+///   policy, as for `file_write`.
 /// - It does NOT fetch, pull, or push.
 /// - It does NOT run arbitrary scripts.
 /// - It is intentionally narrow: patch in, apply/check, status/commit out.
-pub struct ApplyPatchTool;
+pub struct ApplyPatchTool {
+    security: PolicyHandle,
+}
 
 impl ApplyPatchTool {
-    pub fn new() -> Self {
-        Self
+    pub fn new(security: Arc<SecurityPolicy>) -> Self {
+        Self {
+            security: security.into(),
+        }
     }
 
     fn schema() -> serde_json::Value {
@@ -57,7 +66,7 @@ impl Tool for ApplyPatchTool {
     }
 
     fn description(&self) -> &str {
-        "Safely check/apply a unified diff to the current git repository, optionally staging and committing."
+        "Safely check/apply a unified diff to the workspace git repository, optionally staging and committing."
     }
 
     fn parameters_schema(&self) -> serde_json::Value {
@@ -65,6 +74,15 @@ impl Tool for ApplyPatchTool {
     }
 
     async fn execute(&self, args: serde_json::Value) -> Result<ToolResult> {
+        let security = self.security.current();
+        if let Err(error) = security.enforce_tool_operation(ToolOperation::Act, "apply_patch") {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(error.to_string()),
+            });
+        }
+
         let patch = args
             .get("patch")
             .and_then(|v| v.as_str())
@@ -97,7 +115,7 @@ impl Tool for ApplyPatchTool {
             });
         }
 
-        let repo_root = git_repo_root().await?;
+        let repo_root = git_repo_root(&security.workspace_dir).await?;
         let mut log = String::new();
         let _ = writeln!(log, "Repo root: {}", repo_root.display());
         let _ = writeln!(log, "Mode: {}", if dry_run { "dry-run" } else { "apply" });
@@ -143,6 +161,26 @@ impl Tool for ApplyPatchTool {
             }
         }
 
+        // Resolve what the patch touches the way `git apply` reads it, and
+        // refuse the whole patch if any target is not writable.
+        let targets = patch_targets(&repo_root, patch_path.to_string_lossy().as_ref()).await?;
+        let workspace = tokio::fs::canonicalize(&security.workspace_dir)
+            .await
+            .unwrap_or_else(|_| security.workspace_dir.clone());
+        for target in &targets {
+            // Workspace-relative where possible, since the policy may refuse
+            // absolute paths outright.
+            let full = repo_root.join(target);
+            let path = full.strip_prefix(&workspace).unwrap_or(&full);
+            if let Err(error) = security.resolve_write_target(&path.to_string_lossy()).await {
+                return Ok(ToolResult {
+                    success: false,
+                    output: log,
+                    error: Some(format!("{error}. No changes made.")),
+                });
+            }
+        }
+
         if dry_run {
             log.push_str("\nPatch check OK. Dry-run requested, no changes applied.\n");
             return Ok(ToolResult {
@@ -152,6 +190,20 @@ impl Tool for ApplyPatchTool {
             });
         }
 
+        // The checks above take a moment; the operator may have engaged the
+        // kill switch since.
+        if security.kill_switch_engaged() {
+            let denial = PolicyDenial::KillSwitch {
+                operation: "apply_patch".into(),
+            }
+            .surface();
+            return Ok(ToolResult {
+                success: false,
+                output: log,
+                error: Some(format!("{denial}. No changes made.")),
+            });
+        }
+
         // Apply patch.
         {
             let (code, out, err) = run_cmd(
@@ -285,9 +337,8 @@ impl Tool for ApplyPatchTool {
     }
 }
 
-async fn git_repo_root() -> Result<PathBuf> {
-    let cwd = std::env::current_dir().context("Failed to read current_dir")?;
-    let (code, out, err) = run_cmd(&cwd, "git", &["rev-parse", "--show-toplevel"]).await?;
+async fn git_repo_root(workspace_dir: &Path) -> Result<PathBuf> {
+    let (code, out, err) = run_cmd(workspace_dir, "git", &["rev-parse", "--show-toplevel"]).await?;
     if code != 0 {
         return Err(anyhow!(
             "Not a git repo (git rev-parse failed). stderr: {}",
@@ -301,8 +352,39 @@ async fn git_repo_root() -> Result<PathBuf> {
     Ok(PathBuf::from(root))
 }
 
+/// Paths, relative to `repo_root`, that the patch at `patch_path` creates,
+/// changes, renames or deletes.
+async fn patch_targets(repo_root: &Path, patch_path: &str) -> Result<Vec<String>> {
+    let (code, out, err) =
+        run_cmd(repo_root, "git", &["apply", "--numstat", "-z", patch_path]).await?;
+    if code != 0 {
+        bail!("Failed to list the patch's files: {}", err.trim());
+    }
+    Ok(parse_numstat(&out))
+}
+
+/// Paths from `git apply --numstat -z` output. Each record is
+/// `<added>\t<deleted>\t<path>`; a rename leaves the path empty and lists
+/// its source and destination as the next two fields.
+fn parse_numstat(out: &str) -> Vec<String> {
+    let mut targets = Vec::new();
+    let mut fields = out.split('\0');
+    while let Some(record) = fields.next() {
+        if record.is_empty() {
+            continue;
+        }
+        match record.splitn(3, '\t').nth(2) {
+            Some("") => targets.extend(fields.by_ref().take(2).map(str::to_string)),
+            Some(path) => targets.push(path.to_string()),
+            None => {}
+        }
+    }
+    targets
+}
+
 async fn run_cmd(dir: &Path, program: &str, args: &[&str]) -> Result<(i32, String, String)> {
     let mut cmd = Command::new(program);
+    EnvFilter::baseline().apply(cmd.as_std_mut());
     cmd.args(args).current_dir(dir);
 
     let output = cmd
@@ -320,6 +402,102 @@ async fn run_cmd(dir: &Path, program: &str, args: &[&str]) -> Result<(i32, Strin
 mod tests {
     use super::*;
 
+    fn git(dir: &Path, args: &[&str]) {
+        let status = std::process::Command::new("git")
+            .args(args)
+            .current_dir(dir)
+            .status()
+            .unwrap();
+        assert!(status.success());
+    }
+
+    #[test]
+    fn numstat_lists_renames_by_both_paths() {
+        let out = "1\t0\tsrc/lib.rs\x000\t0\t\x00old.rs\x00new.rs\x00";
+        assert_eq!(parse_numstat(out), ["src/lib.rs", "old.rs", "new.rs"]);
+    }
+
+    #[tokio::test]
+    async fn refuses_patches_to_read_only_paths() {
+        let workspace = tempfile::tempdir().unwrap();
+        git(workspace.path(), &["init", "-q"]);
+        std::fs::create_dir_all(workspace.path().join("docs")).unwrap();
+        std::fs::write(workspace.path().join("docs/notes.md"), "old\n").unwrap();
+        let security = Arc::new(SecurityPolicy {
+            workspace_dir: workspace.path().to_path_buf(),
+            path_permissions: vec![(
+                PathBuf::from("docs"),
+                crate::config::schema::PathPermission::Read,
+            )],
+            ..SecurityPolicy::default()
+        });
+        let tool = ApplyPatchTool::new(security);
+        let patch = "--- a/docs/notes.md\n+++ b/docs/notes.md\n@@ -1 +1 @@\n-old\n+new\n";
+
+        let result = tool
+            .execute(json!({ "patch": patch, "dry_run": false }))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("read-only"));
+        assert_eq!(
+            std::fs::read_to_string(workspace.path().join("docs/notes.md")).unwrap(),
+            "old\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn blocked_in_read_only_mode() {
+        let workspace = tempfile::tempdir().unwrap();
+        git(workspace.path(), &["init", "-q"]);
+        std::fs::write(workspace.path().join("notes.md"), "old\n").unwrap();
+        let security = Arc::new(SecurityPolicy {
+            autonomy: crate::security::AutonomyLevel::ReadOnly,
+            workspace_dir: workspace.path().to_path_buf(),
+            ..SecurityPolicy::default()
+        });
+        let tool = ApplyPatchTool::new(security);
+        let patch = "--- a/notes.md\n+++ b/notes.md\n@@ -1 +1 @@\n-old\n+new\n";
+
+        let result = tool
+            .execute(json!({ "patch": patch, "dry_run": false }))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("read-only"));
+        assert_eq!(
+            std::fs::read_to_string(workspace.path().join("notes.md")).unwrap(),
+            "old\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn stopped_by_the_kill_switch() {
+        let workspace = tempfile::tempdir().unwrap();
+        git(workspace.path(), &["init", "-q"]);
+        std::fs::write(workspace.path().join("notes.md"), "old\n").unwrap();
+        let kill_file = crate::security::kill_switch::kill_switch_path(workspace.path());
+        std::fs::create_dir_all(kill_file.parent().unwrap()).unwrap();
+        std::fs::write(&kill_file, "").unwrap();
+        let security = Arc::new(SecurityPolicy {
+            workspace_dir: workspace.path().to_path_buf(),
+            ..SecurityPolicy::default()
+        });
+        let tool = ApplyPatchTool::new(security);
+        let patch = "--- a/notes.md\n+++ b/notes.md\n@@ -1 +1 @@\n-old\n+new\n";
+
+        let result = tool
+            .execute(json!({ "patch": patch, "dry_run": false }))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("kill switch engaged"));
+        assert_eq!(
+            std::fs::read_to_string(workspace.path().join("notes.md")).unwrap(),
+            "old\n"
+        );
+    }
+
     #[test]
     fn schema_is_object() {
         let s = ApplyPatchTool::schema();
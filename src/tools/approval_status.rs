@@ -0,0 +1,138 @@
+use super::traits::{Tool, ToolResult};
+use crate::security::policy::ToolOperation;
+use crate::security::{ApprovalStatus, PolicyHandle, SecurityPolicy};
+use async_trait::async_trait;
+use serde_json::json;
+use std::sync::Arc;
+
+/// Reports on a tool call held for operator approval, and hands over its
+/// result once it has run.
+pub struct ApprovalStatusTool {
+    security: PolicyHandle,
+}
+
+impl ApprovalStatusTool {
+    pub fn new(security: Arc<SecurityPolicy>) -> Self {
+        Self {
+            security: security.into(),
+        }
+    }
+}
+
+#[async_trait]
+impl Tool for ApprovalStatusTool {
+    fn name(&self) -> &str {
+        "approval_status"
+    }
+
+    fn operation(&self) -> ToolOperation {
+        ToolOperation::Read
+    }
+
+    fn description(&self) -> &str {
+        "Check a tool call that is awaiting operator approval, by the request_id its pending \
+         result gave. Returns the call's own result once the operator answered and it ran."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "additionalProperties": false,
+            "properties": {
+                "request_id": {
+                    "type": "string",
+                    "description": "Approval request id from the pending tool result"
+                }
+            },
+            "required": ["request_id"]
+        })
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let id = args
+            .get("request_id")
+            .and_then(|v| v.as_str())
+            .map(str::trim)
+            .ok_or_else(|| anyhow::anyhow!("Missing 'request_id' parameter"))?;
+
+        let Some(call) = self.security.current().pending_approvals.collect(id) else {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(format!(
+                    "No approval request {id} (unknown, or its result was already returned)"
+                )),
+            });
+        };
+        if let Some(result) = call.result {
+            return Ok(result);
+        }
+        let output = match call.status {
+            ApprovalStatus::Pending => {
+                format!("{} is still awaiting operator approval.", call.tool_name)
+            }
+            ApprovalStatus::Approved => format!("Approved; {} is running.", call.tool_name),
+            ApprovalStatus::Denied => format!("{} was declined.", call.tool_name),
+        };
+        Ok(ToolResult {
+            success: true,
+            output,
+            error: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::approval::{ApprovalRequest, ApprovalResponse};
+    use crate::security::ApprovalHook;
+    use crate::tools::test_support::{approval_request_id, StubTool};
+    use crate::tools::ApprovalGatedTool;
+    use tokio::sync::Notify;
+
+    /// Approves once released.
+    #[derive(Debug, Default)]
+    struct ReleasedHook {
+        release: Notify,
+    }
+
+    #[async_trait]
+    impl ApprovalHook for ReleasedHook {
+        async fn request_approval(&self, _request: &ApprovalRequest) -> ApprovalResponse {
+            self.release.notified().await;
+            ApprovalResponse::Yes
+        }
+    }
+
+    #[tokio::test]
+    async fn reports_pending_then_hands_over_the_result_once() {
+        let hook = Arc::new(ReleasedHook::default());
+        let security = Arc::new(SecurityPolicy {
+            approval_hook: Some(hook.clone()),
+            ..SecurityPolicy::default()
+        });
+        let gated =
+            ApprovalGatedTool::new(Box::new(StubTool::new("file_write")), Arc::clone(&security));
+        let status = ApprovalStatusTool::new(security);
+
+        let pending = gated.execute(json!({})).await.unwrap();
+        let id = approval_request_id(&pending);
+        let waiting = status.execute(json!({ "request_id": id })).await.unwrap();
+        assert!(waiting.output.contains("awaiting operator approval"));
+
+        hook.release.notify_one();
+        let mut result = waiting;
+        for _ in 0..100 {
+            result = status.execute(json!({ "request_id": id })).await.unwrap();
+            if result.output == "ok" {
+                break;
+            }
+            tokio::task::yield_now().await;
+        }
+        assert_eq!(result.output, "ok");
+
+        let again = status.execute(json!({ "request_id": id })).await.unwrap();
+        assert!(!again.success);
+    }
+}
@@ -0,0 +1,161 @@
+//! Audit trail for every tool call in the runtime registry.
+//!
+//! [`all_tools_with_runtime`](super::all_tools_with_runtime) wraps each tool
+//! in an [`AuditedTool`] as its last step, so whichever loop executes the
+//! call, it is written to the `[security.audit]` log.
+//! [`default_tools_with_runtime`](super::default_tools_with_runtime) does the
+//! same with the policy's `audit_logger`, when one is set.
+
+use super::traits::{delegate_tool_metadata, Tool, ToolResult};
+use crate::security::audit::{AuditLogger, ToolInvocationLog};
+use crate::security::policy::PolicyDenial;
+use async_trait::async_trait;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Instant;
+
+/// Wraps a tool so each call appends a record (tool, argument hash,
+/// allowed/denied, success, duration) to the audit log. A failed call counts
+/// as denied when the policy raised a [`PolicyDenial`] while it ran.
+pub struct AuditedTool {
+    inner: Box<dyn Tool>,
+    logger: Arc<AuditLogger>,
+}
+
+impl AuditedTool {
+    pub fn new(inner: Box<dyn Tool>, logger: Arc<AuditLogger>) -> Self {
+        Self { inner, logger }
+    }
+}
+
+#[async_trait]
+impl Tool for AuditedTool {
+    delegate_tool_metadata!();
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        audit_call(
+            &self.logger,
+            self.inner.name(),
+            &args,
+            self.inner.execute(args.clone()),
+        )
+        .await
+    }
+}
+
+/// Run `call` (a call of `tool` with `args`) and append its record to the
+/// audit log; see [`AuditedTool`].
+pub(crate) async fn audit_call<F>(
+    logger: &AuditLogger,
+    tool: &str,
+    args: &serde_json::Value,
+    call: F,
+) -> anyhow::Result<ToolResult>
+where
+    F: Future<Output = anyhow::Result<ToolResult>>,
+{
+    let start = Instant::now();
+    let (result, denial) = PolicyDenial::capture(call).await;
+    let duration_ms = u64::try_from(start.elapsed().as_millis()).unwrap_or(u64::MAX);
+
+    let (success, error) = match &result {
+        Ok(r) if r.success => (true, None),
+        Ok(r) => (false, Some(r.error.clone().unwrap_or_default())),
+        Err(e) => (false, Some(e.to_string())),
+    };
+    let allowed = success || denial.is_none();
+    if let Err(e) = logger.log_tool_invocation(ToolInvocationLog {
+        tool,
+        arguments: args,
+        allowed,
+        success,
+        duration_ms,
+        error,
+    }) {
+        tracing::warn!("audit: failed to record '{tool}' call: {e}");
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::AuditConfig;
+    use crate::security::audit::AuditEvent;
+    use crate::security::policy::ToolOperation;
+    use crate::security::{AutonomyLevel, SecurityPolicy};
+    use crate::tools::test_support::StubTool;
+    use crate::tools::PolicyGatedTool;
+
+    fn read_events(dir: &std::path::Path) -> Vec<AuditEvent> {
+        std::fs::read_to_string(dir.join("ariadne/audit/audit.log"))
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn records_allowed_and_denied_calls() {
+        let tmp = tempfile::tempdir().unwrap();
+        let logger =
+            Arc::new(AuditLogger::new(AuditConfig::default(), tmp.path().to_path_buf()).unwrap());
+        let read_only = Arc::new(SecurityPolicy {
+            autonomy: AutonomyLevel::ReadOnly,
+            ..SecurityPolicy::default()
+        });
+        let ok = AuditedTool::new(Box::new(StubTool::new("fixed")), Arc::clone(&logger));
+        let denied = AuditedTool::new(
+            Box::new(PolicyGatedTool::new(
+                Box::new(StubTool::new("fixed")),
+                read_only,
+                ToolOperation::Act,
+            )),
+            Arc::clone(&logger),
+        );
+
+        assert!(
+            ok.execute(serde_json::json!({"a": 1}))
+                .await
+                .unwrap()
+                .success
+        );
+        assert!(!denied.execute(serde_json::json!({})).await.unwrap().success);
+
+        let events = read_events(tmp.path());
+        assert_eq!(events.len(), 2);
+        let first = events[0].action.as_ref().unwrap();
+        assert_eq!(first.command.as_deref(), Some("fixed"));
+        assert!(first.allowed);
+        assert!(events[0].result.as_ref().unwrap().success);
+        assert!(!events[1].action.as_ref().unwrap().allowed);
+    }
+
+    #[tokio::test]
+    async fn failures_that_only_read_like_denials_are_allowed() {
+        let tmp = tempfile::tempdir().unwrap();
+        let logger =
+            Arc::new(AuditLogger::new(AuditConfig::default(), tmp.path().to_path_buf()).unwrap());
+        let failing = AuditedTool::new(
+            Box::new(StubTool::new("fixed").returning(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some("Security policy: read-only mode, cannot perform 'fixed'".into()),
+            })),
+            logger,
+        );
+
+        assert!(
+            !failing
+                .execute(serde_json::json!({}))
+                .await
+                .unwrap()
+                .success
+        );
+
+        let events = read_events(tmp.path());
+        assert!(events[0].action.as_ref().unwrap().allowed);
+        assert!(!events[0].result.as_ref().unwrap().success);
+    }
+}
@@ -30,6 +30,7 @@ use tokio::sync::Mutex;
 use tokio::time::{timeout, Duration};
 
 use super::traits::{Tool, ToolResult};
+use crate::security::policy::ToolOperation;
 
 /// Hard timeout for background tool execution (seconds).
 const BG_TOOL_TIMEOUT_SECS: u64 = 600;
@@ -434,6 +435,10 @@ impl Tool for BgStatusTool {
         "bg_status"
     }
 
+    fn operation(&self) -> ToolOperation {
+        ToolOperation::Read
+    }
+
     fn description(&self) -> &str {
         "Query the status of a background job by ID, or list all jobs if no ID provided. \
          Returns job status (running/complete/failed), result output, and elapsed time."
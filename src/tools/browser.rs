@@ -8,7 +8,7 @@
 use super::traits::{Tool, ToolResult};
 use super::url_validation::{validate_url as validate_network_url, DomainPolicy, UrlSchemePolicy};
 use crate::config::UrlAccessConfig;
-use crate::security::SecurityPolicy;
+use crate::security::{PolicyHandle, SecurityPolicy};
 use anyhow::Context;
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
@@ -63,7 +63,7 @@ impl Default for ComputerUseConfig {
 
 /// Browser automation tool using pluggable backends.
 pub struct BrowserTool {
-    security: Arc<SecurityPolicy>,
+    security: PolicyHandle,
     allowed_domains: Vec<String>,
     url_access: UrlAccessConfig,
     session_name: Option<String>,
@@ -277,7 +277,7 @@ impl BrowserTool {
         computer_use: ComputerUseConfig,
     ) -> Self {
         Self {
-            security,
+            security: security.into(),
             allowed_domains: normalize_domains(allowed_domains),
             url_access,
             session_name,
@@ -529,6 +529,7 @@ impl BrowserTool {
                 scheme_policy: UrlSchemePolicy::HttpsOnly,
                 ipv6_error_context: "browser",
                 url_access: Some(&self.url_access),
+                egress: Some(self.security.current().egress()),
             },
         )?;
         Ok(())
@@ -542,6 +543,7 @@ impl BrowserTool {
         }
 
         let mut cmd = Command::new(command);
+        self.security.current().env_filter().apply(cmd.as_std_mut());
 
         for extra in &self.agent_browser_extra_args {
             let trimmed = extra.trim();
@@ -828,7 +830,7 @@ impl BrowserTool {
         if trimmed.contains('\0') {
             anyhow::bail!("'{key}' path contains invalid null byte");
         }
-        if !self.security.is_path_allowed(trimmed) {
+        if !self.security.current().is_path_allowed(trimmed) {
             anyhow::bail!("'{key}' path blocked by security policy: {trimmed}");
         }
         Ok(())
@@ -839,13 +841,14 @@ impl BrowserTool {
         key: &str,
         path: &str,
     ) -> anyhow::Result<PathBuf> {
+        let security = self.security.current();
         let trimmed = path.trim();
         self.validate_output_path(key, trimmed)?;
 
-        tokio::fs::create_dir_all(&self.security.workspace_dir).await?;
-        let workspace_root = tokio::fs::canonicalize(&self.security.workspace_dir)
+        tokio::fs::create_dir_all(&security.workspace_dir).await?;
+        let workspace_root = tokio::fs::canonicalize(&security.workspace_dir)
             .await
-            .unwrap_or_else(|_| self.security.workspace_dir.clone());
+            .unwrap_or_else(|_| security.workspace_dir.clone());
 
         let raw_path = Path::new(trimmed);
         let output_path = if raw_path.is_absolute() {
@@ -857,13 +860,12 @@ impl BrowserTool {
         let parent = output_path
             .parent()
             .ok_or_else(|| anyhow::anyhow!("'{key}' path has no parent directory"))?;
-        tokio::fs::create_dir_all(parent).await?;
+        security.fs().create_dir_all(parent).await?;
         let resolved_parent = tokio::fs::canonicalize(parent).await?;
-        if !self.security.is_resolved_path_allowed(&resolved_parent) {
+        if !security.is_resolved_path_allowed(&resolved_parent) {
             anyhow::bail!(
                 "{}",
-                self.security
-                    .resolved_path_violation_message(&resolved_parent)
+                security.resolved_path_violation_message(&resolved_parent)
             );
         }
 
@@ -1242,8 +1244,9 @@ impl Tool for BrowserTool {
     }
 
     async fn execute(&self, args: Value) -> anyhow::Result<ToolResult> {
+        let security = self.security.current();
         // Security checks
-        if !self.security.can_act() {
+        if !security.can_act() {
             return Ok(ToolResult {
                 success: false,
                 output: String::new(),
@@ -1251,7 +1254,7 @@ impl Tool for BrowserTool {
             });
         }
 
-        if !self.security.record_action() {
+        if !security.record_action() {
             return Ok(ToolResult {
                 success: false,
                 output: String::new(),
@@ -1478,7 +1481,10 @@ mod native_backend {
                     });
 
                     if let Some(path_str) = path {
-                        tokio::fs::write(&path_str, &png)
+                        self.security
+                            .current()
+                            .fs()
+                            .write(Path::new(&path_str), &png)
                             .await
                             .with_context(|| format!("Failed to write screenshot to {path_str}"))?;
                         payload["path"] = Value::String(path_str);
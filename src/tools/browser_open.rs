@@ -3,7 +3,7 @@ use super::url_validation::{
     normalize_allowed_domains, validate_url, DomainPolicy, UrlSchemePolicy,
 };
 use crate::config::UrlAccessConfig;
-use crate::security::SecurityPolicy;
+use crate::security::{EnvFilter, PolicyHandle, SecurityPolicy};
 use async_trait::async_trait;
 use serde_json::json;
 use std::sync::Arc;
@@ -54,7 +54,7 @@ impl BrowserChoice {
 
 /// Open approved HTTPS URLs in the configured browser (no scraping, no DOM automation).
 pub struct BrowserOpenTool {
-    security: Arc<SecurityPolicy>,
+    security: PolicyHandle,
     allowed_domains: Vec<String>,
     url_access: UrlAccessConfig,
     browser: BrowserChoice,
@@ -68,7 +68,7 @@ impl BrowserOpenTool {
         browser: BrowserChoice,
     ) -> Self {
         Self {
-            security,
+            security: security.into(),
             allowed_domains: normalize_allowed_domains(allowed_domains),
             url_access,
             browser,
@@ -87,6 +87,7 @@ impl BrowserOpenTool {
                 scheme_policy: UrlSchemePolicy::HttpsOnly,
                 ipv6_error_context: "browser_open",
                 url_access: Some(&self.url_access),
+                egress: Some(self.security.current().egress()),
             },
         )
     }
@@ -116,12 +117,13 @@ impl Tool for BrowserOpenTool {
     }
 
     async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let security = self.security.current();
         let url = args
             .get("url")
             .and_then(|v| v.as_str())
             .ok_or_else(|| anyhow::anyhow!("Missing 'url' parameter"))?;
 
-        if !self.security.can_act() {
+        if !security.can_act() {
             return Ok(ToolResult {
                 success: false,
                 output: String::new(),
@@ -129,7 +131,7 @@ impl Tool for BrowserOpenTool {
             });
         }
 
-        if !self.security.record_action() {
+        if !security.record_action() {
             return Ok(ToolResult {
                 success: false,
                 output: String::new(),
@@ -177,16 +179,19 @@ async fn open_in_browser(url: &str, browser: BrowserChoice) -> anyhow::Result<()
     }
 }
 
+/// Build a launcher command that inherits only the baseline environment.
+#[cfg(any(target_os = "macos", target_os = "linux", target_os = "windows"))]
+fn launcher(program: &str) -> tokio::process::Command {
+    let mut cmd = tokio::process::Command::new(program);
+    EnvFilter::baseline().apply(cmd.as_std_mut());
+    cmd
+}
+
 // macOS implementations
 #[cfg(target_os = "macos")]
 async fn open_in_brave(url: &str) -> anyhow::Result<()> {
     for app in ["Brave Browser", "Brave"] {
-        let status = tokio::process::Command::new("open")
-            .arg("-a")
-            .arg(app)
-            .arg(url)
-            .status()
-            .await;
+        let status = launcher("open").arg("-a").arg(app).arg(url).status().await;
 
         if let Ok(s) = status {
             if s.success() {
@@ -202,12 +207,7 @@ async fn open_in_brave(url: &str) -> anyhow::Result<()> {
 #[cfg(target_os = "macos")]
 async fn open_in_chrome(url: &str) -> anyhow::Result<()> {
     for app in ["Google Chrome", "Chrome", "Chromium"] {
-        let status = tokio::process::Command::new("open")
-            .arg("-a")
-            .arg(app)
-            .arg(url)
-            .status()
-            .await;
+        let status = launcher("open").arg("-a").arg(app).arg(url).status().await;
 
         if let Ok(s) = status {
             if s.success() {
@@ -223,12 +223,7 @@ async fn open_in_chrome(url: &str) -> anyhow::Result<()> {
 #[cfg(target_os = "macos")]
 async fn open_in_firefox(url: &str) -> anyhow::Result<()> {
     for app in ["Firefox", "Firefox Developer Edition"] {
-        let status = tokio::process::Command::new("open")
-            .arg("-a")
-            .arg(app)
-            .arg(url)
-            .status()
-            .await;
+        let status = launcher("open").arg("-a").arg(app).arg(url).status().await;
 
         if let Ok(s) = status {
             if s.success() {
@@ -243,10 +238,7 @@ async fn open_in_firefox(url: &str) -> anyhow::Result<()> {
 
 #[cfg(target_os = "macos")]
 async fn open_in_default(url: &str) -> anyhow::Result<()> {
-    let status = tokio::process::Command::new("open")
-        .arg(url)
-        .status()
-        .await?;
+    let status = launcher("open").arg(url).status().await?;
 
     if status.success() {
         Ok(())
@@ -258,12 +250,7 @@ async fn open_in_default(url: &str) -> anyhow::Result<()> {
 #[cfg(target_os = "macos")]
 async fn open_in_edge(url: &str) -> anyhow::Result<()> {
     for app in ["Microsoft Edge", "Edge"] {
-        let status = tokio::process::Command::new("open")
-            .arg("-a")
-            .arg(app)
-            .arg(url)
-            .status()
-            .await;
+        let status = launcher("open").arg("-a").arg(app).arg(url).status().await;
 
         if let Ok(s) = status {
             if s.success() {
@@ -281,7 +268,7 @@ async fn open_in_edge(url: &str) -> anyhow::Result<()> {
 async fn open_in_brave(url: &str) -> anyhow::Result<()> {
     let mut last_error = String::new();
     for cmd in ["brave-browser", "brave"] {
-        match tokio::process::Command::new(cmd).arg(url).status().await {
+        match launcher(cmd).arg(url).status().await {
             Ok(status) if status.success() => return Ok(()),
             Ok(status) => {
                 last_error = format!("{cmd} exited with status {status}");
@@ -304,7 +291,7 @@ async fn open_in_chrome(url: &str) -> anyhow::Result<()> {
         "chromium",
         "chromium-browser",
     ] {
-        match tokio::process::Command::new(cmd).arg(url).status().await {
+        match launcher(cmd).arg(url).status().await {
             Ok(status) if status.success() => return Ok(()),
             Ok(status) => {
                 last_error = format!("{cmd} exited with status {status}");
@@ -321,7 +308,7 @@ async fn open_in_chrome(url: &str) -> anyhow::Result<()> {
 async fn open_in_firefox(url: &str) -> anyhow::Result<()> {
     let mut last_error = String::new();
     for cmd in ["firefox", "firefox-developer-edition"] {
-        match tokio::process::Command::new(cmd).arg(url).status().await {
+        match launcher(cmd).arg(url).status().await {
             Ok(status) if status.success() => return Ok(()),
             Ok(status) => {
                 last_error = format!("{cmd} exited with status {status}");
@@ -337,11 +324,7 @@ async fn open_in_firefox(url: &str) -> anyhow::Result<()> {
 #[cfg(target_os = "linux")]
 async fn open_in_default(url: &str) -> anyhow::Result<()> {
     // Try xdg-open first, fall back to common browsers
-    if let Ok(status) = tokio::process::Command::new("xdg-open")
-        .arg(url)
-        .status()
-        .await
-    {
+    if let Ok(status) = launcher("xdg-open").arg(url).status().await {
         if status.success() {
             return Ok(());
         }
@@ -349,7 +332,7 @@ async fn open_in_default(url: &str) -> anyhow::Result<()> {
 
     // Fallback: try common browsers in order
     for cmd in ["firefox", "google-chrome-stable", "chromium"] {
-        if let Ok(status) = tokio::process::Command::new(cmd).arg(url).status().await {
+        if let Ok(status) = launcher(cmd).arg(url).status().await {
             if status.success() {
                 return Ok(());
             }
@@ -363,7 +346,7 @@ async fn open_in_default(url: &str) -> anyhow::Result<()> {
 async fn open_in_edge(url: &str) -> anyhow::Result<()> {
     let mut last_error = String::new();
     for cmd in ["microsoft-edge", "microsoft-edge-stable", "edge"] {
-        match tokio::process::Command::new(cmd).arg(url).status().await {
+        match launcher(cmd).arg(url).status().await {
             Ok(status) if status.success() => return Ok(()),
             Ok(status) => {
                 last_error = format!("{cmd} exited with status {status}");
@@ -391,7 +374,7 @@ fn escape_for_cmd_start(url: &str) -> String {
 #[cfg(target_os = "windows")]
 async fn open_in_brave(url: &str) -> anyhow::Result<()> {
     let escaped = escape_for_cmd_start(url);
-    let status = tokio::process::Command::new("cmd")
+    let status = launcher("cmd")
         .arg("/C")
         .arg("start")
         .arg("")
@@ -410,7 +393,7 @@ async fn open_in_brave(url: &str) -> anyhow::Result<()> {
 #[cfg(target_os = "windows")]
 async fn open_in_chrome(url: &str) -> anyhow::Result<()> {
     let escaped = escape_for_cmd_start(url);
-    let status = tokio::process::Command::new("cmd")
+    let status = launcher("cmd")
         .arg("/C")
         .arg("start")
         .arg("")
@@ -429,7 +412,7 @@ async fn open_in_chrome(url: &str) -> anyhow::Result<()> {
 #[cfg(target_os = "windows")]
 async fn open_in_firefox(url: &str) -> anyhow::Result<()> {
     let escaped = escape_for_cmd_start(url);
-    let status = tokio::process::Command::new("cmd")
+    let status = launcher("cmd")
         .arg("/C")
         .arg("start")
         .arg("")
@@ -448,7 +431,7 @@ async fn open_in_firefox(url: &str) -> anyhow::Result<()> {
 #[cfg(target_os = "windows")]
 async fn open_in_default(url: &str) -> anyhow::Result<()> {
     let escaped = escape_for_cmd_start(url);
-    let status = tokio::process::Command::new("cmd")
+    let status = launcher("cmd")
         .arg("/C")
         .arg("start")
         .arg("")
@@ -466,7 +449,7 @@ async fn open_in_default(url: &str) -> anyhow::Result<()> {
 #[cfg(target_os = "windows")]
 async fn open_in_edge(url: &str) -> anyhow::Result<()> {
     let escaped = escape_for_cmd_start(url);
-    let status = tokio::process::Command::new("cmd")
+    let status = launcher("cmd")
         .arg("/C")
         .arg("start")
         .arg("")
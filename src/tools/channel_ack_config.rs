@@ -7,7 +7,7 @@ use crate::config::{
     AckReactionChannelsConfig, AckReactionConfig, AckReactionRuleConfig, AckReactionStrategy,
     Config,
 };
-use crate::security::SecurityPolicy;
+use crate::security::{PolicyHandle, SecurityPolicy};
 use async_trait::async_trait;
 use serde_json::{json, Value};
 use std::collections::BTreeMap;
@@ -47,12 +47,15 @@ impl AckChannel {
 
 pub struct ChannelAckConfigTool {
     config: Arc<Config>,
-    security: Arc<SecurityPolicy>,
+    security: PolicyHandle,
 }
 
 impl ChannelAckConfigTool {
     pub fn new(config: Arc<Config>, security: Arc<SecurityPolicy>) -> Self {
-        Self { config, security }
+        Self {
+            config,
+            security: security.into(),
+        }
     }
 
     fn load_config_without_env(&self) -> anyhow::Result<Config> {
@@ -75,7 +78,8 @@ impl ChannelAckConfigTool {
     }
 
     fn require_write_access(&self) -> Option<ToolResult> {
-        if !self.security.can_act() {
+        let security = self.security.current();
+        if !security.can_act() {
             return Some(ToolResult {
                 success: false,
                 output: String::new(),
@@ -83,7 +87,7 @@ impl ChannelAckConfigTool {
             });
         }
 
-        if !self.security.record_action() {
+        if !security.record_action() {
             return Some(ToolResult {
                 success: false,
                 output: String::new(),
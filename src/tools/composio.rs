@@ -8,7 +8,7 @@
 
 use super::traits::{Tool, ToolResult};
 use crate::security::policy::ToolOperation;
-use crate::security::SecurityPolicy;
+use crate::security::{PolicyHandle, SecurityPolicy};
 use anyhow::Context;
 use async_trait::async_trait;
 use parking_lot::RwLock;
@@ -36,7 +36,7 @@ fn ensure_https(url: &str) -> anyhow::Result<()> {
 pub struct ComposioTool {
     api_key: String,
     default_entity_id: String,
-    security: Arc<SecurityPolicy>,
+    security: PolicyHandle,
     recent_connected_accounts: RwLock<HashMap<String, String>>,
     action_slug_cache: RwLock<HashMap<String, String>>,
 }
@@ -50,7 +50,7 @@ impl ComposioTool {
         Self {
             api_key: api_key.to_string(),
             default_entity_id: normalize_entity_id(default_entity_id.unwrap_or("default")),
-            security,
+            security: security.into(),
             recent_connected_accounts: RwLock::new(HashMap::new()),
             action_slug_cache: RwLock::new(HashMap::new()),
         }
@@ -658,11 +658,20 @@ impl Tool for ComposioTool {
     }
 
     async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let security = self.security.current();
         let action = args
             .get("action")
             .and_then(|v| v.as_str())
             .ok_or_else(|| anyhow::anyhow!("Missing 'action' parameter"))?;
 
+        if let Err(e) = security.egress().check_url(COMPOSIO_API_BASE_V3) {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(e),
+            });
+        }
+
         let entity_id = args
             .get("entity_id")
             .and_then(|v| v.as_str())
@@ -763,14 +772,13 @@ impl Tool for ComposioTool {
             }
 
             "execute" => {
-                if let Err(error) = self
-                    .security
+                if let Err(error) = security
                     .enforce_tool_operation(ToolOperation::Act, "composio.execute")
                 {
                     return Ok(ToolResult {
                         success: false,
                         output: String::new(),
-                        error: Some(error),
+                        error: Some(error.to_string()),
                     });
                 }
 
@@ -828,14 +836,13 @@ impl Tool for ComposioTool {
             }
 
             "connect" => {
-                if let Err(error) = self
-                    .security
+                if let Err(error) = security
                     .enforce_tool_operation(ToolOperation::Act, "composio.connect")
                 {
                     return Ok(ToolResult {
                         success: false,
                         output: String::new(),
-                        error: Some(error),
+                        error: Some(error.to_string()),
                     });
                 }
 
@@ -0,0 +1,347 @@
+use super::memory_search::entry_date;
+use super::memory_store::check_memory_quota;
+use super::traits::{Tool, ToolExecutionContext, ToolResult};
+use crate::memory::{self, Memory, MemoryCategory};
+use crate::security::policy::ToolOperation;
+use crate::security::{PolicyHandle, SecurityPolicy};
+use async_trait::async_trait;
+use chrono::{Duration, Local};
+use serde_json::json;
+use std::sync::Arc;
+
+const DEFAULT_CANDIDATE_DAYS: i64 = 1;
+const DEFAULT_CANDIDATE_LIMIT: usize = 50;
+
+/// Promote short-term memory into long-term memory.
+///
+/// Everything outside the `core` category (daily notes, conversation
+/// context, observations) is short-term: memory hygiene archives and purges
+/// it over time. `candidates` lists recent short-term entries; after the
+/// agent summarizes them, `promote` stores the summary as a `core` entry
+/// that records its sources, optionally removing the originals. The nightly
+/// consolidation job drives this, and the agent can call it on its own.
+pub struct ConsolidateMemoryTool {
+    memory: Arc<dyn Memory>,
+    security: PolicyHandle,
+}
+
+impl ConsolidateMemoryTool {
+    pub fn new(memory: Arc<dyn Memory>, security: Arc<SecurityPolicy>) -> Self {
+        Self {
+            memory,
+            security: security.into(),
+        }
+    }
+
+    async fn candidates(&self, args: &serde_json::Value) -> anyhow::Result<ToolResult> {
+        let days = args
+            .get("since_days")
+            .and_then(serde_json::Value::as_i64)
+            .unwrap_or(DEFAULT_CANDIDATE_DAYS)
+            .clamp(1, 365);
+        #[allow(clippy::cast_possible_truncation)]
+        let limit = args
+            .get("limit")
+            .and_then(serde_json::Value::as_u64)
+            .map_or(DEFAULT_CANDIDATE_LIMIT, |v| v as usize);
+        let cutoff = Local::now().date_naive() - Duration::days(days - 1);
+
+        let mut entries: Vec<_> = self
+            .memory
+            .list(None, None)
+            .await?
+            .into_iter()
+            .filter(|e| e.category != MemoryCategory::Core)
+            .filter(|e| !memory::is_assistant_autosave_key(&e.key))
+            .filter(|e| entry_date(&e.timestamp).is_some_and(|date| date >= cutoff))
+            .collect();
+        entries.sort_by(|a, b| a.timestamp.cmp(&b.timestamp));
+        let total = entries.len();
+
+        let listed: Vec<serde_json::Value> = entries
+            .into_iter()
+            .take(limit)
+            .map(|e| {
+                json!({
+                    "key": e.key,
+                    "category": e.category.to_string(),
+                    "timestamp": e.timestamp,
+                    "content": e.content,
+                })
+            })
+            .collect();
+        Ok(ToolResult {
+            success: true,
+            output: serde_json::to_string_pretty(&json!({
+                "total": total,
+                "entries": listed,
+            }))?,
+            error: None,
+        })
+    }
+
+    async fn promote(&self, args: &serde_json::Value) -> anyhow::Result<ToolResult> {
+        let security = self.security.current();
+        let summary = args
+            .get("summary")
+            .and_then(serde_json::Value::as_str)
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .ok_or_else(|| anyhow::anyhow!("Missing 'summary' parameter"))?;
+        let sources: Vec<&str> = args
+            .get("sources")
+            .and_then(serde_json::Value::as_array)
+            .map(|items| items.iter().filter_map(serde_json::Value::as_str).collect())
+            .unwrap_or_default();
+        if sources.is_empty() {
+            return Ok(failed(
+                "'sources' must list the keys of the short-term entries being promoted",
+            ));
+        }
+        let key = args
+            .get("key")
+            .and_then(serde_json::Value::as_str)
+            .map(str::to_string)
+            .unwrap_or_else(|| format!("consolidation_{}", Local::now().format("%Y-%m-%d")));
+        let remove_sources = args
+            .get("remove_sources")
+            .and_then(serde_json::Value::as_bool)
+            .unwrap_or(false);
+
+        for source in &sources {
+            match self.memory.get(source).await? {
+                Some(entry) if entry.key == *source => {
+                    if entry.category == MemoryCategory::Core {
+                        return Ok(failed(format!(
+                            "'{source}' is already long-term (core) memory"
+                        )));
+                    }
+                }
+                _ => return Ok(failed(format!("No memory found with key: {source}"))),
+            }
+        }
+
+        let mut content = format!("{summary}\n\nSources: {}", sources.join(", "));
+        if let Some(line) = ToolExecutionContext::current()
+            .as_ref()
+            .and_then(memory::provenance_line)
+        {
+            content = format!("{content}\n{line}");
+        }
+        if let Err(error) =
+            check_memory_quota(self.memory.as_ref(), &security, &key, &content).await
+        {
+            return Ok(failed(error));
+        }
+        if let Err(error) =
+            security.enforce_tool_operation(ToolOperation::Act, "consolidate_memory")
+        {
+            return Ok(failed(error));
+        }
+
+        if let Err(e) = self
+            .memory
+            .store_from(&key, &content, MemoryCategory::Core, None, self.name())
+            .await
+        {
+            return Ok(failed(format!("Failed to store consolidated memory: {e}")));
+        }
+        security.record_memory_write();
+
+        let mut removed = 0;
+        if remove_sources {
+            for source in &sources {
+                if self.memory.forget(source).await? {
+                    removed += 1;
+                }
+            }
+        }
+
+        let mut output = format!(
+            "Promoted {} short-term entries into long-term memory: {key}",
+            sources.len()
+        );
+        if remove_sources {
+            output.push_str(&format!(" (removed {removed} source entries)"));
+        }
+        Ok(ToolResult {
+            success: true,
+            output,
+            error: None,
+        })
+    }
+}
+
+fn failed(error: impl Into<String>) -> ToolResult {
+    ToolResult {
+        success: false,
+        output: String::new(),
+        error: Some(error.into()),
+    }
+}
+
+#[async_trait]
+impl Tool for ConsolidateMemoryTool {
+    fn name(&self) -> &str {
+        "consolidate_memory"
+    }
+
+    fn description(&self) -> &str {
+        "Promote short-term memory (daily notes, conversation context, observations) into long-term core memory. Use action 'candidates' to list recent short-term entries, summarize the important ones yourself, then call action 'promote' with that summary and the source keys."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "action": {
+                    "type": "string",
+                    "enum": ["candidates", "promote"],
+                    "description": "'candidates' lists recent short-term entries; 'promote' stores a summary of them as core memory"
+                },
+                "since_days": {
+                    "type": "integer",
+                    "description": "candidates: how many days back to look, including today (default: 1)"
+                },
+                "limit": {
+                    "type": "integer",
+                    "description": "candidates: max entries to return (default: 50)"
+                },
+                "summary": {
+                    "type": "string",
+                    "description": "promote: the consolidated long-term note"
+                },
+                "sources": {
+                    "type": "array",
+                    "items": { "type": "string" },
+                    "description": "promote: keys of the short-term entries the summary was drawn from"
+                },
+                "key": {
+                    "type": "string",
+                    "description": "promote: key for the long-term entry (default: consolidation_YYYY-MM-DD)"
+                },
+                "remove_sources": {
+                    "type": "boolean",
+                    "description": "promote: delete the source entries after promoting (default: false)"
+                }
+            },
+            "required": ["action"]
+        })
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        match args.get("action").and_then(serde_json::Value::as_str) {
+            Some("candidates") => self.candidates(&args).await,
+            Some("promote") => self.promote(&args).await,
+            Some(other) => Ok(failed(format!(
+                "Unknown action '{other}'. Use 'candidates' or 'promote'."
+            ))),
+            None => Err(anyhow::anyhow!("Missing 'action' parameter")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::SqliteMemory;
+    use crate::security::AutonomyLevel;
+    use tempfile::TempDir;
+
+    async fn setup(security: SecurityPolicy) -> (TempDir, Arc<dyn Memory>, ConsolidateMemoryTool) {
+        let tmp = TempDir::new().unwrap();
+        let mem: Arc<dyn Memory> = Arc::new(SqliteMemory::new(tmp.path()).unwrap());
+        mem.store(
+            "standup",
+            "Release blocked on CI",
+            MemoryCategory::Daily,
+            None,
+        )
+        .await
+        .unwrap();
+        mem.store(
+            "obs_1",
+            "User reviews PRs in the morning",
+            MemoryCategory::Custom("observation".into()),
+            None,
+        )
+        .await
+        .unwrap();
+        mem.store("lang", "User prefers Rust", MemoryCategory::Core, None)
+            .await
+            .unwrap();
+        let tool = ConsolidateMemoryTool::new(mem.clone(), Arc::new(security));
+        (tmp, mem, tool)
+    }
+
+    #[tokio::test]
+    async fn candidates_lists_recent_short_term_entries() {
+        let (_tmp, _mem, tool) = setup(SecurityPolicy::default()).await;
+        let result = tool.execute(json!({"action": "candidates"})).await.unwrap();
+        assert!(result.success);
+        let out: serde_json::Value = serde_json::from_str(&result.output).unwrap();
+        assert_eq!(out["total"], 2);
+        let keys: Vec<&str> = out["entries"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|e| e["key"].as_str().unwrap())
+            .collect();
+        assert!(!keys.contains(&"lang"));
+    }
+
+    #[tokio::test]
+    async fn promote_stores_core_summary_and_optionally_removes_sources() {
+        let (_tmp, mem, tool) = setup(SecurityPolicy::default()).await;
+        let result = tool
+            .execute(json!({
+                "action": "promote",
+                "summary": "CI flakiness blocks releases; user reviews PRs mornings",
+                "sources": ["standup", "obs_1"],
+                "key": "consolidation_test",
+                "remove_sources": true
+            }))
+            .await
+            .unwrap();
+        assert!(result.success, "{:?}", result.error);
+        assert!(result.output.contains("removed 2"));
+
+        let entry = mem.get("consolidation_test").await.unwrap().unwrap();
+        assert_eq!(entry.category, MemoryCategory::Core);
+        assert!(entry.content.ends_with("Sources: standup, obs_1"));
+        assert!(mem.get("standup").await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn promote_rejects_unknown_or_core_sources() {
+        let (_tmp, mem, tool) = setup(SecurityPolicy::default()).await;
+        for source in ["missing", "lang"] {
+            let result = tool
+                .execute(
+                    json!({"action": "promote", "summary": "x", "sources": [source], "key": "c"}),
+                )
+                .await
+                .unwrap();
+            assert!(!result.success);
+        }
+        assert!(mem.get("c").await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn promote_blocked_in_readonly_mode() {
+        let (_tmp, mem, tool) = setup(SecurityPolicy {
+            autonomy: AutonomyLevel::ReadOnly,
+            ..SecurityPolicy::default()
+        })
+        .await;
+        let result = tool
+            .execute(
+                json!({"action": "promote", "summary": "x", "sources": ["standup"], "key": "c"}),
+            )
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("read-only mode"));
+        assert!(mem.get("c").await.unwrap().is_none());
+    }
+}
@@ -1,5 +1,6 @@
 use super::traits::{Tool, ToolResult};
-use crate::security::SecurityPolicy;
+use crate::security::policy::ToolOperation;
+use crate::security::{PolicyHandle, SecurityPolicy};
 use async_trait::async_trait;
 use serde_json::json;
 use std::process::Stdio;
@@ -14,19 +15,25 @@ const TIMEOUT_SECS: u64 = 30;
 /// Uses ripgrep (`rg`) when available, falling back to `grep -rn -E`.
 /// All searches are confined to the workspace directory by security policy.
 pub struct ContentSearchTool {
-    security: Arc<SecurityPolicy>,
+    security: PolicyHandle,
     has_rg: bool,
 }
 
 impl ContentSearchTool {
     pub fn new(security: Arc<SecurityPolicy>) -> Self {
         let has_rg = which::which("rg").is_ok();
-        Self { security, has_rg }
+        Self {
+            security: security.into(),
+            has_rg,
+        }
     }
 
     #[cfg(test)]
     fn new_with_backend(security: Arc<SecurityPolicy>, has_rg: bool) -> Self {
-        Self { security, has_rg }
+        Self {
+            security: security.into(),
+            has_rg,
+        }
     }
 }
 
@@ -36,6 +43,10 @@ impl Tool for ContentSearchTool {
         "content_search"
     }
 
+    fn operation(&self) -> ToolOperation {
+        ToolOperation::Read
+    }
+
     fn description(&self) -> &str {
         "Search file contents by regex pattern within the workspace. \
          Supports ripgrep (rg) with grep fallback. \
@@ -98,6 +109,7 @@ impl Tool for ContentSearchTool {
     }
 
     async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let security = self.security.current();
         // --- Parse parameters ---
         let pattern = args
             .get("pattern")
@@ -162,7 +174,7 @@ impl Tool for ContentSearchTool {
             .min(MAX_RESULTS);
 
         // --- Rate limit check ---
-        if self.security.is_rate_limited() {
+        if security.is_rate_limited() {
             return Ok(ToolResult {
                 success: false,
                 output: String::new(),
@@ -187,7 +199,7 @@ impl Tool for ContentSearchTool {
             });
         }
 
-        if !self.security.is_path_allowed(search_path) {
+        if !security.is_path_allowed(search_path) {
             return Ok(ToolResult {
                 success: false,
                 output: String::new(),
@@ -198,7 +210,7 @@ impl Tool for ContentSearchTool {
         }
 
         // Record action to consume rate limit budget
-        if !self.security.record_action() {
+        if !security.record_action() {
             return Ok(ToolResult {
                 success: false,
                 output: String::new(),
@@ -207,7 +219,7 @@ impl Tool for ContentSearchTool {
         }
 
         // --- Resolve search directory ---
-        let workspace = &self.security.workspace_dir;
+        let workspace = &security.workspace_dir;
         let resolved_path = workspace.join(search_path);
 
         let resolved_canon = match std::fs::canonicalize(&resolved_path) {
@@ -221,7 +233,7 @@ impl Tool for ContentSearchTool {
             }
         };
 
-        if !self.security.is_resolved_path_allowed(&resolved_canon) {
+        if !security.is_resolved_path_allowed(&resolved_canon) {
             return Ok(ToolResult {
                 success: false,
                 output: String::new(),
@@ -267,12 +279,7 @@ impl Tool for ContentSearchTool {
         };
 
         // Security: clear environment, keep only safe variables
-        cmd.env_clear();
-        for key in &["PATH", "HOME", "LANG", "LC_ALL", "LC_CTYPE"] {
-            if let Ok(val) = std::env::var(key) {
-                cmd.env(key, val);
-            }
-        }
+        security.env_filter().apply(&mut cmd);
 
         cmd.stdout(Stdio::piped());
         cmd.stderr(Stdio::piped());
@@ -1,25 +1,29 @@
 use super::traits::{Tool, ToolResult};
 use crate::config::Config;
 use crate::cron::{self, DeliveryConfig, JobType, Schedule, SessionTarget};
-use crate::security::SecurityPolicy;
+use crate::security::{PolicyHandle, SecurityPolicy};
 use async_trait::async_trait;
 use serde_json::json;
 use std::sync::Arc;
 
 pub struct CronAddTool {
     config: Arc<Config>,
-    security: Arc<SecurityPolicy>,
+    security: PolicyHandle,
 }
 
 const MIN_AGENT_EVERY_MS: u64 = 5 * 60 * 1000;
 
 impl CronAddTool {
     pub fn new(config: Arc<Config>, security: Arc<SecurityPolicy>) -> Self {
-        Self { config, security }
+        Self {
+            config,
+            security: security.into(),
+        }
     }
 
     fn enforce_mutation_allowed(&self, action: &str) -> Option<ToolResult> {
-        if !self.security.can_act() {
+        let security = self.security.current();
+        if !security.can_act() {
             return Some(ToolResult {
                 success: false,
                 output: String::new(),
@@ -29,7 +33,7 @@ impl CronAddTool {
             });
         }
 
-        if self.security.is_rate_limited() {
+        if security.is_rate_limited() {
             return Some(ToolResult {
                 success: false,
                 output: String::new(),
@@ -37,7 +41,7 @@ impl CronAddTool {
             });
         }
 
-        if !self.security.record_action() {
+        if !security.record_action() {
             return Some(ToolResult {
                 success: false,
                 output: String::new(),
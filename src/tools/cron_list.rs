@@ -1,6 +1,7 @@
 use super::traits::{Tool, ToolResult};
 use crate::config::Config;
 use crate::cron;
+use crate::security::policy::ToolOperation;
 use async_trait::async_trait;
 use serde_json::json;
 use std::sync::Arc;
@@ -21,6 +22,10 @@ impl Tool for CronListTool {
         "cron_list"
     }
 
+    fn operation(&self) -> ToolOperation {
+        ToolOperation::Read
+    }
+
     fn description(&self) -> &str {
         "List all scheduled cron jobs"
     }
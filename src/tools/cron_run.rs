@@ -1,7 +1,7 @@
 use super::traits::{Tool, ToolResult};
 use crate::config::Config;
 use crate::cron::{self, JobType};
-use crate::security::SecurityPolicy;
+use crate::security::{PolicyHandle, SecurityPolicy};
 use async_trait::async_trait;
 use chrono::Utc;
 use serde_json::json;
@@ -9,12 +9,15 @@ use std::sync::Arc;
 
 pub struct CronRunTool {
     config: Arc<Config>,
-    security: Arc<SecurityPolicy>,
+    security: PolicyHandle,
 }
 
 impl CronRunTool {
     pub fn new(config: Arc<Config>, security: Arc<SecurityPolicy>) -> Self {
-        Self { config, security }
+        Self {
+            config,
+            security: security.into(),
+        }
     }
 }
 
@@ -44,6 +47,7 @@ impl Tool for CronRunTool {
     }
 
     async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let security = self.security.current();
         if !self.config.cron.enabled {
             return Ok(ToolResult {
                 success: false,
@@ -67,7 +71,7 @@ impl Tool for CronRunTool {
             .and_then(serde_json::Value::as_bool)
             .unwrap_or(false);
 
-        if !self.security.can_act() {
+        if !security.can_act() {
             return Ok(ToolResult {
                 success: false,
                 output: String::new(),
@@ -75,7 +79,7 @@ impl Tool for CronRunTool {
             });
         }
 
-        if self.security.is_rate_limited() {
+        if security.is_rate_limited() {
             return Ok(ToolResult {
                 success: false,
                 output: String::new(),
@@ -95,10 +99,7 @@ impl Tool for CronRunTool {
         };
 
         if matches!(job.job_type, JobType::Shell) {
-            if let Err(reason) = self
-                .security
-                .validate_command_execution(&job.command, approved)
-            {
+            if let Err(reason) = security.validate_command_execution(&job.command, approved) {
                 return Ok(ToolResult {
                     success: false,
                     output: String::new(),
@@ -107,7 +108,7 @@ impl Tool for CronRunTool {
             }
         }
 
-        if !self.security.record_action() {
+        if !security.record_action() {
             return Ok(ToolResult {
                 success: false,
                 output: String::new(),
@@ -1,6 +1,7 @@
 use super::traits::{Tool, ToolResult};
 use crate::config::Config;
 use crate::cron;
+use crate::security::policy::ToolOperation;
 use async_trait::async_trait;
 use serde::Serialize;
 use serde_json::json;
@@ -35,6 +36,10 @@ impl Tool for CronRunsTool {
         "cron_runs"
     }
 
+    fn operation(&self) -> ToolOperation {
+        ToolOperation::Read
+    }
+
     fn description(&self) -> &str {
         "List recent run history for a cron job"
     }
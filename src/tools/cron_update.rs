@@ -1,23 +1,27 @@
 use super::traits::{Tool, ToolResult};
 use crate::config::Config;
 use crate::cron::{self, CronJobPatch};
-use crate::security::SecurityPolicy;
+use crate::security::{PolicyHandle, SecurityPolicy};
 use async_trait::async_trait;
 use serde_json::json;
 use std::sync::Arc;
 
 pub struct CronUpdateTool {
     config: Arc<Config>,
-    security: Arc<SecurityPolicy>,
+    security: PolicyHandle,
 }
 
 impl CronUpdateTool {
     pub fn new(config: Arc<Config>, security: Arc<SecurityPolicy>) -> Self {
-        Self { config, security }
+        Self {
+            config,
+            security: security.into(),
+        }
     }
 
     fn enforce_mutation_allowed(&self, action: &str) -> Option<ToolResult> {
-        if !self.security.can_act() {
+        let security = self.security.current();
+        if !security.can_act() {
             return Some(ToolResult {
                 success: false,
                 output: String::new(),
@@ -27,7 +31,7 @@ impl CronUpdateTool {
             });
         }
 
-        if self.security.is_rate_limited() {
+        if security.is_rate_limited() {
             return Some(ToolResult {
                 success: false,
                 output: String::new(),
@@ -35,7 +39,7 @@ impl CronUpdateTool {
             });
         }
 
-        if !self.security.record_action() {
+        if !security.record_action() {
             return Some(ToolResult {
                 success: false,
                 output: String::new(),
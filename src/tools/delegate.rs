@@ -1,14 +1,15 @@
 use super::agent_load_tracker::AgentLoadTracker;
 use super::agent_selection::{select_agent_with_load, AgentSelectionPolicy};
+use super::gated::CapabilityGatedTool;
 use super::orchestration_settings::load_orchestration_settings;
-use super::traits::{Tool, ToolResult};
+use super::traits::{delegate_tool_metadata, Tool, ToolResult};
 use crate::agent::loop_::run_tool_call_loop;
 use crate::config::{AgentTeamsConfig, DelegateAgentConfig};
 use crate::coordination::{CoordinationEnvelope, CoordinationPayload, InMemoryMessageBus};
 use crate::observability::traits::{Observer, ObserverEvent, ObserverMetric};
 use crate::providers::{self, ChatMessage, Provider};
 use crate::security::policy::ToolOperation;
-use crate::security::SecurityPolicy;
+use crate::security::{CapabilityToken, PolicyHandle, SecurityPolicy};
 use async_trait::async_trait;
 use serde_json::json;
 use std::collections::HashMap;
@@ -32,7 +33,7 @@ const COORDINATION_PREVIEW_MAX_CHARS: usize = 240;
 /// summarization) to purpose-built sub-agents.
 pub struct DelegateTool {
     agents: Arc<HashMap<String, DelegateAgentConfig>>,
-    security: Arc<SecurityPolicy>,
+    security: PolicyHandle,
     /// Global credential fallback (from config.api_key)
     fallback_credential: Option<String>,
     /// Provider runtime options inherited from root config.
@@ -78,7 +79,7 @@ impl DelegateTool {
         let coordination_bus = build_coordination_bus(&agents, DEFAULT_COORDINATION_LEAD_AGENT);
         Self {
             agents: Arc::new(agents),
-            security,
+            security: security.into(),
             fallback_credential,
             provider_runtime_options,
             depth: 0,
@@ -120,7 +121,7 @@ impl DelegateTool {
         let coordination_bus = build_coordination_bus(&agents, DEFAULT_COORDINATION_LEAD_AGENT);
         Self {
             agents: Arc::new(agents),
-            security,
+            security: security.into(),
             fallback_credential,
             provider_runtime_options,
             depth,
@@ -370,12 +371,13 @@ impl Tool for DelegateTool {
 
         if let Err(error) = self
             .security
+            .current()
             .enforce_tool_operation(ToolOperation::Act, "delegate")
         {
             return Ok(ToolResult {
                 success: false,
                 output: String::new(),
-                error: Some(error),
+                error: Some(error.to_string()),
             });
         }
 
@@ -558,12 +560,35 @@ impl DelegateTool {
             .filter(|name| !name.is_empty())
             .collect::<std::collections::HashSet<_>>();
 
+        // The sub-agent acts under a token scoped to its allowlist, so it
+        // can never exceed what this agent's policy still permits.
+        let token = match CapabilityToken::mint(
+            &self.security.current(),
+            &agent_config.allowed_tools,
+            Duration::from_secs(DELEGATE_AGENTIC_TIMEOUT_SECS),
+            None,
+        ) {
+            Ok(token) => Arc::new(token),
+            Err(error) => {
+                return Ok(ToolResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some(error),
+                });
+            }
+        };
+
         let sub_tools: Vec<Box<dyn Tool>> = self
             .parent_tools
             .iter()
             .filter(|tool| allowed.contains(tool.name()))
             .filter(|tool| tool.name() != "delegate")
-            .map(|tool| Box::new(ToolArcRef::new(tool.clone())) as Box<dyn Tool>)
+            .map(|tool| {
+                Box::new(CapabilityGatedTool::new(
+                    Box::new(ToolArcRef::new(tool.clone())),
+                    Arc::clone(&token),
+                )) as Box<dyn Tool>
+            })
             .collect();
 
         if sub_tools.is_empty() {
@@ -834,17 +859,7 @@ impl ToolArcRef {
 
 #[async_trait]
 impl Tool for ToolArcRef {
-    fn name(&self) -> &str {
-        self.inner.name()
-    }
-
-    fn description(&self) -> &str {
-        self.inner.description()
-    }
-
-    fn parameters_schema(&self) -> serde_json::Value {
-        self.inner.parameters_schema()
-    }
+    delegate_tool_metadata!();
 
     async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
         self.inner.execute(args).await
@@ -1,7 +1,7 @@
 use super::traits::{Tool, ToolResult};
 use crate::coordination::{CoordinationPayload, InMemoryMessageBus, SequencedEnvelope};
 use crate::security::policy::ToolOperation;
-use crate::security::SecurityPolicy;
+use crate::security::{PolicyHandle, SecurityPolicy};
 use async_trait::async_trait;
 use serde_json::json;
 use std::sync::Arc;
@@ -19,12 +19,15 @@ const MAX_CONTEXT_OFFSET: usize = 10_000;
 /// Read-only runtime observability tool for delegate coordination events.
 pub struct DelegateCoordinationStatusTool {
     bus: InMemoryMessageBus,
-    security: Arc<SecurityPolicy>,
+    security: PolicyHandle,
 }
 
 impl DelegateCoordinationStatusTool {
     pub fn new(bus: InMemoryMessageBus, security: Arc<SecurityPolicy>) -> Self {
-        Self { bus, security }
+        Self {
+            bus,
+            security: security.into(),
+        }
     }
 }
 
@@ -34,6 +37,10 @@ impl Tool for DelegateCoordinationStatusTool {
         "delegate_coordination_status"
     }
 
+    fn operation(&self) -> ToolOperation {
+        ToolOperation::Read
+    }
+
     fn description(&self) -> &str {
         "Inspect delegate coordination runtime state (agent inbox backlog, context state transitions, and dead-letter events)."
     }
@@ -111,12 +118,13 @@ impl Tool for DelegateCoordinationStatusTool {
     async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
         if let Err(error) = self
             .security
+            .current()
             .enforce_tool_operation(ToolOperation::Read, self.name())
         {
             return Ok(ToolResult {
                 success: false,
                 output: String::new(),
-                error: Some(error),
+                error: Some(error.to_string()),
             });
         }
 
@@ -0,0 +1,253 @@
+use super::memory_search::entry_date;
+use super::memory_store::check_memory_quota;
+use super::traits::{Tool, ToolExecutionContext, ToolResult};
+use crate::memory::import::inline_tags;
+use crate::memory::{self, Memory, MemoryCategory, MemoryEntry};
+use crate::security::policy::ToolOperation;
+use crate::security::{PolicyHandle, SecurityPolicy};
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Local};
+use serde_json::json;
+use std::sync::Arc;
+
+/// Tag and category of digest entries.
+pub const DIGEST_TAG: &str = "digest";
+
+/// Most notes returned for one digest, newest last.
+const MAX_DIGEST_NOTES: usize = 200;
+
+/// Let the agent condense the last 24 hours of memory into one entry.
+///
+/// Called without `summary`, it lists the notes written in the past 24
+/// hours (digests excluded). Called with `summary`, it stores that text as
+/// `digest_YYYY-MM-DD` in the `digest` category, tagged `#digest`. Memory
+/// hygiene leaves custom categories alone, so digests outlive the daily
+/// notes they summarize. The daily digest cron job drives both steps.
+pub struct DigestMemoryTool {
+    memory: Arc<dyn Memory>,
+    security: PolicyHandle,
+}
+
+impl DigestMemoryTool {
+    pub fn new(memory: Arc<dyn Memory>, security: Arc<SecurityPolicy>) -> Self {
+        Self {
+            memory,
+            security: security.into(),
+        }
+    }
+
+    /// Notes written in the past 24 hours, oldest first.
+    async fn recent_notes(&self) -> anyhow::Result<Vec<MemoryEntry>> {
+        let now = Local::now();
+        let cutoff = now - Duration::hours(24);
+        let mut notes: Vec<MemoryEntry> = self
+            .memory
+            .list(None, None)
+            .await?
+            .into_iter()
+            .filter(|e| !is_digest(e) && !memory::is_assistant_autosave_key(&e.key))
+            .filter(|e| match DateTime::parse_from_rfc3339(&e.timestamp) {
+                Ok(ts) => ts >= cutoff,
+                // Date-only timestamps (daily files) count if from today or yesterday.
+                Err(_) => entry_date(&e.timestamp).is_some_and(|d| d >= cutoff.date_naive()),
+            })
+            .collect();
+        notes.sort_by(|a, b| a.timestamp.cmp(&b.timestamp));
+        Ok(notes)
+    }
+}
+
+fn is_digest(entry: &MemoryEntry) -> bool {
+    entry.category == MemoryCategory::Custom(DIGEST_TAG.into())
+        || inline_tags(&entry.content).iter().any(|t| t == DIGEST_TAG)
+}
+
+fn failed(error: impl Into<String>) -> ToolResult {
+    ToolResult {
+        success: false,
+        output: String::new(),
+        error: Some(error.into()),
+    }
+}
+
+#[async_trait]
+impl Tool for DigestMemoryTool {
+    fn name(&self) -> &str {
+        "digest_memory"
+    }
+
+    fn description(&self) -> &str {
+        "Build a daily memory digest. Call without 'summary' to list the notes written in the past 24 hours; call with 'summary' to store your digest of them as one entry tagged #digest."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "summary": {
+                    "type": "string",
+                    "description": "The digest to store. Omit to list the notes to digest."
+                },
+                "key": {
+                    "type": "string",
+                    "description": "Key for the digest entry (default: digest_YYYY-MM-DD)"
+                }
+            }
+        })
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let security = self.security.current();
+        let summary = args
+            .get("summary")
+            .and_then(serde_json::Value::as_str)
+            .map(str::trim)
+            .filter(|s| !s.is_empty());
+
+        let notes = match self.recent_notes().await {
+            Ok(notes) => notes,
+            Err(e) => return Ok(failed(format!("Failed to read memory: {e}"))),
+        };
+
+        let Some(summary) = summary else {
+            if notes.is_empty() {
+                return Ok(ToolResult {
+                    success: true,
+                    output: "No notes written in the past 24 hours.".into(),
+                    error: None,
+                });
+            }
+            let total = notes.len();
+            let listed: Vec<serde_json::Value> = notes
+                .into_iter()
+                .skip(total.saturating_sub(MAX_DIGEST_NOTES))
+                .map(|e| {
+                    json!({
+                        "key": e.key,
+                        "category": e.category.to_string(),
+                        "timestamp": e.timestamp,
+                        "content": e.content,
+                    })
+                })
+                .collect();
+            return Ok(ToolResult {
+                success: true,
+                output: serde_json::to_string_pretty(&json!({
+                    "total": total,
+                    "notes": listed,
+                }))?,
+                error: None,
+            });
+        };
+
+        let key = args
+            .get("key")
+            .and_then(serde_json::Value::as_str)
+            .map(str::to_string)
+            .unwrap_or_else(|| format!("digest_{}", Local::now().format("%Y-%m-%d")));
+        let mut content = format!("{summary}\n\nTags: #{DIGEST_TAG}");
+        if let Some(line) = ToolExecutionContext::current()
+            .as_ref()
+            .and_then(memory::provenance_line)
+        {
+            content = format!("{content}\n{line}");
+        }
+
+        if let Err(error) =
+            check_memory_quota(self.memory.as_ref(), &security, &key, &content).await
+        {
+            return Ok(failed(error));
+        }
+        if let Err(error) = security.enforce_tool_operation(ToolOperation::Act, "digest_memory") {
+            return Ok(failed(error));
+        }
+
+        if let Err(e) = self
+            .memory
+            .store_from(
+                &key,
+                &content,
+                MemoryCategory::Custom(DIGEST_TAG.into()),
+                None,
+                self.name(),
+            )
+            .await
+        {
+            return Ok(failed(format!("Failed to store digest: {e}")));
+        }
+        security.record_memory_write();
+
+        Ok(ToolResult {
+            success: true,
+            output: format!("Stored digest: {key} ({} notes)", notes.len()),
+            error: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::SqliteMemory;
+    use crate::security::AutonomyLevel;
+    use tempfile::TempDir;
+
+    async fn setup(security: SecurityPolicy) -> (TempDir, Arc<dyn Memory>, DigestMemoryTool) {
+        let tmp = TempDir::new().unwrap();
+        let mem: Arc<dyn Memory> = Arc::new(SqliteMemory::new(tmp.path()).unwrap());
+        mem.store(
+            "standup",
+            "Release blocked on CI",
+            MemoryCategory::Daily,
+            None,
+        )
+        .await
+        .unwrap();
+        mem.store("lang", "User prefers Rust", MemoryCategory::Core, None)
+            .await
+            .unwrap();
+        let tool = DigestMemoryTool::new(mem.clone(), Arc::new(security));
+        (tmp, mem, tool)
+    }
+
+    #[tokio::test]
+    async fn lists_recent_notes_then_stores_tagged_digest() {
+        let (_tmp, mem, tool) = setup(SecurityPolicy::default()).await;
+
+        let result = tool.execute(json!({})).await.unwrap();
+        assert!(result.success);
+        let out: serde_json::Value = serde_json::from_str(&result.output).unwrap();
+        assert_eq!(out["total"], 2);
+
+        let result = tool
+            .execute(json!({"summary": "CI blocked the release; user prefers Rust.", "key": "digest_test"}))
+            .await
+            .unwrap();
+        assert!(result.success, "{:?}", result.error);
+        assert!(result.output.contains("2 notes"));
+
+        let entry = mem.get("digest_test").await.unwrap().unwrap();
+        assert_eq!(entry.category, MemoryCategory::Custom("digest".into()));
+        assert_eq!(inline_tags(&entry.content), vec!["digest".to_string()]);
+
+        // The stored digest is not itself a note for the next digest.
+        let out: serde_json::Value =
+            serde_json::from_str(&tool.execute(json!({})).await.unwrap().output).unwrap();
+        assert_eq!(out["total"], 2);
+    }
+
+    #[tokio::test]
+    async fn store_blocked_in_readonly_mode() {
+        let (_tmp, mem, tool) = setup(SecurityPolicy {
+            autonomy: AutonomyLevel::ReadOnly,
+            ..SecurityPolicy::default()
+        })
+        .await;
+        let result = tool
+            .execute(json!({"summary": "x", "key": "digest_test"}))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(mem.get("digest_test").await.unwrap().is_none());
+    }
+}
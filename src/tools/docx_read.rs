@@ -1,5 +1,6 @@
 use super::traits::{Tool, ToolResult};
-use crate::security::SecurityPolicy;
+use crate::security::policy::ToolOperation;
+use crate::security::{PolicyHandle, SecurityPolicy};
 use async_trait::async_trait;
 use serde_json::json;
 use std::sync::Arc;
@@ -13,12 +14,14 @@ const MAX_OUTPUT_CHARS: usize = 200_000;
 
 /// Extract plain text from a DOCX file in the workspace.
 pub struct DocxReadTool {
-    security: Arc<SecurityPolicy>,
+    security: PolicyHandle,
 }
 
 impl DocxReadTool {
     pub fn new(security: Arc<SecurityPolicy>) -> Self {
-        Self { security }
+        Self {
+            security: security.into(),
+        }
     }
 }
 
@@ -79,6 +82,10 @@ impl Tool for DocxReadTool {
         "docx_read"
     }
 
+    fn operation(&self) -> ToolOperation {
+        ToolOperation::Read
+    }
+
     fn description(&self) -> &str {
         "Extract plain text from a DOCX (Word) file in the workspace. \
          Returns all readable text content. No formatting, images, or charts."
@@ -104,6 +111,7 @@ impl Tool for DocxReadTool {
     }
 
     async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let security = self.security.current();
         let path = args
             .get("path")
             .and_then(|v| v.as_str())
@@ -119,7 +127,7 @@ impl Tool for DocxReadTool {
             })
             .unwrap_or(DEFAULT_MAX_CHARS);
 
-        if self.security.is_rate_limited() {
+        if security.is_rate_limited() {
             return Ok(ToolResult {
                 success: false,
                 output: String::new(),
@@ -127,7 +135,7 @@ impl Tool for DocxReadTool {
             });
         }
 
-        if !self.security.is_path_allowed(path) {
+        if !security.is_path_allowed(path) {
             return Ok(ToolResult {
                 success: false,
                 output: String::new(),
@@ -135,7 +143,7 @@ impl Tool for DocxReadTool {
             });
         }
 
-        if !self.security.record_action() {
+        if !security.record_action() {
             return Ok(ToolResult {
                 success: false,
                 output: String::new(),
@@ -143,30 +151,17 @@ impl Tool for DocxReadTool {
             });
         }
 
-        let full_path = self.security.resolve_user_supplied_path(path);
-
-        let resolved_path = match tokio::fs::canonicalize(&full_path).await {
+        let resolved_path = match security.resolve_in_workspace(path).await {
             Ok(p) => p,
-            Err(e) => {
+            Err(error) => {
                 return Ok(ToolResult {
                     success: false,
                     output: String::new(),
-                    error: Some(format!("Failed to resolve file path: {e}")),
+                    error: Some(error),
                 });
             }
         };
 
-        if !self.security.is_resolved_path_allowed(&resolved_path) {
-            return Ok(ToolResult {
-                success: false,
-                output: String::new(),
-                error: Some(
-                    self.security
-                        .resolved_path_violation_message(&resolved_path),
-                ),
-            });
-        }
-
         tracing::debug!("Reading DOCX: {}", resolved_path.display());
 
         match tokio::fs::metadata(&resolved_path).await {
@@ -202,7 +197,8 @@ impl Tool for DocxReadTool {
             }
         };
 
-        let text = match tokio::task::spawn_blocking(move || extract_docx_text(&bytes)).await {
+        let text = match crate::runtime::spawn_fs_blocking(move || extract_docx_text(&bytes)).await
+        {
             Ok(Ok(t)) => t,
             Ok(Err(e)) => {
                 return Ok(ToolResult {
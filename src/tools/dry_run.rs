@@ -0,0 +1,127 @@
+//! Global dry-run mode (`[autonomy] dry_run`).
+//!
+//! Tools listed in [`DRY_RUN_AWARE_TOOLS`] check
+//! [`SecurityPolicy::dry_run`](crate::security::SecurityPolicy::dry_run)
+//! themselves: they validate the call as usual and stop right before the side
+//! effect, reporting what they would have done through [`simulated`]. Every
+//! other `Act` tool in the runtime registry is wrapped in a [`DryRunTool`],
+//! which runs the policy check and answers with the call it would have made.
+
+use super::traits::{delegate_tool_metadata, Tool, ToolResult};
+use crate::security::policy::ToolOperation;
+use crate::security::{PolicyHandle, SecurityPolicy};
+use async_trait::async_trait;
+use std::sync::Arc;
+
+/// Marks the output of a call that was validated but not carried out.
+pub const DRY_RUN_PREFIX: &str = "[dry run] ";
+
+/// Built-in `Act` tools that implement dry-run mode themselves.
+pub(crate) const DRY_RUN_AWARE_TOOLS: &[&str] = &[
+    "file_edit",
+    "file_write",
+    "memory_forget",
+    "memory_store",
+    "shell",
+];
+
+/// Successful result for a simulated call, tagged with [`DRY_RUN_PREFIX`].
+pub fn simulated(output: impl std::fmt::Display) -> ToolResult {
+    ToolResult {
+        success: true,
+        output: format!("{DRY_RUN_PREFIX}{output}"),
+        error: None,
+    }
+}
+
+/// Whether `tool` needs a [`DryRunTool`] wrapper in dry-run mode.
+pub(crate) fn needs_dry_run_wrapper(tool: &dyn Tool) -> bool {
+    tool.operation() == ToolOperation::Act && !DRY_RUN_AWARE_TOOLS.contains(&tool.name())
+}
+
+/// Stands in for an `Act` tool in dry-run mode: checks the call against the
+/// security policy and reports it instead of executing it.
+pub struct DryRunTool {
+    inner: Box<dyn Tool>,
+    security: PolicyHandle,
+}
+
+impl DryRunTool {
+    pub fn new(inner: Box<dyn Tool>, security: Arc<SecurityPolicy>) -> Self {
+        Self {
+            inner,
+            security: security.into(),
+        }
+    }
+}
+
+#[async_trait]
+impl Tool for DryRunTool {
+    delegate_tool_metadata!();
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        if let Err(error) = self
+            .security
+            .current()
+            .enforce_tool_operation(ToolOperation::Act, self.inner.name())
+        {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(error.to_string()),
+            });
+        }
+        Ok(simulated(format!(
+            "Would call '{}' with arguments {args}",
+            self.inner.name()
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::security::AutonomyLevel;
+    use crate::tools::test_support::StubTool;
+
+    #[tokio::test]
+    async fn wrapper_reports_call_without_running_it() {
+        let security = Arc::new(SecurityPolicy {
+            dry_run: true,
+            ..SecurityPolicy::default()
+        });
+        let stub = StubTool::new("cron_add");
+        let calls = stub.calls();
+        let tool = DryRunTool::new(Box::new(stub), security);
+        let result = tool
+            .execute(serde_json::json!({"expression": "0 9 * * *"}))
+            .await
+            .unwrap();
+        assert!(result.success);
+        assert!(result.output.starts_with(DRY_RUN_PREFIX));
+        assert!(result.output.contains("0 9 * * *"));
+        assert_eq!(calls.get(), 0);
+    }
+
+    #[tokio::test]
+    async fn wrapper_still_applies_policy() {
+        let security = Arc::new(SecurityPolicy {
+            autonomy: AutonomyLevel::ReadOnly,
+            dry_run: true,
+            ..SecurityPolicy::default()
+        });
+        let tool = DryRunTool::new(Box::new(StubTool::new("cron_add")), security);
+        let result = tool.execute(serde_json::json!({})).await.unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("read-only"));
+    }
+
+    #[test]
+    fn aware_and_read_only_tools_are_not_wrapped() {
+        assert!(needs_dry_run_wrapper(&StubTool::new("cron_add")));
+        assert!(!needs_dry_run_wrapper(&StubTool::new("memory_store")));
+        assert!(!needs_dry_run_wrapper(
+            &StubTool::new("file_read").with_operation(ToolOperation::Read)
+        ));
+    }
+}
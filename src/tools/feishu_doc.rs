@@ -1,4 +1,4 @@
-use crate::security::{policy::ToolOperation, SecurityPolicy};
+use crate::security::{policy::ToolOperation, PolicyHandle, SecurityPolicy};
 use crate::tools::traits::{Tool, ToolResult};
 use async_trait::async_trait;
 use reqwest::Method;
@@ -42,9 +42,10 @@ pub struct FeishuDocTool {
     app_id: String,
     app_secret: String,
     use_feishu: bool,
-    security: Arc<SecurityPolicy>,
+    security: PolicyHandle,
     tenant_token: Arc<RwLock<Option<CachedTenantToken>>>,
-    client: reqwest::Client,
+    /// Built on first request so registering the tool stays cheap at startup.
+    client: std::sync::OnceLock<reqwest::Client>,
 }
 
 impl FeishuDocTool {
@@ -60,7 +61,7 @@ impl FeishuDocTool {
             use_feishu,
             security,
             tenant_token: Arc::new(RwLock::new(None)),
-            client: crate::config::build_runtime_proxy_client("tool.feishu_doc"),
+            client: std::sync::OnceLock::new(),
         }
     }
 
@@ -73,7 +74,8 @@ impl FeishuDocTool {
     }
 
     fn http_client(&self) -> &reqwest::Client {
-        &self.client
+        self.client
+            .get_or_init(|| crate::config::build_runtime_proxy_client("tool.feishu_doc"))
     }
 
     async fn get_tenant_access_token(&self) -> anyhow::Result<String> {
@@ -972,6 +974,11 @@ impl FeishuDocTool {
         if crate::tools::url_validation::is_private_or_local_host(host) {
             anyhow::bail!("Blocked local/private host in media URL: {}", host);
         }
+        self.security
+            .current()
+            .egress()
+            .check_url(url)
+            .map_err(anyhow::Error::msg)?;
 
         // Use a no-redirect client to prevent SSRF bypass via HTTP redirects
         // (an attacker could redirect to internal/private IPs after initial URL validation)
@@ -1027,13 +1034,14 @@ impl FeishuDocTool {
         file_path: &str,
         filename_override: Option<String>,
     ) -> anyhow::Result<LoadedMedia> {
-        if !self.security.is_path_allowed(file_path) {
+        let security = self.security.current();
+        if !security.is_path_allowed(file_path) {
             anyhow::bail!("Path not allowed by security policy: {}", file_path);
         }
 
-        let resolved = resolve_workspace_path(&self.security.workspace_dir, file_path)?;
-        if !self.security.is_resolved_path_allowed(&resolved) {
-            anyhow::bail!(self.security.resolved_path_violation_message(&resolved));
+        let resolved = resolve_workspace_path(&security.workspace_dir, file_path)?;
+        if !security.is_resolved_path_allowed(&resolved) {
+            anyhow::bail!(security.resolved_path_violation_message(&resolved));
         }
 
         let metadata = tokio::fs::metadata(&resolved).await?;
@@ -1251,6 +1259,7 @@ impl Tool for FeishuDocTool {
     }
 
     async fn execute(&self, args: Value) -> anyhow::Result<ToolResult> {
+        let security = self.security.current();
         let action = match args.get("action").and_then(Value::as_str) {
             Some(v) if !v.trim().is_empty() => v,
             _ => {
@@ -1266,10 +1275,14 @@ impl Tool for FeishuDocTool {
             "read" | "list_blocks" | "get_block" => ToolOperation::Read,
             _ => ToolOperation::Act,
         };
-        if let Err(e) = self
-            .security
-            .enforce_tool_operation(operation, "feishu_doc")
-        {
+        if let Err(e) = security.enforce_tool_operation(operation, "feishu_doc") {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(e.to_string()),
+            });
+        }
+        if let Err(e) = security.egress().check_url(self.api_base()) {
             return Ok(ToolResult {
                 success: false,
                 output: String::new(),
@@ -1,7 +1,8 @@
+use super::dry_run;
 use super::traits::{Tool, ToolResult};
 use crate::security::file_link_guard::has_multiple_hard_links;
 use crate::security::sensitive_paths::is_sensitive_file_path;
-use crate::security::SecurityPolicy;
+use crate::security::{PolicyHandle, SecurityPolicy};
 use async_trait::async_trait;
 use serde_json::json;
 use std::path::Path;
@@ -16,12 +17,14 @@ use std::sync::Arc;
 /// matches = ambiguous). `new_string` may be empty to delete the matched text.
 /// Security checks mirror [`super::file_write::FileWriteTool`].
 pub struct FileEditTool {
-    security: Arc<SecurityPolicy>,
+    security: PolicyHandle,
 }
 
 impl FileEditTool {
     pub fn new(security: Arc<SecurityPolicy>) -> Self {
-        Self { security }
+        Self {
+            security: security.into(),
+        }
     }
 }
 
@@ -235,6 +238,7 @@ impl Tool for FileEditTool {
     }
 
     async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let security = self.security.current();
         // ── 1. Extract parameters ──────────────────────────────────
         let path = args
             .get("path")
@@ -260,7 +264,7 @@ impl Tool for FileEditTool {
         }
 
         // ── 2. Autonomy check ──────────────────────────────────────
-        if !self.security.can_act() {
+        if !security.can_act() {
             return Ok(ToolResult {
                 success: false,
                 output: String::new(),
@@ -269,7 +273,7 @@ impl Tool for FileEditTool {
         }
 
         // ── 3. Rate limit check ────────────────────────────────────
-        if self.security.is_rate_limited() {
+        if security.is_rate_limited() {
             return Ok(ToolResult {
                 success: false,
                 output: String::new(),
@@ -278,7 +282,7 @@ impl Tool for FileEditTool {
         }
 
         // ── 4. Path pre-validation ─────────────────────────────────
-        if !self.security.is_path_allowed(path) {
+        if !security.is_path_allowed(path) {
             return Ok(ToolResult {
                 success: false,
                 output: String::new(),
@@ -286,7 +290,7 @@ impl Tool for FileEditTool {
             });
         }
 
-        if !self.security.allow_sensitive_file_writes && is_sensitive_file_path(Path::new(path)) {
+        if !security.allow_sensitive_file_writes && is_sensitive_file_path(Path::new(path)) {
             return Ok(ToolResult {
                 success: false,
                 output: String::new(),
@@ -294,51 +298,19 @@ impl Tool for FileEditTool {
             });
         }
 
-        let full_path = self.security.resolve_user_supplied_path(path);
-
-        // ── 5. Canonicalize parent ─────────────────────────────────
-        let Some(parent) = full_path.parent() else {
-            return Ok(ToolResult {
-                success: false,
-                output: String::new(),
-                error: Some("Invalid path: missing parent directory".into()),
-            });
-        };
-
-        let resolved_parent = match tokio::fs::canonicalize(parent).await {
+        // ── 5. Resolve target ──────────────────────────────────────
+        let resolved_target = match security.resolve_write_target(path).await {
             Ok(p) => p,
-            Err(e) => {
+            Err(error) => {
                 return Ok(ToolResult {
                     success: false,
                     output: String::new(),
-                    error: Some(format!("Failed to resolve file path: {e}")),
+                    error: Some(error),
                 });
             }
         };
 
-        // ── 6. Resolved path post-validation ───────────────────────
-        if !self.security.is_resolved_path_allowed(&resolved_parent) {
-            return Ok(ToolResult {
-                success: false,
-                output: String::new(),
-                error: Some(
-                    self.security
-                        .resolved_path_violation_message(&resolved_parent),
-                ),
-            });
-        }
-
-        let Some(file_name) = full_path.file_name() else {
-            return Ok(ToolResult {
-                success: false,
-                output: String::new(),
-                error: Some("Invalid path: missing file name".into()),
-            });
-        };
-
-        let resolved_target = resolved_parent.join(file_name);
-
-        if !self.security.allow_sensitive_file_writes && is_sensitive_file_path(&resolved_target) {
+        if !security.allow_sensitive_file_writes && is_sensitive_file_path(&resolved_target) {
             return Ok(ToolResult {
                 success: false,
                 output: String::new(),
@@ -348,7 +320,7 @@ impl Tool for FileEditTool {
             });
         }
 
-        // ── 7. Symlink check ───────────────────────────────────────
+        // ── 6. Symlink check ───────────────────────────────────────
         if let Ok(meta) = tokio::fs::symlink_metadata(&resolved_target).await {
             if meta.file_type().is_symlink() {
                 return Ok(ToolResult {
@@ -370,8 +342,8 @@ impl Tool for FileEditTool {
             }
         }
 
-        // ── 8. Record action ───────────────────────────────────────
-        if !self.security.record_action() {
+        // ── 7. Record action ───────────────────────────────────────
+        if !security.record_action() {
             return Ok(ToolResult {
                 success: false,
                 output: String::new(),
@@ -379,8 +351,8 @@ impl Tool for FileEditTool {
             });
         }
 
-        // ── 9. Read → match → replace → write ─────────────────────
-        let content = match tokio::fs::read_to_string(&resolved_target).await {
+        // ── 8. Read → match → replace → write ─────────────────────
+        let content = match security.fs().read_to_string(&resolved_target).await {
             Ok(c) => c,
             Err(e) => {
                 return Ok(ToolResult {
@@ -417,7 +389,14 @@ impl Tool for FileEditTool {
         new_content.push_str(new_string);
         new_content.push_str(&content[match_outcome.end..]);
 
-        match tokio::fs::write(&resolved_target, &new_content).await {
+        if security.dry_run {
+            return Ok(dry_run::simulated(format!(
+                "Would edit {path}: replace 1 occurrence, giving {} bytes:\n{new_content}",
+                new_content.len()
+            )));
+        }
+
+        match security.fs().write(&resolved_target, &new_content).await {
             Ok(()) => Ok(ToolResult {
                 success: true,
                 output: format!(
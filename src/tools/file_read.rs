@@ -1,7 +1,8 @@
 use super::traits::{Tool, ToolResult};
 use crate::security::file_link_guard::has_multiple_hard_links;
+use crate::security::policy::ToolOperation;
 use crate::security::sensitive_paths::is_sensitive_file_path;
-use crate::security::SecurityPolicy;
+use crate::security::{PolicyHandle, SecurityPolicy};
 use async_trait::async_trait;
 use serde_json::json;
 use std::path::Path;
@@ -26,12 +27,14 @@ fn hard_link_block_message(path: &Path) -> String {
 
 /// Read file contents with path sandboxing
 pub struct FileReadTool {
-    security: Arc<SecurityPolicy>,
+    security: PolicyHandle,
 }
 
 impl FileReadTool {
     pub fn new(security: Arc<SecurityPolicy>) -> Self {
-        Self { security }
+        Self {
+            security: security.into(),
+        }
     }
 }
 
@@ -41,6 +44,10 @@ impl Tool for FileReadTool {
         "file_read"
     }
 
+    fn operation(&self) -> ToolOperation {
+        ToolOperation::Read
+    }
+
     fn description(&self) -> &str {
         "Read file contents with line numbers. Supports partial reading via offset and limit. Extracts text from PDF; other binary files are read with lossy UTF-8 conversion. Sensitive files (for example .env and key material) are blocked by default."
     }
@@ -67,12 +74,13 @@ impl Tool for FileReadTool {
     }
 
     async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let security = self.security.current();
         let path = args
             .get("path")
             .and_then(|v| v.as_str())
             .ok_or_else(|| anyhow::anyhow!("Missing 'path' parameter"))?;
 
-        if self.security.is_rate_limited() {
+        if security.is_rate_limited() {
             return Ok(ToolResult {
                 success: false,
                 output: String::new(),
@@ -81,7 +89,7 @@ impl Tool for FileReadTool {
         }
 
         // Security check: validate path is within workspace
-        if !self.security.is_path_allowed(path) {
+        if !security.is_path_allowed(path) {
             return Ok(ToolResult {
                 success: false,
                 output: String::new(),
@@ -89,7 +97,7 @@ impl Tool for FileReadTool {
             });
         }
 
-        if !self.security.allow_sensitive_file_reads && is_sensitive_file_path(Path::new(path)) {
+        if !security.allow_sensitive_file_reads && is_sensitive_file_path(Path::new(path)) {
             return Ok(ToolResult {
                 success: false,
                 output: String::new(),
@@ -100,7 +108,7 @@ impl Tool for FileReadTool {
         // Record action BEFORE canonicalization so that every non-trivially-rejected
         // request consumes rate limit budget. This prevents attackers from probing
         // path existence (via canonicalize errors) without rate limit cost.
-        if !self.security.record_action() {
+        if !security.record_action() {
             return Ok(ToolResult {
                 success: false,
                 output: String::new(),
@@ -108,32 +116,19 @@ impl Tool for FileReadTool {
             });
         }
 
-        let full_path = self.security.resolve_user_supplied_path(path);
-
         // Resolve path before reading to block symlink escapes.
-        let resolved_path = match tokio::fs::canonicalize(&full_path).await {
+        let resolved_path = match security.resolve_in_workspace(path).await {
             Ok(p) => p,
-            Err(e) => {
+            Err(error) => {
                 return Ok(ToolResult {
                     success: false,
                     output: String::new(),
-                    error: Some(format!("Failed to resolve file path: {e}")),
+                    error: Some(error),
                 });
             }
         };
 
-        if !self.security.is_resolved_path_allowed(&resolved_path) {
-            return Ok(ToolResult {
-                success: false,
-                output: String::new(),
-                error: Some(
-                    self.security
-                        .resolved_path_violation_message(&resolved_path),
-                ),
-            });
-        }
-
-        if !self.security.allow_sensitive_file_reads && is_sensitive_file_path(&resolved_path) {
+        if !security.allow_sensitive_file_reads && is_sensitive_file_path(&resolved_path) {
             return Ok(ToolResult {
                 success: false,
                 output: String::new(),
@@ -174,7 +169,7 @@ impl Tool for FileReadTool {
             }
         }
 
-        match tokio::fs::read_to_string(&resolved_path).await {
+        match security.fs().read_to_string(&resolved_path).await {
             Ok(contents) => {
                 let lines: Vec<&str> = contents.lines().collect();
                 let total = lines.len();
@@ -236,7 +231,9 @@ impl Tool for FileReadTool {
             }
             Err(_) => {
                 // Not valid UTF-8 — read raw bytes and try to extract text
-                let bytes = tokio::fs::read(&resolved_path)
+                let bytes = security
+                    .fs()
+                    .read(&resolved_path)
                     .await
                     .map_err(|e| anyhow::anyhow!("Failed to read file: {e}"))?;
 
@@ -1,7 +1,8 @@
+use super::dry_run;
 use super::traits::{Tool, ToolResult};
 use crate::security::file_link_guard::has_multiple_hard_links;
 use crate::security::sensitive_paths::is_sensitive_file_path;
-use crate::security::SecurityPolicy;
+use crate::security::{PolicyHandle, SecurityPolicy};
 use async_trait::async_trait;
 use serde_json::json;
 use std::path::Path;
@@ -9,12 +10,14 @@ use std::sync::Arc;
 
 /// Write file contents with path sandboxing
 pub struct FileWriteTool {
-    security: Arc<SecurityPolicy>,
+    security: PolicyHandle,
 }
 
 impl FileWriteTool {
     pub fn new(security: Arc<SecurityPolicy>) -> Self {
-        Self { security }
+        Self {
+            security: security.into(),
+        }
     }
 }
 
@@ -61,6 +64,7 @@ impl Tool for FileWriteTool {
     }
 
     async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let security = self.security.current();
         let path = args
             .get("path")
             .and_then(|v| v.as_str())
@@ -71,7 +75,7 @@ impl Tool for FileWriteTool {
             .and_then(|v| v.as_str())
             .ok_or_else(|| anyhow::anyhow!("Missing 'content' parameter"))?;
 
-        if !self.security.can_act() {
+        if !security.can_act() {
             return Ok(ToolResult {
                 success: false,
                 output: String::new(),
@@ -79,7 +83,7 @@ impl Tool for FileWriteTool {
             });
         }
 
-        if self.security.is_rate_limited() {
+        if security.is_rate_limited() {
             return Ok(ToolResult {
                 success: false,
                 output: String::new(),
@@ -88,7 +92,7 @@ impl Tool for FileWriteTool {
         }
 
         // Security check: validate path is within workspace
-        if !self.security.is_path_allowed(path) {
+        if !security.is_path_allowed(path) {
             return Ok(ToolResult {
                 success: false,
                 output: String::new(),
@@ -96,7 +100,7 @@ impl Tool for FileWriteTool {
             });
         }
 
-        if !self.security.allow_sensitive_file_writes && is_sensitive_file_path(Path::new(path)) {
+        if !security.allow_sensitive_file_writes && is_sensitive_file_path(Path::new(path)) {
             return Ok(ToolResult {
                 success: false,
                 output: String::new(),
@@ -104,53 +108,24 @@ impl Tool for FileWriteTool {
             });
         }
 
-        let full_path = self.security.resolve_user_supplied_path(path);
-
-        let Some(parent) = full_path.parent() else {
-            return Ok(ToolResult {
-                success: false,
-                output: String::new(),
-                error: Some("Invalid path: missing parent directory".into()),
-            });
-        };
-
-        // Ensure parent directory exists
-        tokio::fs::create_dir_all(parent).await?;
-
-        // Resolve parent AFTER creation to block symlink escapes.
-        let resolved_parent = match tokio::fs::canonicalize(parent).await {
+        // Resolve BEFORE creating directories to block symlink escapes.
+        let resolved_target = match security.resolve_write_target(path).await {
             Ok(p) => p,
-            Err(e) => {
+            Err(error) => {
                 return Ok(ToolResult {
                     success: false,
                     output: String::new(),
-                    error: Some(format!("Failed to resolve file path: {e}")),
+                    error: Some(error),
                 });
             }
         };
 
-        if !self.security.is_resolved_path_allowed(&resolved_parent) {
-            return Ok(ToolResult {
-                success: false,
-                output: String::new(),
-                error: Some(
-                    self.security
-                        .resolved_path_violation_message(&resolved_parent),
-                ),
-            });
+        // Ensure parent directory exists
+        if let Some(parent) = resolved_target.parent().filter(|_| !security.dry_run) {
+            security.fs().create_dir_all(parent).await?;
         }
 
-        let Some(file_name) = full_path.file_name() else {
-            return Ok(ToolResult {
-                success: false,
-                output: String::new(),
-                error: Some("Invalid path: missing file name".into()),
-            });
-        };
-
-        let resolved_target = resolved_parent.join(file_name);
-
-        if !self.security.allow_sensitive_file_writes && is_sensitive_file_path(&resolved_target) {
+        if !security.allow_sensitive_file_writes && is_sensitive_file_path(&resolved_target) {
             return Ok(ToolResult {
                 success: false,
                 output: String::new(),
@@ -182,7 +157,7 @@ impl Tool for FileWriteTool {
             }
         }
 
-        if !self.security.record_action() {
+        if !security.record_action() {
             return Ok(ToolResult {
                 success: false,
                 output: String::new(),
@@ -190,7 +165,14 @@ impl Tool for FileWriteTool {
             });
         }
 
-        match tokio::fs::write(&resolved_target, content).await {
+        if security.dry_run {
+            return Ok(dry_run::simulated(format!(
+                "Would write {} bytes to {path}:\n{content}",
+                content.len()
+            )));
+        }
+
+        match security.fs().write(&resolved_target, content).await {
             Ok(()) => Ok(ToolResult {
                 success: true,
                 output: format!("Written {} bytes to {path}", content.len()),
@@ -294,6 +276,60 @@ mod tests {
         let _ = tokio::fs::remove_dir_all(&dir).await;
     }
 
+    #[tokio::test]
+    async fn file_write_dry_run_leaves_filesystem_untouched() {
+        let dir = std::env::temp_dir().join("zeroclaw_test_file_write_dry_run");
+        let _ = tokio::fs::remove_dir_all(&dir).await;
+        tokio::fs::create_dir_all(&dir).await.unwrap();
+
+        let tool = FileWriteTool::new(Arc::new(SecurityPolicy {
+            workspace_dir: dir.clone(),
+            dry_run: true,
+            ..SecurityPolicy::default()
+        }));
+        let result = tool
+            .execute(json!({"path": "new/out.txt", "content": "written!"}))
+            .await
+            .unwrap();
+        assert!(result.success);
+        assert!(result.output.starts_with(dry_run::DRY_RUN_PREFIX));
+        assert!(result.output.contains("written!"));
+        assert!(!dir.join("new").exists());
+
+        let _ = tokio::fs::remove_dir_all(&dir).await;
+    }
+
+    #[tokio::test]
+    async fn file_write_refuses_policy_state() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path().canonicalize().unwrap();
+        let workspace = root.join("workspace");
+        let state_dir = root.join("state");
+        tokio::fs::create_dir_all(&workspace).await.unwrap();
+
+        // Even with the whole directory allowed, escalation grants stay out
+        // of reach.
+        let tool = FileWriteTool::new(Arc::new(SecurityPolicy {
+            workspace_dir: workspace,
+            workspace_only: false,
+            allowed_roots: vec![root.clone()],
+            forbidden_paths: vec![],
+            state_dir: Some(state_dir.clone()),
+            ..SecurityPolicy::default()
+        }));
+        let target = state_dir.join(crate::security::escalation::ESCALATION_STATE_FILE);
+        let result = tool
+            .execute(json!({"path": target.to_string_lossy(), "content": "[]"}))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result
+            .error
+            .unwrap()
+            .contains("not allowed by security policy"));
+        assert!(!target.exists());
+    }
+
     #[tokio::test]
     async fn file_write_creates_parent_dirs() {
         let dir = std::env::temp_dir().join("zeroclaw_test_file_write_nested");
@@ -0,0 +1,566 @@
+//! Security-policy gate for tools that do not enforce policy themselves.
+//!
+//! Built-in tools call [`SecurityPolicy::enforce_tool_operation`] from their
+//! own `execute`. Tools supplied by embedders (language bindings, library
+//! users) know nothing about the policy, so [`PolicyGatedTool`] runs the same
+//! check before delegating. [`ApprovalGatedTool`] puts built-in tools to the
+//! policy's approval hook before they run their own check. A call that needs
+//! approval returns a pending result with the request id straight away and
+//! runs once the operator approves; `approval_status` collects its result.
+//! The held call keeps the caller's [`ToolExecutionContext`] and policy
+//! version, and its real outcome is audited and size-capped like any other
+//! call. Its arguments were scanned before the operator was asked.
+//! [`LivePolicyGatedTool`] points a tool's own checks at the newest version
+//! of the hot-reloaded `[security] policy_file`, and [`CapabilityGatedTool`]
+//! limits a sub-agent's tools to its [`CapabilityToken`].
+
+use super::audited::audit_call;
+use super::size_limited::cap_result;
+use super::traits::{delegate_tool_metadata, Tool, ToolExecutionContext, ToolResult, ToolSpec};
+use crate::security::audit::AuditLogger;
+use crate::security::live_policy::{pinned_call_version, with_call_version};
+use crate::security::policy::ToolOperation;
+use crate::security::{
+    ApprovalStatus, ApprovalTicket, CapabilityToken, LiveSecurityPolicy, PolicyHandle,
+    SecurityPolicy,
+};
+use async_trait::async_trait;
+use std::future::Future;
+use std::sync::Arc;
+
+fn refused(error: impl ToString) -> ToolResult {
+    ToolResult {
+        success: false,
+        output: String::new(),
+        error: Some(error.to_string()),
+    }
+}
+
+/// Hold the call of `tool_name` with `args` until the operator answers
+/// `ticket`, then `run` it if approved. It runs with the caller's
+/// [`ToolExecutionContext`] and policy version, is written to `audit`
+/// either way, and its result is cut to `max_tool_output_bytes`. The
+/// returned result tells the model the call is pending.
+fn defer_until_approved<F>(
+    security: Arc<SecurityPolicy>,
+    audit: Option<Arc<AuditLogger>>,
+    ticket: ApprovalTicket,
+    tool_name: &str,
+    args: serde_json::Value,
+    run: F,
+) -> ToolResult
+where
+    F: Future<Output = anyhow::Result<ToolResult>> + Send + 'static,
+{
+    let context = ToolExecutionContext::current();
+    let pinned = pinned_call_version();
+    let name = tool_name.to_string();
+    let pending = security.pending_approvals.clone();
+    let id = pending.defer(ticket, tool_name, move |answer| {
+        let (asked, declined) = (Arc::clone(&security), name.clone());
+        let call = async move {
+            if answer == ApprovalStatus::Approved {
+                run.await
+            } else {
+                Ok(refused(asked.approval_declined(&declined)))
+            }
+        };
+        let resumed = with_call_version(pinned, async move {
+            let outcome = match &audit {
+                Some(logger) => audit_call(logger, &name, &args, call).await,
+                None => call.await,
+            };
+            let mut result = outcome.unwrap_or_else(refused);
+            cap_result(&mut result, security.max_tool_output_bytes);
+            result
+        });
+        async move {
+            match context {
+                Some(context) => context.scope(resumed).await,
+                None => resumed.await,
+            }
+        }
+    });
+    ToolResult {
+        success: true,
+        output: format!(
+            "Awaiting operator approval (request {id}). {tool_name} runs once the operator \
+             approves; call approval_status with this request_id for its result."
+        ),
+        error: None,
+    }
+}
+
+/// Wraps a tool so every call is checked against a [`SecurityPolicy`].
+///
+/// `Act` tools are refused in read-only autonomy and consume the hourly
+/// action budget. In supervised mode with an approval hook installed they
+/// are held until the operator approves, and the budget is charged only
+/// then. `Read` tools pass straight through.
+pub struct PolicyGatedTool {
+    inner: Arc<dyn Tool>,
+    security: PolicyHandle,
+    operation: ToolOperation,
+}
+
+impl PolicyGatedTool {
+    pub fn new(
+        inner: Box<dyn Tool>,
+        security: Arc<SecurityPolicy>,
+        operation: ToolOperation,
+    ) -> Self {
+        Self {
+            inner: Arc::from(inner),
+            security: security.into(),
+            operation,
+        }
+    }
+}
+
+#[async_trait]
+impl Tool for PolicyGatedTool {
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    fn description(&self) -> &str {
+        self.inner.description()
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        self.inner.parameters_schema()
+    }
+
+    fn spec(&self) -> ToolSpec {
+        self.inner.spec()
+    }
+
+    fn operation(&self) -> ToolOperation {
+        self.operation
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let security = self.security.current();
+        let ticket = match security.request_approval(self.operation, self.inner.name(), &args) {
+            Ok(ticket) => ticket,
+            Err(error) => return Ok(refused(error)),
+        };
+        let inner = Arc::clone(&self.inner);
+        let handle = self.security.clone();
+        let operation = self.operation;
+        let run = move |args: serde_json::Value| async move {
+            if let Err(error) = handle
+                .current()
+                .enforce_tool_operation(operation, inner.name())
+            {
+                return Ok(refused(error));
+            }
+            inner.execute(args).await
+        };
+        let Some(ticket) = ticket else {
+            return run(args).await;
+        };
+        let audit = security.audit_logger.clone();
+        Ok(defer_until_approved(
+            security,
+            audit,
+            ticket,
+            self.inner.name(),
+            args.clone(),
+            run(args),
+        ))
+    }
+}
+
+/// Wraps a tool built against a [`LiveSecurityPolicy`] so each call is
+/// checked against the version in effect when it starts. The tool still runs
+/// its own checks, and charges its action once; its [`PolicyHandle`]
+/// resolves to that version for the whole call.
+pub struct LivePolicyGatedTool {
+    inner: Box<dyn Tool>,
+    live: Arc<LiveSecurityPolicy>,
+}
+
+impl LivePolicyGatedTool {
+    pub fn new(inner: Box<dyn Tool>, live: Arc<LiveSecurityPolicy>) -> Self {
+        Self { inner, live }
+    }
+}
+
+#[async_trait]
+impl Tool for LivePolicyGatedTool {
+    delegate_tool_metadata!();
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        self.live.scope(self.inner.execute(args)).await
+    }
+}
+
+/// Wraps a built-in tool so its `Act` calls in supervised mode are held
+/// until the policy's approval hook approves them. The tool's own policy
+/// check then charges the action budgets, so a declined call spends none of
+/// them.
+pub struct ApprovalGatedTool {
+    inner: Arc<dyn Tool>,
+    security: PolicyHandle,
+    audit: Option<Arc<AuditLogger>>,
+}
+
+impl ApprovalGatedTool {
+    /// Held calls are audited to the policy's `audit_logger`, if any.
+    pub fn new(inner: Box<dyn Tool>, security: Arc<SecurityPolicy>) -> Self {
+        Self {
+            inner: Arc::from(inner),
+            audit: security.audit_logger.clone(),
+            security: security.into(),
+        }
+    }
+
+    /// Audit held calls to `logger` instead.
+    pub fn with_audit(mut self, logger: Option<Arc<AuditLogger>>) -> Self {
+        self.audit = logger;
+        self
+    }
+}
+
+#[async_trait]
+impl Tool for ApprovalGatedTool {
+    delegate_tool_metadata!();
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let security = self.security.current();
+        let ticket =
+            match security.request_approval(self.inner.operation(), self.inner.name(), &args) {
+                Ok(Some(ticket)) => ticket,
+                Ok(None) => return self.inner.execute(args).await,
+                Err(error) => return Ok(refused(error)),
+            };
+        let inner = Arc::clone(&self.inner);
+        Ok(defer_until_approved(
+            security,
+            self.audit.clone(),
+            ticket,
+            self.inner.name(),
+            args.clone(),
+            async move { inner.execute(args).await },
+        ))
+    }
+}
+
+/// Wraps a tool handed to a delegate so every call is first checked against
+/// the delegate's [`CapabilityToken`]: scope, expiry, and action budget.
+pub struct CapabilityGatedTool {
+    inner: Box<dyn Tool>,
+    token: Arc<CapabilityToken>,
+}
+
+impl CapabilityGatedTool {
+    pub fn new(inner: Box<dyn Tool>, token: Arc<CapabilityToken>) -> Self {
+        Self { inner, token }
+    }
+}
+
+#[async_trait]
+impl Tool for CapabilityGatedTool {
+    delegate_tool_metadata!();
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let operation = self.inner.operation();
+        if let Err(error) = self.token.authorize(operation, self.inner.name()) {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(error),
+            });
+        }
+        self.inner.execute(args).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::security::AutonomyLevel;
+    use crate::tools::test_support::{approval_outcome, CallCount, StubTool};
+
+    fn gated(policy: SecurityPolicy, operation: ToolOperation) -> (PolicyGatedTool, CallCount) {
+        let stub = StubTool::new("counting");
+        let calls = stub.calls();
+        let tool = PolicyGatedTool::new(Box::new(stub), Arc::new(policy), operation);
+        (tool, calls)
+    }
+
+    #[tokio::test]
+    async fn act_tool_is_blocked_in_read_only_mode() {
+        let policy = SecurityPolicy {
+            autonomy: AutonomyLevel::ReadOnly,
+            ..SecurityPolicy::default()
+        };
+        let (tool, calls) = gated(policy, ToolOperation::Act);
+        let result = tool.execute(serde_json::json!({})).await.unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("read-only"));
+        assert_eq!(calls.get(), 0);
+    }
+
+    #[tokio::test]
+    async fn act_tool_consumes_action_budget() {
+        let policy = SecurityPolicy {
+            max_actions_per_hour: 1,
+            ..SecurityPolicy::default()
+        };
+        let (tool, calls) = gated(policy, ToolOperation::Act);
+        assert!(tool.execute(serde_json::json!({})).await.unwrap().success);
+        let second = tool.execute(serde_json::json!({})).await.unwrap();
+        assert!(!second.success);
+        assert!(second.error.unwrap().contains("Rate limit"));
+        assert_eq!(calls.get(), 1);
+    }
+
+    #[derive(Debug)]
+    struct DenyAll;
+
+    #[async_trait]
+    impl crate::security::ApprovalHook for DenyAll {
+        async fn request_approval(
+            &self,
+            _request: &crate::approval::ApprovalRequest,
+        ) -> crate::approval::ApprovalResponse {
+            crate::approval::ApprovalResponse::No
+        }
+    }
+
+    #[tokio::test]
+    async fn act_tool_is_held_for_approval_when_supervised() {
+        let security = Arc::new(SecurityPolicy {
+            approval_hook: Some(Arc::new(DenyAll)),
+            ..SecurityPolicy::default()
+        });
+        let stub = StubTool::new("counting");
+        let calls = stub.calls();
+        let tool = PolicyGatedTool::new(Box::new(stub), Arc::clone(&security), ToolOperation::Act);
+
+        let pending = tool.execute(serde_json::json!({})).await.unwrap();
+        assert!(pending.output.contains("Awaiting operator approval"));
+        let result = approval_outcome(&security, &pending).await;
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("Operator denied"));
+        assert_eq!(calls.get(), 0);
+    }
+
+    #[derive(Debug)]
+    struct ApproveAll;
+
+    #[async_trait]
+    impl crate::security::ApprovalHook for ApproveAll {
+        async fn request_approval(
+            &self,
+            _request: &crate::approval::ApprovalRequest,
+        ) -> crate::approval::ApprovalResponse {
+            crate::approval::ApprovalResponse::Yes
+        }
+    }
+
+    /// Answers with the session of the call it runs in.
+    struct SessionEcho;
+
+    #[async_trait]
+    impl Tool for SessionEcho {
+        fn name(&self) -> &str {
+            "session_echo"
+        }
+
+        fn description(&self) -> &str {
+            "Test tool"
+        }
+
+        fn parameters_schema(&self) -> serde_json::Value {
+            serde_json::json!({ "type": "object" })
+        }
+
+        async fn execute(&self, _args: serde_json::Value) -> anyhow::Result<ToolResult> {
+            let session = ToolExecutionContext::current().and_then(|context| context.session_id);
+            Ok(ToolResult {
+                success: true,
+                output: format!("session {} done", session.unwrap_or_default()),
+                error: None,
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn held_call_runs_in_the_callers_context_and_is_audited_and_capped() {
+        let tmp = tempfile::tempdir().unwrap();
+        let logger = Arc::new(
+            crate::security::AuditLogger::new(
+                crate::config::AuditConfig::default(),
+                tmp.path().to_path_buf(),
+            )
+            .unwrap(),
+        );
+        let security = Arc::new(SecurityPolicy {
+            approval_hook: Some(Arc::new(ApproveAll)),
+            max_tool_output_bytes: 11,
+            ..SecurityPolicy::default()
+        });
+        let tool = ApprovalGatedTool::new(Box::new(SessionEcho), Arc::clone(&security))
+            .with_audit(Some(logger));
+        let context = ToolExecutionContext {
+            session_id: Some("s-1".into()),
+            ..ToolExecutionContext::default()
+        };
+
+        let pending = context
+            .scope(tool.execute(serde_json::json!({})))
+            .await
+            .unwrap();
+        let result = approval_outcome(&security, &pending).await;
+        assert!(result.output.starts_with("session s-1\n[truncated"));
+
+        let log = std::fs::read_to_string(tmp.path().join("ariadne/audit/audit.log")).unwrap();
+        assert!(log.contains("session_echo"));
+    }
+
+    #[tokio::test]
+    async fn approved_call_runs_later_and_is_charged_then() {
+        let security = Arc::new(SecurityPolicy {
+            approval_hook: Some(Arc::new(ApproveAll)),
+            ..SecurityPolicy::default()
+        });
+        let stub = StubTool::new("counting");
+        let calls = stub.calls();
+        let tool = PolicyGatedTool::new(Box::new(stub), Arc::clone(&security), ToolOperation::Act);
+
+        let pending = tool.execute(serde_json::json!({})).await.unwrap();
+        let result = approval_outcome(&security, &pending).await;
+        assert_eq!(result.output, "ok");
+        assert_eq!(calls.get(), 1);
+        assert_eq!(
+            security.remaining_actions(),
+            security.max_actions_per_hour - 1
+        );
+    }
+
+    #[tokio::test]
+    async fn approval_gate_asks_before_a_built_in_runs_and_charges_nothing_when_declined() {
+        let security = Arc::new(SecurityPolicy {
+            approval_hook: Some(Arc::new(DenyAll)),
+            ..SecurityPolicy::default()
+        });
+        let stub = StubTool::new("file_write");
+        let calls = stub.calls();
+        let tool = ApprovalGatedTool::new(Box::new(stub), Arc::clone(&security));
+
+        let pending = tool.execute(serde_json::json!({})).await.unwrap();
+        let result = approval_outcome(&security, &pending).await;
+        assert!(result.error.unwrap().contains("Operator denied"));
+        assert_eq!(calls.get(), 0);
+        assert_eq!(security.remaining_actions(), security.max_actions_per_hour);
+        assert!(security.pending_approvals.is_empty());
+
+        let reader = ApprovalGatedTool::new(
+            Box::new(StubTool::new("file_read").with_operation(ToolOperation::Read)),
+            security,
+        );
+        assert!(reader.execute(serde_json::json!({})).await.unwrap().success);
+    }
+
+    #[tokio::test]
+    async fn live_policy_gate_follows_policy_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("policy.toml");
+        std::fs::write(&path, "workspace_dir = \".\"\nlevel = \"full\"\n").unwrap();
+        // The file replaces `[autonomy]`: its read-only level never applies.
+        let autonomy = SecurityPolicy {
+            autonomy: AutonomyLevel::ReadOnly,
+            ..SecurityPolicy::default()
+        };
+        let live = Arc::new(LiveSecurityPolicy::load(&path, &autonomy).unwrap());
+        let stub = StubTool::new("counting");
+        let calls = stub.calls();
+        let built_with = live.current();
+        let gated = PolicyGatedTool::new(Box::new(stub), built_with, ToolOperation::Act);
+        let tool = LivePolicyGatedTool::new(Box::new(gated), Arc::clone(&live));
+        assert!(tool.execute(serde_json::json!({})).await.unwrap().success);
+        // Charged once, in the budget every version shares.
+        assert_eq!(
+            autonomy.remaining_actions(),
+            autonomy.max_actions_per_hour - 1
+        );
+
+        std::fs::write(
+            &path,
+            "workspace_dir = \".\"\nlevel = \"full\"\ndenied_tools = [\"counting\"]\n",
+        )
+        .unwrap();
+        let file = std::fs::File::options().write(true).open(&path).unwrap();
+        file.set_modified(std::time::SystemTime::now() + std::time::Duration::from_secs(5))
+            .unwrap();
+        assert!(live.reload_if_changed().unwrap());
+
+        let denied = tool.execute(serde_json::json!({})).await.unwrap();
+        assert!(!denied.success);
+        assert!(denied.error.unwrap().contains("disabled"));
+        assert_eq!(calls.get(), 1);
+    }
+
+    #[tokio::test]
+    async fn capability_gate_spends_token_budget() {
+        let parent = Arc::new(SecurityPolicy {
+            autonomy: AutonomyLevel::Full,
+            ..SecurityPolicy::default()
+        });
+        let token = CapabilityToken::mint(
+            &parent,
+            &["counting".to_string()],
+            std::time::Duration::from_secs(60),
+            Some(1),
+        )
+        .unwrap();
+        let stub = StubTool::new("counting");
+        let calls = stub.calls();
+        let tool = CapabilityGatedTool::new(Box::new(stub), Arc::new(token));
+        assert!(tool.execute(serde_json::json!({})).await.unwrap().success);
+
+        let denied = tool.execute(serde_json::json!({})).await.unwrap();
+        assert!(!denied.success);
+        assert!(denied.error.unwrap().contains("capability token budget"));
+        assert_eq!(calls.get(), 1);
+    }
+
+    #[tokio::test]
+    async fn read_tool_passes_through_and_keeps_spec() {
+        let policy = SecurityPolicy {
+            autonomy: AutonomyLevel::ReadOnly,
+            ..SecurityPolicy::default()
+        };
+        let (tool, calls) = gated(policy, ToolOperation::Read);
+        assert_eq!(tool.spec().name, "counting");
+        assert_eq!(tool.operation(), ToolOperation::Read);
+        assert!(tool.execute(serde_json::json!({})).await.unwrap().success);
+        assert_eq!(calls.get(), 1);
+    }
+
+    #[tokio::test]
+    async fn capability_gate_follows_the_inner_tool_operation() {
+        let parent = Arc::new(SecurityPolicy {
+            autonomy: AutonomyLevel::Full,
+            ..SecurityPolicy::default()
+        });
+        let token = CapabilityToken::mint(
+            &parent,
+            &["counting".to_string()],
+            std::time::Duration::from_secs(60),
+            Some(1),
+        )
+        .unwrap();
+        let stub = StubTool::new("counting").with_operation(ToolOperation::Read);
+        let calls = stub.calls();
+        let tool = CapabilityGatedTool::new(Box::new(stub), Arc::new(token));
+        assert_eq!(tool.operation(), ToolOperation::Read);
+        for _ in 0..3 {
+            assert!(tool.execute(serde_json::json!({})).await.unwrap().success);
+        }
+        assert_eq!(calls.get(), 3);
+    }
+}
@@ -1,5 +1,5 @@
 use super::traits::{Tool, ToolResult};
-use crate::security::{AutonomyLevel, SecurityPolicy};
+use crate::security::{AutonomyLevel, PolicyHandle, SecurityPolicy};
 use async_trait::async_trait;
 use serde_json::json;
 use std::sync::Arc;
@@ -7,14 +7,14 @@ use std::sync::Arc;
 /// Git operations tool for structured repository management.
 /// Provides safe, parsed git operations with JSON output.
 pub struct GitOperationsTool {
-    security: Arc<SecurityPolicy>,
+    security: PolicyHandle,
     workspace_dir: std::path::PathBuf,
 }
 
 impl GitOperationsTool {
     pub fn new(security: Arc<SecurityPolicy>, workspace_dir: std::path::PathBuf) -> Self {
         Self {
-            security,
+            security: security.into(),
             workspace_dir,
         }
     }
@@ -66,7 +66,9 @@ impl GitOperationsTool {
     }
 
     async fn run_git_command(&self, args: &[&str]) -> anyhow::Result<String> {
-        let output = tokio::process::Command::new("git")
+        let mut cmd = tokio::process::Command::new("git");
+        self.security.current().env_filter().apply(cmd.as_std_mut());
+        let output = cmd
             .args(args)
             .current_dir(&self.workspace_dir)
             .output()
@@ -481,6 +483,7 @@ impl Tool for GitOperationsTool {
     }
 
     async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let security = self.security.current();
         let operation = match args.get("operation").and_then(|v| v.as_str()) {
             Some(op) => op,
             None => {
@@ -516,7 +519,7 @@ impl Tool for GitOperationsTool {
 
         // Check autonomy level for write operations
         if self.requires_write_access(operation) {
-            if !self.security.can_act() {
+            if !security.can_act() {
                 return Ok(ToolResult {
                     success: false,
                     output: String::new(),
@@ -526,7 +529,7 @@ impl Tool for GitOperationsTool {
                 });
             }
 
-            match self.security.autonomy {
+            match security.effective_autonomy() {
                 AutonomyLevel::ReadOnly => {
                     return Ok(ToolResult {
                         success: false,
@@ -534,12 +537,12 @@ impl Tool for GitOperationsTool {
                         error: Some("Action blocked: read-only mode".into()),
                     });
                 }
-                AutonomyLevel::Supervised | AutonomyLevel::Full => {}
+                AutonomyLevel::Supervised | AutonomyLevel::Autonomous | AutonomyLevel::Full => {}
             }
         }
 
         // Record action for rate limiting
-        if !self.security.record_action() {
+        if !security.record_action() {
             return Ok(ToolResult {
                 success: false,
                 output: String::new(),
@@ -1,5 +1,6 @@
 use super::traits::{Tool, ToolResult};
-use crate::security::SecurityPolicy;
+use crate::security::policy::ToolOperation;
+use crate::security::{PolicyHandle, SecurityPolicy};
 use async_trait::async_trait;
 use serde_json::json;
 use std::sync::Arc;
@@ -8,12 +9,14 @@ const MAX_RESULTS: usize = 1000;
 
 /// Search for files by glob pattern within the workspace.
 pub struct GlobSearchTool {
-    security: Arc<SecurityPolicy>,
+    security: PolicyHandle,
 }
 
 impl GlobSearchTool {
     pub fn new(security: Arc<SecurityPolicy>) -> Self {
-        Self { security }
+        Self {
+            security: security.into(),
+        }
     }
 }
 
@@ -23,6 +26,10 @@ impl Tool for GlobSearchTool {
         "glob_search"
     }
 
+    fn operation(&self) -> ToolOperation {
+        ToolOperation::Read
+    }
+
     fn description(&self) -> &str {
         "Search for files matching a glob pattern within the workspace. \
          Returns a sorted list of matching file paths relative to the workspace root. \
@@ -43,13 +50,14 @@ impl Tool for GlobSearchTool {
     }
 
     async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let security = self.security.current();
         let pattern = args
             .get("pattern")
             .and_then(|v| v.as_str())
             .ok_or_else(|| anyhow::anyhow!("Missing 'pattern' parameter"))?;
 
         // Rate limit check (fast path)
-        if self.security.is_rate_limited() {
+        if security.is_rate_limited() {
             return Ok(ToolResult {
                 success: false,
                 output: String::new(),
@@ -76,7 +84,7 @@ impl Tool for GlobSearchTool {
         }
 
         // Record action to consume rate limit budget
-        if !self.security.record_action() {
+        if !security.record_action() {
             return Ok(ToolResult {
                 success: false,
                 output: String::new(),
@@ -85,7 +93,7 @@ impl Tool for GlobSearchTool {
         }
 
         // Build full pattern anchored to workspace
-        let workspace = &self.security.workspace_dir;
+        let workspace = &security.workspace_dir;
         let full_pattern = workspace.join(pattern).to_string_lossy().to_string();
 
         let entries = match glob::glob(&full_pattern) {
@@ -125,7 +133,7 @@ impl Tool for GlobSearchTool {
                 Err(_) => continue, // skip broken symlinks / unresolvable paths
             };
 
-            if !self.security.is_resolved_path_allowed(&resolved) {
+            if !security.is_resolved_path_allowed(&resolved) {
                 continue; // silently filter symlink escapes
             }
 
@@ -0,0 +1,444 @@
+use super::traits::{Tool, ToolResult};
+use crate::config::HomeAssistantConfig;
+use crate::security::{AutonomyLevel, PolicyHandle, SecurityPolicy};
+use async_trait::async_trait;
+use serde_json::json;
+use std::sync::Arc;
+
+const HOME_ASSISTANT_TOKEN_ENV: &str = "HOME_ASSISTANT_TOKEN";
+/// Cap on the serialized state list so a large installation does not flood
+/// the context window.
+const MAX_STATES_OUTPUT_CHARS: usize = 20_000;
+/// Service-call fields that would retarget the call away from the allowlisted
+/// `entity_id`.
+const TARGETING_FIELDS: &[&str] = &["entity_id", "device_id", "area_id", "floor_id", "label_id"];
+
+/// Home Assistant REST API tool.
+///
+/// Reading entity states is unrestricted once the tool is enabled. Service
+/// calls change the physical world, so they are limited to
+/// `[home_assistant] allowed_entities`, blocked in read-only autonomy, count
+/// against the action budget, and need `approved: true` in supervised mode.
+pub struct HomeAssistantTool {
+    security: PolicyHandle,
+    config: HomeAssistantConfig,
+}
+
+impl HomeAssistantTool {
+    pub fn new(security: Arc<SecurityPolicy>, config: HomeAssistantConfig) -> Self {
+        Self {
+            security: security.into(),
+            config,
+        }
+    }
+
+    fn base_url(&self) -> &str {
+        self.config.url.trim_end_matches('/')
+    }
+
+    fn token(&self) -> anyhow::Result<String> {
+        self.config
+            .token
+            .clone()
+            .filter(|t| !t.trim().is_empty())
+            .or_else(|| {
+                std::env::var(HOME_ASSISTANT_TOKEN_ENV)
+                    .ok()
+                    .filter(|t| !t.trim().is_empty())
+            })
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "Home Assistant token missing: set [home_assistant] token or {HOME_ASSISTANT_TOKEN_ENV}"
+                )
+            })
+    }
+
+    /// Whether `entity_id` matches the allowlist. Entries are exact entity IDs
+    /// (`light.kitchen`), domain wildcards (`light.*`), or `*` for everything.
+    fn is_entity_allowed(&self, entity_id: &str) -> bool {
+        let entity_id = entity_id.trim().to_ascii_lowercase();
+        self.config.allowed_entities.iter().any(|pattern| {
+            let pattern = pattern.trim().to_ascii_lowercase();
+            if pattern == "*" {
+                return true;
+            }
+            match pattern.strip_suffix(".*") {
+                Some(domain) => entity_id
+                    .split_once('.')
+                    .is_some_and(|(entity_domain, _)| entity_domain == domain),
+                None => entity_id == pattern,
+            }
+        })
+    }
+
+    fn is_valid_identifier(value: &str) -> bool {
+        !value.is_empty()
+            && value
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+    }
+
+    fn is_valid_entity_id(value: &str) -> bool {
+        value.split_once('.').is_some_and(|(domain, object)| {
+            Self::is_valid_identifier(domain) && Self::is_valid_identifier(object)
+        })
+    }
+
+    fn client(&self) -> reqwest::Client {
+        crate::config::build_runtime_proxy_client_with_timeouts(
+            "tool.home_assistant",
+            self.config.timeout_secs,
+            10,
+        )
+    }
+
+    async fn get(&self, path: &str) -> anyhow::Result<serde_json::Value> {
+        let response = self
+            .client()
+            .get(format!("{}{path}", self.base_url()))
+            .bearer_auth(self.token()?)
+            .send()
+            .await?;
+        Self::read_json(response).await
+    }
+
+    async fn read_json(response: reqwest::Response) -> anyhow::Result<serde_json::Value> {
+        let status = response.status();
+        let body = response.text().await?;
+        if !status.is_success() {
+            anyhow::bail!(
+                "Home Assistant returned {status}: {}",
+                crate::util::truncate_with_ellipsis(&body, 300)
+            );
+        }
+        Ok(serde_json::from_str(&body)?)
+    }
+
+    fn summarize_state(state: &serde_json::Value) -> serde_json::Value {
+        json!({
+            "entity_id": state.get("entity_id"),
+            "state": state.get("state"),
+            "friendly_name": state.pointer("/attributes/friendly_name"),
+            "last_changed": state.get("last_changed"),
+        })
+    }
+
+    async fn get_states(&self, domain: Option<&str>) -> anyhow::Result<ToolResult> {
+        let states = self.get("/api/states").await?;
+        let prefix = domain.map(|d| format!("{}.", d.trim().to_ascii_lowercase()));
+        let summaries: Vec<serde_json::Value> = states
+            .as_array()
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+            .iter()
+            .filter(|state| match (&prefix, state["entity_id"].as_str()) {
+                (Some(prefix), Some(id)) => id.starts_with(prefix.as_str()),
+                (None, _) => true,
+                (Some(_), None) => false,
+            })
+            .map(Self::summarize_state)
+            .collect();
+        let output = serde_json::to_string_pretty(&summaries)?;
+        Ok(ToolResult {
+            success: true,
+            output: crate::util::truncate_with_ellipsis(&output, MAX_STATES_OUTPUT_CHARS),
+            error: None,
+        })
+    }
+
+    async fn get_state(&self, entity_id: &str) -> anyhow::Result<ToolResult> {
+        if !Self::is_valid_entity_id(entity_id) {
+            return Ok(Self::failure(format!("Invalid entity_id '{entity_id}'")));
+        }
+        let state = self.get(&format!("/api/states/{entity_id}")).await?;
+        Ok(ToolResult {
+            success: true,
+            output: serde_json::to_string_pretty(&state)?,
+            error: None,
+        })
+    }
+
+    /// Check every gate a service call must pass, returning the reason it is
+    /// refused. Does not consume the action budget.
+    fn check_service_call(
+        &self,
+        domain: &str,
+        service: &str,
+        entity_id: &str,
+        data: &serde_json::Map<String, serde_json::Value>,
+        approved: bool,
+    ) -> Result<(), String> {
+        let security = self.security.current();
+        if !security.can_act() {
+            return Err(
+                "Security policy: read-only mode, cannot call Home Assistant services".into(),
+            );
+        }
+        if !Self::is_valid_identifier(domain) || !Self::is_valid_identifier(service) {
+            return Err(format!("Invalid service '{domain}.{service}'"));
+        }
+        if !Self::is_valid_entity_id(entity_id) {
+            return Err(format!("Invalid entity_id '{entity_id}'"));
+        }
+        if let Some(field) = TARGETING_FIELDS.iter().find(|f| data.contains_key(**f)) {
+            return Err(format!(
+                "'data' must not contain '{field}'; target entities with the 'entity_id' parameter"
+            ));
+        }
+        if !self.is_entity_allowed(entity_id) {
+            return Err(format!(
+                "Entity '{entity_id}' is not in [home_assistant] allowed_entities"
+            ));
+        }
+        if security.effective_autonomy() == AutonomyLevel::Supervised && !approved {
+            return Err(format!(
+                "Calling {domain}.{service} on {entity_id} requires explicit approval \
+                 (approved: true) in supervised mode"
+            ));
+        }
+        if security.is_rate_limited() {
+            return Err("Rate limit exceeded: too many actions in the last hour".into());
+        }
+        Ok(())
+    }
+
+    async fn call_service(&self, args: &serde_json::Value) -> anyhow::Result<ToolResult> {
+        let domain = args["domain"].as_str().unwrap_or_default().trim();
+        let service = args["service"].as_str().unwrap_or_default().trim();
+        let entity_id = args["entity_id"].as_str().unwrap_or_default().trim();
+        let approved = args["approved"].as_bool().unwrap_or(false);
+        let mut data = match args.get("data") {
+            None | Some(serde_json::Value::Null) => serde_json::Map::new(),
+            Some(serde_json::Value::Object(map)) => map.clone(),
+            Some(_) => return Ok(Self::failure("'data' must be an object")),
+        };
+
+        if let Err(reason) = self.check_service_call(domain, service, entity_id, &data, approved) {
+            return Ok(Self::failure(reason));
+        }
+        if !self.security.current().record_action() {
+            return Ok(Self::failure(
+                "Rate limit exceeded: action budget exhausted",
+            ));
+        }
+
+        data.insert("entity_id".into(), json!(entity_id));
+        let response = self
+            .client()
+            .post(format!(
+                "{}/api/services/{domain}/{service}",
+                self.base_url()
+            ))
+            .bearer_auth(self.token()?)
+            .json(&data)
+            .send()
+            .await?;
+        let changed = Self::read_json(response).await?;
+        let changed: Vec<serde_json::Value> = changed
+            .as_array()
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+            .iter()
+            .map(Self::summarize_state)
+            .collect();
+        Ok(ToolResult {
+            success: true,
+            output: serde_json::to_string_pretty(&json!({
+                "service": format!("{domain}.{service}"),
+                "entity_id": entity_id,
+                "changed_states": changed,
+            }))?,
+            error: None,
+        })
+    }
+
+    fn failure(error: impl Into<String>) -> ToolResult {
+        ToolResult {
+            success: false,
+            output: String::new(),
+            error: Some(error.into()),
+        }
+    }
+}
+
+#[async_trait]
+impl Tool for HomeAssistantTool {
+    fn name(&self) -> &str {
+        "home_assistant"
+    }
+
+    fn description(&self) -> &str {
+        "Read entity states from the configured Home Assistant instance and call services \
+         (turn on lights, set thermostats, ...) on allowlisted entities. Service calls \
+         change the physical world; in supervised mode they require approved=true after \
+         the user has confirmed."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "action": {
+                    "type": "string",
+                    "enum": ["get_states", "get_state", "call_service"],
+                    "description": "get_states lists entities, get_state reads one entity, call_service acts on one entity"
+                },
+                "domain": {
+                    "type": "string",
+                    "description": "get_states: optional entity domain filter (e.g. 'light'). call_service: service domain (e.g. 'light', 'climate')"
+                },
+                "service": {
+                    "type": "string",
+                    "description": "call_service: service name (e.g. 'turn_on', 'set_temperature')"
+                },
+                "entity_id": {
+                    "type": "string",
+                    "description": "Entity to read or target, e.g. 'light.kitchen'"
+                },
+                "data": {
+                    "type": "object",
+                    "description": "call_service: extra service data (e.g. {\"brightness_pct\": 40}); must not contain targeting fields"
+                },
+                "approved": {
+                    "type": "boolean",
+                    "description": "Set true only after the user confirmed this exact service call (required in supervised mode)",
+                    "default": false
+                }
+            },
+            "required": ["action"]
+        })
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        if let Err(e) = self.security.current().egress().check_url(self.base_url()) {
+            return Ok(Self::failure(e));
+        }
+        let action = args["action"].as_str().unwrap_or_default();
+        let result = match action {
+            "get_states" => self.get_states(args["domain"].as_str()).await,
+            "get_state" => match args["entity_id"].as_str() {
+                Some(entity_id) => self.get_state(entity_id.trim()).await,
+                None => return Ok(Self::failure("Missing 'entity_id' parameter")),
+            },
+            "call_service" => self.call_service(&args).await,
+            other => {
+                return Ok(Self::failure(format!(
+                    "Unknown action '{other}'. Use get_states, get_state, or call_service."
+                )))
+            }
+        };
+        Ok(result.unwrap_or_else(|e| Self::failure(format!("Home Assistant request failed: {e}"))))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ha_tool(autonomy: AutonomyLevel, allowed_entities: &[&str]) -> HomeAssistantTool {
+        let security = SecurityPolicy {
+            autonomy,
+            ..SecurityPolicy::default()
+        };
+        let config = HomeAssistantConfig {
+            enabled: true,
+            allowed_entities: allowed_entities.iter().map(|s| s.to_string()).collect(),
+            ..HomeAssistantConfig::default()
+        };
+        HomeAssistantTool::new(Arc::new(security), config)
+    }
+
+    #[test]
+    fn allowlist_supports_exact_and_domain_wildcards() {
+        let tool = ha_tool(AutonomyLevel::Full, &["light.*", "switch.garage_door"]);
+        assert!(tool.is_entity_allowed("light.kitchen"));
+        assert!(tool.is_entity_allowed("switch.garage_door"));
+        assert!(!tool.is_entity_allowed("switch.boiler"));
+        assert!(!tool.is_entity_allowed("lightbulb.kitchen"));
+
+        let empty = ha_tool(AutonomyLevel::Full, &[]);
+        assert!(!empty.is_entity_allowed("light.kitchen"));
+    }
+
+    #[test]
+    fn service_call_outside_allowlist_is_refused() {
+        let tool = ha_tool(AutonomyLevel::Full, &["light.*"]);
+        let err = tool
+            .check_service_call(
+                "lock",
+                "unlock",
+                "lock.front_door",
+                &Default::default(),
+                true,
+            )
+            .unwrap_err();
+        assert!(err.contains("allowed_entities"));
+        assert!(tool
+            .check_service_call(
+                "light",
+                "turn_on",
+                "light.kitchen",
+                &Default::default(),
+                false
+            )
+            .is_ok());
+    }
+
+    #[test]
+    fn service_call_requires_approval_in_supervised_mode() {
+        let tool = ha_tool(AutonomyLevel::Supervised, &["light.kitchen"]);
+        let err = tool
+            .check_service_call(
+                "light",
+                "turn_on",
+                "light.kitchen",
+                &Default::default(),
+                false,
+            )
+            .unwrap_err();
+        assert!(err.contains("approved: true"));
+        assert!(tool
+            .check_service_call(
+                "light",
+                "turn_on",
+                "light.kitchen",
+                &Default::default(),
+                true
+            )
+            .is_ok());
+    }
+
+    #[test]
+    fn service_call_blocked_in_read_only_mode() {
+        let tool = ha_tool(AutonomyLevel::ReadOnly, &["*"]);
+        let err = tool
+            .check_service_call(
+                "light",
+                "turn_on",
+                "light.kitchen",
+                &Default::default(),
+                true,
+            )
+            .unwrap_err();
+        assert!(err.contains("read-only"));
+    }
+
+    #[test]
+    fn service_data_cannot_retarget_the_call() {
+        let tool = ha_tool(AutonomyLevel::Full, &["light.kitchen"]);
+        let mut data = serde_json::Map::new();
+        data.insert("area_id".into(), json!("whole_house"));
+        let err = tool
+            .check_service_call("light", "turn_on", "light.kitchen", &data, true)
+            .unwrap_err();
+        assert!(err.contains("area_id"));
+    }
+
+    #[tokio::test]
+    async fn unknown_action_is_reported() {
+        let tool = ha_tool(AutonomyLevel::Full, &[]);
+        let result = tool.execute(json!({ "action": "reboot" })).await.unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("Unknown action"));
+    }
+}
@@ -3,7 +3,7 @@ use super::url_validation::{
     normalize_allowed_domains, validate_url, DomainPolicy, UrlSchemePolicy,
 };
 use crate::config::{HttpRequestCredentialProfile, UrlAccessConfig};
-use crate::security::SecurityPolicy;
+use crate::security::{PolicyHandle, SecurityPolicy};
 use async_trait::async_trait;
 use serde_json::json;
 use std::collections::HashMap;
@@ -13,7 +13,7 @@ use std::time::Duration;
 /// HTTP request tool for API interactions.
 /// Supports GET, POST, PUT, DELETE methods with configurable security.
 pub struct HttpRequestTool {
-    security: Arc<SecurityPolicy>,
+    security: PolicyHandle,
     allowed_domains: Vec<String>,
     url_access: UrlAccessConfig,
     max_response_size: usize,
@@ -105,7 +105,7 @@ impl HttpRequestTool {
         }
 
         Self {
-            security,
+            security: security.into(),
             allowed_domains: normalize_allowed_domains(allowed_domains),
             url_access,
             max_response_size,
@@ -128,6 +128,7 @@ impl HttpRequestTool {
                 scheme_policy: UrlSchemePolicy::HttpOrHttps,
                 ipv6_error_context: "http_request",
                 url_access: Some(&self.url_access),
+                egress: Some(self.security.current().egress()),
             },
         )
     }
@@ -346,6 +347,7 @@ impl Tool for HttpRequestTool {
     }
 
     async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let security = self.security.current();
         let url = args
             .get("url")
             .and_then(|v| v.as_str())
@@ -368,7 +370,7 @@ impl Tool for HttpRequestTool {
         };
         let body = args.get("body").and_then(|v| v.as_str());
 
-        if !self.security.can_act() {
+        if !security.can_act() {
             return Ok(ToolResult {
                 success: false,
                 output: String::new(),
@@ -376,7 +378,7 @@ impl Tool for HttpRequestTool {
             });
         }
 
-        if !self.security.record_action() {
+        if !security.record_action() {
             return Ok(ToolResult {
                 success: false,
                 output: String::new(),
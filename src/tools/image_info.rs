@@ -1,9 +1,9 @@
 use super::traits::{Tool, ToolResult};
-use crate::security::SecurityPolicy;
+use crate::security::policy::ToolOperation;
+use crate::security::{PolicyHandle, SecurityPolicy};
 use async_trait::async_trait;
 use serde_json::json;
 use std::fmt::Write;
-use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
 /// Maximum file size we will read and base64-encode (5 MB).
@@ -15,12 +15,14 @@ const MAX_IMAGE_BYTES: u64 = 5_242_880;
 /// (file size, format, dimensions from header bytes) and provides base64
 /// data for future multimodal provider support.
 pub struct ImageInfoTool {
-    security: Arc<SecurityPolicy>,
+    security: PolicyHandle,
 }
 
 impl ImageInfoTool {
     pub fn new(security: Arc<SecurityPolicy>) -> Self {
-        Self { security }
+        Self {
+            security: security.into(),
+        }
     }
 
     /// Detect image format from first few bytes (magic numbers).
@@ -116,32 +118,6 @@ impl ImageInfoTool {
         }
         None
     }
-
-    fn resolve_image_path(&self, path_str: &str) -> Result<PathBuf, String> {
-        // Syntax-level checks first.
-        if !self.security.is_path_allowed(path_str) {
-            return Err(format!(
-                "Path not allowed: {path_str} (must be within workspace)"
-            ));
-        }
-
-        let raw_path = Path::new(path_str);
-        let candidate = if raw_path.is_absolute() {
-            raw_path.to_path_buf()
-        } else {
-            self.security.workspace_dir.join(raw_path)
-        };
-
-        let resolved = candidate
-            .canonicalize()
-            .map_err(|_| format!("File not found: {path_str}"))?;
-
-        if !self.security.is_resolved_path_allowed(&resolved) {
-            return Err(self.security.resolved_path_violation_message(&resolved));
-        }
-
-        Ok(resolved)
-    }
 }
 
 #[async_trait]
@@ -150,6 +126,10 @@ impl Tool for ImageInfoTool {
         "image_info"
     }
 
+    fn operation(&self) -> ToolOperation {
+        ToolOperation::Read
+    }
+
     fn description(&self) -> &str {
         "Read image file metadata (format, dimensions, size) and optionally return base64-encoded data."
     }
@@ -182,7 +162,7 @@ impl Tool for ImageInfoTool {
             .and_then(serde_json::Value::as_bool)
             .unwrap_or(false);
 
-        let resolved_path = match self.resolve_image_path(path_str) {
+        let resolved_path = match self.security.current().resolve_in_workspace(path_str).await {
             Ok(path) => path,
             Err(error) => {
                 return Ok(ToolResult {
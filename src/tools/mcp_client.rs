@@ -7,11 +7,13 @@ use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 
 use anyhow::{anyhow, bail, Context, Result};
+use futures_util::future::join_all;
 use serde_json::json;
 use tokio::sync::Mutex;
 use tokio::time::{timeout, Duration};
 
 use crate::config::schema::McpServerConfig;
+use crate::security::SecurityPolicy;
 use crate::tools::mcp_protocol::{
     JsonRpcRequest, McpToolDef, McpToolsListResult, MCP_PROTOCOL_VERSION,
 };
@@ -46,9 +48,11 @@ pub struct McpServer {
 
 impl McpServer {
     /// Connect to the server, perform the initialize handshake, and fetch the tool list.
-    pub async fn connect(config: McpServerConfig) -> Result<Self> {
+    ///
+    /// HTTP and SSE servers are refused unless `security` allows their host.
+    pub async fn connect(config: McpServerConfig, security: &SecurityPolicy) -> Result<Self> {
         // Create transport based on config
-        let mut transport = create_transport(&config).with_context(|| {
+        let mut transport = create_transport(&config, security).with_context(|| {
             format!(
                 "failed to create transport for MCP server `{}`",
                 config.name
@@ -207,12 +211,25 @@ pub struct McpRegistry {
 
 impl McpRegistry {
     /// Connect to all configured servers. Non-fatal: failures are logged and skipped.
-    pub async fn connect_all(configs: &[McpServerConfig]) -> Result<Self> {
+    ///
+    /// Handshakes run concurrently so one slow server does not serialize
+    /// startup behind it; registration order still follows `configs`.
+    pub async fn connect_all(
+        configs: &[McpServerConfig],
+        security: &SecurityPolicy,
+    ) -> Result<Self> {
         let mut servers = Vec::new();
         let mut tool_index = HashMap::new();
 
-        for config in configs {
-            match McpServer::connect(config.clone()).await {
+        let connections = join_all(
+            configs
+                .iter()
+                .map(|config| McpServer::connect(config.clone(), security)),
+        )
+        .await;
+
+        for (config, connection) in configs.iter().zip(connections) {
+            match connection {
                 Ok(server) => {
                     let server_idx = servers.len();
                     // Collect tools while holding the lock once, then release
@@ -307,7 +324,7 @@ mod tests {
             url: None,
             headers: std::collections::HashMap::default(),
         };
-        let result = McpServer::connect(config).await;
+        let result = McpServer::connect(config, &SecurityPolicy::default()).await;
         assert!(result.is_err());
         let msg = result.err().unwrap().to_string();
         assert!(msg.contains("failed to create transport"), "got: {msg}");
@@ -326,13 +343,32 @@ mod tests {
             url: None,
             headers: std::collections::HashMap::default(),
         }];
-        let registry = McpRegistry::connect_all(&configs)
+        let registry = McpRegistry::connect_all(&configs, &SecurityPolicy::default())
             .await
             .expect("connect_all should not fail");
         assert!(registry.is_empty());
         assert_eq!(registry.tool_count(), 0);
     }
 
+    #[tokio::test]
+    async fn connect_all_skips_every_failed_server_when_connecting_concurrently() {
+        let bad = |name: &str| McpServerConfig {
+            name: name.to_string(),
+            command: "/usr/bin/does_not_exist_zc_test".to_string(),
+            args: vec![],
+            env: std::collections::HashMap::default(),
+            tool_timeout_secs: None,
+            transport: McpTransport::Stdio,
+            url: None,
+            headers: std::collections::HashMap::default(),
+        };
+        let configs = vec![bad("first"), bad("second"), bad("third")];
+        let registry = McpRegistry::connect_all(&configs, &SecurityPolicy::default())
+            .await
+            .expect("connect_all should not fail");
+        assert_eq!(registry.server_count(), 0);
+    }
+
     #[test]
     fn http_transport_requires_url() {
         let config = McpServerConfig {
@@ -340,7 +376,7 @@ mod tests {
             transport: McpTransport::Http,
             ..Default::default()
         };
-        let result = create_transport(&config);
+        let result = create_transport(&config, &SecurityPolicy::default());
         assert!(result.is_err());
     }
 
@@ -351,7 +387,7 @@ mod tests {
             transport: McpTransport::Sse,
             ..Default::default()
         };
-        let result = create_transport(&config);
+        let result = create_transport(&config, &SecurityPolicy::default());
         assert!(result.is_err());
     }
 }
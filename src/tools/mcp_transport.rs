@@ -10,6 +10,7 @@ use tokio::time::{timeout, Duration};
 use tokio_stream::StreamExt;
 
 use crate::config::schema::{McpServerConfig, McpTransport};
+use crate::security::{EnvFilter, SecurityPolicy};
 use crate::tools::mcp_protocol::{JsonRpcError, JsonRpcRequest, JsonRpcResponse, INTERNAL_ERROR};
 
 /// Maximum bytes for a single JSON-RPC response.
@@ -49,7 +50,11 @@ pub struct StdioTransport {
 
 impl StdioTransport {
     pub fn new(config: &McpServerConfig) -> Result<Self> {
-        let mut child = Command::new(&config.command)
+        let mut cmd = Command::new(&config.command);
+        // The server gets the safe baseline plus the variables configured
+        // for it, never the agent's own credentials.
+        EnvFilter::baseline().apply(cmd.as_std_mut());
+        let mut child = cmd
             .args(&config.args)
             .envs(&config.env)
             .stdin(std::process::Stdio::piped())
@@ -576,11 +581,14 @@ fn parse_endpoint_from_data(sse_url: &str, data: &str) -> Option<String> {
         let endpoint = v.get("endpoint")?.as_str()?;
         return parse_endpoint_from_data(sse_url, endpoint);
     }
-    if data.starts_with("http://") || data.starts_with("https://") {
-        return Some(data.to_string());
-    }
     let base = reqwest::Url::parse(sse_url).ok()?;
-    base.join(data).ok().map(|u| u.to_string())
+    let url = base.join(data).ok()?;
+    // Only the SSE URL's host was checked against the egress rules, so an
+    // endpoint pointing anywhere else is ignored.
+    if url.host_str() != base.host_str() {
+        return None;
+    }
+    Some(url.to_string())
 }
 
 fn extract_json_from_sse_text(resp_text: &str) -> Cow<'_, str> {
@@ -909,7 +917,18 @@ impl McpTransportConn for SseTransport {
 // ── Factory ──────────────────────────────────────────────────────────────
 
 /// Create a transport based on config.
-pub fn create_transport(config: &McpServerConfig) -> Result<Box<dyn McpTransportConn>> {
+///
+/// HTTP and SSE server URLs are checked against `security`'s egress rules
+/// before anything connects.
+pub fn create_transport(
+    config: &McpServerConfig,
+    security: &SecurityPolicy,
+) -> Result<Box<dyn McpTransportConn>> {
+    if config.transport != McpTransport::Stdio {
+        if let Some(url) = &config.url {
+            security.egress().check_url(url).map_err(|e| anyhow!(e))?;
+        }
+    }
     match config.transport {
         McpTransport::Stdio => Ok(Box::new(StdioTransport::new(config)?)),
         McpTransport::Http => Ok(Box::new(HttpTransport::new(config)?)),
@@ -929,6 +948,35 @@ mod tests {
         assert_eq!(config.transport, McpTransport::Stdio);
     }
 
+    #[tokio::test]
+    async fn stdio_server_gets_configured_env_but_not_secrets() {
+        let dir = tempfile::tempdir().unwrap();
+        let out = dir.path().join("env.txt");
+        let key = "ZEROCLAW_MCP_TEST_API_KEY";
+        std::env::set_var(key, "sk-mcp-secret");
+        let config = McpServerConfig {
+            name: "env".into(),
+            command: "sh".into(),
+            args: vec!["-c".into(), format!("env > {}", out.display())],
+            env: [("MCP_MODE".to_string(), "test".to_string())].into(),
+            ..Default::default()
+        };
+        let transport = StdioTransport::new(&config);
+        std::env::remove_var(key);
+        let _transport = transport.unwrap();
+
+        let mut env = String::new();
+        for _ in 0..100 {
+            env = std::fs::read_to_string(&out).unwrap_or_default();
+            if env.contains("MCP_MODE=test") {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+        assert!(env.contains("MCP_MODE=test"));
+        assert!(!env.contains("sk-mcp-secret"));
+    }
+
     #[test]
     fn test_http_transport_requires_url() {
         let config = McpServerConfig {
@@ -949,6 +997,45 @@ mod tests {
         assert!(SseTransport::new(&config).is_err());
     }
 
+    #[test]
+    fn http_and_sse_transports_refuse_denied_hosts() {
+        let security = SecurityPolicy {
+            denied_hosts: vec!["mcp.blocked.example".into()],
+            ..SecurityPolicy::default()
+        };
+        for transport in [McpTransport::Http, McpTransport::Sse] {
+            let config = McpServerConfig {
+                name: "test".into(),
+                transport,
+                url: Some("https://mcp.blocked.example/mcp".into()),
+                ..Default::default()
+            };
+            let error = create_transport(&config, &security).err().unwrap();
+            assert!(error.to_string().contains("Security policy"), "{error}");
+        }
+
+        let allowed = McpServerConfig {
+            name: "test".into(),
+            transport: McpTransport::Http,
+            url: Some("https://mcp.allowed.example/mcp".into()),
+            ..Default::default()
+        };
+        assert!(create_transport(&allowed, &security).is_ok());
+    }
+
+    #[test]
+    fn sse_endpoint_on_another_host_is_ignored() {
+        let sse_url = "https://mcp.example.com/sse";
+        assert_eq!(
+            parse_endpoint_from_data(sse_url, "/messages?session=1").as_deref(),
+            Some("https://mcp.example.com/messages?session=1")
+        );
+        assert_eq!(
+            parse_endpoint_from_data(sse_url, "https://elsewhere.example/messages"),
+            None
+        );
+    }
+
     #[test]
     fn test_extract_json_from_sse_data_no_space() {
         let input = "data:{\"jsonrpc\":\"2.0\",\"result\":{}}\n\n";
@@ -1,7 +1,8 @@
+use super::dry_run;
 use super::traits::{Tool, ToolResult};
 use crate::memory::Memory;
 use crate::security::policy::ToolOperation;
-use crate::security::SecurityPolicy;
+use crate::security::{PolicyHandle, SecurityPolicy};
 use async_trait::async_trait;
 use serde_json::json;
 use std::sync::Arc;
@@ -9,12 +10,15 @@ use std::sync::Arc;
 /// Let the agent forget/delete a memory entry
 pub struct MemoryForgetTool {
     memory: Arc<dyn Memory>,
-    security: Arc<SecurityPolicy>,
+    security: PolicyHandle,
 }
 
 impl MemoryForgetTool {
     pub fn new(memory: Arc<dyn Memory>, security: Arc<SecurityPolicy>) -> Self {
-        Self { memory, security }
+        Self {
+            memory,
+            security: security.into(),
+        }
     }
 }
 
@@ -42,22 +46,24 @@ impl Tool for MemoryForgetTool {
     }
 
     async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let security = self.security.current();
         let key = args
             .get("key")
             .and_then(|v| v.as_str())
             .ok_or_else(|| anyhow::anyhow!("Missing 'key' parameter"))?;
 
-        if let Err(error) = self
-            .security
-            .enforce_tool_operation(ToolOperation::Act, "memory_forget")
-        {
+        if let Err(error) = security.enforce_tool_operation(ToolOperation::Act, "memory_forget") {
             return Ok(ToolResult {
                 success: false,
                 output: String::new(),
-                error: Some(error),
+                error: Some(error.to_string()),
             });
         }
 
+        if security.dry_run {
+            return Ok(dry_run::simulated(format!("Would forget memory: {key}")));
+        }
+
         match self.memory.forget(key).await {
             Ok(true) => Ok(ToolResult {
                 success: true,
@@ -1,5 +1,6 @@
 use super::traits::{Tool, ToolResult};
-use crate::memory::Memory;
+use crate::memory::{self, Memory};
+use crate::security::policy::ToolOperation;
 use async_trait::async_trait;
 use serde_json::json;
 use std::fmt::Write;
@@ -22,8 +23,12 @@ impl Tool for MemoryRecallTool {
         "memory_recall"
     }
 
+    fn operation(&self) -> ToolOperation {
+        ToolOperation::Read
+    }
+
     fn description(&self) -> &str {
-        "Search long-term memory for relevant facts, preferences, or context. Returns scored results ranked by relevance."
+        "Search long-term memory for relevant facts, preferences, or context. Returns scored results ranked by relevance. Set follow_links to also return the entries those results relate to."
     }
 
     fn parameters_schema(&self) -> serde_json::Value {
@@ -37,6 +42,10 @@ impl Tool for MemoryRecallTool {
                 "limit": {
                     "type": "integer",
                     "description": "Max results to return (default: 5)"
+                },
+                "follow_links": {
+                    "type": "boolean",
+                    "description": "Also return entries the results link to via relates_to (default: false)"
                 }
             },
             "required": ["query"]
@@ -54,6 +63,10 @@ impl Tool for MemoryRecallTool {
             .get("limit")
             .and_then(serde_json::Value::as_u64)
             .map_or(5, |v| v as usize);
+        let follow_links = args
+            .get("follow_links")
+            .and_then(serde_json::Value::as_bool)
+            .unwrap_or(false);
 
         match self.memory.recall(query, limit, None).await {
             Ok(entries) if entries.is_empty() => Ok(ToolResult {
@@ -73,6 +86,29 @@ impl Tool for MemoryRecallTool {
                         entry.category, entry.key, entry.content
                     );
                 }
+                if follow_links {
+                    let linked = match memory::linked_entries(self.memory.as_ref(), &entries).await
+                    {
+                        Ok(linked) => linked,
+                        Err(e) => {
+                            return Ok(ToolResult {
+                                success: false,
+                                output: String::new(),
+                                error: Some(format!("Memory recall failed: {e}")),
+                            })
+                        }
+                    };
+                    if !linked.is_empty() {
+                        let _ = writeln!(output, "Linked entries:");
+                        for entry in &linked {
+                            let _ = writeln!(
+                                output,
+                                "- [{}] {}: {}",
+                                entry.category, entry.key, entry.content
+                            );
+                        }
+                    }
+                }
                 Ok(ToolResult {
                     success: true,
                     output,
@@ -126,6 +162,39 @@ mod tests {
         assert!(result.output.contains("Found 1"));
     }
 
+    #[tokio::test]
+    async fn recall_follows_links() {
+        let (_tmp, mem) = seeded_mem();
+        mem.store(
+            "deploy_policy",
+            "Deploy on Tuesdays",
+            MemoryCategory::Core,
+            None,
+        )
+        .await
+        .unwrap();
+        mem.store(
+            "incident",
+            "Friday rollout broke prod\n\nRelates-To: deploy_policy",
+            MemoryCategory::Core,
+            None,
+        )
+        .await
+        .unwrap();
+
+        let tool = MemoryRecallTool::new(mem);
+        let result = tool
+            .execute(json!({"query": "rollout", "follow_links": true}))
+            .await
+            .unwrap();
+        assert!(result.success);
+        assert!(result.output.contains("Linked entries:"));
+        assert!(result.output.contains("deploy_policy: Deploy on Tuesdays"));
+
+        let result = tool.execute(json!({"query": "rollout"})).await.unwrap();
+        assert!(!result.output.contains("Linked entries:"));
+    }
+
     #[tokio::test]
     async fn recall_respects_limit() {
         let (_tmp, mem) = seeded_mem();
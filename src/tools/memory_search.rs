@@ -0,0 +1,543 @@
+use super::traits::{Tool, ToolResult};
+use crate::config::MemorySearchRankingConfig;
+use crate::memory::import::inline_tags;
+use crate::memory::tag_suggest::suggested_tags;
+use crate::memory::{self, Memory, MemoryCategory, MemoryEntry};
+use crate::security::policy::ToolOperation;
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use serde_json::json;
+use std::sync::Arc;
+
+const DEFAULT_LIMIT: usize = 20;
+
+/// Let the agent filter its memory by keyword, tag, category, and date.
+///
+/// Unlike `memory_recall`, which ranks entries by relevance to a query, this
+/// is an exact filter over stored entries and returns structured results.
+/// Tags are the `#tag` tokens in an entry's content, including the
+/// `Tags: #a #b` line written by `memory_store` and note import. Tag
+/// filters also match tags on the `Suggested-Tags:` line, which results
+/// report separately. Hits are ranked by recency and configured tag
+/// weights (`[memory.search_ranking]`).
+pub struct MemorySearchTool {
+    memory: Arc<dyn Memory>,
+    ranking: MemorySearchRankingConfig,
+}
+
+impl MemorySearchTool {
+    pub fn new(memory: Arc<dyn Memory>) -> Self {
+        Self {
+            memory,
+            ranking: MemorySearchRankingConfig::default(),
+        }
+    }
+
+    /// Rank hits with `ranking` instead of the defaults.
+    pub fn with_ranking(mut self, ranking: MemorySearchRankingConfig) -> Self {
+        self.ranking = ranking;
+        self
+    }
+
+    /// `2^(-age / half_life) * (1 + tag weights)`, floored at zero. Entries
+    /// without a readable timestamp get no recency credit.
+    fn rank_score(&self, entry: &MemoryEntry, tags: &[String], now: DateTime<Utc>) -> f64 {
+        let recency = memory::entry_time(&entry.timestamp).map_or(0.0, |time| {
+            #[allow(clippy::cast_precision_loss)]
+            let age_days = (now - time).num_seconds().max(0) as f64 / 86_400.0;
+            let half_life = self.ranking.recency_half_life_days;
+            if half_life > 0.0 {
+                (-age_days / half_life * std::f64::consts::LN_2).exp()
+            } else {
+                1.0
+            }
+        });
+        let suggested = suggested_tags(&entry.content);
+        let boost: f64 = self
+            .ranking
+            .tag_weights
+            .iter()
+            .filter(|(tag, _)| {
+                tags.iter()
+                    .chain(suggested.iter())
+                    .any(|t| t.eq_ignore_ascii_case(tag.trim_start_matches('#')))
+            })
+            .map(|(_, weight)| weight)
+            .sum();
+        recency * (1.0 + boost).max(0.0)
+    }
+}
+
+/// Parsed search filters.
+#[derive(Debug, Default)]
+struct SearchFilter {
+    keywords: Vec<String>,
+    tags: Vec<String>,
+    since: Option<DateTime<Utc>>,
+    until: Option<DateTime<Utc>>,
+}
+
+impl SearchFilter {
+    fn from_args(args: &serde_json::Value) -> Result<Self, String> {
+        let keywords = args
+            .get("keywords")
+            .and_then(serde_json::Value::as_str)
+            .unwrap_or_default()
+            .split_whitespace()
+            .map(str::to_lowercase)
+            .collect();
+        let tags = match args.get("tags") {
+            None | Some(serde_json::Value::Null) => Vec::new(),
+            Some(serde_json::Value::Array(items)) => items
+                .iter()
+                .filter_map(serde_json::Value::as_str)
+                .map(|t| t.trim().trim_start_matches('#').to_lowercase())
+                .filter(|t| !t.is_empty())
+                .collect(),
+            Some(_) => return Err("'tags' must be an array of strings".into()),
+        };
+        Ok(Self {
+            keywords,
+            tags,
+            since: parse_time_arg(args, "since", false)?,
+            until: parse_time_arg(args, "until", true)?,
+        })
+    }
+
+    /// Bounds for [`Memory::entries_between`], if a date range was given.
+    fn date_range(&self) -> Option<(DateTime<Utc>, DateTime<Utc>)> {
+        if self.since.is_none() && self.until.is_none() {
+            return None;
+        }
+        let start = self.since.unwrap_or(DateTime::UNIX_EPOCH);
+        Some((start, self.until.unwrap_or_else(Utc::now)))
+    }
+
+    fn matches(&self, entry: &MemoryEntry, tags: &[String]) -> bool {
+        let haystack = format!("{} {}", entry.key, entry.content).to_lowercase();
+        if !self.keywords.iter().all(|k| haystack.contains(k.as_str())) {
+            return false;
+        }
+        let suggested = suggested_tags(&entry.content);
+        if !self.tags.iter().all(|wanted| {
+            tags.iter()
+                .chain(suggested.iter())
+                .any(|t| t.eq_ignore_ascii_case(wanted))
+        }) {
+            return false;
+        }
+        true
+    }
+}
+
+/// Accept a full RFC 3339 timestamp or `YYYY-MM-DD`. A bare date covers the
+/// whole local day: `since` starts at its first instant, `until` ends at its
+/// last.
+fn parse_time_arg(
+    args: &serde_json::Value,
+    field: &str,
+    end_of_day: bool,
+) -> Result<Option<DateTime<Utc>>, String> {
+    let Some(raw) = args.get(field).and_then(serde_json::Value::as_str) else {
+        return Ok(None);
+    };
+    let raw = raw.trim();
+    let invalid = || format!("Invalid '{field}' date '{raw}'; use YYYY-MM-DD or RFC 3339");
+    if let Ok(parsed) = DateTime::parse_from_rfc3339(raw) {
+        return Ok(Some(parsed.with_timezone(&Utc)));
+    }
+    let date = NaiveDate::parse_from_str(raw, "%Y-%m-%d").map_err(|_| invalid())?;
+    if !end_of_day {
+        return memory::entry_time(raw).map(Some).ok_or_else(invalid);
+    }
+    date.succ_opt()
+        .and_then(|next| memory::entry_time(&next.format("%Y-%m-%d").to_string()))
+        .map(|next| Some(next - Duration::nanoseconds(1)))
+        .ok_or_else(invalid)
+}
+
+/// Calendar date of a stored timestamp. SQLite-backed entries carry RFC 3339
+/// timestamps; markdown entries carry their daily file name (`2025-01-31`).
+pub(super) fn entry_date(timestamp: &str) -> Option<NaiveDate> {
+    let timestamp = timestamp.trim();
+    if let Ok(parsed) = chrono::DateTime::parse_from_rfc3339(timestamp) {
+        return Some(parsed.date_naive());
+    }
+    timestamp
+        .get(..10)
+        .and_then(|prefix| NaiveDate::parse_from_str(prefix, "%Y-%m-%d").ok())
+}
+
+fn entry_json(entry: &MemoryEntry, tags: &[String]) -> serde_json::Value {
+    json!({
+        "id": entry.id,
+        "key": entry.key,
+        "category": entry.category.to_string(),
+        "timestamp": entry.timestamp,
+        "tags": tags,
+        "suggested_tags": suggested_tags(&entry.content),
+        "relates_to": memory::related_keys(&entry.content),
+        "provenance": memory::entry_provenance(&entry.content).map(|p| json!({
+            "session_id": p.session_id,
+            "correlation_id": p.correlation_id,
+            "model": p.model,
+        })),
+        "content": entry.content,
+    })
+}
+
+#[async_trait]
+impl Tool for MemorySearchTool {
+    fn name(&self) -> &str {
+        "memory_search"
+    }
+
+    fn operation(&self) -> ToolOperation {
+        ToolOperation::Read
+    }
+
+    fn description(&self) -> &str {
+        "Filter long-term memory by keywords, #tags, category, and date range. Returns matching entries as structured JSON, ranked by recency and tag weight, with the keys each entry relates to; set follow_links to include those linked entries. Use memory_recall instead for fuzzy relevance search."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "keywords": {
+                    "type": "string",
+                    "description": "Space-separated words that must all appear in the entry (case-insensitive)"
+                },
+                "tags": {
+                    "type": "array",
+                    "items": { "type": "string" },
+                    "description": "Tags the entry must all carry, with or without '#'"
+                },
+                "category": {
+                    "type": "string",
+                    "description": "Only search this category: 'core', 'daily', 'conversation', or a custom name"
+                },
+                "since": {
+                    "type": "string",
+                    "description": "Earliest entry time, inclusive: YYYY-MM-DD (start of that day) or an RFC 3339 timestamp"
+                },
+                "until": {
+                    "type": "string",
+                    "description": "Latest entry time, inclusive: YYYY-MM-DD (end of that day) or an RFC 3339 timestamp"
+                },
+                "limit": {
+                    "type": "integer",
+                    "description": "Max results to return (default: 20)"
+                },
+                "follow_links": {
+                    "type": "boolean",
+                    "description": "Also return entries the results link to via relates_to, under 'linked' (default: false)"
+                }
+            }
+        })
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let filter = match SearchFilter::from_args(&args) {
+            Ok(filter) => filter,
+            Err(error) => {
+                return Ok(ToolResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some(error),
+                })
+            }
+        };
+        let category = args
+            .get("category")
+            .and_then(serde_json::Value::as_str)
+            .map(|raw| match raw {
+                "core" => MemoryCategory::Core,
+                "daily" => MemoryCategory::Daily,
+                "conversation" => MemoryCategory::Conversation,
+                other => MemoryCategory::Custom(other.to_string()),
+            });
+        #[allow(clippy::cast_possible_truncation)]
+        let limit = args
+            .get("limit")
+            .and_then(serde_json::Value::as_u64)
+            .map_or(DEFAULT_LIMIT, |v| v as usize);
+
+        let entries = match filter.date_range() {
+            Some((start, end)) => {
+                self.memory
+                    .entries_between(start, end, category.as_ref())
+                    .await
+            }
+            None => self.memory.list(category.as_ref(), None).await,
+        };
+        let entries = match entries {
+            Ok(entries) => entries,
+            Err(e) => {
+                return Ok(ToolResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some(format!("Memory search failed: {e}")),
+                })
+            }
+        };
+
+        let now = Utc::now();
+        let mut matches: Vec<(f64, MemoryEntry, Vec<String>)> = entries
+            .into_iter()
+            .filter_map(|entry| {
+                let tags = inline_tags(&entry.content);
+                filter
+                    .matches(&entry, &tags)
+                    .then(|| (self.rank_score(&entry, &tags, now), entry, tags))
+            })
+            .collect();
+        matches.sort_by(|(a_score, a, _), (b_score, b, _)| {
+            b_score
+                .total_cmp(a_score)
+                .then_with(|| b.timestamp.cmp(&a.timestamp))
+        });
+        let total = matches.len();
+
+        let matches: Vec<(MemoryEntry, Vec<String>)> = matches
+            .into_iter()
+            .take(limit)
+            .map(|(_, entry, tags)| (entry, tags))
+            .collect();
+        let results: Vec<serde_json::Value> = matches
+            .iter()
+            .map(|(entry, tags)| entry_json(entry, tags))
+            .collect();
+        let mut output = json!({
+            "total": total,
+            "results": results,
+        });
+
+        if args
+            .get("follow_links")
+            .and_then(serde_json::Value::as_bool)
+            .unwrap_or(false)
+        {
+            let found: Vec<MemoryEntry> = matches.into_iter().map(|(entry, _)| entry).collect();
+            let linked = match memory::linked_entries(self.memory.as_ref(), &found).await {
+                Ok(linked) => linked,
+                Err(e) => {
+                    return Ok(ToolResult {
+                        success: false,
+                        output: String::new(),
+                        error: Some(format!("Memory search failed: {e}")),
+                    })
+                }
+            };
+            output["linked"] = linked
+                .iter()
+                .map(|entry| entry_json(entry, &inline_tags(&entry.content)))
+                .collect();
+        }
+
+        Ok(ToolResult {
+            success: true,
+            output: serde_json::to_string_pretty(&output)?,
+            error: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::SqliteMemory;
+    use tempfile::TempDir;
+
+    async fn seeded_mem() -> (TempDir, Arc<dyn Memory>) {
+        let tmp = TempDir::new().unwrap();
+        let mem = SqliteMemory::new(tmp.path()).unwrap();
+        mem.store(
+            "deploy",
+            "Deploy with blue/green rollout\n\nTags: #ops #release",
+            MemoryCategory::Core,
+            None,
+        )
+        .await
+        .unwrap();
+        mem.store(
+            "standup",
+            "Discussed release blockers #meeting",
+            MemoryCategory::Daily,
+            None,
+        )
+        .await
+        .unwrap();
+        mem.store("lang", "User prefers Rust", MemoryCategory::Core, None)
+            .await
+            .unwrap();
+        (tmp, Arc::new(mem))
+    }
+
+    async fn search(mem: Arc<dyn Memory>, args: serde_json::Value) -> serde_json::Value {
+        let result = MemorySearchTool::new(mem).execute(args).await.unwrap();
+        assert!(result.success, "{:?}", result.error);
+        serde_json::from_str(&result.output).unwrap()
+    }
+
+    #[tokio::test]
+    async fn filters_by_tag_and_reports_tags() {
+        let (_tmp, mem) = seeded_mem().await;
+        let out = search(mem, json!({"tags": ["#ops"]})).await;
+        assert_eq!(out["total"], 1);
+        assert_eq!(out["results"][0]["key"], "deploy");
+        assert_eq!(out["results"][0]["tags"], json!(["ops", "release"]));
+    }
+
+    #[tokio::test]
+    async fn keywords_must_all_match() {
+        let (_tmp, mem) = seeded_mem().await;
+        let out = search(mem.clone(), json!({"keywords": "release"})).await;
+        assert_eq!(out["total"], 2);
+        let out = search(mem, json!({"keywords": "release blockers"})).await;
+        assert_eq!(out["total"], 1);
+        assert_eq!(out["results"][0]["key"], "standup");
+    }
+
+    #[tokio::test]
+    async fn filters_by_category_and_date_range() {
+        let (_tmp, mem) = seeded_mem().await;
+        let out = search(mem.clone(), json!({"category": "core"})).await;
+        assert_eq!(out["total"], 2);
+
+        let out = search(mem.clone(), json!({"since": "2000-01-01"})).await;
+        assert_eq!(out["total"], 3);
+        let out = search(mem.clone(), json!({"until": "2000-01-01"})).await;
+        assert_eq!(out["total"], 0);
+
+        // A bare `until` date covers the whole of that day.
+        let today = chrono::Local::now().format("%Y-%m-%d").to_string();
+        let out = search(mem.clone(), json!({"since": today, "until": today})).await;
+        assert_eq!(out["total"], 3);
+
+        let hour_ago = (Utc::now() - Duration::hours(1)).to_rfc3339();
+        let out = search(mem.clone(), json!({"since": hour_ago, "category": "core"})).await;
+        assert_eq!(out["total"], 2);
+        let out = search(mem, json!({"until": hour_ago})).await;
+        assert_eq!(out["total"], 0);
+    }
+
+    #[tokio::test]
+    async fn follows_links_to_related_entries() {
+        let (_tmp, mem) = seeded_mem().await;
+        mem.store(
+            "incident",
+            "Rollback after failed rollout\n\nRelates-To: deploy, missing",
+            MemoryCategory::Daily,
+            None,
+        )
+        .await
+        .unwrap();
+
+        let out = search(mem.clone(), json!({"keywords": "rollback"})).await;
+        assert_eq!(
+            out["results"][0]["relates_to"],
+            json!(["deploy", "missing"])
+        );
+        assert!(out.get("linked").is_none());
+
+        let out = search(mem, json!({"keywords": "rollback", "follow_links": true})).await;
+        let linked = out["linked"].as_array().unwrap();
+        assert_eq!(linked.len(), 1);
+        assert_eq!(linked[0]["key"], "deploy");
+    }
+
+    #[tokio::test]
+    async fn tag_filter_matches_suggested_tags() {
+        let (_tmp, mem) = seeded_mem().await;
+        mem.store(
+            "ship",
+            "We agreed to ship Tuesdays\n\nSuggested-Tags: decision",
+            MemoryCategory::Core,
+            None,
+        )
+        .await
+        .unwrap();
+
+        let out = search(mem, json!({"tags": ["decision"]})).await;
+        assert_eq!(out["total"], 1);
+        assert_eq!(out["results"][0]["tags"], json!([]));
+        assert_eq!(out["results"][0]["suggested_tags"], json!(["decision"]));
+    }
+
+    #[tokio::test]
+    async fn ranks_by_recency_and_tag_weight() {
+        let tmp = TempDir::new().unwrap();
+        let mem = SqliteMemory::new(tmp.path()).unwrap();
+        for (key, content) in [
+            ("old_decision", "Ship on Tuesdays #decision"),
+            ("new_observation", "Ship looked slow #observation"),
+            ("new_plain", "Ship notes"),
+        ] {
+            mem.store(key, content, MemoryCategory::Daily, None)
+                .await
+                .unwrap();
+        }
+        let old = (Utc::now() - Duration::days(10)).to_rfc3339();
+        rusqlite::Connection::open(tmp.path().join("memory").join("brain.db"))
+            .unwrap()
+            .execute(
+                "UPDATE memories SET created_at = ?1 WHERE key = 'old_decision'",
+                [&old],
+            )
+            .unwrap();
+        let mem: Arc<dyn Memory> = Arc::new(mem);
+
+        let keys = |out: serde_json::Value| -> Vec<String> {
+            out["results"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|r| r["key"].as_str().unwrap().to_string())
+                .collect()
+        };
+
+        // Default ranking: newest first.
+        let out = search(mem.clone(), json!({"keywords": "ship"})).await;
+        assert_eq!(keys(out).last().unwrap(), "old_decision");
+
+        let ranking = MemorySearchRankingConfig {
+            recency_half_life_days: 30.0,
+            tag_weights: [
+                ("decision".to_string(), 1.0),
+                ("observation".to_string(), -0.5),
+            ]
+            .into_iter()
+            .collect(),
+        };
+        let result = MemorySearchTool::new(mem)
+            .with_ranking(ranking)
+            .execute(json!({"keywords": "ship"}))
+            .await
+            .unwrap();
+        let out: serde_json::Value = serde_json::from_str(&result.output).unwrap();
+        assert_eq!(
+            keys(out),
+            vec!["old_decision", "new_plain", "new_observation"]
+        );
+    }
+
+    #[tokio::test]
+    async fn rejects_invalid_date() {
+        let (_tmp, mem) = seeded_mem().await;
+        let result = MemorySearchTool::new(mem)
+            .execute(json!({"since": "last week"}))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("YYYY-MM-DD"));
+    }
+
+    #[test]
+    fn entry_date_reads_rfc3339_and_daily_file_names() {
+        let expected = NaiveDate::from_ymd_opt(2025, 1, 31);
+        assert_eq!(entry_date("2025-01-31T23:10:00+00:00"), expected);
+        assert_eq!(entry_date("2025-01-31"), expected);
+        assert_eq!(entry_date("MEMORY"), None);
+    }
+}
@@ -7,18 +7,30 @@
 //!
 //! Tools are assembled into registries by [`default_tools`] (shell, file read/write)
 //! and [`all_tools`] (full set including memory, browser, cron, HTTP, delegation,
-//! and optional integrations). Security policy enforcement is injected via
+//! and optional integrations). Library users wiring a custom tool set should use
+//! [`ToolRegistry`]. Security policy enforcement is injected via
 //! [`SecurityPolicy`](crate::security::SecurityPolicy) at construction time.
 //!
 //! # Extension
 //!
 //! To add a new tool, implement [`Tool`] in a new submodule and register it in
 //! [`all_tools_with_runtime`]. See `AGENTS.md` §7.3 for the full change playbook.
+//!
+//! Registries are built eagerly, every tool at once. Keep constructors cheap:
+//! build HTTP clients and open connections on the first call, as the web,
+//! browser, and integration tools do, so a one-shot CLI command only pays for
+//! the tools it uses. MCP servers are the exception, since their tool specs
+//! come from the handshake; [`McpRegistry::connect_all`] runs the handshakes
+//! concurrently. Providers follow the same rule: fallbacks and secondary
+//! model routes are built on their first request (see
+//! [`crate::providers::lazy`]).
 
 pub mod agent_load_tracker;
 pub mod agent_selection;
 pub mod agents_ipc;
 pub mod apply_patch;
+pub mod approval_status;
+pub mod audited;
 pub mod auth_profile;
 pub mod bg_run;
 pub mod browser;
@@ -26,6 +38,7 @@ pub mod browser_open;
 pub mod channel_ack_config;
 pub mod cli_discovery;
 pub mod composio;
+pub mod consolidate_memory;
 pub mod content_search;
 pub mod cron_add;
 pub mod cron_list;
@@ -35,12 +48,15 @@ pub mod cron_runs;
 pub mod cron_update;
 pub mod delegate;
 pub mod delegate_coordination_status;
+pub mod digest_memory;
 pub mod docx_read;
+pub mod dry_run;
 #[cfg(feature = "channel-lark")]
 pub mod feishu_doc;
 pub mod file_edit;
 pub mod file_read;
 pub mod file_write;
+pub mod gated;
 pub mod git_operations;
 pub mod glob_search;
 #[cfg(feature = "hardware")]
@@ -49,6 +65,7 @@ pub mod hardware_board_info;
 pub mod hardware_memory_map;
 #[cfg(feature = "hardware")]
 pub mod hardware_memory_read;
+pub mod home_assistant;
 pub mod http_request;
 pub mod image_info;
 pub mod mcp_client;
@@ -58,6 +75,7 @@ pub mod mcp_transport;
 pub mod memory_forget;
 pub mod memory_observe;
 pub mod memory_recall;
+pub mod memory_search;
 pub mod memory_store;
 pub mod model_routing_config;
 pub mod openclaw_migration;
@@ -68,15 +86,24 @@ pub mod process;
 pub mod proxy_config;
 pub mod pushover;
 pub mod quota_tools;
+pub mod redact_memory;
+pub mod registry;
+pub mod request_escalation;
+pub mod scanned;
 pub mod schedule;
 pub mod schema;
+pub mod schema_export;
+pub mod scratchpad;
 pub mod screenshot;
 pub mod shell;
+pub mod size_limited;
 pub mod subagent_list;
 pub mod subagent_manage;
 pub mod subagent_registry;
 pub mod subagent_spawn;
 pub mod task_plan;
+#[cfg(test)]
+pub(crate) mod test_support;
 pub mod traits;
 pub mod url_validation;
 pub mod wasm_module;
@@ -85,10 +112,14 @@ pub mod web_access_config;
 pub mod web_fetch;
 pub mod web_search_config;
 pub mod web_search_tool;
+pub mod wrappers;
 pub mod xlsx_read;
 
 pub use agent_load_tracker::AgentLoadTracker;
 pub use apply_patch::ApplyPatchTool;
+pub use approval_status::ApprovalStatusTool;
+#[allow(unused_imports)]
+pub use audited::AuditedTool;
 #[allow(unused_imports)]
 pub use bg_run::{
     format_bg_result_for_injection, BgJob, BgJobStatus, BgJobStore, BgRunTool, BgStatusTool,
@@ -97,6 +128,7 @@ pub use browser::{BrowserTool, ComputerUseConfig};
 pub use browser_open::BrowserOpenTool;
 pub use channel_ack_config::ChannelAckConfigTool;
 pub use composio::ComposioTool;
+pub use consolidate_memory::ConsolidateMemoryTool;
 pub use content_search::ContentSearchTool;
 pub use cron_add::CronAddTool;
 pub use cron_list::CronListTool;
@@ -106,12 +138,16 @@ pub use cron_runs::CronRunsTool;
 pub use cron_update::CronUpdateTool;
 pub use delegate::DelegateTool;
 pub use delegate_coordination_status::DelegateCoordinationStatusTool;
+pub use digest_memory::DigestMemoryTool;
 pub use docx_read::DocxReadTool;
+pub use dry_run::DryRunTool;
 #[cfg(feature = "channel-lark")]
 pub use feishu_doc::FeishuDocTool;
 pub use file_edit::FileEditTool;
 pub use file_read::FileReadTool;
 pub use file_write::FileWriteTool;
+#[allow(unused_imports)]
+pub use gated::{ApprovalGatedTool, CapabilityGatedTool, LivePolicyGatedTool, PolicyGatedTool};
 pub use git_operations::GitOperationsTool;
 pub use glob_search::GlobSearchTool;
 #[cfg(feature = "hardware")]
@@ -120,6 +156,7 @@ pub use hardware_board_info::HardwareBoardInfoTool;
 pub use hardware_memory_map::HardwareMemoryMapTool;
 #[cfg(feature = "hardware")]
 pub use hardware_memory_read::HardwareMemoryReadTool;
+pub use home_assistant::HomeAssistantTool;
 pub use http_request::HttpRequestTool;
 pub use image_info::ImageInfoTool;
 pub use mcp_client::McpRegistry;
@@ -127,6 +164,7 @@ pub use mcp_tool::McpToolWrapper;
 pub use memory_forget::MemoryForgetTool;
 pub use memory_observe::MemoryObserveTool;
 pub use memory_recall::MemoryRecallTool;
+pub use memory_search::MemorySearchTool;
 pub use memory_store::MemoryStoreTool;
 pub use model_routing_config::ModelRoutingConfigTool;
 pub use openclaw_migration::OpenClawMigrationTool;
@@ -135,28 +173,37 @@ pub use pptx_read::PptxReadTool;
 pub use process::ProcessTool;
 pub use proxy_config::ProxyConfigTool;
 pub use pushover::PushoverTool;
-pub use schedule::ScheduleTool;
+pub use redact_memory::RedactMemoryTool;
+pub use request_escalation::RequestEscalationTool;
 #[allow(unused_imports)]
+pub use scanned::ScannedTool;
+pub use schedule::ScheduleTool;
 pub use schema::{CleaningStrategy, SchemaCleanr};
+pub use scratchpad::ScratchpadTool;
 pub use screenshot::ScreenshotTool;
 pub use shell::ShellTool;
+#[allow(unused_imports)]
+pub use size_limited::SizeLimitedTool;
 pub use subagent_list::SubAgentListTool;
 pub use subagent_manage::SubAgentManageTool;
 pub use subagent_registry::SubAgentRegistry;
 pub use subagent_spawn::SubAgentSpawnTool;
 pub use task_plan::TaskPlanTool;
+use traits::delegate_tool_metadata;
 pub use traits::Tool;
 #[allow(unused_imports)]
-pub use traits::{ToolResult, ToolSpec};
+pub use traits::{ToolExecutionContext, ToolResult, ToolSpec};
 pub use wasm_module::WasmModuleTool;
 pub use web_access_config::WebAccessConfigTool;
 pub use web_fetch::WebFetchTool;
 pub use web_search_config::WebSearchConfigTool;
 pub use web_search_tool::WebSearchTool;
+pub use wrappers::ToolWrappers;
 pub use xlsx_read::XlsxReadTool;
 
 pub use auth_profile::ManageAuthProfileTool;
 pub use quota_tools::{CheckProviderQuotaTool, EstimateQuotaCostTool, SwitchProviderTool};
+pub use registry::ToolRegistry;
 
 use crate::config::{Config, DelegateAgentConfig};
 use crate::memory::Memory;
@@ -180,17 +227,7 @@ impl ArcDelegatingTool {
 
 #[async_trait]
 impl Tool for ArcDelegatingTool {
-    fn name(&self) -> &str {
-        self.inner.name()
-    }
-
-    fn description(&self) -> &str {
-        self.inner.description()
-    }
-
-    fn parameters_schema(&self) -> serde_json::Value {
-        self.inner.parameters_schema()
-    }
+    delegate_tool_metadata!();
 
     async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
         self.inner.execute(args).await
@@ -359,15 +396,44 @@ pub fn default_tools_with_runtime(
         tools.push(Box::new(FileReadTool::new(security.clone())));
         tools.push(Box::new(FileWriteTool::new(security.clone())));
         tools.push(Box::new(FileEditTool::new(security.clone())));
-        tools.push(Box::new(ApplyPatchTool::new()));
+        tools.push(Box::new(ApplyPatchTool::new(security.clone())));
         tools.push(Box::new(GlobSearchTool::new(security.clone())));
         tools.push(Box::new(ContentSearchTool::new(security.clone())));
     }
     if runtime.as_any().is::<crate::runtime::WasmRuntime>() {
-        tools.push(Box::new(WasmModuleTool::new(security, runtime)));
+        tools.push(Box::new(WasmModuleTool::new(security.clone(), runtime)));
     }
 
+    tools.retain(|tool| security.is_tool_allowed(tool.name()));
+    let tools = with_approval_gate(tools, &security, security.audit_logger.clone());
+    let Some(logger) = security.audit_logger.as_ref() else {
+        return tools;
+    };
     tools
+        .into_iter()
+        .map(|tool| Box::new(AuditedTool::new(tool, Arc::clone(logger))) as Box<dyn Tool>)
+        .collect()
+}
+
+/// Put every tool to the policy's approval hook first, when it has one, and
+/// add `approval_status` to collect the calls it holds. Held calls are
+/// audited to `audit`.
+fn with_approval_gate(
+    mut tools: Vec<Box<dyn Tool>>,
+    security: &Arc<SecurityPolicy>,
+    audit: Option<Arc<crate::security::AuditLogger>>,
+) -> Vec<Box<dyn Tool>> {
+    if security.approval_hook.is_none() {
+        return tools;
+    }
+    tools.push(Box::new(ApprovalStatusTool::new(Arc::clone(security))));
+    tools
+        .into_iter()
+        .map(|tool| {
+            Box::new(ApprovalGatedTool::new(tool, Arc::clone(security)).with_audit(audit.clone()))
+                as Box<dyn Tool>
+        })
+        .collect()
 }
 
 /// Create full tool registry including memory tools and optional Composio
@@ -420,6 +486,42 @@ pub fn all_tools_with_runtime(
     fallback_api_key: Option<&str>,
     root_config: &crate::config::Config,
 ) -> Vec<Box<dyn Tool>> {
+    tool_registry_with_runtime(
+        config,
+        security,
+        runtime,
+        memory,
+        composio_key,
+        composio_entity_id,
+        browser_config,
+        http_config,
+        web_fetch_config,
+        workspace_dir,
+        agents,
+        fallback_api_key,
+        root_config,
+    )
+    .0
+}
+
+/// [`all_tools_with_runtime`], plus the [`ToolWrappers`] its tools were
+/// wrapped in, for tools registered later.
+#[allow(clippy::implicit_hasher, clippy::too_many_arguments)]
+pub fn tool_registry_with_runtime(
+    config: Arc<Config>,
+    security: &Arc<SecurityPolicy>,
+    runtime: Arc<dyn RuntimeAdapter>,
+    memory: Arc<dyn Memory>,
+    composio_key: Option<&str>,
+    composio_entity_id: Option<&str>,
+    browser_config: &crate::config::BrowserConfig,
+    http_config: &crate::config::HttpRequestConfig,
+    web_fetch_config: &crate::config::WebFetchConfig,
+    workspace_dir: &std::path::Path,
+    agents: &HashMap<String, DelegateAgentConfig>,
+    fallback_api_key: Option<&str>,
+    root_config: &crate::config::Config,
+) -> (Vec<Box<dyn Tool>>, ToolWrappers) {
     let has_shell_access = runtime.has_shell_access();
     let has_filesystem_access = runtime.has_filesystem_access();
     let zeroclaw_dir = root_config
@@ -433,6 +535,26 @@ pub fn all_tools_with_runtime(
         root_config.security.audit.clone(),
     ));
 
+    // A `[security] policy_file` replaces `[autonomy]` for every tool: they
+    // are built against its first version, and each call is checked against
+    // the newest one with the budgets it shares with `security`.
+    let live_policy = match root_config.security.policy_file.as_deref() {
+        Some(policy_file) => {
+            let path = zeroclaw_dir.join(policy_file);
+            match crate::security::LiveSecurityPolicy::load(&path, security) {
+                Ok(live) => Some(Arc::new(live)),
+                Err(e) => {
+                    // Fail closed: an unreadable policy must not widen access.
+                    tracing::error!("security.policy_file: {e:#}; all tools are disabled");
+                    return (Vec::new(), ToolWrappers::disabled(Arc::clone(security)));
+                }
+            }
+        }
+        None => None,
+    };
+    let file_policy = live_policy.as_ref().map(|live| live.current());
+    let security = file_policy.as_ref().unwrap_or(security);
+
     let mut tool_arcs: Vec<Arc<dyn Tool>> = vec![
         Arc::new(CronAddTool::new(config.clone(), security.clone())),
         Arc::new(CronListTool::new(config.clone())),
@@ -440,12 +562,27 @@ pub fn all_tools_with_runtime(
         Arc::new(CronUpdateTool::new(config.clone(), security.clone())),
         Arc::new(CronRunTool::new(config.clone(), security.clone())),
         Arc::new(CronRunsTool::new(config.clone())),
-        Arc::new(MemoryStoreTool::new(memory.clone(), security.clone())),
+        Arc::new(
+            MemoryStoreTool::new(memory.clone(), security.clone())
+                .with_tag_suggestions(root_config.memory.tag_suggestions.clone()),
+        ),
         Arc::new(MemoryObserveTool::new(memory.clone(), security.clone())),
         Arc::new(MemoryRecallTool::new(memory.clone())),
-        Arc::new(MemoryForgetTool::new(memory, security.clone())),
+        Arc::new(
+            MemorySearchTool::new(memory.clone())
+                .with_ranking(root_config.memory.search_ranking.clone()),
+        ),
+        Arc::new(MemoryForgetTool::new(memory.clone(), security.clone())),
+        Arc::new(RedactMemoryTool::new(memory.clone(), security.clone())),
+        Arc::new(ConsolidateMemoryTool::new(memory.clone(), security.clone())),
+        Arc::new(DigestMemoryTool::new(memory, security.clone())),
+        Arc::new(ScratchpadTool::new(
+            scratchpad::Scratchpads::shared(),
+            security.clone(),
+        )),
         Arc::new(ScheduleTool::new(security.clone(), root_config.clone())),
         Arc::new(TaskPlanTool::new(security.clone())),
+        Arc::new(RequestEscalationTool::new(security.clone())),
         Arc::new(ModelRoutingConfigTool::new(
             config.clone(),
             security.clone(),
@@ -489,7 +626,7 @@ pub fn all_tools_with_runtime(
         tool_arcs.push(Arc::new(FileReadTool::new(security.clone())));
         tool_arcs.push(Arc::new(FileWriteTool::new(security.clone())));
         tool_arcs.push(Arc::new(FileEditTool::new(security.clone())));
-        tool_arcs.push(Arc::new(ApplyPatchTool::new()));
+        tool_arcs.push(Arc::new(ApplyPatchTool::new(security.clone())));
         tool_arcs.push(Arc::new(GlobSearchTool::new(security.clone())));
         tool_arcs.push(Arc::new(ContentSearchTool::new(security.clone())));
     }
@@ -593,6 +730,13 @@ pub fn all_tools_with_runtime(
         )));
     }
 
+    if root_config.home_assistant.enabled {
+        tool_arcs.push(Arc::new(HomeAssistantTool::new(
+            security.clone(),
+            root_config.home_assistant.clone(),
+        )));
+    }
+
     // PDF extraction (feature-gated at compile time via rag-pdf)
     tool_arcs.push(Arc::new(PdfReadTool::new(security.clone())));
 
@@ -785,8 +929,56 @@ pub fn all_tools_with_runtime(
     // This ensures `bg_run` / `bg_status` are available anywhere the
     // runtime tool graph is used.
     let built_tools = boxed_registry_from_arcs(tool_arcs);
-    let (extended_tools, _bg_job_store) = add_bg_tools(built_tools);
-    extended_tools
+    let (mut extended_tools, _bg_job_store) = add_bg_tools(built_tools);
+    // Policy-disabled tools never reach model context; tools that run
+    // `enforce_tool_operation` also refuse them if called some other way.
+    extended_tools.retain(|tool| security.is_tool_allowed(tool.name()));
+    let audit = if root_config.security.audit.enabled {
+        match crate::security::AuditLogger::shared(&root_config.security.audit, &zeroclaw_dir) {
+            Ok(logger) => Some(logger),
+            Err(e) => {
+                tracing::warn!("audit: tool calls will not be logged: {e}");
+                None
+            }
+        }
+    } else {
+        None
+    };
+    if security.approval_hook.is_some() {
+        extended_tools.push(Box::new(ApprovalStatusTool::new(Arc::clone(security))));
+    }
+
+    if let Some(live) = &live_policy {
+        if tokio::runtime::Handle::try_current().is_ok() {
+            live.spawn_watcher(crate::security::live_policy::POLICY_RELOAD_INTERVAL);
+        }
+    }
+
+    let scanner_config = &root_config.security.content_scanner;
+    let scanner = if scanner_config.enabled {
+        let mut policy_files: Vec<&str> = Vec::new();
+        if let Some(name) = root_config.config_path.file_name().and_then(|n| n.to_str()) {
+            policy_files.push(name);
+        }
+        if let Some(policy_file) = root_config.security.policy_file.as_deref() {
+            policy_files.push(policy_file);
+        }
+        match crate::security::ContentScanner::from_config(scanner_config, &policy_files) {
+            Ok(scanner) => Some(Arc::new(scanner)),
+            Err(e) => {
+                tracing::warn!("security.content_scanner: {e:#}; arguments will not be scanned");
+                None
+            }
+        }
+    } else {
+        None
+    };
+
+    let wrappers = ToolWrappers::new(Arc::clone(security))
+        .with_audit(audit)
+        .with_live_policy(live_policy)
+        .with_scanner(scanner);
+    (wrappers.wrap_all(extended_tools), wrappers)
 }
 
 #[cfg(test)]
@@ -903,6 +1095,31 @@ mod tests {
         assert!(tools.iter().any(|tool| tool.name() == "apply_patch"));
     }
 
+    #[tokio::test]
+    async fn default_tools_record_calls_in_the_policy_audit_logger() {
+        let tmp = TempDir::new().unwrap();
+        let logger = Arc::new(
+            crate::security::AuditLogger::new(
+                crate::config::AuditConfig::default(),
+                tmp.path().to_path_buf(),
+            )
+            .unwrap(),
+        );
+        let security = Arc::new(SecurityPolicy {
+            workspace_dir: tmp.path().to_path_buf(),
+            audit_logger: Some(logger),
+            ..SecurityPolicy::default()
+        });
+        let tools = default_tools(security);
+        let glob = tools.iter().find(|t| t.name() == "glob_search").unwrap();
+
+        glob.execute(json!({"pattern": "*.md"})).await.unwrap();
+
+        let log = std::fs::read_to_string(tmp.path().join("ariadne/audit/audit.log")).unwrap();
+        assert_eq!(log.lines().count(), 1);
+        assert!(log.contains("glob_search"));
+    }
+
     #[test]
     fn default_tools_with_runtime_includes_wasm_module_for_wasm_runtime() {
         let security = Arc::new(SecurityPolicy::default());
@@ -965,6 +1182,7 @@ mod tests {
         );
         let names: Vec<&str> = tools.iter().map(|t| t.name()).collect();
         assert!(!names.contains(&"browser_open"));
+        assert!(!names.contains(&"home_assistant"));
         assert!(names.contains(&"schedule"));
         assert!(names.contains(&"model_routing_config"));
         assert!(names.contains(&"pushover"));
@@ -1056,6 +1274,60 @@ mod tests {
         assert!(names.contains(&"pdf_read"));
     }
 
+    #[tokio::test]
+    async fn all_tools_with_policy_file_keep_policy_state_out_of_reach() {
+        let tmp = TempDir::new().unwrap();
+        let state_file = tmp
+            .path()
+            .join("state")
+            .join(crate::security::policy::RATE_LIMIT_STATE_FILE);
+        std::fs::create_dir_all(state_file.parent().unwrap()).unwrap();
+        std::fs::write(&state_file, "{}").unwrap();
+        // The policy file's workspace is the config dir, so `state/` lies
+        // inside it and only the policy state rule keeps it out.
+        std::fs::write(
+            tmp.path().join("policy.toml"),
+            "workspace_dir = \".\"\nlevel = \"full\"\n",
+        )
+        .unwrap();
+
+        let mut cfg = test_config(&tmp);
+        cfg.security.policy_file = Some("policy.toml".into());
+        let security = Arc::new(SecurityPolicy::from_root_config(&cfg));
+        let mem_cfg = MemoryConfig {
+            backend: "markdown".into(),
+            ..MemoryConfig::default()
+        };
+        let mem: Arc<dyn Memory> =
+            Arc::from(crate::memory::create_memory(&mem_cfg, tmp.path(), None).unwrap());
+        let browser = BrowserConfig {
+            enabled: false,
+            ..BrowserConfig::default()
+        };
+        let http = crate::config::HttpRequestConfig::default();
+
+        let tools = all_tools(
+            Arc::new(Config::default()),
+            &security,
+            mem,
+            None,
+            None,
+            &browser,
+            &http,
+            &crate::config::WebFetchConfig::default(),
+            tmp.path(),
+            &HashMap::new(),
+            None,
+            &cfg,
+        );
+        let file_read = tools.iter().find(|t| t.name() == "file_read").unwrap();
+        let result = file_read
+            .execute(json!({ "path": "state/ratelimit.json" }))
+            .await
+            .unwrap();
+        assert!(!result.success, "{}", result.output);
+    }
+
     #[test]
     fn all_tools_with_runtime_includes_wasm_module_for_wasm_runtime() {
         let tmp = TempDir::new().unwrap();
@@ -4,7 +4,7 @@ use crate::config::{
     ModelRouteConfig, SubAgentsConfig,
 };
 use crate::providers::has_provider_credential;
-use crate::security::SecurityPolicy;
+use crate::security::{PolicyHandle, SecurityPolicy};
 use crate::util::MaybeSet;
 use async_trait::async_trait;
 use serde_json::{json, Value};
@@ -17,12 +17,15 @@ const DEFAULT_AGENT_MAX_ITERATIONS: usize = 10;
 
 pub struct ModelRoutingConfigTool {
     config: Arc<Config>,
-    security: Arc<SecurityPolicy>,
+    security: PolicyHandle,
 }
 
 impl ModelRoutingConfigTool {
     pub fn new(config: Arc<Config>, security: Arc<SecurityPolicy>) -> Self {
-        Self { config, security }
+        Self {
+            config,
+            security: security.into(),
+        }
     }
 
     fn load_config_without_env(&self) -> anyhow::Result<Config> {
@@ -45,7 +48,8 @@ impl ModelRoutingConfigTool {
     }
 
     fn require_write_access(&self) -> Option<ToolResult> {
-        if !self.security.can_act() {
+        let security = self.security.current();
+        if !security.can_act() {
             return Some(ToolResult {
                 success: false,
                 output: String::new(),
@@ -53,7 +57,7 @@ impl ModelRoutingConfigTool {
             });
         }
 
-        if !self.security.record_action() {
+        if !security.record_action() {
             return Some(ToolResult {
                 success: false,
                 output: String::new(),
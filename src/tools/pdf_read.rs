@@ -1,5 +1,6 @@
 use super::traits::{Tool, ToolResult};
-use crate::security::SecurityPolicy;
+use crate::security::policy::ToolOperation;
+use crate::security::{PolicyHandle, SecurityPolicy};
 use async_trait::async_trait;
 use serde_json::json;
 use std::sync::Arc;
@@ -19,12 +20,14 @@ const MAX_OUTPUT_CHARS: usize = 200_000;
 /// Without the feature the tool is still registered so the LLM receives a
 /// clear, actionable error rather than a missing-tool confusion.
 pub struct PdfReadTool {
-    security: Arc<SecurityPolicy>,
+    security: PolicyHandle,
 }
 
 impl PdfReadTool {
     pub fn new(security: Arc<SecurityPolicy>) -> Self {
-        Self { security }
+        Self {
+            security: security.into(),
+        }
     }
 }
 
@@ -34,6 +37,10 @@ impl Tool for PdfReadTool {
         "pdf_read"
     }
 
+    fn operation(&self) -> ToolOperation {
+        ToolOperation::Read
+    }
+
     fn description(&self) -> &str {
         "Extract plain text from a PDF file in the workspace. \
          Returns all readable text. Image-only or encrypted PDFs return an empty result. \
@@ -60,6 +67,7 @@ impl Tool for PdfReadTool {
     }
 
     async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let security = self.security.current();
         let path = args
             .get("path")
             .and_then(|v| v.as_str())
@@ -75,7 +83,7 @@ impl Tool for PdfReadTool {
             })
             .unwrap_or(DEFAULT_MAX_CHARS);
 
-        if self.security.is_rate_limited() {
+        if security.is_rate_limited() {
             return Ok(ToolResult {
                 success: false,
                 output: String::new(),
@@ -83,7 +91,7 @@ impl Tool for PdfReadTool {
             });
         }
 
-        if !self.security.is_path_allowed(path) {
+        if !security.is_path_allowed(path) {
             return Ok(ToolResult {
                 success: false,
                 output: String::new(),
@@ -92,7 +100,7 @@ impl Tool for PdfReadTool {
         }
 
         // Record action before canonicalization so path-probing still consumes budget.
-        if !self.security.record_action() {
+        if !security.record_action() {
             return Ok(ToolResult {
                 success: false,
                 output: String::new(),
@@ -100,30 +108,17 @@ impl Tool for PdfReadTool {
             });
         }
 
-        let full_path = self.security.resolve_user_supplied_path(path);
-
-        let resolved_path = match tokio::fs::canonicalize(&full_path).await {
+        let resolved_path = match security.resolve_in_workspace(path).await {
             Ok(p) => p,
-            Err(e) => {
+            Err(error) => {
                 return Ok(ToolResult {
                     success: false,
                     output: String::new(),
-                    error: Some(format!("Failed to resolve file path: {e}")),
+                    error: Some(error),
                 });
             }
         };
 
-        if !self.security.is_resolved_path_allowed(&resolved_path) {
-            return Ok(ToolResult {
-                success: false,
-                output: String::new(),
-                error: Some(
-                    self.security
-                        .resolved_path_violation_message(&resolved_path),
-                ),
-            });
-        }
-
         tracing::debug!("Reading PDF: {}", resolved_path.display());
 
         match tokio::fs::metadata(&resolved_path).await {
@@ -162,7 +157,7 @@ impl Tool for PdfReadTool {
         // pdf_extract is a blocking CPU-bound operation; keep it off the async executor.
         #[cfg(feature = "rag-pdf")]
         {
-            let text = match tokio::task::spawn_blocking(move || {
+            let text = match crate::runtime::spawn_fs_blocking(move || {
                 pdf_extract::extract_text_from_mem(&bytes)
             })
             .await
@@ -1,5 +1,6 @@
 use super::traits::{Tool, ToolResult};
-use crate::security::SecurityPolicy;
+use crate::security::policy::ToolOperation;
+use crate::security::{PolicyHandle, SecurityPolicy};
 use async_trait::async_trait;
 use serde_json::json;
 use std::collections::{HashMap, HashSet};
@@ -17,12 +18,14 @@ const MAX_TOTAL_SLIDE_XML_BYTES: u64 = 16 * 1024 * 1024;
 
 /// Extract plain text from a PPTX file in the workspace.
 pub struct PptxReadTool {
-    security: Arc<SecurityPolicy>,
+    security: PolicyHandle,
 }
 
 impl PptxReadTool {
     pub fn new(security: Arc<SecurityPolicy>) -> Self {
-        Self { security }
+        Self {
+            security: security.into(),
+        }
     }
 }
 
@@ -310,6 +313,10 @@ impl Tool for PptxReadTool {
         "pptx_read"
     }
 
+    fn operation(&self) -> ToolOperation {
+        ToolOperation::Read
+    }
+
     fn description(&self) -> &str {
         "Extract plain text from a PPTX (PowerPoint) file in the workspace. \
          Returns all readable text content from all slides. No formatting, images, or charts."
@@ -335,6 +342,7 @@ impl Tool for PptxReadTool {
     }
 
     async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let security = self.security.current();
         let path = args
             .get("path")
             .and_then(|v| v.as_str())
@@ -351,7 +359,7 @@ impl Tool for PptxReadTool {
             }
         };
 
-        if self.security.is_rate_limited() {
+        if security.is_rate_limited() {
             return Ok(ToolResult {
                 success: false,
                 output: String::new(),
@@ -359,7 +367,7 @@ impl Tool for PptxReadTool {
             });
         }
 
-        if !self.security.is_path_allowed(path) {
+        if !security.is_path_allowed(path) {
             return Ok(ToolResult {
                 success: false,
                 output: String::new(),
@@ -367,7 +375,7 @@ impl Tool for PptxReadTool {
             });
         }
 
-        if !self.security.record_action() {
+        if !security.record_action() {
             return Ok(ToolResult {
                 success: false,
                 output: String::new(),
@@ -375,30 +383,17 @@ impl Tool for PptxReadTool {
             });
         }
 
-        let full_path = self.security.resolve_user_supplied_path(path);
-
-        let resolved_path = match tokio::fs::canonicalize(&full_path).await {
+        let resolved_path = match security.resolve_in_workspace(path).await {
             Ok(p) => p,
-            Err(e) => {
+            Err(error) => {
                 return Ok(ToolResult {
                     success: false,
                     output: String::new(),
-                    error: Some(format!("Failed to resolve file path: {e}")),
+                    error: Some(error),
                 });
             }
         };
 
-        if !self.security.is_resolved_path_allowed(&resolved_path) {
-            return Ok(ToolResult {
-                success: false,
-                output: String::new(),
-                error: Some(
-                    self.security
-                        .resolved_path_violation_message(&resolved_path),
-                ),
-            });
-        }
-
         tracing::debug!("Reading PPTX: {}", resolved_path.display());
 
         match tokio::fs::metadata(&resolved_path).await {
@@ -434,7 +429,8 @@ impl Tool for PptxReadTool {
             }
         };
 
-        let text = match tokio::task::spawn_blocking(move || extract_pptx_text(&bytes)).await {
+        let text = match crate::runtime::spawn_fs_blocking(move || extract_pptx_text(&bytes)).await
+        {
             Ok(Ok(t)) => t,
             Ok(Err(e)) => {
                 return Ok(ToolResult {
@@ -1,9 +1,8 @@
-use super::shell::collect_allowed_shell_env_vars;
 use super::traits::{Tool, ToolResult};
 use crate::runtime::RuntimeAdapter;
 use crate::security::policy::ToolOperation;
-use crate::security::SecurityPolicy;
 use crate::security::SyscallAnomalyDetector;
+use crate::security::{PolicyHandle, SecurityPolicy};
 use async_trait::async_trait;
 use serde_json::json;
 use std::collections::HashMap;
@@ -41,7 +40,7 @@ struct ProcessEntry {
 /// and terminate them. Complements the synchronous `ShellTool` for commands
 /// that need to run beyond the 60-second shell timeout.
 pub struct ProcessTool {
-    security: Arc<SecurityPolicy>,
+    security: PolicyHandle,
     runtime: Arc<dyn RuntimeAdapter>,
     syscall_detector: Option<Arc<SyscallAnomalyDetector>>,
     processes: Arc<RwLock<HashMap<usize, ProcessEntry>>>,
@@ -59,7 +58,7 @@ impl ProcessTool {
         syscall_detector: Option<Arc<SyscallAnomalyDetector>>,
     ) -> Self {
         Self {
-            security,
+            security: security.into(),
             runtime,
             syscall_detector,
             processes: Arc::new(RwLock::new(HashMap::new())),
@@ -68,6 +67,7 @@ impl ProcessTool {
     }
 
     fn handle_spawn(&self, args: &serde_json::Value) -> anyhow::Result<ToolResult> {
+        let security = self.security.current();
         if !self.runtime.supports_long_running() {
             return Ok(ToolResult {
                 success: false,
@@ -105,7 +105,7 @@ impl ProcessTool {
         }
 
         // Reuse shell security chain: rate limit → command validation → path check → record.
-        if self.security.is_rate_limited() {
+        if security.is_rate_limited() {
             return Ok(ToolResult {
                 success: false,
                 output: String::new(),
@@ -118,7 +118,7 @@ impl ProcessTool {
             .and_then(|v| v.as_bool())
             .unwrap_or(false);
 
-        if let Err(reason) = self.security.validate_command_execution(command, approved) {
+        if let Err(reason) = security.validate_command_execution(command, approved) {
             return Ok(ToolResult {
                 success: false,
                 output: String::new(),
@@ -126,7 +126,7 @@ impl ProcessTool {
             });
         }
 
-        if let Some(path) = self.security.forbidden_path_argument(command) {
+        if let Some(path) = security.forbidden_path_argument(command) {
             return Ok(ToolResult {
                 success: false,
                 output: String::new(),
@@ -134,7 +134,7 @@ impl ProcessTool {
             });
         }
 
-        if !self.security.record_action() {
+        if !security.record_action() {
             return Ok(ToolResult {
                 success: false,
                 output: String::new(),
@@ -145,7 +145,7 @@ impl ProcessTool {
         // Build command via runtime adapter.
         let mut cmd = match self
             .runtime
-            .build_shell_command(command, &self.security.workspace_dir)
+            .build_shell_command(command, &security.workspace_dir)
         {
             Ok(cmd) => cmd,
             Err(e) => {
@@ -160,13 +160,7 @@ impl ProcessTool {
         cmd.stdin(Stdio::null());
         cmd.stdout(Stdio::piped());
         cmd.stderr(Stdio::piped());
-        cmd.env_clear();
-
-        for var in collect_allowed_shell_env_vars(&self.security) {
-            if let Ok(val) = std::env::var(&var) {
-                cmd.env(&var, val);
-            }
-        }
+        security.env_filter().apply(cmd.as_std_mut());
 
         let mut child = match cmd.spawn() {
             Ok(child) => child,
@@ -227,12 +221,13 @@ impl ProcessTool {
     fn handle_list(&self) -> anyhow::Result<ToolResult> {
         if let Err(e) = self
             .security
+            .current()
             .enforce_tool_operation(ToolOperation::Read, "process")
         {
             return Ok(ToolResult {
                 success: false,
                 output: String::new(),
-                error: Some(e),
+                error: Some(e.to_string()),
             });
         }
 
@@ -270,12 +265,13 @@ impl ProcessTool {
     fn handle_output(&self, args: &serde_json::Value) -> anyhow::Result<ToolResult> {
         if let Err(e) = self
             .security
+            .current()
             .enforce_tool_operation(ToolOperation::Read, "process")
         {
             return Ok(ToolResult {
                 success: false,
                 output: String::new(),
-                error: Some(e),
+                error: Some(e.to_string()),
             });
         }
 
@@ -333,14 +329,12 @@ impl ProcessTool {
     }
 
     fn handle_kill(&self, args: &serde_json::Value) -> anyhow::Result<ToolResult> {
-        if let Err(e) = self
-            .security
-            .enforce_tool_operation(ToolOperation::Act, "process")
-        {
+        let security = self.security.current();
+        if let Err(e) = security.enforce_tool_operation(ToolOperation::Act, "process") {
             return Ok(ToolResult {
                 success: false,
                 output: String::new(),
-                error: Some(e),
+                error: Some(e.to_string()),
             });
         }
 
@@ -361,9 +355,9 @@ impl ProcessTool {
         };
 
         // Send SIGTERM via kill command.
-        let kill_result = std::process::Command::new("kill")
-            .arg(pid.to_string())
-            .output();
+        let mut kill = std::process::Command::new("kill");
+        security.env_filter().apply(&mut kill);
+        let kill_result = kill.arg(pid.to_string()).output();
 
         match kill_result {
             Ok(output) if output.status.success() => Ok(ToolResult {
@@ -1,5 +1,5 @@
 use super::traits::{Tool, ToolResult};
-use crate::security::SecurityPolicy;
+use crate::security::{PolicyHandle, SecurityPolicy};
 use async_trait::async_trait;
 use serde_json::json;
 use std::path::PathBuf;
@@ -11,14 +11,14 @@ const PUSHOVER_TOKEN_ENV: &str = "PUSHOVER_TOKEN";
 const PUSHOVER_USER_KEY_ENV: &str = "PUSHOVER_USER_KEY";
 
 pub struct PushoverTool {
-    security: Arc<SecurityPolicy>,
+    security: PolicyHandle,
     workspace_dir: PathBuf,
 }
 
 impl PushoverTool {
     pub fn new(security: Arc<SecurityPolicy>, workspace_dir: PathBuf) -> Self {
         Self {
-            security,
+            security: security.into(),
             workspace_dir,
         }
     }
@@ -152,7 +152,8 @@ impl Tool for PushoverTool {
     }
 
     async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
-        if !self.security.can_act() {
+        let security = self.security.current();
+        if !security.can_act() {
             return Ok(ToolResult {
                 success: false,
                 output: String::new(),
@@ -160,7 +161,7 @@ impl Tool for PushoverTool {
             });
         }
 
-        if !self.security.record_action() {
+        if !security.record_action() {
             return Ok(ToolResult {
                 success: false,
                 output: String::new(),
@@ -213,6 +214,14 @@ impl Tool for PushoverTool {
             form = form.text("sound", sound);
         }
 
+        if let Err(e) = security.egress().check_url(PUSHOVER_API_URL) {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(e),
+            });
+        }
+
         let client = crate::config::build_runtime_proxy_client_with_timeouts(
             "tool.pushover",
             PUSHOVER_REQUEST_TIMEOUT_SECS,
@@ -11,6 +11,7 @@ use crate::config::Config;
 use crate::cost::tracker::CostTracker;
 use crate::providers::health::ProviderHealthTracker;
 use crate::providers::quota_types::{QuotaStatus, QuotaSummary};
+use crate::security::policy::ToolOperation;
 use crate::tools::{Tool, ToolResult};
 use anyhow::Result;
 use async_trait::async_trait;
@@ -71,6 +72,10 @@ impl Tool for CheckProviderQuotaTool {
         "check_provider_quota"
     }
 
+    fn operation(&self) -> ToolOperation {
+        ToolOperation::Read
+    }
+
     fn description(&self) -> &str {
         "Check current rate limit and quota status for AI providers. \
          Returns available providers, rate-limited providers, quota remaining, \
@@ -391,6 +396,10 @@ impl Tool for EstimateQuotaCostTool {
         "estimate_quota_cost"
     }
 
+    fn operation(&self) -> ToolOperation {
+        ToolOperation::Read
+    }
+
     fn description(&self) -> &str {
         "Estimate quota cost (tokens, requests) for an operation before executing it. \
          Useful for warning user if operation may exhaust quota or when planning \
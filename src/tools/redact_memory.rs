@@ -0,0 +1,275 @@
+use super::traits::{Tool, ToolResult};
+use crate::memory::Memory;
+use crate::security::policy::ToolOperation;
+use crate::security::{AutonomyLevel, PolicyHandle, SecurityPolicy};
+use async_trait::async_trait;
+use serde_json::json;
+use sha2::{Digest, Sha256};
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+/// Content written in place of a redacted entry.
+pub const REDACTION_MARKER: &str = "[REDACTED]";
+
+/// Audit log of redactions, relative to the workspace.
+const REDACTION_LOG: &str = "memory/redactions.jsonl";
+
+/// Let the agent redact a memory entry that holds something it should not,
+/// such as an accidentally stored secret.
+///
+/// The entry keeps its key and category but its content is replaced with
+/// [`REDACTION_MARKER`]. Each redaction is appended to
+/// `memory/redactions.jsonl` with a SHA-256 of the removed content, never the
+/// content itself. Only runs in supervised mode, where an operator approves
+/// the call.
+pub struct RedactMemoryTool {
+    memory: Arc<dyn Memory>,
+    security: PolicyHandle,
+}
+
+impl RedactMemoryTool {
+    pub fn new(memory: Arc<dyn Memory>, security: Arc<SecurityPolicy>) -> Self {
+        Self {
+            memory,
+            security: security.into(),
+        }
+    }
+
+    fn log_path(&self) -> PathBuf {
+        self.security.current().workspace_dir.join(REDACTION_LOG)
+    }
+
+    fn append_audit(&self, record: &serde_json::Value) -> anyhow::Result<()> {
+        let path = self.log_path();
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let mut file = self.security.current().fs().open_append(&path)?;
+        writeln!(file, "{record}")?;
+        Ok(())
+    }
+}
+
+fn denied(error: impl Into<String>) -> ToolResult {
+    ToolResult {
+        success: false,
+        output: String::new(),
+        error: Some(error.into()),
+    }
+}
+
+#[async_trait]
+impl Tool for RedactMemoryTool {
+    fn name(&self) -> &str {
+        "redact_memory"
+    }
+
+    fn description(&self) -> &str {
+        "Redact a memory entry by key: its content is replaced with [REDACTED] and the redaction is recorded in an audit log. Use when a stored note contains a secret or other data that must not be kept. Requires supervised mode."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "key": {
+                    "type": "string",
+                    "description": "Key of the memory entry to redact"
+                },
+                "reason": {
+                    "type": "string",
+                    "description": "Why the entry is being redacted (recorded in the audit log)"
+                }
+            },
+            "required": ["key"]
+        })
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let security = self.security.current();
+        let key = args
+            .get("key")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| anyhow::anyhow!("Missing 'key' parameter"))?;
+        let reason = args
+            .get("reason")
+            .and_then(|v| v.as_str())
+            .unwrap_or_default();
+
+        // An escalation to full autonomy would drop the per-redaction
+        // approval, so check the level in force, not the configured one.
+        if security.effective_autonomy() != AutonomyLevel::Supervised {
+            return Ok(denied(
+                "Security policy: redact_memory only runs in supervised mode, so an operator approves each redaction",
+            ));
+        }
+        if let Err(error) = security.enforce_tool_operation(ToolOperation::Act, "redact_memory") {
+            return Ok(denied(error));
+        }
+
+        let entry = match self.memory.get(key).await {
+            Ok(Some(entry)) if entry.key == key => entry,
+            Ok(_) => {
+                return Ok(ToolResult {
+                    success: true,
+                    output: format!("No memory found with key: {key}"),
+                    error: None,
+                })
+            }
+            Err(e) => return Ok(denied(format!("Failed to read memory: {e}"))),
+        };
+        if entry.content == REDACTION_MARKER {
+            return Ok(ToolResult {
+                success: true,
+                output: format!("Memory already redacted: {key}"),
+                error: None,
+            });
+        }
+
+        match self.memory.redact(key, REDACTION_MARKER).await {
+            Ok(true) => {}
+            Ok(false) => {
+                return Ok(ToolResult {
+                    success: true,
+                    output: format!("No memory found with key: {key}"),
+                    error: None,
+                })
+            }
+            Err(e) => return Ok(denied(format!("Failed to redact memory: {e}"))),
+        }
+
+        let record = json!({
+            "timestamp": chrono::Utc::now().to_rfc3339(),
+            "key": key,
+            "category": entry.category.to_string(),
+            "reason": reason,
+            "content_sha256": hex::encode(Sha256::digest(entry.content.as_bytes())),
+            "content_chars": entry.content.chars().count(),
+            "backend": self.memory.name(),
+        });
+        if let Err(e) = self.append_audit(&record) {
+            // The secret is already gone; report the missing audit record
+            // rather than pretending the redaction failed.
+            return Ok(denied(format!(
+                "Redacted memory '{key}', but failed to write audit record to {}: {e}",
+                self.log_path().display()
+            )));
+        }
+
+        Ok(ToolResult {
+            success: true,
+            output: format!("Redacted memory: {key} (logged to {REDACTION_LOG})"),
+            error: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::{MemoryCategory, SqliteMemory};
+    use tempfile::TempDir;
+
+    fn setup(autonomy: AutonomyLevel) -> (TempDir, Arc<dyn Memory>, RedactMemoryTool) {
+        let tmp = TempDir::new().unwrap();
+        let mem: Arc<dyn Memory> = Arc::new(SqliteMemory::new(tmp.path()).unwrap());
+        let security = Arc::new(SecurityPolicy {
+            autonomy,
+            workspace_dir: tmp.path().to_path_buf(),
+            ..SecurityPolicy::default()
+        });
+        let tool = RedactMemoryTool::new(mem.clone(), security);
+        (tmp, mem, tool)
+    }
+
+    #[tokio::test]
+    async fn redacts_content_and_writes_audit_record() {
+        let (tmp, mem, tool) = setup(AutonomyLevel::Supervised);
+        mem.store("wifi", "password is hunter2", MemoryCategory::Core, None)
+            .await
+            .unwrap();
+
+        let result = tool
+            .execute(json!({"key": "wifi", "reason": "secret"}))
+            .await
+            .unwrap();
+        assert!(result.success, "{:?}", result.error);
+
+        let entry = mem.get("wifi").await.unwrap().unwrap();
+        assert_eq!(entry.content, REDACTION_MARKER);
+        assert_eq!(entry.category, MemoryCategory::Core);
+
+        let log = std::fs::read_to_string(tmp.path().join(REDACTION_LOG)).unwrap();
+        assert!(!log.contains("hunter2"));
+        let record: serde_json::Value = serde_json::from_str(log.trim()).unwrap();
+        assert_eq!(record["key"], "wifi");
+        assert_eq!(record["reason"], "secret");
+        assert_eq!(
+            record["content_sha256"],
+            hex::encode(Sha256::digest(b"password is hunter2"))
+        );
+    }
+
+    #[tokio::test]
+    async fn missing_key_is_reported_without_audit_record() {
+        let (tmp, _mem, tool) = setup(AutonomyLevel::Supervised);
+        let result = tool.execute(json!({"key": "nope"})).await.unwrap();
+        assert!(result.success);
+        assert!(result.output.contains("No memory found"));
+        assert!(!tmp.path().join(REDACTION_LOG).exists());
+    }
+
+    #[tokio::test]
+    async fn refuses_outside_supervised_mode() {
+        for autonomy in [AutonomyLevel::Full, AutonomyLevel::ReadOnly] {
+            let (_tmp, mem, tool) = setup(autonomy);
+            mem.store("wifi", "password is hunter2", MemoryCategory::Core, None)
+                .await
+                .unwrap();
+            let result = tool.execute(json!({"key": "wifi"})).await.unwrap();
+            assert!(!result.success);
+            assert!(result.error.unwrap().contains("supervised mode"));
+            assert_eq!(
+                mem.get("wifi").await.unwrap().unwrap().content,
+                "password is hunter2"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn refuses_while_escalated_to_full_autonomy() {
+        let tmp = TempDir::new().unwrap();
+        let mem: Arc<dyn Memory> = Arc::new(SqliteMemory::new(tmp.path()).unwrap());
+        let state_dir = tmp.path().join("state");
+        let security = Arc::new(SecurityPolicy {
+            autonomy: AutonomyLevel::Supervised,
+            workspace_dir: tmp.path().to_path_buf(),
+            state_dir: Some(state_dir.clone()),
+            ..SecurityPolicy::default()
+        });
+        let store = crate::security::escalation::EscalationStore::new(&state_dir);
+        let request = store
+            .request(
+                crate::security::escalation::EscalationKind::Autonomy {
+                    level: AutonomyLevel::Full,
+                },
+                "clean up",
+                None,
+            )
+            .unwrap();
+        store.grant(&request.id, 30).unwrap();
+        mem.store("wifi", "password is hunter2", MemoryCategory::Core, None)
+            .await
+            .unwrap();
+
+        let tool = RedactMemoryTool::new(mem.clone(), security);
+        let result = tool.execute(json!({"key": "wifi"})).await.unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("supervised mode"));
+        assert_eq!(
+            mem.get("wifi").await.unwrap().unwrap().content,
+            "password is hunter2"
+        );
+    }
+}
@@ -0,0 +1,235 @@
+//! Name-keyed tool set for assembling an agent from library code.
+//!
+//! [`ToolRegistry`] is the supported way to wire a custom tool set into
+//! [`AgentBuilder`](crate::agent::AgentBuilder) without going through
+//! [`all_tools`](super::all_tools) and a full [`Config`](crate::Config). Start
+//! from an empty registry or from the policy-gated defaults, then add, replace,
+//! or remove tools by name:
+//!
+//! ```ignore
+//! use std::sync::Arc;
+//! use zeroclaw::{Agent, SecurityPolicy, ToolRegistry};
+//!
+//! let security = Arc::new(SecurityPolicy::default());
+//! let tools = ToolRegistry::with_defaults(security)
+//!     .without("shell")
+//!     .with(MyLookupTool::new());
+//! let agent = Agent::builder()
+//!     .provider(provider)
+//!     .memory(memory)
+//!     .tool_registry(tools)
+//!     .build()?;
+//! ```
+
+use super::traits::{Tool, ToolSpec};
+use crate::runtime::RuntimeAdapter;
+use crate::security::SecurityPolicy;
+use std::sync::Arc;
+
+/// Ordered set of tools with unique names.
+///
+/// Registration order is preserved because it is the order tool specs are
+/// presented to the model.
+#[derive(Default)]
+pub struct ToolRegistry {
+    tools: Vec<Box<dyn Tool>>,
+}
+
+impl ToolRegistry {
+    /// Create an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start from the default shell and file tools, gated by `security`.
+    pub fn with_defaults(security: Arc<SecurityPolicy>) -> Self {
+        Self::from_tools(super::default_tools(security))
+    }
+
+    /// Start from the default tools for an explicit runtime adapter.
+    pub fn with_defaults_for_runtime(
+        security: Arc<SecurityPolicy>,
+        runtime: Arc<dyn RuntimeAdapter>,
+    ) -> Self {
+        Self::from_tools(super::default_tools_with_runtime(security, runtime))
+    }
+
+    /// Wrap an existing tool list; later duplicates replace earlier ones.
+    pub fn from_tools(tools: Vec<Box<dyn Tool>>) -> Self {
+        let mut registry = Self::new();
+        for tool in tools {
+            registry.insert(tool);
+        }
+        registry
+    }
+
+    /// Add `tool`, replacing any registered tool with the same name.
+    ///
+    /// Returns the replaced tool, if any. The replacement keeps the original
+    /// position so prompt ordering stays stable.
+    pub fn insert(&mut self, tool: Box<dyn Tool>) -> Option<Box<dyn Tool>> {
+        match self.position(tool.name()) {
+            Some(idx) => Some(std::mem::replace(&mut self.tools[idx], tool)),
+            None => {
+                self.tools.push(tool);
+                None
+            }
+        }
+    }
+
+    /// Add `tool`, failing if its name is already registered.
+    pub fn register(&mut self, tool: Box<dyn Tool>) -> anyhow::Result<()> {
+        if self.contains(tool.name()) {
+            anyhow::bail!("tool '{}' is already registered", tool.name());
+        }
+        self.tools.push(tool);
+        Ok(())
+    }
+
+    /// Builder form of [`insert`](Self::insert).
+    pub fn with(mut self, tool: impl Tool + 'static) -> Self {
+        self.insert(Box::new(tool));
+        self
+    }
+
+    /// Builder form of [`remove`](Self::remove); unknown names are ignored.
+    pub fn without(mut self, name: &str) -> Self {
+        self.remove(name);
+        self
+    }
+
+    /// Remove and return the tool named `name`.
+    pub fn remove(&mut self, name: &str) -> Option<Box<dyn Tool>> {
+        let idx = self.position(name)?;
+        Some(self.tools.remove(idx))
+    }
+
+    /// Keep only tools whose names satisfy `keep`.
+    pub fn retain(&mut self, mut keep: impl FnMut(&str) -> bool) {
+        self.tools.retain(|tool| keep(tool.name()));
+    }
+
+    pub fn get(&self, name: &str) -> Option<&dyn Tool> {
+        self.position(name).map(|idx| self.tools[idx].as_ref())
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.position(name).is_some()
+    }
+
+    /// Registered tool names in registration order.
+    pub fn names(&self) -> Vec<&str> {
+        self.tools.iter().map(|tool| tool.name()).collect()
+    }
+
+    /// Specs for every registered tool, in registration order.
+    pub fn specs(&self) -> Vec<ToolSpec> {
+        self.tools.iter().map(|tool| tool.spec()).collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.tools.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tools.is_empty()
+    }
+
+    /// Consume the registry, yielding the tool list `AgentBuilder` expects.
+    pub fn into_tools(self) -> Vec<Box<dyn Tool>> {
+        self.tools
+    }
+
+    fn position(&self, name: &str) -> Option<usize> {
+        self.tools.iter().position(|tool| tool.name() == name)
+    }
+}
+
+impl From<Vec<Box<dyn Tool>>> for ToolRegistry {
+    fn from(tools: Vec<Box<dyn Tool>>) -> Self {
+        Self::from_tools(tools)
+    }
+}
+
+impl std::fmt::Debug for ToolRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ToolRegistry")
+            .field("tools", &self.names())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tools::ToolResult;
+    use async_trait::async_trait;
+
+    struct NamedTool {
+        name: &'static str,
+        description: &'static str,
+    }
+
+    impl NamedTool {
+        fn new(name: &'static str, description: &'static str) -> Self {
+            Self { name, description }
+        }
+    }
+
+    #[async_trait]
+    impl Tool for NamedTool {
+        fn name(&self) -> &str {
+            self.name
+        }
+
+        fn description(&self) -> &str {
+            self.description
+        }
+
+        fn parameters_schema(&self) -> serde_json::Value {
+            serde_json::json!({ "type": "object" })
+        }
+
+        async fn execute(&self, _args: serde_json::Value) -> anyhow::Result<ToolResult> {
+            Ok(ToolResult {
+                success: true,
+                output: self.description.to_string(),
+                error: None,
+            })
+        }
+    }
+
+    #[test]
+    fn insert_replaces_in_place_and_register_rejects_duplicates() {
+        let mut registry = ToolRegistry::new()
+            .with(NamedTool::new("alpha", "first"))
+            .with(NamedTool::new("beta", "second"));
+
+        let replaced = registry.insert(Box::new(NamedTool::new("alpha", "patched")));
+        assert_eq!(replaced.unwrap().description(), "first");
+        assert_eq!(registry.names(), vec!["alpha", "beta"]);
+        assert_eq!(registry.get("alpha").unwrap().description(), "patched");
+
+        let err = registry
+            .register(Box::new(NamedTool::new("beta", "dup")))
+            .unwrap_err();
+        assert!(err.to_string().contains("already registered"));
+        assert_eq!(registry.len(), 2);
+    }
+
+    #[test]
+    fn defaults_can_be_trimmed_by_name() {
+        let security = Arc::new(SecurityPolicy::default());
+        let registry = ToolRegistry::with_defaults(security).without("shell");
+        assert!(!registry.contains("shell"));
+        assert!(registry.contains("file_read"));
+
+        let mut registry = registry;
+        registry.retain(|name| name.starts_with("file_"));
+        assert!(registry
+            .names()
+            .iter()
+            .all(|name| name.starts_with("file_")));
+        assert_eq!(registry.specs().len(), registry.len());
+    }
+}
@@ -0,0 +1,213 @@
+use super::traits::{Tool, ToolResult};
+use crate::security::escalation::{EscalationKind, EscalationStore, MAX_ESCALATION_MINUTES};
+use crate::security::policy::ToolOperation;
+use crate::security::{AutonomyLevel, PolicyHandle, SecurityPolicy};
+use async_trait::async_trait;
+use serde_json::json;
+use std::sync::Arc;
+
+/// Let the agent ask the operator for a higher autonomy level or extra
+/// actions on its rate budget.
+///
+/// The call only files a pending request in `state/escalations.json`; it
+/// changes nothing until an operator grants it with `zeroclaw escalation
+/// grant <id>`, which activates it for a fixed time and records the grant in
+/// the audit log. Filing a request writes state, so it is an `Act` call:
+/// it spends the action budget and is refused in read-only mode, where only
+/// the operator can raise the level.
+pub struct RequestEscalationTool {
+    security: PolicyHandle,
+}
+
+impl RequestEscalationTool {
+    pub fn new(security: Arc<SecurityPolicy>) -> Self {
+        Self {
+            security: security.into(),
+        }
+    }
+
+    fn kind(&self, args: &serde_json::Value) -> Result<EscalationKind, String> {
+        match args.get("kind").and_then(|v| v.as_str()) {
+            Some("autonomy") => {
+                let level: AutonomyLevel = args
+                    .get("level")
+                    .and_then(|v| v.as_str())
+                    .ok_or("'level' is required when kind is 'autonomy'")?
+                    .parse()?;
+                let current = self.security.current().effective_autonomy();
+                if level <= current {
+                    return Err(format!(
+                        "the agent already has {}",
+                        EscalationKind::Autonomy { level: current }
+                    ));
+                }
+                Ok(EscalationKind::Autonomy { level })
+            }
+            Some("actions") => {
+                let extra = args
+                    .get("extra_actions")
+                    .and_then(|v| v.as_u64())
+                    .filter(|extra| *extra > 0)
+                    .ok_or("'extra_actions' must be a positive integer when kind is 'actions'")?;
+                Ok(EscalationKind::Actions {
+                    extra: u32::try_from(extra).unwrap_or(u32::MAX),
+                })
+            }
+            _ => Err("'kind' must be 'autonomy' or 'actions'".into()),
+        }
+    }
+}
+
+fn failed(error: impl Into<String>) -> ToolResult {
+    ToolResult {
+        success: false,
+        output: String::new(),
+        error: Some(error.into()),
+    }
+}
+
+#[async_trait]
+impl Tool for RequestEscalationTool {
+    fn name(&self) -> &str {
+        "request_escalation"
+    }
+
+    fn description(&self) -> &str {
+        "Ask the operator for a temporarily higher autonomy level or extra actions on the hourly rate budget. Files a pending request the operator may grant for a limited time; nothing changes until they do. Use when policy blocks a step the task genuinely needs."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "kind": {
+                    "type": "string",
+                    "enum": ["autonomy", "actions"],
+                    "description": "'autonomy' for a higher autonomy level, 'actions' for extra rate budget"
+                },
+                "level": {
+                    "type": "string",
+                    "enum": ["supervised", "full"],
+                    "description": "Autonomy level to request (kind 'autonomy')"
+                },
+                "extra_actions": {
+                    "type": "integer",
+                    "minimum": 1,
+                    "description": "Extra actions to request (kind 'actions')"
+                },
+                "reason": {
+                    "type": "string",
+                    "description": "Why the escalation is needed; shown to the operator"
+                },
+                "minutes": {
+                    "type": "integer",
+                    "minimum": 1,
+                    "maximum": MAX_ESCALATION_MINUTES,
+                    "description": "How long the escalation is needed; the operator decides the actual length"
+                }
+            },
+            "required": ["kind", "reason"]
+        })
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let security = self.security.current();
+        let Some(state_dir) = security.state_dir.as_deref() else {
+            return Ok(failed(
+                "Escalations are not available: this agent has no policy state directory",
+            ));
+        };
+        if let Err(error) =
+            security.enforce_tool_operation(ToolOperation::Act, "request_escalation")
+        {
+            return Ok(failed(error));
+        }
+        let reason = args
+            .get("reason")
+            .and_then(|v| v.as_str())
+            .map(str::trim)
+            .filter(|reason| !reason.is_empty())
+            .ok_or_else(|| anyhow::anyhow!("Missing 'reason' parameter"))?;
+        let kind = match self.kind(&args) {
+            Ok(kind) => kind,
+            Err(error) => return Ok(failed(error)),
+        };
+        let minutes = args.get("minutes").and_then(|v| v.as_u64()).map(|minutes| {
+            u32::try_from(minutes)
+                .unwrap_or(u32::MAX)
+                .min(MAX_ESCALATION_MINUTES)
+        });
+
+        let store = EscalationStore::new(state_dir);
+        match store.request(kind, reason, minutes) {
+            Ok(request) => Ok(ToolResult {
+                success: true,
+                output: format!(
+                    "Escalation request {} for {} is pending operator approval. It has no effect until granted; continue with what current policy allows.",
+                    request.id, request.kind
+                ),
+                error: None,
+            }),
+            Err(e) => Ok(failed(format!("Failed to file escalation request: {e:#}"))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn files_a_pending_request_that_grants_nothing_yet() {
+        let tmp = tempfile::tempdir().unwrap();
+        let security = Arc::new(SecurityPolicy {
+            workspace_dir: tmp.path().join("workspace"),
+            state_dir: Some(tmp.path().to_path_buf()),
+            ..SecurityPolicy::default()
+        });
+        let tool = RequestEscalationTool::new(security.clone());
+        assert_eq!(tool.operation(), ToolOperation::Act);
+        let budget = security.remaining_actions();
+
+        let result = tool
+            .execute(json!({ "kind": "autonomy", "level": "full", "reason": "need to deploy" }))
+            .await
+            .unwrap();
+        assert!(result.success, "{:?}", result.error);
+        assert!(result.output.contains("autonomy level full"));
+        assert_eq!(security.effective_autonomy(), AutonomyLevel::Supervised);
+        assert_eq!(security.remaining_actions(), budget - 1);
+
+        let id = EscalationStore::new(tmp.path()).list().unwrap()[0]
+            .id
+            .clone();
+        EscalationStore::new(tmp.path()).grant(&id, 10).unwrap();
+        assert_eq!(security.effective_autonomy(), AutonomyLevel::Full);
+
+        let redundant = tool
+            .execute(json!({ "kind": "autonomy", "level": "full", "reason": "again" }))
+            .await
+            .unwrap();
+        assert!(!redundant.success);
+    }
+
+    #[tokio::test]
+    async fn is_refused_in_read_only_mode() {
+        let tmp = tempfile::tempdir().unwrap();
+        let security = Arc::new(SecurityPolicy {
+            autonomy: AutonomyLevel::ReadOnly,
+            workspace_dir: tmp.path().join("workspace"),
+            state_dir: Some(tmp.path().to_path_buf()),
+            ..SecurityPolicy::default()
+        });
+        let tool = RequestEscalationTool::new(security);
+        let result = tool
+            .execute(
+                json!({ "kind": "autonomy", "level": "supervised", "reason": "need to write" }),
+            )
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(EscalationStore::new(tmp.path()).list().unwrap().is_empty());
+    }
+}
@@ -1,7 +1,7 @@
 use super::traits::{Tool, ToolResult};
 use crate::config::Config;
 use crate::cron;
-use crate::security::SecurityPolicy;
+use crate::security::{PolicyHandle, SecurityPolicy};
 use anyhow::Result;
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
@@ -10,13 +10,16 @@ use std::sync::Arc;
 
 /// Tool that lets the agent manage recurring and one-shot scheduled tasks.
 pub struct ScheduleTool {
-    security: Arc<SecurityPolicy>,
+    security: PolicyHandle,
     config: Config,
 }
 
 impl ScheduleTool {
     pub fn new(security: Arc<SecurityPolicy>, config: Config) -> Self {
-        Self { security, config }
+        Self {
+            security: security.into(),
+            config,
+        }
     }
 }
 
@@ -140,6 +143,7 @@ impl Tool for ScheduleTool {
 
 impl ScheduleTool {
     fn enforce_mutation_allowed(&self, action: &str) -> Option<ToolResult> {
+        let security = self.security.current();
         if !self.config.cron.enabled {
             return Some(ToolResult {
                 success: false,
@@ -150,7 +154,7 @@ impl ScheduleTool {
             });
         }
 
-        if !self.security.can_act() {
+        if !security.can_act() {
             return Some(ToolResult {
                 success: false,
                 output: String::new(),
@@ -160,7 +164,7 @@ impl ScheduleTool {
             });
         }
 
-        if !self.security.record_action() {
+        if !security.record_action() {
             return Some(ToolResult {
                 success: false,
                 output: String::new(),
@@ -253,7 +257,11 @@ impl ScheduleTool {
             .filter(|value| !value.trim().is_empty())
             .ok_or_else(|| anyhow::anyhow!("Missing or empty 'command' parameter"))?;
 
-        if let Err(reason) = self.security.validate_command_execution(command, approved) {
+        if let Err(reason) = self
+            .security
+            .current()
+            .validate_command_execution(command, approved)
+        {
             return Ok(ToolResult {
                 success: false,
                 output: String::new(),
@@ -0,0 +1,173 @@
+//! Tool schema export for external orchestrators.
+//!
+//! Renders the agent's tool specs in the two shapes most frameworks accept:
+//! OpenAI function-calling definitions and MCP `tools/list` entries. Only the
+//! schemas leave the process; calls routed back through zeroclaw still go
+//! through each tool's own security checks.
+
+use super::mcp_protocol::McpToolDef;
+use super::traits::ToolSpec;
+use crate::config::Config;
+use anyhow::{bail, Context, Result};
+use console::style;
+
+/// Target schema dialect for `zeroclaw tools export`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ToolSchemaFormat {
+    /// `[{"type": "function", "function": {name, description, parameters}}]`
+    OpenAi,
+    /// `{"tools": [{name, description, inputSchema}]}`
+    Mcp,
+}
+
+impl ToolSchemaFormat {
+    pub fn parse(raw: &str) -> Result<Self> {
+        match raw.trim().to_ascii_lowercase().as_str() {
+            "openai" => Ok(Self::OpenAi),
+            "mcp" => Ok(Self::Mcp),
+            other => bail!("Unknown tool schema format '{other}'. Use 'openai' or 'mcp'."),
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::OpenAi => "openai",
+            Self::Mcp => "mcp",
+        }
+    }
+}
+
+/// Handle `zeroclaw tools <subcommand>`.
+pub fn handle_command(command: crate::ToolCommands, config: &Config) -> Result<()> {
+    match command {
+        crate::ToolCommands::Export { format, output } => {
+            handle_export(config, &format, output.as_deref())
+        }
+    }
+}
+
+fn handle_export(config: &Config, format: &str, output: Option<&str>) -> Result<()> {
+    let format = ToolSchemaFormat::parse(format)?;
+    // Build the agent rather than the raw registry so the export reflects
+    // exactly what the agent would offer: config-gated tools, plugins, and
+    // [agent] allowed_tools/denied_tools filters.
+    let agent = crate::agent::Agent::from_config(config)?;
+    let specs = agent.tool_specs();
+    let schemas = export_tool_specs(specs, format);
+
+    if let Some(path) = output {
+        let document = serde_json::to_string_pretty(&schemas)?;
+        std::fs::write(path, document + "\n").with_context(|| format!("Failed to write {path}"))?;
+        if crate::util::json_output() {
+            return crate::util::print_json(&serde_json::json!({
+                "format": format.as_str(),
+                "tools": specs.len(),
+                "path": path,
+            }));
+        }
+        println!(
+            "{} Exported {} tool schemas ({}) to {path}",
+            style("✓").green().bold(),
+            specs.len(),
+            format.as_str()
+        );
+        Ok(())
+    } else {
+        crate::util::print_json(&schemas)
+    }
+}
+
+/// Render `specs` in the requested format, sorted by tool name so the output
+/// is stable across runs.
+pub fn export_tool_specs(specs: &[ToolSpec], format: ToolSchemaFormat) -> serde_json::Value {
+    let mut specs: Vec<&ToolSpec> = specs.iter().collect();
+    specs.sort_by(|a, b| a.name.cmp(&b.name));
+
+    match format {
+        ToolSchemaFormat::OpenAi => serde_json::Value::Array(
+            specs
+                .into_iter()
+                .map(|spec| {
+                    serde_json::json!({
+                        "type": "function",
+                        "function": {
+                            "name": spec.name,
+                            "description": spec.description,
+                            "parameters": spec.parameters,
+                        }
+                    })
+                })
+                .collect(),
+        ),
+        ToolSchemaFormat::Mcp => {
+            let tools: Vec<McpToolDef> = specs
+                .into_iter()
+                .map(|spec| McpToolDef {
+                    name: spec.name.clone(),
+                    description: Some(spec.description.clone()),
+                    input_schema: spec.parameters.clone(),
+                })
+                .collect();
+            serde_json::json!({ "tools": tools })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn specs() -> Vec<ToolSpec> {
+        vec![
+            ToolSpec {
+                name: "shell".into(),
+                description: "Run a shell command".into(),
+                parameters: serde_json::json!({
+                    "type": "object",
+                    "properties": { "command": { "type": "string" } },
+                    "required": ["command"]
+                }),
+            },
+            ToolSpec {
+                name: "file_read".into(),
+                description: "Read a file".into(),
+                parameters: serde_json::json!({ "type": "object" }),
+            },
+        ]
+    }
+
+    #[test]
+    fn parse_accepts_known_formats() {
+        assert_eq!(
+            ToolSchemaFormat::parse("OpenAI").unwrap(),
+            ToolSchemaFormat::OpenAi
+        );
+        assert_eq!(
+            ToolSchemaFormat::parse("mcp").unwrap(),
+            ToolSchemaFormat::Mcp
+        );
+        let err = ToolSchemaFormat::parse("langchain").unwrap_err();
+        assert!(err.to_string().contains("'openai' or 'mcp'"));
+    }
+
+    #[test]
+    fn openai_export_wraps_functions_sorted_by_name() {
+        let out = export_tool_specs(&specs(), ToolSchemaFormat::OpenAi);
+        let items = out.as_array().unwrap();
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0]["type"], "function");
+        assert_eq!(items[0]["function"]["name"], "file_read");
+        assert_eq!(items[1]["function"]["name"], "shell");
+        assert_eq!(items[1]["function"]["parameters"]["required"][0], "command");
+    }
+
+    #[test]
+    fn mcp_export_uses_input_schema_key() {
+        let out = export_tool_specs(&specs(), ToolSchemaFormat::Mcp);
+        let tools = out["tools"].as_array().unwrap();
+        assert_eq!(tools[1]["name"], "shell");
+        assert_eq!(tools[1]["description"], "Run a shell command");
+        assert_eq!(tools[1]["inputSchema"]["type"], "object");
+        assert!(tools[1].get("parameters").is_none());
+    }
+}
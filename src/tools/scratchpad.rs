@@ -0,0 +1,347 @@
+//! Session-scoped scratchpad for intermediate working state.
+//!
+//! Provides a `scratchpad` tool: a small key/value store per conversation
+//! session where the agent can stash partial results, drafts, or notes to
+//! self between tool calls. Unlike memory, nothing here is persisted or
+//! recalled later. Pads live in process memory, keyed by the session id of
+//! the calling [`ToolExecutionContext`], and are dropped when the channel
+//! conversation is reset (`/new`) or the process exits.
+
+use crate::security::{policy::ToolOperation, PolicyHandle, SecurityPolicy};
+use crate::tools::traits::{Tool, ToolExecutionContext, ToolResult};
+use async_trait::async_trait;
+use parking_lot::Mutex;
+use serde_json::json;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, OnceLock};
+
+/// Pad used by calls without a session (CLI runs, tests).
+const DEFAULT_SESSION: &str = "default";
+/// Most entries one session's pad may hold.
+const MAX_ENTRIES_PER_SESSION: usize = 100;
+/// Largest value accepted, in bytes.
+const MAX_VALUE_BYTES: usize = 16 * 1024;
+
+// ── Storage ──────────────────────────────────────────────────────────────
+
+/// Scratchpads of every live session.
+#[derive(Default)]
+pub struct Scratchpads {
+    sessions: Mutex<HashMap<String, BTreeMap<String, String>>>,
+}
+
+impl Scratchpads {
+    /// Process-wide pads shared by the tool registry and the channel
+    /// runtime, which clears a session's pad when its conversation resets.
+    pub fn shared() -> Arc<Self> {
+        static SHARED: OnceLock<Arc<Scratchpads>> = OnceLock::new();
+        SHARED.get_or_init(|| Arc::new(Self::default())).clone()
+    }
+
+    /// Drop the pad of `session_id`. Returns whether it held anything.
+    pub fn clear_session(&self, session_id: &str) -> bool {
+        self.sessions.lock().remove(session_id).is_some()
+    }
+}
+
+// ── Tool ─────────────────────────────────────────────────────────────────
+
+pub struct ScratchpadTool {
+    pads: Arc<Scratchpads>,
+    security: PolicyHandle,
+}
+
+impl ScratchpadTool {
+    pub fn new(pads: Arc<Scratchpads>, security: Arc<SecurityPolicy>) -> Self {
+        Self {
+            pads,
+            security: security.into(),
+        }
+    }
+
+    fn session() -> String {
+        ToolExecutionContext::current()
+            .and_then(|context| context.session_id)
+            .unwrap_or_else(|| DEFAULT_SESSION.to_string())
+    }
+
+    fn handle_set(&self, key: &str, value: &str) -> ToolResult {
+        if value.len() > MAX_VALUE_BYTES {
+            return failed(format!(
+                "Value is {} bytes; the scratchpad holds at most {MAX_VALUE_BYTES} bytes per key",
+                value.len()
+            ));
+        }
+        let session = Self::session();
+        let mut sessions = self.pads.sessions.lock();
+        let pad = sessions.entry(session).or_default();
+        if !pad.contains_key(key) && pad.len() >= MAX_ENTRIES_PER_SESSION {
+            return failed(format!(
+                "Scratchpad is full ({MAX_ENTRIES_PER_SESSION} keys); delete or clear entries first"
+            ));
+        }
+        pad.insert(key.to_string(), value.to_string());
+        ok(format!("Saved scratchpad key '{key}'"))
+    }
+
+    fn handle_get(&self, key: &str) -> ToolResult {
+        let sessions = self.pads.sessions.lock();
+        match sessions.get(&Self::session()).and_then(|pad| pad.get(key)) {
+            Some(value) => ok(value.clone()),
+            None => failed(format!("No scratchpad key '{key}'")),
+        }
+    }
+
+    fn handle_list(&self) -> ToolResult {
+        let sessions = self.pads.sessions.lock();
+        let Some(pad) = sessions.get(&Self::session()).filter(|pad| !pad.is_empty()) else {
+            return ok("Scratchpad is empty.".into());
+        };
+        let entries: Vec<serde_json::Value> = pad
+            .iter()
+            .map(|(key, value)| json!({ "key": key, "bytes": value.len() }))
+            .collect();
+        ok(serde_json::to_string_pretty(&json!({ "entries": entries }))
+            .unwrap_or_else(|_| "[]".into()))
+    }
+
+    fn handle_delete(&self, key: &str) -> ToolResult {
+        let mut sessions = self.pads.sessions.lock();
+        let removed = sessions
+            .get_mut(&Self::session())
+            .and_then(|pad| pad.remove(key))
+            .is_some();
+        if removed {
+            ok(format!("Deleted scratchpad key '{key}'"))
+        } else {
+            failed(format!("No scratchpad key '{key}'"))
+        }
+    }
+
+    fn handle_clear(&self) -> ToolResult {
+        self.pads.clear_session(&Self::session());
+        ok("Scratchpad cleared.".into())
+    }
+}
+
+fn ok(output: String) -> ToolResult {
+    ToolResult {
+        success: true,
+        output,
+        error: None,
+    }
+}
+
+fn failed(error: impl Into<String>) -> ToolResult {
+    ToolResult {
+        success: false,
+        output: String::new(),
+        error: Some(error.into()),
+    }
+}
+
+#[async_trait]
+impl Tool for ScratchpadTool {
+    fn name(&self) -> &str {
+        "scratchpad"
+    }
+
+    fn description(&self) -> &str {
+        "Temporary key/value notes for this session only: stash intermediate results or working state between steps. Cleared when the session ends and never saved to long-term memory. Use memory_store for anything worth remembering."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "action": {
+                    "type": "string",
+                    "enum": ["set", "get", "list", "delete", "clear"],
+                    "description": "'set' stores a value, 'get' reads one, 'list' shows keys, 'delete' removes a key, 'clear' empties the pad"
+                },
+                "key": {
+                    "type": "string",
+                    "description": "set/get/delete: the scratchpad key"
+                },
+                "value": {
+                    "type": "string",
+                    "description": "set: the value to store (max 16 KiB)"
+                }
+            },
+            "required": ["action"]
+        })
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let action = args
+            .get("action")
+            .and_then(serde_json::Value::as_str)
+            .unwrap_or_default();
+        let key = args
+            .get("key")
+            .and_then(serde_json::Value::as_str)
+            .map(str::trim)
+            .filter(|key| !key.is_empty());
+
+        if matches!(action, "set" | "delete" | "clear") {
+            if let Err(error) = self
+                .security
+                .current()
+                .enforce_tool_operation(ToolOperation::Act, "scratchpad")
+            {
+                return Ok(failed(error));
+            }
+        }
+
+        let result = match (action, key) {
+            ("set", Some(key)) => {
+                let value = args
+                    .get("value")
+                    .and_then(serde_json::Value::as_str)
+                    .unwrap_or_default();
+                self.handle_set(key, value)
+            }
+            ("get", Some(key)) => self.handle_get(key),
+            ("delete", Some(key)) => self.handle_delete(key),
+            ("set" | "get" | "delete", None) => {
+                failed(format!("Parameter 'key' is required for {action}"))
+            }
+            ("list", _) => self.handle_list(),
+            ("clear", _) => self.handle_clear(),
+            (other, _) => failed(format!(
+                "Unknown action '{other}'. Valid: set, get, list, delete, clear"
+            )),
+        };
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::security::AutonomyLevel;
+
+    fn tool(pads: &Arc<Scratchpads>) -> ScratchpadTool {
+        ScratchpadTool::new(pads.clone(), Arc::new(SecurityPolicy::default()))
+    }
+
+    fn in_session(session: &str) -> ToolExecutionContext {
+        ToolExecutionContext {
+            session_id: Some(session.into()),
+            ..ToolExecutionContext::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn set_get_list_delete() {
+        let pads = Arc::new(Scratchpads::default());
+        let tool = tool(&pads);
+        let result = tool
+            .execute(json!({"action": "set", "key": "draft", "value": "step 1 done"}))
+            .await
+            .unwrap();
+        assert!(result.success, "{:?}", result.error);
+
+        let result = tool
+            .execute(json!({"action": "get", "key": "draft"}))
+            .await
+            .unwrap();
+        assert_eq!(result.output, "step 1 done");
+
+        let result = tool.execute(json!({"action": "list"})).await.unwrap();
+        assert!(result.output.contains("\"draft\""));
+
+        let result = tool
+            .execute(json!({"action": "delete", "key": "draft"}))
+            .await
+            .unwrap();
+        assert!(result.success);
+        let result = tool
+            .execute(json!({"action": "get", "key": "draft"}))
+            .await
+            .unwrap();
+        assert!(!result.success);
+    }
+
+    #[tokio::test]
+    async fn sessions_are_isolated_and_cleared_independently() {
+        let pads = Arc::new(Scratchpads::default());
+        let tool = tool(&pads);
+        for session in ["telegram_alice", "telegram_bob"] {
+            in_session(session)
+                .scope(tool.execute(json!({"action": "set", "key": "who", "value": session})))
+                .await
+                .unwrap();
+        }
+
+        let get = json!({"action": "get", "key": "who"});
+        let alice = in_session("telegram_alice")
+            .scope(tool.execute(get.clone()))
+            .await
+            .unwrap();
+        assert_eq!(alice.output, "telegram_alice");
+        assert!(!tool.execute(get.clone()).await.unwrap().success);
+
+        assert!(pads.clear_session("telegram_alice"));
+        let alice = in_session("telegram_alice")
+            .scope(tool.execute(get.clone()))
+            .await
+            .unwrap();
+        assert!(!alice.success);
+        let bob = in_session("telegram_bob")
+            .scope(tool.execute(get))
+            .await
+            .unwrap();
+        assert_eq!(bob.output, "telegram_bob");
+    }
+
+    #[tokio::test]
+    async fn rejects_oversized_values_and_full_pads() {
+        let pads = Arc::new(Scratchpads::default());
+        let tool = tool(&pads);
+        let big = "x".repeat(MAX_VALUE_BYTES + 1);
+        let result = tool
+            .execute(json!({"action": "set", "key": "big", "value": big}))
+            .await
+            .unwrap();
+        assert!(!result.success);
+
+        // Fill the pad directly; going through the tool would spend the
+        // hourly action budget before reaching the entry cap.
+        {
+            let mut sessions = pads.sessions.lock();
+            let pad = sessions.entry(DEFAULT_SESSION.into()).or_default();
+            for i in 0..MAX_ENTRIES_PER_SESSION {
+                pad.insert(format!("k{i}"), "v".into());
+            }
+        }
+        let result = tool
+            .execute(json!({"action": "set", "key": "one_more", "value": "v"}))
+            .await
+            .unwrap();
+        assert!(result.error.unwrap().contains("full"));
+    }
+
+    #[tokio::test]
+    async fn writes_blocked_in_readonly_mode() {
+        let pads = Arc::new(Scratchpads::default());
+        let tool = ScratchpadTool::new(
+            pads,
+            Arc::new(SecurityPolicy {
+                autonomy: AutonomyLevel::ReadOnly,
+                ..SecurityPolicy::default()
+            }),
+        );
+        let result = tool
+            .execute(json!({"action": "set", "key": "k", "value": "v"}))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(
+            tool.execute(json!({"action": "list"}))
+                .await
+                .unwrap()
+                .success
+        );
+    }
+}
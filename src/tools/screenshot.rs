@@ -1,5 +1,5 @@
 use super::traits::{Tool, ToolResult};
-use crate::security::SecurityPolicy;
+use crate::security::{PolicyHandle, SecurityPolicy};
 use async_trait::async_trait;
 use serde_json::json;
 use std::fmt::Write;
@@ -18,12 +18,14 @@ const MAX_BASE64_BYTES: usize = 2_097_152;
 /// macOS: `screencapture`
 /// Linux: tries `gnome-screenshot`, `scrot`, `import` (`ImageMagick`) in order.
 pub struct ScreenshotTool {
-    security: Arc<SecurityPolicy>,
+    security: PolicyHandle,
 }
 
 impl ScreenshotTool {
     pub fn new(security: Arc<SecurityPolicy>) -> Self {
-        Self { security }
+        Self {
+            security: security.into(),
+        }
     }
 
     fn sanitize_output_filename(filename: &str, fallback: &str) -> String {
@@ -44,11 +46,12 @@ impl ScreenshotTool {
 
     /// Resolve screenshot output path and block writes through symlink targets.
     async fn resolve_output_path_for_write(&self, filename: &str) -> anyhow::Result<PathBuf> {
-        tokio::fs::create_dir_all(&self.security.workspace_dir).await?;
+        let security = self.security.current();
+        tokio::fs::create_dir_all(&security.workspace_dir).await?;
 
-        let workspace_root = tokio::fs::canonicalize(&self.security.workspace_dir)
+        let workspace_root = tokio::fs::canonicalize(&security.workspace_dir)
             .await
-            .unwrap_or_else(|_| self.security.workspace_dir.clone());
+            .unwrap_or_else(|_| security.workspace_dir.clone());
         let output_path = workspace_root.join(filename);
 
         // Parent must remain inside workspace after resolution.
@@ -56,11 +59,10 @@ impl ScreenshotTool {
             .parent()
             .ok_or_else(|| anyhow::anyhow!("Invalid screenshot output path"))?;
         let resolved_parent = tokio::fs::canonicalize(parent).await?;
-        if !self.security.is_resolved_path_allowed(&resolved_parent) {
+        if !security.is_resolved_path_allowed(&resolved_parent) {
             anyhow::bail!(
                 "{}",
-                self.security
-                    .resolved_path_violation_message(&resolved_parent)
+                security.resolved_path_violation_message(&resolved_parent)
             );
         }
 
@@ -174,11 +176,11 @@ impl ScreenshotTool {
                 continue;
             }
             let program = cmd_args.remove(0);
+            let mut cmd = tokio::process::Command::new(&program);
+            self.security.current().env_filter().apply(cmd.as_std_mut());
             let result = tokio::time::timeout(
                 Duration::from_secs(SCREENSHOT_TIMEOUT_SECS),
-                tokio::process::Command::new(&program)
-                    .args(&cmd_args)
-                    .output(),
+                cmd.args(&cmd_args).output(),
             )
             .await;
 
@@ -324,7 +326,7 @@ impl Tool for ScreenshotTool {
     }
 
     async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
-        if !self.security.can_act() {
+        if !self.security.current().can_act() {
             return Ok(ToolResult {
                 success: false,
                 output: String::new(),
@@ -1,10 +1,10 @@
+use super::dry_run;
 use super::traits::{Tool, ToolResult};
 use crate::runtime::RuntimeAdapter;
-use crate::security::SecurityPolicy;
 use crate::security::SyscallAnomalyDetector;
+use crate::security::{PolicyHandle, SecurityPolicy};
 use async_trait::async_trait;
 use serde_json::json;
-use std::collections::HashSet;
 use std::sync::Arc;
 use std::time::Duration;
 
@@ -12,31 +12,6 @@ use std::time::Duration;
 const SHELL_TIMEOUT_SECS: u64 = 60;
 /// Maximum output size in bytes (1MB).
 const MAX_OUTPUT_BYTES: usize = 1_048_576;
-/// Environment variables safe to pass to shell commands.
-/// Only functional variables are included — never API keys or secrets.
-const SAFE_ENV_VARS: &[&str] = &[
-    "PATH",
-    "HOME",
-    "TERM",
-    "LANG",
-    "LC_ALL",
-    "LC_CTYPE",
-    "USER",
-    "SHELL",
-    "TMPDIR",
-    // Windows runtime essentials when env is cleared before shell spawn.
-    "USERPROFILE",
-    "APPDATA",
-    "LOCALAPPDATA",
-    "PROGRAMDATA",
-    "SYSTEMROOT",
-    "WINDIR",
-    "COMSPEC",
-    "TEMP",
-    "TMP",
-    "PATHEXT",
-];
-
 fn truncate_utf8_to_max_bytes(text: &mut String, max_bytes: usize) {
     if text.len() <= max_bytes {
         return;
@@ -50,7 +25,7 @@ fn truncate_utf8_to_max_bytes(text: &mut String, max_bytes: usize) {
 
 /// Shell command execution tool with sandboxing
 pub struct ShellTool {
-    security: Arc<SecurityPolicy>,
+    security: PolicyHandle,
     runtime: Arc<dyn RuntimeAdapter>,
     syscall_detector: Option<Arc<SyscallAnomalyDetector>>,
 }
@@ -66,41 +41,13 @@ impl ShellTool {
         syscall_detector: Option<Arc<SyscallAnomalyDetector>>,
     ) -> Self {
         Self {
-            security,
+            security: security.into(),
             runtime,
             syscall_detector,
         }
     }
 }
 
-fn is_valid_env_var_name(name: &str) -> bool {
-    let mut chars = name.chars();
-    match chars.next() {
-        Some(first) if first.is_ascii_alphabetic() || first == '_' => {}
-        _ => return false,
-    }
-    chars.all(|ch| ch.is_ascii_alphanumeric() || ch == '_')
-}
-
-pub(super) fn collect_allowed_shell_env_vars(security: &SecurityPolicy) -> Vec<String> {
-    let mut out = Vec::new();
-    let mut seen = HashSet::new();
-    for key in SAFE_ENV_VARS
-        .iter()
-        .copied()
-        .chain(security.shell_env_passthrough.iter().map(|s| s.as_str()))
-    {
-        let candidate = key.trim();
-        if candidate.is_empty() || !is_valid_env_var_name(candidate) {
-            continue;
-        }
-        if seen.insert(candidate.to_string()) {
-            out.push(candidate.to_string());
-        }
-    }
-    out
-}
-
 fn extract_command_argument(args: &serde_json::Value) -> Option<String> {
     if let Some(command) = args
         .get("command")
@@ -158,6 +105,10 @@ impl Tool for ShellTool {
                     "type": "boolean",
                     "description": "Set true to explicitly approve medium/high-risk commands in supervised mode",
                     "default": false
+                },
+                "working_dir": {
+                    "type": "string",
+                    "description": "Directory to run the command in, relative to the workspace (default: the workspace)"
                 }
             },
             "required": ["command"]
@@ -166,14 +117,24 @@ impl Tool for ShellTool {
 
     #[allow(clippy::incompatible_msrv)]
     async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let security = self.security.current();
         let command = extract_command_argument(&args)
             .ok_or_else(|| anyhow::anyhow!("Missing 'command' parameter"))?;
         let approved = args
             .get("approved")
             .and_then(|v| v.as_bool())
             .unwrap_or(false);
+        let working_dir = args.get("working_dir").and_then(|v| v.as_str());
 
-        if self.security.is_rate_limited() {
+        if let Err(denial) = security.check_denial_cooldown("shell") {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(denial.to_string()),
+            });
+        }
+
+        if security.is_rate_limited() {
             return Ok(ToolResult {
                 success: false,
                 output: String::new(),
@@ -181,39 +142,50 @@ impl Tool for ShellTool {
             });
         }
 
-        match self.security.validate_command_execution(&command, approved) {
-            Ok(_) => {}
+        let validated = match security
+            .validate_command(&command, working_dir, approved)
+            .await
+        {
+            Ok(validated) => validated,
             Err(reason) => {
+                security.record_denial("shell", &reason);
                 return Ok(ToolResult {
                     success: false,
                     output: String::new(),
                     error: Some(reason),
                 });
             }
-        }
+        };
 
-        if let Some(path) = self.security.forbidden_path_argument(&command) {
+        if let Some(path) = security.forbidden_path_argument(&command) {
+            let reason = format!("Path blocked by security policy: {path}");
+            security.record_denial("shell", &reason);
             return Ok(ToolResult {
                 success: false,
                 output: String::new(),
-                error: Some(format!("Path blocked by security policy: {path}")),
+                error: Some(reason),
             });
         }
 
-        if !self.security.record_action() {
+        if !security.record_action() {
             return Ok(ToolResult {
                 success: false,
                 output: String::new(),
                 error: Some("Rate limit exceeded: action budget exhausted".into()),
             });
         }
+        security.clear_denials("shell");
+
+        if security.dry_run {
+            return Ok(dry_run::simulated(format!("Would run: {command}")));
+        }
 
         // Execute with timeout to prevent hanging commands.
         // Clear the environment to prevent leaking API keys and other secrets
         // (CWE-200), then re-add only safe, functional variables.
         let mut cmd = match self
             .runtime
-            .build_shell_command(&command, &self.security.workspace_dir)
+            .build_shell_command(&command, &validated.working_dir)
         {
             Ok(cmd) => cmd,
             Err(e) => {
@@ -224,16 +196,12 @@ impl Tool for ShellTool {
                 });
             }
         };
-        cmd.env_clear();
-
-        for var in collect_allowed_shell_env_vars(&self.security) {
-            if let Ok(val) = std::env::var(&var) {
-                cmd.env(&var, val);
-            }
-        }
+        security.env_filter().apply(cmd.as_std_mut());
 
-        let result =
-            tokio::time::timeout(Duration::from_secs(SHELL_TIMEOUT_SECS), cmd.output()).await;
+        let timeout = validated
+            .max_runtime
+            .unwrap_or(Duration::from_secs(SHELL_TIMEOUT_SECS));
+        let result = tokio::time::timeout(timeout, cmd.output()).await;
 
         match result {
             Ok(Ok(output)) => {
@@ -278,7 +246,8 @@ impl Tool for ShellTool {
                 success: false,
                 output: String::new(),
                 error: Some(format!(
-                    "Command timed out after {SHELL_TIMEOUT_SECS}s and was killed"
+                    "Command timed out after {}s and was killed",
+                    timeout.as_secs()
                 )),
             }),
         }
@@ -290,6 +259,7 @@ mod tests {
     use super::*;
     use crate::config::{AuditConfig, SyscallAnomalyConfig};
     use crate::runtime::{NativeRuntime, RuntimeAdapter};
+    use crate::security::env_filter::SAFE_ENV_VARS;
     use crate::security::{AutonomyLevel, SecurityPolicy, SyscallAnomalyDetector};
     use tempfile::TempDir;
 
@@ -373,6 +343,30 @@ mod tests {
         assert!(result.error.is_none());
     }
 
+    #[tokio::test]
+    async fn shell_runs_in_requested_working_dir() {
+        let tmp = TempDir::new().unwrap();
+        std::fs::create_dir(tmp.path().join("sub")).unwrap();
+        let security = Arc::new(SecurityPolicy {
+            workspace_dir: tmp.path().to_path_buf(),
+            ..SecurityPolicy::default()
+        });
+        let tool = ShellTool::new(security, test_runtime());
+
+        let result = tool
+            .execute(json!({"command": "pwd", "working_dir": "sub"}))
+            .await
+            .unwrap();
+        assert!(result.success);
+        assert!(result.output.trim().ends_with("sub"));
+
+        let result = tool
+            .execute(json!({"command": "pwd", "working_dir": "missing"}))
+            .await
+            .unwrap();
+        assert!(!result.success);
+    }
+
     #[tokio::test]
     async fn shell_executes_command_from_cmd_alias() {
         let tool = ShellTool::new(test_security(AutonomyLevel::Supervised), test_runtime());
@@ -638,7 +632,7 @@ mod tests {
             ],
             ..SecurityPolicy::default()
         };
-        let vars = collect_allowed_shell_env_vars(&security);
+        let vars = security.env_filter().allowed_vars();
         assert!(vars.contains(&"VALID_NAME".to_string()));
         assert!(vars.contains(&"ALSO_VALID".to_string()));
         assert!(!vars.contains(&"BAD-NAME".to_string()));
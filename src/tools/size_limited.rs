@@ -0,0 +1,134 @@
+//! Size limits on tool arguments and results (`[autonomy] max_tool_input_bytes`
+//! / `max_tool_output_bytes`).
+//!
+//! [`all_tools_with_runtime`](super::all_tools_with_runtime) wraps every tool
+//! in a [`SizeLimitedTool`] when either limit is set. Oversized arguments are
+//! refused before the tool runs, so megabyte payloads never reach memory or
+//! disk; oversized output and error text are cut to the limit and end with a
+//! truncation marker, so they never flood the model context.
+
+use super::traits::{delegate_tool_metadata, Tool, ToolResult};
+use crate::security::policy::PolicyDenial;
+use crate::security::{PolicyHandle, SecurityPolicy};
+use crate::util::floor_utf8_char_boundary;
+use async_trait::async_trait;
+use std::sync::Arc;
+
+/// Wraps a tool so its arguments and results respect the policy's size limits.
+pub struct SizeLimitedTool {
+    inner: Box<dyn Tool>,
+    security: PolicyHandle,
+}
+
+impl SizeLimitedTool {
+    pub fn new(inner: Box<dyn Tool>, security: Arc<SecurityPolicy>) -> Self {
+        Self {
+            inner,
+            security: security.into(),
+        }
+    }
+}
+
+/// Cut `text` to at most `max_bytes` bytes plus a marker saying how much was
+/// dropped. `0` leaves it untouched.
+fn truncate(text: &mut String, max_bytes: usize) {
+    if max_bytes == 0 || text.len() <= max_bytes {
+        return;
+    }
+    let cutoff = floor_utf8_char_boundary(text, max_bytes);
+    let dropped = text.len() - cutoff;
+    text.truncate(cutoff);
+    text.push_str(&format!(
+        "\n[truncated {dropped} bytes: output exceeds autonomy.max_tool_output_bytes ({max_bytes})]"
+    ));
+}
+
+/// Cut `result`'s output and error text to `max_bytes` each; `0` leaves
+/// them untouched.
+pub(crate) fn cap_result(result: &mut ToolResult, max_bytes: usize) {
+    truncate(&mut result.output, max_bytes);
+    if let Some(error) = result.error.as_mut() {
+        truncate(error, max_bytes);
+    }
+}
+
+#[async_trait]
+impl Tool for SizeLimitedTool {
+    delegate_tool_metadata!();
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let security = self.security.current();
+        let max_input = security.max_tool_input_bytes;
+        if max_input > 0 {
+            let size = serde_json::to_vec(&args).map_or(0, |bytes| bytes.len());
+            if size > max_input {
+                let denial = PolicyDenial::InputTooLarge {
+                    tool: self.inner.name().to_string(),
+                    size,
+                    limit: max_input,
+                }
+                .surface();
+                return Ok(ToolResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some(denial.to_string()),
+                });
+            }
+        }
+
+        let mut result = self.inner.execute(args).await?;
+        cap_result(&mut result, security.max_tool_output_bytes);
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tools::test_support::StubTool;
+
+    fn limited(max_input: usize, max_output: usize, output: &str) -> SizeLimitedTool {
+        let security = Arc::new(SecurityPolicy {
+            max_tool_input_bytes: max_input,
+            max_tool_output_bytes: max_output,
+            ..SecurityPolicy::default()
+        });
+        let stub = StubTool::new("echo").returning(ToolResult {
+            success: true,
+            output: output.into(),
+            error: None,
+        });
+        SizeLimitedTool::new(Box::new(stub), security)
+    }
+
+    #[tokio::test]
+    async fn oversized_arguments_are_refused() {
+        let tool = limited(32, 0, "ok");
+        let result = tool
+            .execute(serde_json::json!({ "text": "x".repeat(64) }))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result
+            .error
+            .unwrap()
+            .contains("over autonomy.max_tool_input_bytes (32)"));
+
+        let ok = tool
+            .execute(serde_json::json!({ "text": "hi" }))
+            .await
+            .unwrap();
+        assert_eq!(ok.output, "ok");
+    }
+
+    #[tokio::test]
+    async fn oversized_output_is_truncated_on_a_char_boundary() {
+        let tool = limited(0, 5, &"é".repeat(12));
+        let result = tool.execute(serde_json::json!({})).await.unwrap();
+        assert!(result.success);
+        assert!(result.output.starts_with("éé\n[truncated 20 bytes"));
+        assert!(result
+            .output
+            .ends_with("output exceeds autonomy.max_tool_output_bytes (5)]"));
+    }
+}
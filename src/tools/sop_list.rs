@@ -6,6 +6,7 @@ use serde_json::json;
 
 use super::traits::{Tool, ToolResult};
 use crate::sop::SopEngine;
+use crate::security::policy::ToolOperation;
 
 /// Lists all loaded SOPs with their triggers, priority, step count, and active runs.
 pub struct SopListTool {
@@ -24,6 +25,10 @@ impl Tool for SopListTool {
         "sop_list"
     }
 
+    fn operation(&self) -> ToolOperation {
+        ToolOperation::Read
+    }
+
     fn description(&self) -> &str {
         "List all loaded Standard Operating Procedures (SOPs) with their triggers, priority, step count, and active run count. Optionally filter by name or priority."
     }
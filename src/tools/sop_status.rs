@@ -6,6 +6,7 @@ use serde_json::json;
 
 use super::traits::{Tool, ToolResult};
 use crate::sop::{SopEngine, SopMetricsCollector};
+use crate::security::policy::ToolOperation;
 
 /// Query SOP execution status — active runs, finished runs, or a specific run by ID.
 pub struct SopStatusTool {
@@ -96,6 +97,10 @@ impl Tool for SopStatusTool {
         "sop_status"
     }
 
+    fn operation(&self) -> ToolOperation {
+        ToolOperation::Read
+    }
+
     fn description(&self) -> &str {
         "Query SOP execution status. Provide run_id for a specific run, or sop_name to list runs for that SOP. With no arguments, shows all active runs."
     }
@@ -5,6 +5,7 @@
 
 use super::subagent_registry::SubAgentRegistry;
 use super::traits::{Tool, ToolResult};
+use crate::security::policy::ToolOperation;
 use async_trait::async_trait;
 use serde_json::json;
 use std::sync::Arc;
@@ -29,6 +30,10 @@ impl Tool for SubAgentListTool {
         "subagent_list"
     }
 
+    fn operation(&self) -> ToolOperation {
+        ToolOperation::Read
+    }
+
     fn description(&self) -> &str {
         "List running and completed background sub-agents. \
          Filter by status: running, completed, failed, killed, or all (default)."
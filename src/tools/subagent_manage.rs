@@ -6,7 +6,7 @@
 use super::subagent_registry::SubAgentRegistry;
 use super::traits::{Tool, ToolResult};
 use crate::security::policy::ToolOperation;
-use crate::security::SecurityPolicy;
+use crate::security::{PolicyHandle, SecurityPolicy};
 use async_trait::async_trait;
 use serde_json::json;
 use std::sync::Arc;
@@ -14,13 +14,16 @@ use std::sync::Arc;
 /// Tool that manages running sub-agent sessions — check status or kill.
 pub struct SubAgentManageTool {
     registry: Arc<SubAgentRegistry>,
-    security: Arc<SecurityPolicy>,
+    security: PolicyHandle,
 }
 
 impl SubAgentManageTool {
     /// pub fn new.
     pub fn new(registry: Arc<SubAgentRegistry>, security: Arc<SecurityPolicy>) -> Self {
-        Self { registry, security }
+        Self {
+            registry,
+            security: security.into(),
+        }
     }
 }
 
@@ -151,12 +154,13 @@ impl SubAgentManageTool {
         // Kill is a write operation — enforce security
         if let Err(error) = self
             .security
+            .current()
             .enforce_tool_operation(ToolOperation::Act, "subagent_manage:kill")
         {
             return Ok(ToolResult {
                 success: false,
                 output: String::new(),
-                error: Some(error),
+                error: Some(error.to_string()),
             });
         }
 
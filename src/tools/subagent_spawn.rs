@@ -6,14 +6,15 @@
 
 use super::agent_load_tracker::AgentLoadTracker;
 use super::agent_selection::{select_agent_with_load, AgentSelectionPolicy};
+use super::gated::CapabilityGatedTool;
 use super::orchestration_settings::load_orchestration_settings;
 use super::subagent_registry::{SubAgentRegistry, SubAgentSession, SubAgentStatus};
-use super::traits::{Tool, ToolResult};
+use super::traits::{delegate_tool_metadata, Tool, ToolResult};
 use crate::config::{DelegateAgentConfig, SubAgentsConfig};
 use crate::observability::traits::{Observer, ObserverEvent, ObserverMetric};
 use crate::providers::{self, ChatMessage, Provider};
 use crate::security::policy::ToolOperation;
-use crate::security::SecurityPolicy;
+use crate::security::{CapabilityToken, PolicyHandle, SecurityPolicy};
 use async_trait::async_trait;
 use chrono::Utc;
 use serde_json::json;
@@ -30,7 +31,7 @@ const SPAWN_TIMEOUT_SECS: u64 = 300;
 /// in the shared [`SubAgentRegistry`].
 pub struct SubAgentSpawnTool {
     agents: Arc<HashMap<String, DelegateAgentConfig>>,
-    security: Arc<SecurityPolicy>,
+    security: PolicyHandle,
     fallback_credential: Option<String>,
     provider_runtime_options: providers::ProviderRuntimeOptions,
     registry: Arc<SubAgentRegistry>,
@@ -63,7 +64,7 @@ impl SubAgentSpawnTool {
 
         Self {
             agents: Arc::new(agents),
-            security,
+            security: security.into(),
             fallback_credential,
             provider_runtime_options,
             registry,
@@ -226,12 +227,13 @@ impl Tool for SubAgentSpawnTool {
         // Security enforcement: spawn is a write operation
         if let Err(error) = self
             .security
+            .current()
             .enforce_tool_operation(ToolOperation::Act, "subagent_spawn")
         {
             return Ok(ToolResult {
                 success: false,
                 output: String::new(),
-                error: Some(error),
+                error: Some(error.to_string()),
             });
         }
 
@@ -315,6 +317,7 @@ impl Tool for SubAgentSpawnTool {
         // Determine if agentic mode
         let is_agentic = agent_config.agentic;
         let parent_tools = self.parent_tools.clone();
+        let security = self.security.current();
         let multimodal_config = self.multimodal_config.clone();
         let mut load_lease = self.load_tracker.start(&agent_name_owned);
 
@@ -358,6 +361,7 @@ impl Tool for SubAgentSpawnTool {
                     &*provider,
                     &full_prompt,
                     &parent_tools,
+                    &security,
                     &multimodal_config,
                 )
                 .await
@@ -480,17 +484,7 @@ impl ToolArcRef {
 
 #[async_trait]
 impl Tool for ToolArcRef {
-    fn name(&self) -> &str {
-        self.inner.name()
-    }
-
-    fn description(&self) -> &str {
-        self.inner.description()
-    }
-
-    fn parameters_schema(&self) -> serde_json::Value {
-        self.inner.parameters_schema()
-    }
+    delegate_tool_metadata!();
 
     async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
         self.inner.execute(args).await
@@ -516,6 +510,7 @@ async fn run_agentic_background(
     provider: &dyn Provider,
     full_prompt: &str,
     parent_tools: &[Arc<dyn Tool>],
+    security: &Arc<SecurityPolicy>,
     multimodal_config: &crate::config::MultimodalConfig,
 ) -> anyhow::Result<ToolResult> {
     if agent_config.allowed_tools.is_empty() {
@@ -535,6 +530,25 @@ async fn run_agentic_background(
         .filter(|name| !name.is_empty())
         .collect::<std::collections::HashSet<_>>();
 
+    // Scoped to the allowlist and the parent's remaining budget, and
+    // expiring with the run, so the background agent cannot outgrow the
+    // permissions it was spawned with.
+    let token = match CapabilityToken::mint(
+        security,
+        &agent_config.allowed_tools,
+        Duration::from_secs(SPAWN_TIMEOUT_SECS),
+        None,
+    ) {
+        Ok(token) => Arc::new(token),
+        Err(error) => {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(error),
+            });
+        }
+    };
+
     let sub_tools: Vec<Box<dyn Tool>> = parent_tools
         .iter()
         .filter(|tool| allowed.contains(tool.name()))
@@ -543,7 +557,12 @@ async fn run_agentic_background(
                 && tool.name() != "subagent_spawn"
                 && tool.name() != "subagent_manage"
         })
-        .map(|tool| Box::new(ToolArcRef::new(tool.clone())) as Box<dyn Tool>)
+        .map(|tool| {
+            Box::new(CapabilityGatedTool::new(
+                Box::new(ToolArcRef::new(tool.clone())),
+                Arc::clone(&token),
+            )) as Box<dyn Tool>
+        })
         .collect();
 
     if sub_tools.is_empty() {
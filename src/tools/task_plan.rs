@@ -5,7 +5,7 @@
 //! memory (`Arc<RwLock<Vec<TaskItem>>>`) and is discarded when the session
 //! ends — it is intentionally not persisted via the Memory trait.
 
-use crate::security::{policy::ToolOperation, SecurityPolicy};
+use crate::security::{policy::ToolOperation, PolicyHandle, SecurityPolicy};
 use crate::tools::traits::{Tool, ToolResult};
 use async_trait::async_trait;
 use serde_json::json;
@@ -52,7 +52,7 @@ struct TaskItem {
 // ── Tool ─────────────────────────────────────────────────────────────────
 
 pub struct TaskPlanTool {
-    security: Arc<SecurityPolicy>,
+    security: PolicyHandle,
     tasks: Arc<RwLock<Vec<TaskItem>>>,
     next_id: Arc<RwLock<usize>>,
 }
@@ -60,7 +60,7 @@ pub struct TaskPlanTool {
 impl TaskPlanTool {
     pub fn new(security: Arc<SecurityPolicy>) -> Self {
         Self {
-            security,
+            security: security.into(),
             tasks: Arc::new(RwLock::new(Vec::new())),
             next_id: Arc::new(RwLock::new(1)),
         }
@@ -69,11 +69,12 @@ impl TaskPlanTool {
     /// Enforce mutation permission (autonomy + rate limit).
     fn enforce_mutation(&self) -> Result<(), ToolResult> {
         self.security
+            .current()
             .enforce_tool_operation(ToolOperation::Act, "task_plan")
             .map_err(|msg| ToolResult {
                 success: false,
                 output: String::new(),
-                error: Some(msg),
+                error: Some(msg.to_string()),
             })
     }
 
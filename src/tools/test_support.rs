@@ -0,0 +1,106 @@
+//! Test doubles and helpers shared by the tests of the wrapper tools.
+
+use super::traits::{Tool, ToolResult};
+use crate::security::policy::ToolOperation;
+use crate::security::SecurityPolicy;
+use async_trait::async_trait;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+/// Tool that counts its calls and returns a canned result (`"ok"` by
+/// default). An `Act` tool unless told otherwise.
+pub(crate) struct StubTool {
+    name: &'static str,
+    operation: ToolOperation,
+    result: ToolResult,
+    calls: Arc<AtomicUsize>,
+}
+
+impl StubTool {
+    pub(crate) fn new(name: &'static str) -> Self {
+        Self {
+            name,
+            operation: ToolOperation::Act,
+            result: ToolResult {
+                success: true,
+                output: "ok".into(),
+                error: None,
+            },
+            calls: Arc::new(AtomicUsize::new(0)),
+        }
+    }
+
+    pub(crate) fn with_operation(mut self, operation: ToolOperation) -> Self {
+        self.operation = operation;
+        self
+    }
+
+    pub(crate) fn returning(mut self, result: ToolResult) -> Self {
+        self.result = result;
+        self
+    }
+
+    /// Call counter that stays readable after the stub is boxed.
+    pub(crate) fn calls(&self) -> CallCount {
+        CallCount(Arc::clone(&self.calls))
+    }
+}
+
+/// How many times a [`StubTool`] ran.
+pub(crate) struct CallCount(Arc<AtomicUsize>);
+
+impl CallCount {
+    pub(crate) fn get(&self) -> usize {
+        self.0.load(Ordering::SeqCst)
+    }
+}
+
+#[async_trait]
+impl Tool for StubTool {
+    fn name(&self) -> &str {
+        self.name
+    }
+
+    fn description(&self) -> &str {
+        "Test stub"
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        serde_json::json!({ "type": "object" })
+    }
+
+    fn operation(&self) -> ToolOperation {
+        self.operation
+    }
+
+    async fn execute(&self, _args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        self.calls.fetch_add(1, Ordering::SeqCst);
+        Ok(self.result.clone())
+    }
+}
+
+/// The id of the approval request a held call's pending result names.
+pub(crate) fn approval_request_id(pending: &ToolResult) -> String {
+    let start = pending.output.find("request ").unwrap() + "request ".len();
+    let end = start + pending.output[start..].find(')').unwrap();
+    pending.output[start..end].to_string()
+}
+
+/// Wait for the call held under `pending` to finish, and take its result.
+pub(crate) async fn approval_outcome(
+    security: &SecurityPolicy,
+    pending: &ToolResult,
+) -> ToolResult {
+    let id = approval_request_id(pending);
+    for _ in 0..100 {
+        if let Some(result) = security
+            .pending_approvals
+            .collect(&id)
+            .and_then(|call| call.result)
+        {
+            return result;
+        }
+        tokio::task::yield_now().await;
+    }
+    panic!("approval request {id} did not finish");
+}
@@ -1,3 +1,4 @@
+use crate::security::policy::ToolOperation;
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 
@@ -17,6 +18,36 @@ pub struct ToolSpec {
     pub parameters: serde_json::Value,
 }
 
+/// Who is calling a tool: the session, the tool call, and the model.
+///
+/// The agent loop scopes one around each [`Tool::execute`] call (see
+/// [`ToolExecutionContext::scope`]); tools that record provenance read it
+/// with [`ToolExecutionContext::current`]. Outside the loop (CLI commands,
+/// tests) there is none.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ToolExecutionContext {
+    pub session_id: Option<String>,
+    /// Identifies the tool call; the provider's call id when it sends one.
+    pub correlation_id: Option<String>,
+    pub model: Option<String>,
+}
+
+tokio::task_local! {
+    static TOOL_EXECUTION_CONTEXT: ToolExecutionContext;
+}
+
+impl ToolExecutionContext {
+    /// Context of the tool call running on this task, if any.
+    pub fn current() -> Option<Self> {
+        TOOL_EXECUTION_CONTEXT.try_with(Clone::clone).ok()
+    }
+
+    /// Run `future` (a tool execution) with this context.
+    pub async fn scope<F: std::future::Future>(self, future: F) -> F::Output {
+        TOOL_EXECUTION_CONTEXT.scope(self, future).await
+    }
+}
+
 /// Core tool trait — implement for any capability
 #[async_trait]
 pub trait Tool: Send + Sync {
@@ -29,6 +60,13 @@ pub trait Tool: Send + Sync {
     /// JSON schema for parameters
     fn parameters_schema(&self) -> serde_json::Value;
 
+    /// Whether calls can have side effects. Policy gates, dry-run mode and
+    /// the content scanner treat `Act` tools as side-effecting, so only
+    /// tools that never change anything should override this with `Read`.
+    fn operation(&self) -> ToolOperation {
+        ToolOperation::Act
+    }
+
     /// Execute the tool with given arguments
     async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult>;
 
@@ -42,6 +80,34 @@ pub trait Tool: Send + Sync {
     }
 }
 
+/// Implements the metadata methods of [`Tool`] (`name`, `description`,
+/// `parameters_schema`, `spec`, `operation`) by delegating to `self.inner`,
+/// for wrappers that only change how `execute` runs.
+macro_rules! delegate_tool_metadata {
+    () => {
+        fn name(&self) -> &str {
+            self.inner.name()
+        }
+
+        fn description(&self) -> &str {
+            self.inner.description()
+        }
+
+        fn parameters_schema(&self) -> serde_json::Value {
+            self.inner.parameters_schema()
+        }
+
+        fn spec(&self) -> $crate::tools::traits::ToolSpec {
+            self.inner.spec()
+        }
+
+        fn operation(&self) -> $crate::security::policy::ToolOperation {
+            self.inner.operation()
+        }
+    };
+}
+pub(crate) use delegate_tool_metadata;
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -91,6 +157,21 @@ mod tests {
         assert_eq!(spec.parameters["properties"]["value"]["type"], "string");
     }
 
+    #[tokio::test]
+    async fn execution_context_is_visible_only_inside_scope() {
+        assert_eq!(ToolExecutionContext::current(), None);
+        let context = ToolExecutionContext {
+            session_id: Some("telegram_alice".into()),
+            correlation_id: Some("call_1".into()),
+            model: Some("gpt-4o".into()),
+        };
+        let seen = context
+            .clone()
+            .scope(async { ToolExecutionContext::current() })
+            .await;
+        assert_eq!(seen, Some(context));
+    }
+
     #[tokio::test]
     async fn execute_returns_expected_output() {
         let tool = DummyTool;
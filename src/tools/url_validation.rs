@@ -1,4 +1,5 @@
 use crate::config::UrlAccessConfig;
+use crate::security::EgressGuard;
 use anyhow::{Context, Result};
 use std::net::{IpAddr, SocketAddr, ToSocketAddrs};
 
@@ -18,6 +19,8 @@ pub struct DomainPolicy<'a> {
     pub scheme_policy: UrlSchemePolicy,
     pub ipv6_error_context: &'a str,
     pub url_access: Option<&'a UrlAccessConfig>,
+    /// Host rules from the security policy (`autonomy.allowed_hosts`).
+    pub egress: Option<EgressGuard<'a>>,
 }
 
 pub fn validate_url(raw_url: &str, policy: &DomainPolicy<'_>) -> Result<String> {
@@ -50,6 +53,10 @@ pub fn validate_url(raw_url: &str, policy: &DomainPolicy<'_>) -> Result<String>
     enforce_global_domain_access_policy(&host, policy.url_access)?;
     enforce_private_host_policy(&host, policy.url_access)?;
 
+    if let Some(egress) = policy.egress {
+        egress.check_host(&host).map_err(anyhow::Error::msg)?;
+    }
+
     Ok(url.to_string())
 }
 
@@ -506,9 +513,29 @@ mod tests {
             scheme_policy: UrlSchemePolicy::HttpOrHttps,
             ipv6_error_context: "web_fetch",
             url_access: None,
+            egress: None,
         }
     }
 
+    #[test]
+    fn validate_url_applies_security_policy_egress_rules() {
+        let allowed = vec!["*".to_string()];
+        let blocked: Vec<String> = Vec::new();
+        let security = crate::security::SecurityPolicy {
+            allowed_hosts: vec!["*.example.com".to_string()],
+            ..crate::security::SecurityPolicy::default()
+        };
+        let policy = DomainPolicy {
+            egress: Some(security.egress()),
+            ..policy(&allowed, &blocked)
+        };
+        assert!(validate_url("https://docs.example.com", &policy).is_ok());
+        let err = validate_url("https://example.org", &policy)
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("autonomy.allowed_hosts"));
+    }
+
     #[test]
     fn validate_url_accepts_public_allowed_host() {
         let allowed = vec!["example.com".to_string()];
@@ -1,19 +1,22 @@
 use super::traits::{Tool, ToolResult};
 use crate::runtime::{RuntimeAdapter, WasmCapabilities, WasmRuntime};
-use crate::security::SecurityPolicy;
+use crate::security::{PolicyHandle, SecurityPolicy};
 use async_trait::async_trait;
 use serde_json::json;
 use std::sync::Arc;
 
 /// Tool for listing and executing sandboxed WASM modules.
 pub struct WasmModuleTool {
-    security: Arc<SecurityPolicy>,
+    security: PolicyHandle,
     runtime: Arc<dyn RuntimeAdapter>,
 }
 
 impl WasmModuleTool {
     pub fn new(security: Arc<SecurityPolicy>, runtime: Arc<dyn RuntimeAdapter>) -> Self {
-        Self { security, runtime }
+        Self {
+            security: security.into(),
+            runtime,
+        }
     }
 
     fn wasm_runtime(&self) -> Option<&WasmRuntime> {
@@ -123,19 +126,20 @@ impl Tool for WasmModuleTool {
     }
 
     async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let security = self.security.current();
         let action = args
             .get("action")
             .and_then(serde_json::Value::as_str)
             .ok_or_else(|| anyhow::anyhow!("Missing 'action' parameter"))?;
 
-        if self.security.is_rate_limited() {
+        if security.is_rate_limited() {
             return Ok(ToolResult {
                 success: false,
                 output: String::new(),
                 error: Some("Rate limit exceeded: too many actions in the last hour".into()),
             });
         }
-        if !self.security.record_action() {
+        if !security.record_action() {
             return Ok(ToolResult {
                 success: false,
                 output: String::new(),
@@ -154,7 +158,7 @@ impl Tool for WasmModuleTool {
         };
 
         match action {
-            "list" => match wasm_runtime.list_modules(&self.security.workspace_dir) {
+            "list" => match wasm_runtime.list_modules(&security.workspace_dir) {
                 Ok(modules) => Ok(ToolResult {
                     success: true,
                     output: serde_json::to_string_pretty(&json!({ "modules": modules }))?,
@@ -172,7 +176,7 @@ impl Tool for WasmModuleTool {
                     .and_then(serde_json::Value::as_str)
                     .ok_or_else(|| anyhow::anyhow!("Missing 'module' parameter for action=run"))?;
                 let caps = Self::parse_caps(&args)?;
-                match wasm_runtime.execute_module(module, &self.security.workspace_dir, &caps) {
+                match wasm_runtime.execute_module(module, &security.workspace_dir, &caps) {
                     Ok(result) => {
                         let output = serde_json::to_string_pretty(&json!({
                             "module": module,
@@ -34,6 +34,8 @@
 //! - Output capped at 1 MiB (enforced by [`MemoryOutputPipe`] capacity).
 
 use super::traits::{Tool, ToolResult};
+use crate::plugins::handshake::{check_handshake, PluginKind, PLUGIN_API_VERSION};
+use crate::plugins::PluginCapability;
 use anyhow::Context;
 use async_trait::async_trait;
 use serde_json::Value;
@@ -320,7 +322,8 @@ pub struct WasmManifest {
     pub description: String,
     /// JSON Schema for the tool's parameters.
     pub parameters: Value,
-    /// Manifest format version (currently `"1"`).
+    /// Plugin API version the tool targets (currently `"1"`). Checked at load
+    /// time; tools built for an unsupported version are skipped.
     #[serde(default = "default_manifest_version")]
     pub version: String,
     /// Optional homepage / source URL (shown in `zeroclaw skill list`).
@@ -339,6 +342,28 @@ impl WasmManifest {
         serde_json::from_slice(&bytes)
             .with_context(|| format!("invalid manifest JSON: {}", path.display()))
     }
+
+    /// Run the plugin API handshake for this tool.
+    ///
+    /// `version` may carry a minor part (`"1.2"`); only the major number is
+    /// the interface version.
+    pub fn check_compatibility(&self) -> anyhow::Result<u32> {
+        let major = self.version.trim().split('.').next().unwrap_or_default();
+        let api_version = major.parse::<u32>().map_err(|_| {
+            anyhow::anyhow!(
+                "WASM tool '{}' has invalid manifest version '{}' (expected an integer such as \"{}\")",
+                self.name,
+                self.version,
+                PLUGIN_API_VERSION
+            )
+        })?;
+        Ok(check_handshake(
+            &self.name,
+            PluginKind::Wasm,
+            Some(api_version),
+            &[PluginCapability::Tools],
+        )?)
+    }
 }
 
 // ─── Loader ──────────────────────────────────────────────────────────────────
@@ -470,6 +495,15 @@ fn load_single_tool(
         return;
     }
 
+    if let Err(e) = manifest.check_compatibility() {
+        tracing::warn!(
+            path = %manifest_path.display(),
+            error = %e,
+            "skipping WASM tool: incompatible plugin API"
+        );
+        return;
+    }
+
     match WasmTool::load(
         wasm,
         manifest.name.clone(),
@@ -512,6 +546,26 @@ mod tests {
         assert!(m.homepage.is_none());
     }
 
+    #[test]
+    fn manifest_compatibility_checks_major_version() {
+        let mut m: WasmManifest = serde_json::from_value(serde_json::json!({
+            "name": "zeroclaw_test_tool",
+            "description": "Test tool",
+            "parameters": { "type": "object" },
+            "version": "1.3"
+        }))
+        .unwrap();
+        assert_eq!(m.check_compatibility().unwrap(), 1);
+
+        m.version = "2".into();
+        let err = m.check_compatibility().unwrap_err().to_string();
+        assert!(err.contains("plugin API v2"));
+
+        m.version = "latest".into();
+        let err = m.check_compatibility().unwrap_err().to_string();
+        assert!(err.contains("invalid manifest version 'latest'"));
+    }
+
     #[test]
     fn load_from_empty_dir_returns_empty() {
         let tools = load_wasm_tools_from_skills(std::path::Path::new(
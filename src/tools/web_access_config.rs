@@ -3,7 +3,7 @@ use super::url_validation::{
     normalize_allowed_domains, validate_url, DomainPolicy, UrlSchemePolicy,
 };
 use crate::config::{Config, UrlAccessConfig};
-use crate::security::SecurityPolicy;
+use crate::security::{PolicyHandle, SecurityPolicy};
 use async_trait::async_trait;
 use serde_json::{json, Value};
 use std::collections::HashSet;
@@ -12,12 +12,15 @@ use std::sync::Arc;
 
 pub struct WebAccessConfigTool {
     config: Arc<Config>,
-    security: Arc<SecurityPolicy>,
+    security: PolicyHandle,
 }
 
 impl WebAccessConfigTool {
     pub fn new(config: Arc<Config>, security: Arc<SecurityPolicy>) -> Self {
-        Self { config, security }
+        Self {
+            config,
+            security: security.into(),
+        }
     }
 
     fn load_config_without_env(&self) -> anyhow::Result<Config> {
@@ -40,7 +43,8 @@ impl WebAccessConfigTool {
     }
 
     fn require_write_access(&self) -> Option<ToolResult> {
-        if !self.security.can_act() {
+        let security = self.security.current();
+        if !security.can_act() {
             return Some(ToolResult {
                 success: false,
                 output: String::new(),
@@ -48,7 +52,7 @@ impl WebAccessConfigTool {
             });
         }
 
-        if !self.security.record_action() {
+        if !security.record_action() {
             return Some(ToolResult {
                 success: false,
                 output: String::new(),
@@ -151,6 +155,7 @@ impl WebAccessConfigTool {
 
         let cfg = self.load_config_without_env()?;
         let wildcard = vec!["*".to_string()];
+        let security = self.security.current();
         let policy = DomainPolicy {
             allowed_domains: &wildcard,
             blocked_domains: &[],
@@ -160,6 +165,7 @@ impl WebAccessConfigTool {
             scheme_policy: UrlSchemePolicy::HttpOrHttps,
             ipv6_error_context: "web_access_config.check_url",
             url_access: Some(&cfg.security.url_access),
+            egress: Some(security.egress()),
         };
 
         let result = validate_url(url, &policy);
@@ -3,7 +3,7 @@ use super::url_validation::{
     normalize_allowed_domains, validate_url, DomainPolicy, UrlSchemePolicy,
 };
 use crate::config::UrlAccessConfig;
-use crate::security::SecurityPolicy;
+use crate::security::{PolicyHandle, SecurityPolicy};
 use async_trait::async_trait;
 use serde_json::json;
 use std::sync::atomic::{AtomicUsize, Ordering};
@@ -24,7 +24,7 @@ const WEB_FETCH_PROVIDER_HELP: &str =
 /// - `firecrawl`: fetch using Firecrawl cloud/self-hosted API
 /// - `tavily`: fetch using Tavily Extract API
 pub struct WebFetchTool {
-    security: Arc<SecurityPolicy>,
+    security: PolicyHandle,
     provider: String,
     api_keys: Vec<String>,
     api_url: Option<String>,
@@ -64,7 +64,7 @@ impl WebFetchTool {
             })
             .unwrap_or_default();
         Self {
-            security,
+            security: security.into(),
             provider: if provider.is_empty() {
                 "nanohtml2text".to_string()
             } else {
@@ -104,6 +104,7 @@ impl WebFetchTool {
                 scheme_policy: UrlSchemePolicy::HttpOrHttps,
                 ipv6_error_context: "web_fetch",
                 url_access: Some(&self.url_access),
+                egress: Some(self.security.current().egress()),
             },
         )
     }
@@ -274,6 +275,11 @@ impl WebFetchTool {
             .filter(|s| !s.is_empty())
             .unwrap_or("https://api.firecrawl.dev");
         let endpoint = format!("{}/v1/scrape", api_url.trim_end_matches('/'));
+        self.security
+            .current()
+            .egress()
+            .check_url(&endpoint)
+            .map_err(anyhow::Error::msg)?;
 
         let response = self
             .build_http_client()?
@@ -354,6 +360,11 @@ impl WebFetchTool {
             .filter(|s| !s.is_empty())
             .unwrap_or("https://api.tavily.com");
         let endpoint = format!("{}/extract", api_url.trim_end_matches('/'));
+        self.security
+            .current()
+            .egress()
+            .check_url(&endpoint)
+            .map_err(anyhow::Error::msg)?;
 
         let response = self
             .build_http_client()?
@@ -430,12 +441,13 @@ impl Tool for WebFetchTool {
     }
 
     async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let security = self.security.current();
         let url = args
             .get("url")
             .and_then(|v| v.as_str())
             .ok_or_else(|| anyhow::anyhow!("Missing 'url' parameter"))?;
 
-        if !self.security.can_act() {
+        if !security.can_act() {
             return Ok(ToolResult {
                 success: false,
                 output: String::new(),
@@ -443,7 +455,7 @@ impl Tool for WebFetchTool {
             });
         }
 
-        if !self.security.record_action() {
+        if !security.record_action() {
             return Ok(ToolResult {
                 success: false,
                 output: String::new(),
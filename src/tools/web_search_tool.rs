@@ -1,5 +1,5 @@
 use super::traits::{Tool, ToolResult};
-use crate::security::SecurityPolicy;
+use crate::security::{PolicyHandle, SecurityPolicy};
 use async_trait::async_trait;
 use regex::Regex;
 use reqwest::StatusCode;
@@ -12,7 +12,7 @@ use std::time::Duration;
 /// Web search tool for searching the internet.
 /// Supports providers: DuckDuckGo (free), Brave, Firecrawl, Tavily, Perplexity, Exa, and Jina.
 pub struct WebSearchTool {
-    security: Arc<SecurityPolicy>,
+    security: PolicyHandle,
     provider: String,
     fallback_providers: Vec<String>,
     api_keys: Vec<String>,
@@ -123,7 +123,7 @@ impl WebSearchTool {
         let exa_api_keys = Self::parse_api_keys(exa_api_key.as_deref());
         let jina_api_keys = Self::parse_api_keys(jina_api_key.as_deref());
         Self {
-            security,
+            security: security.into(),
             provider: provider.trim().to_lowercase(),
             fallback_providers,
             api_keys,
@@ -237,6 +237,12 @@ impl WebSearchTool {
         let encoded_query = urlencoding::encode(query);
         let search_url = format!("https://html.duckduckgo.com/html/?q={}", encoded_query);
 
+        self.security
+            .current()
+            .egress()
+            .check_url(&search_url)
+            .map_err(anyhow::Error::msg)?;
+
         let client = reqwest::Client::builder()
             .timeout(Duration::from_secs(self.timeout_secs))
             .user_agent(self.user_agent.as_str())
@@ -320,6 +326,12 @@ impl WebSearchTool {
             encoded_query, self.max_results
         );
 
+        self.security
+            .current()
+            .egress()
+            .check_url(&search_url)
+            .map_err(anyhow::Error::msg)?;
+
         let client = reqwest::Client::builder()
             .timeout(Duration::from_secs(self.timeout_secs))
             .user_agent(self.user_agent.as_str())
@@ -389,6 +401,12 @@ impl WebSearchTool {
             .filter(|s| !s.is_empty())
             .unwrap_or("https://api.firecrawl.dev");
         let endpoint = format!("{}/v1/search", api_url.trim_end_matches('/'));
+        self.security
+            .current()
+            .egress()
+            .check_url(&endpoint)
+            .map_err(anyhow::Error::msg)?;
+
         let client = reqwest::Client::builder()
             .timeout(Duration::from_secs(self.timeout_secs))
             .user_agent(self.user_agent.as_str())
@@ -489,6 +507,12 @@ impl WebSearchTool {
             .unwrap_or("https://api.tavily.com");
         let endpoint = format!("{}/search", api_url.trim_end_matches('/'));
 
+        self.security
+            .current()
+            .egress()
+            .check_url(&endpoint)
+            .map_err(anyhow::Error::msg)?;
+
         let client = reqwest::Client::builder()
             .timeout(Duration::from_secs(self.timeout_secs))
             .user_agent(self.user_agent.as_str())
@@ -571,6 +595,12 @@ impl WebSearchTool {
             .filter(|s| !s.is_empty())
             .unwrap_or("https://api.perplexity.ai");
         let endpoint = format!("{}/search", api_url.trim_end_matches('/'));
+        self.security
+            .current()
+            .egress()
+            .check_url(&endpoint)
+            .map_err(anyhow::Error::msg)?;
+
         let client = reqwest::Client::builder()
             .timeout(Duration::from_secs(self.timeout_secs))
             .user_agent(self.user_agent.as_str())
@@ -681,6 +711,12 @@ impl WebSearchTool {
             .filter(|s| !s.is_empty())
             .unwrap_or("https://api.exa.ai");
         let endpoint = format!("{}/search", api_url.trim_end_matches('/'));
+        self.security
+            .current()
+            .egress()
+            .check_url(&endpoint)
+            .map_err(anyhow::Error::msg)?;
+
         let client = reqwest::Client::builder()
             .timeout(Duration::from_secs(self.timeout_secs))
             .user_agent(self.user_agent.as_str())
@@ -771,6 +807,12 @@ impl WebSearchTool {
             url = format!("{url}?{site_query}");
         }
 
+        self.security
+            .current()
+            .egress()
+            .check_url(&url)
+            .map_err(anyhow::Error::msg)?;
+
         let client = reqwest::Client::builder()
             .timeout(Duration::from_secs(self.timeout_secs))
             .user_agent(self.user_agent.as_str())
@@ -864,7 +906,8 @@ impl Tool for WebSearchTool {
     }
 
     async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
-        if !self.security.can_act() {
+        let security = self.security.current();
+        if !security.can_act() {
             return Ok(ToolResult {
                 success: false,
                 output: String::new(),
@@ -872,7 +915,7 @@ impl Tool for WebSearchTool {
             });
         }
 
-        if !self.security.record_action() {
+        if !security.record_action() {
             return Ok(ToolResult {
                 success: false,
                 output: String::new(),
@@ -0,0 +1,152 @@
+//! The wrapper chain every registry tool runs inside.
+//!
+//! From the outside in: [`AuditedTool`], [`SizeLimitedTool`],
+//! [`ScannedTool`], [`LivePolicyGatedTool`], [`DryRunTool`] and
+//! [`ApprovalGatedTool`]. [`all_tools_with_runtime`](super::all_tools_with_runtime)
+//! builds one [`ToolWrappers`] from the config and applies it to the built-in
+//! registry; [`Agent::register_tool`](crate::agent::Agent::register_tool)
+//! applies the same one to tools added later, so those are audited, limited,
+//! scanned and approval-gated like any other.
+
+use super::traits::Tool;
+use super::{
+    dry_run, scanned, ApprovalGatedTool, AuditedTool, DryRunTool, LivePolicyGatedTool, ScannedTool,
+    SizeLimitedTool,
+};
+use crate::security::{AuditLogger, ContentScanner, LiveSecurityPolicy, SecurityPolicy};
+use std::sync::Arc;
+
+/// What a tool is wrapped in before an agent may call it.
+#[derive(Clone)]
+pub struct ToolWrappers {
+    security: Arc<SecurityPolicy>,
+    audit: Option<Arc<AuditLogger>>,
+    live_policy: Option<Arc<LiveSecurityPolicy>>,
+    scanner: Option<Arc<ContentScanner>>,
+    disabled: bool,
+}
+
+impl ToolWrappers {
+    /// Only the wrappers `security` itself asks for: approval, dry run and
+    /// size limits.
+    pub fn new(security: Arc<SecurityPolicy>) -> Self {
+        Self {
+            security,
+            audit: None,
+            live_policy: None,
+            scanner: None,
+            disabled: false,
+        }
+    }
+
+    /// Wrappers that refuse every tool, for a registry whose policy file
+    /// could not be loaded.
+    pub(crate) fn disabled(security: Arc<SecurityPolicy>) -> Self {
+        Self {
+            disabled: true,
+            ..Self::new(security)
+        }
+    }
+
+    pub fn with_audit(mut self, audit: Option<Arc<AuditLogger>>) -> Self {
+        self.audit = audit;
+        self
+    }
+
+    pub fn with_live_policy(mut self, live_policy: Option<Arc<LiveSecurityPolicy>>) -> Self {
+        self.live_policy = live_policy;
+        self
+    }
+
+    pub fn with_scanner(mut self, scanner: Option<Arc<ContentScanner>>) -> Self {
+        self.scanner = scanner;
+        self
+    }
+
+    /// Wrap `tool` in the full chain. Fails when tools are disabled.
+    pub fn wrap(&self, tool: Box<dyn Tool>) -> anyhow::Result<Box<dyn Tool>> {
+        if self.disabled {
+            anyhow::bail!(
+                "Security policy: tools are disabled (security.policy_file could not be loaded)"
+            );
+        }
+        let security = &self.security;
+        let mut tool = tool;
+        if security.approval_hook.is_some() {
+            tool = Box::new(
+                ApprovalGatedTool::new(tool, Arc::clone(security)).with_audit(self.audit.clone()),
+            );
+        }
+        if security.dry_run && dry_run::needs_dry_run_wrapper(tool.as_ref()) {
+            tool = Box::new(DryRunTool::new(tool, Arc::clone(security)));
+        }
+        if let Some(live) = &self.live_policy {
+            tool = Box::new(LivePolicyGatedTool::new(tool, Arc::clone(live)));
+        }
+        if let Some(scanner) = &self.scanner {
+            if scanned::needs_scanning(tool.as_ref()) {
+                tool = Box::new(ScannedTool::new(
+                    tool,
+                    Arc::clone(scanner),
+                    self.audit.clone(),
+                ));
+            }
+        }
+        // Outside the scanner so oversized arguments are refused before they
+        // are scanned.
+        if security.max_tool_input_bytes > 0 || security.max_tool_output_bytes > 0 {
+            tool = Box::new(SizeLimitedTool::new(tool, Arc::clone(security)));
+        }
+        // Wrapping last means every call goes through the audit log,
+        // including calls refused by the policy or the scanner.
+        if let Some(logger) = &self.audit {
+            tool = Box::new(AuditedTool::new(tool, Arc::clone(logger)));
+        }
+        Ok(tool)
+    }
+
+    /// Wrap each of `tools`; none survive when tools are disabled.
+    pub fn wrap_all(&self, tools: Vec<Box<dyn Tool>>) -> Vec<Box<dyn Tool>> {
+        tools
+            .into_iter()
+            .filter_map(|tool| self.wrap(tool).ok())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tools::test_support::StubTool;
+
+    #[tokio::test]
+    async fn disabled_wrappers_refuse_every_tool() {
+        let wrappers = ToolWrappers::disabled(Arc::new(SecurityPolicy::default()));
+        assert!(wrappers.wrap(Box::new(StubTool::new("echo"))).is_err());
+        assert!(wrappers
+            .wrap_all(vec![Box::new(StubTool::new("echo"))])
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn wrapped_tool_is_audited() {
+        let tmp = tempfile::tempdir().unwrap();
+        let logger = Arc::new(
+            AuditLogger::new(
+                crate::config::AuditConfig::default(),
+                tmp.path().to_path_buf(),
+            )
+            .unwrap(),
+        );
+        let wrappers =
+            ToolWrappers::new(Arc::new(SecurityPolicy::default())).with_audit(Some(logger));
+        let tool = wrappers.wrap(Box::new(StubTool::new("echo"))).unwrap();
+
+        assert_eq!(
+            tool.execute(serde_json::json!({})).await.unwrap().output,
+            "ok"
+        );
+        let log = std::fs::read_to_string(tmp.path().join("ariadne/audit/audit.log")).unwrap();
+        assert!(log.contains("echo"));
+    }
+}
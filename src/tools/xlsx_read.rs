@@ -1,5 +1,6 @@
 use super::traits::{Tool, ToolResult};
-use crate::security::SecurityPolicy;
+use crate::security::policy::ToolOperation;
+use crate::security::{PolicyHandle, SecurityPolicy};
 use async_trait::async_trait;
 use serde_json::json;
 use std::collections::HashMap;
@@ -17,12 +18,14 @@ const MAX_TOTAL_SHEET_XML_BYTES: u64 = 16 * 1024 * 1024;
 
 /// Extract plain text from an XLSX file in the workspace.
 pub struct XlsxReadTool {
-    security: Arc<SecurityPolicy>,
+    security: PolicyHandle,
 }
 
 impl XlsxReadTool {
     pub fn new(security: Arc<SecurityPolicy>) -> Self {
-        Self { security }
+        Self {
+            security: security.into(),
+        }
     }
 }
 
@@ -494,6 +497,10 @@ impl Tool for XlsxReadTool {
         "xlsx_read"
     }
 
+    fn operation(&self) -> ToolOperation {
+        ToolOperation::Read
+    }
+
     fn description(&self) -> &str {
         "Extract plain text and numeric data from an XLSX (Excel) file in the workspace. \
          Returns tab-separated cell values per row for each sheet. \
@@ -520,6 +527,7 @@ impl Tool for XlsxReadTool {
     }
 
     async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let security = self.security.current();
         let path = args
             .get("path")
             .and_then(|v| v.as_str())
@@ -536,7 +544,7 @@ impl Tool for XlsxReadTool {
             }
         };
 
-        if self.security.is_rate_limited() {
+        if security.is_rate_limited() {
             return Ok(ToolResult {
                 success: false,
                 output: String::new(),
@@ -544,7 +552,7 @@ impl Tool for XlsxReadTool {
             });
         }
 
-        if !self.security.is_path_allowed(path) {
+        if !security.is_path_allowed(path) {
             return Ok(ToolResult {
                 success: false,
                 output: String::new(),
@@ -552,7 +560,7 @@ impl Tool for XlsxReadTool {
             });
         }
 
-        if !self.security.record_action() {
+        if !security.record_action() {
             return Ok(ToolResult {
                 success: false,
                 output: String::new(),
@@ -560,30 +568,17 @@ impl Tool for XlsxReadTool {
             });
         }
 
-        let full_path = self.security.resolve_user_supplied_path(path);
-
-        let resolved_path = match tokio::fs::canonicalize(&full_path).await {
+        let resolved_path = match security.resolve_in_workspace(path).await {
             Ok(p) => p,
-            Err(e) => {
+            Err(error) => {
                 return Ok(ToolResult {
                     success: false,
                     output: String::new(),
-                    error: Some(format!("Failed to resolve file path: {e}")),
+                    error: Some(error),
                 });
             }
         };
 
-        if !self.security.is_resolved_path_allowed(&resolved_path) {
-            return Ok(ToolResult {
-                success: false,
-                output: String::new(),
-                error: Some(
-                    self.security
-                        .resolved_path_violation_message(&resolved_path),
-                ),
-            });
-        }
-
         tracing::debug!("Reading XLSX: {}", resolved_path.display());
 
         match tokio::fs::metadata(&resolved_path).await {
@@ -619,7 +614,8 @@ impl Tool for XlsxReadTool {
             }
         };
 
-        let text = match tokio::task::spawn_blocking(move || extract_xlsx_text(&bytes)).await {
+        let text = match crate::runtime::spawn_fs_blocking(move || extract_xlsx_text(&bytes)).await
+        {
             Ok(Ok(t)) => t,
             Ok(Err(e)) => {
                 return Ok(ToolResult {
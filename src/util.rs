@@ -2,6 +2,8 @@
 //!
 //! This module contains reusable helper functions used across the codebase.
 
+use std::sync::atomic::{AtomicBool, Ordering};
+
 /// Truncate a string to at most `max_chars` characters, appending "..." if truncated.
 ///
 /// This function safely handles multi-byte UTF-8 characters (emoji, CJK, accented characters)
@@ -111,6 +113,37 @@ pub fn is_serial_path_allowed(path: &str) -> bool {
     false
 }
 
+/// Environment variable that enables JSON CLI output, equivalent to `--json`.
+pub const JSON_OUTPUT_ENV: &str = "ZEROCLAW_JSON";
+
+static JSON_OUTPUT: AtomicBool = AtomicBool::new(false);
+
+/// Switch CLI handlers to machine-readable JSON output for this process.
+pub fn set_json_output(enabled: bool) {
+    JSON_OUTPUT.store(enabled, Ordering::Relaxed);
+}
+
+/// Whether CLI handlers should print JSON instead of human-readable text.
+pub fn json_output() -> bool {
+    JSON_OUTPUT.load(Ordering::Relaxed)
+}
+
+/// Interpret an environment flag value (`1`, `true`, `yes`, `on`) as enabled.
+pub fn env_flag_enabled(value: Option<&str>) -> bool {
+    value.is_some_and(|v| {
+        matches!(
+            v.trim().to_ascii_lowercase().as_str(),
+            "1" | "true" | "yes" | "on"
+        )
+    })
+}
+
+/// Print `value` to stdout as pretty JSON.
+pub fn print_json<T: serde::Serialize + ?Sized>(value: &T) -> anyhow::Result<()> {
+    println!("{}", serde_json::to_string_pretty(value)?);
+    Ok(())
+}
+
 /// Utility enum for handling optional values.
 pub enum MaybeSet<T> {
     Set(T),
@@ -227,4 +260,14 @@ mod tests {
         // Index 5 is inside "你" (3-byte char), floor should move back to 3.
         assert_eq!(floor_utf8_char_boundary(s, 5), 3);
     }
+
+    #[test]
+    fn test_env_flag_enabled_values() {
+        assert!(env_flag_enabled(Some("1")));
+        assert!(env_flag_enabled(Some(" TRUE ")));
+        assert!(env_flag_enabled(Some("on")));
+        assert!(!env_flag_enabled(Some("0")));
+        assert!(!env_flag_enabled(Some("")));
+        assert!(!env_flag_enabled(None));
+    }
 }
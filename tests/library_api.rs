@@ -0,0 +1,128 @@
+//! Embedding contract: an agent can be assembled and driven using only the
+//! crate-root re-exports plus provider response types, with no CLI or
+//! `Config` involved.
+
+use anyhow::Result;
+use async_trait::async_trait;
+use serde_json::json;
+use std::sync::{Arc, Mutex};
+use zeroclaw::providers::{ChatRequest, ChatResponse, ToolCall};
+use zeroclaw::{Agent, Memory, Provider, SecurityPolicy, Tool, ToolRegistry, ToolResult};
+
+struct ScriptedProvider {
+    responses: Mutex<Vec<ChatResponse>>,
+}
+
+fn response(text: &str, tool_calls: Vec<ToolCall>) -> ChatResponse {
+    ChatResponse {
+        text: Some(text.into()),
+        tool_calls,
+        usage: None,
+        reasoning_content: None,
+        quota_metadata: None,
+        stop_reason: None,
+        raw_stop_reason: None,
+    }
+}
+
+#[async_trait]
+impl Provider for ScriptedProvider {
+    async fn chat_with_system(
+        &self,
+        _system_prompt: Option<&str>,
+        _message: &str,
+        _model: &str,
+        _temperature: f64,
+    ) -> Result<String> {
+        Ok("fallback".into())
+    }
+
+    async fn chat(
+        &self,
+        _request: ChatRequest<'_>,
+        _model: &str,
+        _temperature: f64,
+    ) -> Result<ChatResponse> {
+        let mut guard = self.responses.lock().unwrap();
+        Ok(if guard.is_empty() {
+            response("done", vec![])
+        } else {
+            guard.remove(0)
+        })
+    }
+
+    fn supports_native_tools(&self) -> bool {
+        true
+    }
+}
+
+struct LookupTool {
+    calls: Arc<Mutex<Vec<String>>>,
+}
+
+#[async_trait]
+impl Tool for LookupTool {
+    fn name(&self) -> &str {
+        "lookup"
+    }
+
+    fn description(&self) -> &str {
+        "Look up an inventory item"
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({ "type": "object", "properties": { "item": { "type": "string" } } })
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> Result<ToolResult> {
+        let item = args["item"].as_str().unwrap_or_default().to_string();
+        self.calls.lock().unwrap().push(item.clone());
+        Ok(ToolResult {
+            success: true,
+            output: format!("{item}: 3 in stock"),
+            error: None,
+        })
+    }
+}
+
+#[tokio::test]
+async fn custom_tool_set_runs_through_public_api() {
+    let calls = Arc::new(Mutex::new(Vec::new()));
+    let provider = ScriptedProvider {
+        responses: Mutex::new(vec![
+            response(
+                "",
+                vec![ToolCall {
+                    id: "call-1".into(),
+                    name: "lookup".into(),
+                    arguments: r#"{"item":"bolts"}"#.into(),
+                }],
+            ),
+            response("Bolts: 3 in stock.", vec![]),
+        ]),
+    };
+
+    let security = Arc::new(SecurityPolicy::default());
+    let tools = ToolRegistry::with_defaults(security)
+        .without("shell")
+        .with(LookupTool {
+            calls: Arc::clone(&calls),
+        });
+    assert!(tools.contains("file_read"));
+
+    let memory: Arc<dyn Memory> = Arc::new(zeroclaw::memory::NoneMemory::new());
+    let mut agent = Agent::builder()
+        .provider(Box::new(provider))
+        .memory(memory)
+        .tool_registry(tools)
+        .workspace_dir(std::env::temp_dir())
+        .build()
+        .expect("builder should only need provider, tools, and memory");
+
+    assert!(agent.tool_specs().iter().any(|spec| spec.name == "lookup"));
+    assert!(agent.tool_specs().iter().all(|spec| spec.name != "shell"));
+
+    let reply = agent.turn("How many bolts?").await.unwrap();
+    assert_eq!(reply, "Bolts: 3 in stock.");
+    assert_eq!(*calls.lock().unwrap(), vec!["bolts".to_string()]);
+}